@@ -0,0 +1,75 @@
+//! benchmarks of graph operations on random graphs of increasing size
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use pgm_rust::graph::ops::graph::pathops::bidirectional_shortest_path;
+use pgm_rust::graph::ops::graph::search::{dfs, EdgeNeighborhood};
+use pgm_rust::graph::ops::setops::{intersection, union_graph};
+use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+use pgm_rust::graph::types::edge::Edge;
+use pgm_rust::graph::types::edgetype::EdgeType;
+use pgm_rust::graph::types::graph::Graph;
+use pgm_rust::graph::types::node::Node;
+use pgm_rust::rng::SplitMix64;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::hint::black_box;
+
+const SIZES: [usize; 3] = [100, 1_000, 5_000];
+
+/// random undirected graph with `n` vertices and `2n` edges
+fn random_graph(id: &str, n: usize, seed: u64) -> Graph<Node, Edge<Node>> {
+    let mut rng = SplitMix64::new(seed);
+    let nodes: HashSet<Node> = (0..n).map(|i| Node::empty(&format!("n{i}"))).collect();
+    let edges: HashSet<Edge<Node>> = (0..2 * n)
+        .map(|i| {
+            let (a, b) = (rng.gen_range(n), rng.gen_range(n));
+            Edge::empty(
+                &format!("e{i}"),
+                EdgeType::Undirected,
+                &format!("n{a}"),
+                &format!("n{b}"),
+            )
+        })
+        .collect();
+    Graph::new(id.to_string(), HashMap::new(), nodes, edges)
+}
+
+fn bench_set_operations(c: &mut Criterion) {
+    let mut group = c.benchmark_group("setops");
+    for n in SIZES {
+        let g1 = random_graph("g1", n, 1);
+        let g2 = random_graph("g2", n, 2);
+        group.bench_with_input(BenchmarkId::new("union", n), &n, |b, _| {
+            b.iter(|| union_graph(black_box(&g1), black_box(&g2)))
+        });
+        group.bench_with_input(BenchmarkId::new("intersection", n), &n, |b, _| {
+            b.iter(|| intersection(black_box(&g1), black_box(&g2)))
+        });
+    }
+    group.finish();
+}
+
+fn bench_search(c: &mut Criterion) {
+    let mut group = c.benchmark_group("search");
+    for n in SIZES {
+        let g = random_graph("g", n, 3);
+        let start = Node::empty("n0");
+        let target = Node::empty(&format!("n{}", n - 1));
+        group.bench_with_input(BenchmarkId::new("dfs", n), &n, |b, _| {
+            b.iter(|| dfs(&g, black_box(&start), &EdgeNeighborhood::Both, false))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("bidirectional_shortest_path", n),
+            &n,
+            |b, _| {
+                b.iter(|| bidirectional_shortest_path(&g, black_box(&start), black_box(&target)))
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("vertices_sorted", n), &n, |b, _| {
+            b.iter(|| black_box(&g).vertices_sorted().len())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_set_operations, bench_search);
+criterion_main!(benches);
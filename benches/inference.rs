@@ -0,0 +1,41 @@
+//! benchmarks of inference on bayesian networks of increasing size
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use pgm_rust::pgm::bayesnet::BayesianNetwork;
+use pgm_rust::pgm::cpd::TabularCPD;
+use pgm_rust::pgm::query::{Enumeration, InferenceEngine, LikelihoodWeighting, Query};
+use pgm_rust::pgm::variable::DiscreteVariable;
+use std::hint::black_box;
+
+/// binary chain x0 -> x1 -> ... with noisy copies
+fn chain(n: usize) -> BayesianNetwork {
+    let names: Vec<String> = (0..n).map(|i| format!("x{i}")).collect();
+    let refs: Vec<&str> = names.iter().map(|s| s.as_str()).collect();
+    let arcs: Vec<(&str, &str)> = refs.windows(2).map(|w| (w[0], w[1])).collect();
+    let mut bn = BayesianNetwork::from_arcs("chain", &refs, &arcs);
+    let var = |i: usize| DiscreteVariable::new(refs[i], &["f", "t"]);
+    bn.add_cpd(TabularCPD::marginal(var(0), vec![0.5, 0.5]));
+    for i in 1..n {
+        let table = vec![vec![0.9, 0.1], vec![0.2, 0.8]];
+        bn.add_cpd(TabularCPD::new(var(i), vec![var(i - 1)], table));
+    }
+    bn
+}
+
+fn bench_inference(c: &mut Criterion) {
+    let mut group = c.benchmark_group("inference");
+    for n in [4, 8, 12] {
+        let bn = chain(n);
+        let last = format!("x{}", n - 1);
+        let query = Query::marginal([last.as_str()]).given([("x0", "t")]);
+        group.bench_with_input(BenchmarkId::new("enumeration", n), &n, |b, _| {
+            b.iter(|| Enumeration::new(&bn).query(black_box(&query)))
+        });
+        group.bench_with_input(BenchmarkId::new("likelihood_weighting", n), &n, |b, _| {
+            b.iter(|| LikelihoodWeighting::new(&bn, 1_000, 0).query(black_box(&query)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_inference);
+criterion_main!(benches);
@@ -1 +1,400 @@
-// factor module declarations
+//! factors over discrete random variables
+use crate::pgm::cpd::TabularCPD;
+use crate::pgm::variable::DiscreteVariable;
+
+/// advance a joint state in table order, the last variable varying fastest,
+/// return false once every joint state has been visited
+fn next_states(states: &mut [usize], cardinalities: &[usize]) -> bool {
+    for i in (0..states.len()).rev() {
+        states[i] += 1;
+        if states[i] < cardinalities[i] {
+            return true;
+        }
+        states[i] = 0;
+    }
+    false
+}
+
+/// Factor over discrete random variables
+/// # Description
+/// A factor maps every joint state of the variables of its scope to a non
+/// negative value, see Koller and Friedman 2009, p. 104. Values are stored
+/// in lexicographic order of state indices, the last variable of the scope
+/// varying fastest, which is the order of the rows of a [TabularCPD] when
+/// its variable is put after its parents. Structured distributions such as
+/// noisy-OR go through [crate::pgm::cpd::ConditionalDistribution::to_tabular]
+/// first.
+///
+/// # References
+/// Koller D, Friedman N. Probabilistic Graphical Models. 2009, p. 104.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Factor {
+    scope: Vec<DiscreteVariable>,
+    values: Vec<f64>,
+}
+
+impl Factor {
+    /// Create a factor from its scope and its values
+    /// # Description
+    /// Panics if a variable appears twice in `scope`, if there is not one
+    /// value per joint state of `scope` or if a value is negative or not
+    /// finite. The empty scope has a single joint state.
+    ///
+    /// # Example
+    /// ```
+    /// use pgm_rust::factor::Factor;
+    /// use pgm_rust::pgm::variable::DiscreteVariable;
+    ///
+    /// let a = DiscreteVariable::new("A", &["a0", "a1"]);
+    /// let b = DiscreteVariable::new("B", &["b0", "b1"]);
+    /// let f = Factor::new(vec![a, b], vec![1.0, 2.0, 3.0, 4.0]);
+    /// f.value(&[1, 0]) == 3.0; // true
+    /// ```
+    pub fn new(scope: Vec<DiscreteVariable>, values: Vec<f64>) -> Factor {
+        for (i, v) in scope.iter().enumerate() {
+            if scope[..i].iter().any(|w| w.name() == v.name()) {
+                panic!("{} appears more than once in the scope", v.name());
+            }
+        }
+        let size: usize = scope.iter().map(|v| v.cardinality()).product();
+        if values.len() != size {
+            panic!("factor has {} values, {} expected", values.len(), size);
+        }
+        if values.iter().any(|x| !x.is_finite() || *x < 0.0) {
+            panic!("factor has a negative or non finite value");
+        }
+        Factor { scope, values }
+    }
+
+    /// variables of the factor in table order
+    pub fn scope(&self) -> &[DiscreteVariable] {
+        &self.scope
+    }
+
+    /// number of states of the variables of the scope in table order
+    pub fn cardinalities(&self) -> Vec<usize> {
+        self.scope.iter().map(|v| v.cardinality()).collect()
+    }
+
+    /// values of the factor in table order, see [Factor]
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// position of a variable in the scope
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.scope.iter().position(|v| v.name() == name)
+    }
+
+    /// index of a joint state given as state indices
+    fn index(&self, states: &[usize]) -> usize {
+        if states.len() != self.scope.len() {
+            panic!(
+                "{} states given, {} expected",
+                states.len(),
+                self.scope.len()
+            );
+        }
+        let mut index = 0;
+        for (v, s) in self.scope.iter().zip(states) {
+            if *s >= v.cardinality() {
+                panic!("{} has no state {}", v.name(), s);
+            }
+            index = index * v.cardinality() + s;
+        }
+        index
+    }
+
+    /// value of a joint state given as state indices in scope order
+    pub fn value(&self, states: &[usize]) -> f64 {
+        self.values[self.index(states)]
+    }
+
+    /// Multiply two factors
+    /// # Description
+    /// The scope of the product is the scope of `self` followed by the
+    /// variables of `other` missing from it. Variables are matched by name.
+    ///
+    /// # Example
+    /// ```
+    /// use pgm_rust::factor::Factor;
+    /// use pgm_rust::pgm::variable::DiscreteVariable;
+    ///
+    /// let a = DiscreteVariable::new("A", &["a0", "a1"]);
+    /// let b = DiscreteVariable::new("B", &["b0", "b1"]);
+    /// let f = Factor::new(vec![a.clone()], vec![0.5, 2.0]);
+    /// let g = Factor::new(vec![a, b], vec![1.0, 2.0, 3.0, 4.0]);
+    /// f.product(&g).value(&[1, 1]) == 8.0; // true
+    /// ```
+    pub fn product(&self, other: &Factor) -> Factor {
+        let mut scope = self.scope.clone();
+        for v in &other.scope {
+            if self.position(v.name()).is_none() {
+                scope.push(v.clone());
+            }
+        }
+        let other_pos: Vec<usize> = other
+            .scope
+            .iter()
+            .map(|v| scope.iter().position(|w| w.name() == v.name()).unwrap())
+            .collect();
+        let cardinalities: Vec<usize> = scope.iter().map(|v| v.cardinality()).collect();
+        let size: usize = cardinalities.iter().product();
+        let mut values = Vec::with_capacity(size);
+        let mut states = vec![0; scope.len()];
+        let nb_self = self.scope.len();
+        loop {
+            let other_states: Vec<usize> = other_pos.iter().map(|p| states[*p]).collect();
+            values.push(self.value(&states[..nb_self]) * other.value(&other_states));
+            if !next_states(&mut states, &cardinalities) {
+                break;
+            }
+        }
+        Factor { scope, values }
+    }
+
+    /// remove a variable from the scope combining the values of its states
+    fn eliminate<C>(&self, name: &str, combine: C) -> Factor
+    where
+        C: Fn(f64, f64) -> f64,
+    {
+        let pos = match self.position(name) {
+            None => panic!("{name} is not in the scope of the factor"),
+            Some(p) => p,
+        };
+        let mut scope = self.scope.clone();
+        let removed = scope.remove(pos);
+        let cardinalities: Vec<usize> = scope.iter().map(|v| v.cardinality()).collect();
+        let mut values = Vec::new();
+        let mut states = vec![0; scope.len()];
+        loop {
+            let mut full = states.clone();
+            full.insert(pos, 0);
+            let mut acc = self.value(&full);
+            for s in 1..removed.cardinality() {
+                full[pos] = s;
+                acc = combine(acc, self.value(&full));
+            }
+            values.push(acc);
+            if !next_states(&mut states, &cardinalities) {
+                break;
+            }
+        }
+        Factor { scope, values }
+    }
+
+    /// Sum a variable out of the factor
+    /// # Description
+    /// Panics if the variable is not in the scope.
+    ///
+    /// # Example
+    /// ```
+    /// use pgm_rust::factor::Factor;
+    /// use pgm_rust::pgm::variable::DiscreteVariable;
+    ///
+    /// let a = DiscreteVariable::new("A", &["a0", "a1"]);
+    /// let b = DiscreteVariable::new("B", &["b0", "b1"]);
+    /// let f = Factor::new(vec![a, b], vec![1.0, 2.0, 3.0, 4.0]);
+    /// f.sum_out("A").values() == &[4.0, 6.0]; // true
+    /// ```
+    pub fn sum_out(&self, name: &str) -> Factor {
+        self.eliminate(name, |x, y| x + y)
+    }
+
+    /// Maximize a variable out of the factor
+    /// # Description
+    /// Panics if the variable is not in the scope.
+    ///
+    /// # Example
+    /// ```
+    /// use pgm_rust::factor::Factor;
+    /// use pgm_rust::pgm::variable::DiscreteVariable;
+    ///
+    /// let a = DiscreteVariable::new("A", &["a0", "a1"]);
+    /// let b = DiscreteVariable::new("B", &["b0", "b1"]);
+    /// let f = Factor::new(vec![a, b], vec![1.0, 2.0, 3.0, 4.0]);
+    /// f.maximize_out("B").values() == &[2.0, 4.0]; // true
+    /// ```
+    pub fn maximize_out(&self, name: &str) -> Factor {
+        self.eliminate(name, f64::max)
+    }
+
+    /// States of a variable maximizing the factor
+    /// # Description
+    /// For every joint state of the other variables, in the table order of
+    /// [Factor::maximize_out], the index of the first state of `name` with
+    /// the largest value. Panics if the variable is not in the scope.
+    ///
+    /// # Example
+    /// ```
+    /// use pgm_rust::factor::Factor;
+    /// use pgm_rust::pgm::variable::DiscreteVariable;
+    ///
+    /// let a = DiscreteVariable::new("A", &["a0", "a1"]);
+    /// let b = DiscreteVariable::new("B", &["b0", "b1"]);
+    /// let f = Factor::new(vec![a, b], vec![1.0, 2.0, 4.0, 3.0]);
+    /// f.argmax("B") == vec![1, 0]; // true
+    /// ```
+    pub fn argmax(&self, name: &str) -> Vec<usize> {
+        let pos = match self.position(name) {
+            None => panic!("{name} is not in the scope of the factor"),
+            Some(p) => p,
+        };
+        let mut rest = self.cardinalities();
+        let card = rest.remove(pos);
+        let mut maximizers = Vec::new();
+        let mut states = vec![0; rest.len()];
+        loop {
+            let mut full = states.clone();
+            full.insert(pos, 0);
+            let mut best = (0, self.value(&full));
+            for s in 1..card {
+                full[pos] = s;
+                let x = self.value(&full);
+                if x > best.1 {
+                    best = (s, x);
+                }
+            }
+            maximizers.push(best.0);
+            if !next_states(&mut states, &rest) {
+                break;
+            }
+        }
+        maximizers
+    }
+
+    /// Fix the state of a variable of the scope
+    /// # Description
+    /// Returns the factor over the remaining variables whose values are
+    /// those of the joint states where `name` takes state index `state`.
+    /// Panics if the variable is not in the scope or has no such state.
+    ///
+    /// # Example
+    /// ```
+    /// use pgm_rust::factor::Factor;
+    /// use pgm_rust::pgm::variable::DiscreteVariable;
+    ///
+    /// let a = DiscreteVariable::new("A", &["a0", "a1"]);
+    /// let b = DiscreteVariable::new("B", &["b0", "b1"]);
+    /// let f = Factor::new(vec![a, b], vec![1.0, 2.0, 3.0, 4.0]);
+    /// f.reduce("B", 1).values() == &[2.0, 4.0]; // true
+    /// ```
+    pub fn reduce(&self, name: &str, state: usize) -> Factor {
+        let pos = match self.position(name) {
+            None => panic!("{name} is not in the scope of the factor"),
+            Some(p) => p,
+        };
+        if state >= self.scope[pos].cardinality() {
+            panic!("{name} has no state {state}");
+        }
+        let mut scope = self.scope.clone();
+        scope.remove(pos);
+        let cardinalities: Vec<usize> = scope.iter().map(|v| v.cardinality()).collect();
+        let mut values = Vec::new();
+        let mut states = vec![0; scope.len()];
+        loop {
+            let mut full = states.clone();
+            full.insert(pos, state);
+            values.push(self.value(&full));
+            if !next_states(&mut states, &cardinalities) {
+                break;
+            }
+        }
+        Factor { scope, values }
+    }
+
+    /// sum of the values of the factor
+    pub fn total(&self) -> f64 {
+        self.values.iter().sum()
+    }
+}
+
+// values are checked to be finite and non negative so no value is NaN
+impl Eq for Factor {}
+
+impl From<&TabularCPD> for Factor {
+    /// factor over the parents of the table followed by its variable, whose
+    /// values are the conditional probabilities
+    fn from(cpd: &TabularCPD) -> Factor {
+        let mut scope = cpd.parents().to_vec();
+        scope.push(cpd.variable().clone());
+        let values = cpd.table().iter().flatten().copied().collect();
+        Factor { scope, values }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mk_var(name: &str, card: usize) -> DiscreteVariable {
+        let states: Vec<String> = (0..card).map(|i| format!("{name}{i}")).collect();
+        let refs: Vec<&str> = states.iter().map(|s| s.as_str()).collect();
+        DiscreteVariable::new(name, &refs)
+    }
+
+    #[test]
+    fn test_from_cpd() {
+        let a = mk_var("A", 2);
+        let x = mk_var("X", 3);
+        let cpd = TabularCPD::new(x, vec![a], vec![vec![0.1, 0.2, 0.7], vec![0.5, 0.25, 0.25]]);
+        let f = Factor::from(&cpd);
+        let names: Vec<&String> = f.scope().iter().map(|v| v.name()).collect();
+        assert_eq!(names, vec!["A", "X"]);
+        assert_eq!(f.cardinalities(), vec![2, 3]);
+        assert_eq!(f.value(&[1, 2]), 0.25);
+        assert_eq!(f.value(&[0, 2]), 0.7);
+    }
+
+    #[test]
+    fn test_product() {
+        let a = mk_var("A", 2);
+        let b = mk_var("B", 3);
+        let c = mk_var("C", 2);
+        let f = Factor::new(vec![a.clone(), b.clone()], (1..=6).map(f64::from).collect());
+        let g = Factor::new(vec![b, c], (1..=6).map(f64::from).collect());
+        let h = f.product(&g);
+        assert_eq!(h.cardinalities(), vec![2, 3, 2]);
+        for i in 0..2 {
+            for j in 0..3 {
+                for k in 0..2 {
+                    assert_eq!(h.value(&[i, j, k]), f.value(&[i, j]) * g.value(&[j, k]));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_eliminate_and_reduce() {
+        let a = mk_var("A", 2);
+        let b = mk_var("B", 3);
+        let f = Factor::new(vec![a, b], vec![1.0, 5.0, 3.0, 4.0, 2.0, 6.0]);
+        assert_eq!(f.sum_out("B").values(), &[9.0, 12.0]);
+        assert_eq!(f.maximize_out("A").values(), &[4.0, 5.0, 6.0]);
+        assert_eq!(f.argmax("A"), vec![1, 0, 1]);
+        assert_eq!(f.argmax("B"), vec![1, 2]);
+        assert_eq!(f.reduce("A", 1).values(), &[4.0, 2.0, 6.0]);
+        let e = f.sum_out("A").sum_out("B");
+        assert!(e.scope().is_empty());
+        assert_eq!(e.value(&[]), 21.0);
+        assert_eq!(e.total(), f.total());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_wrong_size() {
+        Factor::new(vec![mk_var("A", 2)], vec![1.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_negative() {
+        Factor::new(vec![mk_var("A", 2)], vec![1.0, -1.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sum_out_missing() {
+        Factor::new(vec![mk_var("A", 2)], vec![1.0, 1.0]).sum_out("B");
+    }
+}
@@ -8,3 +8,6 @@ pub mod ops;
 
 /// behaviors associated to graph objects
 pub mod traits;
+
+/// reading and writing graphs
+pub mod io;
@@ -0,0 +1,4 @@
+//! graph input output
+
+/// plain text edge lists
+pub mod edgelist;
@@ -0,0 +1,168 @@
+//! read and write graphs as comma separated edge lists
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::edge::Edge;
+use crate::graph::types::edgetype::EdgeType;
+use crate::graph::types::graph::Graph;
+use crate::graph::types::node::Node;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io;
+use std::io::BufRead;
+use std::io::Write;
+
+/// data key under which edge weights are stored
+pub const WEIGHT_KEY: &str = "weight";
+
+fn invalid_line(line_nb: usize, msg: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("line {}: {}", line_nb + 1, msg),
+    )
+}
+
+/// Read a graph from an edge list
+/// # Description
+/// Every line of the input has the form `src,dst[,weight]`. Empty lines and
+/// lines starting with `#` are skipped. Edges are named `e0`, `e1`, ... in
+/// the order they appear, and the weight, if present, is stored in the edge
+/// data under [WEIGHT_KEY].
+///
+/// # Args
+/// - reader: source of the edge list
+/// - directed: whether the edges are directed from `src` to `dst`
+///
+/// # Errors
+/// Returns an [io::ErrorKind::InvalidData] error if a line does not have two
+/// or three fields, if a vertex identifier is empty or if the weight is not a
+/// number.
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::io::edgelist::from_edge_list;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+///
+/// let text = "a,b,0.5\nb,c\n";
+/// let g = from_edge_list(text.as_bytes(), true).unwrap();
+/// g.order() == 3; // true
+/// g.size() == 2; // true
+/// ```
+pub fn from_edge_list<R: BufRead>(
+    reader: R,
+    directed: bool,
+) -> io::Result<Graph<Node, Edge<Node>>> {
+    let edge_type = if directed {
+        EdgeType::Directed
+    } else {
+        EdgeType::Undirected
+    };
+    let mut edges: HashSet<Edge<Node>> = HashSet::new();
+    for (line_nb, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(',').map(|f| f.trim()).collect();
+        if fields.len() < 2 || fields.len() > 3 {
+            return Err(invalid_line(line_nb, "expected src,dst[,weight]"));
+        }
+        if fields[0].is_empty() || fields[1].is_empty() {
+            return Err(invalid_line(line_nb, "empty vertex identifier"));
+        }
+        let mut data: HashMap<String, Vec<String>> = HashMap::new();
+        if fields.len() == 3 {
+            if fields[2].parse::<f64>().is_err() {
+                return Err(invalid_line(line_nb, "weight is not a number"));
+            }
+            data.insert(WEIGHT_KEY.to_string(), vec![fields[2].to_string()]);
+        }
+        edges.insert(Edge::new(
+            format!("e{}", edges.len()),
+            data,
+            Node::empty(fields[0]),
+            Node::empty(fields[1]),
+            edge_type.clone(),
+        ));
+    }
+    Ok(Graph::from_edgeset(edges))
+}
+
+/// Write a graph as an edge list
+/// # Description
+/// Writes one `src,dst[,weight]` line per edge, in edge identifier order,
+/// so that [from_edge_list] reads it back. The weight is taken from the
+/// [WEIGHT_KEY] entry of the edge data. Isolated vertices, edge identifiers
+/// and other data are not written.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - writer: destination of the edge list
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::io::edgelist::from_edge_list;
+/// use pgm_rust::graph::io::edgelist::to_edge_list;
+///
+/// let g = from_edge_list("a,b,0.5\n".as_bytes(), false).unwrap();
+/// let mut out: Vec<u8> = Vec::new();
+/// to_edge_list(&g, &mut out).unwrap();
+/// String::from_utf8(out).unwrap() == "a,b,0.5\n"; // true
+/// ```
+pub fn to_edge_list<N, E, G, W>(g: &G, writer: &mut W) -> io::Result<()>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    W: Write,
+{
+    for e in g.edges_sorted() {
+        write!(writer, "{},{}", e.start().id(), e.end().id())?;
+        if let Some(w) = e.data().get(WEIGHT_KEY).and_then(|ws| ws.first()) {
+            write!(writer, ",{w}")?;
+        }
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::traits::graph_obj::GraphObject;
+
+    #[test]
+    fn test_from_edge_list() {
+        let text = "# comment\na,b,1.5\n\nb, c\nc,a\n";
+        let g = from_edge_list(text.as_bytes(), true).unwrap();
+        assert_eq!(g.order(), 3);
+        assert_eq!(g.size(), 3);
+        for e in g.edges() {
+            assert_eq!(e.has_type(), &EdgeType::Directed);
+            if e.start().id() == "a" {
+                assert_eq!(e.end().id(), "b");
+                assert_eq!(e.data()[WEIGHT_KEY], vec!["1.5".to_string()]);
+            } else {
+                assert!(e.data().is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_edge_list_invalid() {
+        assert!(from_edge_list("a,b,c,d\n".as_bytes(), false).is_err());
+        assert!(from_edge_list("a\n".as_bytes(), false).is_err());
+        assert!(from_edge_list("a,b,heavy\n".as_bytes(), false).is_err());
+        assert!(from_edge_list(",b\n".as_bytes(), false).is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let text = "a,b,0.25\nb,c\nc,d,2\n";
+        let g = from_edge_list(text.as_bytes(), false).unwrap();
+        let mut out: Vec<u8> = Vec::new();
+        to_edge_list(&g, &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), text);
+    }
+}
@@ -8,3 +8,6 @@ pub mod setops;
 
 /// graph obj operations
 pub mod graph_obj;
+
+/// tree operations
+pub mod tree;
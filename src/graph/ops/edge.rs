@@ -3,3 +3,4 @@
 pub mod boolops;
 pub mod miscops;
 pub mod nodeops;
+pub mod transform;
@@ -27,7 +27,7 @@ where
     E: EdgeTrait<N>,
 {
     let ids = node_ids(e);
-    let nid: &String = &n.id();
+    let nid: &String = n.id();
     ids.contains(nid)
 }
 
@@ -4,7 +4,7 @@ use crate::graph::traits::node::Node as NodeTrait;
 
 /// get the opposite node from edge
 /// # Description
-pub fn get_other<'a, 'b, N, E>(e: &'a E, n: &'b N) -> &'a N
+pub fn get_other<'a, N, E>(e: &'a E, n: &N) -> &'a N
 where
     N: NodeTrait,
     E: EdgeTrait<N>,
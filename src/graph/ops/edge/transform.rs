@@ -0,0 +1,67 @@
+//! Functions that has an [Edge] among arguments that output an [Edge]
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+
+/// Reverse an edge
+/// # Description
+/// The start and end vertices of the edge are swapped, its identifier, data
+/// and type are kept. A reversed directed edge points the other way while a
+/// reversed undirected edge joins the same vertices.
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::edge::Edge as EdgeTrait;
+/// use pgm_rust::graph::traits::graph_obj::GraphObject;
+/// use pgm_rust::graph::ops::edge::transform::reverse_edge;
+///
+/// let e: Edge<Node> = Edge::empty("e1", EdgeType::Directed, "a", "b");
+/// let r = reverse_edge(&e);
+/// r.start().id() == "b"; // true
+/// r.id() == "e1"; // true
+/// ```
+pub fn reverse_edge<N, E>(e: &E) -> E
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+{
+    E::create(
+        e.id().clone(),
+        e.data().clone(),
+        e.end().clone(),
+        e.start().clone(),
+        e.has_type().clone(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::graph::traits::graph_obj::GraphObject;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::edgetype::EdgeType;
+    use crate::graph::types::node::Node;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_reverse_edge() {
+        let mut data = HashMap::new();
+        data.insert(String::from("weight"), vec![String::from("2")]);
+        let e: Edge<Node> = Edge::directed(
+            String::from("e1"),
+            Node::empty("a"),
+            Node::empty("b"),
+            data.clone(),
+        );
+        let r = reverse_edge(&e);
+        assert_eq!(r.id(), "e1");
+        assert_eq!(r.data(), &data);
+        assert_eq!(r.has_type(), &EdgeType::Directed);
+        assert_eq!(r.start().id(), "b");
+        assert_eq!(r.end().id(), "a");
+        assert_eq!(reverse_edge(&r), e);
+    }
+}
@@ -14,3 +14,105 @@ pub mod edge;
 
 /// search related operations
 pub mod search;
+
+/// vertex cover and independent set operations
+pub mod cover;
+
+/// matching algorithms
+pub mod matching;
+
+/// structure preserving transformations
+pub mod transform;
+
+/// numeric matrix representations
+pub mod matrixops;
+
+/// path finding operations
+pub mod pathops;
+
+/// descriptive statistics of graphs
+pub mod summary;
+
+/// isolated vertex and self loop handling
+pub mod cleanup;
+
+/// semantics of graphs mixing directed and undirected edges
+pub mod mixed;
+
+/// spanning trees and forests
+pub mod spanning;
+
+/// rooted trees extracted from graphs
+pub mod treeops;
+
+/// depth, size and target bounded traversals
+pub mod bounded;
+
+/// reachability indexes
+pub mod reach;
+
+/// core and other decompositions
+pub mod decompose;
+
+/// random walks
+pub mod walks;
+
+/// random structural perturbations
+pub mod perturb;
+
+/// vertex orderings
+pub mod order;
+
+/// eulerian circuits and hamiltonian paths
+pub mod tours;
+
+/// spectral estimates of connectivity
+pub mod spectral;
+
+/// multilevel coarsening by edge contraction
+pub mod coarsen;
+
+/// balanced graph partitioning
+pub mod partition;
+
+/// data parallel operations over graphs
+pub mod parallel;
+
+/// connectivity of graphs changing over time
+pub mod dynamic;
+
+/// binary compositions of graphs
+pub mod compose;
+
+/// graph properties decided together with a witness
+pub mod certify;
+
+/// selection and grouping of edges by their data
+pub mod attrops;
+
+/// network statistics of graphs
+pub mod metrics;
+
+/// random subgraphs of graphs
+pub mod sample;
+
+/// relabeling of graphs for privacy preserving export
+pub mod anonymize;
+
+/// cycle space of graphs
+pub mod cycleops;
+
+/// flows and cuts of graphs
+pub mod flow;
+
+/// connectivity numbers of graphs
+pub mod connectivity;
+
+/// blocks and cut vertices of graphs
+pub mod blocks;
+
+/// recognition of graph classes
+pub mod recognition;
+
+/// canonical labeling of graphs
+pub mod canonical;
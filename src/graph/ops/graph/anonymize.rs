@@ -0,0 +1,319 @@
+//! relabeling of graphs for privacy preserving export
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::idgen::IdGenerator;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::idgen::{fnv1a, UuidGenerator, FNV_OFFSET};
+use crate::rng::SplitMix64;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+/// how pseudonyms are produced, see [anonymize]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PseudonymScheme {
+    /// identifiers are shuffled by a generator seeded by the value and
+    /// numbered in that order, as `v0, v1, ...` for vertices and
+    /// `e0, e1, ...` for edges
+    Sequential(u64),
+    /// the pseudonym of an identifier is its 64 bit FNV-1a hash keyed by the
+    /// value, as `v` or `e` followed by 16 hexadecimal digits. The same
+    /// identifier gets the same pseudonym in every graph anonymized with
+    /// the same key, so exports of related graphs can still be joined.
+    Hashed(u64),
+}
+
+/// which entries of the data maps are exported, see [anonymize]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DataPolicy {
+    /// every entry is kept
+    KeepAll,
+    /// every entry is dropped
+    DropAll,
+    /// only the entries with these keys are kept
+    Keep(HashSet<String>),
+    /// the entries with these keys are dropped
+    Drop(HashSet<String>),
+}
+
+impl DataPolicy {
+    fn apply(&self, data: &HashMap<String, Vec<String>>) -> HashMap<String, Vec<String>> {
+        data.iter()
+            .filter(|(k, _)| match self {
+                DataPolicy::KeepAll => true,
+                DataPolicy::DropAll => false,
+                DataPolicy::Keep(keys) => keys.contains(*k),
+                DataPolicy::Drop(keys) => !keys.contains(*k),
+            })
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect()
+    }
+}
+
+/// what [anonymize] replaces and drops
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnonymizationPolicy {
+    /// how vertex and edge pseudonyms are produced
+    pub scheme: PseudonymScheme,
+    /// entries of the vertex data that are exported
+    pub node_data: DataPolicy,
+    /// entries of the edge data that are exported
+    pub edge_data: DataPolicy,
+    /// entries of the graph data that are exported
+    pub graph_data: DataPolicy,
+}
+
+impl AnonymizationPolicy {
+    /// policy using `scheme` and dropping every data entry
+    pub fn new(scheme: PseudonymScheme) -> AnonymizationPolicy {
+        AnonymizationPolicy {
+            scheme,
+            node_data: DataPolicy::DropAll,
+            edge_data: DataPolicy::DropAll,
+            graph_data: DataPolicy::DropAll,
+        }
+    }
+}
+
+/// output of [anonymize]
+#[derive(Debug, Clone)]
+pub struct Anonymized<G> {
+    /// the relabeled graph
+    pub graph: G,
+    /// pseudonym of every vertex identifier of the input graph
+    pub node_mapping: HashMap<String, String>,
+    /// pseudonym of every edge identifier of the input graph
+    pub edge_mapping: HashMap<String, String>,
+}
+
+/// pseudonyms of `ids`, prefixed by `prefix`
+fn pseudonyms(
+    ids: Vec<&String>,
+    prefix: &str,
+    scheme: &PseudonymScheme,
+) -> HashMap<String, String> {
+    let mut mapping: HashMap<String, String> = HashMap::new();
+    match scheme {
+        PseudonymScheme::Sequential(seed) => {
+            let mut ids = ids;
+            ids.sort();
+            SplitMix64::new(*seed).shuffle(&mut ids);
+            for (i, id) in ids.into_iter().enumerate() {
+                mapping.insert(id.clone(), format!("{prefix}{i}"));
+            }
+        }
+        PseudonymScheme::Hashed(key) => {
+            let mut taken: HashSet<String> = HashSet::new();
+            for id in ids {
+                let h = fnv1a(fnv1a(FNV_OFFSET, &key.to_le_bytes()), id.as_bytes());
+                let pseudonym = format!("{prefix}{h:016x}");
+                if !taken.insert(pseudonym.clone()) {
+                    panic!("{pseudonym} is produced for more than one identifier, change the key");
+                }
+                mapping.insert(id.clone(), pseudonym);
+            }
+        }
+    }
+    mapping
+}
+
+/// Replace the identifiers of a graph by pseudonyms
+/// # Description
+/// Every vertex and edge identifier is replaced by a pseudonym produced by
+/// the scheme of `policy`, and the data maps of the vertices, the edges and
+/// the graph are filtered by its data policies. Edge types and topology are
+/// preserved. The pseudonyms are returned next to the graph, so that results
+/// computed on the exported graph can be mapped back by whoever holds them.
+/// The result only depends on `g` and `policy`, apart from its identifier
+/// which is a random UUID.
+///
+/// Pseudonyms hide identifiers, not structure: a vertex with a distinctive
+/// neighborhood may still be recognized. [PseudonymScheme::Hashed] is not a
+/// cryptographic hash, the key must be kept secret and should not be
+/// reused across unrelated exports.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - policy: pseudonym scheme and exported data
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+/// use pgm_rust::graph::ops::graph::anonymize::{anonymize, AnonymizationPolicy, PseudonymScheme};
+/// use std::collections::HashMap;
+/// use std::collections::HashSet;
+///
+/// let data = HashMap::from([("email".to_string(), vec!["alice@example.com".to_string()])]);
+/// let alice = Node::new("alice".to_string(), data);
+/// let e1 = Edge::undirected("e1".to_string(), alice, Node::empty("bob"), HashMap::new());
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1]));
+/// let out = anonymize(&g, &AnonymizationPolicy::new(PseudonymScheme::Sequential(7)));
+/// out.graph.contains_node_id(&out.node_mapping["alice"]); // true
+/// out.graph.contains_node_id("alice"); // false
+/// ```
+pub fn anonymize<N, E, G>(g: &G, policy: &AnonymizationPolicy) -> Anonymized<G>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    anonymize_with(g, policy, &mut UuidGenerator)
+}
+
+/// Replace the identifiers of a graph by pseudonyms, the identifier of the
+/// result is produced by `idgen`, see [anonymize]
+pub fn anonymize_with<N, E, G, I>(
+    g: &G,
+    policy: &AnonymizationPolicy,
+    idgen: &mut I,
+) -> Anonymized<G>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    I: IdGenerator,
+{
+    let vertices = g.vertices();
+    let edges = g.edges();
+    let node_mapping = pseudonyms(
+        vertices.iter().map(|v| v.id()).collect(),
+        "v",
+        &policy.scheme,
+    );
+    let edge_mapping = pseudonyms(edges.iter().map(|e| e.id()).collect(), "e", &policy.scheme);
+    let nodes: HashMap<&String, N> = vertices
+        .iter()
+        .map(|v| {
+            let nv = N::create(
+                node_mapping[v.id()].clone(),
+                policy.node_data.apply(v.data()),
+            );
+            (v.id(), nv)
+        })
+        .collect();
+    let new_edges: HashSet<E> = edges
+        .iter()
+        .map(|e| {
+            E::create(
+                edge_mapping[e.id()].clone(),
+                policy.edge_data.apply(e.data()),
+                nodes[e.start().id()].clone(),
+                nodes[e.end().id()].clone(),
+                e.has_type().clone(),
+            )
+        })
+        .collect();
+    let new_nodes: HashSet<N> = nodes.into_values().collect();
+    let gid = idgen.generate(&new_nodes.iter().collect(), &new_edges.iter().collect());
+    let graph = G::create(gid, policy.graph_data.apply(g.data()), new_nodes, new_edges);
+    Anonymized {
+        graph,
+        node_mapping,
+        edge_mapping,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::traits::graph_obj::GraphObject;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::edgetype::EdgeType;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::idgen::SequentialIdGenerator;
+    use crate::graph::types::node::Node;
+
+    fn data(pairs: &[(&str, &str)]) -> HashMap<String, Vec<String>> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), vec![v.to_string()]))
+            .collect()
+    }
+
+    /// alice -> bob, bob -- carol, with personal data on the vertices
+    fn mk_g() -> Graph<Node, Edge<Node>> {
+        let alice = Node::new(
+            "alice".to_string(),
+            data(&[("email", "a@x"), ("age", "31")]),
+        );
+        let bob = Node::new("bob".to_string(), data(&[("email", "b@x"), ("age", "45")]));
+        let carol = Node::empty("carol");
+        let e1 = Edge::new(
+            "alice->bob".to_string(),
+            data(&[("since", "2019")]),
+            alice,
+            bob.clone(),
+            EdgeType::Directed,
+        );
+        let e2 = Edge::undirected("bob--carol".to_string(), bob, carol, HashMap::new());
+        Graph::new(
+            "contacts".to_string(),
+            data(&[("owner", "alice")]),
+            HashSet::new(),
+            HashSet::from([e1, e2]),
+        )
+    }
+
+    #[test]
+    fn test_anonymize_sequential() {
+        let g = mk_g();
+        let policy = AnonymizationPolicy::new(PseudonymScheme::Sequential(3));
+        let out = anonymize_with(&g, &policy, &mut SequentialIdGenerator::new("g"));
+        let h = out.graph;
+        assert_eq!(h.id(), "g0");
+        assert_eq!(h.order(), 3);
+        assert_eq!(h.size(), 2);
+        let mut vids: Vec<&String> = out.node_mapping.values().collect();
+        vids.sort();
+        assert_eq!(vids, vec!["v0", "v1", "v2"]);
+        let e = h.find_edge(&out.edge_mapping["alice->bob"]).unwrap();
+        assert_eq!(e.start().id(), &out.node_mapping["alice"]);
+        assert_eq!(e.end().id(), &out.node_mapping["bob"]);
+        assert_eq!(e.has_type(), &EdgeType::Directed);
+        assert!(e.data().is_empty());
+        assert!(h.data().is_empty());
+        assert!(h.vertices().iter().all(|v| v.data().is_empty()));
+        let again = anonymize(&g, &policy);
+        assert_eq!(again.node_mapping, out.node_mapping);
+        assert_eq!(again.edge_mapping, out.edge_mapping);
+    }
+
+    #[test]
+    fn test_anonymize_hashed() {
+        let g = mk_g();
+        let policy = AnonymizationPolicy::new(PseudonymScheme::Hashed(42));
+        let out = anonymize(&g, &policy);
+        let pseudonym = &out.node_mapping["bob"];
+        assert_eq!(pseudonym.len(), 17);
+        assert!(pseudonym.starts_with('v'));
+        // the pseudonym of bob does not depend on the rest of the graph
+        let e = Edge::empty("e", EdgeType::Undirected, "bob", "dave");
+        let other: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e]));
+        assert_eq!(&anonymize(&other, &policy).node_mapping["bob"], pseudonym);
+        let rekeyed = anonymize(&g, &AnonymizationPolicy::new(PseudonymScheme::Hashed(43)));
+        assert_ne!(&rekeyed.node_mapping["bob"], pseudonym);
+    }
+
+    #[test]
+    fn test_anonymize_data_policies() {
+        let g = mk_g();
+        let policy = AnonymizationPolicy {
+            scheme: PseudonymScheme::Sequential(0),
+            node_data: DataPolicy::Drop(HashSet::from(["email".to_string()])),
+            edge_data: DataPolicy::KeepAll,
+            graph_data: DataPolicy::Keep(HashSet::from(["version".to_string()])),
+        };
+        let out = anonymize(&g, &policy);
+        let alice = out.graph.find_node(&out.node_mapping["alice"]).unwrap();
+        assert_eq!(alice.data(), &data(&[("age", "31")]));
+        let e = out
+            .graph
+            .find_edge(&out.edge_mapping["alice->bob"])
+            .unwrap();
+        assert_eq!(e.data(), &data(&[("since", "2019")]));
+        assert!(out.graph.data().is_empty());
+    }
+}
@@ -0,0 +1,192 @@
+//! selection and grouping of edges by their data
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::maps::EdgeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+/// Get the values stored under a key by the edges of a graph
+/// # Description
+/// Edges whose data has no entry for `key` are left out, so the keys of the
+/// returned map are exactly the identifiers of the edges carrying `key`.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - key: data key to look for
+/// - returns: the values of `key` by edge identifier
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::attrops::edges_with_key;
+/// use std::collections::{HashMap, HashSet};
+///
+/// let data = HashMap::from([("kind".to_string(), vec!["spatial".to_string()])]);
+/// let e1 = Edge::new("e1".to_string(), data, Node::empty("n1"), Node::empty("n2"), EdgeType::Undirected);
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "n2", "n3");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+/// let kinds = edges_with_key(&g, "kind");
+/// kinds.len() == 1; // true
+/// kinds["e1"] == &vec!["spatial".to_string()]; // true
+/// ```
+pub fn edges_with_key<'a, N, E, G>(g: &'a G, key: &str) -> EdgeMap<&'a Vec<String>>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N> + 'a,
+    G: GraphTrait<N, E>,
+{
+    g.edges()
+        .into_iter()
+        .filter_map(|e| e.data().get(key).map(|vs| (e.id(), vs)))
+        .collect()
+}
+
+/// Get the edges of a graph satisfying a predicate
+/// # Description
+/// The predicate sees the whole edge, so conditions may combine its data,
+/// its type and its end vertices.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - predicate: condition an edge must satisfy to be kept
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph_obj::GraphObject;
+/// use pgm_rust::graph::ops::graph::attrops::edges_where;
+/// use std::collections::{HashMap, HashSet};
+///
+/// let data = HashMap::from([("kind".to_string(), vec!["temporal".to_string()])]);
+/// let e1 = Edge::new("e1".to_string(), data, Node::empty("n1"), Node::empty("n2"), EdgeType::Directed);
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "n2", "n3");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1.clone(), e2]));
+/// let temporal = edges_where(&g, |e| e.data().get("kind").is_some_and(|vs| vs.contains(&"temporal".to_string())));
+/// temporal == HashSet::from([&e1]); // true
+/// ```
+pub fn edges_where<'a, N, E, G, F>(g: &'a G, predicate: F) -> HashSet<&'a E>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N> + 'a,
+    G: GraphTrait<N, E>,
+    F: Fn(&E) -> bool,
+{
+    g.edges().into_iter().filter(|e| predicate(e)).collect()
+}
+
+/// Group the edges of a graph by the values stored under a key
+/// # Description
+/// An edge joins the group of every value it stores under `key`, so an edge
+/// tagged both `spatial` and `temporal` appears in both groups. Edges
+/// without `key` belong to no group.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - key: data key whose values name the groups
+/// - returns: the edges of each value
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::attrops::group_edges_by;
+/// use std::collections::{HashMap, HashSet};
+///
+/// let spatial = HashMap::from([("kind".to_string(), vec!["spatial".to_string()])]);
+/// let e1 = Edge::new("e1".to_string(), spatial.clone(), Node::empty("n1"), Node::empty("n2"), EdgeType::Undirected);
+/// let e2 = Edge::new("e2".to_string(), spatial, Node::empty("n2"), Node::empty("n3"), EdgeType::Undirected);
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1.clone(), e2.clone()]));
+/// let groups = group_edges_by(&g, "kind");
+/// groups["spatial"] == HashSet::from([&e1, &e2]); // true
+/// ```
+pub fn group_edges_by<'a, N, E, G>(g: &'a G, key: &str) -> HashMap<String, HashSet<&'a E>>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N> + 'a,
+    G: GraphTrait<N, E>,
+{
+    let mut groups: HashMap<String, HashSet<&E>> = HashMap::new();
+    for e in g.edges() {
+        if let Some(values) = e.data().get(key) {
+            for value in values {
+                groups.entry(value.clone()).or_default().insert(e);
+            }
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::traits::graph_obj::GraphObject;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::edgetype::EdgeType;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+
+    fn mk_tagged(id: &str, n1: &str, n2: &str, tags: &[&str]) -> Edge<Node> {
+        let data = HashMap::from([(
+            "kind".to_string(),
+            tags.iter().map(|t| t.to_string()).collect(),
+        )]);
+        Edge::new(
+            id.to_string(),
+            data,
+            Node::empty(n1),
+            Node::empty(n2),
+            EdgeType::Undirected,
+        )
+    }
+
+    fn mk_g1() -> Graph<Node, Edge<Node>> {
+        let e1 = mk_tagged("e1", "a", "b", &["spatial"]);
+        let e2 = mk_tagged("e2", "b", "c", &["spatial", "temporal"]);
+        let e3 = mk_tagged("e3", "c", "d", &["temporal"]);
+        let e4 = Edge::empty("e4", EdgeType::Undirected, "d", "a");
+        Graph::from_edgeset(HashSet::from([e1, e2, e3, e4]))
+    }
+
+    #[test]
+    fn test_edges_with_key() {
+        let g = mk_g1();
+        let kinds = edges_with_key(&g, "kind");
+        let ids: Vec<&String> = kinds.sorted().into_iter().map(|(k, _)| k).collect();
+        assert_eq!(ids, vec!["e1", "e2", "e3"]);
+        assert_eq!(
+            kinds["e2"],
+            &vec!["spatial".to_string(), "temporal".to_string()]
+        );
+        assert!(edges_with_key(&g, "weight").is_empty());
+    }
+
+    #[test]
+    fn test_edges_where() {
+        let g = mk_g1();
+        let untagged = edges_where(&g, |e| !e.data().contains_key("kind"));
+        let ids: Vec<&String> = untagged.into_iter().map(|e| e.id()).collect();
+        assert_eq!(ids, vec!["e4"]);
+    }
+
+    #[test]
+    fn test_group_edges_by() {
+        let g = mk_g1();
+        let groups = group_edges_by(&g, "kind");
+        assert_eq!(groups.len(), 2);
+        let mut spatial: Vec<&String> = groups["spatial"].iter().map(|e| e.id()).collect();
+        spatial.sort();
+        assert_eq!(spatial, vec!["e1", "e2"]);
+        let mut temporal: Vec<&String> = groups["temporal"].iter().map(|e| e.id()).collect();
+        temporal.sort();
+        assert_eq!(temporal, vec!["e2", "e3"]);
+    }
+}
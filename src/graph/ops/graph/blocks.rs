@@ -0,0 +1,407 @@
+//! blocks and cut vertices of graphs
+use crate::graph::ops::graph::coarsen::MEMBERS_KEY;
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::idgen::IdGenerator;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::edgetype::EdgeType;
+use crate::graph::types::idgen::UuidGenerator;
+use crate::graph::types::tree::Tree;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+/// blocks and cut vertices of a graph over vertex and edge positions
+struct Biconnected {
+    /// vertex identifiers sorted
+    ids: Vec<String>,
+    /// positions of the vertices of every block, sorted, blocks sorted
+    block_vertices: Vec<Vec<usize>>,
+    /// identifiers of the edges of every block, sorted
+    block_edges: Vec<Vec<String>>,
+    /// whether every vertex is a cut vertex
+    is_cut: Vec<bool>,
+}
+
+/// blocks of `g` with an iterative depth first search keeping the edges of
+/// the current block on a stack. Orientation is ignored, loops are dropped.
+fn biconnected<N, E, G>(g: &G) -> Biconnected
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let ids: Vec<String> = g
+        .vertices_sorted()
+        .into_iter()
+        .map(|v| v.id().clone())
+        .collect();
+    let n = ids.len();
+    let index: HashMap<&String, usize> = ids.iter().enumerate().map(|(i, v)| (v, i)).collect();
+    let edges: Vec<(usize, usize, &String)> = g
+        .edges_sorted()
+        .into_iter()
+        .map(|e| (index[e.start().id()], index[e.end().id()], e.id()))
+        .filter(|(u, v, _)| u != v)
+        .collect();
+    let mut incident: Vec<Vec<(usize, usize)>> = vec![Vec::new(); n];
+    for (k, (u, v, _)) in edges.iter().enumerate() {
+        incident[*u].push((*v, k));
+        incident[*v].push((*u, k));
+    }
+    let mut disc: Vec<Option<usize>> = vec![None; n];
+    let mut low: Vec<usize> = vec![0; n];
+    let mut is_cut = vec![false; n];
+    let mut blocks: Vec<BTreeSet<usize>> = Vec::new();
+    let mut block_edges: Vec<Vec<String>> = Vec::new();
+    let mut time = 0;
+    for root in 0..n {
+        if disc[root].is_some() {
+            continue;
+        }
+        disc[root] = Some(time);
+        low[root] = time;
+        time += 1;
+        if incident[root].is_empty() {
+            blocks.push(BTreeSet::from([root]));
+            block_edges.push(Vec::new());
+            continue;
+        }
+        let mut root_children = 0;
+        let mut edge_stack: Vec<usize> = Vec::new();
+        // vertex, edge leading to it and position of the next incident edge
+        let mut stack: Vec<(usize, Option<usize>, usize)> = vec![(root, None, 0)];
+        while let Some((u, via, next)) = stack.pop() {
+            if let Some((w, k)) = incident[u].get(next).copied() {
+                stack.push((u, via, next + 1));
+                if via == Some(k) {
+                    continue;
+                }
+                match disc[w] {
+                    None => {
+                        disc[w] = Some(time);
+                        low[w] = time;
+                        time += 1;
+                        edge_stack.push(k);
+                        stack.push((w, Some(k), 0));
+                    }
+                    Some(dw) if dw < disc[u].unwrap() => {
+                        low[u] = low[u].min(dw);
+                        edge_stack.push(k);
+                    }
+                    Some(_) => {}
+                }
+                continue;
+            }
+            // u is finished, report to its parent
+            let Some(k) = via else { continue };
+            let p = if edges[k].0 == u {
+                edges[k].1
+            } else {
+                edges[k].0
+            };
+            low[p] = low[p].min(low[u]);
+            if low[u] >= disc[p].unwrap() {
+                if p == root {
+                    root_children += 1;
+                } else {
+                    is_cut[p] = true;
+                }
+                let mut vertices = BTreeSet::new();
+                let mut members = Vec::new();
+                while let Some(top) = edge_stack.pop() {
+                    vertices.insert(edges[top].0);
+                    vertices.insert(edges[top].1);
+                    members.push(edges[top].2.clone());
+                    if top == k {
+                        break;
+                    }
+                }
+                members.sort();
+                blocks.push(vertices);
+                block_edges.push(members);
+            }
+        }
+        if root_children > 1 {
+            is_cut[root] = true;
+        }
+    }
+    let mut order: Vec<usize> = (0..blocks.len()).collect();
+    order.sort_by(|a, b| blocks[*a].cmp(&blocks[*b]));
+    Biconnected {
+        ids,
+        block_vertices: order
+            .iter()
+            .map(|b| blocks[*b].iter().copied().collect())
+            .collect(),
+        block_edges: order.iter().map(|b| block_edges[*b].clone()).collect(),
+        is_cut,
+    }
+}
+
+/// Cut vertices of a graph
+/// # Description
+/// A cut vertex, or articulation point, is a vertex whose removal
+/// increases the number of connected components. Edge orientation is
+/// ignored and loops are dropped.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - returns: identifiers of the cut vertices, sorted
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::blocks::articulation_points;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "c", "d");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// articulation_points(&g) == vec!["b", "c"]; // true
+/// ```
+///
+/// # References
+/// Hopcroft J, Tarjan R. Algorithm 447: efficient algorithms for graph
+/// manipulation. 1973.
+pub fn articulation_points<N, E, G>(g: &G) -> Vec<String>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let b = biconnected(g);
+    b.ids
+        .into_iter()
+        .zip(b.is_cut)
+        .filter(|(_, cut)| *cut)
+        .map(|(id, _)| id)
+        .collect()
+}
+
+/// output of [block_cut_tree]
+#[derive(Debug, Clone)]
+pub struct BlockCutTree<N: NodeTrait, E: EdgeTrait<N>> {
+    /// tree joining every block to its cut vertices, rooted at the block
+    /// holding the smallest vertex identifier
+    pub tree: Tree<N, E>,
+    /// identifiers of the edges of every block vertex of the tree, sorted
+    pub block_edges: HashMap<String, Vec<String>>,
+    /// identifiers of the vertices of every block vertex of the tree,
+    /// sorted
+    pub block_vertices: HashMap<String, Vec<String>>,
+    /// tree vertex of every cut vertex of the graph
+    pub cut_vertices: HashMap<String, String>,
+}
+
+/// Block-cut tree of a connected graph
+/// # Description
+/// A block is a maximal connected subgraph without a cut vertex: a
+/// maximal 2-connected subgraph, a bridge or an isolated vertex. Two blocks
+/// share at most one vertex, which is then a cut vertex. The block-cut tree
+/// has a vertex `b:i` for every block and a vertex `c:v` for every cut
+/// vertex `v`, and joins every block to the cut vertices it contains. Blocks
+/// are numbered in the lexicographic order of their sorted vertex
+/// identifiers, so `b:0` holds the smallest one and roots the tree. A block
+/// vertex lists its vertex identifiers under [MEMBERS_KEY], a cut vertex
+/// keeps the data of the vertex of `g`. Tree edges are undirected, named
+/// `b:i--c:v` and carry no data, the tree identifier is a random UUID, see
+/// [block_cut_tree_with]. Edge orientation is ignored, parallel edges
+/// belong to the block of their end vertices and loops belong to no block.
+/// Panics if `g` is empty or not connected.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+/// use pgm_rust::graph::ops::graph::blocks::block_cut_tree;
+/// use std::collections::HashSet;
+///
+/// // a triangle a b c with a pendant edge c d
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "c", "a");
+/// let e4 = Edge::empty("e4", EdgeType::Undirected, "c", "d");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3, e4]));
+/// let bct = block_cut_tree(&g);
+/// bct.tree.order() == 3; // true: b:0 - c:c - b:1
+/// bct.block_edges["b:1"] == vec!["e4"]; // true
+/// ```
+///
+/// # References
+/// Diestel R. Graph theory. 2017. Section 3.1.
+pub fn block_cut_tree<N, E, G>(g: &G) -> BlockCutTree<N, E>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N> + Clone,
+    G: GraphTrait<N, E>,
+{
+    block_cut_tree_with(g, &mut UuidGenerator)
+}
+
+/// Block-cut tree of a connected graph, the identifier of the tree is
+/// produced by `idgen`, see [block_cut_tree]
+pub fn block_cut_tree_with<N, E, G, I>(g: &G, idgen: &mut I) -> BlockCutTree<N, E>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N> + Clone,
+    G: GraphTrait<N, E>,
+    I: IdGenerator,
+{
+    let b = biconnected(g);
+    if b.ids.is_empty() {
+        panic!("{g} has no block");
+    }
+    let vertices: HashMap<&String, &N> = g.vertices().into_iter().map(|v| (v.id(), v)).collect();
+    let mut cut_vertices: HashMap<String, String> = HashMap::new();
+    let mut cut_nodes: HashMap<usize, N> = HashMap::new();
+    for (i, id) in b.ids.iter().enumerate() {
+        if b.is_cut[i] {
+            let name = format!("c:{id}");
+            cut_nodes.insert(i, N::create(name.clone(), vertices[id].data().clone()));
+            cut_vertices.insert(id.clone(), name);
+        }
+    }
+    let mut nodes: HashSet<N> = cut_nodes.values().cloned().collect();
+    let mut edges: HashSet<E> = HashSet::new();
+    let mut block_edges: HashMap<String, Vec<String>> = HashMap::new();
+    let mut block_vertices: HashMap<String, Vec<String>> = HashMap::new();
+    for (k, members) in b.block_vertices.iter().enumerate() {
+        let name = format!("b:{k}");
+        let member_ids: Vec<String> = members.iter().map(|v| b.ids[*v].clone()).collect();
+        let block = N::create(
+            name.clone(),
+            HashMap::from([(MEMBERS_KEY.to_string(), member_ids.clone())]),
+        );
+        for v in members.iter().filter(|v| b.is_cut[**v]) {
+            let cut = &cut_nodes[v];
+            edges.insert(E::create(
+                format!("{name}--{}", cut.id()),
+                HashMap::new(),
+                block.clone(),
+                cut.clone(),
+                EdgeType::Undirected,
+            ));
+        }
+        block_edges.insert(name.clone(), b.block_edges[k].clone());
+        block_vertices.insert(name, member_ids);
+        nodes.insert(block);
+    }
+    let root = nodes.iter().find(|v| v.id() == "b:0").unwrap().clone();
+    let tid = idgen.generate(&nodes.iter().collect(), &edges.iter().collect());
+    let tree = Tree::new(tid, HashMap::new(), root, nodes, edges);
+    BlockCutTree {
+        tree,
+        block_edges,
+        block_vertices,
+        cut_vertices,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::traits::graph_obj::GraphObject;
+    use crate::graph::traits::tree::Tree as TreeTrait;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::idgen::SequentialIdGenerator;
+    use crate::graph::types::node::Node;
+
+    fn mk_uedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Undirected, n1_id, n2_id)
+    }
+
+    /// triangles a b c and c d e sharing c, a bridge e -- f, a square
+    /// f g h i, a parallel edge a -- b and a loop on d
+    fn mk_g() -> Graph<Node, Edge<Node>> {
+        Graph::from_edgeset(HashSet::from([
+            mk_uedge("a", "b", "e01"),
+            mk_uedge("b", "c", "e02"),
+            mk_uedge("c", "a", "e03"),
+            mk_uedge("c", "d", "e04"),
+            mk_uedge("d", "e", "e05"),
+            mk_uedge("e", "c", "e06"),
+            mk_uedge("e", "f", "e07"),
+            mk_uedge("f", "g", "e08"),
+            mk_uedge("g", "h", "e09"),
+            mk_uedge("h", "i", "e10"),
+            mk_uedge("i", "f", "e11"),
+            mk_uedge("b", "a", "e12"),
+            mk_uedge("d", "d", "e13"),
+        ]))
+    }
+
+    #[test]
+    fn test_articulation_points() {
+        assert_eq!(articulation_points(&mk_g()), vec!["c", "e", "f"]);
+        let square: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([
+            mk_uedge("a", "b", "e1"),
+            mk_uedge("b", "c", "e2"),
+            mk_uedge("c", "d", "e3"),
+            mk_uedge("d", "a", "e4"),
+        ]));
+        assert!(articulation_points(&square).is_empty());
+        // a star is held by its center only, whatever the search root
+        let star: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([
+            mk_uedge("z", "a", "e1"),
+            mk_uedge("z", "b", "e2"),
+            mk_uedge("z", "c", "e3"),
+        ]));
+        assert_eq!(articulation_points(&star), vec!["z"]);
+    }
+
+    #[test]
+    fn test_block_cut_tree() {
+        let bct = block_cut_tree(&mk_g());
+        assert_eq!(bct.block_vertices.len(), 4);
+        assert_eq!(bct.block_vertices["b:0"], vec!["a", "b", "c"]);
+        assert_eq!(bct.block_edges["b:0"], vec!["e01", "e02", "e03", "e12"]);
+        assert_eq!(bct.block_vertices["b:1"], vec!["c", "d", "e"]);
+        assert_eq!(bct.block_edges["b:2"], vec!["e07"]);
+        assert_eq!(bct.block_vertices["b:3"], vec!["f", "g", "h", "i"]);
+        assert_eq!(bct.cut_vertices["e"], "c:e");
+        let t = &bct.tree;
+        assert_eq!(t.order(), 7);
+        assert_eq!(t.size(), 6);
+        assert_eq!(t.root().id(), "b:0");
+        let leaves: Vec<&String> = t.leaves().into_iter().map(|v| v.id()).collect();
+        assert_eq!(leaves, vec!["b:3"]);
+        let b1 = t.find_node("b:1").unwrap();
+        let neighbors: Vec<&String> = t.children_of(b1).into_iter().map(|v| v.id()).collect();
+        assert_eq!(neighbors, vec!["c:e"]);
+        assert_eq!(t.parent_of(b1).unwrap().id(), "c:c");
+    }
+
+    #[test]
+    fn test_block_cut_tree_single_block() {
+        let single: Graph<Node, Edge<Node>> =
+            Graph::from_edge_node_set(HashSet::new(), HashSet::from([Node::empty("a")]));
+        let bct = block_cut_tree(&single);
+        assert_eq!(bct.tree.order(), 1);
+        assert!(bct.block_edges["b:0"].is_empty());
+        assert!(bct.cut_vertices.is_empty());
+        let bct = block_cut_tree_with(&single, &mut SequentialIdGenerator::new("t"));
+        assert_eq!(bct.tree.id(), "t0");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_block_cut_tree_disconnected() {
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([
+            mk_uedge("a", "b", "e1"),
+            mk_uedge("c", "d", "e2"),
+        ]));
+        block_cut_tree(&g);
+    }
+}
@@ -1,6 +1,9 @@
 //! functions that has a graph among its arguments that output a boolean value
 use crate::graph::ops::edge::boolops::is_endvertice;
 use crate::graph::ops::edge::miscops::node_ids;
+use crate::graph::ops::graph::certify::has_cycle_with_certificate;
+use crate::graph::ops::graph::certify::is_bipartite_with_certificate;
+use crate::graph::ops::graph::certify::is_connected_with_certificate;
 use crate::graph::traits::edge::Edge as EdgeTrait;
 use crate::graph::traits::graph::Graph as GraphTrait;
 use crate::graph::traits::graph_obj::GraphObject;
@@ -21,11 +24,12 @@ where
 ///
 /// # Description
 /// This applies to anything that implements [Graph] trait and [GraphObject]
-/// trait. We first iterate over edges and then over nodes.
+/// trait. Edge identifiers are looked up first, then vertex identifiers, see
+/// [Graph::contains_edge_id] and [Graph::contains_node_id].
 /// # Args
 /// - g something that implements [Graph] trait
 /// - element something that implements [GraphObject] trait
-
+///
 /// # Example
 /// ```
 /// use pgm_rust::graph::types::node::Node;
@@ -48,7 +52,6 @@ where
 /// is_in(&g, &e1); // true
 /// is_in(&g, &n5); // false
 /// ```
-
 pub fn is_in<N, E, G, T>(g: &G, element: &T) -> bool
 where
     N: NodeTrait,
@@ -57,28 +60,7 @@ where
     T: GraphObject,
 {
     let eid = element.id();
-    let mut ns = HashSet::new();
-    for e in g.edges() {
-        if e.id() == eid {
-            return true;
-        }
-        let estart = e.start();
-        if estart.id() == eid {
-            return true;
-        }
-        ns.insert(estart);
-        let eend = e.end();
-        if eend.id() == eid {
-            return true;
-        }
-        ns.insert(eend);
-    }
-    for n in g.vertices().difference(&ns) {
-        if n.id() == eid {
-            return true;
-        }
-    }
-    false
+    g.contains_edge_id(eid) || g.contains_node_id(eid)
 }
 
 /// Check if two edges are adjacent.
@@ -90,7 +72,7 @@ where
 /// - e1 an edge like object
 /// - e2 an edge like object
 /// - g a graph like object
-
+///
 /// # Example
 /// ```
 /// use pgm_rust::graph::types::node::Node;
@@ -241,6 +223,39 @@ where
     false
 }
 
+/// check if a graph is bipartite, see [is_bipartite_with_certificate] for
+/// the two sides or an odd cycle
+pub fn is_bipartite<N, E, G>(g: &G) -> bool
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    is_bipartite_with_certificate(g).is_ok()
+}
+
+/// check if a graph is connected, edge orientation being ignored, see
+/// [is_connected_with_certificate] for a spanning tree or a component
+pub fn is_connected<N, E, G>(g: &G) -> bool
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    is_connected_with_certificate(g).is_ok()
+}
+
+/// check if a graph has a cycle, see [has_cycle_with_certificate] for the
+/// cycle. Panics on a graph mixing directed and undirected edges.
+pub fn has_cycle<N, E, G>(g: &G) -> bool
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    has_cycle_with_certificate(g).is_some()
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -347,4 +362,22 @@ mod tests {
         let n3 = mk_node("n3");
         assert!(!is_neighbor_of(&g1, &n1, &n3));
     }
+
+    #[test]
+    fn test_graph_properties() {
+        // path n1 n2 n3 and an isolated n4
+        let g1 = mk_g1();
+        assert!(is_bipartite(&g1));
+        assert!(!is_connected(&g1));
+        assert!(!has_cycle(&g1));
+        let es = HashSet::from([
+            mk_uedge("n1", "n2", "e1"),
+            mk_uedge("n2", "n3", "e2"),
+            mk_uedge("n3", "n1", "e3"),
+        ]);
+        let triangle: Graph<Node, Edge<Node>> = Graph::from_edgeset(es);
+        assert!(!is_bipartite(&triangle));
+        assert!(is_connected(&triangle));
+        assert!(has_cycle(&triangle));
+    }
 }
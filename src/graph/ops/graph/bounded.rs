@@ -0,0 +1,538 @@
+//! bounded and early exit traversals
+use crate::graph::ops::edge::nodeops::get_other;
+use crate::graph::ops::graph::search::EdgeNeighborhood;
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use std::cmp::Ordering;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+
+/// predicate telling a traversal to stop at a vertex
+type StopPredicate<'a, N> = Box<dyn Fn(&N) -> bool + 'a>;
+
+/// limits restricting how much of a graph a traversal explores
+/// # Description
+/// Every limit is optional, [TraversalLimits::default] explores the whole
+/// region reachable from the start vertex.
+pub struct TraversalLimits<'a, N> {
+    /// vertices at this depth of the search tree are visited but not
+    /// expanded
+    pub max_depth: Option<usize>,
+    /// the search stops once this many vertices are visited
+    pub max_visited: Option<usize>,
+    /// the search stops at the first visited vertex satisfying the predicate
+    pub stop_at: Option<StopPredicate<'a, N>>,
+}
+
+impl<N> Default for TraversalLimits<'_, N> {
+    fn default() -> Self {
+        TraversalLimits {
+            max_depth: None,
+            max_visited: None,
+            stop_at: None,
+        }
+    }
+}
+
+/// output of a bounded traversal
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TraversalResult {
+    /// identifiers of the visited vertices in visiting order
+    pub visited: Vec<String>,
+    /// number of edges between the start and each visited vertex in the
+    /// search tree
+    pub depths: HashMap<String, usize>,
+    /// parent of every visited vertex but the start in the search tree
+    pub parents: HashMap<String, String>,
+    /// weight of the search tree path to each visited vertex, only filled by
+    /// [bounded_dijkstra]
+    pub distances: HashMap<String, f64>,
+    /// first visited vertex satisfying [TraversalLimits::stop_at]
+    pub target: Option<String>,
+    /// the search stopped because [TraversalLimits::max_visited] was reached
+    pub truncated: bool,
+}
+
+impl TraversalResult {
+    /// record the visit of `n`, returns true if the search must stop
+    fn visit<N: NodeTrait>(&mut self, n: &N, depth: usize, limits: &TraversalLimits<N>) -> bool {
+        let id = n.id();
+        self.visited.push(id.clone());
+        self.depths.insert(id.clone(), depth);
+        if limits.stop_at.as_ref().is_some_and(|f| f(n)) {
+            self.target = Some(id.clone());
+            return true;
+        }
+        if limits.max_visited.is_some_and(|m| self.visited.len() >= m) {
+            self.truncated = true;
+            return true;
+        }
+        false
+    }
+
+    /// path of the search tree from the start to `id`, `None` if `id` was
+    /// not visited
+    /// # Example
+    /// ```
+    /// use pgm_rust::graph::types::edge::Edge;
+    /// use pgm_rust::graph::types::edgetype::EdgeType;
+    /// use pgm_rust::graph::types::graph::Graph;
+    /// use pgm_rust::graph::types::node::Node;
+    /// use pgm_rust::graph::ops::graph::search::EdgeNeighborhood;
+    /// use pgm_rust::graph::ops::graph::bounded::{bounded_bfs, TraversalLimits};
+    /// use std::collections::HashSet;
+    ///
+    /// let e1 = Edge::empty("e1", EdgeType::Undirected, "n1", "n2");
+    /// let e2 = Edge::empty("e2", EdgeType::Undirected, "n2", "n3");
+    /// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+    /// let r = bounded_bfs(&g, &Node::empty("n1"), &EdgeNeighborhood::Both, &TraversalLimits::default());
+    /// r.path_to("n3") == Some(vec!["n1".to_string(), "n2".to_string(), "n3".to_string()]); // true
+    /// ```
+    pub fn path_to(&self, id: &str) -> Option<Vec<String>> {
+        if !self.depths.contains_key(id) {
+            return None;
+        }
+        let mut path = vec![id.to_string()];
+        while let Some(p) = self.parents.get(path.last().unwrap()) {
+            path.push(p.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// start vertex as stored in `g`, panics if it is missing
+fn start_vertex<'a, N, E, G>(g: &'a G, start: &N) -> &'a N
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    match g.vertices().get(start) {
+        None => panic!("{start} not in {g}"),
+        Some(v) => v,
+    }
+}
+
+/// edges of `u` in identifier order
+fn sorted_edges<'a, N, E, G>(
+    g: &'a G,
+    u: &'a N,
+    edge_generator: &EdgeNeighborhood<'a, N, E>,
+) -> Vec<&'a E>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N> + 'a,
+    G: GraphTrait<N, E>,
+{
+    let mut edges: Vec<&E> = edge_generator.edges(g, u).into_iter().collect();
+    edges.sort_by(|a, b| a.id().cmp(b.id()));
+    edges
+}
+
+/// Breadth first search with limits
+/// # Description
+/// Visits the vertices reachable from `start` level by level, neighbors
+/// being visited in edge identifier order. The search stops as soon as one
+/// of the `limits` is hit, so that a local question does not pay for a full
+/// traversal of a large graph.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - start: the root of the search
+/// - edge_generator: edges that can be followed from a vertex, see
+///   [EdgeNeighborhood]
+/// - limits: depth, visit count and target limits, see [TraversalLimits]
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph_obj::GraphObject;
+/// use pgm_rust::graph::ops::graph::search::EdgeNeighborhood;
+/// use pgm_rust::graph::ops::graph::bounded::{bounded_bfs, TraversalLimits};
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "n1", "n2");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "n2", "n3");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "n3", "n4");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// let limits = TraversalLimits {
+///     stop_at: Some(Box::new(|n: &Node| n.id() == "n3")),
+///     ..TraversalLimits::default()
+/// };
+/// let r = bounded_bfs(&g, &Node::empty("n1"), &EdgeNeighborhood::Both, &limits);
+/// r.target == Some("n3".to_string()); // true
+/// r.visited.len() == 3; // true: n4 is never visited
+/// ```
+pub fn bounded_bfs<'a, N, E, G>(
+    g: &'a G,
+    start: &N,
+    edge_generator: &EdgeNeighborhood<'a, N, E>,
+    limits: &TraversalLimits<N>,
+) -> TraversalResult
+where
+    N: NodeTrait + 'a,
+    E: EdgeTrait<N> + 'a,
+    G: GraphTrait<N, E>,
+{
+    let first = start_vertex(g, start);
+    let mut result = TraversalResult::default();
+    if result.visit(first, 0, limits) {
+        return result;
+    }
+    let mut queue: VecDeque<(&N, usize)> = VecDeque::from([(first, 0)]);
+    while let Some((u, depth)) = queue.pop_front() {
+        if limits.max_depth.is_some_and(|m| depth >= m) {
+            continue;
+        }
+        for e in sorted_edges(g, u, edge_generator) {
+            let v = get_other(e, u);
+            if result.depths.contains_key(v.id()) {
+                continue;
+            }
+            result.parents.insert(v.id().clone(), u.id().clone());
+            if result.visit(v, depth + 1, limits) {
+                return result;
+            }
+            queue.push_back((v, depth + 1));
+        }
+    }
+    result
+}
+
+/// Depth first search with limits
+/// # Description
+/// Visits the vertices reachable from `start` in depth first preorder,
+/// neighbors being visited in edge identifier order. Unlike
+/// [dfs](crate::graph::ops::graph::search::dfs) the search does not restart
+/// from unvisited vertices and stops as soon as one of the `limits` is hit.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - start: the root of the search
+/// - edge_generator: edges that can be followed from a vertex, see
+///   [EdgeNeighborhood]
+/// - limits: depth, visit count and target limits, see [TraversalLimits]
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::search::EdgeNeighborhood;
+/// use pgm_rust::graph::ops::graph::bounded::{bounded_dfs, TraversalLimits};
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Directed, "n1", "n2");
+/// let e2 = Edge::empty("e2", EdgeType::Directed, "n2", "n3");
+/// let e3 = Edge::empty("e3", EdgeType::Directed, "n3", "n4");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// let limits = TraversalLimits { max_depth: Some(2), ..TraversalLimits::default() };
+/// let r = bounded_dfs(&g, &Node::empty("n1"), &EdgeNeighborhood::Out, &limits);
+/// r.visited == vec!["n1", "n2", "n3"]; // true
+/// ```
+pub fn bounded_dfs<'a, N, E, G>(
+    g: &'a G,
+    start: &N,
+    edge_generator: &EdgeNeighborhood<'a, N, E>,
+    limits: &TraversalLimits<N>,
+) -> TraversalResult
+where
+    N: NodeTrait + 'a,
+    E: EdgeTrait<N> + 'a,
+    G: GraphTrait<N, E>,
+{
+    let first = start_vertex(g, start);
+    let mut result = TraversalResult::default();
+    // (parent, vertex, depth) entries, children are pushed in reverse so
+    // that they are popped in edge identifier order
+    let mut stack: Vec<(Option<&N>, &N, usize)> = vec![(None, first, 0)];
+    while let Some((parent, u, depth)) = stack.pop() {
+        if result.depths.contains_key(u.id()) {
+            continue;
+        }
+        if let Some(p) = parent {
+            result.parents.insert(u.id().clone(), p.id().clone());
+        }
+        if result.visit(u, depth, limits) {
+            return result;
+        }
+        if limits.max_depth.is_some_and(|m| depth >= m) {
+            continue;
+        }
+        for e in sorted_edges(g, u, edge_generator).into_iter().rev() {
+            let v = get_other(e, u);
+            if !result.depths.contains_key(v.id()) {
+                stack.push((Some(u), v, depth + 1));
+            }
+        }
+    }
+    result
+}
+
+/// entry of the dijkstra priority queue, ordered by distance then by vertex
+/// identifier
+struct QueueEntry<'a, N> {
+    distance: f64,
+    depth: usize,
+    vertex: &'a N,
+    parent: Option<&'a N>,
+}
+
+impl<N: NodeTrait> PartialEq for QueueEntry<'_, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<N: NodeTrait> Eq for QueueEntry<'_, N> {}
+
+impl<N: NodeTrait> PartialOrd for QueueEntry<'_, N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N: NodeTrait> Ord for QueueEntry<'_, N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance
+            .total_cmp(&other.distance)
+            .then_with(|| self.vertex.id().cmp(other.vertex.id()))
+    }
+}
+
+/// Dijkstra search with limits
+/// # Description
+/// Visits the vertices reachable from `start` by increasing distance, a
+/// vertex being visited when its distance is settled. Ties are broken by
+/// vertex identifier. The search stops as soon as one of the `limits` is
+/// hit. With [TraversalLimits::max_depth] paths of more edges are not
+/// followed, hence distances are the shortest among paths of at most that
+/// many edges found by the search. Panics on a negative edge weight.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - start: the root of the search
+/// - edge_generator: edges that can be followed from a vertex, see
+///   [EdgeNeighborhood]
+/// - weight: weight of an edge
+/// - limits: depth, visit count and target limits, see [TraversalLimits]
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph_obj::GraphObject;
+/// use pgm_rust::graph::ops::graph::search::EdgeNeighborhood;
+/// use pgm_rust::graph::ops::graph::bounded::{bounded_dijkstra, TraversalLimits};
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "a", "c");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// let weight = |e: &Edge<Node>| if e.id() == "e3" { 5.0 } else { 1.0 };
+/// let r = bounded_dijkstra(&g, &Node::empty("a"), &EdgeNeighborhood::Both, weight, &TraversalLimits::default());
+/// r.distances["c"] == 2.0; // true
+/// ```
+///
+/// # References
+/// Dijkstra E. A note on two problems in connexion with graphs. 1959.
+pub fn bounded_dijkstra<'a, N, E, G, F>(
+    g: &'a G,
+    start: &N,
+    edge_generator: &EdgeNeighborhood<'a, N, E>,
+    weight: F,
+    limits: &TraversalLimits<N>,
+) -> TraversalResult
+where
+    N: NodeTrait + 'a,
+    E: EdgeTrait<N> + 'a,
+    G: GraphTrait<N, E>,
+    F: Fn(&E) -> f64,
+{
+    let first = start_vertex(g, start);
+    let mut result = TraversalResult::default();
+    let mut best: HashMap<&String, f64> = HashMap::from([(first.id(), 0.0)]);
+    let mut heap = BinaryHeap::from([Reverse(QueueEntry {
+        distance: 0.0,
+        depth: 0,
+        vertex: first,
+        parent: None,
+    })]);
+    while let Some(Reverse(entry)) = heap.pop() {
+        let u = entry.vertex;
+        if result.depths.contains_key(u.id()) {
+            continue;
+        }
+        if let Some(p) = entry.parent {
+            result.parents.insert(u.id().clone(), p.id().clone());
+        }
+        result.distances.insert(u.id().clone(), entry.distance);
+        if result.visit(u, entry.depth, limits) {
+            return result;
+        }
+        if limits.max_depth.is_some_and(|m| entry.depth >= m) {
+            continue;
+        }
+        for e in sorted_edges(g, u, edge_generator) {
+            let w = weight(e);
+            if w < 0.0 {
+                panic!("edge {e} has negative weight {w}");
+            }
+            let v = get_other(e, u);
+            if result.depths.contains_key(v.id()) {
+                continue;
+            }
+            let d = entry.distance + w;
+            if best.get(v.id()).is_some_and(|b| *b <= d) {
+                continue;
+            }
+            best.insert(v.id(), d);
+            heap.push(Reverse(QueueEntry {
+                distance: d,
+                depth: entry.depth + 1,
+                vertex: v,
+                parent: Some(u),
+            }));
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::traits::graph_obj::GraphObject;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::edgetype::EdgeType;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+    use std::collections::HashSet;
+
+    fn mk_uedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Undirected, n1_id, n2_id)
+    }
+
+    /// a - b - d - e, a - c - d, with an isolated f
+    fn mk_g() -> Graph<Node, Edge<Node>> {
+        let es = HashSet::from([
+            mk_uedge("a", "b", "e1"),
+            mk_uedge("a", "c", "e2"),
+            mk_uedge("b", "d", "e3"),
+            mk_uedge("c", "d", "e4"),
+            mk_uedge("d", "e", "e5"),
+        ]);
+        Graph::from_edge_node_set(es, HashSet::from([Node::empty("f")]))
+    }
+
+    fn ids(v: Vec<&str>) -> Vec<String> {
+        v.into_iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_bounded_bfs_unbounded() {
+        let g = mk_g();
+        let r = bounded_bfs(
+            &g,
+            &Node::empty("a"),
+            &EdgeNeighborhood::Both,
+            &TraversalLimits::default(),
+        );
+        assert_eq!(r.visited, ids(vec!["a", "b", "c", "d", "e"]));
+        assert_eq!(r.depths["e"], 3);
+        assert_eq!(r.path_to("e"), Some(ids(vec!["a", "b", "d", "e"])));
+        assert_eq!(r.path_to("f"), None);
+        assert_eq!(r.target, None);
+        assert!(!r.truncated);
+    }
+
+    #[test]
+    fn test_bounded_bfs_limits() {
+        let g = mk_g();
+        let a = Node::empty("a");
+        let depth = TraversalLimits {
+            max_depth: Some(1),
+            ..TraversalLimits::default()
+        };
+        let r = bounded_bfs(&g, &a, &EdgeNeighborhood::Both, &depth);
+        assert_eq!(r.visited, ids(vec!["a", "b", "c"]));
+        let count = TraversalLimits {
+            max_visited: Some(2),
+            ..TraversalLimits::default()
+        };
+        let r = bounded_bfs(&g, &a, &EdgeNeighborhood::Both, &count);
+        assert_eq!(r.visited, ids(vec!["a", "b"]));
+        assert!(r.truncated);
+        let target = TraversalLimits {
+            stop_at: Some(Box::new(|n: &Node| n.id() == "a")),
+            ..TraversalLimits::default()
+        };
+        let r = bounded_bfs(&g, &a, &EdgeNeighborhood::Both, &target);
+        assert_eq!(r.visited, ids(vec!["a"]));
+        assert_eq!(r.target, Some("a".to_string()));
+    }
+
+    #[test]
+    fn test_bounded_dfs() {
+        let g = mk_g();
+        let a = Node::empty("a");
+        let r = bounded_dfs(&g, &a, &EdgeNeighborhood::Both, &TraversalLimits::default());
+        assert_eq!(r.visited, ids(vec!["a", "b", "d", "c", "e"]));
+        assert_eq!(r.parents["c"], "d");
+        let target = TraversalLimits {
+            stop_at: Some(Box::new(|n: &Node| n.id() == "d")),
+            ..TraversalLimits::default()
+        };
+        let r = bounded_dfs(&g, &a, &EdgeNeighborhood::Both, &target);
+        assert_eq!(r.visited, ids(vec!["a", "b", "d"]));
+        assert_eq!(r.path_to("d"), Some(ids(vec!["a", "b", "d"])));
+    }
+
+    #[test]
+    fn test_bounded_dijkstra() {
+        let g = mk_g();
+        let a = Node::empty("a");
+        let weight = |e: &Edge<Node>| if e.id() == "e1" { 4.0 } else { 1.0 };
+        let r = bounded_dijkstra(
+            &g,
+            &a,
+            &EdgeNeighborhood::Both,
+            weight,
+            &TraversalLimits::default(),
+        );
+        assert_eq!(r.visited, ids(vec!["a", "c", "d", "b", "e"]));
+        assert_eq!(r.distances["b"], 3.0);
+        assert_eq!(r.path_to("b"), Some(ids(vec!["a", "c", "d", "b"])));
+        let depth = TraversalLimits {
+            max_depth: Some(1),
+            ..TraversalLimits::default()
+        };
+        let r = bounded_dijkstra(&g, &a, &EdgeNeighborhood::Both, weight, &depth);
+        assert_eq!(r.distances["b"], 4.0);
+        assert_eq!(r.visited.len(), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bounded_dijkstra_negative_weight() {
+        let g = mk_g();
+        let limits = TraversalLimits::default();
+        bounded_dijkstra(
+            &g,
+            &Node::empty("a"),
+            &EdgeNeighborhood::Both,
+            |_| -1.0,
+            &limits,
+        );
+    }
+}
@@ -0,0 +1,409 @@
+//! canonical labeling of graphs
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::edgetype::EdgeType;
+use std::collections::HashMap;
+
+/// edges of a relabeled graph as first position, second position and
+/// whether the edge is directed, undirected edges starting at the smaller
+/// position, sorted
+type Certificate = Vec<(usize, usize, bool)>;
+
+/// color of a vertex with the sorted colors and edge counts around it
+type Signature = (usize, Vec<(usize, usize, usize, usize)>);
+
+/// individualization refinement search over vertex positions
+struct Search {
+    /// number of vertices
+    n: usize,
+    /// edges over vertex positions, with whether they are directed
+    edges: Vec<(usize, usize, bool)>,
+    /// related vertices of every vertex, with the number of undirected
+    /// edges, of directed edges towards and of directed edges from them
+    related: Vec<Vec<(usize, usize, usize, usize)>>,
+    /// smallest certificate found so far with the coloring reaching it
+    best: Option<(Certificate, Vec<usize>)>,
+    /// automorphisms found between leaves with the same certificate
+    automorphisms: Vec<Vec<usize>>,
+}
+
+impl Search {
+    /// coarsest equitable refinement of `colors`. The new colors are
+    /// numbered from zero in the order of the old color and then of the
+    /// colors around the vertex, so that they only depend on the structure.
+    fn refine(&self, mut colors: Vec<usize>) -> Vec<usize> {
+        let mut count = {
+            let mut cs = colors.clone();
+            cs.sort_unstable();
+            cs.dedup();
+            cs.len()
+        };
+        loop {
+            let signatures: Vec<Signature> = (0..self.n)
+                .map(|v| {
+                    let mut around: Vec<(usize, usize, usize, usize)> = self.related[v]
+                        .iter()
+                        .map(|(w, u, o, i)| (colors[*w], *u, *o, *i))
+                        .collect();
+                    around.sort_unstable();
+                    (colors[v], around)
+                })
+                .collect();
+            let mut keys = signatures.clone();
+            keys.sort();
+            keys.dedup();
+            colors = signatures
+                .iter()
+                .map(|s| keys.binary_search(s).unwrap())
+                .collect();
+            if keys.len() == count {
+                return colors;
+            }
+            count = keys.len();
+        }
+    }
+
+    /// edges relabeled by a discrete coloring
+    fn certificate(&self, colors: &[usize]) -> Certificate {
+        let mut cert: Certificate = self
+            .edges
+            .iter()
+            .map(|(s, t, directed)| {
+                let (p, q) = (colors[*s], colors[*t]);
+                if *directed || p <= q {
+                    (p, q, *directed)
+                } else {
+                    (q, p, *directed)
+                }
+            })
+            .collect();
+        cert.sort_unstable();
+        cert
+    }
+
+    /// orbits of the vertices under the automorphisms found so far that
+    /// fix every vertex of `fixed`, as a representative per vertex
+    fn orbits(&self, fixed: &[usize]) -> Vec<usize> {
+        fn find(parent: &mut [usize], v: usize) -> usize {
+            let mut r = v;
+            while parent[r] != r {
+                r = parent[r];
+            }
+            parent[v] = r;
+            r
+        }
+        let mut parent: Vec<usize> = (0..self.n).collect();
+        for gamma in self
+            .automorphisms
+            .iter()
+            .filter(|gamma| fixed.iter().all(|v| gamma[*v] == *v))
+        {
+            for (v, w) in gamma.iter().enumerate() {
+                let (a, b) = (find(&mut parent, v), find(&mut parent, *w));
+                parent[a.max(b)] = a.min(b);
+            }
+        }
+        (0..self.n).map(|v| find(&mut parent, v)).collect()
+    }
+
+    /// explore the search tree below the equitable coloring `colors`
+    /// reached by individualizing the vertices of `fixed` in order
+    fn search(&mut self, colors: Vec<usize>, fixed: &mut Vec<usize>) {
+        let mut sizes = vec![0; self.n];
+        for c in colors.iter() {
+            sizes[*c] += 1;
+        }
+        let Some(target) = (0..self.n).find(|c| sizes[*c] > 1) else {
+            let cert = self.certificate(&colors);
+            match &self.best {
+                Some((best, labeling)) if *best == cert => {
+                    let mut at = vec![0; self.n];
+                    for (v, p) in labeling.iter().enumerate() {
+                        at[*p] = v;
+                    }
+                    self.automorphisms
+                        .push(colors.iter().map(|p| at[*p]).collect());
+                }
+                Some((best, _)) if *best < cert => {}
+                _ => self.best = Some((cert, colors)),
+            }
+            return;
+        };
+        let cell: Vec<usize> = (0..self.n).filter(|v| colors[*v] == target).collect();
+        let mut explored: Vec<usize> = Vec::new();
+        for v in cell {
+            // children in the same orbit have the same leaves
+            let orbits = self.orbits(fixed);
+            if explored.iter().any(|u| orbits[*u] == orbits[v]) {
+                continue;
+            }
+            explored.push(v);
+            let child = colors
+                .iter()
+                .enumerate()
+                .map(|(w, c)| 2 * c + usize::from(w != v))
+                .collect();
+            let child = self.refine(child);
+            fixed.push(v);
+            self.search(child, fixed);
+            fixed.pop();
+        }
+    }
+}
+
+/// Canonical form of a graph, equal for two graphs exactly when they are
+/// isomorphic
+/// # Description
+/// Vertices get canonical positions by individualization and refinement:
+/// colors are refined until every vertex of a color sees the same number of
+/// edges of each kind towards every color, then a vertex of the first
+/// color shared by several vertices is singled out, for every choice, and
+/// so on until every vertex has its own color. Each such labeling gives the
+/// sorted list of relabeled edges and the smallest one is kept. Two
+/// labelings giving the same list differ by an automorphism, which is
+/// recorded and used to skip choices in the same orbit as an explored one,
+/// so graphs with many symmetries stay cheap to label. The form is the
+/// number of vertices followed by the edges, `p-q` for an undirected edge
+/// and `p>q` for a directed one, so edge types and parallel edges are
+/// taken into account while identifiers and data are not. It can be used
+/// as a dictionary key to share results between isomorphic graphs. The
+/// search is exponential in the worst case.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - returns: the canonical form of `g`
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::canonical::canonical_form;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let g1: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "x", "z");
+/// let e4 = Edge::empty("e4", EdgeType::Undirected, "y", "x");
+/// let g2: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e3, e4]));
+/// canonical_form(&g1) == canonical_form(&g2); // true
+/// canonical_form(&g1) == "3;0-2,1-2"; // true
+/// ```
+///
+/// # References
+/// McKay B, Piperno A. Practical graph isomorphism, II. 2014.
+pub fn canonical_form<N, E, G>(g: &G) -> String
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let ids: Vec<&String> = g.vertices_sorted().into_iter().map(|v| v.id()).collect();
+    let n = ids.len();
+    let index: HashMap<&String, usize> = ids.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+    let edges: Vec<(usize, usize, bool)> = g
+        .edges_sorted()
+        .into_iter()
+        .map(|e| {
+            (
+                index[e.start().id()],
+                index[e.end().id()],
+                *e.has_type() == EdgeType::Directed,
+            )
+        })
+        .collect();
+    let mut counts: Vec<HashMap<usize, (usize, usize, usize)>> = vec![HashMap::new(); n];
+    for (s, t, directed) in edges.iter() {
+        if *directed {
+            counts[*s].entry(*t).or_default().1 += 1;
+            counts[*t].entry(*s).or_default().2 += 1;
+        } else {
+            counts[*s].entry(*t).or_default().0 += 1;
+            if s != t {
+                counts[*t].entry(*s).or_default().0 += 1;
+            }
+        }
+    }
+    let related = counts
+        .into_iter()
+        .map(|c| c.into_iter().map(|(w, (u, o, i))| (w, u, o, i)).collect())
+        .collect();
+    let mut search = Search {
+        n,
+        edges,
+        related,
+        best: None,
+        automorphisms: Vec::new(),
+    };
+    let colors = search.refine(vec![0; n]);
+    search.search(colors, &mut Vec::new());
+    let cert = search.best.map(|(cert, _)| cert).unwrap_or_default();
+    let edges: Vec<String> = cert
+        .iter()
+        .map(|(p, q, directed)| format!("{p}{}{q}", if *directed { ">" } else { "-" }))
+        .collect();
+    format!("{n};{}", edges.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+    use crate::rng::SplitMix64;
+    use std::collections::HashSet;
+
+    fn mk_edge(n1_id: &str, n2_id: &str, e_id: &str, directed: bool) -> Edge<Node> {
+        let t = if directed {
+            EdgeType::Directed
+        } else {
+            EdgeType::Undirected
+        };
+        Edge::empty(e_id, t, n1_id, n2_id)
+    }
+
+    fn mk_g(edges: &[(&str, &str)]) -> Graph<Node, Edge<Node>> {
+        Graph::from_edgeset(
+            edges
+                .iter()
+                .enumerate()
+                .map(|(i, (a, b))| mk_edge(a, b, &format!("e{i}"), false))
+                .collect(),
+        )
+    }
+
+    /// random graph over `n` vertices with some directed, parallel and
+    /// loop edges, and the same graph with shuffled identifiers
+    fn mk_random_pair(
+        rng: &mut SplitMix64,
+        n: usize,
+    ) -> (Graph<Node, Edge<Node>>, Graph<Node, Edge<Node>>) {
+        let mut names: Vec<usize> = (0..n).collect();
+        rng.shuffle(&mut names);
+        let mut edges = Vec::new();
+        let mut renamed = Vec::new();
+        for k in 0..2 * n {
+            let (s, t) = (rng.gen_range(n), rng.gen_range(n));
+            let directed = rng.gen_range(4) == 0;
+            edges.push(mk_edge(
+                &format!("v{s}"),
+                &format!("v{t}"),
+                &format!("e{k}"),
+                directed,
+            ));
+            // undirected edges are given the other way around
+            let (a, b) = if directed { (s, t) } else { (t, s) };
+            renamed.push(mk_edge(
+                &format!("v{}", names[a]),
+                &format!("v{}", names[b]),
+                &format!("f{k}"),
+                directed,
+            ));
+        }
+        let nodes: HashSet<Node> = (0..n).map(|i| Node::empty(&format!("v{i}"))).collect();
+        (
+            Graph::from_edge_node_set(edges.into_iter().collect(), nodes.clone()),
+            Graph::from_edge_node_set(renamed.into_iter().collect(), nodes),
+        )
+    }
+
+    #[test]
+    fn test_canonical_form_relabeled() {
+        let mut rng = SplitMix64::new(3);
+        for _ in 0..100 {
+            let n = 1 + rng.gen_range(9);
+            let (g, h) = mk_random_pair(&mut rng, n);
+            assert_eq!(canonical_form(&g), canonical_form(&h));
+        }
+    }
+
+    #[test]
+    fn test_canonical_form_regular() {
+        // both are 2 regular, refinement alone does not separate them
+        let hexagon = mk_g(&[
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "d"),
+            ("d", "e"),
+            ("e", "f"),
+            ("f", "a"),
+        ]);
+        let triangles = mk_g(&[
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "a"),
+            ("d", "e"),
+            ("e", "f"),
+            ("f", "d"),
+        ]);
+        let shuffled = mk_g(&[
+            ("x", "z"),
+            ("z", "u"),
+            ("u", "w"),
+            ("w", "y"),
+            ("y", "v"),
+            ("v", "x"),
+        ]);
+        assert_ne!(canonical_form(&hexagon), canonical_form(&triangles));
+        assert_eq!(canonical_form(&hexagon), canonical_form(&shuffled));
+    }
+
+    #[test]
+    fn test_canonical_form_edge_kinds() {
+        let undirected = mk_g(&[("a", "b")]);
+        let directed: Graph<Node, Edge<Node>> =
+            Graph::from_edgeset(HashSet::from([mk_edge("a", "b", "e0", true)]));
+        let reversed: Graph<Node, Edge<Node>> =
+            Graph::from_edgeset(HashSet::from([mk_edge("b", "a", "e0", true)]));
+        let parallel = mk_g(&[("a", "b"), ("b", "a")]);
+        assert_eq!(canonical_form(&undirected), "2;0-1");
+        assert_eq!(canonical_form(&directed), canonical_form(&reversed));
+        assert_ne!(canonical_form(&directed), canonical_form(&undirected));
+        assert_eq!(canonical_form(&parallel), "2;0-1,0-1");
+        let loop_g = mk_g(&[("a", "a"), ("a", "b")]);
+        let isolated: Graph<Node, Edge<Node>> = Graph::from_edge_node_set(
+            HashSet::from([mk_edge("a", "b", "e0", false)]),
+            HashSet::from([Node::empty("c")]),
+        );
+        assert_ne!(canonical_form(&loop_g), canonical_form(&undirected));
+        assert_eq!(canonical_form(&isolated), "3;1-2");
+        let empty: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::new());
+        assert_eq!(canonical_form(&empty), "0;");
+    }
+
+    #[test]
+    fn test_canonical_form_symmetric() {
+        // complete graph and hypercube, where orbits prune the search
+        let ids: Vec<String> = (0..10).map(|i| format!("v{i}")).collect();
+        let mut pairs = Vec::new();
+        for i in 0..10 {
+            for j in i + 1..10 {
+                pairs.push((ids[i].as_str(), ids[j].as_str()));
+            }
+        }
+        assert!(canonical_form(&mk_g(&pairs)).starts_with("10;0-1,0-2"));
+        let cube_ids: Vec<String> = (0..16).map(|i| format!("q{i}")).collect();
+        let cube_edges: Vec<(usize, usize)> = (0..16)
+            .flat_map(|i| (0..4).map(move |b| (i, i ^ (1 << b))))
+            .filter(|(i, j)| i < j)
+            .collect();
+        let cube = mk_g(
+            &cube_edges
+                .iter()
+                .map(|(i, j)| (cube_ids[*i].as_str(), cube_ids[*j].as_str()))
+                .collect::<Vec<_>>(),
+        );
+        let moved = mk_g(
+            &cube_edges
+                .iter()
+                .map(|(i, j)| (cube_ids[*i ^ 5].as_str(), cube_ids[*j ^ 5].as_str()))
+                .collect::<Vec<_>>(),
+        );
+        assert_eq!(canonical_form(&cube).matches('-').count(), 32);
+        assert_eq!(canonical_form(&cube), canonical_form(&moved));
+    }
+}
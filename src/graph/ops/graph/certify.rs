@@ -0,0 +1,494 @@
+//! graph properties decided together with a witness
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::edgetype::EdgeType;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+
+/// two sides of a bipartite graph, see [is_bipartite_with_certificate]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bipartition {
+    /// vertex identifiers of the side holding the smallest identifier of
+    /// every component, sorted
+    pub left: Vec<String>,
+    /// vertex identifiers of the other side, sorted
+    pub right: Vec<String>,
+}
+
+/// cycle of odd length proving that a graph is not bipartite
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OddCycle {
+    /// vertex identifiers along the cycle, the last one is adjacent to the
+    /// first one. A single vertex stands for a loop.
+    pub vertices: Vec<String>,
+}
+
+/// component proving that a graph is not connected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeparatedComponent {
+    /// vertex identifiers of the component of the smallest identifier,
+    /// sorted. No edge leaves it and some vertex lies outside of it.
+    pub vertices: Vec<String>,
+}
+
+/// cycle found by [has_cycle_with_certificate], also used for the
+/// [fundamental_cycles](crate::graph::ops::graph::cycleops::fundamental_cycles)
+/// of a graph
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle {
+    /// vertex identifiers along the cycle
+    pub vertices: Vec<String>,
+    /// edge identifiers along the cycle, the edge at position `i` leaves
+    /// the vertex at position `i` and the last one leads back to the first
+    /// vertex
+    pub edges: Vec<String>,
+}
+
+/// adjacency of a graph over vertex and edge positions
+struct Arcs<'a> {
+    /// vertex identifiers sorted
+    ids: Vec<&'a String>,
+    /// neighbors of every vertex with the position of the edge joining them
+    out: Vec<Vec<(usize, usize)>>,
+    /// edge identifiers in the order of [GraphTrait::edges_sorted]
+    edge_ids: Vec<&'a String>,
+}
+
+/// adjacency of `g`, directed edges are followed along their orientation
+/// only if `directed` holds
+fn arcs<'a, N, E, G>(g: &'a G, directed: bool) -> Arcs<'a>
+where
+    N: NodeTrait + 'a,
+    E: EdgeTrait<N> + 'a,
+    G: GraphTrait<N, E>,
+{
+    let ids: Vec<&String> = g.vertices_sorted().into_iter().map(|v| v.id()).collect();
+    let index: HashMap<&String, usize> = ids.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+    let mut out: Vec<Vec<(usize, usize)>> = vec![Vec::new(); ids.len()];
+    let edges = g.edges_sorted();
+    for (k, e) in edges.iter().enumerate() {
+        let (s, t) = (index[e.start().id()], index[e.end().id()]);
+        out[s].push((t, k));
+        let one_way = directed && e.has_type() == &EdgeType::Directed;
+        if s != t && !one_way {
+            out[t].push((s, k));
+        }
+    }
+    let edge_ids = edges.into_iter().map(|e| e.id()).collect();
+    Arcs { ids, out, edge_ids }
+}
+
+/// Decide whether a graph is bipartite, with a witness either way
+/// # Description
+/// A graph is bipartite if its vertices split in two sides such that every
+/// edge joins both sides, which by König's theorem holds exactly when it
+/// has no cycle of odd length. Vertices are two colored along a breadth
+/// first search, edge orientation being ignored. When an edge joins two
+/// vertices of the same color, the tree paths from both of them to their
+/// closest common ancestor close an odd cycle. Searches start from the
+/// smallest identifier of every component and neighbors are visited in
+/// edge identifier order, so the witness is reproducible.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - returns: both sides of the graph, or an odd cycle
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::certify::is_bipartite_with_certificate;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Directed, "b", "c");
+/// let path: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1.clone(), e2.clone()]));
+/// let sides = is_bipartite_with_certificate(&path).unwrap();
+/// sides.left == vec!["a", "c"]; // true
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "c", "a");
+/// let triangle: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// is_bipartite_with_certificate(&triangle).unwrap_err().vertices.len() == 3; // true
+/// ```
+///
+/// # References
+/// König D. Theorie der endlichen und unendlichen Graphen. 1936.
+pub fn is_bipartite_with_certificate<N, E, G>(g: &G) -> Result<Bipartition, OddCycle>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let Arcs { ids, out, .. } = arcs(g, false);
+    let n = ids.len();
+    let mut depth: Vec<Option<usize>> = vec![None; n];
+    let mut parent: Vec<usize> = (0..n).collect();
+    for root in 0..n {
+        if depth[root].is_some() {
+            continue;
+        }
+        depth[root] = Some(0);
+        let mut queue = VecDeque::from([root]);
+        while let Some(u) = queue.pop_front() {
+            let du = depth[u].unwrap();
+            for (v, _) in &out[u] {
+                match depth[*v] {
+                    None => {
+                        depth[*v] = Some(du + 1);
+                        parent[*v] = u;
+                        queue.push_back(*v);
+                    }
+                    Some(dv) if dv % 2 == du % 2 => {
+                        // adjacent vertices of a breadth first tree with
+                        // depths of equal parity lie at the same depth, so
+                        // their paths to the common ancestor have equal
+                        // lengths
+                        let (mut a, mut b) = (u, *v);
+                        let mut up: Vec<usize> = vec![a];
+                        let mut down: Vec<usize> = Vec::new();
+                        while a != b {
+                            if depth[a] >= depth[b] {
+                                a = parent[a];
+                                up.push(a);
+                            } else {
+                                down.push(b);
+                                b = parent[b];
+                            }
+                        }
+                        // `up` ends with the common ancestor
+                        up.extend(down.into_iter().rev());
+                        let vertices = up.into_iter().map(|i| ids[i].clone()).collect();
+                        return Err(OddCycle { vertices });
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+    }
+    let (mut left, mut right) = (Vec::new(), Vec::new());
+    for (i, d) in depth.into_iter().enumerate() {
+        match d {
+            Some(d) if d % 2 == 0 => left.push(ids[i].clone()),
+            _ => right.push(ids[i].clone()),
+        }
+    }
+    Ok(Bipartition { left, right })
+}
+
+/// Decide whether a graph is connected, with a witness either way
+/// # Description
+/// Edge orientation is ignored, so directed graphs are tested for weak
+/// connectivity. A breadth first search from the smallest identifier either
+/// reaches every vertex, and its tree edges span the graph, or stops at a
+/// component which no edge leaves. A graph without vertices is connected
+/// with an empty spanning tree.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - returns: edge identifiers of a spanning tree sorted, or the component
+///   of the smallest identifier
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::certify::is_connected_with_certificate;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Directed, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Directed, "c", "b");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1.clone(), e2]));
+/// is_connected_with_certificate(&g).unwrap() == vec!["e1", "e2"]; // true
+/// let h: Graph<Node, Edge<Node>> =
+///     Graph::from_edge_node_set(HashSet::from([e1]), HashSet::from([Node::empty("d")]));
+/// is_connected_with_certificate(&h).unwrap_err().vertices == vec!["a", "b"]; // true
+/// ```
+pub fn is_connected_with_certificate<N, E, G>(g: &G) -> Result<Vec<String>, SeparatedComponent>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let Arcs { ids, out, edge_ids } = arcs(g, false);
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut seen = vec![false; ids.len()];
+    seen[0] = true;
+    let mut tree: Vec<String> = Vec::new();
+    let mut queue = VecDeque::from([0]);
+    while let Some(u) = queue.pop_front() {
+        for (v, k) in &out[u] {
+            if !seen[*v] {
+                seen[*v] = true;
+                tree.push(edge_ids[*k].clone());
+                queue.push_back(*v);
+            }
+        }
+    }
+    if tree.len() + 1 == ids.len() {
+        tree.sort();
+        return Ok(tree);
+    }
+    let vertices = (0..ids.len())
+        .filter(|i| seen[*i])
+        .map(|i| ids[i].clone())
+        .collect();
+    Err(SeparatedComponent { vertices })
+}
+
+/// Find a cycle of a graph
+/// # Description
+/// Directed edges are followed along their orientation, so a directed
+/// graph has a cycle when it is not acyclic. In an undirected graph an edge
+/// is not walked back right after being walked, but a loop or two parallel
+/// edges form a cycle. A depth first search, from the smallest identifiers
+/// first and along edges in identifier order, returns the cycle closed by
+/// the first back edge it meets. Without a cycle, a directed graph can be
+/// sorted topologically and an undirected graph is a forest. Panics on a
+/// graph mixing directed and undirected edges.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - returns: a cycle if there is one
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::certify::has_cycle_with_certificate;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Directed, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Directed, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Directed, "c", "a");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// let cycle = has_cycle_with_certificate(&g).unwrap();
+/// cycle.vertices == vec!["a", "b", "c"]; // true
+/// cycle.edges == vec!["e1", "e2", "e3"]; // true
+/// ```
+pub fn has_cycle_with_certificate<N, E, G>(g: &G) -> Option<Cycle>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let edges = g.edges();
+    let nb_directed = edges
+        .iter()
+        .filter(|e| e.has_type() == &EdgeType::Directed)
+        .count();
+    if nb_directed != 0 && nb_directed != edges.len() {
+        panic!("{g} mixes directed and undirected edges");
+    }
+    let Arcs { ids, out, edge_ids } = arcs(g, true);
+    let n = ids.len();
+    // 0 unvisited, 1 on the search stack, 2 finished
+    let mut state = vec![0u8; n];
+    // vertex and edge through which every visited vertex was reached
+    let mut parent: Vec<Option<(usize, usize)>> = vec![None; n];
+    for root in 0..n {
+        if state[root] != 0 {
+            continue;
+        }
+        state[root] = 1;
+        let mut stack: Vec<(usize, usize)> = vec![(root, 0)];
+        while let Some((u, next)) = stack.pop() {
+            let Some((v, k)) = out[u].get(next).copied() else {
+                state[u] = 2;
+                continue;
+            };
+            stack.push((u, next + 1));
+            if parent[u].is_some_and(|(_, pk)| pk == k) {
+                // the edge leading to an undirected tree vertex
+                continue;
+            }
+            match state[v] {
+                0 => {
+                    state[v] = 1;
+                    parent[v] = Some((u, k));
+                    stack.push((v, 0));
+                }
+                1 => {
+                    let mut vertices = vec![u];
+                    let mut path = vec![k];
+                    let mut w = u;
+                    while w != v {
+                        let (p, pk) = parent[w].unwrap();
+                        vertices.push(p);
+                        path.push(pk);
+                        w = p;
+                    }
+                    // the walk went from u back to v, the cycle goes from v
+                    vertices.reverse();
+                    path.reverse();
+                    return Some(Cycle {
+                        vertices: vertices.into_iter().map(|i| ids[i].clone()).collect(),
+                        edges: path.into_iter().map(|i| edge_ids[i].clone()).collect(),
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::traits::graph_obj::GraphObject;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+    use std::collections::HashSet;
+
+    fn mk_g(es: Vec<(&str, &str, &str, EdgeType)>) -> Graph<Node, Edge<Node>> {
+        let edges: HashSet<Edge<Node>> = es
+            .into_iter()
+            .map(|(id, a, b, t)| Edge::empty(id, t, a, b))
+            .collect();
+        Graph::from_edgeset(edges)
+    }
+
+    fn mk_ugraph(es: Vec<(&str, &str, &str)>) -> Graph<Node, Edge<Node>> {
+        mk_g(
+            es.into_iter()
+                .map(|(id, a, b)| (id, a, b, EdgeType::Undirected))
+                .collect(),
+        )
+    }
+
+    /// every consecutive pair of the cycle, the last closing it, is joined
+    /// by an edge
+    fn closes(g: &Graph<Node, Edge<Node>>, cycle: &[String]) -> bool {
+        let ends: HashSet<(&String, &String)> = g
+            .edges()
+            .into_iter()
+            .flat_map(|e| {
+                [
+                    (e.start().id(), e.end().id()),
+                    (e.end().id(), e.start().id()),
+                ]
+            })
+            .collect();
+        (0..cycle.len()).all(|i| ends.contains(&(&cycle[i], &cycle[(i + 1) % cycle.len()])))
+    }
+
+    #[test]
+    fn test_bipartition() {
+        // an even cycle and a separate edge
+        let g = mk_ugraph(vec![
+            ("e1", "a", "b"),
+            ("e2", "b", "c"),
+            ("e3", "c", "d"),
+            ("e4", "d", "a"),
+            ("e5", "x", "y"),
+        ]);
+        let sides = is_bipartite_with_certificate(&g).unwrap();
+        assert_eq!(sides.left, vec!["a", "c", "x"]);
+        assert_eq!(sides.right, vec!["b", "d", "y"]);
+    }
+
+    #[test]
+    fn test_odd_cycle() {
+        // a pentagon a b c d e hanging from a pendant path r s
+        let g = mk_ugraph(vec![
+            ("e0", "a", "s"),
+            ("e1", "r", "s"),
+            ("e2", "a", "b"),
+            ("e3", "b", "c"),
+            ("e4", "c", "d"),
+            ("e5", "d", "e"),
+            ("e6", "e", "a"),
+        ]);
+        let cycle = is_bipartite_with_certificate(&g).unwrap_err().vertices;
+        assert_eq!(cycle.len(), 5);
+        assert!(closes(&g, &cycle));
+        let set: HashSet<&String> = cycle.iter().collect();
+        assert_eq!(set.len(), 5);
+        let looped = mk_ugraph(vec![("e1", "a", "b"), ("e2", "b", "b")]);
+        assert_eq!(
+            is_bipartite_with_certificate(&looped).unwrap_err().vertices,
+            vec!["b"]
+        );
+    }
+
+    #[test]
+    fn test_connected() {
+        let g = mk_ugraph(vec![("e1", "a", "b"), ("e2", "b", "c"), ("e3", "c", "a")]);
+        assert_eq!(is_connected_with_certificate(&g).unwrap(), vec!["e1", "e3"]);
+        let h = mk_ugraph(vec![("e1", "a", "b"), ("e2", "c", "d")]);
+        let component = is_connected_with_certificate(&h).unwrap_err();
+        assert_eq!(component.vertices, vec!["a", "b"]);
+        let empty = mk_ugraph(vec![]);
+        assert_eq!(is_connected_with_certificate(&empty), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_undirected_cycle() {
+        let tree = mk_ugraph(vec![("e1", "a", "b"), ("e2", "b", "c"), ("e3", "b", "d")]);
+        assert!(has_cycle_with_certificate(&tree).is_none());
+        let g = mk_ugraph(vec![
+            ("e1", "a", "b"),
+            ("e2", "b", "c"),
+            ("e3", "c", "d"),
+            ("e4", "d", "b"),
+        ]);
+        let cycle = has_cycle_with_certificate(&g).unwrap();
+        assert_eq!(cycle.vertices, vec!["b", "c", "d"]);
+        assert_eq!(cycle.edges, vec!["e2", "e3", "e4"]);
+        let parallel = mk_ugraph(vec![("e1", "a", "b"), ("e2", "b", "a")]);
+        let cycle = has_cycle_with_certificate(&parallel).unwrap();
+        assert_eq!(cycle.vertices, vec!["a", "b"]);
+        assert_eq!(cycle.edges, vec!["e1", "e2"]);
+        let looped = mk_ugraph(vec![("e1", "a", "a")]);
+        assert_eq!(
+            has_cycle_with_certificate(&looped).unwrap().edges,
+            vec!["e1"]
+        );
+    }
+
+    #[test]
+    fn test_directed_cycle() {
+        let dag = mk_g(vec![
+            ("e1", "a", "b", EdgeType::Directed),
+            ("e2", "a", "c", EdgeType::Directed),
+            ("e3", "b", "c", EdgeType::Directed),
+        ]);
+        assert!(has_cycle_with_certificate(&dag).is_none());
+        let g = mk_g(vec![
+            ("e1", "a", "b", EdgeType::Directed),
+            ("e2", "b", "c", EdgeType::Directed),
+            ("e3", "c", "d", EdgeType::Directed),
+            ("e4", "d", "b", EdgeType::Directed),
+        ]);
+        let cycle = has_cycle_with_certificate(&g).unwrap();
+        assert_eq!(cycle.vertices, vec!["b", "c", "d"]);
+        assert_eq!(cycle.edges, vec!["e2", "e3", "e4"]);
+        let antiparallel = mk_g(vec![
+            ("e1", "a", "b", EdgeType::Directed),
+            ("e2", "b", "a", EdgeType::Directed),
+        ]);
+        assert_eq!(
+            has_cycle_with_certificate(&antiparallel).unwrap().edges,
+            vec!["e1", "e2"]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_cycle_mixed_graph() {
+        let g = mk_g(vec![
+            ("e1", "a", "b", EdgeType::Directed),
+            ("e2", "b", "c", EdgeType::Undirected),
+        ]);
+        has_cycle_with_certificate(&g);
+    }
+}
@@ -0,0 +1,235 @@
+//! isolated vertex and self loop handling
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use std::collections::HashSet;
+
+/// Get the isolated vertices of a graph
+/// # Description
+/// A vertex is isolated if no edge is incident to it, see Diestel 2017,
+/// p. 5. A vertex whose only edges are loops is not isolated.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::cleanup::isolated_vertices;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "n1", "n2");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edge_node_set(HashSet::from([e1]), HashSet::from([Node::empty("n3")]));
+/// let n3 = Node::empty("n3");
+/// isolated_vertices(&g) == HashSet::from([&n3]); // true
+/// ```
+pub fn isolated_vertices<'a, N, E, G>(g: &'a G) -> HashSet<&'a N>
+where
+    N: NodeTrait + 'a,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let mut incident: HashSet<&String> = HashSet::new();
+    for e in g.edges() {
+        incident.insert(e.start().id());
+        incident.insert(e.end().id());
+    }
+    g.vertices()
+        .into_iter()
+        .filter(|v| !incident.contains(v.id()))
+        .collect()
+}
+
+/// Get the loops of a graph
+/// # Description
+/// A loop, or self loop, is an edge whose end vertices are the same, see
+/// Diestel 2017, p. 28.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::cleanup::self_loops;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "n1", "n2");
+/// let e2 = Edge::empty("e2", EdgeType::Directed, "n2", "n2");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2.clone()]));
+/// self_loops(&g) == HashSet::from([&e2]); // true
+/// ```
+pub fn self_loops<'a, N, E, G>(g: &'a G) -> HashSet<&'a E>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N> + 'a,
+    G: GraphTrait<N, E>,
+{
+    g.edges()
+        .into_iter()
+        .filter(|e| e.start().id() == e.end().id())
+        .collect()
+}
+
+/// Remove the isolated vertices of a graph
+/// # Description
+/// Returns a copy of `g` without its isolated vertices, see
+/// [isolated_vertices]. Graph identifier and data are kept.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+/// use pgm_rust::graph::ops::graph::cleanup::remove_isolated;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "n1", "n2");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edge_node_set(HashSet::from([e1]), HashSet::from([Node::empty("n3")]));
+/// remove_isolated(&g).order() == 2; // true
+/// ```
+pub fn remove_isolated<N, E, G>(g: &G) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let isolated = isolated_vertices(g);
+    let nodes: HashSet<&N> = g
+        .vertices()
+        .into_iter()
+        .filter(|v| !isolated.contains(v))
+        .collect();
+    G::create_from_ref(g.id().clone(), g.data().clone(), nodes, g.edges())
+}
+
+/// Remove the loops of a graph
+/// # Description
+/// Returns a copy of `g` without its loops, see [self_loops]. Every vertex is
+/// kept, even if it becomes isolated. Graph identifier and data are kept.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+/// use pgm_rust::graph::ops::graph::cleanup::remove_self_loops;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "n1", "n2");
+/// let e2 = Edge::empty("e2", EdgeType::Directed, "n2", "n2");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+/// let h = remove_self_loops(&g);
+/// h.size() == 1; // true
+/// h.order() == 2; // true
+/// ```
+pub fn remove_self_loops<N, E, G>(g: &G) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let loops = self_loops(g);
+    let edges: HashSet<&E> = g
+        .edges()
+        .into_iter()
+        .filter(|e| !loops.contains(e))
+        .collect();
+    G::create_from_ref(g.id().clone(), g.data().clone(), g.vertices(), edges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::traits::graph_obj::GraphObject;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::edgetype::EdgeType;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+    use std::collections::HashMap;
+
+    fn mk_nodes(ns: Vec<&str>) -> HashSet<Node> {
+        let mut hs: HashSet<Node> = HashSet::new();
+        for n in ns {
+            hs.insert(Node::empty(n));
+        }
+        hs
+    }
+    fn mk_uedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Undirected, n1_id, n2_id)
+    }
+    fn mk_edges(es: Vec<Edge<Node>>) -> HashSet<Edge<Node>> {
+        let mut hs = HashSet::new();
+        for e in es {
+            hs.insert(e);
+        }
+        hs
+    }
+
+    /// n1 - n2 with a loop at n2, a loop at n3 and isolated n4, n5
+    fn mk_g1() -> Graph<Node, Edge<Node>> {
+        let es = vec![
+            mk_uedge("n1", "n2", "e1"),
+            mk_uedge("n2", "n2", "e2"),
+            mk_uedge("n3", "n3", "e3"),
+        ];
+        Graph::new(
+            "g1".to_string(),
+            HashMap::new(),
+            mk_nodes(vec!["n4", "n5"]),
+            mk_edges(es),
+        )
+    }
+
+    #[test]
+    fn test_isolated_vertices() {
+        let g = mk_g1();
+        let ns = mk_nodes(vec!["n4", "n5"]);
+        let comp: HashSet<&Node> = ns.iter().collect();
+        assert_eq!(isolated_vertices(&g), comp);
+    }
+
+    #[test]
+    fn test_self_loops() {
+        let g = mk_g1();
+        let ids: HashSet<&String> = self_loops(&g).into_iter().map(|e| e.id()).collect();
+        assert_eq!(ids, HashSet::from([&"e2".to_string(), &"e3".to_string()]));
+    }
+
+    #[test]
+    fn test_remove_isolated() {
+        let g = mk_g1();
+        let h = remove_isolated(&g);
+        assert_eq!(h.id(), "g1");
+        let ns = mk_nodes(vec!["n1", "n2", "n3"]);
+        let comp: HashSet<&Node> = ns.iter().collect();
+        assert_eq!(h.vertices(), comp);
+        assert_eq!(h.edges(), g.edges());
+    }
+
+    #[test]
+    fn test_remove_self_loops() {
+        let g = mk_g1();
+        let h = remove_self_loops(&g);
+        assert_eq!(h.vertices(), g.vertices());
+        assert_eq!(h.size(), 1);
+        let n3 = Node::empty("n3");
+        assert!(isolated_vertices(&h).contains(&n3));
+    }
+}
@@ -0,0 +1,638 @@
+//! multilevel coarsening by edge contraction
+use crate::graph::io::edgelist::WEIGHT_KEY;
+use crate::graph::ops::graph::transform::map_nodes;
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::graph_obj::GraphObject;
+use crate::graph::traits::idgen::IdGenerator;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::edgetype::EdgeType;
+use crate::graph::types::idgen::UuidGenerator;
+use crate::rng::SplitMix64;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+/// how vertices are paired before contraction, see [coarsen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MatchingStrategy {
+    /// vertices are visited in identifier order and matched with the
+    /// unmatched neighbor joined by the heaviest edges, ties broken by
+    /// identifier
+    HeavyEdge,
+    /// vertices are visited in a random order and matched with a random
+    /// unmatched neighbor, drawn from a generator seeded by the value
+    Random(u64),
+}
+
+/// one level of a [CoarseningHierarchy]
+#[derive(Debug, Clone)]
+pub struct CoarseLevel<G> {
+    /// the contracted graph
+    pub graph: G,
+    /// coarse vertex of every vertex of the previous level
+    pub mapping: HashMap<String, String>,
+}
+
+/// output of [coarsen]
+#[derive(Debug, Clone)]
+pub struct CoarseningHierarchy<G> {
+    /// levels from the finest to the coarsest, the input graph excluded
+    pub levels: Vec<CoarseLevel<G>>,
+}
+
+impl<G> CoarseningHierarchy<G> {
+    /// coarsest graph of the hierarchy, `None` if no level was built
+    pub fn coarsest(&self) -> Option<&G> {
+        self.levels.last().map(|l| &l.graph)
+    }
+
+    /// vertex of level `level` (starting at 1) containing the vertex `id` of
+    /// the input graph, panics if `level` is out of range or `id` unknown
+    pub fn representative<'a>(&'a self, id: &'a str, level: usize) -> &'a str {
+        if level == 0 || level > self.levels.len() {
+            panic!("level {level} is not in 1..={}", self.levels.len());
+        }
+        let mut current = id;
+        for l in &self.levels[..level] {
+            current = match l.mapping.get(current) {
+                None => panic!("{current} is not a vertex of the hierarchy"),
+                Some(c) => c,
+            };
+        }
+        current
+    }
+}
+
+/// Weight of a vertex or an edge
+/// # Description
+/// The numeric [WEIGHT_KEY] entry of its data, 1 when missing. Panics if the
+/// entry is not a number. This is the weight accumulated by [contract].
+pub fn weight_of(obj: &impl GraphObject) -> f64 {
+    match obj.data().get(WEIGHT_KEY).and_then(|ws| ws.first()) {
+        None => 1.0,
+        Some(w) => match w.parse::<f64>() {
+            Err(_) => panic!("{obj} has a non numeric {WEIGHT_KEY}"),
+            Ok(w) => w,
+        },
+    }
+}
+
+/// data key listing the members of a block vertex, see [quotient]
+pub const MEMBERS_KEY: &str = "members";
+
+fn weight_data(w: f64) -> HashMap<String, Vec<String>> {
+    HashMap::from([(WEIGHT_KEY.to_string(), vec![w.to_string()])])
+}
+
+/// Contract groups of vertices into single vertices
+/// # Description
+/// Vertices sharing the same image under `mapping` are merged into a vertex
+/// named after that image. The [WEIGHT_KEY] of a merged vertex is the sum
+/// of the weights of its members, a missing weight counting as 1. Edges
+/// inside a group disappear, the others are merged by end vertices and
+/// type: directed edges `u -> v` and `v -> u` stay apart. A merged edge is
+/// named after the smallest identifier among its members and its
+/// [WEIGHT_KEY] is the sum of their weights. Panics if a vertex has no
+/// image.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - mapping: group of every vertex identifier
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+/// use pgm_rust::graph::ops::graph::coarsen::contract;
+/// use std::collections::HashMap;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "a", "c");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// let mapping: HashMap<String, String> =
+///     [("a", "ab"), ("b", "ab"), ("c", "c")].iter().map(|(v, c)| (v.to_string(), c.to_string())).collect();
+/// let h = contract(&g, &mapping);
+/// h.order() == 2; // true
+/// h.size() == 1; // true: e2 and e3 are merged into e2 with weight 2
+/// ```
+pub fn contract<N, E, G>(g: &G, mapping: &HashMap<String, String>) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    contract_with(g, mapping, &mut UuidGenerator)
+}
+
+/// Contract groups of vertices into single vertices, the identifier of the
+/// output is produced by `idgen`, see [contract]
+pub fn contract_with<N, E, G, I>(g: &G, mapping: &HashMap<String, String>, idgen: &mut I) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    I: IdGenerator,
+{
+    contract_by(g, mapping, weight_of, idgen)
+}
+
+/// [contract_with] where a merged edge sums `edge_value` over its members
+fn contract_by<N, E, G, I, F>(
+    g: &G,
+    mapping: &HashMap<String, String>,
+    edge_value: F,
+    idgen: &mut I,
+) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    I: IdGenerator,
+    F: Fn(&E) -> f64,
+{
+    let image = |v: &N| -> &String {
+        match mapping.get(v.id()) {
+            None => panic!("{v} has no image in the mapping"),
+            Some(c) => c,
+        }
+    };
+    let mut node_weights: BTreeMap<&String, f64> = BTreeMap::new();
+    for v in g.vertices() {
+        *node_weights.entry(image(v)).or_default() += weight_of(v);
+    }
+    let nodes: HashMap<&String, N> = node_weights
+        .iter()
+        .map(|(c, w)| (*c, N::create((*c).clone(), weight_data(*w))))
+        .collect();
+    // merged edges keyed by end vertices and orientation
+    let mut merged: HashMap<(&String, &String, bool), (&String, f64)> = HashMap::new();
+    for e in g.edges_sorted() {
+        let (s, t) = (image(e.start()), image(e.end()));
+        if s == t {
+            continue;
+        }
+        let directed = e.has_type() == &EdgeType::Directed;
+        let key = if directed || s < t {
+            (s, t, directed)
+        } else {
+            (t, s, directed)
+        };
+        merged.entry(key).or_insert((e.id(), 0.0)).1 += edge_value(e);
+    }
+    let edges: HashSet<E> = merged
+        .into_iter()
+        .map(|((s, t, directed), (id, w))| {
+            let etype = if directed {
+                EdgeType::Directed
+            } else {
+                EdgeType::Undirected
+            };
+            E::create(
+                id.clone(),
+                weight_data(w),
+                nodes[s].clone(),
+                nodes[t].clone(),
+                etype,
+            )
+        })
+        .collect();
+    let nodes: HashSet<N> = nodes.into_values().collect();
+    let gid = idgen.generate(&nodes.iter().collect(), &edges.iter().collect());
+    G::create(gid, HashMap::new(), nodes, edges)
+}
+
+/// how [quotient] aggregates the edges joining two blocks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EdgeAggregation {
+    /// the weight of a block edge is the number of edges it replaces
+    Count,
+    /// the weight of a block edge is the total weight of the edges it
+    /// replaces, see [weight_of]
+    WeightSum,
+}
+
+/// Quotient of a graph by a partition of its vertices
+/// # Description
+/// Every block of `blocks` is collapsed into a single vertex named after
+/// the smallest identifier of its members. The block vertex lists its
+/// sorted members under [MEMBERS_KEY] and its [WEIGHT_KEY] is the total
+/// weight of its members, the number of members when they have no weight.
+/// Edges inside a block disappear and the edges joining two blocks are
+/// merged by type as in [contract], the [WEIGHT_KEY] of a merged edge being
+/// set by `aggregation`. Empty blocks are ignored. Panics if a vertex of
+/// `g` belongs to no block or to several blocks, or if a member is not a
+/// vertex of `g`. The identifier of the quotient is a random UUID.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - blocks: vertex identifiers of every block of the partition
+/// - aggregation: weight of the merged edges, see [EdgeAggregation]
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+/// use pgm_rust::graph::ops::graph::coarsen::{quotient, weight_of, EdgeAggregation};
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "a", "c");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// let blocks = vec![
+///     HashSet::from(["a".to_string(), "b".to_string()]),
+///     HashSet::from(["c".to_string()]),
+/// ];
+/// let h = quotient(&g, &blocks, &EdgeAggregation::Count);
+/// h.order() == 2; // true
+/// weight_of(h.find_edge("e2").unwrap()) == 2.0; // true
+/// ```
+pub fn quotient<N, E, G>(g: &G, blocks: &[HashSet<String>], aggregation: &EdgeAggregation) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    quotient_with(g, blocks, aggregation, &mut UuidGenerator)
+}
+
+/// Quotient of a graph by a partition of its vertices, the identifier of
+/// the quotient is produced by `idgen`, see [quotient]
+pub fn quotient_with<N, E, G, I>(
+    g: &G,
+    blocks: &[HashSet<String>],
+    aggregation: &EdgeAggregation,
+    idgen: &mut I,
+) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    I: IdGenerator,
+{
+    let mut mapping: HashMap<String, String> = HashMap::new();
+    let mut members: HashMap<&String, Vec<String>> = HashMap::new();
+    for block in blocks.iter().filter(|b| !b.is_empty()) {
+        let mut sorted: Vec<&String> = block.iter().collect();
+        sorted.sort();
+        let name = sorted[0];
+        for v in &sorted {
+            if !g.contains_node_id(v) {
+                panic!("{v} not in {g}");
+            }
+            if mapping.insert((*v).clone(), name.clone()).is_some() {
+                panic!("{v} belongs to several blocks");
+            }
+        }
+        members.insert(name, sorted.into_iter().cloned().collect());
+    }
+    if let Some(v) = g
+        .vertices()
+        .into_iter()
+        .find(|v| !mapping.contains_key(v.id()))
+    {
+        panic!("{v} belongs to no block");
+    }
+    let contracted: G = match aggregation {
+        EdgeAggregation::Count => contract_by(g, &mapping, |_| 1.0, idgen),
+        EdgeAggregation::WeightSum => contract_by(g, &mapping, weight_of, idgen),
+    };
+    map_nodes(&contracted, |v: &N| {
+        let mut data = v.data().clone();
+        data.insert(MEMBERS_KEY.to_string(), members[v.id()].clone());
+        N::create(v.id().clone(), data)
+    })
+}
+
+/// pair vertices of `g` along its edges, unmatched vertices map to
+/// themselves and a pair maps to its smallest identifier
+fn match_vertices<N, E, G>(
+    g: &G,
+    strategy: &MatchingStrategy,
+    rng: &mut SplitMix64,
+) -> HashMap<String, String>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let mut connection: HashMap<&String, BTreeMap<&String, f64>> = HashMap::new();
+    for e in g.edges() {
+        let (s, t) = (e.start().id(), e.end().id());
+        if s != t {
+            let w = weight_of(e);
+            *connection.entry(s).or_default().entry(t).or_default() += w;
+            *connection.entry(t).or_default().entry(s).or_default() += w;
+        }
+    }
+    let mut order: Vec<&String> = g.vertices_sorted().into_iter().map(|v| v.id()).collect();
+    if let MatchingStrategy::Random(_) = strategy {
+        rng.shuffle(&mut order);
+    }
+    let mut mapping: HashMap<String, String> = HashMap::new();
+    for v in order {
+        if mapping.contains_key(v) {
+            continue;
+        }
+        let free: Vec<(&String, f64)> = connection
+            .get(v)
+            .into_iter()
+            .flatten()
+            .filter(|(w, _)| !mapping.contains_key(**w))
+            .map(|(w, weight)| (*w, *weight))
+            .collect();
+        let mate = match strategy {
+            _ if free.is_empty() => None,
+            MatchingStrategy::HeavyEdge => free
+                .iter()
+                .fold(
+                    None,
+                    |best: Option<(&String, f64)>, (w, weight)| match best {
+                        Some((_, b)) if b >= *weight => best,
+                        _ => Some((w, *weight)),
+                    },
+                )
+                .map(|(w, _)| w),
+            MatchingStrategy::Random(_) => Some(free[rng.gen_range(free.len())].0),
+        };
+        let rep = match mate {
+            Some(w) if w < v => w,
+            _ => v,
+        };
+        mapping.insert(v.clone(), rep.clone());
+        if let Some(w) = mate {
+            mapping.insert(w.clone(), rep.clone());
+        }
+    }
+    mapping
+}
+
+/// Build a coarsening hierarchy of a graph
+/// # Description
+/// Each level pairs vertices with a matching chosen by `matching_strategy`
+/// and [contract]s the pairs, so that a level has roughly half as many
+/// vertices as the previous one. A coarse vertex is named after the
+/// smallest identifier of its pair and accumulates vertex and edge weights
+/// under [WEIGHT_KEY], which drives heavy edge matching at the next levels
+/// and lets partitions of a coarse level be balanced by weight. Edge
+/// orientation is ignored when matching. Coarsening stops early once a
+/// level contracts nothing.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - levels: largest number of levels
+/// - matching_strategy: how vertices are paired, see [MatchingStrategy]
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+/// use pgm_rust::graph::ops::graph::coarsen::{coarsen, MatchingStrategy};
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "c", "d");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// let h = coarsen(&g, 5, &MatchingStrategy::HeavyEdge);
+/// h.levels.len() == 2; // true: a b c d, then a c, then a
+/// h.coarsest().unwrap().order() == 1; // true
+/// h.representative("d", 1) == "c"; // true
+/// ```
+///
+/// # References
+/// Karypis G, Kumar V. A fast and high quality multilevel scheme for
+/// partitioning irregular graphs. 1998.
+pub fn coarsen<N, E, G>(
+    g: &G,
+    levels: usize,
+    matching_strategy: &MatchingStrategy,
+) -> CoarseningHierarchy<G>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let seed = match matching_strategy {
+        MatchingStrategy::HeavyEdge => 0,
+        MatchingStrategy::Random(seed) => *seed,
+    };
+    let mut rng = SplitMix64::new(seed);
+    let mut hierarchy = CoarseningHierarchy { levels: Vec::new() };
+    for _ in 0..levels {
+        let fine = hierarchy.coarsest().unwrap_or(g);
+        let mapping = match_vertices(fine, matching_strategy, &mut rng);
+        let nb_coarse = mapping.values().collect::<HashSet<_>>().len();
+        if nb_coarse == fine.order() {
+            break;
+        }
+        let graph = contract(fine, &mapping);
+        hierarchy.levels.push(CoarseLevel { graph, mapping });
+    }
+    hierarchy
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+
+    fn mk_uedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Undirected, n1_id, n2_id)
+    }
+
+    fn mk_wedge(n1_id: &str, n2_id: &str, e_id: &str, w: f64) -> Edge<Node> {
+        Edge::new(
+            e_id.to_string(),
+            weight_data(w),
+            Node::empty(n1_id),
+            Node::empty(n2_id),
+            EdgeType::Undirected,
+        )
+    }
+
+    #[test]
+    fn test_contract_merges_weights() {
+        let es = HashSet::from([
+            mk_uedge("a", "b", "e1"),
+            mk_uedge("a", "c", "e2"),
+            mk_uedge("b", "c", "e3"),
+            Edge::empty("e4", EdgeType::Directed, "c", "a"),
+            Edge::empty("e5", EdgeType::Directed, "b", "c"),
+        ]);
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(es);
+        let mapping: HashMap<String, String> = [("a", "a"), ("b", "a"), ("c", "c")]
+            .iter()
+            .map(|(v, c)| (v.to_string(), c.to_string()))
+            .collect();
+        let h = contract(&g, &mapping);
+        assert_eq!(h.order(), 2);
+        let weights: HashMap<&String, f64> = h
+            .vertices()
+            .into_iter()
+            .map(|v| (v.id(), weight_of(v)))
+            .collect();
+        assert_eq!(weights[&"a".to_string()], 2.0);
+        assert_eq!(weights[&"c".to_string()], 1.0);
+        let mut edges: Vec<(String, String, String, f64)> = h
+            .edges()
+            .into_iter()
+            .map(|e| {
+                (
+                    e.id().clone(),
+                    e.start().id().clone(),
+                    e.end().id().clone(),
+                    weight_of(e),
+                )
+            })
+            .collect();
+        edges.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            edges,
+            vec![
+                ("e2".to_string(), "a".to_string(), "c".to_string(), 2.0),
+                ("e4".to_string(), "c".to_string(), "a".to_string(), 1.0),
+                ("e5".to_string(), "a".to_string(), "c".to_string(), 1.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_heavy_edge_matching() {
+        // the heavy edges b -- c and a -- d are contracted first
+        let es = HashSet::from([
+            mk_wedge("a", "b", "e1", 1.0),
+            mk_wedge("b", "c", "e2", 5.0),
+            mk_wedge("c", "d", "e3", 1.0),
+            mk_wedge("d", "a", "e4", 3.0),
+        ]);
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(es);
+        let h = coarsen(&g, 1, &MatchingStrategy::HeavyEdge);
+        assert_eq!(h.levels.len(), 1);
+        let m = &h.levels[0].mapping;
+        assert_eq!(m["a"], "a");
+        assert_eq!(m["d"], "a");
+        assert_eq!(m["b"], "b");
+        assert_eq!(m["c"], "b");
+        let coarse = h.coarsest().unwrap();
+        assert_eq!(coarse.size(), 1);
+        let e = coarse.edges().into_iter().next().unwrap();
+        assert_eq!(weight_of(e), 2.0);
+    }
+
+    #[test]
+    fn test_coarsen_hierarchy() {
+        let es: HashSet<Edge<Node>> = (1..16)
+            .map(|i| {
+                mk_uedge(
+                    &format!("v{:02}", i - 1),
+                    &format!("v{i:02}"),
+                    &format!("e{i:02}"),
+                )
+            })
+            .collect();
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(es);
+        for strategy in [MatchingStrategy::HeavyEdge, MatchingStrategy::Random(3)] {
+            let h = coarsen(&g, 10, &strategy);
+            let mut previous = g.order();
+            for level in &h.levels {
+                assert!(level.graph.order() < previous);
+                assert_eq!(level.mapping.len(), previous);
+                let total: f64 = level.graph.vertices().into_iter().map(weight_of).sum();
+                assert_eq!(total, 16.0);
+                previous = level.graph.order();
+            }
+            assert_eq!(h.coarsest().unwrap().order(), 1);
+            let last = h.levels.len();
+            let root = h.representative("v00", last);
+            for v in g.vertices() {
+                assert_eq!(h.representative(v.id(), last), root);
+            }
+        }
+        let random = coarsen(&g, 10, &MatchingStrategy::Random(3));
+        let again = coarsen(&g, 10, &MatchingStrategy::Random(3));
+        assert_eq!(random.levels[0].mapping, again.levels[0].mapping);
+    }
+
+    #[test]
+    fn test_coarsen_without_edges() {
+        let g: Graph<Node, Edge<Node>> = Graph::from_edge_node_set(
+            HashSet::new(),
+            HashSet::from([Node::empty("a"), Node::empty("b")]),
+        );
+        let h = coarsen(&g, 3, &MatchingStrategy::HeavyEdge);
+        assert!(h.levels.is_empty());
+        assert!(h.coarsest().is_none());
+    }
+
+    fn blocks(bs: &[&[&str]]) -> Vec<HashSet<String>> {
+        bs.iter()
+            .map(|b| b.iter().map(|v| v.to_string()).collect())
+            .collect()
+    }
+
+    /// two weighted triangles a b c and d e f joined by c -- d and c -- e
+    fn mk_two_triangles() -> Graph<Node, Edge<Node>> {
+        Graph::from_edgeset(HashSet::from([
+            mk_wedge("a", "b", "e1", 1.0),
+            mk_wedge("b", "c", "e2", 1.0),
+            mk_wedge("c", "a", "e3", 1.0),
+            mk_wedge("d", "e", "e4", 1.0),
+            mk_wedge("e", "f", "e5", 1.0),
+            mk_wedge("f", "d", "e6", 1.0),
+            mk_wedge("c", "d", "e7", 2.0),
+            mk_wedge("c", "e", "e8", 0.5),
+        ]))
+    }
+
+    #[test]
+    fn test_quotient() {
+        let g = mk_two_triangles();
+        let bs = blocks(&[&["c", "a", "b"], &["f", "e", "d"], &[]]);
+        let counted = quotient(&g, &bs, &EdgeAggregation::Count);
+        assert_eq!(counted.order(), 2);
+        assert_eq!(counted.size(), 1);
+        let e = counted.find_edge("e7").unwrap();
+        assert_eq!(weight_of(e), 2.0);
+        let a = counted.find_node("a").unwrap();
+        assert_eq!(weight_of(a), 3.0);
+        assert_eq!(a.data()[MEMBERS_KEY], vec!["a", "b", "c"]);
+        assert_eq!(counted.find_node("d").unwrap().data()[MEMBERS_KEY].len(), 3);
+        let summed = quotient(&g, &bs, &EdgeAggregation::WeightSum);
+        assert_eq!(weight_of(summed.find_edge("e7").unwrap()), 2.5);
+        let identity = quotient(
+            &g,
+            &blocks(&[&["a"], &["b"], &["c"], &["d"], &["e"], &["f"]]),
+            &EdgeAggregation::WeightSum,
+        );
+        assert_eq!(identity.size(), g.size());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_quotient_overlapping_blocks() {
+        let bs = blocks(&[&["a", "b", "c"], &["c", "d", "e", "f"]]);
+        quotient(&mk_two_triangles(), &bs, &EdgeAggregation::Count);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_quotient_uncovered_vertex() {
+        let bs = blocks(&[&["a", "b", "c"], &["d", "e"]]);
+        quotient(&mk_two_triangles(), &bs, &EdgeAggregation::Count);
+    }
+}
@@ -0,0 +1,413 @@
+//! binary graph compositions with deterministic identifiers
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::idgen::IdGenerator;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::edgetype::EdgeType;
+use crate::graph::types::idgen::UuidGenerator;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+/// vertices and edges of a composition under construction
+struct Composition<N, E> {
+    nodes: HashSet<N>,
+    edges: HashSet<E>,
+    edge_ids: HashSet<String>,
+}
+
+impl<N, E> Composition<N, E>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+{
+    fn new() -> Composition<N, E> {
+        Composition {
+            nodes: HashSet::new(),
+            edges: HashSet::new(),
+            edge_ids: HashSet::new(),
+        }
+    }
+
+    /// add a copy of `g` whose identifiers are prefixed by `prefix.`,
+    /// returns the copied vertices sorted by identifier
+    fn add_copy<G: GraphTrait<N, E>>(&mut self, g: &G, prefix: &str) -> Vec<N> {
+        let rename = |v: &N| N::create(format!("{prefix}.{}", v.id()), v.data().clone());
+        let copies: Vec<N> = g.vertices_sorted().into_iter().map(rename).collect();
+        for e in g.edges() {
+            let id = format!("{prefix}.{}", e.id());
+            self.edge_ids.insert(id.clone());
+            self.edges.insert(E::create(
+                id,
+                e.data().clone(),
+                rename(e.start()),
+                rename(e.end()),
+                e.has_type().clone(),
+            ));
+        }
+        self.nodes.extend(copies.iter().cloned());
+        copies
+    }
+
+    /// add an undirected edge between `u` and `v` whose identifier is
+    /// produced by `idgen` from its endpoints. Panics if the identifier is
+    /// already used by an edge.
+    fn add_cross_edge<I: IdGenerator>(&mut self, u: &N, v: &N, idgen: &mut I) {
+        let id = idgen.generate::<N, E>(&HashSet::from([u, v]), &HashSet::new());
+        if !self.edge_ids.insert(id.clone()) {
+            panic!("edge identifier {id} is already used");
+        }
+        self.edges.insert(E::create(
+            id,
+            HashMap::new(),
+            u.clone(),
+            v.clone(),
+            EdgeType::Undirected,
+        ));
+    }
+
+    fn build<G: GraphTrait<N, E>, I: IdGenerator>(self, idgen: &mut I) -> G {
+        let gid = idgen.generate(&self.nodes.iter().collect(), &self.edges.iter().collect());
+        G::create(gid, HashMap::new(), self.nodes, self.edges)
+    }
+}
+
+/// Disjoint union of two graphs with identifiers prefixed by position
+/// # Description
+/// Every vertex and edge of `g1` is copied with its identifier prefixed by
+/// `0.` and every one of `g2` with its identifier prefixed by `1.`, so the
+/// result has `g1.order() + g2.order()` vertices whatever the identifiers
+/// of the operands. Data and edge types are kept. Unlike
+/// [disjoint_union](crate::graph::ops::setops::disjoint_union), which only
+/// renames conflicting identifiers after the identifier of `g2`, the names
+/// only depend on the position of the operands, which makes the
+/// compositions of this module easy to chain and to check in tests. The
+/// identifier of the result is a random UUID.
+///
+/// # Args
+/// - g1: something that implements [Graph] trait
+/// - g2: something that implements [Graph] trait
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+/// use pgm_rust::graph::ops::graph::compose::prefixed_union;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1]));
+/// let u: Graph<Node, Edge<Node>> = prefixed_union(&g, &g);
+/// u.order() == 4; // true
+/// u.contains_edge_id("1.e1"); // true
+/// ```
+pub fn prefixed_union<N, E, G>(g1: &G, g2: &G) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    prefixed_union_with(g1, g2, &mut UuidGenerator)
+}
+
+/// Disjoint union of two graphs with identifiers prefixed by position, the
+/// identifier of the result is produced by `idgen`, see [prefixed_union]
+pub fn prefixed_union_with<N, E, G, I>(g1: &G, g2: &G, idgen: &mut I) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    I: IdGenerator,
+{
+    let mut c = Composition::new();
+    c.add_copy(g1, "0");
+    c.add_copy(g2, "1");
+    c.build(idgen)
+}
+
+/// Join of two graphs
+/// # Description
+/// The join is the [prefixed_union] of the graphs in which every vertex of
+/// `g1` is joined to every vertex of `g2` by an undirected edge without
+/// data. Joining a complete graph with an edgeless graph gives a complete
+/// split graph, and joining two edgeless graphs a complete bipartite graph.
+/// The identifiers of the joining edges and of the result are random UUIDs,
+/// use [join_with] to name them.
+///
+/// # Args
+/// - g1: something that implements [Graph] trait
+/// - g2: something that implements [Graph] trait
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+/// use pgm_rust::graph::traits::graph_obj::GraphObject;
+/// use pgm_rust::graph::ops::graph::compose::join_with;
+/// use pgm_rust::graph::types::idgen::SequentialIdGenerator;
+/// use std::collections::HashMap;
+/// use std::collections::HashSet;
+///
+/// let two: Graph<Node, Edge<Node>> = Graph::new(
+///     "two".to_string(),
+///     HashMap::new(),
+///     HashSet::from([Node::empty("a"), Node::empty("b")]),
+///     HashSet::new(),
+/// );
+/// let k22: Graph<Node, Edge<Node>> = join_with(&two, &two, &mut SequentialIdGenerator::new("x"));
+/// k22.size() == 4; // true
+/// k22.contains_edge_id("x1"); // true
+/// k22.id() == "x4"; // true
+/// ```
+pub fn join<N, E, G>(g1: &G, g2: &G) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    join_with(g1, g2, &mut UuidGenerator)
+}
+
+/// Join of two graphs, the identifiers of the joining edges and of the
+/// result are produced by `idgen`, see [join]
+/// # Description
+/// Joining edges are named first, from `0.u` and `1.v` with `u` then `v`
+/// in identifier order, and the result last, so a
+/// [SequentialIdGenerator](crate::graph::types::idgen::SequentialIdGenerator)
+/// gives reproducible names. Panics if `idgen` gives an identifier already
+/// used by an edge.
+pub fn join_with<N, E, G, I>(g1: &G, g2: &G, idgen: &mut I) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    I: IdGenerator,
+{
+    let mut c = Composition::new();
+    let left = c.add_copy(g1, "0");
+    let right = c.add_copy(g2, "1");
+    for u in &left {
+        for v in &right {
+            c.add_cross_edge(u, v, idgen);
+        }
+    }
+    c.build(idgen)
+}
+
+/// Corona product of two graphs
+/// # Description
+/// The corona of `g1` and `g2` is made of one copy of `g1` and one copy of
+/// `g2` per vertex of `g1`, every vertex of `g1` being joined to every
+/// vertex of its copy of `g2` by an undirected edge without data, see Frucht
+/// and Harary 1970. The copy of `g1` is prefixed by `0.` as in
+/// [prefixed_union]. Vertices of `g1` are numbered from 0 in identifier
+/// order and the copy of `g2` attached to vertex `i` is prefixed by `1.i.`,
+/// numbers rather than identifiers keeping the names unambiguous. The
+/// corona of a graph with a single vertex graph adds a pendant vertex to
+/// every vertex. As for [join], the identifiers of the joining edges and of
+/// the result are random UUIDs, use [corona_with] to name them.
+///
+/// # Args
+/// - g1: something that implements [Graph] trait, the center
+/// - g2: something that implements [Graph] trait, copied around the center
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+/// use pgm_rust::graph::ops::graph::compose::corona;
+/// use pgm_rust::graph::ops::graph::mixed::degree;
+/// use std::collections::HashMap;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1]));
+/// let k1: Graph<Node, Edge<Node>> =
+///     Graph::new("k1".to_string(), HashMap::new(), HashSet::from([Node::empty("x")]), HashSet::new());
+/// let c: Graph<Node, Edge<Node>> = corona(&g, &k1);
+/// c.order() == 4; // true
+/// degree(&c, &Node::empty("1.1.x")) == 1; // true
+/// ```
+///
+/// # References
+/// Frucht R, Harary F. On the corona of two graphs. 1970.
+pub fn corona<N, E, G>(g1: &G, g2: &G) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    corona_with(g1, g2, &mut UuidGenerator)
+}
+
+/// Corona product of two graphs, the identifiers of the joining edges and
+/// of the result are produced by `idgen`, see [corona]
+/// # Description
+/// Joining edges are named first, from `0.u` in identifier order and then
+/// the vertices of its copy of `g2` in identifier order, and the result
+/// last. Panics if `idgen` gives an identifier already used by an edge.
+pub fn corona_with<N, E, G, I>(g1: &G, g2: &G, idgen: &mut I) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    I: IdGenerator,
+{
+    let mut c = Composition::new();
+    let center = c.add_copy(g1, "0");
+    for (i, u) in center.iter().enumerate() {
+        for w in c.add_copy(g2, &format!("1.{i}")) {
+            c.add_cross_edge(u, &w, idgen);
+        }
+    }
+    c.build(idgen)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::ops::graph::mixed::degree;
+    use crate::graph::traits::graph_obj::GraphObject;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::idgen::SequentialIdGenerator;
+    use crate::graph::types::node::Node;
+
+    fn mk_complete(n: usize) -> Graph<Node, Edge<Node>> {
+        let nodes: HashSet<Node> = (0..n).map(|i| Node::empty(&format!("v{i}"))).collect();
+        let mut edges = HashSet::new();
+        for i in 0..n {
+            for j in i + 1..n {
+                let (a, b) = (format!("v{i}"), format!("v{j}"));
+                edges.insert(Edge::empty(
+                    &format!("e{i}{j}"),
+                    EdgeType::Undirected,
+                    &a,
+                    &b,
+                ));
+            }
+        }
+        Graph::new("k".to_string(), HashMap::new(), nodes, edges)
+    }
+
+    fn mk_empty(n: usize) -> Graph<Node, Edge<Node>> {
+        let nodes: HashSet<Node> = (0..n).map(|i| Node::empty(&format!("v{i}"))).collect();
+        Graph::new("e".to_string(), HashMap::new(), nodes, HashSet::new())
+    }
+
+    #[test]
+    fn test_prefixed_union_keeps_data_and_types() {
+        let mut data = HashMap::new();
+        data.insert("weight".to_string(), vec!["3".to_string()]);
+        let e1 = Edge::new(
+            "e1".to_string(),
+            data.clone(),
+            Node::empty("a"),
+            Node::empty("b"),
+            EdgeType::Directed,
+        );
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1]));
+        let u: Graph<Node, Edge<Node>> = prefixed_union(&g, &g);
+        assert_eq!(u.order(), 4);
+        let e = u.find_edge("0.e1").unwrap();
+        assert_eq!(e.data(), &data);
+        assert_eq!(e.has_type(), &EdgeType::Directed);
+        assert_eq!(e.start().id(), "0.a");
+        assert_eq!(u.find_edge("1.e1").unwrap().end().id(), "1.b");
+    }
+
+    #[test]
+    fn test_join_complete_split_graph() {
+        // the complete split graph with a clique of 3 and an independent
+        // set of 2
+        let g: Graph<Node, Edge<Node>> = join(&mk_complete(3), &mk_empty(2));
+        assert_eq!(g.order(), 5);
+        assert_eq!(g.size(), 3 + 3 * 2);
+        for i in 0..3 {
+            assert_eq!(degree(&g, &Node::empty(&format!("0.v{i}"))), 4);
+        }
+        for i in 0..2 {
+            assert_eq!(degree(&g, &Node::empty(&format!("1.v{i}"))), 3);
+        }
+        assert!(g
+            .edges()
+            .iter()
+            .all(|e| e.has_type() == &EdgeType::Undirected));
+    }
+
+    #[test]
+    fn test_join_with_names_edges() {
+        let mut idgen = SequentialIdGenerator::new("x");
+        let g: Graph<Node, Edge<Node>> = join_with(&mk_complete(2), &mk_empty(2), &mut idgen);
+        let e = g.find_edge("x1").unwrap();
+        assert_eq!(
+            (e.start().id().as_str(), e.end().id().as_str()),
+            ("0.v0", "1.v1")
+        );
+        assert!(g.contains_edge_id("x3"));
+        assert_eq!(g.id(), "x4");
+    }
+
+    #[test]
+    fn test_join_edge_ids_do_not_collide() {
+        // naming the edges after their endpoints gives 0.a--1.b--1.c twice
+        let mk = |a: &str, b: &str| -> Graph<Node, Edge<Node>> {
+            let nodes = HashSet::from([Node::empty(a), Node::empty(b)]);
+            Graph::new("g".to_string(), HashMap::new(), nodes, HashSet::new())
+        };
+        let g: Graph<Node, Edge<Node>> = join(&mk("a", "a--1.b"), &mk("c", "b--1.c"));
+        assert_eq!(g.size(), 4);
+    }
+
+    /// generator giving the same identifier every time
+    struct ConstantIdGenerator;
+
+    impl IdGenerator for ConstantIdGenerator {
+        fn generate<N, E>(&mut self, _: &HashSet<&N>, _: &HashSet<&E>) -> String
+        where
+            N: NodeTrait,
+            E: EdgeTrait<N>,
+        {
+            "c".to_string()
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_join_with_repeated_identifier() {
+        let _: Graph<Node, Edge<Node>> =
+            join_with(&mk_empty(2), &mk_empty(1), &mut ConstantIdGenerator);
+    }
+
+    #[test]
+    fn test_corona() {
+        let g: Graph<Node, Edge<Node>> = corona(&mk_complete(3), &mk_complete(2));
+        // 3 + 3 * 2 vertices, 3 + 3 * 1 copied edges and 3 * 2 spokes
+        assert_eq!(g.order(), 9);
+        assert_eq!(g.size(), 12);
+        assert!(g.contains_node_id("1.2.v1"));
+        assert!(g.contains_edge_id("1.0.e01"));
+        assert!(g
+            .edges()
+            .iter()
+            .any(|e| e.start().id() == "0.v0" && e.end().id() == "1.0.v1"));
+        assert_eq!(degree(&g, &Node::empty("0.v1")), 4);
+        assert_eq!(degree(&g, &Node::empty("1.1.v0")), 2);
+    }
+
+    #[test]
+    fn test_compose_with_empty_operand() {
+        let empty = mk_empty(0);
+        let k = mk_complete(3);
+        assert_eq!(join(&k, &empty).size(), 3);
+        assert_eq!(corona(&k, &empty).order(), 3);
+        assert_eq!(corona(&empty, &k).order(), 0);
+    }
+}
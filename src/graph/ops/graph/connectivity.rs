@@ -0,0 +1,326 @@
+//! connectivity numbers of graphs
+use crate::graph::ops::graph::flow::Network;
+use crate::graph::ops::graph::order::simple_neighbors;
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use std::collections::HashMap;
+
+/// Edge connectivity of a graph
+/// # Description
+/// The largest `l` such that removing fewer than `l` edges leaves the
+/// graph connected. By Menger's theorem it is the smallest number of edge
+/// disjoint paths between two vertices, which is computed as a unit
+/// capacity maximum flow from a fixed vertex to every other one. Edge
+/// orientation is ignored, parallel edges count as distinct edges and
+/// loops are dropped. A graph with fewer than two vertices has edge
+/// connectivity 0, as does a disconnected graph.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::connectivity::edge_connectivity;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "c", "a");
+/// let e4 = Edge::empty("e4", EdgeType::Undirected, "c", "d");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3, e4]));
+/// edge_connectivity(&g) == 1; // true: e4 is a bridge
+/// ```
+///
+/// # References
+/// Diestel R. Graph theory. 2017. Section 3.3.
+pub fn edge_connectivity<N, E, G>(g: &G) -> usize
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let ids: Vec<&String> = g.vertices_sorted().into_iter().map(|v| v.id()).collect();
+    if ids.len() < 2 {
+        return 0;
+    }
+    let index: HashMap<&String, usize> = ids.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+    let pairs: Vec<(usize, usize)> = g
+        .edges_sorted()
+        .into_iter()
+        .map(|e| (index[e.start().id()], index[e.end().id()]))
+        .filter(|(u, v)| u != v)
+        .collect();
+    let mut best = pairs.len();
+    for t in 1..ids.len() {
+        let mut network = Network::new(ids.len());
+        for (u, v) in &pairs {
+            network.add_arcs(*u, *v, 1.0, 1.0);
+        }
+        let flow = network.max_flow(0, t).round() as usize;
+        best = best.min(flow);
+        if best == 0 {
+            break;
+        }
+    }
+    best
+}
+
+/// number of internally vertex disjoint paths between two non adjacent
+/// vertices, as a unit capacity flow in which every vertex `i` is split
+/// into an entry `2i` and an exit `2i + 1`
+fn local_vertex_connectivity(neighbors: &[Vec<usize>], s: usize, t: usize) -> usize {
+    let n = neighbors.len();
+    let unbounded = n as f64;
+    let mut network = Network::new(2 * n);
+    for (u, vs) in neighbors.iter().enumerate() {
+        network.add_arcs(2 * u, 2 * u + 1, 1.0, 0.0);
+        for v in vs {
+            network.add_arcs(2 * u + 1, 2 * v, unbounded, 0.0);
+        }
+    }
+    network.max_flow(2 * s + 1, 2 * t).round() as usize
+}
+
+/// Vertex connectivity of a graph
+/// # Description
+/// The largest `k` such that the graph has more than `k` vertices and
+/// removing fewer than `k` vertices leaves it connected. By Menger's
+/// theorem it is the smallest number of internally disjoint paths between
+/// two non adjacent vertices, which is computed as a maximum flow in which
+/// every vertex has unit capacity. Only the pairs made of a vertex `v` of
+/// minimum degree and a vertex not adjacent to it, or of two non adjacent
+/// neighbors of `v`, need to be checked. A complete graph on `n` vertices
+/// has vertex connectivity `n - 1`, a disconnected graph 0. Edge
+/// orientation, parallel edges and loops are ignored.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::connectivity::vertex_connectivity;
+/// use std::collections::HashSet;
+///
+/// // two triangles sharing the vertex c
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "c", "a");
+/// let e4 = Edge::empty("e4", EdgeType::Undirected, "c", "d");
+/// let e5 = Edge::empty("e5", EdgeType::Undirected, "d", "e");
+/// let e6 = Edge::empty("e6", EdgeType::Undirected, "e", "c");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3, e4, e5, e6]));
+/// vertex_connectivity(&g) == 1; // true: c is a cut vertex
+/// ```
+///
+/// # References
+/// Diestel R. Graph theory. 2017. Section 3.3.
+///
+/// Esfahanian A, Hakimi S. On computing the connectivities of graphs and
+/// digraphs. 1984.
+pub fn vertex_connectivity<N, E, G>(g: &G) -> usize
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let adjacency = simple_neighbors(g);
+    let mut ids: Vec<&String> = adjacency.keys().copied().collect();
+    ids.sort();
+    let n = ids.len();
+    let index: HashMap<&String, usize> = ids.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+    let neighbors: Vec<Vec<usize>> = ids
+        .iter()
+        .map(|v| {
+            let mut ns: Vec<usize> = adjacency[v].iter().map(|w| index[w]).collect();
+            ns.sort();
+            ns
+        })
+        .collect();
+    let v = match (0..n).min_by_key(|v| neighbors[*v].len()) {
+        None => return 0,
+        Some(v) => v,
+    };
+    // n - 1 for a complete graph, and never more than the minimum degree
+    let mut best = neighbors[v].len();
+    let adjacent = |a: usize, b: usize| neighbors[a].binary_search(&b).is_ok();
+    for w in 0..n {
+        if w != v && !adjacent(v, w) {
+            best = best.min(local_vertex_connectivity(&neighbors, v, w));
+        }
+    }
+    let around: &Vec<usize> = &neighbors[v];
+    for (i, x) in around.iter().enumerate() {
+        for y in &around[i + 1..] {
+            if best == 0 {
+                return 0;
+            }
+            if !adjacent(*x, *y) {
+                best = best.min(local_vertex_connectivity(&neighbors, *x, *y));
+            }
+        }
+    }
+    best
+}
+
+/// Decide whether a graph is k-connected
+/// # Description
+/// A graph is k-connected when it has more than `k` vertices and removing
+/// fewer than `k` vertices leaves it connected, that is when its
+/// [vertex_connectivity] is at least `k`. Every non empty graph is
+/// 0-connected, and the 1-connected graphs are the connected graphs with at
+/// least two vertices.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - k: required connectivity
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::connectivity::is_k_connected;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "c", "a");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// is_k_connected(&g, 2); // true
+/// !is_k_connected(&g, 3); // true: a triangle has 3 vertices
+/// ```
+///
+/// # References
+/// Diestel R. Graph theory. 2017. Section 1.4.
+pub fn is_k_connected<N, E, G>(g: &G, k: usize) -> bool
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    g.order() > k && vertex_connectivity(g) >= k
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::edgetype::EdgeType;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+    use std::collections::HashSet;
+
+    fn mk_uedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Undirected, n1_id, n2_id)
+    }
+
+    fn complete(n: usize) -> Graph<Node, Edge<Node>> {
+        let mut es: HashSet<Edge<Node>> = HashSet::new();
+        for i in 0..n {
+            for j in i + 1..n {
+                es.insert(mk_uedge(
+                    &format!("v{i}"),
+                    &format!("v{j}"),
+                    &format!("e{i}.{j}"),
+                ));
+            }
+        }
+        Graph::from_edgeset(es)
+    }
+
+    /// cube graph, 3-regular and 3-connected
+    fn cube() -> Graph<Node, Edge<Node>> {
+        let mut es: HashSet<Edge<Node>> = HashSet::new();
+        for i in 0..8usize {
+            for b in [1, 2, 4] {
+                let j = i ^ b;
+                if i < j {
+                    es.insert(mk_uedge(
+                        &format!("v{i}"),
+                        &format!("v{j}"),
+                        &format!("e{i}.{j}"),
+                    ));
+                }
+            }
+        }
+        Graph::from_edgeset(es)
+    }
+
+    #[test]
+    fn test_complete_graphs() {
+        for n in 2..6 {
+            let g = complete(n);
+            assert_eq!(vertex_connectivity(&g), n - 1);
+            assert_eq!(edge_connectivity(&g), n - 1);
+            assert!(is_k_connected(&g, n - 1));
+            assert!(!is_k_connected(&g, n));
+        }
+    }
+
+    #[test]
+    fn test_cube() {
+        let g = cube();
+        assert_eq!(vertex_connectivity(&g), 3);
+        assert_eq!(edge_connectivity(&g), 3);
+        assert!(is_k_connected(&g, 3));
+        assert!(!is_k_connected(&g, 4));
+    }
+
+    #[test]
+    fn test_vertex_and_edge_connectivity_differ() {
+        // two triangles sharing the vertex s and joined by a1 -- b1, then
+        // without a1 -- b1 where s becomes a cut vertex but no edge is a
+        // bridge
+        let mut es: HashSet<Edge<Node>> = HashSet::new();
+        for side in ["a", "b"] {
+            let vs = [format!("{side}1"), format!("{side}2"), "s".to_string()];
+            for i in 0..3 {
+                for j in i + 1..3 {
+                    es.insert(mk_uedge(&vs[i], &vs[j], &format!("{side}{i}{j}")));
+                }
+            }
+        }
+        es.insert(mk_uedge("a1", "b1", "x1"));
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(es);
+        assert_eq!(vertex_connectivity(&g), 2);
+        assert_eq!(edge_connectivity(&g), 2);
+        es = g.edges().into_iter().cloned().collect();
+        es.remove(&mk_uedge("a1", "b1", "x1"));
+        let h: Graph<Node, Edge<Node>> = Graph::from_edgeset(es.clone());
+        assert_eq!(vertex_connectivity(&h), 1);
+        assert_eq!(edge_connectivity(&h), 2);
+        // parallel edges and loops change neither
+        es.insert(mk_uedge("s", "a1", "p1"));
+        es.insert(mk_uedge("s", "a2", "p2"));
+        es.insert(mk_uedge("a1", "a2", "p3"));
+        es.insert(mk_uedge("s", "s", "l1"));
+        let k: Graph<Node, Edge<Node>> = Graph::from_edgeset(es);
+        assert_eq!(vertex_connectivity(&k), 1);
+        assert_eq!(edge_connectivity(&k), 2);
+    }
+
+    #[test]
+    fn test_disconnected() {
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([
+            mk_uedge("a", "b", "e1"),
+            mk_uedge("c", "d", "e2"),
+        ]));
+        assert_eq!(vertex_connectivity(&g), 0);
+        assert_eq!(edge_connectivity(&g), 0);
+        assert!(is_k_connected(&g, 0));
+        assert!(!is_k_connected(&g, 1));
+        let empty: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::new());
+        assert_eq!(vertex_connectivity(&empty), 0);
+        assert!(!is_k_connected(&empty, 0));
+    }
+}
@@ -0,0 +1,359 @@
+//! vertex cover and independent set operations
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+/// vertices sorted by identifier and edges as pairs of vertex indices
+fn indexed_graph<'a, N, E, G>(g: &'a G) -> (Vec<&'a N>, Vec<(usize, usize)>)
+where
+    N: NodeTrait,
+    E: EdgeTrait<N> + 'a,
+    G: GraphTrait<N, E>,
+{
+    let vertices = g.vertices_sorted();
+    let index: HashMap<&String, usize> = vertices
+        .iter()
+        .enumerate()
+        .map(|(i, v)| (v.id(), i))
+        .collect();
+    let pairs = g
+        .edges_sorted()
+        .iter()
+        .map(|e| (index[e.start().id()], index[e.end().id()]))
+        .collect();
+    (vertices, pairs)
+}
+
+/// Approximate a minimum vertex cover
+/// # Description
+/// A vertex cover is a vertex set that meets every edge of the graph. We
+/// compute a maximal matching greedily and take both ends of every matched
+/// edge. The resulting cover is at most twice as large as a minimum cover.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - returns: a set of vertices covering every edge of `g`
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::cover::vertex_cover;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "n1", "n2");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "n2", "n3");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+/// let cover = vertex_cover(&g);
+/// cover.len() <= 2; // true
+/// ```
+pub fn vertex_cover<'a, N, E, G>(g: &'a G) -> HashSet<&'a N>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N> + 'a,
+    G: GraphTrait<N, E>,
+{
+    let (vertices, edges) = indexed_graph(g);
+    let mut covered = vec![false; vertices.len()];
+    for (u, v) in edges {
+        if !covered[u] && !covered[v] {
+            covered[u] = true;
+            covered[v] = true;
+        }
+    }
+    vertices
+        .into_iter()
+        .zip(covered)
+        .filter(|(_, c)| *c)
+        .map(|(v, _)| v)
+        .collect()
+}
+
+/// Compute a maximal independent set greedily
+/// # Description
+/// An independent set is a vertex set whose members are pairwise
+/// non-adjacent. We repeatedly pick the vertex with the smallest number of
+/// remaining neighbors and discard its neighbors. Vertices with a loop are
+/// never picked.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - returns: a maximal, not necessarily maximum, independent set
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::cover::maximal_independent_set;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "n1", "n2");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "n2", "n3");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+/// let mis = maximal_independent_set(&g);
+/// mis.len() == 2; // true: n1 and n3
+/// ```
+pub fn maximal_independent_set<'a, N, E, G>(g: &'a G) -> HashSet<&'a N>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N> + 'a,
+    G: GraphTrait<N, E>,
+{
+    let (vertices, edges) = indexed_graph(g);
+    let mut neighbors: Vec<HashSet<usize>> = vec![HashSet::new(); vertices.len()];
+    let mut available = vec![true; vertices.len()];
+    for (u, v) in edges {
+        if u == v {
+            available[u] = false;
+        } else {
+            neighbors[u].insert(v);
+            neighbors[v].insert(u);
+        }
+    }
+    let mut independent = HashSet::new();
+    loop {
+        let pick = (0..vertices.len())
+            .filter(|i| available[*i])
+            .min_by_key(|i| neighbors[*i].iter().filter(|j| available[**j]).count());
+        match pick {
+            None => break,
+            Some(i) => {
+                independent.insert(vertices[i]);
+                available[i] = false;
+                for j in &neighbors[i] {
+                    available[*j] = false;
+                }
+            }
+        }
+    }
+    independent
+}
+
+/// branch on the end vertices of the first uncovered edge
+fn branch_cover(
+    edges: &[(usize, usize)],
+    chosen: &mut Vec<bool>,
+    count: usize,
+    best: &mut (usize, Vec<bool>),
+) {
+    if count >= best.0 {
+        return;
+    }
+    let uncovered = edges.iter().find(|(u, v)| !chosen[*u] && !chosen[*v]);
+    match uncovered {
+        None => {
+            *best = (count, chosen.clone());
+        }
+        Some((u, v)) => {
+            // at least one more vertex is needed
+            if count + 1 >= best.0 {
+                return;
+            }
+            for w in [*u, *v] {
+                chosen[w] = true;
+                branch_cover(edges, chosen, count + 1, best);
+                chosen[w] = false;
+                if u == v {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Compute a minimum vertex cover exactly
+/// # Description
+/// Branch and bound search over the end vertices of uncovered edges. The
+/// search is exponential in the size of the cover, so it should be used on
+/// small graphs only. The 2-approximation of [vertex_cover] serves as the
+/// initial bound.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - returns: a vertex cover of minimum cardinality
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::cover::minimum_vertex_cover;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "n1", "n2");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "n2", "n3");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+/// let cover = minimum_vertex_cover(&g);
+/// cover.len() == 1; // true: n2
+/// ```
+pub fn minimum_vertex_cover<'a, N, E, G>(g: &'a G) -> HashSet<&'a N>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N> + 'a,
+    G: GraphTrait<N, E>,
+{
+    let (vertices, edges) = indexed_graph(g);
+    let approx = vertex_cover(g);
+    let initial: Vec<bool> = vertices.iter().map(|v| approx.contains(v)).collect();
+    let mut best = (approx.len() + 1, initial);
+    let mut chosen = vec![false; vertices.len()];
+    branch_cover(&edges, &mut chosen, 0, &mut best);
+    vertices
+        .into_iter()
+        .zip(best.1)
+        .filter(|(_, c)| *c)
+        .map(|(v, _)| v)
+        .collect()
+}
+
+/// Compute a maximum independent set exactly
+/// # Description
+/// The complement of a minimum vertex cover is a maximum independent set,
+/// so this has the same cost as [minimum_vertex_cover].
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - returns: an independent set of maximum cardinality
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::cover::maximum_independent_set;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "n1", "n2");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "n1", "n3");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "n1", "n4");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// let mis = maximum_independent_set(&g);
+/// mis.len() == 3; // true: n2, n3, n4
+/// ```
+pub fn maximum_independent_set<'a, N, E, G>(g: &'a G) -> HashSet<&'a N>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N> + 'a,
+    G: GraphTrait<N, E>,
+{
+    let cover = minimum_vertex_cover(g);
+    g.vertices()
+        .into_iter()
+        .filter(|v| !cover.contains(v))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::edgetype::EdgeType;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+
+    fn mk_node(n_id: &str) -> Node {
+        Node::empty(n_id)
+    }
+    fn mk_nodes(ns: Vec<&str>) -> HashSet<Node> {
+        let mut hs: HashSet<Node> = HashSet::new();
+        for n in ns {
+            hs.insert(mk_node(n));
+        }
+        hs
+    }
+    fn mk_uedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Undirected, n1_id, n2_id)
+    }
+    fn mk_edges(es: Vec<Edge<Node>>) -> HashSet<Edge<Node>> {
+        let mut hs = HashSet::new();
+        for e in es {
+            hs.insert(e);
+        }
+        hs
+    }
+    /// n1 - n2 - n3 - n4 - n5 with n5 isolated from n6
+    fn mk_g1() -> Graph<Node, Edge<Node>> {
+        let e1 = mk_uedge("n1", "n2", "e1");
+        let e2 = mk_uedge("n2", "n3", "e2");
+        let e3 = mk_uedge("n3", "n4", "e3");
+        let e4 = mk_uedge("n4", "n5", "e4");
+        let nset = mk_nodes(vec!["n1", "n2", "n3", "n4", "n5", "n6"]);
+        let h2 = mk_edges(vec![e1, e2, e3, e4]);
+        Graph::new("g1".to_string(), HashMap::new(), nset, h2)
+    }
+
+    fn is_cover(g: &Graph<Node, Edge<Node>>, cover: &HashSet<&Node>) -> bool {
+        g.edges()
+            .iter()
+            .all(|e| cover.contains(e.start()) || cover.contains(e.end()))
+    }
+
+    fn is_independent(g: &Graph<Node, Edge<Node>>, set: &HashSet<&Node>) -> bool {
+        g.edges()
+            .iter()
+            .all(|e| !(set.contains(e.start()) && set.contains(e.end())))
+    }
+
+    #[test]
+    fn test_vertex_cover() {
+        let g = mk_g1();
+        let cover = vertex_cover(&g);
+        assert!(is_cover(&g, &cover));
+        assert!(cover.len() <= 4);
+    }
+
+    #[test]
+    fn test_maximal_independent_set() {
+        let g = mk_g1();
+        let mis = maximal_independent_set(&g);
+        assert!(is_independent(&g, &mis));
+        // every other vertex has a neighbor inside the set
+        for v in g.vertices() {
+            if !mis.contains(v) {
+                assert!(g.edges().iter().any(|e| {
+                    (e.start() == v && mis.contains(e.end()))
+                        || (e.end() == v && mis.contains(e.start()))
+                }));
+            }
+        }
+    }
+
+    #[test]
+    fn test_minimum_vertex_cover() {
+        let g = mk_g1();
+        let cover = minimum_vertex_cover(&g);
+        assert!(is_cover(&g, &cover));
+        assert_eq!(cover.len(), 2);
+        let ns = mk_nodes(vec!["n2", "n4"]);
+        let comp: HashSet<&Node> = ns.iter().collect();
+        assert_eq!(cover, comp);
+    }
+
+    #[test]
+    fn test_minimum_vertex_cover_self_loop() {
+        let e1 = mk_uedge("n1", "n1", "e1");
+        let e2 = mk_uedge("n1", "n2", "e2");
+        let g = Graph::from_edgeset(mk_edges(vec![e1, e2]));
+        let cover = minimum_vertex_cover(&g);
+        let n1 = mk_node("n1");
+        assert_eq!(cover, HashSet::from([&n1]));
+    }
+
+    #[test]
+    fn test_maximum_independent_set() {
+        let g = mk_g1();
+        let mis = maximum_independent_set(&g);
+        assert!(is_independent(&g, &mis));
+        let ns = mk_nodes(vec!["n1", "n3", "n5", "n6"]);
+        let comp: HashSet<&Node> = ns.iter().collect();
+        assert_eq!(mis, comp);
+    }
+}
@@ -0,0 +1,295 @@
+//! cycle space of graphs
+use crate::graph::ops::graph::certify::Cycle;
+use crate::graph::ops::graph::dynamic::DynamicConnectivity;
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+
+/// Dimension of the cycle space of a graph
+/// # Description
+/// The cycle space is the set of edge sets in which every vertex has even
+/// degree, a vector space over the two element field. Its dimension, the
+/// cyclomatic number, is `m - n + c` for `m` edges, `n` vertices and `c`
+/// connected components, the number of edges left out of a spanning
+/// forest. Edge orientation is ignored, a loop and every parallel edge add
+/// one dimension.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::cycleops::cycle_space_dimension;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "c", "a");
+/// let e4 = Edge::empty("e4", EdgeType::Undirected, "c", "d");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3, e4]));
+/// cycle_space_dimension(&g) == 1; // true
+/// ```
+///
+/// # References
+/// Diestel R. Graph theory. 2017. Section 1.9.
+pub fn cycle_space_dimension<N, E, G>(g: &G) -> usize
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let nb_components = DynamicConnectivity::from_graph(g).nb_components();
+    g.size() + nb_components - g.order()
+}
+
+/// Fundamental cycles of a graph with respect to a spanning forest
+/// # Description
+/// Every edge of `g` outside `spanning_tree` closes a cycle with the path of
+/// the forest between its end vertices. These fundamental cycles form a
+/// basis of the cycle space, see [cycle_space_dimension]. A cycle starts at
+/// the start vertex of its edge outside the forest, follows the forest and
+/// comes back through that edge, which is the last edge of the cycle. A
+/// loop gives a cycle of one vertex. Cycles are sorted by the identifier of
+/// their edge outside the forest. Edge orientation is ignored.
+///
+/// The forest can be any graph made of edges of `g`, such as a
+/// [spanning_tree](crate::graph::ops::graph::treeops::spanning_tree) or a
+/// [minimum_spanning_tree](crate::graph::ops::graph::spanning::minimum_spanning_tree).
+/// Panics if one of its edges is not an edge of `g`, if it has a cycle, or
+/// if it does not connect the end vertices of an edge of `g`.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - spanning_tree: spanning forest of `g`
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::cycleops::fundamental_cycles;
+/// use pgm_rust::graph::ops::graph::treeops::spanning_tree;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "c", "a");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// let t = spanning_tree(&g, &Node::empty("a"));
+/// let cycles = fundamental_cycles(&g, &t);
+/// cycles.len() == 1; // true
+/// cycles[0].edges.len() == 3; // true
+/// ```
+///
+/// # References
+/// Diestel R. Graph theory. 2017. Section 1.9.
+pub fn fundamental_cycles<N, E, G, T>(g: &G, spanning_tree: &T) -> Vec<Cycle>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    T: GraphTrait<N, E>,
+{
+    let g_edges: HashSet<&String> = g.edges().into_iter().map(|e| e.id()).collect();
+    let mut adjacency: HashMap<&String, Vec<(&String, &String)>> = HashMap::new();
+    let mut tree_edges: HashSet<&String> = HashSet::new();
+    for e in spanning_tree.edges_sorted() {
+        if !g_edges.contains(e.id()) {
+            panic!("{e} not in {g}");
+        }
+        let (s, t) = (e.start().id(), e.end().id());
+        adjacency.entry(s).or_default().push((t, e.id()));
+        adjacency.entry(t).or_default().push((s, e.id()));
+        tree_edges.insert(e.id());
+    }
+    // parent, edge to the parent and depth of every vertex of the forest
+    let mut parent: HashMap<&String, (&String, &String)> = HashMap::new();
+    let mut depth: HashMap<&String, usize> = HashMap::new();
+    let mut roots: Vec<&String> = adjacency.keys().copied().collect();
+    roots.sort();
+    for root in roots {
+        if depth.contains_key(root) {
+            continue;
+        }
+        depth.insert(root, 0);
+        let mut queue: VecDeque<&String> = VecDeque::from([root]);
+        while let Some(u) = queue.pop_front() {
+            for (v, k) in &adjacency[u] {
+                if parent.get(u).is_some_and(|(_, pk)| pk == k) {
+                    continue;
+                }
+                if depth.contains_key(v) {
+                    panic!("{spanning_tree} has a cycle through {k}");
+                }
+                depth.insert(v, depth[u] + 1);
+                parent.insert(v, (u, k));
+                queue.push_back(v);
+            }
+        }
+    }
+    let root_of = |v: &String| -> Option<&String> {
+        let mut w = depth.get_key_value(v)?.0;
+        while let Some((p, _)) = parent.get(w) {
+            w = p;
+        }
+        Some(w)
+    };
+    let mut cycles = Vec::new();
+    for e in g.edges_sorted() {
+        if tree_edges.contains(e.id()) {
+            continue;
+        }
+        let (s, t) = (e.start().id(), e.end().id());
+        if s == t {
+            cycles.push(Cycle {
+                vertices: vec![s.clone()],
+                edges: vec![e.id().clone()],
+            });
+            continue;
+        }
+        let connected = match (root_of(s), root_of(t)) {
+            (Some(rs), Some(rt)) => rs == rt,
+            _ => false,
+        };
+        if !connected {
+            panic!("{spanning_tree} does not connect the end vertices of {e}");
+        }
+        // climb from both ends to their lowest common ancestor
+        let (mut a, mut b) = (s, t);
+        let mut up_from_s: Vec<(&String, &String)> = Vec::new();
+        let mut up_from_t: Vec<(&String, &String)> = Vec::new();
+        while a != b {
+            if depth[a] >= depth[b] {
+                let (p, k) = parent[a];
+                up_from_s.push((a, k));
+                a = p;
+            } else {
+                let (p, k) = parent[b];
+                up_from_t.push((b, k));
+                b = p;
+            }
+        }
+        let mut vertices: Vec<String> = up_from_s.iter().map(|(v, _)| (*v).clone()).collect();
+        let mut edges: Vec<String> = up_from_s.iter().map(|(_, k)| (*k).clone()).collect();
+        vertices.push(a.clone());
+        for (v, k) in up_from_t.into_iter().rev() {
+            edges.push(k.clone());
+            vertices.push(v.clone());
+        }
+        edges.push(e.id().clone());
+        cycles.push(Cycle { vertices, edges });
+    }
+    cycles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::ops::graph::spanning::minimum_spanning_tree;
+    use crate::graph::ops::graph::treeops::spanning_tree;
+    use crate::graph::traits::graph_obj::GraphObject;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::edgetype::EdgeType;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+
+    fn mk_uedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Undirected, n1_id, n2_id)
+    }
+
+    /// square a b c d with the chord a c, a parallel edge c d, a loop on b
+    /// and a separate edge x y
+    fn mk_g() -> Graph<Node, Edge<Node>> {
+        Graph::from_edgeset(HashSet::from([
+            mk_uedge("a", "b", "e1"),
+            mk_uedge("b", "c", "e2"),
+            mk_uedge("c", "d", "e3"),
+            mk_uedge("d", "a", "e4"),
+            mk_uedge("a", "c", "e5"),
+            mk_uedge("d", "c", "e6"),
+            mk_uedge("b", "b", "e7"),
+            mk_uedge("x", "y", "e8"),
+        ]))
+    }
+
+    /// every vertex has even degree in the cycle, which is a closed walk
+    fn is_cycle(g: &Graph<Node, Edge<Node>>, c: &Cycle) -> bool {
+        let n = c.vertices.len();
+        c.edges.len() == n
+            && c.edges.iter().enumerate().all(|(i, k)| {
+                let e = g.find_edge(k).unwrap();
+                let (u, v) = (&c.vertices[i], &c.vertices[(i + 1) % n]);
+                (e.start().id() == u && e.end().id() == v)
+                    || (e.start().id() == v && e.end().id() == u)
+            })
+    }
+
+    #[test]
+    fn test_cycle_space_dimension() {
+        let g = mk_g();
+        assert_eq!(cycle_space_dimension(&g), 8 - 6 + 2);
+        let empty: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::new());
+        assert_eq!(cycle_space_dimension(&empty), 0);
+    }
+
+    #[test]
+    fn test_fundamental_cycles() {
+        let g = mk_g();
+        let forest = minimum_spanning_tree(&g, |e: &Edge<Node>| match e.id().as_str() {
+            "e1" | "e2" | "e3" | "e8" => 1.0,
+            _ => 2.0,
+        });
+        let cycles = fundamental_cycles(&g, &forest);
+        assert_eq!(cycles.len(), cycle_space_dimension(&g));
+        assert!(cycles.iter().all(|c| is_cycle(&g, c)));
+        let closing: Vec<&String> = cycles.iter().map(|c| c.edges.last().unwrap()).collect();
+        assert_eq!(closing, vec!["e4", "e5", "e6", "e7"]);
+        assert_eq!(cycles[0].vertices, vec!["d", "c", "b", "a"]);
+        assert_eq!(cycles[0].edges, vec!["e3", "e2", "e1", "e4"]);
+        assert_eq!(cycles[2].vertices, vec!["d", "c"]);
+        assert_eq!(cycles[3].vertices, vec!["b"]);
+    }
+
+    #[test]
+    fn test_fundamental_cycles_of_tree() {
+        // the component of a, a depth first search tree of it
+        let es: HashSet<Edge<Node>> = mk_g()
+            .edges()
+            .into_iter()
+            .filter(|e| e.id() != "e8")
+            .cloned()
+            .collect();
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(es);
+        let t = spanning_tree(&g, &Node::empty("a"));
+        let cycles = fundamental_cycles(&g, &t);
+        assert_eq!(cycles.len(), 4);
+        assert!(cycles.iter().all(|c| is_cycle(&g, c)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_fundamental_cycles_not_spanning() {
+        // the tree of a misses the component x y
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([
+            mk_uedge("a", "b", "e1"),
+            mk_uedge("x", "y", "e8"),
+        ]));
+        let t = spanning_tree(&g, &Node::empty("a"));
+        fundamental_cycles(&g, &t);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_fundamental_cycles_with_cyclic_forest() {
+        let g = mk_g();
+        fundamental_cycles(&g, &g);
+    }
+}
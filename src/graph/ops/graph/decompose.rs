@@ -0,0 +1,191 @@
+//! graph decompositions
+use crate::graph::ops::graph::misc::get_subgraph_by_vertices;
+use crate::graph::ops::graph::order::degeneracy_order;
+use crate::graph::ops::graph::order::simple_neighbors;
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::idgen::IdGenerator;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::idgen::UuidGenerator;
+use crate::graph::types::maps::NodeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+/// Compute the core number of every vertex
+/// # Description
+/// The k-core of a graph is its largest subgraph in which every vertex has
+/// at least `k` neighbors. The core number of a vertex is the largest `k`
+/// such that the vertex belongs to the k-core. Vertices are peeled by
+/// increasing degree as in Batagelj and Zaversnik 2003, see
+/// [degeneracy_order]. Edge orientation is ignored, parallel edges count
+/// as a single neighbor and self loops are not taken into account.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::decompose::core_number;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "c", "a");
+/// let e4 = Edge::empty("e4", EdgeType::Undirected, "c", "d");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3, e4]));
+/// let cores = core_number(&g);
+/// cores["a"] == 2; // true
+/// cores["d"] == 1; // true
+/// ```
+///
+/// # References
+/// Batagelj V, Zaversnik M. An O(m) algorithm for cores decomposition of
+/// networks. 2003.
+pub fn core_number<N, E, G>(g: &G) -> NodeMap<usize>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    // the remaining degree of a vertex when it is peeled is its number of
+    // neighbors later in the degeneracy order
+    let neighbors = simple_neighbors(g);
+    let order = degeneracy_order(g);
+    let position: HashMap<&String, usize> = order.iter().enumerate().map(|(i, v)| (v, i)).collect();
+    let mut cores: NodeMap<usize> = NodeMap::new();
+    let mut current = 0;
+    for (i, v) in order.iter().enumerate() {
+        let later = neighbors[v].iter().filter(|w| position[*w] > i).count();
+        current = current.max(later);
+        cores.insert(v, current);
+    }
+    cores
+}
+
+/// Compute the k-core of a graph
+/// # Description
+/// The k-core is the subgraph induced by the vertices of core number at
+/// least `k`, see [core_number]. It prunes the weakly connected fringe of a
+/// graph. The identifier of the k-core is a random UUID.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - k: smallest number of neighbors of a kept vertex
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+/// use pgm_rust::graph::ops::graph::decompose::k_core;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "c", "a");
+/// let e4 = Edge::empty("e4", EdgeType::Undirected, "c", "d");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3, e4]));
+/// let core: Graph<Node, Edge<Node>> = k_core(&g, 2);
+/// core.order() == 3; // true: d is pruned
+/// ```
+pub fn k_core<N, E, G>(g: &G, k: usize) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    k_core_with(g, k, &mut UuidGenerator)
+}
+
+/// Compute the k-core of a graph, the identifier of the k-core is produced
+/// by `idgen`, see [k_core]
+pub fn k_core_with<N, E, G, I>(g: &G, k: usize, idgen: &mut I) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    I: IdGenerator,
+{
+    let cores = core_number(g);
+    let kept: HashSet<&N> = g
+        .vertices()
+        .into_iter()
+        .filter(|v| cores[v.id()] >= k)
+        .collect();
+    let (nodes, edges) = get_subgraph_by_vertices(g, kept, None::<fn(&E, &HashSet<&N>) -> bool>);
+    let gid = idgen.generate(&nodes, &edges);
+    G::create_from_ref(gid, HashMap::new(), nodes, edges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::edgetype::EdgeType;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+
+    fn mk_uedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Undirected, n1_id, n2_id)
+    }
+
+    /// clique a b c d, a triangle d e f hanging on d, a pendant g, a loop
+    /// on g and an isolated h
+    fn mk_g() -> Graph<Node, Edge<Node>> {
+        let es = HashSet::from([
+            mk_uedge("a", "b", "e1"),
+            mk_uedge("a", "c", "e2"),
+            mk_uedge("a", "d", "e3"),
+            mk_uedge("b", "c", "e4"),
+            mk_uedge("b", "d", "e5"),
+            mk_uedge("c", "d", "e6"),
+            mk_uedge("d", "e", "e7"),
+            mk_uedge("e", "f", "e8"),
+            Edge::empty("e9", EdgeType::Directed, "f", "d"),
+            mk_uedge("f", "g", "e10"),
+            mk_uedge("g", "g", "e11"),
+            mk_uedge("f", "g", "e12"),
+        ]);
+        Graph::from_edge_node_set(es, HashSet::from([Node::empty("h")]))
+    }
+
+    #[test]
+    fn test_core_number() {
+        let cores = core_number(&mk_g());
+        let expected: HashMap<String, usize> = [
+            ("a", 3),
+            ("b", 3),
+            ("c", 3),
+            ("d", 3),
+            ("e", 2),
+            ("f", 2),
+            ("g", 1),
+            ("h", 0),
+        ]
+        .iter()
+        .map(|(v, k)| (v.to_string(), *k))
+        .collect();
+        assert_eq!(cores, expected);
+    }
+
+    #[test]
+    fn test_k_core() {
+        let g = mk_g();
+        let core: Graph<Node, Edge<Node>> = k_core(&g, 2);
+        assert_eq!(core.order(), 6);
+        assert_eq!(core.size(), 9);
+        let core3: Graph<Node, Edge<Node>> = k_core(&g, 3);
+        assert_eq!(core3.order(), 4);
+        assert_eq!(core3.size(), 6);
+        let core0: Graph<Node, Edge<Node>> = k_core(&g, 0);
+        assert_eq!(core0.order(), g.order());
+        let empty: Graph<Node, Edge<Node>> = k_core(&g, 4);
+        assert_eq!(empty.order(), 0);
+    }
+}
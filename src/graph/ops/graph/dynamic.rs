@@ -0,0 +1,342 @@
+//! connectivity of graphs changing over time
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+/// Connected components maintained under edge insertions and deletions
+/// # Description
+/// Vertices are kept in a union find forest with union by size and path
+/// halving, so that inserting an edge and answering [connected] cost
+/// almost constant amortized time. Edge orientation is ignored.
+///
+/// Edges joining two components when they were inserted form a spanning
+/// forest. Deleting any other edge leaves the components unchanged and is
+/// constant time. Deleting a forest edge may split a component: the forest
+/// is then rebuilt from the remaining edges, lazily, at the next query, so
+/// that a burst of deletions costs a single rebuild. Streams that mostly
+/// insert edges stay fast, streams deleting many forest edges between
+/// queries pay `O(E)` per query.
+///
+/// [connected]: DynamicConnectivity::connected
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::ops::graph::dynamic::DynamicConnectivity;
+///
+/// let mut dc = DynamicConnectivity::new();
+/// dc.add_edge("e1", "a", "b");
+/// dc.add_edge("e2", "b", "c");
+/// dc.add_vertex("d");
+/// dc.connected("a", "c"); // true
+/// dc.nb_components() == 2; // true
+/// dc.remove_edge("e2");
+/// !dc.connected("a", "c"); // true
+/// ```
+///
+/// # References
+/// Tarjan R. Efficiency of a good but not linear set union algorithm. 1975.
+#[derive(Debug, Clone, Default)]
+pub struct DynamicConnectivity {
+    /// position of every vertex identifier
+    index: HashMap<String, usize>,
+    /// union find parent of every vertex
+    parent: Vec<usize>,
+    /// number of vertices below every root
+    size: Vec<usize>,
+    /// end vertices of every edge
+    edges: HashMap<String, (usize, usize)>,
+    /// edges of the spanning forest
+    forest: HashSet<String>,
+    nb_components: usize,
+    /// whether a forest edge was deleted since the last rebuild
+    stale: bool,
+}
+
+impl DynamicConnectivity {
+    /// structure without vertices
+    pub fn new() -> DynamicConnectivity {
+        DynamicConnectivity::default()
+    }
+
+    /// structure holding the vertices and edges of `g`
+    pub fn from_graph<N, E, G>(g: &G) -> DynamicConnectivity
+    where
+        N: NodeTrait,
+        E: EdgeTrait<N>,
+        G: GraphTrait<N, E>,
+    {
+        let mut dc = DynamicConnectivity::new();
+        for v in g.vertices_sorted() {
+            dc.add_vertex(v.id());
+        }
+        for e in g.edges_sorted() {
+            dc.add_edge(e.id(), e.start().id(), e.end().id());
+        }
+        dc
+    }
+
+    /// number of vertices
+    pub fn nb_vertices(&self) -> usize {
+        self.parent.len()
+    }
+
+    /// number of edges
+    pub fn nb_edges(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// whether the vertex `id` is present
+    pub fn contains_vertex(&self, id: &str) -> bool {
+        self.index.contains_key(id)
+    }
+
+    /// whether the edge `id` is present
+    pub fn contains_edge(&self, id: &str) -> bool {
+        self.edges.contains_key(id)
+    }
+
+    /// add an isolated vertex, returns false if it was already present
+    pub fn add_vertex(&mut self, id: &str) -> bool {
+        if self.index.contains_key(id) {
+            return false;
+        }
+        let i = self.parent.len();
+        self.index.insert(id.to_string(), i);
+        self.parent.push(i);
+        self.size.push(1);
+        self.nb_components += 1;
+        true
+    }
+
+    /// add the edge `id` between `a` and `b`, adding missing vertices.
+    /// Panics if an edge named `id` is already present.
+    pub fn add_edge(&mut self, id: &str, a: &str, b: &str) {
+        if self.edges.contains_key(id) {
+            panic!("edge {id} is already present");
+        }
+        self.add_vertex(a);
+        self.add_vertex(b);
+        let (i, j) = (self.index[a], self.index[b]);
+        self.edges.insert(id.to_string(), (i, j));
+        // a stale forest is rebuilt from every edge anyway
+        if !self.stale && self.union(i, j) {
+            self.forest.insert(id.to_string());
+        }
+    }
+
+    /// remove the edge `id`, returns false if it was not present
+    pub fn remove_edge(&mut self, id: &str) -> bool {
+        if self.edges.remove(id).is_none() {
+            return false;
+        }
+        if self.forest.remove(id) {
+            self.stale = true;
+        }
+        true
+    }
+
+    /// whether `a` and `b` are in the same component, panics if either
+    /// vertex is not present
+    pub fn connected(&mut self, a: &str, b: &str) -> bool {
+        let i = self.position(a);
+        let j = self.position(b);
+        self.refresh();
+        self.root(i) == self.root(j)
+    }
+
+    /// number of connected components
+    pub fn nb_components(&mut self) -> usize {
+        self.refresh();
+        self.nb_components
+    }
+
+    /// identifiers of the vertices in the component of `a`, sorted. Panics
+    /// if `a` is not present. Costs `O(V)`.
+    pub fn component_of(&mut self, a: &str) -> Vec<String> {
+        let i = self.position(a);
+        self.refresh();
+        let r = self.root(i);
+        let ids: Vec<(String, usize)> = self.index.iter().map(|(v, k)| (v.clone(), *k)).collect();
+        let mut component: Vec<String> = ids
+            .into_iter()
+            .filter(|(_, k)| self.root(*k) == r)
+            .map(|(v, _)| v)
+            .collect();
+        component.sort();
+        component
+    }
+
+    fn position(&self, id: &str) -> usize {
+        match self.index.get(id) {
+            None => panic!("{id} is not a vertex"),
+            Some(i) => *i,
+        }
+    }
+
+    /// root of `i`, with path halving
+    fn root(&mut self, mut i: usize) -> usize {
+        while self.parent[i] != i {
+            self.parent[i] = self.parent[self.parent[i]];
+            i = self.parent[i];
+        }
+        i
+    }
+
+    /// merge the components of `i` and `j`, returns false if they were
+    /// already merged
+    fn union(&mut self, i: usize, j: usize) -> bool {
+        let (ri, rj) = (self.root(i), self.root(j));
+        if ri == rj {
+            return false;
+        }
+        let (small, large) = if self.size[ri] < self.size[rj] {
+            (ri, rj)
+        } else {
+            (rj, ri)
+        };
+        self.parent[small] = large;
+        self.size[large] += self.size[small];
+        self.nb_components -= 1;
+        true
+    }
+
+    /// rebuild the forest from the remaining edges if a forest edge was
+    /// deleted
+    fn refresh(&mut self) {
+        if !self.stale {
+            return;
+        }
+        let n = self.parent.len();
+        self.parent = (0..n).collect();
+        self.size = vec![1; n];
+        self.nb_components = n;
+        self.forest.clear();
+        let mut edges: Vec<(String, (usize, usize))> = self
+            .edges
+            .iter()
+            .map(|(e, ends)| (e.clone(), *ends))
+            .collect();
+        edges.sort();
+        for (e, (i, j)) in edges {
+            if self.union(i, j) {
+                self.forest.insert(e);
+            }
+        }
+        self.stale = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::edgetype::EdgeType;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+    use crate::rng::SplitMix64;
+
+    /// components computed from scratch, as sorted lists of vertices
+    fn components_of(vertices: usize, edges: &HashMap<String, (usize, usize)>) -> Vec<Vec<usize>> {
+        let mut dc = DynamicConnectivity::new();
+        for v in 0..vertices {
+            dc.add_vertex(&v.to_string());
+        }
+        for (e, (i, j)) in edges {
+            dc.add_edge(e, &i.to_string(), &j.to_string());
+        }
+        let mut components: Vec<Vec<usize>> = (0..vertices)
+            .map(|v| {
+                let mut c: Vec<usize> = dc
+                    .component_of(&v.to_string())
+                    .iter()
+                    .map(|s| s.parse().unwrap())
+                    .collect();
+                c.sort();
+                c
+            })
+            .collect();
+        components.sort();
+        components.dedup();
+        components
+    }
+
+    #[test]
+    fn test_dynamic_connectivity_from_graph() {
+        let es = HashSet::from([
+            Edge::empty("e1", EdgeType::Directed, "a", "b"),
+            Edge::empty("e2", EdgeType::Directed, "c", "b"),
+            Edge::empty("e3", EdgeType::Undirected, "d", "e"),
+        ]);
+        let g: Graph<Node, Edge<Node>> =
+            Graph::from_edge_node_set(es, HashSet::from([Node::empty("f")]));
+        let mut dc = DynamicConnectivity::from_graph(&g);
+        assert_eq!(dc.nb_vertices(), 6);
+        assert_eq!(dc.nb_edges(), 3);
+        assert_eq!(dc.nb_components(), 3);
+        assert!(dc.connected("a", "c"));
+        assert!(!dc.connected("a", "d"));
+        assert_eq!(dc.component_of("b"), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_non_forest_deletion_keeps_forest() {
+        let mut dc = DynamicConnectivity::new();
+        dc.add_edge("e1", "a", "b");
+        dc.add_edge("e2", "b", "c");
+        dc.add_edge("e3", "c", "a");
+        dc.add_edge("e4", "a", "b");
+        assert!(dc.remove_edge("e3"));
+        assert!(dc.remove_edge("e4"));
+        assert!(!dc.stale);
+        assert!(!dc.remove_edge("e4"));
+        assert!(dc.remove_edge("e1"));
+        assert!(dc.stale);
+        assert!(!dc.connected("a", "c"));
+        assert!(dc.connected("b", "c"));
+        assert_eq!(dc.nb_components(), 2);
+    }
+
+    #[test]
+    fn test_dynamic_connectivity_random_stream() {
+        let n = 20;
+        let mut rng = SplitMix64::new(5);
+        let mut dc = DynamicConnectivity::new();
+        for v in 0..n {
+            dc.add_vertex(&v.to_string());
+        }
+        let mut edges: HashMap<String, (usize, usize)> = HashMap::new();
+        for step in 0..300 {
+            if edges.is_empty() || rng.gen_range(3) > 0 {
+                let (i, j) = (rng.gen_range(n), rng.gen_range(n));
+                let e = format!("e{step}");
+                dc.add_edge(&e, &i.to_string(), &j.to_string());
+                edges.insert(e, (i, j));
+            } else {
+                let mut ids: Vec<&String> = edges.keys().collect();
+                ids.sort();
+                let e = ids[rng.gen_range(ids.len())].clone();
+                assert!(dc.remove_edge(&e));
+                edges.remove(&e);
+            }
+            if step % 10 == 0 {
+                let expected = components_of(n, &edges);
+                assert_eq!(dc.nb_components(), expected.len());
+                for c in &expected {
+                    for v in c {
+                        assert!(dc.connected(&c[0].to_string(), &v.to_string()));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_dynamic_connectivity_unknown_vertex() {
+        let mut dc = DynamicConnectivity::new();
+        dc.add_vertex("a");
+        dc.connected("a", "b");
+    }
+}
@@ -2,7 +2,6 @@
 //
 use crate::graph::ops::edge::boolops::is_endvertice;
 use crate::graph::ops::graph::boolops::is_in;
-use crate::graph::ops::graph::misc::by_id;
 use crate::graph::traits::edge::Edge as EdgeTrait;
 use crate::graph::traits::graph::Graph as GraphTrait;
 use crate::graph::traits::node::Node as NodeTrait;
@@ -34,7 +33,7 @@ where
 /// # Args
 /// - g something that implements [Graph] trait
 /// - n something that implements [Node] trait
-
+///
 /// # Example
 /// ```
 /// use pgm_rust::graph::traits::edge::Edge as EdgeTrait;
@@ -268,6 +267,10 @@ where
 /// # Args
 /// - g something that implements [Graph] trait
 /// - id a string slice
+///
+/// Every call scans the edge set, use [IndexedGraph] for repeated lookups.
+///
+/// [IndexedGraph]: crate::graph::types::indexed::IndexedGraph
 /// # Example
 /// ```
 /// use pgm_rust::graph::traits::edge::Edge as EdgeTrait;
@@ -305,15 +308,16 @@ where
 /// let e1 = mk_uedge("n1", "n2", "e1");
 /// edge == (&e1); // true
 /// ```
-pub fn edge_by_id<'a, 'b, N, E, G>(g: &'a G, id: &str) -> &'a E
+pub fn edge_by_id<'a, N, E, G>(g: &'a G, id: &str) -> &'a E
 where
     N: NodeTrait,
     E: EdgeTrait<N>,
     G: GraphTrait<N, E>,
 {
-    //
-    let f = |mg: &'a G| -> HashSet<&'a E> { mg.edges() };
-    by_id(g, id, f)
+    match g.find_edge(id) {
+        None => panic!("{id} not contained in {g}"),
+        Some(e) => e,
+    }
 }
 
 #[cfg(test)]
@@ -387,7 +391,7 @@ mod tests {
     fn test_edge_by_id() {
         let g = mk_g1();
         let eid = "e1";
-        let edge = edge_by_id(&g, &eid);
+        let edge = edge_by_id(&g, eid);
         let e1 = mk_uedge("n1", "n2", "e1");
         assert_eq!(&e1, edge);
     }
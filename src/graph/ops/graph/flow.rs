@@ -0,0 +1,536 @@
+//! flows and cuts of graphs
+use crate::graph::ops::graph::coarsen::weight_of;
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::edgetype::EdgeType;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+
+/// residual capacities below this are considered saturated
+const FLOW_EPSILON: f64 = 1e-12;
+
+/// cut of a graph, see [min_st_cut] and [min_cut]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cut {
+    /// total capacity of the cut edges
+    pub value: f64,
+    /// vertex identifiers of one shore of the cut, sorted
+    pub side: Vec<String>,
+    /// identifiers of the edges leaving `side`, sorted. Directed edges
+    /// entering `side` are not cut.
+    pub edges: Vec<String>,
+}
+
+/// residual network over vertex positions. Arcs are stored in pairs, arc
+/// `a ^ 1` being the reverse of arc `a`.
+pub(crate) struct Network {
+    /// head and residual capacity of every arc
+    arcs: Vec<(usize, f64)>,
+    /// arcs leaving every vertex
+    out: Vec<Vec<usize>>,
+}
+
+impl Network {
+    pub(crate) fn new(n: usize) -> Network {
+        Network {
+            arcs: Vec::new(),
+            out: vec![Vec::new(); n],
+        }
+    }
+
+    /// arc `u -> v` of capacity `forward` whose reverse has capacity
+    /// `backward`
+    pub(crate) fn add_arcs(&mut self, u: usize, v: usize, forward: f64, backward: f64) {
+        self.out[u].push(self.arcs.len());
+        self.arcs.push((v, forward));
+        self.out[v].push(self.arcs.len());
+        self.arcs.push((u, backward));
+    }
+
+    /// level of every vertex reachable from `s` in the residual network
+    fn levels(&self, s: usize) -> Vec<Option<usize>> {
+        let mut level = vec![None; self.out.len()];
+        level[s] = Some(0);
+        let mut queue = VecDeque::from([s]);
+        while let Some(u) = queue.pop_front() {
+            for a in &self.out[u] {
+                let (v, cap) = self.arcs[*a];
+                if cap > FLOW_EPSILON && level[v].is_none() {
+                    level[v] = level[u].map(|l| l + 1);
+                    queue.push_back(v);
+                }
+            }
+        }
+        level
+    }
+
+    /// saturate the network from `s` to `t` with Dinic's algorithm and
+    /// return the value of the flow
+    pub(crate) fn max_flow(&mut self, s: usize, t: usize) -> f64 {
+        let mut value = 0.0;
+        loop {
+            let mut level = self.levels(s);
+            if level[t].is_none() {
+                return value;
+            }
+            // blocking flow along the level graph with an explicit stack
+            let mut next = vec![0; self.out.len()];
+            let mut path: Vec<usize> = Vec::new();
+            let mut u = s;
+            loop {
+                if u == t {
+                    let bottleneck = path
+                        .iter()
+                        .map(|a| self.arcs[*a].1)
+                        .fold(f64::INFINITY, f64::min);
+                    for a in &path {
+                        self.arcs[*a].1 -= bottleneck;
+                        self.arcs[*a ^ 1].1 += bottleneck;
+                    }
+                    value += bottleneck;
+                    // retreat to the tail of the first saturated arc
+                    let k = path
+                        .iter()
+                        .position(|a| self.arcs[*a].1 <= FLOW_EPSILON)
+                        .unwrap_or(0);
+                    path.truncate(k);
+                    u = path.last().map_or(s, |a| self.arcs[*a].0);
+                    continue;
+                }
+                let advance = loop {
+                    let Some(a) = self.out[u].get(next[u]).copied() else {
+                        break None;
+                    };
+                    let (v, cap) = self.arcs[a];
+                    if cap > FLOW_EPSILON && level[v] == level[u].map(|l| l + 1) {
+                        break Some(a);
+                    }
+                    next[u] += 1;
+                };
+                match advance {
+                    Some(a) => {
+                        path.push(a);
+                        u = self.arcs[a].0;
+                    }
+                    None => {
+                        // dead end, no augmenting path goes through u
+                        level[u] = None;
+                        match path.pop() {
+                            None => break,
+                            Some(a) => {
+                                u = self.arcs[a ^ 1].0;
+                                next[u] += 1;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// cut of `g` whose shore is `side`, the value summing `capacity` over the
+/// cut edges
+fn cut_of<N, E, G, F>(g: &G, side: HashSet<&String>, capacity: F) -> Cut
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    F: Fn(&E) -> f64,
+{
+    let mut value = 0.0;
+    let mut edges = Vec::new();
+    for e in g.edges_sorted() {
+        let (s, t) = (side.contains(e.start().id()), side.contains(e.end().id()));
+        let leaves = match e.has_type() {
+            EdgeType::Directed => s && !t,
+            EdgeType::Undirected => s != t,
+        };
+        if leaves {
+            value += capacity(e);
+            edges.push(e.id().clone());
+        }
+    }
+    let mut side: Vec<String> = side.into_iter().cloned().collect();
+    side.sort();
+    Cut { value, side, edges }
+}
+
+/// Minimum cut separating two vertices
+/// # Description
+/// A maximum flow from `s` to `t` is computed with Dinic's algorithm.
+/// Directed edges carry flow along their orientation, undirected edges
+/// either way, up to their capacity. By max flow min cut duality, the
+/// vertices reachable from `s` in the residual network form the source side
+/// of a minimum cut and its value is the value of the maximum flow. The
+/// source side is the smallest among the minimum cuts. Loops are ignored.
+/// Panics if `s` or `t` is not a vertex of `g`, if they are the same vertex
+/// or if a capacity is negative.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - s: source vertex
+/// - t: sink vertex
+/// - capacity: capacity of an edge
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph_obj::GraphObject;
+/// use pgm_rust::graph::ops::graph::flow::min_st_cut;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Directed, "s", "a");
+/// let e2 = Edge::empty("e2", EdgeType::Directed, "a", "t");
+/// let e3 = Edge::empty("e3", EdgeType::Directed, "s", "t");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// let capacity = |e: &Edge<Node>| if e.id() == "e2" { 2.0 } else { 3.0 };
+/// let cut = min_st_cut(&g, &Node::empty("s"), &Node::empty("t"), capacity);
+/// cut.value == 5.0; // true
+/// cut.edges == vec!["e2", "e3"]; // true
+/// ```
+///
+/// # References
+/// Dinic E. Algorithm for solution of a problem of maximum flow in a
+/// network with power estimation. 1970.
+pub fn min_st_cut<N, E, G, F>(g: &G, s: &N, t: &N, capacity: F) -> Cut
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    F: Fn(&E) -> f64,
+{
+    for v in [s, t] {
+        if !g.contains_node_id(v.id()) {
+            panic!("{v} not in {g}");
+        }
+    }
+    if s.id() == t.id() {
+        panic!("{s} can not be separated from itself");
+    }
+    let ids: Vec<&String> = g.vertices_sorted().into_iter().map(|v| v.id()).collect();
+    let index: HashMap<&String, usize> = ids.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+    let mut network = Network::new(ids.len());
+    for e in g.edges_sorted() {
+        let c = capacity(e);
+        if c < 0.0 {
+            panic!("{e} has a negative capacity {c}");
+        }
+        let (u, v) = (index[e.start().id()], index[e.end().id()]);
+        if u == v {
+            continue;
+        }
+        match e.has_type() {
+            EdgeType::Directed => network.add_arcs(u, v, c, 0.0),
+            EdgeType::Undirected => network.add_arcs(u, v, c, c),
+        }
+    }
+    let (si, ti) = (index[s.id()], index[t.id()]);
+    network.max_flow(si, ti);
+    let side: HashSet<&String> = network
+        .levels(si)
+        .into_iter()
+        .enumerate()
+        .filter(|(_, l)| l.is_some())
+        .map(|(i, _)| ids[i])
+        .collect();
+    cut_of(g, side, capacity)
+}
+
+/// a vertex with its connection to the vertices added in a phase of
+/// [min_cut], ordered by connection
+struct Candidate {
+    connection: f64,
+    vertex: usize,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // stronger connections first, then smaller vertices
+        self.connection
+            .total_cmp(&other.connection)
+            .then_with(|| other.vertex.cmp(&self.vertex))
+    }
+}
+
+/// Global minimum cut of a graph
+/// # Description
+/// The cut of smallest total weight splitting the vertices into two non
+/// empty shores, found with the Stoer-Wagner algorithm. Each phase orders
+/// the vertices by maximum adjacency, the last two vertices of the order
+/// give a candidate cut and are merged, so that `n - 1` phases cover every
+/// cut. Edge weights are read with [weight_of], a missing weight counting
+/// as 1, so that the value of the cut of an unweighted graph is its edge
+/// connectivity. Edge orientation is ignored and loops are dropped. The
+/// shore holds the smallest vertex identifier and a disconnected graph has a
+/// cut of value 0. `None` if `g` has fewer than two vertices. Panics if a
+/// weight is negative.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::flow::min_cut;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "c", "a");
+/// let e4 = Edge::empty("e4", EdgeType::Undirected, "c", "d");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3, e4]));
+/// let cut = min_cut(&g).unwrap();
+/// cut.value == 1.0; // true
+/// cut.edges == vec!["e4"]; // true
+/// ```
+///
+/// # References
+/// Stoer M, Wagner F. A simple min-cut algorithm. 1997.
+pub fn min_cut<N, E, G>(g: &G) -> Option<Cut>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let ids: Vec<&String> = g.vertices_sorted().into_iter().map(|v| v.id()).collect();
+    let n = ids.len();
+    if n < 2 {
+        return None;
+    }
+    let index: HashMap<&String, usize> = ids.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+    let mut adjacency: Vec<HashMap<usize, f64>> = vec![HashMap::new(); n];
+    for e in g.edges() {
+        let w = weight_of(e);
+        if w < 0.0 {
+            panic!("{e} has a negative weight {w}");
+        }
+        let (u, v) = (index[e.start().id()], index[e.end().id()]);
+        if u != v {
+            *adjacency[u].entry(v).or_default() += w;
+            *adjacency[v].entry(u).or_default() += w;
+        }
+    }
+    // vertices merged into every remaining vertex
+    let mut members: Vec<Vec<usize>> = (0..n).map(|v| vec![v]).collect();
+    let mut active: Vec<usize> = (0..n).collect();
+    let mut best: Option<(f64, Vec<usize>)> = None;
+    while active.len() > 1 {
+        let mut connection: HashMap<usize, f64> = active.iter().map(|v| (*v, 0.0)).collect();
+        let mut heap: BinaryHeap<Candidate> = active
+            .iter()
+            .map(|v| Candidate {
+                connection: 0.0,
+                vertex: *v,
+            })
+            .collect();
+        let mut added: HashSet<usize> = HashSet::new();
+        let (mut previous, mut last) = (None, None);
+        while let Some(Candidate {
+            connection: c,
+            vertex: u,
+        }) = heap.pop()
+        {
+            if added.contains(&u) || c != connection[&u] {
+                continue;
+            }
+            added.insert(u);
+            previous = last;
+            last = Some((u, c));
+            for (v, w) in &adjacency[u] {
+                if !added.contains(v) {
+                    let cv = connection.get_mut(v).unwrap();
+                    *cv += w;
+                    heap.push(Candidate {
+                        connection: *cv,
+                        vertex: *v,
+                    });
+                }
+            }
+        }
+        let (s, _) = previous.unwrap();
+        let (t, cut_of_phase) = last.unwrap();
+        if best.as_ref().is_none_or(|(b, _)| cut_of_phase < *b) {
+            best = Some((cut_of_phase, members[t].clone()));
+        }
+        // merge t into s
+        let t_adjacency = std::mem::take(&mut adjacency[t]);
+        for (v, w) in t_adjacency {
+            adjacency[v].remove(&t);
+            if v != s {
+                *adjacency[s].entry(v).or_default() += w;
+                *adjacency[v].entry(s).or_default() += w;
+            }
+        }
+        let t_members = std::mem::take(&mut members[t]);
+        members[s].extend(t_members);
+        active.retain(|v| *v != t);
+    }
+    let (_, shore) = best.unwrap();
+    // the shore holding the smallest identifier, which is at position 0
+    let inside: HashSet<usize> = shore.into_iter().collect();
+    let side: HashSet<&String> = (0..n)
+        .filter(|v| inside.contains(v) == inside.contains(&0))
+        .map(|v| ids[v])
+        .collect();
+    Some(cut_of(g, side, weight_of))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+
+    fn mk_wedge(n1_id: &str, n2_id: &str, e_id: &str, w: f64, t: EdgeType) -> Edge<Node> {
+        Edge::new(
+            e_id.to_string(),
+            HashMap::from([("weight".to_string(), vec![w.to_string()])]),
+            Node::empty(n1_id),
+            Node::empty(n2_id),
+            t,
+        )
+    }
+
+    /// the network of Cormen et al., maximum flow 23 from s to t
+    fn mk_network() -> Graph<Node, Edge<Node>> {
+        let d = EdgeType::Directed;
+        Graph::from_edgeset(HashSet::from([
+            mk_wedge("s", "v1", "e01", 16.0, d.clone()),
+            mk_wedge("s", "v2", "e02", 13.0, d.clone()),
+            mk_wedge("v2", "v1", "e03", 4.0, d.clone()),
+            mk_wedge("v1", "v3", "e04", 12.0, d.clone()),
+            mk_wedge("v3", "v2", "e05", 9.0, d.clone()),
+            mk_wedge("v2", "v4", "e06", 14.0, d.clone()),
+            mk_wedge("v4", "v3", "e07", 7.0, d.clone()),
+            mk_wedge("v3", "t", "e08", 20.0, d.clone()),
+            mk_wedge("v4", "t", "e09", 4.0, d),
+        ]))
+    }
+
+    /// the graph of Stoer and Wagner, minimum cut 4 around 3 4 7 8
+    fn mk_stoer_wagner() -> Graph<Node, Edge<Node>> {
+        let u = EdgeType::Undirected;
+        let es = [
+            ("1", "2", 2.0),
+            ("1", "5", 3.0),
+            ("2", "3", 3.0),
+            ("2", "5", 2.0),
+            ("2", "6", 2.0),
+            ("3", "4", 4.0),
+            ("3", "7", 2.0),
+            ("4", "7", 2.0),
+            ("4", "8", 2.0),
+            ("5", "6", 3.0),
+            ("6", "7", 1.0),
+            ("7", "8", 3.0),
+        ];
+        Graph::from_edgeset(
+            es.iter()
+                .enumerate()
+                .map(|(i, (a, b, w))| mk_wedge(a, b, &format!("e{i:02}"), *w, u.clone()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_min_st_cut() {
+        let g = mk_network();
+        let cut = min_st_cut(&g, &Node::empty("s"), &Node::empty("t"), weight_of);
+        assert_eq!(cut.value, 23.0);
+        assert_eq!(cut.side, vec!["s", "v1", "v2", "v4"]);
+        assert_eq!(cut.edges, vec!["e04", "e07", "e09"]);
+        let back = min_st_cut(&g, &Node::empty("t"), &Node::empty("s"), weight_of);
+        assert_eq!(back.value, 0.0);
+        assert_eq!(back.side, vec!["t"]);
+    }
+
+    #[test]
+    fn test_min_st_cut_undirected() {
+        let g = mk_stoer_wagner();
+        let cut = min_st_cut(&g, &Node::empty("1"), &Node::empty("8"), weight_of);
+        assert_eq!(cut.value, 4.0);
+        assert_eq!(cut.side, vec!["1", "2", "5", "6"]);
+        let unit = min_st_cut(&g, &Node::empty("1"), &Node::empty("8"), |_| 1.0);
+        assert_eq!(unit.value, 2.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_min_st_cut_same_vertex() {
+        let g = mk_network();
+        min_st_cut(&g, &Node::empty("s"), &Node::empty("s"), weight_of);
+    }
+
+    #[test]
+    fn test_min_cut() {
+        let g = mk_stoer_wagner();
+        let cut = min_cut(&g).unwrap();
+        assert_eq!(cut.value, 4.0);
+        assert_eq!(cut.side, vec!["1", "2", "5", "6"]);
+        assert_eq!(cut.edges.len(), 2);
+        let t = min_st_cut(&g, &Node::empty("1"), &Node::empty("3"), weight_of);
+        assert!(t.value >= cut.value);
+    }
+
+    #[test]
+    fn test_min_cut_small_graphs() {
+        let single: Graph<Node, Edge<Node>> =
+            Graph::from_edge_node_set(HashSet::new(), HashSet::from([Node::empty("a")]));
+        assert!(min_cut(&single).is_none());
+        let u = EdgeType::Undirected;
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([
+            mk_wedge("a", "b", "e1", 1.0, u.clone()),
+            mk_wedge("c", "d", "e2", 1.0, u.clone()),
+            mk_wedge("c", "c", "e3", 5.0, u),
+        ]));
+        let cut = min_cut(&g).unwrap();
+        assert_eq!(cut.value, 0.0);
+        assert_eq!(cut.side, vec!["a", "b"]);
+        assert!(cut.edges.is_empty());
+    }
+
+    #[test]
+    fn test_min_cut_edge_connectivity() {
+        // a cycle of 6 vertices with unit weights has edge connectivity 2
+        let es: HashSet<Edge<Node>> = (0..6)
+            .map(|i| {
+                Edge::empty(
+                    &format!("e{i}"),
+                    EdgeType::Undirected,
+                    &format!("v{i}"),
+                    &format!("v{}", (i + 1) % 6),
+                )
+            })
+            .collect();
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(es);
+        let cut = min_cut(&g).unwrap();
+        assert_eq!(cut.value, 2.0);
+        assert!(cut.side.contains(&"v0".to_string()));
+    }
+}
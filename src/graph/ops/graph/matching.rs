@@ -0,0 +1,237 @@
+//! matching algorithms
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+
+/// layer the free left vertices and the vertices reachable from them through
+/// alternating paths. Returns true if some free right vertex is reachable.
+fn bfs_layers(
+    adj: &[Vec<(usize, usize)>],
+    match_left: &[Option<usize>],
+    match_right: &[Option<usize>],
+    dist: &mut [usize],
+) -> bool {
+    let mut queue = VecDeque::new();
+    for (u, m) in match_left.iter().enumerate() {
+        if m.is_none() {
+            dist[u] = 0;
+            queue.push_back(u);
+        } else {
+            dist[u] = usize::MAX;
+        }
+    }
+    let mut found = false;
+    while let Some(u) = queue.pop_front() {
+        for (v, _) in &adj[u] {
+            match match_right[*v] {
+                None => found = true,
+                Some(w) => {
+                    if dist[w] == usize::MAX {
+                        dist[w] = dist[u] + 1;
+                        queue.push_back(w);
+                    }
+                }
+            }
+        }
+    }
+    found
+}
+
+/// search an augmenting path starting from left vertex `u` along the layers
+fn dfs_augment(
+    u: usize,
+    adj: &[Vec<(usize, usize)>],
+    match_left: &mut [Option<usize>],
+    match_right: &mut [Option<usize>],
+    match_edge: &mut [Option<usize>],
+    dist: &mut [usize],
+) -> bool {
+    for (v, e) in &adj[u] {
+        let advance = match match_right[*v] {
+            None => true,
+            Some(w) => {
+                dist[w] == dist[u] + 1
+                    && dfs_augment(w, adj, match_left, match_right, match_edge, dist)
+            }
+        };
+        if advance {
+            match_left[u] = Some(*v);
+            match_right[*v] = Some(u);
+            match_edge[u] = Some(*e);
+            return true;
+        }
+    }
+    dist[u] = usize::MAX;
+    false
+}
+
+/// Compute a maximum matching of a bipartite graph
+/// # Description
+/// A matching is a set of independent edges, see Diestel 2017, p. 33. We use
+/// the Hopcroft-Karp algorithm which repeatedly augments the matching along
+/// a maximal set of shortest vertex disjoint augmenting paths. It runs in
+/// `O(E sqrt(V))`. Edge orientation is ignored and edges that do not join
+/// `left` to `right` are not considered.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - left: first vertex class of the bipartition
+/// - right: second vertex class of the bipartition
+/// - returns: the set of matched edges
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::matching::maximum_bipartite_matching;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a1", "b1");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "a1", "b2");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "a2", "b1");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// let (a1, a2) = (Node::empty("a1"), Node::empty("a2"));
+/// let (b1, b2) = (Node::empty("b1"), Node::empty("b2"));
+/// let m = maximum_bipartite_matching(&g, &HashSet::from([&a1, &a2]), &HashSet::from([&b1, &b2]));
+/// m.len() == 2; // true: e2 and e3
+/// ```
+///
+/// # References
+/// Hopcroft J, Karp R. An n^{5/2} algorithm for maximum matchings in
+/// bipartite graphs. 1973.
+pub fn maximum_bipartite_matching<'a, N, E, G>(
+    g: &'a G,
+    left: &HashSet<&N>,
+    right: &HashSet<&N>,
+) -> HashSet<&'a E>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N> + 'a,
+    G: GraphTrait<N, E>,
+{
+    for n in left {
+        if right.contains(n) {
+            panic!("{n} belongs to both vertex classes");
+        }
+    }
+    let mut lefts: Vec<&String> = left.iter().map(|n| n.id()).collect();
+    lefts.sort();
+    let mut rights: Vec<&String> = right.iter().map(|n| n.id()).collect();
+    rights.sort();
+    let lindex: HashMap<&String, usize> = lefts.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+    let rindex: HashMap<&String, usize> = rights.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+
+    let edges = g.edges_sorted();
+    let mut adj: Vec<Vec<(usize, usize)>> = vec![Vec::new(); lefts.len()];
+    for (i, e) in edges.iter().enumerate() {
+        let sid = e.start().id();
+        let eid = e.end().id();
+        if let (Some(u), Some(v)) = (lindex.get(sid), rindex.get(eid)) {
+            adj[*u].push((*v, i));
+        } else if let (Some(u), Some(v)) = (lindex.get(eid), rindex.get(sid)) {
+            adj[*u].push((*v, i));
+        }
+    }
+
+    let mut match_left: Vec<Option<usize>> = vec![None; lefts.len()];
+    let mut match_right: Vec<Option<usize>> = vec![None; rights.len()];
+    let mut match_edge: Vec<Option<usize>> = vec![None; lefts.len()];
+    let mut dist = vec![usize::MAX; lefts.len()];
+    while bfs_layers(&adj, &match_left, &match_right, &mut dist) {
+        for u in 0..lefts.len() {
+            if match_left[u].is_none() {
+                dfs_augment(
+                    u,
+                    &adj,
+                    &mut match_left,
+                    &mut match_right,
+                    &mut match_edge,
+                    &mut dist,
+                );
+            }
+        }
+    }
+    match_edge.into_iter().flatten().map(|i| edges[i]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::traits::graph_obj::GraphObject;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::edgetype::EdgeType;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+
+    fn mk_nodes(ns: Vec<&str>) -> HashSet<Node> {
+        let mut hs: HashSet<Node> = HashSet::new();
+        for n in ns {
+            hs.insert(Node::empty(n));
+        }
+        hs
+    }
+    fn mk_uedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Undirected, n1_id, n2_id)
+    }
+    fn mk_edges(es: Vec<Edge<Node>>) -> HashSet<Edge<Node>> {
+        let mut hs = HashSet::new();
+        for e in es {
+            hs.insert(e);
+        }
+        hs
+    }
+
+    /// a greedy matching picking e1 first blocks the perfect matching
+    fn mk_g1() -> Graph<Node, Edge<Node>> {
+        let e1 = mk_uedge("a1", "b1", "e1");
+        let e2 = mk_uedge("a1", "b2", "e2");
+        let e3 = mk_uedge("b1", "a2", "e3");
+        let e4 = mk_uedge("a3", "b2", "e4");
+        let e5 = mk_uedge("a3", "b3", "e5");
+        let e6 = mk_uedge("a1", "a2", "e6");
+        Graph::from_edgeset(mk_edges(vec![e1, e2, e3, e4, e5, e6]))
+    }
+
+    #[test]
+    fn test_maximum_bipartite_matching() {
+        let g = mk_g1();
+        let ls = mk_nodes(vec!["a1", "a2", "a3"]);
+        let rs = mk_nodes(vec!["b1", "b2", "b3"]);
+        let left: HashSet<&Node> = ls.iter().collect();
+        let right: HashSet<&Node> = rs.iter().collect();
+        let m = maximum_bipartite_matching(&g, &left, &right);
+        assert_eq!(m.len(), 3);
+        let mut ids: Vec<&String> = m.iter().map(|e| e.id()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["e2", "e3", "e5"]);
+    }
+
+    #[test]
+    fn test_maximum_bipartite_matching_unbalanced() {
+        let e1 = mk_uedge("a1", "b1", "e1");
+        let e2 = mk_uedge("a2", "b1", "e2");
+        let g = Graph::from_edgeset(mk_edges(vec![e1, e2]));
+        let ls = mk_nodes(vec!["a1", "a2"]);
+        let rs = mk_nodes(vec!["b1"]);
+        let left: HashSet<&Node> = ls.iter().collect();
+        let right: HashSet<&Node> = rs.iter().collect();
+        let m = maximum_bipartite_matching(&g, &left, &right);
+        assert_eq!(m.len(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_maximum_bipartite_matching_overlap() {
+        let g = mk_g1();
+        let ls = mk_nodes(vec!["a1", "b1"]);
+        let rs = mk_nodes(vec!["b1"]);
+        let left: HashSet<&Node> = ls.iter().collect();
+        let right: HashSet<&Node> = rs.iter().collect();
+        maximum_bipartite_matching(&g, &left, &right);
+    }
+}
@@ -0,0 +1,582 @@
+//! numeric matrix representations of graphs
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::idgen::IdGenerator;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::edgetype::EdgeType;
+use crate::graph::types::idgen::UuidGenerator;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+/// resolve the row order of a matrix: the given order after checking that it
+/// is a permutation of the vertex identifiers, or the identifiers sorted
+fn resolve_node_order<N, E, G>(g: &G, node_order: Option<&[String]>) -> Vec<String>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let ids: HashSet<&String> = g.vertices().into_iter().map(|v| v.id()).collect();
+    match node_order {
+        None => {
+            let mut order: Vec<String> = ids.into_iter().cloned().collect();
+            order.sort();
+            order
+        }
+        Some(order) => {
+            let given: HashSet<&String> = order.iter().collect();
+            if given.len() != order.len() || given != ids {
+                panic!("node order is not a permutation of the vertices of {g}");
+            }
+            order.to_vec()
+        }
+    }
+}
+
+/// Compute the dense adjacency matrix of a graph
+/// # Description
+/// Entry `(i, j)` counts the edges going from the i-th vertex to the j-th
+/// vertex of the returned order. Undirected edges are counted in both
+/// directions, so the matrix of an undirected graph is symmetric. A loop
+/// contributes once to its diagonal entry. Unlike [to_adjmat] the result can
+/// be handed over directly to linear algebra routines.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - node_order: row and column order given as vertex identifiers. If it is
+///   `None` vertices are ordered by their identifiers. It must contain every
+///   vertex exactly once.
+/// - returns: the node order used and the matrix
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::matrixops::to_dense_matrix;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "n1", "n2");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1]));
+/// let (order, mat) = to_dense_matrix(&g, None);
+/// order == vec!["n1", "n2"]; // true
+/// mat == vec![vec![0.0, 1.0], vec![1.0, 0.0]]; // true
+/// ```
+///
+/// [to_adjmat]: crate::graph::ops::graph::misc::to_adjmat
+pub fn to_dense_matrix<N, E, G>(
+    g: &G,
+    node_order: Option<&[String]>,
+) -> (Vec<String>, Vec<Vec<f64>>)
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let order = resolve_node_order(g, node_order);
+    let index: HashMap<&String, usize> = order.iter().enumerate().map(|(i, n)| (n, i)).collect();
+    let mut mat = vec![vec![0.0; order.len()]; order.len()];
+    for e in g.edges() {
+        let i = index[e.start().id()];
+        let j = index[e.end().id()];
+        mat[i][j] += 1.0;
+        if i != j && matches!(e.has_type(), EdgeType::Undirected) {
+            mat[j][i] += 1.0;
+        }
+    }
+    (order, mat)
+}
+
+/// Compute the laplacian matrix of a graph
+/// # Description
+/// The laplacian is `D - A` where `A` is the matrix of [to_dense_matrix] and
+/// `D` is the diagonal matrix of the row sums of `A`. For undirected graphs
+/// row sums are the degrees (loops excluded) and the matrix is symmetric
+/// positive semidefinite. For directed graphs the out-degree laplacian is
+/// obtained.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - node_order: row and column order, see [to_dense_matrix]
+/// - returns: the node order used and the matrix
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::matrixops::laplacian_matrix;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "n1", "n2");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1]));
+/// let (_, lap) = laplacian_matrix(&g, None);
+/// lap == vec![vec![1.0, -1.0], vec![-1.0, 1.0]]; // true
+/// ```
+///
+/// # References
+/// Chung F. Spectral Graph Theory. 1997.
+pub fn laplacian_matrix<N, E, G>(
+    g: &G,
+    node_order: Option<&[String]>,
+) -> (Vec<String>, Vec<Vec<f64>>)
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let (order, adj) = to_dense_matrix(g, node_order);
+    let mut lap = adj;
+    for (i, row) in lap.iter_mut().enumerate() {
+        let degree: f64 = row.iter().sum();
+        for a in row.iter_mut() {
+            *a = -*a;
+        }
+        row[i] += degree;
+    }
+    (order, lap)
+}
+
+/// Compute the incidence matrix of a graph
+/// # Description
+/// Rows are vertices and columns are edges, both ordered by identifier. For
+/// an undirected edge the entries of its two end vertices are `1`, a loop
+/// has a single `2` entry. For a directed edge the start vertex has `-1` and
+/// the end vertex has `1`, so that a directed loop gives a zero column.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - returns: the vertex identifiers, the edge identifiers and the matrix
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::matrixops::to_incidence_matrix;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Directed, "n1", "n2");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1]));
+/// let (nodes, edges, mat) = to_incidence_matrix(&g);
+/// mat == vec![vec![-1.0], vec![1.0]]; // true
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn to_incidence_matrix<N, E, G>(g: &G) -> (Vec<String>, Vec<String>, Vec<Vec<f64>>)
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let node_ids = resolve_node_order(g, None);
+    let index: HashMap<&String, usize> = node_ids.iter().enumerate().map(|(i, n)| (n, i)).collect();
+    let edges = g.edges_sorted();
+    let mut mat = vec![vec![0.0; edges.len()]; node_ids.len()];
+    for (j, e) in edges.iter().enumerate() {
+        let s = index[e.start().id()];
+        let t = index[e.end().id()];
+        match e.has_type() {
+            EdgeType::Directed => {
+                mat[s][j] -= 1.0;
+                mat[t][j] += 1.0;
+            }
+            EdgeType::Undirected => {
+                mat[s][j] += 1.0;
+                mat[t][j] += 1.0;
+            }
+        }
+    }
+    let edge_ids = edges.into_iter().map(|e| e.id().clone()).collect();
+    (node_ids, edge_ids, mat)
+}
+
+/// Build a graph from its incidence matrix
+/// # Description
+/// Inverse of [to_incidence_matrix]. Every column must either have a `-1`
+/// and a `1` entry, which gives a directed edge, two `1` entries or a single
+/// `2` entry, which give an undirected edge or loop. Vertices and edges have
+/// empty data, vertices without incident edges are kept.
+///
+/// # Args
+/// - rows: the matrix, one row per vertex and one column per edge
+/// - node_ids: vertex identifiers in row order
+/// - edge_ids: edge identifiers in column order
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+/// use pgm_rust::graph::ops::graph::matrixops::from_incidence_matrix;
+///
+/// let rows = vec![vec![-1.0], vec![1.0], vec![0.0]];
+/// let nodes = vec!["n1".to_string(), "n2".to_string(), "n3".to_string()];
+/// let edges = vec!["e1".to_string()];
+/// let g: Graph<Node, Edge<Node>> = from_incidence_matrix(&rows, &nodes, &edges);
+/// g.order() == 3; // true
+/// ```
+pub fn from_incidence_matrix<N, E, G>(
+    rows: &[Vec<f64>],
+    node_ids: &[String],
+    edge_ids: &[String],
+) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    from_incidence_matrix_with(rows, node_ids, edge_ids, &mut UuidGenerator)
+}
+
+/// Build a graph from its incidence matrix, the identifier of the graph is
+/// produced by `idgen`, see [from_incidence_matrix]
+pub fn from_incidence_matrix_with<N, E, G, I>(
+    rows: &[Vec<f64>],
+    node_ids: &[String],
+    edge_ids: &[String],
+    idgen: &mut I,
+) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    I: IdGenerator,
+{
+    if rows.len() != node_ids.len() {
+        panic!("{} rows for {} vertices", rows.len(), node_ids.len());
+    }
+    let nodes: Vec<N> = node_ids
+        .iter()
+        .map(|n| N::create(n.clone(), HashMap::new()))
+        .collect();
+    let mut edges: HashSet<E> = HashSet::new();
+    for (j, eid) in edge_ids.iter().enumerate() {
+        let mut column: Vec<(usize, f64)> = Vec::new();
+        for (i, row) in rows.iter().enumerate() {
+            if row.len() != edge_ids.len() {
+                panic!(
+                    "row {} has {} entries for {} edges",
+                    i,
+                    row.len(),
+                    edge_ids.len()
+                );
+            }
+            if row[j] != 0.0 {
+                column.push((i, row[j]));
+            }
+        }
+        let (s, t, edge_type) = match column[..] {
+            [(i, -1.0), (k, 1.0)] => (i, k, EdgeType::Directed),
+            [(i, 1.0), (k, -1.0)] => (k, i, EdgeType::Directed),
+            [(i, 1.0), (k, 1.0)] => (i, k, EdgeType::Undirected),
+            [(i, 2.0)] => (i, i, EdgeType::Undirected),
+            _ => panic!("column of {eid} does not describe an edge"),
+        };
+        edges.insert(E::create(
+            eid.clone(),
+            HashMap::new(),
+            nodes[s].clone(),
+            nodes[t].clone(),
+            edge_type,
+        ));
+    }
+    let nodes: HashSet<N> = nodes.into_iter().collect();
+    let gid = idgen.generate(&nodes.iter().collect(), &edges.iter().collect());
+    G::create(gid, HashMap::new(), nodes, edges)
+}
+
+/// how [node_feature_matrix] fills the entry of a vertex lacking a key
+#[derive(Debug, Clone, PartialEq)]
+pub enum MissingFeature {
+    /// panic naming the vertex and the key
+    Panic,
+    /// use the given value
+    Fill(f64),
+    /// use the mean of the values present in the same column, or zero if
+    /// no vertex has the key
+    ColumnMean,
+}
+
+/// Extract a dense feature matrix from the data of the vertices
+/// # Description
+/// Row `i` holds the features of the i-th vertex of the returned order and
+/// column `j` the value stored under `keys[j]`. The matrix is flattened in
+/// row-major order, so entry `(i, j)` is at index `i * keys.len() + j`, the
+/// layout expected by most array and tensor crates. A feature is the first
+/// value stored under its key parsed as a float. Vertices without the key,
+/// or with an empty value list, are handled according to `missing`. A value
+/// that is not a number panics.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - keys: data keys of the features, one column each
+/// - node_order: row order, see [to_dense_matrix]
+/// - missing: policy for absent features
+/// - returns: the node order used and the flattened matrix
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::ops::graph::matrixops::{node_feature_matrix, MissingFeature};
+/// use std::collections::{HashMap, HashSet};
+///
+/// let n1 = Node::new(
+///     "n1".to_string(),
+///     HashMap::from([
+///         ("x".to_string(), vec!["1.5".to_string()]),
+///         ("y".to_string(), vec!["2".to_string()]),
+///     ]),
+/// );
+/// let n2 = Node::new(
+///     "n2".to_string(),
+///     HashMap::from([("x".to_string(), vec!["3".to_string()])]),
+/// );
+/// let g: Graph<Node, Edge<Node>> =
+///     Graph::from_edge_node_set(HashSet::new(), HashSet::from([n1, n2]));
+/// let (order, feats) = node_feature_matrix(&g, &["x", "y"], None, &MissingFeature::Fill(0.0));
+/// order == vec!["n1", "n2"]; // true
+/// feats == vec![1.5, 2.0, 3.0, 0.0]; // true
+/// ```
+///
+/// # References
+/// Hamilton W. Graph Representation Learning. 2020.
+pub fn node_feature_matrix<N, E, G>(
+    g: &G,
+    keys: &[&str],
+    node_order: Option<&[String]>,
+    missing: &MissingFeature,
+) -> (Vec<String>, Vec<f64>)
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let order = resolve_node_order(g, node_order);
+    let vertices: HashMap<&String, &N> = g.vertices().into_iter().map(|v| (v.id(), v)).collect();
+    let mut cells: Vec<Option<f64>> = Vec::with_capacity(order.len() * keys.len());
+    for nid in order.iter() {
+        let data = vertices[nid].data();
+        for key in keys {
+            let cell = data.get(*key).and_then(|vs| vs.first()).map(|v| {
+                v.trim().parse::<f64>().unwrap_or_else(|_| {
+                    panic!("value {v} of {key} at vertex {nid} is not a number")
+                })
+            });
+            if cell.is_none() && missing == &MissingFeature::Panic {
+                panic!("vertex {nid} has no value for {key}");
+            }
+            cells.push(cell);
+        }
+    }
+    let fills: Vec<f64> = match missing {
+        MissingFeature::Panic => vec![0.0; keys.len()],
+        MissingFeature::Fill(value) => vec![*value; keys.len()],
+        MissingFeature::ColumnMean => (0..keys.len())
+            .map(|j| {
+                let present: Vec<f64> = cells
+                    .iter()
+                    .skip(j)
+                    .step_by(keys.len())
+                    .filter_map(|c| *c)
+                    .collect();
+                if present.is_empty() {
+                    0.0
+                } else {
+                    present.iter().sum::<f64>() / present.len() as f64
+                }
+            })
+            .collect(),
+    };
+    let features = cells
+        .into_iter()
+        .enumerate()
+        .map(|(k, c)| c.unwrap_or(fills[k % keys.len()]))
+        .collect();
+    (order, features)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::traits::graph_obj::GraphObject;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::idgen::SequentialIdGenerator;
+    use crate::graph::types::node::Node;
+
+    fn mk_uedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Undirected, n1_id, n2_id)
+    }
+    fn mk_dedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Directed, n1_id, n2_id)
+    }
+    fn mk_edges(es: Vec<Edge<Node>>) -> HashSet<Edge<Node>> {
+        let mut hs = HashSet::new();
+        for e in es {
+            hs.insert(e);
+        }
+        hs
+    }
+    /// path n1 - n2 - n3 with an isolated n4
+    fn mk_g1() -> Graph<Node, Edge<Node>> {
+        let e1 = mk_uedge("n1", "n2", "e1");
+        let e2 = mk_uedge("n2", "n3", "e2");
+        let mut nodes = HashSet::new();
+        nodes.insert(Node::empty("n4"));
+        Graph::from_edge_node_set(mk_edges(vec![e1, e2]), nodes)
+    }
+
+    #[test]
+    fn test_to_dense_matrix() {
+        let g = mk_g1();
+        let (order, mat) = to_dense_matrix(&g, None);
+        assert_eq!(order, vec!["n1", "n2", "n3", "n4"]);
+        let comp = vec![
+            vec![0.0, 1.0, 0.0, 0.0],
+            vec![1.0, 0.0, 1.0, 0.0],
+            vec![0.0, 1.0, 0.0, 0.0],
+            vec![0.0, 0.0, 0.0, 0.0],
+        ];
+        assert_eq!(mat, comp);
+    }
+
+    #[test]
+    fn test_to_dense_matrix_order() {
+        let g = mk_g1();
+        let given: Vec<String> = vec!["n3", "n2", "n4", "n1"]
+            .into_iter()
+            .map(|s| s.to_string())
+            .collect();
+        let (order, mat) = to_dense_matrix(&g, Some(&given));
+        assert_eq!(order, given);
+        assert_eq!(mat[0], vec![0.0, 1.0, 0.0, 0.0]);
+        assert_eq!(mat[3], vec![0.0, 1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_to_dense_matrix_bad_order() {
+        let g = mk_g1();
+        let given = vec!["n1".to_string(), "n2".to_string()];
+        to_dense_matrix(&g, Some(&given));
+    }
+
+    #[test]
+    fn test_to_dense_matrix_directed() {
+        let e1 = mk_dedge("a", "b", "e1");
+        let e2 = mk_dedge("b", "b", "e2");
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(mk_edges(vec![e1, e2]));
+        let (_, mat) = to_dense_matrix(&g, None);
+        assert_eq!(mat, vec![vec![0.0, 1.0], vec![0.0, 1.0]]);
+    }
+
+    #[test]
+    fn test_laplacian_matrix() {
+        let g = mk_g1();
+        let (_, lap) = laplacian_matrix(&g, None);
+        let comp = vec![
+            vec![1.0, -1.0, 0.0, 0.0],
+            vec![-1.0, 2.0, -1.0, 0.0],
+            vec![0.0, -1.0, 1.0, 0.0],
+            vec![0.0, 0.0, 0.0, 0.0],
+        ];
+        assert_eq!(lap, comp);
+        for row in lap {
+            assert_eq!(row.iter().sum::<f64>(), 0.0);
+        }
+    }
+
+    #[test]
+    fn test_to_incidence_matrix() {
+        let e1 = mk_dedge("a", "b", "e1");
+        let e2 = mk_uedge("b", "c", "e2");
+        let e3 = mk_uedge("c", "c", "e3");
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(mk_edges(vec![e1, e2, e3]));
+        let (nodes, edges, mat) = to_incidence_matrix(&g);
+        assert_eq!(nodes, vec!["a", "b", "c"]);
+        assert_eq!(edges, vec!["e1", "e2", "e3"]);
+        let comp = vec![
+            vec![-1.0, 0.0, 0.0],
+            vec![1.0, 1.0, 0.0],
+            vec![0.0, 1.0, 2.0],
+        ];
+        assert_eq!(mat, comp);
+    }
+
+    #[test]
+    fn test_incidence_round_trip() {
+        let e1 = mk_dedge("b", "a", "e1");
+        let e2 = mk_uedge("b", "c", "e2");
+        let e3 = mk_uedge("c", "c", "e3");
+        let mut isolated = HashSet::new();
+        isolated.insert(Node::empty("d"));
+        let g: Graph<Node, Edge<Node>> =
+            Graph::from_edge_node_set(mk_edges(vec![e1, e2, e3]), isolated);
+        let (nodes, edges, mat) = to_incidence_matrix(&g);
+        let g2: Graph<Node, Edge<Node>> = from_incidence_matrix(&mat, &nodes, &edges);
+        assert_eq!(g2.vertices(), g.vertices());
+        assert_eq!(g2.edges(), g.edges());
+        let g3: Graph<Node, Edge<Node>> =
+            from_incidence_matrix_with(&mat, &nodes, &edges, &mut SequentialIdGenerator::new("m"));
+        assert_eq!(g3.id(), "m0");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_incidence_matrix_bad_column() {
+        let rows = vec![vec![1.0], vec![1.0], vec![1.0]];
+        let nodes = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let edges = vec!["e1".to_string()];
+        let _: Graph<Node, Edge<Node>> = from_incidence_matrix(&rows, &nodes, &edges);
+    }
+
+    fn mk_featured(id: &str, feats: &[(&str, &str)]) -> Node {
+        let data = feats
+            .iter()
+            .map(|(k, v)| (k.to_string(), vec![v.to_string()]))
+            .collect();
+        Node::new(id.to_string(), data)
+    }
+
+    #[test]
+    fn test_node_feature_matrix_column_mean() {
+        let nodes = HashSet::from([
+            mk_featured("a", &[("x", "1"), ("y", "4")]),
+            mk_featured("b", &[("x", "3")]),
+            mk_featured("c", &[("y", "2")]),
+        ]);
+        let g: Graph<Node, Edge<Node>> = Graph::from_edge_node_set(HashSet::new(), nodes);
+        let order = vec!["c".to_string(), "b".to_string(), "a".to_string()];
+        let (rows, feats) = node_feature_matrix(
+            &g,
+            &["x", "y", "z"],
+            Some(&order),
+            &MissingFeature::ColumnMean,
+        );
+        assert_eq!(rows, order);
+        let comp = vec![2.0, 2.0, 0.0, 3.0, 3.0, 0.0, 1.0, 4.0, 0.0];
+        assert_eq!(feats, comp);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_node_feature_matrix_missing_panics() {
+        let nodes = HashSet::from([mk_featured("a", &[("x", "1")]), Node::empty("b")]);
+        let g: Graph<Node, Edge<Node>> = Graph::from_edge_node_set(HashSet::new(), nodes);
+        node_feature_matrix(&g, &["x"], None, &MissingFeature::Panic);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_node_feature_matrix_not_a_number() {
+        let nodes = HashSet::from([mk_featured("a", &[("x", "one")])]);
+        let g: Graph<Node, Edge<Node>> = Graph::from_edge_node_set(HashSet::new(), nodes);
+        node_feature_matrix(&g, &["x"], None, &MissingFeature::Fill(0.0));
+    }
+}
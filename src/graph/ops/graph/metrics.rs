@@ -0,0 +1,885 @@
+//! network statistics of graphs
+use crate::graph::ops::graph::order::simple_neighbors;
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// simple undirected adjacency with vertices numbered in identifier order
+struct Adjacency<'a> {
+    ids: Vec<&'a String>,
+    /// neighbors of every vertex, sorted
+    neighbors: Vec<Vec<usize>>,
+}
+
+impl<'a> Adjacency<'a> {
+    fn new<N, E, G>(g: &'a G) -> Adjacency<'a>
+    where
+        N: NodeTrait + 'a,
+        E: EdgeTrait<N> + 'a,
+        G: GraphTrait<N, E>,
+    {
+        let simple = simple_neighbors(g);
+        let mut ids: Vec<&String> = simple.keys().copied().collect();
+        ids.sort();
+        let index: HashMap<&String, usize> = ids.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+        let neighbors = ids
+            .iter()
+            .map(|v| {
+                let mut ns: Vec<usize> = simple[v].iter().map(|w| index[w]).collect();
+                ns.sort_unstable();
+                ns
+            })
+            .collect();
+        Adjacency { ids, neighbors }
+    }
+
+    fn index_of(&self, id: &str) -> Option<usize> {
+        self.ids.binary_search_by(|v| v.as_str().cmp(id)).ok()
+    }
+
+    /// Call `f` once on every triangle, with its vertices in increasing
+    /// order. Every vertex only keeps its neighbors of higher degree rank,
+    /// and the triangles of an edge are found by intersecting the sorted
+    /// lists of its ends, which takes `O(m^1.5)` time overall.
+    fn for_each_triangle<F: FnMut(usize, usize, usize)>(&self, mut f: F) {
+        let rank = |v: usize| (self.neighbors[v].len(), v);
+        let forward: Vec<Vec<usize>> = self
+            .neighbors
+            .iter()
+            .enumerate()
+            .map(|(u, ns)| ns.iter().copied().filter(|v| rank(*v) > rank(u)).collect())
+            .collect();
+        for (u, fu) in forward.iter().enumerate() {
+            for v in fu.iter() {
+                intersect_sorted(fu, &forward[*v], |w| {
+                    let mut t = [u, *v, w];
+                    t.sort_unstable();
+                    f(t[0], t[1], t[2]);
+                });
+            }
+        }
+    }
+
+    /// Call `f` with the two other vertices of every triangle containing
+    /// `u`, the smallest first
+    fn for_each_triangle_at<F: FnMut(usize, usize)>(&self, u: usize, mut f: F) {
+        let nu = &self.neighbors[u];
+        for v in nu.iter() {
+            intersect_sorted(nu, &self.neighbors[*v], |w| {
+                if w > *v {
+                    f(*v, w);
+                }
+            });
+        }
+    }
+
+    fn nb_triangles_at(&self, u: usize) -> usize {
+        let mut count = 0;
+        self.for_each_triangle_at(u, |_, _| count += 1);
+        count
+    }
+
+    fn nb_triangles(&self) -> usize {
+        let mut count = 0;
+        self.for_each_triangle(|_, _, _| count += 1);
+        count
+    }
+}
+
+/// call `f` on every element common to two sorted lists
+fn intersect_sorted<F: FnMut(usize)>(a: &[usize], b: &[usize], mut f: F) {
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            Ordering::Less => i += 1,
+            Ordering::Greater => j += 1,
+            Ordering::Equal => {
+                f(a[i]);
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+}
+
+/// Enumerate the triangles of a graph
+/// # Description
+/// A triangle is a set of three vertices pairwise joined by an edge. Edge
+/// orientation is ignored, parallel edges count as a single edge and self
+/// loops are not taken into account. Every triangle is listed once, as the
+/// identifiers of its vertices in increasing order, and triangles are
+/// sorted.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::metrics::triangles;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Directed, "c", "b");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "c", "a");
+/// let e4 = Edge::empty("e4", EdgeType::Undirected, "c", "d");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3, e4]));
+/// triangles(&g) == vec![("a".to_string(), "b".to_string(), "c".to_string())]; // true
+/// ```
+pub fn triangles<N, E, G>(g: &G) -> Vec<(String, String, String)>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let adj = Adjacency::new(g);
+    let mut found = Vec::new();
+    adj.for_each_triangle(|a, b, c| found.push((a, b, c)));
+    found.sort_unstable();
+    found
+        .into_iter()
+        .map(|(a, b, c)| (adj.ids[a].clone(), adj.ids[b].clone(), adj.ids[c].clone()))
+        .collect()
+}
+
+/// Count the triangles of a graph
+/// # Description
+/// Number of triangles listed by [triangles], computed without building
+/// them. Vertices are ranked by degree and only edges towards higher ranked
+/// vertices are kept, so that every triangle is found once, from its lowest
+/// ranked vertex, by intersecting sorted neighbor lists. This takes
+/// `O(m^1.5)` time where `m` is the number of edges, instead of the
+/// `O(n^3)` of testing every triple of vertices.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::metrics::count_triangles;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "c", "a");
+/// let e4 = Edge::empty("e4", EdgeType::Undirected, "c", "d");
+/// let e5 = Edge::empty("e5", EdgeType::Undirected, "d", "a");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3, e4, e5]));
+/// count_triangles(&g) == 2; // true
+/// ```
+///
+/// # References
+/// Latapy M. Main-memory triangle computations for very large (sparse
+/// (power-law)) graphs. 2008.
+pub fn count_triangles<N, E, G>(g: &G) -> usize
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    Adjacency::new(g).nb_triangles()
+}
+
+/// Enumerate the triangles containing a vertex
+/// # Description
+/// Triangles of [triangles] having `n` as a vertex, found by intersecting
+/// the sorted neighbor list of `n` with that of each of its neighbors.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - n: vertex of `g`
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::metrics::triangles_of;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "c", "a");
+/// let e4 = Edge::empty("e4", EdgeType::Undirected, "c", "d");
+/// let e5 = Edge::empty("e5", EdgeType::Undirected, "d", "a");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3, e4, e5]));
+/// triangles_of(&g, &Node::empty("b")) == vec![("a".to_string(), "b".to_string(), "c".to_string())]; // true
+/// triangles_of(&g, &Node::empty("a")).len() == 2; // true
+/// ```
+pub fn triangles_of<N, E, G>(g: &G, n: &N) -> Vec<(String, String, String)>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let adj = Adjacency::new(g);
+    let Some(u) = adj.index_of(n.id()) else {
+        panic!("{n} not in {g}");
+    };
+    let mut found = Vec::new();
+    adj.for_each_triangle_at(u, |v, w| {
+        let mut t = [u, v, w];
+        t.sort_unstable();
+        found.push(t);
+    });
+    found.sort_unstable();
+    found
+        .into_iter()
+        .map(|[a, b, c]| (adj.ids[a].clone(), adj.ids[b].clone(), adj.ids[c].clone()))
+        .collect()
+}
+
+/// Compute the local clustering coefficient of a vertex
+/// # Description
+/// Fraction of the pairs of neighbors of `n` that are adjacent, that is the
+/// number of triangles containing `n` over `d(d-1)/2` where `d` is its
+/// degree. Vertices with less than two neighbors have a coefficient of
+/// zero. The graph is seen as simple and undirected, see [triangles].
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - n: vertex of `g`
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::metrics::local_clustering;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "c", "a");
+/// let e4 = Edge::empty("e4", EdgeType::Undirected, "c", "d");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3, e4]));
+/// local_clustering(&g, &Node::empty("a")) == 1.0; // true
+/// (local_clustering(&g, &Node::empty("c")) - 1.0 / 3.0).abs() < 1e-12; // true
+/// ```
+///
+/// # References
+/// Watts D, Strogatz S. Collective dynamics of 'small-world' networks.
+/// 1998.
+pub fn local_clustering<N, E, G>(g: &G, n: &N) -> f64
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let adj = Adjacency::new(g);
+    let Some(u) = adj.index_of(n.id()) else {
+        panic!("{n} not in {g}");
+    };
+    clustering_of(adj.neighbors[u].len(), adj.nb_triangles_at(u))
+}
+
+/// clustering coefficient of a vertex of degree `d` in `t` triangles
+fn clustering_of(d: usize, t: usize) -> f64 {
+    if d < 2 {
+        0.0
+    } else {
+        (2 * t) as f64 / ((d * (d - 1)) as f64)
+    }
+}
+
+/// Compute the average clustering coefficient of a graph
+/// # Description
+/// Mean of [local_clustering] over all vertices, vertices with less than
+/// two neighbors included with a coefficient of zero. The empty graph has
+/// an average clustering of zero.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::metrics::average_clustering;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "c", "a");
+/// let e4 = Edge::empty("e4", EdgeType::Undirected, "c", "d");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3, e4]));
+/// (average_clustering(&g) - (1.0 + 1.0 + 1.0 / 3.0) / 4.0).abs() < 1e-12; // true
+/// ```
+///
+/// # References
+/// Watts D, Strogatz S. Collective dynamics of 'small-world' networks.
+/// 1998.
+pub fn average_clustering<N, E, G>(g: &G) -> f64
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let adj = Adjacency::new(g);
+    if adj.ids.is_empty() {
+        return 0.0;
+    }
+    let mut counts = vec![0; adj.ids.len()];
+    adj.for_each_triangle(|a, b, c| {
+        for v in [a, b, c] {
+            counts[v] += 1;
+        }
+    });
+    let total: f64 = counts
+        .iter()
+        .enumerate()
+        .map(|(v, t)| clustering_of(adj.neighbors[v].len(), *t))
+        .sum();
+    total / adj.ids.len() as f64
+}
+
+/// Compute the transitivity of a graph
+/// # Description
+/// Three times the number of triangles over the number of connected
+/// triples, that is of paths of length two, or the probability that two
+/// neighbors of a vertex are adjacent when all such pairs are weighted
+/// equally. Unlike [average_clustering] high degree vertices weigh more.
+/// Graphs without connected triples have a transitivity of zero. The graph
+/// is seen as simple and undirected, see [triangles].
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::metrics::transitivity;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "c", "a");
+/// let e4 = Edge::empty("e4", EdgeType::Undirected, "c", "d");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3, e4]));
+/// transitivity(&g) == 3.0 / 5.0; // true
+/// ```
+///
+/// # References
+/// Newman M. The structure and function of complex networks. 2003.
+pub fn transitivity<N, E, G>(g: &G) -> f64
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let adj = Adjacency::new(g);
+    let triples: usize = adj
+        .neighbors
+        .iter()
+        .map(|nv| nv.len() * nv.len().saturating_sub(1) / 2)
+        .sum();
+    if triples == 0 {
+        return 0.0;
+    }
+    (3 * adj.nb_triangles()) as f64 / triples as f64
+}
+
+/// Compute the degree assortativity of a graph
+/// # Description
+/// Pearson correlation between the degrees of the two ends of the edges,
+/// every edge being taken in both directions. Positive values mean that
+/// vertices tend to be adjacent to vertices of similar degree, as in
+/// social networks, negative values that hubs tend to be adjacent to low
+/// degree vertices, as in technological and biological networks. The graph
+/// is seen as simple and undirected, see [triangles]. Returns `None` when
+/// the correlation is undefined: the graph has no edges or every edge joins
+/// vertices of the same degree.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::metrics::degree_assortativity;
+/// use std::collections::HashSet;
+///
+/// // a star is perfectly disassortative
+/// let es = HashSet::from([
+///     Edge::empty("e1", EdgeType::Undirected, "h", "a"),
+///     Edge::empty("e2", EdgeType::Undirected, "h", "b"),
+///     Edge::empty("e3", EdgeType::Undirected, "h", "c"),
+/// ]);
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(es);
+/// (degree_assortativity(&g).unwrap() + 1.0).abs() < 1e-12; // true
+/// ```
+///
+/// # References
+/// Newman M. Assortative mixing in networks. 2002.
+pub fn degree_assortativity<N, E, G>(g: &G) -> Option<f64>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let adj = Adjacency::new(g);
+    let (mut count, mut sum, mut sum_sq, mut sum_prod) = (0.0, 0.0, 0.0, 0.0);
+    for nu in adj.neighbors.iter() {
+        let x = nu.len() as f64;
+        for v in nu.iter() {
+            let y = adj.neighbors[*v].len() as f64;
+            count += 1.0;
+            sum += x;
+            sum_sq += x * x;
+            sum_prod += x * y;
+        }
+    }
+    if count == 0.0 {
+        return None;
+    }
+    // both directions are counted, so both ends have the same mean and
+    // variance
+    let mean = sum / count;
+    let variance = sum_sq / count - mean * mean;
+    if variance <= f64::EPSILON * sum_sq / count {
+        return None;
+    }
+    Some((sum_prod / count - mean * mean) / variance)
+}
+
+/// Compute the assortativity of a graph by a categorical attribute
+/// # Description
+/// Newman's assortativity coefficient of the category of the vertices, the
+/// category being the first value stored under `key` in the vertex data.
+/// With `e_ij` the fraction of edge ends going from category `i` to
+/// category `j` and `a_i` the fraction of edge ends in category `i`, it is
+/// `(sum e_ii - sum a_i^2) / (1 - sum a_i^2)`: one when edges only join
+/// vertices of the same category, zero when categories mix at random.
+/// Edges with an end lacking `key` are ignored. The graph is seen as simple
+/// and undirected, see [triangles]. Returns `None` when no edge is left or
+/// every edge end has the same category.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - key: data key holding the category of a vertex
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::metrics::attribute_assortativity;
+/// use std::collections::{HashMap, HashSet};
+///
+/// let mk = |id: &str, kind: &str| {
+///     Node::new(id.to_string(), HashMap::from([("kind".to_string(), vec![kind.to_string()])]))
+/// };
+/// let (a, b, c, d) = (mk("a", "x"), mk("b", "x"), mk("c", "y"), mk("d", "y"));
+/// let es = HashSet::from([
+///     Edge::undirected("e1".to_string(), a, b, HashMap::new()),
+///     Edge::undirected("e2".to_string(), c, d, HashMap::new()),
+/// ]);
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(es);
+/// attribute_assortativity(&g, "kind") == Some(1.0); // true
+/// ```
+///
+/// # References
+/// Newman M. Mixing patterns in networks. 2003.
+pub fn attribute_assortativity<N, E, G>(g: &G, key: &str) -> Option<f64>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let adj = Adjacency::new(g);
+    let data: HashMap<&String, &N> = g.vertices().into_iter().map(|v| (v.id(), v)).collect();
+    let category: Vec<Option<&String>> = adj
+        .ids
+        .iter()
+        .map(|v| data[v].data().get(key).and_then(|vs| vs.first()))
+        .collect();
+    let mut mixing: HashMap<(&String, &String), f64> = HashMap::new();
+    let mut ends: HashMap<&String, f64> = HashMap::new();
+    let mut count = 0.0;
+    for (u, nu) in adj.neighbors.iter().enumerate() {
+        for v in nu.iter() {
+            if let (Some(cu), Some(cv)) = (category[u], category[*v]) {
+                *mixing.entry((cu, cv)).or_insert(0.0) += 1.0;
+                *ends.entry(cu).or_insert(0.0) += 1.0;
+                count += 1.0;
+            }
+        }
+    }
+    if count == 0.0 {
+        return None;
+    }
+    let same: f64 = mixing
+        .iter()
+        .filter(|((cu, cv), _)| cu == cv)
+        .map(|(_, n)| n / count)
+        .sum();
+    let expected: f64 = ends.values().map(|n| (n / count) * (n / count)).sum();
+    if 1.0 - expected <= f64::EPSILON {
+        return None;
+    }
+    Some((same - expected) / (1.0 - expected))
+}
+
+/// Compute the degree histogram of a graph
+/// # Description
+/// Entry `k` of the output is the number of vertices of degree `k`, the
+/// last entry being that of the largest degree. The degree of a vertex is
+/// the number of edges incident to it, regardless of their orientation,
+/// loops counting twice, as in
+/// [summarize](crate::graph::ops::graph::summary::summarize). The empty
+/// graph has an empty histogram.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::metrics::degree_histogram;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Directed, "b", "c");
+/// let g: Graph<Node, Edge<Node>> =
+///     Graph::from_edge_node_set(HashSet::from([e1, e2]), HashSet::from([Node::empty("d")]));
+/// degree_histogram(&g) == vec![1, 2, 1]; // true
+/// ```
+pub fn degree_histogram<N, E, G>(g: &G) -> Vec<usize>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let mut histogram = Vec::new();
+    for d in degrees(g).into_values() {
+        if histogram.len() <= d {
+            histogram.resize(d + 1, 0);
+        }
+        histogram[d] += 1;
+    }
+    histogram
+}
+
+/// degree of every vertex, see [degree_histogram]
+fn degrees<'a, N, E, G>(g: &'a G) -> HashMap<&'a String, usize>
+where
+    N: NodeTrait + 'a,
+    E: EdgeTrait<N> + 'a,
+    G: GraphTrait<N, E>,
+{
+    let mut degrees: HashMap<&String, usize> =
+        g.vertices().into_iter().map(|v| (v.id(), 0)).collect();
+    for e in g.edges() {
+        *degrees.get_mut(e.start().id()).unwrap() += 1;
+        *degrees.get_mut(e.end().id()).unwrap() += 1;
+    }
+    degrees
+}
+
+/// smallest number of vertices a tail is fitted on, see
+/// [powerlaw_alpha_estimate]
+pub const POWERLAW_MIN_TAIL: usize = 10;
+
+/// power law fitted to the tail of a degree distribution, see
+/// [powerlaw_alpha_estimate]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PowerLawFit {
+    /// estimated exponent, the probability of degree `k` in the tail being
+    /// proportional to `k^-alpha`
+    pub alpha: f64,
+    /// smallest degree of the tail
+    pub k_min: usize,
+    /// number of vertices in the tail
+    pub tail_size: usize,
+    /// Kolmogorov-Smirnov distance between the tail and the fitted law
+    pub ks_distance: f64,
+}
+
+/// fit of the tail of `degrees` starting at `k_min`
+fn fit_tail(degrees: &[usize], k_min: usize) -> PowerLawFit {
+    let tail: Vec<usize> = degrees.iter().copied().filter(|k| *k >= k_min).collect();
+    let n = tail.len() as f64;
+    let shift = k_min as f64 - 0.5;
+    let log_sum: f64 = tail.iter().map(|k| (*k as f64 / shift).ln()).sum();
+    let alpha = 1.0 + n / log_sum;
+    // the discrete law is approximated by a continuous one starting at
+    // k_min - 1/2, degree k covering [k - 1/2, k + 1/2)
+    let mut ks_distance: f64 = 0.0;
+    let mut seen = 0;
+    for (i, k) in tail.iter().enumerate() {
+        seen += 1;
+        if tail.get(i + 1) == Some(k) {
+            continue;
+        }
+        let empirical = seen as f64 / n;
+        let model = 1.0 - ((*k as f64 + 0.5) / shift).powf(1.0 - alpha);
+        ks_distance = ks_distance.max((empirical - model).abs());
+    }
+    PowerLawFit {
+        alpha,
+        k_min,
+        tail_size: tail.len(),
+        ks_distance,
+    }
+}
+
+/// best fit over the candidate tails of a sorted degree sequence
+fn fit_power_law(degrees: &[usize]) -> Option<PowerLawFit> {
+    let mut candidates: Vec<usize> = degrees.iter().copied().filter(|k| *k > 0).collect();
+    candidates.dedup();
+    candidates
+        .into_iter()
+        .map(|k_min| fit_tail(degrees, k_min))
+        .filter(|fit| fit.tail_size >= POWERLAW_MIN_TAIL && fit.alpha.is_finite())
+        .min_by(|a, b| a.ks_distance.total_cmp(&b.ks_distance))
+}
+
+/// Estimate the exponent of a power law degree distribution
+/// # Description
+/// Scale free graphs, such as those grown by preferential attachment, have
+/// a degree distribution whose tail follows `P(k) ~ k^-alpha`. For every
+/// candidate smallest degree `k_min` of the tail, the exponent is estimated
+/// by maximum likelihood with the discrete approximation
+/// `alpha = 1 + n / sum ln(k_i / (k_min - 1/2))` over the `n` degrees of
+/// the tail, and the candidate whose fitted law is the closest to the tail
+/// in Kolmogorov-Smirnov distance is kept. Tails of less than
+/// [POWERLAW_MIN_TAIL] vertices are not considered, and vertices of degree
+/// zero never are. Degrees are those of [degree_histogram]. Returns `None`
+/// if no tail can be fitted. A good fit does not prove that the
+/// distribution is a power law, it only gives its exponent if it is one.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::metrics::powerlaw_alpha_estimate;
+/// use std::collections::HashSet;
+///
+/// let es: HashSet<Edge<Node>> = (0..12)
+///     .map(|i| Edge::empty(&format!("e{i}"), EdgeType::Undirected, "hub", &format!("v{i}")))
+///     .collect();
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(es);
+/// let fit = powerlaw_alpha_estimate(&g).unwrap();
+/// fit.k_min == 1 && fit.tail_size == 13; // true
+/// fit.alpha > 2.0; // true
+/// ```
+///
+/// # References
+/// Clauset A, Shalizi C, Newman M. Power-law distributions in empirical
+/// data. 2009.
+pub fn powerlaw_alpha_estimate<N, E, G>(g: &G) -> Option<PowerLawFit>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let mut ds: Vec<usize> = degrees(g).into_values().collect();
+    ds.sort_unstable();
+    fit_power_law(&ds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::edgetype::EdgeType;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+    use std::collections::HashSet;
+
+    fn mk_uedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Undirected, n1_id, n2_id)
+    }
+
+    /// complete graph on a, b, c, d with a pendant vertex e on d, a loop on
+    /// e and an isolated vertex f
+    fn mk_g() -> Graph<Node, Edge<Node>> {
+        let es = HashSet::from([
+            mk_uedge("a", "b", "e1"),
+            mk_uedge("a", "c", "e2"),
+            mk_uedge("a", "d", "e3"),
+            mk_uedge("b", "c", "e4"),
+            mk_uedge("b", "d", "e5"),
+            mk_uedge("c", "d", "e6"),
+            mk_uedge("d", "e", "e7"),
+            mk_uedge("e", "e", "e8"),
+            // parallel to e1
+            mk_uedge("b", "a", "e9"),
+        ]);
+        Graph::from_edge_node_set(es, HashSet::from([Node::empty("f")]))
+    }
+
+    #[test]
+    fn test_triangles() {
+        let g = mk_g();
+        let ts = triangles(&g);
+        let s = |a: &str, b: &str, c: &str| (a.to_string(), b.to_string(), c.to_string());
+        assert_eq!(
+            ts,
+            vec![
+                s("a", "b", "c"),
+                s("a", "b", "d"),
+                s("a", "c", "d"),
+                s("b", "c", "d")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_count_triangles_and_triangles_of() {
+        let g = mk_g();
+        assert_eq!(count_triangles(&g), 4);
+        let s = |a: &str, b: &str, c: &str| (a.to_string(), b.to_string(), c.to_string());
+        assert_eq!(
+            triangles_of(&g, &Node::empty("b")),
+            vec![s("a", "b", "c"), s("a", "b", "d"), s("b", "c", "d")]
+        );
+        assert!(triangles_of(&g, &Node::empty("e")).is_empty());
+        assert!(triangles_of(&g, &Node::empty("f")).is_empty());
+    }
+
+    #[test]
+    fn test_count_triangles_wheel() {
+        // wheel: hub h joined to a cycle of 40 vertices, 40 triangles
+        let mut es = HashSet::new();
+        for i in 0..40 {
+            let (v, w) = (format!("v{i}"), format!("v{}", (i + 1) % 40));
+            es.insert(mk_uedge(&v, &w, &format!("r{i}")));
+            es.insert(mk_uedge("h", &v, &format!("s{i}")));
+        }
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(es);
+        assert_eq!(count_triangles(&g), 40);
+        assert_eq!(triangles(&g).len(), 40);
+        assert_eq!(triangles_of(&g, &Node::empty("h")).len(), 40);
+        assert_eq!(triangles_of(&g, &Node::empty("v7")).len(), 2);
+        assert_eq!(local_clustering(&g, &Node::empty("v7")), 2.0 / 3.0);
+    }
+
+    #[test]
+    fn test_local_clustering() {
+        let g = mk_g();
+        assert_eq!(local_clustering(&g, &Node::empty("a")), 1.0);
+        assert_eq!(local_clustering(&g, &Node::empty("d")), 0.5);
+        assert_eq!(local_clustering(&g, &Node::empty("e")), 0.0);
+        assert_eq!(local_clustering(&g, &Node::empty("f")), 0.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_local_clustering_missing_vertex() {
+        local_clustering(&mk_g(), &Node::empty("z"));
+    }
+
+    #[test]
+    fn test_average_clustering_and_transitivity() {
+        let g = mk_g();
+        let avg = average_clustering(&g);
+        assert!((avg - 3.5 / 6.0).abs() < 1e-12);
+        // 4 triangles, triples: 3 * 3 + 6
+        assert!((transitivity(&g) - 12.0 / 15.0).abs() < 1e-12);
+        let empty: Graph<Node, Edge<Node>> = Graph::empty("g");
+        assert_eq!(average_clustering(&empty), 0.0);
+        assert_eq!(transitivity(&empty), 0.0);
+    }
+
+    #[test]
+    fn test_degree_assortativity() {
+        // a path a - b - c - d: ends of degrees (1, 2) twice and (2, 2) once
+        let es = HashSet::from([
+            mk_uedge("a", "b", "e1"),
+            mk_uedge("b", "c", "e2"),
+            mk_uedge("c", "d", "e3"),
+        ]);
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(es);
+        let r = degree_assortativity(&g).unwrap();
+        assert!((r + 0.5).abs() < 1e-12);
+        // regular graphs and edgeless graphs have no assortativity
+        let cycle: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([
+            mk_uedge("a", "b", "e1"),
+            mk_uedge("b", "c", "e2"),
+            mk_uedge("c", "a", "e3"),
+        ]));
+        assert_eq!(degree_assortativity(&cycle), None);
+        assert_eq!(
+            degree_assortativity(&Graph::<Node, Edge<Node>>::empty("g")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_attribute_assortativity() {
+        let mk = |id: &str, kind: &str| {
+            Node::new(
+                id.to_string(),
+                HashMap::from([("kind".to_string(), vec![kind.to_string()])]),
+            )
+        };
+        let mk_e = |id: &str, a: &Node, b: &Node| {
+            Edge::undirected(id.to_string(), a.clone(), b.clone(), HashMap::new())
+        };
+        let (a, b, c, d) = (mk("a", "x"), mk("b", "y"), mk("c", "x"), mk("d", "y"));
+        let u = Node::empty("u");
+        // every edge joins different categories: perfectly disassortative
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([
+            mk_e("e1", &a, &b),
+            mk_e("e2", &c, &d),
+            mk_e("e3", &a, &u),
+        ]));
+        assert_eq!(attribute_assortativity(&g, "kind"), Some(-1.0));
+        assert_eq!(attribute_assortativity(&g, "color"), None);
+        let same: Graph<Node, Edge<Node>> =
+            Graph::from_edgeset(HashSet::from([mk_e("e1", &a, &c)]));
+        assert_eq!(attribute_assortativity(&same, "kind"), None);
+    }
+
+    #[test]
+    fn test_degree_histogram() {
+        assert_eq!(degree_histogram(&mk_g()), vec![1, 0, 0, 2, 3]);
+        let empty: Graph<Node, Edge<Node>> = Graph::empty("g");
+        assert!(degree_histogram(&empty).is_empty());
+    }
+
+    #[test]
+    fn test_fit_power_law() {
+        // 1e5 * k^-2.5 vertices of degree k
+        let mut ds: Vec<usize> = Vec::new();
+        for k in 1..=2000usize {
+            let n = (1e5 * (k as f64).powf(-2.5)).round() as usize;
+            ds.extend(std::iter::repeat_n(k, n));
+        }
+        let fit = fit_power_law(&ds).unwrap();
+        assert!((fit.alpha - 2.5).abs() < 0.05);
+        assert!(fit.ks_distance < 0.01);
+        assert!(fit.tail_size >= POWERLAW_MIN_TAIL);
+        // too few vertices to fit a tail
+        assert_eq!(fit_power_law(&[1, 2, 3]), None);
+    }
+}
@@ -4,14 +4,18 @@ use crate::graph::ops::edge::boolops::is_endvertice;
 use crate::graph::traits::edge::Edge as EdgeTrait;
 use crate::graph::traits::graph::Graph as GraphTrait;
 use crate::graph::traits::graph_obj::GraphObject;
+use crate::graph::traits::idgen::IdGenerator;
 use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::edgetype::EdgeType;
+use crate::graph::types::idgen::UuidGenerator;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::collections::VecDeque;
 use std::option::Option;
 
 /// create an edge list representation of graph
 /// for each node we register all the edges
-pub fn to_adjacencylist<'a, N, E, G>(g: &'a G) -> HashMap<&str, Option<HashSet<&str>>>
+pub fn to_adjacencylist<'a, N, E, G>(g: &'a G) -> HashMap<&'a str, Option<HashSet<&'a str>>>
 where
     N: NodeTrait + 'a,
     E: EdgeTrait<N> + 'a,
@@ -157,7 +161,7 @@ where
 /// - g: something that implements [Graph] trait.
 /// - ns: a set of things that implement [Node] trait
 /// - edge_policy: defines how to handle edges given a node. By default, we
-/// conserve edges whose incident nodes are a subset of `ns`
+///   conserve edges whose incident nodes are a subset of `ns`
 pub fn get_subgraph_by_vertices<'a, G, N, E, F>(
     g: &'a G,
     ns: HashSet<&N>,
@@ -206,6 +210,95 @@ where
     (nset, eset)
 }
 
+/// Get the ego graph of a node
+/// # Description
+/// The ego graph of `n` is the subgraph induced by all the vertices that are
+/// at most `k` hops away from `n`. When `respect_direction` is true, directed
+/// edges are only followed from their start node to their end node, while
+/// undirected edges are followed in both directions.
+///
+/// # Args
+/// - g: something that implements [Graph] trait.
+/// - n: something that implements [Node] trait, center of the ego graph
+/// - k: maximum number of hops from `n`
+/// - respect_direction: follow directed edges only along their orientation
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+/// use pgm_rust::graph::ops::graph::misc::ego_graph;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "n1", "n2");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "n2", "n3");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "n3", "n4");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// let ego: Graph<Node, Edge<Node>> = ego_graph(&g, &Node::empty("n2"), 1, false);
+/// ego.order() == 3; // true: n1, n2, n3
+/// ego.size() == 2; // true: e1, e2
+/// ```
+pub fn ego_graph<N, E, G>(g: &G, n: &N, k: usize, respect_direction: bool) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    ego_graph_with(g, n, k, respect_direction, &mut UuidGenerator)
+}
+
+/// Get the ego graph of a node, the identifier of the ego graph is produced
+/// by `idgen`, see [ego_graph]
+pub fn ego_graph_with<N, E, G, I>(
+    g: &G,
+    n: &N,
+    k: usize,
+    respect_direction: bool,
+    idgen: &mut I,
+) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    I: IdGenerator,
+{
+    let vertices = g.vertices();
+    if !vertices.contains(n) {
+        panic!("{n} not in {g}");
+    }
+    let mut adjacency: HashMap<&String, Vec<&N>> = HashMap::new();
+    for e in g.edges() {
+        let start = e.start();
+        let end = e.end();
+        adjacency.entry(start.id()).or_default().push(end);
+        let follow_back = !respect_direction || e.has_type() == &EdgeType::Undirected;
+        if follow_back {
+            adjacency.entry(end.id()).or_default().push(start);
+        }
+    }
+    let center = vertices.get(n).unwrap();
+    let mut visited: HashSet<&N> = HashSet::from([*center]);
+    let mut queue: VecDeque<(&N, usize)> = VecDeque::from([(*center, 0)]);
+    while let Some((u, depth)) = queue.pop_front() {
+        if depth == k {
+            continue;
+        }
+        if let Some(vs) = adjacency.get(u.id()) {
+            for v in vs {
+                if visited.insert(v) {
+                    queue.push_back((v, depth + 1));
+                }
+            }
+        }
+    }
+    let (nodes, edges) = get_subgraph_by_vertices(g, visited, None::<fn(&E, &HashSet<&N>) -> bool>);
+    let gid = idgen.generate(&nodes, &edges);
+    G::create_from_ref(gid, HashMap::new(), nodes, edges)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -246,6 +339,8 @@ mod tests {
         Graph::new("g1".to_string(), h1, nset, h2)
     }
 
+    type EdgePolicy = Box<dyn Fn(&Edge<Node>, &HashSet<&Node>) -> bool>;
+
     fn mk_refset(es: Vec<&str>) -> HashSet<&str> {
         let mut ns: HashSet<&str> = HashSet::new();
         for e in es {
@@ -254,6 +349,10 @@ mod tests {
         ns
     }
 
+    fn mk_dedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Directed, n1_id, n2_id)
+    }
+
     #[test]
     fn test_to_adjacencylist() {
         let g = mk_g1();
@@ -324,7 +423,7 @@ mod tests {
         let e1 = mk_uedge("n2", "n4", "e3");
         erefset.insert(&e1);
         // let opt: Option<dyn Fn(&Edge, &HashSet<&Node>) -> bool> = None;
-        let opt: Option<Box<dyn Fn(&Edge<Node>, &HashSet<&Node>) -> bool>> = None;
+        let opt: Option<EdgePolicy> = None;
         // let opt = None;
         let result: (HashSet<&Node>, HashSet<&Edge<Node>>) =
             get_subgraph_by_vertices(&g1, nrefset, opt);
@@ -377,4 +476,35 @@ mod tests {
         //
         assert_eq!(edges, erefset);
     }
+
+    #[test]
+    fn test_ego_graph() {
+        let g = mk_g1();
+        let ego: Graph<Node, Edge<Node>> = ego_graph(&g, &mk_node("n1"), 2, false);
+        let ns = mk_nodes(vec!["n1", "n2", "n3"]);
+        let comp: HashSet<&Node> = ns.iter().collect();
+        assert_eq!(ego.vertices(), comp);
+        assert_eq!(ego.size(), 2);
+    }
+
+    #[test]
+    fn test_ego_graph_zero_hops() {
+        let g = mk_g1();
+        let ego: Graph<Node, Edge<Node>> = ego_graph(&g, &mk_node("n5"), 0, false);
+        assert_eq!(ego.order(), 1);
+        assert_eq!(ego.size(), 0);
+    }
+
+    #[test]
+    fn test_ego_graph_respect_direction() {
+        let e1 = mk_dedge("n1", "n2", "e1");
+        let e2 = mk_dedge("n3", "n1", "e2");
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(mk_edges(vec![e1, e2]));
+        let ego: Graph<Node, Edge<Node>> = ego_graph(&g, &mk_node("n1"), 1, true);
+        let ns = mk_nodes(vec!["n1", "n2"]);
+        let comp: HashSet<&Node> = ns.iter().collect();
+        assert_eq!(ego.vertices(), comp);
+        let ego2: Graph<Node, Edge<Node>> = ego_graph(&g, &mk_node("n1"), 1, false);
+        assert_eq!(ego2.order(), 3);
+    }
 }
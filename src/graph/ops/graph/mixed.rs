@@ -0,0 +1,374 @@
+//! semantics of graphs mixing directed and undirected edges
+//!
+//! [EdgeType] allows a graph to contain both directed and undirected edges.
+//! Operations in this module take a [MixedMode] telling how the orientation
+//! of edges is interpreted.
+use crate::graph::ops::graph::edge::outgoing_edges_of;
+use crate::graph::ops::graph::search::EdgeNeighborhood;
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::edgetype::EdgeType;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+/// interpretation of edge orientation in a graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MixedMode {
+    /// every edge is directed from its start vertex to its end vertex,
+    /// whatever its type
+    AsDirected,
+    /// every edge is undirected, whatever its type
+    AsUndirected,
+    /// each edge keeps its own type: directed edges are followed along their
+    /// orientation and undirected edges in both directions
+    Strict,
+}
+
+impl MixedMode {
+    /// whether `e` can be followed from `start` to `end`, and from `end` to
+    /// `start`
+    fn directions<N, E>(&self, e: &E) -> (bool, bool)
+    where
+        N: NodeTrait,
+        E: EdgeTrait<N>,
+    {
+        match self {
+            MixedMode::AsDirected => (true, false),
+            MixedMode::AsUndirected => (true, true),
+            MixedMode::Strict => (true, e.has_type() == &EdgeType::Undirected),
+        }
+    }
+
+    /// traversal strategy following edges as this mode interprets them
+    /// # Example
+    /// ```
+    /// use pgm_rust::graph::types::edge::Edge;
+    /// use pgm_rust::graph::types::edgetype::EdgeType;
+    /// use pgm_rust::graph::types::graph::Graph;
+    /// use pgm_rust::graph::types::node::Node;
+    /// use pgm_rust::graph::ops::graph::mixed::MixedMode;
+    /// use pgm_rust::graph::ops::graph::search::dfs;
+    /// use std::collections::HashSet;
+    ///
+    /// let e1 = Edge::empty("e1", EdgeType::Undirected, "n2", "n1");
+    /// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1]));
+    /// let r = dfs(&g, &Node::empty("n1"), &MixedMode::AsDirected.neighborhood(&g), false);
+    /// r.roots.len() == 2; // true
+    /// ```
+    pub fn neighborhood<'a, N, E, G>(&self, g: &'a G) -> EdgeNeighborhood<'a, N, E>
+    where
+        N: NodeTrait + 'a,
+        E: EdgeTrait<N> + 'a,
+        G: GraphTrait<N, E>,
+    {
+        match self {
+            MixedMode::AsDirected => {
+                EdgeNeighborhood::Custom(Box::new(move |n| outgoing_edges_of(g, n)))
+            }
+            MixedMode::AsUndirected => EdgeNeighborhood::Both,
+            MixedMode::Strict => EdgeNeighborhood::Out,
+        }
+    }
+}
+
+/// Check if every edge of the graph is directed
+/// # Description
+/// Graphs without edges are purely directed and purely undirected.
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::mixed::is_purely_directed;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Directed, "n1", "n2");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1]));
+/// is_purely_directed(&g); // true
+/// ```
+pub fn is_purely_directed<N, E, G>(g: &G) -> bool
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    g.edges()
+        .iter()
+        .all(|e| e.has_type() == &EdgeType::Directed)
+}
+
+/// Check if every edge of the graph is undirected, see [is_purely_directed]
+pub fn is_purely_undirected<N, E, G>(g: &G) -> bool
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    g.edges()
+        .iter()
+        .all(|e| e.has_type() == &EdgeType::Undirected)
+}
+
+/// Check if the graph has both directed and undirected edges
+pub fn is_mixed<N, E, G>(g: &G) -> bool
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    !is_purely_directed(g) && !is_purely_undirected(g)
+}
+
+/// Compute the adjacency of a graph under a mode
+/// # Description
+/// Maps every vertex identifier to the identifiers of the vertices that can
+/// be reached from it through a single edge, where edges are interpreted
+/// according to `mode`. Every vertex has an entry.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - mode: interpretation of edge orientation
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::mixed::adjacency;
+/// use pgm_rust::graph::ops::graph::mixed::MixedMode;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Directed, "n1", "n2");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1]));
+/// let strict = adjacency(&g, MixedMode::Strict);
+/// let n2 = "n2".to_string();
+/// strict[&n2].is_empty(); // true
+/// let undirected = adjacency(&g, MixedMode::AsUndirected);
+/// undirected[&n2].len() == 1; // true
+/// ```
+pub fn adjacency<'a, N, E, G>(g: &'a G, mode: MixedMode) -> HashMap<&'a String, HashSet<&'a String>>
+where
+    N: NodeTrait + 'a,
+    E: EdgeTrait<N> + 'a,
+    G: GraphTrait<N, E>,
+{
+    let mut adj: HashMap<&String, HashSet<&String>> = g
+        .vertices()
+        .into_iter()
+        .map(|v| (v.id(), HashSet::new()))
+        .collect();
+    for e in g.edges() {
+        let (s, t) = (e.start().id(), e.end().id());
+        let (forward, backward) = mode.directions(e);
+        if forward {
+            adj.entry(s).or_default().insert(t);
+        }
+        if backward {
+            adj.entry(t).or_default().insert(s);
+        }
+    }
+    adj
+}
+
+/// Compute the degree of a vertex
+/// # Description
+/// Number of edges incident to `n`, loops counting twice, see Diestel 2017,
+/// p. 5. The degree does not depend on the [MixedMode]: it is the sum of the
+/// in and out degrees under [MixedMode::AsDirected].
+pub fn degree<N, E, G>(g: &G, n: &N) -> usize
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    if !g.vertices().contains(n) {
+        panic!("{n} not in {g}");
+    }
+    let nid = n.id();
+    g.edges()
+        .into_iter()
+        .map(|e| (e.start().id() == nid) as usize + (e.end().id() == nid) as usize)
+        .sum()
+}
+
+/// count the edges that can be followed out of `n` if `outgoing`, into `n`
+/// otherwise
+fn directed_degree<N, E, G>(g: &G, n: &N, mode: MixedMode, outgoing: bool) -> usize
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    if !g.vertices().contains(n) {
+        panic!("{n} not in {g}");
+    }
+    let nid = n.id();
+    let mut count = 0;
+    for e in g.edges() {
+        let (forward, backward) = mode.directions(e);
+        let (from, to) = if outgoing {
+            (e.start().id(), e.end().id())
+        } else {
+            (e.end().id(), e.start().id())
+        };
+        if (forward && from == nid) || (backward && to == nid) {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Compute the out degree of a vertex under a mode
+/// # Description
+/// Number of edges that can be followed out of `n` when edges are
+/// interpreted according to `mode`. With [MixedMode::AsUndirected] and
+/// [MixedMode::Strict] an undirected edge counts for the out degree and the
+/// in degree of both its end vertices.
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::mixed::out_degree;
+/// use pgm_rust::graph::ops::graph::mixed::MixedMode;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Directed, "n1", "n2");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "n3", "n2");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+/// let n2 = Node::empty("n2");
+/// out_degree(&g, &n2, MixedMode::Strict) == 1; // true
+/// out_degree(&g, &n2, MixedMode::AsDirected) == 0; // true
+/// out_degree(&g, &n2, MixedMode::AsUndirected) == 2; // true
+/// ```
+pub fn out_degree<N, E, G>(g: &G, n: &N, mode: MixedMode) -> usize
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    directed_degree(g, n, mode, true)
+}
+
+/// Compute the in degree of a vertex under a mode, see [out_degree]
+pub fn in_degree<N, E, G>(g: &G, n: &N, mode: MixedMode) -> usize
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    directed_degree(g, n, mode, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::ops::graph::search::dfs;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+
+    fn mk_uedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Undirected, n1_id, n2_id)
+    }
+    fn mk_dedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Directed, n1_id, n2_id)
+    }
+    fn mk_edges(es: Vec<Edge<Node>>) -> HashSet<Edge<Node>> {
+        let mut hs = HashSet::new();
+        for e in es {
+            hs.insert(e);
+        }
+        hs
+    }
+
+    /// a -> b, c - b, b -> d
+    fn mk_g1() -> Graph<Node, Edge<Node>> {
+        let es = vec![
+            mk_dedge("a", "b", "e1"),
+            mk_uedge("c", "b", "e2"),
+            mk_dedge("b", "d", "e3"),
+        ];
+        Graph::from_edgeset(mk_edges(es))
+    }
+
+    fn mk_ids(ids: Vec<&str>) -> HashSet<String> {
+        ids.into_iter().map(|i| i.to_string()).collect()
+    }
+    fn owned(adj: &HashMap<&String, HashSet<&String>>, n: &str) -> HashSet<String> {
+        adj[&n.to_string()].iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_purity() {
+        let g = mk_g1();
+        assert!(is_mixed(&g));
+        assert!(!is_purely_directed(&g));
+        assert!(!is_purely_undirected(&g));
+        let empty: Graph<Node, Edge<Node>> = Graph::empty("g0");
+        assert!(is_purely_directed(&empty) && is_purely_undirected(&empty));
+        assert!(!is_mixed(&empty));
+    }
+
+    #[test]
+    fn test_adjacency() {
+        let g = mk_g1();
+        let strict = adjacency(&g, MixedMode::Strict);
+        assert_eq!(owned(&strict, "b"), mk_ids(vec!["c", "d"]));
+        assert_eq!(owned(&strict, "c"), mk_ids(vec!["b"]));
+        assert_eq!(owned(&strict, "d"), mk_ids(vec![]));
+        let directed = adjacency(&g, MixedMode::AsDirected);
+        assert_eq!(owned(&directed, "b"), mk_ids(vec!["d"]));
+        assert_eq!(owned(&directed, "c"), mk_ids(vec!["b"]));
+        let undirected = adjacency(&g, MixedMode::AsUndirected);
+        assert_eq!(owned(&undirected, "b"), mk_ids(vec!["a", "c", "d"]));
+    }
+
+    #[test]
+    fn test_degrees() {
+        let g = mk_g1();
+        let b = Node::empty("b");
+        assert_eq!(degree(&g, &b), 3);
+        assert_eq!(out_degree(&g, &b, MixedMode::Strict), 2);
+        assert_eq!(in_degree(&g, &b, MixedMode::Strict), 2);
+        assert_eq!(out_degree(&g, &b, MixedMode::AsDirected), 1);
+        assert_eq!(in_degree(&g, &b, MixedMode::AsDirected), 2);
+        assert_eq!(out_degree(&g, &b, MixedMode::AsUndirected), 3);
+        assert_eq!(in_degree(&g, &b, MixedMode::AsUndirected), 3);
+    }
+
+    #[test]
+    fn test_degree_loop() {
+        let g: Graph<Node, Edge<Node>> =
+            Graph::from_edgeset(mk_edges(vec![mk_dedge("a", "a", "e1")]));
+        let a = Node::empty("a");
+        assert_eq!(degree(&g, &a), 2);
+        assert_eq!(out_degree(&g, &a, MixedMode::AsDirected), 1);
+        assert_eq!(in_degree(&g, &a, MixedMode::AsDirected), 1);
+    }
+
+    #[test]
+    fn test_neighborhood() {
+        let g = mk_g1();
+        let start = Node::empty("c");
+        let strict = dfs(&g, &start, &MixedMode::Strict.neighborhood(&g), false);
+        assert_eq!(strict.components["c"], mk_ids(vec!["b", "c", "d"]));
+        let directed = dfs(&g, &start, &MixedMode::AsDirected.neighborhood(&g), false);
+        assert_eq!(directed.components["c"], mk_ids(vec!["b", "c", "d"]));
+        let from_b = dfs(
+            &g,
+            &Node::empty("b"),
+            &MixedMode::AsDirected.neighborhood(&g),
+            false,
+        );
+        assert_eq!(from_b.components["b"], mk_ids(vec!["b", "d"]));
+        let undirected = dfs(&g, &start, &MixedMode::AsUndirected.neighborhood(&g), false);
+        assert_eq!(undirected.roots, vec!["c"]);
+    }
+}
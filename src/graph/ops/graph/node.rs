@@ -1,9 +1,7 @@
 use crate::graph::ops::edge::boolops::is_endvertice;
 use crate::graph::ops::edge::nodeops::get_other;
 use crate::graph::ops::graph::boolops::is_in;
-use crate::graph::ops::graph::misc::by_id;
 use crate::graph::traits::edge::Edge as EdgeTrait;
-///
 use crate::graph::traits::graph::Graph as GraphTrait;
 use crate::graph::traits::node::Node as NodeTrait;
 use std::collections::HashSet;
@@ -102,6 +100,11 @@ where
 /// - g: something that implements [Graph] trait
 /// - vid: string reference
 ///
+/// Every call scans the vertex set, use [IndexedGraph] for repeated
+/// lookups.
+///
+/// [IndexedGraph]: crate::graph::types::indexed::IndexedGraph
+///
 /// # Example
 /// ```
 /// use pgm_rust::graph::types::edge::Edge;
@@ -152,8 +155,10 @@ where
     E: EdgeTrait<N>,
     G: GraphTrait<N, E>,
 {
-    let f = |mg: &'a G| -> HashSet<&'a N> { mg.vertices() };
-    by_id(g, vid, f)
+    match g.find_node(vid) {
+        None => panic!("{vid} not contained in {g}"),
+        Some(v) => v,
+    }
 }
 
 #[cfg(test)]
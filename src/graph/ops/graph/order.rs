@@ -0,0 +1,469 @@
+//! vertex orderings
+use crate::graph::ops::graph::bounded::bounded_bfs;
+use crate::graph::ops::graph::bounded::TraversalLimits;
+use crate::graph::ops::graph::search::dfs;
+use crate::graph::ops::graph::search::EdgeNeighborhood;
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use std::cmp::Reverse;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+/// Order vertices breadth first
+/// # Description
+/// Vertices reachable from `root` in the order a breadth first search
+/// visits them, neighbors being visited in edge identifier order. Vertices
+/// that can not be reached are not in the output.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - root: first vertex of the order
+/// - edge_generator: edges that can be followed from a vertex, see
+///   [EdgeNeighborhood]
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::search::EdgeNeighborhood;
+/// use pgm_rust::graph::ops::graph::order::bfs_order;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "a", "d");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// bfs_order(&g, &Node::empty("a"), &EdgeNeighborhood::Both) == vec!["a", "b", "d", "c"]; // true
+/// ```
+pub fn bfs_order<'a, N, E, G>(
+    g: &'a G,
+    root: &N,
+    edge_generator: &EdgeNeighborhood<'a, N, E>,
+) -> Vec<String>
+where
+    N: NodeTrait + 'a,
+    E: EdgeTrait<N> + 'a,
+    G: GraphTrait<N, E>,
+{
+    bounded_bfs(g, root, edge_generator, &TraversalLimits::default()).visited
+}
+
+/// Order vertices by depth first discovery
+/// # Description
+/// Every vertex in the order a depth first search first visits it, see
+/// [dfs]. The search starts at `root` and restarts from the unvisited
+/// vertex with the smallest identifier, so that every vertex is in the
+/// output.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - root: first vertex of the order
+/// - edge_generator: edges that can be followed from a vertex, see
+///   [EdgeNeighborhood]
+pub fn dfs_order<'a, N, E, G>(
+    g: &'a G,
+    root: &N,
+    edge_generator: &EdgeNeighborhood<'a, N, E>,
+) -> Vec<String>
+where
+    N: NodeTrait + 'a,
+    E: EdgeTrait<N> + 'a,
+    G: GraphTrait<N, E>,
+{
+    by_time(dfs(g, root, edge_generator, false).first_visit_times)
+}
+
+/// Order vertices by depth first finish time
+/// # Description
+/// Every vertex in the order a depth first search is done with it, see
+/// [dfs] and [dfs_order]. On a directed acyclic graph searched along edge
+/// orientation, the reverse of this order is a topological order.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - root: first vertex of the search
+/// - edge_generator: edges that can be followed from a vertex, see
+///   [EdgeNeighborhood]
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::search::EdgeNeighborhood;
+/// use pgm_rust::graph::ops::graph::order::dfs_finish_order;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Directed, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Directed, "b", "c");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+/// dfs_finish_order(&g, &Node::empty("a"), &EdgeNeighborhood::Out) == vec!["c", "b", "a"]; // true
+/// ```
+pub fn dfs_finish_order<'a, N, E, G>(
+    g: &'a G,
+    root: &N,
+    edge_generator: &EdgeNeighborhood<'a, N, E>,
+) -> Vec<String>
+where
+    N: NodeTrait + 'a,
+    E: EdgeTrait<N> + 'a,
+    G: GraphTrait<N, E>,
+{
+    by_time(dfs(g, root, edge_generator, false).last_visit_times)
+}
+
+/// vertex identifiers sorted by visit time
+fn by_time(times: HashMap<String, usize>) -> Vec<String> {
+    let mut vs: Vec<(usize, String)> = times.into_iter().map(|(v, t)| (t, v)).collect();
+    vs.sort();
+    vs.into_iter().map(|(_, v)| v).collect()
+}
+
+/// Order vertices by degeneracy
+/// # Description
+/// Repeatedly removes a vertex of smallest degree in the remaining graph,
+/// the one with the smallest identifier among ties. Every vertex has at
+/// most `d` neighbors later in the order, `d` being the degeneracy of the
+/// graph, which is what greedy coloring and clique enumeration rely on.
+/// Edge orientation is ignored, parallel edges count as a single neighbor
+/// and self loops are not taken into account.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::order::degeneracy_order;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "c", "a");
+/// let e4 = Edge::empty("e4", EdgeType::Undirected, "c", "d");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3, e4]));
+/// degeneracy_order(&g) == vec!["d", "a", "b", "c"]; // true
+/// ```
+///
+/// # References
+/// Matula D, Beck L. Smallest-last ordering and clustering and graph
+/// coloring algorithms. 1983.
+pub fn degeneracy_order<N, E, G>(g: &G) -> Vec<String>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let neighbors = simple_neighbors(g);
+    let mut degree: HashMap<&String, usize> =
+        neighbors.iter().map(|(v, ns)| (*v, ns.len())).collect();
+    let mut queue: BTreeSet<(usize, &String)> = degree.iter().map(|(v, d)| (*d, *v)).collect();
+    let mut removed: HashSet<&String> = HashSet::new();
+    let mut order = Vec::with_capacity(neighbors.len());
+    while let Some((_, v)) = queue.pop_first() {
+        removed.insert(v);
+        order.push(v.clone());
+        for w in &neighbors[v] {
+            if removed.contains(*w) {
+                continue;
+            }
+            let dw = degree.get_mut(w).unwrap();
+            queue.remove(&(*dw, *w));
+            *dw -= 1;
+            queue.insert((*dw, *w));
+        }
+    }
+    order
+}
+
+/// Order vertices for elimination by maximum cardinality search
+/// # Description
+/// Maximum cardinality search visits next the vertex with the most
+/// visited neighbors, the one with the smallest identifier among ties. The
+/// output is the reverse of the visit order, so that vertices are
+/// eliminated first to last. It is a perfect elimination ordering, one
+/// with no [fill_in], exactly when the graph is chordal, see
+/// [is_chordal](crate::graph::ops::graph::recognition::is_chordal), and a
+/// cheap heuristic elimination ordering otherwise. Edge orientation is
+/// ignored, parallel edges count as a single neighbor and self loops are
+/// not taken into account.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::order::mcs_ordering;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "c", "a");
+/// let e4 = Edge::empty("e4", EdgeType::Undirected, "c", "d");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3, e4]));
+/// mcs_ordering(&g) == vec!["d", "c", "b", "a"]; // true
+/// ```
+///
+/// # References
+/// Tarjan R, Yannakakis M. Simple linear-time algorithms to test
+/// chordality of graphs, test acyclicity of hypergraphs, and selectively
+/// reduce acyclic hypergraphs. 1984.
+pub fn mcs_ordering<N, E, G>(g: &G) -> Vec<String>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let neighbors = simple_neighbors(g);
+    let mut weight: HashMap<&String, usize> = neighbors.keys().map(|v| (*v, 0)).collect();
+    let mut queue: BTreeSet<(Reverse<usize>, &String)> =
+        neighbors.keys().map(|v| (Reverse(0), *v)).collect();
+    let mut order = Vec::with_capacity(neighbors.len());
+    while let Some((_, v)) = queue.pop_first() {
+        weight.remove(v);
+        order.push(v.clone());
+        for w in &neighbors[v] {
+            if let Some(ww) = weight.get_mut(w) {
+                queue.remove(&(Reverse(*ww), *w));
+                *ww += 1;
+                queue.insert((Reverse(*ww), *w));
+            }
+        }
+    }
+    order.reverse();
+    order
+}
+
+/// Fill-in edges of an elimination ordering
+/// # Description
+/// Eliminating a vertex joins all its neighbors not yet eliminated. The
+/// edges added this way over the whole `order` are its fill-in, whose
+/// number measures how far the ordering is from a perfect elimination
+/// ordering: it triangulates the graph. Every edge is given by the
+/// identifiers of its end vertices, smaller first, and edges are sorted.
+/// Edge orientation is ignored, parallel edges count as a single neighbor
+/// and self loops are not taken into account. Panics if `order` is not an
+/// ordering of the vertices of `g`.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - order: identifiers of the vertices of `g` in elimination order, such
+///   as [mcs_ordering] or [degeneracy_order]
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::order::fill_in;
+/// use std::collections::HashSet;
+///
+/// // a square a b c d
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "c", "d");
+/// let e4 = Edge::empty("e4", EdgeType::Undirected, "d", "a");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3, e4]));
+/// let order = vec!["a".to_string(), "b".to_string(), "c".to_string(), "d".to_string()];
+/// fill_in(&g, &order) == vec![("b".to_string(), "d".to_string())]; // true
+/// ```
+///
+/// # References
+/// Rose D, Tarjan R, Lueker G. Algorithmic aspects of vertex elimination
+/// on graphs. 1976.
+pub fn fill_in<N, E, G>(g: &G, order: &[String]) -> Vec<(String, String)>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let mut neighbors = simple_neighbors(g);
+    let mut position: HashMap<&String, usize> = HashMap::new();
+    for (i, v) in order.iter().enumerate() {
+        let Some((v, _)) = neighbors.get_key_value(v) else {
+            panic!("{v} not in {g}");
+        };
+        if position.insert(*v, i).is_some() {
+            panic!("{v} appears twice in the ordering");
+        }
+    }
+    if position.len() != neighbors.len() {
+        panic!("ordering misses vertices of {g}");
+    }
+    let mut filled = Vec::new();
+    for (i, v) in order.iter().enumerate() {
+        let mut later: Vec<&String> = neighbors[v]
+            .iter()
+            .filter(|w| position[*w] > i)
+            .copied()
+            .collect();
+        later.sort();
+        for (j, a) in later.iter().enumerate() {
+            for b in &later[j + 1..] {
+                if neighbors.get_mut(a).unwrap().insert(b) {
+                    neighbors.get_mut(b).unwrap().insert(a);
+                    filled.push(((*a).clone(), (*b).clone()));
+                }
+            }
+        }
+    }
+    filled.sort();
+    filled
+}
+
+/// Neighbors of every vertex ignoring orientation, parallel edges and self
+/// loops
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::order::simple_neighbors;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Directed, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "a");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "b", "b");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// simple_neighbors(&g)[&"b".to_string()].len() == 1; // true
+/// ```
+pub fn simple_neighbors<'a, N, E, G>(g: &'a G) -> HashMap<&'a String, HashSet<&'a String>>
+where
+    N: NodeTrait + 'a,
+    E: EdgeTrait<N> + 'a,
+    G: GraphTrait<N, E>,
+{
+    let mut neighbors: HashMap<&String, HashSet<&String>> = g
+        .vertices()
+        .into_iter()
+        .map(|v| (v.id(), HashSet::new()))
+        .collect();
+    for e in g.edges() {
+        let (s, t) = (e.start().id(), e.end().id());
+        if s != t {
+            neighbors.get_mut(s).unwrap().insert(t);
+            neighbors.get_mut(t).unwrap().insert(s);
+        }
+    }
+    neighbors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::edgetype::EdgeType;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+
+    fn mk_uedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Undirected, n1_id, n2_id)
+    }
+
+    /// a - b - c - a triangle with a tail c - d - e and an isolated f
+    fn mk_g() -> Graph<Node, Edge<Node>> {
+        let es = HashSet::from([
+            mk_uedge("a", "b", "e1"),
+            mk_uedge("b", "c", "e2"),
+            mk_uedge("c", "a", "e3"),
+            mk_uedge("c", "d", "e4"),
+            mk_uedge("d", "e", "e5"),
+        ]);
+        Graph::from_edge_node_set(es, HashSet::from([Node::empty("f")]))
+    }
+
+    fn ids(v: Vec<&str>) -> Vec<String> {
+        v.into_iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_search_orders() {
+        let g = mk_g();
+        let c = Node::empty("c");
+        let gen = EdgeNeighborhood::Both;
+        assert_eq!(bfs_order(&g, &c, &gen), ids(vec!["c", "b", "a", "d", "e"]));
+        assert_eq!(
+            dfs_order(&g, &c, &gen),
+            ids(vec!["c", "b", "a", "d", "e", "f"])
+        );
+        assert_eq!(
+            dfs_finish_order(&g, &c, &gen),
+            ids(vec!["a", "b", "e", "d", "c", "f"])
+        );
+    }
+
+    #[test]
+    fn test_degeneracy_order() {
+        let g = mk_g();
+        let order = degeneracy_order(&g);
+        assert_eq!(order, ids(vec!["f", "e", "d", "a", "b", "c"]));
+        // every vertex has at most 2 neighbors later in the order
+        let neighbors = simple_neighbors(&g);
+        for (i, v) in order.iter().enumerate() {
+            let later = order[i + 1..]
+                .iter()
+                .filter(|w| neighbors[v].contains(w))
+                .count();
+            assert!(later <= 2);
+        }
+    }
+
+    #[test]
+    fn test_mcs_ordering() {
+        let g = mk_g();
+        let order = mcs_ordering(&g);
+        assert_eq!(order, ids(vec!["f", "e", "d", "c", "b", "a"]));
+        assert!(fill_in(&g, &order).is_empty());
+        // a square with a pendant vertex needs a single chord
+        let square = Graph::from_edgeset(HashSet::from([
+            mk_uedge("a", "b", "e1"),
+            mk_uedge("b", "c", "e2"),
+            mk_uedge("c", "d", "e3"),
+            mk_uedge("d", "a", "e4"),
+            mk_uedge("d", "x", "e5"),
+        ]));
+        assert_eq!(fill_in(&square, &mcs_ordering(&square)).len(), 1);
+    }
+
+    #[test]
+    fn test_fill_in() {
+        let square = Graph::from_edgeset(HashSet::from([
+            mk_uedge("a", "b", "e1"),
+            mk_uedge("b", "c", "e2"),
+            mk_uedge("c", "d", "e3"),
+            mk_uedge("d", "a", "e4"),
+        ]));
+        assert_eq!(
+            fill_in(&square, &ids(vec!["b", "a", "c", "d"])),
+            vec![("a".to_string(), "c".to_string())]
+        );
+        // eliminating the center of a star first joins all its leaves
+        let star = Graph::from_edgeset(HashSet::from([
+            mk_uedge("z", "a", "e1"),
+            mk_uedge("z", "b", "e2"),
+            mk_uedge("z", "c", "e3"),
+        ]));
+        assert_eq!(fill_in(&star, &ids(vec!["z", "a", "b", "c"])).len(), 3);
+        assert!(fill_in(&star, &ids(vec!["a", "b", "c", "z"])).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_fill_in_partial_order() {
+        fill_in(&mk_g(), &ids(vec!["a", "b"]));
+    }
+}
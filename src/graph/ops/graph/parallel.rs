@@ -0,0 +1,146 @@
+//! data parallel operations over graphs
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::maps::NodeMap;
+use std::thread;
+
+/// Apply a function to every vertex of a graph on several threads
+/// # Description
+/// Vertices are sorted by identifier and split into contiguous chunks, one
+/// per thread, and each thread applies `f` to its chunk while borrowing the
+/// graph. This needs the graph and its vertices to be `Sync`, which holds
+/// for the graph types of the crate, see
+/// [ArcGraph](crate::graph::types::shared::ArcGraph). Typical uses are
+/// per vertex analytics such as local searches from every vertex. A panic
+/// in `f` is propagated to the caller.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - threads: number of threads, `0` meaning the available parallelism of
+///   the machine
+/// - f: function of a vertex
+/// - returns: the value of `f` for every vertex identifier
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::node::neighbors_of;
+/// use pgm_rust::graph::ops::graph::parallel::par_map_nodes;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+/// let degrees = par_map_nodes(&g, 2, |v| neighbors_of(&g, v).len());
+/// degrees["b"] == 2; // true
+/// degrees["c"] == 1; // true
+/// ```
+pub fn par_map_nodes<N, E, G, T, F>(g: &G, threads: usize, f: F) -> NodeMap<T>
+where
+    N: NodeTrait + Sync,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E> + Sync,
+    T: Send,
+    F: Fn(&N) -> T + Sync,
+{
+    let threads = match threads {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        t => t,
+    };
+    let vertices = g.vertices_sorted();
+    if vertices.is_empty() {
+        return NodeMap::new();
+    }
+    let chunk_size = vertices.len().div_ceil(threads);
+    let f = &f;
+    thread::scope(|scope| {
+        let handles: Vec<_> = vertices
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|v| (v.id().clone(), f(v)))
+                        .collect::<Vec<(String, T)>>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|h| match h.join() {
+                Err(e) => std::panic::resume_unwind(e),
+                Ok(values) => values,
+            })
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::ops::graph::bounded::bounded_bfs;
+    use crate::graph::ops::graph::bounded::TraversalLimits;
+    use crate::graph::ops::graph::search::EdgeNeighborhood;
+    use crate::graph::traits::graph_obj::GraphObject;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::edgetype::EdgeType;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+    use crate::graph::types::shared::ArcGraph;
+    use std::collections::HashMap;
+    use std::collections::HashSet;
+
+    fn mk_chain(n: usize) -> Graph<Node, Edge<Node>> {
+        let es: HashSet<Edge<Node>> = (1..n)
+            .map(|i| {
+                Edge::empty(
+                    &format!("e{i}"),
+                    EdgeType::Directed,
+                    &format!("v{:02}", i - 1),
+                    &format!("v{i:02}"),
+                )
+            })
+            .collect();
+        Graph::from_edgeset(es)
+    }
+
+    #[test]
+    fn test_par_map_nodes_matches_sequential() {
+        let g = ArcGraph::new(mk_chain(30));
+        let reach = |v: &Node| {
+            bounded_bfs(&g, v, &EdgeNeighborhood::Out, &TraversalLimits::default())
+                .visited
+                .len()
+        };
+        let sequential: HashMap<String, usize> = g
+            .vertices()
+            .into_iter()
+            .map(|v| (v.id().clone(), reach(v)))
+            .collect();
+        for threads in [0, 1, 4, 64] {
+            assert_eq!(par_map_nodes(&g, threads, reach), sequential);
+        }
+        assert_eq!(sequential["v00"], 30);
+    }
+
+    #[test]
+    fn test_par_map_nodes_empty() {
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::new());
+        assert!(par_map_nodes(&g, 4, |v| v.id().len()).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_par_map_nodes_propagates_panics() {
+        let g = mk_chain(4);
+        par_map_nodes(&g, 2, |v| {
+            if v.id() == "v02" {
+                panic!("fails on v02");
+            }
+        });
+    }
+}
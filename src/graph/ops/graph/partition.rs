@@ -0,0 +1,526 @@
+//! balanced graph partitioning
+use crate::graph::ops::graph::coarsen::coarsen;
+use crate::graph::ops::graph::coarsen::weight_of;
+use crate::graph::ops::graph::coarsen::MatchingStrategy;
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::maps::NodeMap;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::collections::BinaryHeap;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+
+/// coarsening stops before a level has fewer vertices than this times the
+/// number of parts
+const COARSEST_VERTICES_PER_PART: usize = 10;
+
+/// largest number of refinement passes per level
+const MAX_PASSES: usize = 8;
+
+/// a refinement pass stops after this many moves without improving the cut
+const MAX_FRUITLESS_MOVES: usize = 64;
+
+/// output of [partition]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Partition {
+    /// part, in `0..k`, of every vertex identifier
+    pub parts: NodeMap<usize>,
+    /// total weight of the edges whose end vertices lie in different parts
+    pub cut_size: f64,
+    /// total weight of the vertices of every part
+    pub part_weights: Vec<f64>,
+}
+
+/// vertex weights and merged undirected adjacency of one level
+struct Level {
+    /// vertex identifiers sorted
+    ids: Vec<String>,
+    weights: Vec<f64>,
+    /// neighbors with the total weight of the edges joining them, sorted
+    adjacency: Vec<Vec<(usize, f64)>>,
+}
+
+/// a pending move of `vertex` to part `to`, ordered by gain
+struct Move {
+    gain: f64,
+    vertex: usize,
+    to: usize,
+    stamp: usize,
+}
+
+impl PartialEq for Move {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Move {}
+
+impl PartialOrd for Move {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Move {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // larger gains first, then smaller vertices
+        self.gain
+            .total_cmp(&other.gain)
+            .then_with(|| other.vertex.cmp(&self.vertex))
+    }
+}
+
+impl Level {
+    fn new<N, E, G>(g: &G) -> Level
+    where
+        N: NodeTrait,
+        E: EdgeTrait<N>,
+        G: GraphTrait<N, E>,
+    {
+        let vertices = g.vertices_sorted();
+        let ids: Vec<String> = vertices.iter().map(|v| v.id().clone()).collect();
+        let weights: Vec<f64> = vertices.into_iter().map(weight_of).collect();
+        let index: HashMap<&String, usize> = ids.iter().enumerate().map(|(i, v)| (v, i)).collect();
+        let mut merged: Vec<BTreeMap<usize, f64>> = vec![BTreeMap::new(); ids.len()];
+        for e in g.edges() {
+            let (i, j) = (index[e.start().id()], index[e.end().id()]);
+            if i != j {
+                let w = weight_of(e);
+                *merged[i].entry(j).or_default() += w;
+                *merged[j].entry(i).or_default() += w;
+            }
+        }
+        let adjacency = merged
+            .into_iter()
+            .map(|m| m.into_iter().collect())
+            .collect();
+        Level {
+            ids,
+            weights,
+            adjacency,
+        }
+    }
+
+    /// weight of the edges from `v` to each part it is adjacent to
+    fn connections(&self, v: usize, parts: &[usize]) -> BTreeMap<usize, f64> {
+        let mut conn: BTreeMap<usize, f64> = BTreeMap::new();
+        for (u, w) in &self.adjacency[v] {
+            *conn.entry(parts[*u]).or_default() += w;
+        }
+        conn
+    }
+
+    /// best move of `v` to an adjacent part that stays within `max_weight`,
+    /// as (cut decrease, part)
+    fn best_move(
+        &self,
+        v: usize,
+        parts: &[usize],
+        part_weights: &[f64],
+        max_weight: f64,
+    ) -> Option<(f64, usize)> {
+        let conn = self.connections(v, parts);
+        let internal = conn.get(&parts[v]).copied().unwrap_or(0.0);
+        conn.iter()
+            .filter(|(q, _)| **q != parts[v] && part_weights[**q] + self.weights[v] <= max_weight)
+            .fold(None, |best, (q, c)| match best {
+                Some((b, _)) if b >= c - internal => best,
+                _ => Some((c - internal, *q)),
+            })
+    }
+
+    fn part_weights(&self, parts: &[usize], k: usize) -> Vec<f64> {
+        let mut part_weights = vec![0.0; k];
+        for (v, p) in parts.iter().enumerate() {
+            part_weights[*p] += self.weights[v];
+        }
+        part_weights
+    }
+
+    /// grow parts one after the other along a breadth first order, cutting
+    /// it where the cumulated weight crosses multiples of the average part
+    /// weight
+    fn initial_parts(&self, k: usize) -> Vec<usize> {
+        let n = self.ids.len();
+        let target = self.weights.iter().sum::<f64>() / k as f64;
+        let mut parts = vec![0; n];
+        let mut seen = vec![false; n];
+        let mut cumulated = 0.0;
+        for root in 0..n {
+            if seen[root] {
+                continue;
+            }
+            seen[root] = true;
+            let mut queue = VecDeque::from([root]);
+            while let Some(v) = queue.pop_front() {
+                let middle = cumulated + self.weights[v] / 2.0;
+                parts[v] = ((middle / target) as usize).min(k - 1);
+                cumulated += self.weights[v];
+                for (u, _) in &self.adjacency[v] {
+                    if !seen[*u] {
+                        seen[*u] = true;
+                        queue.push_back(*u);
+                    }
+                }
+            }
+        }
+        parts
+    }
+
+    /// move vertices out of parts heavier than `max_weight`, choosing the
+    /// moves that increase the cut the least
+    fn rebalance(&self, parts: &mut [usize], part_weights: &mut [f64], max_weight: f64) {
+        loop {
+            let heaviest = (0..part_weights.len()).fold(0, |h, p| {
+                if part_weights[p] > part_weights[h] {
+                    p
+                } else {
+                    h
+                }
+            });
+            if part_weights[heaviest] <= max_weight {
+                return;
+            }
+            let mut best: Option<(f64, usize, usize)> = None;
+            for v in (0..parts.len()).filter(|v| parts[*v] == heaviest) {
+                let conn = self.connections(v, parts);
+                let internal = conn.get(&heaviest).copied().unwrap_or(0.0);
+                for (q, w) in part_weights.iter().enumerate() {
+                    if q == heaviest || w + self.weights[v] > max_weight {
+                        continue;
+                    }
+                    let gain = conn.get(&q).copied().unwrap_or(0.0) - internal;
+                    if best.is_none_or(|(b, _, _)| gain > b) {
+                        best = Some((gain, v, q));
+                    }
+                }
+            }
+            match best {
+                // the remaining vertices are too heavy to move
+                None => return,
+                Some((_, v, q)) => {
+                    part_weights[heaviest] -= self.weights[v];
+                    part_weights[q] += self.weights[v];
+                    parts[v] = q;
+                }
+            }
+        }
+    }
+
+    /// Fiduccia–Mattheyses passes: vertices are moved once per pass by best
+    /// gain, even when it is negative, and the pass is rolled back to its
+    /// best balanced prefix. Within a pass parts may exceed `max_weight` by
+    /// one vertex, which lets tightly balanced parts exchange vertices.
+    fn refine(&self, parts: &mut [usize], part_weights: &mut [f64], max_weight: f64) {
+        let n = self.ids.len();
+        let limit = max_weight + self.weights.iter().cloned().fold(0.0, f64::max);
+        let balanced = |part_weights: &[f64]| part_weights.iter().all(|w| *w <= max_weight);
+        for _ in 0..MAX_PASSES {
+            let mut locked = vec![false; n];
+            let mut stamps = vec![0; n];
+            let mut heap: BinaryHeap<Move> = BinaryHeap::new();
+            for v in 0..n {
+                if let Some((gain, to)) = self.best_move(v, parts, part_weights, limit) {
+                    heap.push(Move {
+                        gain,
+                        vertex: v,
+                        to,
+                        stamp: 0,
+                    });
+                }
+            }
+            let mut log: Vec<(usize, usize)> = Vec::new();
+            let (mut total, mut best, mut best_len) = (0.0, 0.0, 0);
+            while let Some(m) = heap.pop() {
+                let v = m.vertex;
+                if locked[v] || stamps[v] != m.stamp {
+                    continue;
+                }
+                if log.len() - best_len > MAX_FRUITLESS_MOVES {
+                    break;
+                }
+                // part weights may have changed since the move was pushed
+                if part_weights[m.to] + self.weights[v] > limit {
+                    stamps[v] += 1;
+                    if let Some((gain, to)) = self.best_move(v, parts, part_weights, limit) {
+                        heap.push(Move {
+                            gain,
+                            vertex: v,
+                            to,
+                            stamp: stamps[v],
+                        });
+                    }
+                    continue;
+                }
+                let from = parts[v];
+                parts[v] = m.to;
+                part_weights[from] -= self.weights[v];
+                part_weights[m.to] += self.weights[v];
+                locked[v] = true;
+                log.push((v, from));
+                total += m.gain;
+                if total > best && balanced(part_weights) {
+                    best = total;
+                    best_len = log.len();
+                }
+                for (u, _) in &self.adjacency[v] {
+                    if locked[*u] {
+                        continue;
+                    }
+                    stamps[*u] += 1;
+                    if let Some((gain, to)) = self.best_move(*u, parts, part_weights, limit) {
+                        heap.push(Move {
+                            gain,
+                            vertex: *u,
+                            to,
+                            stamp: stamps[*u],
+                        });
+                    }
+                }
+            }
+            for (v, from) in log.drain(best_len..).rev() {
+                part_weights[parts[v]] -= self.weights[v];
+                part_weights[from] += self.weights[v];
+                parts[v] = from;
+            }
+            if best_len == 0 {
+                return;
+            }
+        }
+    }
+
+    fn cut_size(&self, parts: &[usize]) -> f64 {
+        let mut cut = 0.0;
+        for (v, neighbors) in self.adjacency.iter().enumerate() {
+            for (u, w) in neighbors {
+                if v < *u && parts[v] != parts[*u] {
+                    cut += w;
+                }
+            }
+        }
+        cut
+    }
+}
+
+/// Partition a graph into `k` balanced parts with few cut edges
+/// # Description
+/// Multilevel scheme: the graph is [coarsen]ed by heavy edge matching until
+/// it has about ten vertices per part, the coarsest graph is split by
+/// growing parts along a breadth first order, and the assignment is then
+/// projected back level by level, each level being rebalanced and refined
+/// with Fiduccia–Mattheyses passes. Vertex and edge weights are read under
+/// [WEIGHT_KEY], a missing weight counting as 1. Edge orientation is
+/// ignored and loops never count in the cut.
+///
+/// A part weighs at most `(1 + max_imbalance) W / k`, where `W` is the
+/// total vertex weight, or the ceiling of `W / k` if it is larger, so that
+/// unit weights can always be balanced. The bound may be exceeded when
+/// vertex weights make it unreachable. The output is deterministic.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - k: number of parts, at least 1
+/// - max_imbalance: allowed relative excess of a part over the average
+///   part weight, non negative
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::partition::partition;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "c", "a");
+/// let e4 = Edge::empty("e4", EdgeType::Undirected, "c", "d");
+/// let e5 = Edge::empty("e5", EdgeType::Undirected, "d", "e");
+/// let e6 = Edge::empty("e6", EdgeType::Undirected, "e", "f");
+/// let e7 = Edge::empty("e7", EdgeType::Undirected, "f", "d");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3, e4, e5, e6, e7]));
+/// let p = partition(&g, 2, 0.0);
+/// p.cut_size == 1.0; // true: only e4 is cut
+/// p.parts["a"] == p.parts["c"]; // true
+/// p.part_weights == vec![3.0, 3.0]; // true
+/// ```
+///
+/// # References
+/// Fiduccia C, Mattheyses R. A linear-time heuristic for improving network
+/// partitions. 1982.
+///
+/// Karypis G, Kumar V. A fast and high quality multilevel scheme for
+/// partitioning irregular graphs. 1998.
+///
+/// [WEIGHT_KEY]: crate::graph::io::edgelist::WEIGHT_KEY
+pub fn partition<N, E, G>(g: &G, k: usize, max_imbalance: f64) -> Partition
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    if k == 0 {
+        panic!("cannot partition {g} into 0 parts");
+    }
+    if max_imbalance.is_nan() || max_imbalance < 0.0 {
+        panic!("imbalance {max_imbalance} must be non negative");
+    }
+    let hierarchy = coarsen(g, usize::MAX, &MatchingStrategy::HeavyEdge);
+    let depth = hierarchy
+        .levels
+        .iter()
+        .take_while(|l| l.graph.order() >= COARSEST_VERTICES_PER_PART * k)
+        .count();
+    let mut levels: Vec<Level> = vec![Level::new(g)];
+    levels.extend(
+        hierarchy.levels[..depth]
+            .iter()
+            .map(|l| Level::new(&l.graph)),
+    );
+    let total: f64 = levels[0].weights.iter().sum();
+    let max_weight = ((1.0 + max_imbalance) * total / k as f64).max((total / k as f64).ceil());
+
+    let coarsest = &levels[depth];
+    let mut parts = coarsest.initial_parts(k);
+    for d in (0..=depth).rev() {
+        let level = &levels[d];
+        if d < depth {
+            let coarse = &levels[d + 1];
+            let coarse_index: HashMap<&String, usize> =
+                coarse.ids.iter().enumerate().map(|(i, v)| (v, i)).collect();
+            let mapping = &hierarchy.levels[d].mapping;
+            parts = level
+                .ids
+                .iter()
+                .map(|v| parts[coarse_index[&mapping[v]]])
+                .collect();
+        }
+        let mut part_weights = level.part_weights(&parts, k);
+        level.rebalance(&mut parts, &mut part_weights, max_weight);
+        level.refine(&mut parts, &mut part_weights, max_weight);
+    }
+    let finest = &levels[0];
+    Partition {
+        cut_size: finest.cut_size(&parts),
+        part_weights: finest.part_weights(&parts, k),
+        parts: finest.ids.iter().cloned().zip(parts).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::traits::graph_obj::GraphObject;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::edgetype::EdgeType;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+    use std::collections::HashSet;
+
+    fn mk_uedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Undirected, n1_id, n2_id)
+    }
+
+    /// `rows` by `cols` grid
+    fn mk_grid(rows: usize, cols: usize) -> Graph<Node, Edge<Node>> {
+        let name = |r: usize, c: usize| format!("v{r:02}_{c:02}");
+        let mut es = HashSet::new();
+        for r in 0..rows {
+            for c in 0..cols {
+                if r + 1 < rows {
+                    es.insert(mk_uedge(&name(r, c), &name(r + 1, c), &format!("d{r}_{c}")));
+                }
+                if c + 1 < cols {
+                    es.insert(mk_uedge(&name(r, c), &name(r, c + 1), &format!("r{r}_{c}")));
+                }
+            }
+        }
+        Graph::from_edgeset(es)
+    }
+
+    /// `k` cliques of `size` vertices joined in a ring by single edges
+    fn mk_ring_of_cliques(k: usize, size: usize) -> Graph<Node, Edge<Node>> {
+        let mut es = HashSet::new();
+        for q in 0..k {
+            for i in 0..size {
+                for j in i + 1..size {
+                    es.insert(mk_uedge(
+                        &format!("c{q}_{i}"),
+                        &format!("c{q}_{j}"),
+                        &format!("e{q}_{i}_{j}"),
+                    ));
+                }
+            }
+            let next = (q + 1) % k;
+            es.insert(mk_uedge(
+                &format!("c{q}_0"),
+                &format!("c{next}_1"),
+                &format!("ring{q}"),
+            ));
+        }
+        Graph::from_edgeset(es)
+    }
+
+    fn check_consistency(g: &Graph<Node, Edge<Node>>, p: &Partition, k: usize) {
+        assert_eq!(p.parts.len(), g.order());
+        assert!(p.parts.values().all(|q| *q < k));
+        let cut = g
+            .edges()
+            .into_iter()
+            .filter(|e| p.parts[e.start().id()] != p.parts[e.end().id()])
+            .count();
+        assert_eq!(p.cut_size, cut as f64);
+        let total: f64 = p.part_weights.iter().sum();
+        assert_eq!(total, g.order() as f64);
+    }
+
+    #[test]
+    fn test_partition_ring_of_cliques() {
+        let g = mk_ring_of_cliques(4, 6);
+        let p = partition(&g, 4, 0.0);
+        check_consistency(&g, &p, 4);
+        assert_eq!(p.cut_size, 4.0);
+        assert_eq!(p.part_weights, vec![6.0; 4]);
+        for q in 0..4 {
+            let part = p.parts[&format!("c{q}_0")];
+            assert!((1..6).all(|i| p.parts[&format!("c{q}_{i}")] == part));
+        }
+    }
+
+    #[test]
+    fn test_partition_grid_balance() {
+        let g = mk_grid(12, 12);
+        for (k, imbalance) in [(2, 0.0), (3, 0.05), (4, 0.1)] {
+            let p = partition(&g, k, imbalance);
+            check_consistency(&g, &p, k);
+            let max_weight = ((1.0 + imbalance) * 144.0 / k as f64).max((144.0 / k as f64).ceil());
+            assert!(p.part_weights.iter().all(|w| *w <= max_weight));
+            // a straight cut of the grid crosses 12 edges per part boundary
+            assert!(p.cut_size <= 12.0 * (k as f64) * 1.5);
+        }
+        let p1 = partition(&g, 2, 0.0);
+        let p2 = partition(&g, 2, 0.0);
+        assert_eq!(p1, p2);
+    }
+
+    #[test]
+    fn test_partition_trivial() {
+        let g = mk_grid(3, 3);
+        let one = partition(&g, 1, 0.0);
+        assert_eq!(one.cut_size, 0.0);
+        assert_eq!(one.part_weights, vec![9.0]);
+        let many = partition(&g, 12, 0.0);
+        check_consistency(&g, &many, 12);
+        assert!(many.part_weights.iter().all(|w| *w <= 1.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_partition_zero_parts() {
+        partition(&mk_grid(2, 2), 0, 0.0);
+    }
+}
@@ -0,0 +1,1338 @@
+//! path finding operations
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::idgen::IdGenerator;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::edgetype::EdgeType;
+use crate::graph::types::idgen::UuidGenerator;
+use crate::graph::types::maps::NodeMap;
+use crate::graph::types::path::Path;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::Index;
+
+/// adjacency of one search direction: vertex id to (neighbor, edge) pairs
+type Adjacency<'a, N, E> = HashMap<&'a String, Vec<(&'a N, &'a E)>>;
+
+/// adjacency followed by the forward search and by the backward search.
+/// Directed edges are followed along their orientation forward and against it
+/// backward, undirected edges are followed both ways in both searches.
+fn directed_adjacency<'a, N, E, G>(g: &'a G) -> (Adjacency<'a, N, E>, Adjacency<'a, N, E>)
+where
+    N: NodeTrait,
+    E: EdgeTrait<N> + 'a,
+    G: GraphTrait<N, E>,
+{
+    let mut forward: Adjacency<'a, N, E> = HashMap::new();
+    let mut backward: Adjacency<'a, N, E> = HashMap::new();
+    for e in g.edges_sorted() {
+        let (s, t) = (e.start(), e.end());
+        forward.entry(s.id()).or_default().push((t, e));
+        backward.entry(t.id()).or_default().push((s, e));
+        if e.has_type() == &EdgeType::Undirected {
+            forward.entry(t.id()).or_default().push((s, e));
+            backward.entry(s.id()).or_default().push((t, e));
+        }
+    }
+    (forward, backward)
+}
+
+/// expand every vertex of the frontier by one hop. Returns the new frontier
+/// and the best meeting vertex found with the other search, if any.
+fn expand_level<'a, N, E>(
+    frontier: &[&'a N],
+    adjacency: &Adjacency<'a, N, E>,
+    dist: &mut HashMap<&'a String, usize>,
+    pred: &mut HashMap<&'a String, (&'a N, &'a E)>,
+    other_dist: &HashMap<&'a String, usize>,
+) -> (Vec<&'a N>, Option<(&'a N, usize)>)
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+{
+    let mut next: Vec<&N> = Vec::new();
+    let mut best: Option<(&N, usize)> = None;
+    for u in frontier {
+        let du = dist[u.id()];
+        for (v, e) in adjacency.get(u.id()).into_iter().flatten() {
+            if dist.contains_key(v.id()) {
+                continue;
+            }
+            dist.insert(v.id(), du + 1);
+            pred.insert(v.id(), (*u, *e));
+            next.push(v);
+            if let Some(dv) = other_dist.get(v.id()) {
+                let total = du + 1 + dv;
+                if best.is_none_or(|(_, b)| total < b) {
+                    best = Some((v, total));
+                }
+            }
+        }
+    }
+    (next, best)
+}
+
+/// Find a shortest path with a bidirectional breadth first search
+/// # Description
+/// Two breadth first searches are run, one from `s` along the edges and one
+/// from `t` against them. At each step the search with the smaller frontier
+/// is expanded by one level, and the search stops as soon as the two meet.
+/// On sparse graphs this visits far fewer vertices than a single search from
+/// `s`. The length of a path is its number of edges, edge weights are not
+/// taken into account. Directed edges are followed along their orientation
+/// only. Among shortest paths, the one found first in edge identifier order
+/// is returned.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - s: start vertex
+/// - t: end vertex
+/// - returns: a shortest path from `s` to `t`, `None` if `t` is not reachable
+///   from `s`
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::path::Path as PathTrait;
+/// use pgm_rust::graph::ops::graph::pathops::bidirectional_shortest_path;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "n1", "n2");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "n2", "n3");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "n3", "n4");
+/// let e4 = Edge::empty("e4", EdgeType::Undirected, "n1", "n4");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3, e4]));
+/// let p = bidirectional_shortest_path(&g, &Node::empty("n1"), &Node::empty("n3")).unwrap();
+/// p.length() == 2; // true
+/// ```
+///
+/// # References
+/// Pohl I. Bi-directional search. 1971.
+pub fn bidirectional_shortest_path<N, E, G>(g: &G, s: &N, t: &N) -> Option<Path<N, E, G>>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N> + Clone,
+    G: GraphTrait<N, E>,
+{
+    bidirectional_shortest_path_with(g, s, t, &mut UuidGenerator)
+}
+
+/// Find a shortest path with a bidirectional breadth first search, the
+/// identifier of the path is produced by `idgen`, see
+/// [bidirectional_shortest_path]
+pub fn bidirectional_shortest_path_with<N, E, G, I>(
+    g: &G,
+    s: &N,
+    t: &N,
+    idgen: &mut I,
+) -> Option<Path<N, E, G>>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N> + Clone,
+    G: GraphTrait<N, E>,
+    I: IdGenerator,
+{
+    let vertices = g.vertices();
+    let source: &N = match vertices.get(s) {
+        None => panic!("{s} not in {g}"),
+        Some(v) => v,
+    };
+    let target: &N = match vertices.get(t) {
+        None => panic!("{t} not in {g}"),
+        Some(v) => v,
+    };
+    let (forward, backward) = directed_adjacency(g);
+    let mut dist_f: HashMap<&String, usize> = HashMap::from([(source.id(), 0)]);
+    let mut dist_b: HashMap<&String, usize> = HashMap::from([(target.id(), 0)]);
+    let mut pred_f: HashMap<&String, (&N, &E)> = HashMap::new();
+    let mut pred_b: HashMap<&String, (&N, &E)> = HashMap::new();
+    let mut frontier_f: Vec<&N> = vec![source];
+    let mut frontier_b: Vec<&N> = vec![target];
+    let mut meeting: Option<&N> = if source == target { Some(source) } else { None };
+    while meeting.is_none() && !frontier_f.is_empty() && !frontier_b.is_empty() {
+        let found = if frontier_f.len() <= frontier_b.len() {
+            let (next, found) =
+                expand_level(&frontier_f, &forward, &mut dist_f, &mut pred_f, &dist_b);
+            frontier_f = next;
+            found
+        } else {
+            let (next, found) =
+                expand_level(&frontier_b, &backward, &mut dist_b, &mut pred_b, &dist_f);
+            frontier_b = next;
+            found
+        };
+        meeting = found.map(|(v, _)| v);
+    }
+    let meet = meeting?;
+    // walk back to s, then forward to t
+    let mut nodes: Vec<N> = vec![meet.clone()];
+    let mut edges: Vec<E> = Vec::new();
+    let mut current = meet;
+    while let Some((p, e)) = pred_f.get(current.id()) {
+        nodes.push((*p).clone());
+        edges.push((*e).clone());
+        current = p;
+    }
+    nodes.reverse();
+    edges.reverse();
+    current = meet;
+    while let Some((n, e)) = pred_b.get(current.id()) {
+        nodes.push((*n).clone());
+        edges.push((*e).clone());
+        current = n;
+    }
+    let gid = {
+        let vs: HashSet<&N> = nodes.iter().collect();
+        let es: HashSet<&E> = edges.iter().collect();
+        idgen.generate(&vs, &es)
+    };
+    Some(Path::from_sequence(gid, HashMap::new(), nodes, edges))
+}
+
+/// vertex identifiers in identifier order together with the arcs of `g` as
+/// (start index, end index, weight) triples. Directed edges give one arc,
+/// undirected edges give an arc in each direction.
+fn weighted_arcs<'a, N, E, G, F>(g: &'a G, weight: F) -> (Vec<&'a String>, Vec<(usize, usize, f64)>)
+where
+    N: NodeTrait + 'a,
+    E: EdgeTrait<N> + 'a,
+    G: GraphTrait<N, E>,
+    F: Fn(&E) -> f64,
+{
+    let ids: Vec<&String> = g.vertices_sorted().into_iter().map(|v| v.id()).collect();
+    let index: HashMap<&String, usize> = ids.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+    let mut arcs = Vec::new();
+    for e in g.edges_sorted() {
+        let (s, t, w) = (index[e.start().id()], index[e.end().id()], weight(e));
+        arcs.push((s, t, w));
+        if e.has_type() == &EdgeType::Undirected && s != t {
+            arcs.push((t, s, w));
+        }
+    }
+    (ids, arcs)
+}
+
+/// Compute shortest path distances between all pairs of vertices
+/// # Description
+/// Floyd–Warshall algorithm in `O(V^3)`, suited to dense graphs. Directed
+/// edges are followed along their orientation, undirected edges both ways.
+/// Weights may be negative. Only pairs of vertices joined by a path are in
+/// the output, every vertex being at distance 0 of itself. Panics if the
+/// graph has a negative cycle.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - weight: weight of an edge
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph_obj::GraphObject;
+/// use pgm_rust::graph::ops::graph::pathops::floyd_warshall;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Directed, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Directed, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Directed, "a", "c");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// let d = floyd_warshall(&g, |e: &Edge<Node>| if e.id() == "e3" { 5.0 } else { 1.0 });
+/// d[("a", "c")] == 2.0; // true
+/// !d.contains("c", "a"); // true
+/// ```
+///
+/// # References
+/// Floyd R. Algorithm 97: Shortest path. 1962.
+pub fn floyd_warshall<N, E, G, F>(g: &G, weight: F) -> DistanceMap
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    F: Fn(&E) -> f64,
+{
+    let (ids, arcs) = weighted_arcs(g, weight);
+    let n = ids.len();
+    let mut dist = vec![vec![f64::INFINITY; n]; n];
+    // pred[i][j] is the vertex before j on a shortest path from i
+    let mut pred: Vec<Vec<Option<usize>>> = vec![vec![None; n]; n];
+    for (i, row) in dist.iter_mut().enumerate() {
+        row[i] = 0.0;
+    }
+    for (s, t, w) in arcs {
+        if w < dist[s][t] {
+            dist[s][t] = w;
+            pred[s][t] = Some(s);
+        }
+    }
+    for k in 0..n {
+        let row_k = dist[k].clone();
+        let pred_k = pred[k].clone();
+        for (row, pred_row) in dist.iter_mut().zip(pred.iter_mut()) {
+            let dik = row[k];
+            if dik == f64::INFINITY {
+                continue;
+            }
+            for (j, dkj) in row_k.iter().enumerate() {
+                if dik + dkj < row[j] {
+                    row[j] = dik + dkj;
+                    pred_row[j] = pred_k[j];
+                }
+            }
+        }
+    }
+    if (0..n).any(|i| dist[i][i] < 0.0) {
+        panic!("{g} has a negative cycle");
+    }
+    let mut distances = DistanceMap::new();
+    for i in 0..n {
+        distances.insert(single_source_paths(&ids, i, &dist[i], &pred[i]));
+    }
+    distances
+}
+
+/// Bellman–Ford relaxation of `arcs` starting from the distances `dist`.
+/// Returns the distances and the predecessor of every improved vertex, or a
+/// vertex of a negative cycle if distances still improve after as many
+/// rounds as there are vertices.
+#[allow(clippy::type_complexity)]
+fn relax_arcs(
+    arcs: &[(usize, usize, f64)],
+    mut dist: Vec<f64>,
+) -> Result<(Vec<f64>, Vec<Option<usize>>), (usize, Vec<Option<usize>>)> {
+    let n = dist.len();
+    let mut pred: Vec<Option<usize>> = vec![None; n];
+    for round in 0..=n {
+        let mut changed = None;
+        for (s, t, w) in arcs {
+            if dist[*s] + w < dist[*t] {
+                dist[*t] = dist[*s] + w;
+                pred[*t] = Some(*s);
+                changed = Some(*t);
+            }
+        }
+        match changed {
+            None => break,
+            Some(t) if round == n => return Err((t, pred)),
+            Some(_) => {}
+        }
+    }
+    Ok((dist, pred))
+}
+
+/// shortest paths from a single source, see [bellman_ford]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SingleSourcePaths {
+    /// identifier of the source vertex
+    pub source: String,
+    /// distance from the source of every reachable vertex
+    pub distances: NodeMap<f64>,
+    /// predecessor on a shortest path of every reachable vertex but the
+    /// source
+    pub predecessors: HashMap<String, String>,
+}
+
+impl SingleSourcePaths {
+    /// vertex identifiers of a shortest path from the source to `id`, `None`
+    /// if `id` is not reachable
+    pub fn path_to(&self, id: &str) -> Option<Vec<String>> {
+        if !self.distances.contains(id) {
+            return None;
+        }
+        let mut path = vec![id.to_string()];
+        while let Some(p) = self.predecessors.get(path.last().unwrap()) {
+            path.push(p.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Shortest path distances between all pairs of vertices
+/// # Description
+/// Output of [floyd_warshall] and [johnson]. It holds the shortest paths
+/// from every vertex as [SingleSourcePaths], so that besides the distance
+/// of a pair of vertices, given by [get](DistanceMap::get), a shortest path
+/// between them can be rebuilt with [path_to](DistanceMap::path_to). Only
+/// pairs of vertices joined by a path have an entry.
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::pathops::floyd_warshall;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Directed, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Directed, "b", "c");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+/// let d = floyd_warshall(&g, |_: &Edge<Node>| 1.0);
+/// d.get("a", "c") == Some(2.0); // true
+/// d.path_to("a", "c").unwrap() == vec!["a", "b", "c"]; // true
+/// d.len() == 6; // true
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DistanceMap {
+    paths: HashMap<String, SingleSourcePaths>,
+}
+
+impl DistanceMap {
+    /// map without any pair of vertices
+    pub fn new() -> DistanceMap {
+        DistanceMap {
+            paths: HashMap::new(),
+        }
+    }
+
+    /// distance from `start` to `end`, `None` if `end` is not reachable
+    /// from `start`
+    pub fn get(&self, start: &str, end: &str) -> Option<f64> {
+        self.paths.get(start)?.distances.get(end).copied()
+    }
+
+    /// vertex identifiers of a shortest path from `start` to `end`, `None`
+    /// if `end` is not reachable from `start`
+    pub fn path_to(&self, start: &str, end: &str) -> Option<Vec<String>> {
+        self.paths.get(start)?.path_to(end)
+    }
+
+    /// shortest paths from `start`, `None` if `start` is not a vertex
+    pub fn paths_from(&self, start: &str) -> Option<&SingleSourcePaths> {
+        self.paths.get(start)
+    }
+
+    /// true if `end` is reachable from `start`
+    pub fn contains(&self, start: &str, end: &str) -> bool {
+        self.get(start, end).is_some()
+    }
+
+    /// number of pairs of vertices joined by a path
+    pub fn len(&self) -> usize {
+        self.paths.values().map(|p| p.distances.len()).sum()
+    }
+
+    /// true if there is no pair of vertices
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// (start, end) identifiers and distances, in arbitrary order
+    pub fn iter(&self) -> impl Iterator<Item = ((&String, &String), &f64)> + '_ {
+        self.paths
+            .iter()
+            .flat_map(|(s, p)| p.distances.iter().map(move |(t, d)| ((s, t), d)))
+    }
+
+    /// (start, end) identifiers and distances sorted by identifiers
+    pub fn sorted(&self) -> Vec<((&String, &String), &f64)> {
+        let mut entries: Vec<((&String, &String), &f64)> = self.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        entries
+    }
+
+    /// add the shortest paths from their source
+    fn insert(&mut self, paths: SingleSourcePaths) {
+        self.paths.insert(paths.source.clone(), paths);
+    }
+}
+
+/// Maps are equal when they hold the same distances, the shortest paths
+/// chosen between equally distant vertices may differ
+impl PartialEq for DistanceMap {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|((s, t), d)| other.get(s, t) == Some(*d))
+    }
+}
+
+/// Indexing panics if the end is not reachable from the start, like for a
+/// [HashMap]
+impl Index<(&str, &str)> for DistanceMap {
+    type Output = f64;
+
+    fn index(&self, (start, end): (&str, &str)) -> &f64 {
+        &self.paths[start].distances[end]
+    }
+}
+
+impl fmt::Display for DistanceMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let entries: Vec<String> = self
+            .sorted()
+            .into_iter()
+            .map(|((s, t), d)| format!("{s} -> {t}: {d}"))
+            .collect();
+        write!(f, "DistanceMap[ {} ]", entries.join(", "))
+    }
+}
+
+/// shortest paths from the vertex `ids[source]` given the distances and
+/// predecessors of the vertices by index
+fn single_source_paths(
+    ids: &[&String],
+    source: usize,
+    dist: &[f64],
+    pred: &[Option<usize>],
+) -> SingleSourcePaths {
+    let mut paths = SingleSourcePaths {
+        source: ids[source].clone(),
+        distances: NodeMap::new(),
+        predecessors: HashMap::new(),
+    };
+    for (i, d) in dist.iter().enumerate() {
+        if d.is_finite() {
+            paths.distances.insert(ids[i], *d);
+        }
+        if let Some(p) = pred[i] {
+            paths.predecessors.insert(ids[i].clone(), ids[p].clone());
+        }
+    }
+    paths
+}
+
+/// negative cycle found by [bellman_ford]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NegativeCycle {
+    /// vertex identifiers along the cycle, the last one leads back to the
+    /// first one
+    pub vertices: Vec<String>,
+}
+
+/// Compute shortest paths from a vertex with possibly negative weights
+/// # Description
+/// Bellman–Ford algorithm in `O(V E)`. Directed edges are followed along
+/// their orientation, undirected edges both ways, hence a negative
+/// undirected edge is a negative cycle. Unlike a Dijkstra search, negative
+/// weights such as log probabilities are supported. Only negative cycles
+/// reachable from `source` are detected, since others do not change the
+/// distances from it.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - source: start vertex
+/// - weight: weight of an edge
+/// - returns: distances and predecessors of the vertices reachable from
+///   `source`, or a negative cycle reachable from it
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph_obj::GraphObject;
+/// use pgm_rust::graph::ops::graph::pathops::bellman_ford;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Directed, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Directed, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Directed, "c", "b");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// let paths = bellman_ford(&g, &Node::empty("a"), |_: &Edge<Node>| 1.0).unwrap();
+/// paths.distances["c"] == 2.0; // true
+/// let cycle = bellman_ford(&g, &Node::empty("a"), |e: &Edge<Node>| if e.id() == "e3" { -2.0 } else { 1.0 });
+/// cycle.unwrap_err().vertices.len() == 2; // true
+/// ```
+///
+/// # References
+/// Bellman R. On a routing problem. 1958.
+pub fn bellman_ford<N, E, G, F>(
+    g: &G,
+    source: &N,
+    weight: F,
+) -> Result<SingleSourcePaths, NegativeCycle>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    F: Fn(&E) -> f64,
+{
+    if !g.vertices().contains(source) {
+        panic!("{source} not in {g}");
+    }
+    let (ids, arcs) = weighted_arcs(g, weight);
+    let s = ids.iter().position(|v| *v == source.id()).unwrap();
+    let mut init = vec![f64::INFINITY; ids.len()];
+    init[s] = 0.0;
+    match relax_arcs(&arcs, init) {
+        Ok((dist, pred)) => Ok(single_source_paths(&ids, s, &dist, &pred)),
+        Err((t, pred)) => {
+            // going back as many steps as there are vertices lands on the
+            // cycle, which is then followed until it closes
+            let mut v = t;
+            for _ in 0..ids.len() {
+                v = pred[v].unwrap();
+            }
+            let mut cycle = vec![v];
+            let mut u = pred[v].unwrap();
+            while u != v {
+                cycle.push(u);
+                u = pred[u].unwrap();
+            }
+            cycle.reverse();
+            Err(NegativeCycle {
+                vertices: cycle.into_iter().map(|i| ids[i].clone()).collect(),
+            })
+        }
+    }
+}
+
+/// entry of the dijkstra priority queue, smallest distance first
+struct HeapEntry(f64, usize);
+
+impl PartialEq for HeapEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for HeapEntry {}
+
+impl PartialOrd for HeapEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HeapEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .0
+            .total_cmp(&self.0)
+            .then_with(|| other.1.cmp(&self.1))
+    }
+}
+
+/// distances from `source` over adjacency lists of non negative weights,
+/// with the predecessor of every reached vertex but the source
+fn dijkstra_distances(
+    adjacency: &[Vec<(usize, f64)>],
+    source: usize,
+) -> (Vec<f64>, Vec<Option<usize>>) {
+    let mut dist = vec![f64::INFINITY; adjacency.len()];
+    let mut pred = vec![None; adjacency.len()];
+    dist[source] = 0.0;
+    let mut heap = BinaryHeap::from([HeapEntry(0.0, source)]);
+    while let Some(HeapEntry(d, u)) = heap.pop() {
+        if d > dist[u] {
+            continue;
+        }
+        for (v, w) in &adjacency[u] {
+            let dv = d + w;
+            if dv < dist[*v] {
+                dist[*v] = dv;
+                pred[*v] = Some(u);
+                heap.push(HeapEntry(dv, *v));
+            }
+        }
+    }
+    (dist, pred)
+}
+
+/// Compute shortest path distances between all pairs of vertices of a
+/// sparse graph
+/// # Description
+/// Johnson's algorithm: a Bellman–Ford pass computes vertex potentials that
+/// make every weight non negative, then a Dijkstra search is run from every
+/// vertex. This costs `O(V E log V)`, less than [floyd_warshall] on sparse
+/// graphs, and supports negative weights. The output follows the same
+/// conventions as [floyd_warshall]. Panics if the graph has a negative
+/// cycle.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - weight: weight of an edge
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph_obj::GraphObject;
+/// use pgm_rust::graph::ops::graph::pathops::johnson;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Directed, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Directed, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Directed, "a", "c");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// let d = johnson(&g, |e: &Edge<Node>| if e.id() == "e2" { -2.0 } else { 1.0 });
+/// d[("a", "c")] == -1.0; // true
+/// d.path_to("a", "c").unwrap() == vec!["a", "b", "c"]; // true
+/// ```
+///
+/// # References
+/// Johnson D. Efficient algorithms for shortest paths in sparse networks.
+/// 1977.
+pub fn johnson<N, E, G, F>(g: &G, weight: F) -> DistanceMap
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    F: Fn(&E) -> f64,
+{
+    let (ids, arcs) = weighted_arcs(g, weight);
+    let n = ids.len();
+    // potentials are distances from a virtual vertex joined to every vertex
+    // by an arc of weight 0
+    let h = match relax_arcs(&arcs, vec![0.0; n]) {
+        Err(_) => panic!("{g} has a negative cycle"),
+        Ok((h, _)) => h,
+    };
+    let mut adjacency: Vec<Vec<(usize, f64)>> = vec![Vec::new(); n];
+    for (s, t, w) in arcs {
+        // rounding may leave reduced weights slightly below zero
+        adjacency[s].push((t, (w + h[s] - h[t]).max(0.0)));
+    }
+    let mut distances = DistanceMap::new();
+    for i in 0..n {
+        let (mut dist, pred) = dijkstra_distances(&adjacency, i);
+        for (j, d) in dist.iter_mut().enumerate() {
+            if d.is_finite() {
+                *d = if i == j { 0.0 } else { *d - h[i] + h[j] };
+            }
+        }
+        distances.insert(single_source_paths(&ids, i, &dist, &pred));
+    }
+    distances
+}
+
+/// first invalid step of a vertex sequence, see [is_walk]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WalkError {
+    /// the sequence has no vertex
+    Empty,
+    /// the vertex at `position` is not a vertex of the graph
+    UnknownVertex {
+        /// position in the sequence
+        position: usize,
+        /// vertex identifier
+        id: String,
+    },
+    /// no edge leads from the vertex at `position` to the next one
+    MissingEdge {
+        /// position of the start of the step in the sequence
+        position: usize,
+        /// identifier of the start of the step
+        from: String,
+        /// identifier of the end of the step
+        to: String,
+    },
+    /// every edge leading from the vertex at `position` to the next one is
+    /// already used by a previous step
+    RepeatedEdge {
+        /// position of the start of the step in the sequence
+        position: usize,
+        /// identifier of the start of the step
+        from: String,
+        /// identifier of the end of the step
+        to: String,
+    },
+    /// the vertex at `position` already appears at `first`
+    RepeatedVertex {
+        /// position of the repetition in the sequence
+        position: usize,
+        /// position of the first occurrence in the sequence
+        first: usize,
+        /// vertex identifier
+        id: String,
+    },
+    /// the last vertex of the sequence differs from the first one, or the
+    /// sequence has a single vertex
+    NotClosed,
+}
+
+/// edges usable to step from a vertex to another, keyed by (from, to).
+/// Directed edges come first, then edges are sorted by identifier, so that
+/// picking the first unused edge never spends an undirected edge a later
+/// step in the other direction could need.
+fn step_edges<'a, N, E, G>(g: &'a G) -> HashMap<(&'a String, &'a String), Vec<&'a E>>
+where
+    N: NodeTrait + 'a,
+    E: EdgeTrait<N> + 'a,
+    G: GraphTrait<N, E>,
+{
+    let mut steps: HashMap<(&String, &String), Vec<&E>> = HashMap::new();
+    let mut edges = g.edges_sorted();
+    edges.sort_by_key(|e| e.has_type() != &EdgeType::Directed);
+    for e in edges {
+        let (s, t) = (e.start().id(), e.end().id());
+        steps.entry((s, t)).or_default().push(e);
+        if e.has_type() == &EdgeType::Undirected && s != t {
+            steps.entry((t, s)).or_default().push(e);
+        }
+    }
+    steps
+}
+
+/// edge identifiers along `seq` if it is a walk of `g`, with distinct edges
+/// if `trail` holds and distinct vertices, but the last one of a closed
+/// sequence, if `path` holds
+fn check_walk<N, E, G>(
+    g: &G,
+    seq: &[&str],
+    trail: bool,
+    path: bool,
+) -> Result<Vec<String>, WalkError>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    if seq.is_empty() {
+        return Err(WalkError::Empty);
+    }
+    let ids: HashMap<&str, &String> = g
+        .vertices()
+        .into_iter()
+        .map(|v| (v.id().as_str(), v.id()))
+        .collect();
+    let mut vertices: Vec<&String> = Vec::with_capacity(seq.len());
+    for (position, id) in seq.iter().enumerate() {
+        match ids.get(id) {
+            None => {
+                return Err(WalkError::UnknownVertex {
+                    position,
+                    id: id.to_string(),
+                })
+            }
+            Some(v) => vertices.push(v),
+        }
+    }
+    if path {
+        let mut seen: HashMap<&String, usize> = HashMap::new();
+        for (position, v) in vertices.iter().enumerate() {
+            let closing = position > 0 && position == vertices.len() - 1 && *v == vertices[0];
+            if closing {
+                continue;
+            }
+            if let Some(first) = seen.insert(v, position) {
+                return Err(WalkError::RepeatedVertex {
+                    position,
+                    first,
+                    id: v.to_string(),
+                });
+            }
+        }
+    }
+    let steps = step_edges(g);
+    let mut used: HashSet<&String> = HashSet::new();
+    let mut edges: Vec<String> = Vec::with_capacity(seq.len() - 1);
+    for (position, w) in vertices.windows(2).enumerate() {
+        let (from, to) = (w[0].to_string(), w[1].to_string());
+        let candidates = match steps.get(&(w[0], w[1])) {
+            None => return Err(WalkError::MissingEdge { position, from, to }),
+            Some(es) => es,
+        };
+        let edge = if trail {
+            match candidates.iter().find(|e| !used.contains(e.id())) {
+                None => return Err(WalkError::RepeatedEdge { position, from, to }),
+                Some(e) => e,
+            }
+        } else {
+            &candidates[0]
+        };
+        used.insert(edge.id());
+        edges.push(edge.id().clone());
+    }
+    Ok(edges)
+}
+
+/// Check that a vertex sequence is a walk of a graph
+/// # Description
+/// A walk is a non empty sequence of vertices in which every vertex is
+/// joined to the next one by an edge. Directed edges are followed along
+/// their orientation, undirected edges both ways, and a vertex repeated in
+/// a row needs a loop. When the sequence is a walk, the identifiers of the
+/// edges taken at every step are returned, otherwise the first invalid step
+/// is reported. Between parallel edges, directed edges are taken first,
+/// then the smallest identifier.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - seq: vertex identifiers along the walk
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::pathops::{is_walk, WalkError};
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Directed, "b", "c");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+/// is_walk(&g, &["a", "b", "a", "b", "c"]).is_ok(); // true
+/// let err = is_walk(&g, &["a", "b", "c", "b"]).unwrap_err();
+/// err == WalkError::MissingEdge { position: 2, from: "c".to_string(), to: "b".to_string() }; // true
+/// ```
+pub fn is_walk<N, E, G>(g: &G, seq: &[&str]) -> Result<Vec<String>, WalkError>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    check_walk(g, seq, false, false)
+}
+
+/// Check that a vertex sequence is a trail of a graph
+/// # Description
+/// A trail is a walk whose steps take distinct edges, vertices may repeat.
+/// A step between two vertices joined by parallel edges takes an edge no
+/// previous step took, preferring directed edges, so that undirected
+/// edges remain available for steps in the other direction. See [is_walk].
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - seq: vertex identifiers along the trail
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::pathops::is_trail;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1]));
+/// is_trail(&g, &["a", "b"]).is_ok(); // true
+/// is_trail(&g, &["a", "b", "a"]).is_err(); // true: e1 is taken twice
+/// ```
+pub fn is_trail<N, E, G>(g: &G, seq: &[&str]) -> Result<Vec<String>, WalkError>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    check_walk(g, seq, true, false)
+}
+
+/// Check that a vertex sequence is a path of a graph
+/// # Description
+/// A path is a walk whose vertices are distinct, hence whose edges are
+/// distinct as well. A repeated vertex is reported before any invalid step.
+/// See [is_walk].
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - seq: vertex identifiers along the path
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::pathops::is_path;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+/// is_path(&g, &["a", "b", "c"]).unwrap() == vec!["e1", "e2"]; // true
+/// ```
+pub fn is_path<N, E, G>(g: &G, seq: &[&str]) -> Result<Vec<String>, WalkError>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    if seq.len() > 1 && seq.first() == seq.last() {
+        let position = seq.len() - 1;
+        return Err(WalkError::RepeatedVertex {
+            position,
+            first: 0,
+            id: seq[position].to_string(),
+        });
+    }
+    check_walk(g, seq, true, true)
+}
+
+/// Check that a vertex sequence is a cycle of a graph
+/// # Description
+/// The sequence lists the vertices of the cycle and ends with its first
+/// vertex, as `a b c a`. It must be a trail whose vertices are distinct
+/// apart from the closing one. A loop is the cycle `a a` and two parallel
+/// or antiparallel edges form the cycle `a b a`. See [is_walk].
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - seq: vertex identifiers along the cycle, the first one repeated at
+///   the end
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::pathops::{is_cycle_sequence, WalkError};
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Directed, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Directed, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Directed, "c", "a");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// is_cycle_sequence(&g, &["a", "b", "c", "a"]).is_ok(); // true
+/// is_cycle_sequence(&g, &["a", "b", "c"]) == Err(WalkError::NotClosed); // true
+/// ```
+pub fn is_cycle_sequence<N, E, G>(g: &G, seq: &[&str]) -> Result<Vec<String>, WalkError>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    if seq.is_empty() {
+        return Err(WalkError::Empty);
+    }
+    if seq.len() < 2 || seq.first() != seq.last() {
+        return Err(WalkError::NotClosed);
+    }
+    check_walk(g, seq, true, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::traits::graph_obj::GraphObject;
+    use crate::graph::traits::path::Path as PathTrait;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::idgen::SequentialIdGenerator;
+    use crate::graph::types::node::Node;
+
+    fn mk_uedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Undirected, n1_id, n2_id)
+    }
+    fn mk_dedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Directed, n1_id, n2_id)
+    }
+    fn mk_edges(es: Vec<Edge<Node>>) -> HashSet<Edge<Node>> {
+        let mut hs = HashSet::new();
+        for e in es {
+            hs.insert(e);
+        }
+        hs
+    }
+    fn edge_ids(p: &Path<Node, Edge<Node>, Graph<Node, Edge<Node>>>) -> Vec<String> {
+        let mut ids: Vec<String> = p.edges().into_iter().map(|e| e.id().clone()).collect();
+        ids.sort();
+        ids
+    }
+
+    /// grid like graph with a long detour n1 - n5 - n6 - n7 - n8 - n4
+    fn mk_g1() -> Graph<Node, Edge<Node>> {
+        let es = vec![
+            mk_uedge("n1", "n2", "e1"),
+            mk_uedge("n2", "n3", "e2"),
+            mk_uedge("n3", "n4", "e3"),
+            mk_uedge("n1", "n5", "e4"),
+            mk_uedge("n5", "n6", "e5"),
+            mk_uedge("n6", "n7", "e6"),
+            mk_uedge("n7", "n8", "e7"),
+            mk_uedge("n8", "n4", "e8"),
+            mk_uedge("n9", "n10", "e9"),
+        ];
+        Graph::from_edgeset(mk_edges(es))
+    }
+
+    #[test]
+    fn test_bidirectional_shortest_path() {
+        let g = mk_g1();
+        let p = bidirectional_shortest_path(&g, &Node::empty("n1"), &Node::empty("n4")).unwrap();
+        assert_eq!(p.length(), 3);
+        assert_eq!(edge_ids(&p), vec!["e1", "e2", "e3"]);
+        assert_eq!(p.endvertices(), (&Node::empty("n1"), &Node::empty("n4")));
+        let mut idgen = SequentialIdGenerator::new("p");
+        let p = bidirectional_shortest_path_with(
+            &g,
+            &Node::empty("n1"),
+            &Node::empty("n4"),
+            &mut idgen,
+        )
+        .unwrap();
+        assert_eq!(p.id(), "p0");
+    }
+
+    #[test]
+    fn test_bidirectional_shortest_path_unreachable() {
+        let g = mk_g1();
+        let p = bidirectional_shortest_path(&g, &Node::empty("n1"), &Node::empty("n9"));
+        assert!(p.is_none());
+    }
+
+    #[test]
+    fn test_bidirectional_shortest_path_trivial() {
+        let g = mk_g1();
+        let p = bidirectional_shortest_path(&g, &Node::empty("n2"), &Node::empty("n2")).unwrap();
+        assert_eq!(p.length(), 0);
+    }
+
+    #[test]
+    fn test_bidirectional_shortest_path_directed() {
+        let es = vec![
+            mk_dedge("a", "b", "e1"),
+            mk_dedge("b", "c", "e2"),
+            mk_dedge("c", "a", "e3"),
+        ];
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(mk_edges(es));
+        let p = bidirectional_shortest_path(&g, &Node::empty("a"), &Node::empty("c")).unwrap();
+        assert_eq!(edge_ids(&p), vec!["e1", "e2"]);
+        let back = bidirectional_shortest_path(&g, &Node::empty("c"), &Node::empty("a")).unwrap();
+        assert_eq!(edge_ids(&back), vec!["e3"]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bidirectional_shortest_path_missing() {
+        let g = mk_g1();
+        bidirectional_shortest_path(&g, &Node::empty("n1"), &Node::empty("z"));
+    }
+
+    /// directed graph with a negative edge b -> c and an undirected edge
+    /// c - d
+    fn mk_weighted() -> (Graph<Node, Edge<Node>>, impl Fn(&Edge<Node>) -> f64) {
+        let es = vec![
+            mk_dedge("a", "b", "e1"),
+            mk_dedge("b", "c", "e2"),
+            mk_dedge("a", "c", "e3"),
+            mk_uedge("c", "d", "e4"),
+            mk_dedge("d", "a", "e5"),
+        ];
+        let weight = |e: &Edge<Node>| match e.id().as_str() {
+            "e1" => 4.0,
+            "e2" => -2.0,
+            "e3" => 3.0,
+            "e4" => 1.0,
+            _ => 2.0,
+        };
+        (Graph::from_edgeset(mk_edges(es)), weight)
+    }
+
+    #[test]
+    fn test_floyd_warshall() {
+        let (g, weight) = mk_weighted();
+        let d = floyd_warshall(&g, weight);
+        assert_eq!(d.len(), 16);
+        assert_eq!(d[("a", "c")], 2.0);
+        assert_eq!(d[("a", "d")], 3.0);
+        assert_eq!(d[("d", "c")], 1.0);
+        assert_eq!(d[("b", "a")], 1.0);
+        assert_eq!(d[("b", "b")], 0.0);
+    }
+
+    #[test]
+    fn test_floyd_warshall_unreachable() {
+        let g = mk_g1();
+        let d = floyd_warshall(&g, |_| 1.0);
+        assert_eq!(d[("n1", "n4")], 3.0);
+        assert!(!d.contains("n1", "n9"));
+    }
+
+    #[test]
+    fn test_johnson_matches_floyd_warshall() {
+        let (g, weight) = mk_weighted();
+        assert_eq!(johnson(&g, &weight), floyd_warshall(&g, &weight));
+        let g1 = mk_g1();
+        assert_eq!(johnson(&g1, |_| 1.0), floyd_warshall(&g1, |_| 1.0));
+    }
+
+    #[test]
+    fn test_distance_map() {
+        let (g, weight) = mk_weighted();
+        let path: Vec<String> = ["a", "b", "c", "d"].iter().map(|v| v.to_string()).collect();
+        for d in [floyd_warshall(&g, &weight), johnson(&g, &weight)] {
+            assert_eq!(d.get("a", "d"), Some(3.0));
+            assert_eq!(d.path_to("a", "d"), Some(path.clone()));
+            assert_eq!(d.path_to("b", "b"), Some(vec!["b".to_string()]));
+            assert_eq!(d.paths_from("d").unwrap().distances.len(), 4);
+            assert_eq!(d.iter().count(), d.len());
+            let sorted = d.sorted();
+            assert_eq!(sorted[0], ((&"a".to_string(), &"a".to_string()), &0.0));
+            assert_eq!(sorted[1], ((&"a".to_string(), &"b".to_string()), &4.0));
+        }
+        let d = floyd_warshall(&mk_g1(), |_| 1.0);
+        assert_eq!(d.get("n1", "n9"), None);
+        assert_eq!(d.path_to("n1", "n9"), None);
+        assert_eq!(d.path_to("n1", "n4").unwrap().len(), 4);
+        assert!(d.paths_from("z").is_none());
+        assert!(DistanceMap::new().is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_floyd_warshall_negative_cycle() {
+        let (g, _) = mk_weighted();
+        floyd_warshall(&g, |_| -1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_johnson_negative_cycle() {
+        let (g, _) = mk_weighted();
+        johnson(&g, |_| -1.0);
+    }
+
+    #[test]
+    fn test_bellman_ford() {
+        let (g, weight) = mk_weighted();
+        let paths = bellman_ford(&g, &Node::empty("a"), &weight).unwrap();
+        let all = floyd_warshall(&g, &weight);
+        for (v, d) in &paths.distances {
+            assert_eq!(*d, all[("a", v.as_str())]);
+        }
+        assert_eq!(paths.distances.len(), 4);
+        let path: Vec<String> = ["a", "b", "c", "d"].iter().map(|v| v.to_string()).collect();
+        assert_eq!(paths.path_to("d"), Some(path));
+        let unreachable = bellman_ford(&mk_g1(), &Node::empty("n1"), |_| 1.0).unwrap();
+        assert_eq!(unreachable.path_to("n9"), None);
+    }
+
+    #[test]
+    fn test_bellman_ford_negative_cycle() {
+        let es = vec![
+            mk_dedge("s", "a", "e0"),
+            mk_dedge("a", "b", "e1"),
+            mk_dedge("b", "c", "e2"),
+            mk_dedge("c", "a", "e3"),
+        ];
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(mk_edges(es));
+        let weight = |e: &Edge<Node>| if e.id() == "e3" { -3.0 } else { 1.0 };
+        let cycle = bellman_ford(&g, &Node::empty("s"), weight).unwrap_err();
+        let mut vs = cycle.vertices.clone();
+        assert_eq!(vs.len(), 3);
+        // the cycle is in edge order whatever vertex it starts from
+        let first = vs.iter().position(|v| v == "a").unwrap();
+        vs.rotate_left(first);
+        assert_eq!(vs, vec!["a", "b", "c"]);
+        // the cycle cannot be reached from a vertex outside of it
+        let es = vec![mk_dedge("a", "b", "e1"), mk_dedge("b", "a", "e2")];
+        let h: Graph<Node, Edge<Node>> =
+            Graph::from_edge_node_set(mk_edges(es), HashSet::from([Node::empty("z")]));
+        assert!(bellman_ford(&h, &Node::empty("z"), |_| -1.0).is_ok());
+    }
+
+    #[test]
+    fn test_is_walk() {
+        let g = mk_g1();
+        assert_eq!(is_walk(&g, &["n1"]), Ok(vec![]));
+        assert_eq!(
+            is_walk(&g, &["n1", "n2", "n1", "n5"]),
+            Ok(vec!["e1".to_string(), "e1".to_string(), "e4".to_string()])
+        );
+        assert_eq!(is_walk(&g, &[]), Err(WalkError::Empty));
+        assert_eq!(
+            is_walk(&g, &["n1", "n2", "x"]),
+            Err(WalkError::UnknownVertex {
+                position: 2,
+                id: "x".to_string()
+            })
+        );
+        assert_eq!(
+            is_walk(&g, &["n1", "n2", "n4"]),
+            Err(WalkError::MissingEdge {
+                position: 1,
+                from: "n2".to_string(),
+                to: "n4".to_string()
+            })
+        );
+        // a vertex repeated in a row needs a loop
+        assert!(is_walk(&g, &["n1", "n1"]).is_err());
+    }
+
+    #[test]
+    fn test_is_walk_follows_orientation() {
+        let g = Graph::from_edgeset(mk_edges(vec![
+            mk_dedge("a", "b", "d1"),
+            mk_uedge("b", "c", "u1"),
+            mk_dedge("c", "c", "l1"),
+        ]));
+        assert!(is_walk(&g, &["a", "b", "c", "c", "b"]).is_ok());
+        assert!(is_walk(&g, &["b", "a"]).is_err());
+    }
+
+    #[test]
+    fn test_is_trail() {
+        // a -> b and a -- b: a b a is a trail only if a -> b is taken first
+        let g = Graph::from_edgeset(mk_edges(vec![
+            mk_dedge("a", "b", "z1"),
+            mk_uedge("a", "b", "a1"),
+            mk_uedge("b", "c", "b1"),
+        ]));
+        assert_eq!(
+            is_trail(&g, &["a", "b", "a"]),
+            Ok(vec!["z1".to_string(), "a1".to_string()])
+        );
+        assert_eq!(
+            is_trail(&g, &["c", "b", "c"]),
+            Err(WalkError::RepeatedEdge {
+                position: 1,
+                from: "b".to_string(),
+                to: "c".to_string()
+            })
+        );
+        assert!(is_walk(&g, &["c", "b", "c"]).is_ok());
+    }
+
+    #[test]
+    fn test_is_path() {
+        let g = mk_g1();
+        assert!(is_path(&g, &["n1", "n5", "n6", "n7"]).is_ok());
+        assert_eq!(
+            is_path(&g, &["n1", "n2", "n3", "n2"]),
+            Err(WalkError::RepeatedVertex {
+                position: 3,
+                first: 1,
+                id: "n2".to_string()
+            })
+        );
+        assert_eq!(
+            is_path(&g, &["n4", "n3", "n2", "n1", "n5", "n6", "n7", "n8", "n4"]),
+            Err(WalkError::RepeatedVertex {
+                position: 8,
+                first: 0,
+                id: "n4".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_is_cycle_sequence() {
+        let g = mk_g1();
+        let cycle = ["n1", "n2", "n3", "n4", "n8", "n7", "n6", "n5", "n1"];
+        assert_eq!(is_cycle_sequence(&g, &cycle).unwrap().len(), 8);
+        assert_eq!(is_cycle_sequence(&g, &["n1"]), Err(WalkError::NotClosed));
+        assert_eq!(
+            is_cycle_sequence(&g, &["n1", "n2", "n1"]),
+            Err(WalkError::RepeatedEdge {
+                position: 1,
+                from: "n2".to_string(),
+                to: "n1".to_string()
+            })
+        );
+        assert!(matches!(
+            is_cycle_sequence(&g, &["n1", "n2", "n3", "n2", "n1"]),
+            Err(WalkError::RepeatedVertex { position: 3, .. })
+        ));
+        let h = Graph::from_edgeset(mk_edges(vec![
+            mk_dedge("a", "b", "d1"),
+            mk_dedge("b", "a", "d2"),
+            mk_uedge("c", "c", "l1"),
+        ]));
+        assert!(is_cycle_sequence(&h, &["a", "b", "a"]).is_ok());
+        assert!(is_cycle_sequence(&h, &["c", "c"]).is_ok());
+    }
+}
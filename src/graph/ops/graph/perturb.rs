@@ -0,0 +1,368 @@
+//! random structural perturbations of graphs
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::edgetype::EdgeType;
+use crate::rng::SplitMix64;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+/// number of elements of `n` corresponding to `fraction`, panics if the
+/// fraction is not in `[0, 1]`
+fn fraction_of(fraction: f64, n: usize) -> usize {
+    if !(0.0..=1.0).contains(&fraction) {
+        panic!("fraction {fraction} must be in [0, 1]");
+    }
+    (fraction * n as f64).round() as usize
+}
+
+/// key identifying the vertex pair joined by an edge, ordered for directed
+/// edges and unordered for undirected ones
+fn pair_key(s: &str, t: &str, edge_type: &EdgeType) -> (String, String, bool) {
+    match edge_type {
+        EdgeType::Directed => (s.to_string(), t.to_string(), true),
+        EdgeType::Undirected if s <= t => (s.to_string(), t.to_string(), false),
+        EdgeType::Undirected => (t.to_string(), s.to_string(), false),
+    }
+}
+
+/// Remove a random fraction of the edges of a graph
+/// # Description
+/// `fraction` of the edges, rounded to the nearest integer, are drawn
+/// uniformly without replacement and removed. Vertices are kept, as are the
+/// graph identifier and data. Edges are drawn from a [SplitMix64]
+/// generator seeded by `seed`.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - fraction: fraction of the edges to remove, in `[0, 1]`
+/// - seed: seed of the random number generator
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+/// use pgm_rust::graph::ops::graph::perturb::drop_edges_random;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+/// let h = drop_edges_random(&g, 0.5, 1);
+/// h.size() == 1; // true
+/// h.order() == 3; // true
+/// ```
+pub fn drop_edges_random<N, E, G>(g: &G, fraction: f64, seed: u64) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let mut edges = g.edges_sorted();
+    let k = fraction_of(fraction, edges.len());
+    SplitMix64::new(seed).shuffle(&mut edges);
+    let kept: HashSet<&E> = edges.into_iter().skip(k).collect();
+    G::create_from_ref(g.id().clone(), g.data().clone(), g.vertices(), kept)
+}
+
+/// Rewire a random fraction of the edges of a graph
+/// # Description
+/// Performs double edge swaps: two edges `a - b` and `c - d` of the same
+/// type on four distinct vertices are replaced by `a - d` and `c - b`. A
+/// swap keeps the degree of every vertex, and the in and out degrees when
+/// both edges are directed. Swaps creating a parallel edge are rejected.
+/// `fraction` of the edges, rounded to the nearest integer, is the number
+/// of swaps attempted to be made. Since a swap may be impossible, the
+/// number of tries is bounded and fewer swaps can be made on small or
+/// dense graphs. Edge identifiers, data and types are kept, as are the
+/// graph identifier and data.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - fraction: fraction of the edges giving the number of swaps, in `[0, 1]`
+/// - seed: seed of the random number generator
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+/// use pgm_rust::graph::ops::graph::perturb::rewire_edges;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "c", "d");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+/// let h = rewire_edges(&g, 0.5, 1);
+/// h.size() == 2; // true
+/// h != g; // true: a - d and c - b
+/// ```
+///
+/// # References
+/// Maslov S, Sneppen K. Specificity and stability in topology of protein
+/// networks. 2002.
+pub fn rewire_edges<N, E, G>(g: &G, fraction: f64, seed: u64) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let sorted = g.edges_sorted();
+    let swaps = fraction_of(fraction, sorted.len());
+    // (edge, start, end) with ends updated as swaps are made
+    let mut edges: Vec<(&E, &N, &N)> = sorted.iter().map(|e| (*e, e.start(), e.end())).collect();
+    let mut pairs: HashSet<(String, String, bool)> = edges
+        .iter()
+        .map(|(e, s, t)| pair_key(s.id(), t.id(), e.has_type()))
+        .collect();
+    let mut rng = SplitMix64::new(seed);
+    let mut made = 0;
+    let mut tries = 0;
+    while made < swaps && tries < 100 * swaps && edges.len() > 1 {
+        tries += 1;
+        let i = rng.gen_range(edges.len());
+        let j = rng.gen_range(edges.len());
+        let (e1, a, b) = edges[i];
+        let (e2, c, d) = edges[j];
+        if e1.has_type() != e2.has_type() {
+            continue;
+        }
+        let ids: HashSet<&String> = HashSet::from([a.id(), b.id(), c.id(), d.id()]);
+        if ids.len() < 4 {
+            continue;
+        }
+        let t = e1.has_type();
+        let (ad, cb) = (pair_key(a.id(), d.id(), t), pair_key(c.id(), b.id(), t));
+        if pairs.contains(&ad) || pairs.contains(&cb) {
+            continue;
+        }
+        pairs.remove(&pair_key(a.id(), b.id(), t));
+        pairs.remove(&pair_key(c.id(), d.id(), t));
+        pairs.insert(ad);
+        pairs.insert(cb);
+        edges[i] = (e1, a, d);
+        edges[j] = (e2, c, b);
+        made += 1;
+    }
+    let rewired: HashSet<E> = edges
+        .into_iter()
+        .map(|(e, s, t)| {
+            E::create(
+                e.id().clone(),
+                e.data().clone(),
+                s.clone(),
+                t.clone(),
+                e.has_type().clone(),
+            )
+        })
+        .collect();
+    let nodes: HashSet<N> = g.vertices().into_iter().cloned().collect();
+    G::create(g.id().clone(), g.data().clone(), nodes, rewired)
+}
+
+/// Add random edges to a graph
+/// # Description
+/// `count` edges are added between pairs of distinct vertices drawn
+/// uniformly among the pairs not joined by an edge yet, whatever its
+/// orientation. Added edges are
+/// directed and named `a->b` when every edge of `g` is directed, otherwise
+/// they are undirected and named `a--b`. They carry no data. Panics if
+/// there are fewer than `count` free pairs. Graph identifier and data are
+/// kept.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - count: number of edges to add
+/// - seed: seed of the random number generator
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+/// use pgm_rust::graph::ops::graph::perturb::add_noise_edges;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let nodes = HashSet::from([Node::empty("c")]);
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edge_node_set(HashSet::from([e1]), nodes);
+/// add_noise_edges(&g, 2, 1).size() == 3; // true: the triangle
+/// ```
+pub fn add_noise_edges<N, E, G>(g: &G, count: usize, seed: u64) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let edges = g.edges();
+    let directed = !edges.is_empty() && edges.iter().all(|e| e.has_type() == &EdgeType::Directed);
+    let edge_type = if directed {
+        EdgeType::Directed
+    } else {
+        EdgeType::Undirected
+    };
+    // any edge between two vertices makes them adjacent
+    let adjacent: HashSet<(&String, &String)> = edges
+        .iter()
+        .flat_map(|e| {
+            [
+                (e.start().id(), e.end().id()),
+                (e.end().id(), e.start().id()),
+            ]
+        })
+        .filter(|(s, t)| directed || s <= t)
+        .collect();
+    let vertices = g.vertices_sorted();
+    let mut free: Vec<(&N, &N)> = Vec::new();
+    for (i, a) in vertices.iter().enumerate() {
+        for (j, b) in vertices.iter().enumerate() {
+            let wanted = if directed { i != j } else { i < j };
+            if wanted && !adjacent.contains(&(a.id(), b.id())) {
+                free.push((*a, *b));
+            }
+        }
+    }
+    if free.len() < count {
+        panic!(
+            "{g} has {} free vertex pairs, {count} edges can not be added",
+            free.len()
+        );
+    }
+    SplitMix64::new(seed).shuffle(&mut free);
+    let mut noisy: HashSet<E> = edges
+        .into_iter()
+        .map(|e| {
+            E::create(
+                e.id().clone(),
+                e.data().clone(),
+                e.start().clone(),
+                e.end().clone(),
+                e.has_type().clone(),
+            )
+        })
+        .collect();
+    let sep = if directed { "->" } else { "--" };
+    for (a, b) in free.into_iter().take(count) {
+        noisy.insert(E::create(
+            format!("{}{sep}{}", a.id(), b.id()),
+            HashMap::new(),
+            a.clone(),
+            b.clone(),
+            edge_type.clone(),
+        ));
+    }
+    let nodes: HashSet<N> = g.vertices().into_iter().cloned().collect();
+    G::create(g.id().clone(), g.data().clone(), nodes, noisy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::traits::graph_obj::GraphObject;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+
+    fn mk_uedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Undirected, n1_id, n2_id)
+    }
+
+    fn mk_dedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Directed, n1_id, n2_id)
+    }
+
+    /// cycle of 8 vertices with 2 chords
+    fn mk_g() -> Graph<Node, Edge<Node>> {
+        let mut es: HashSet<Edge<Node>> = (0..8)
+            .map(|i| {
+                mk_uedge(
+                    &format!("v{i}"),
+                    &format!("v{}", (i + 1) % 8),
+                    &format!("e{i}"),
+                )
+            })
+            .collect();
+        es.insert(mk_uedge("v0", "v4", "c1"));
+        es.insert(mk_uedge("v2", "v6", "c2"));
+        Graph::from_edgeset(es)
+    }
+
+    fn degrees(g: &Graph<Node, Edge<Node>>) -> HashMap<String, usize> {
+        let mut ds: HashMap<String, usize> = HashMap::new();
+        for e in g.edges() {
+            *ds.entry(e.start().id().clone()).or_default() += 1;
+            *ds.entry(e.end().id().clone()).or_default() += 1;
+        }
+        ds
+    }
+
+    #[test]
+    fn test_drop_edges_random() {
+        let g = mk_g();
+        let h = drop_edges_random(&g, 0.3, 5);
+        assert_eq!(h.size(), 7);
+        assert_eq!(h.order(), g.order());
+        assert!(h.edges().iter().all(|e| g.edges().contains(e)));
+        assert_eq!(h, drop_edges_random(&g, 0.3, 5));
+        assert_eq!(drop_edges_random(&g, 1.0, 5).size(), 0);
+    }
+
+    #[test]
+    fn test_rewire_edges_keeps_degrees() {
+        let g = mk_g();
+        let h = rewire_edges(&g, 0.5, 9);
+        assert_eq!(degrees(&h), degrees(&g));
+        assert_eq!(h.size(), g.size());
+        assert_ne!(h, g);
+        let ids: HashSet<&String> = h.edges().into_iter().map(|e| e.id()).collect();
+        assert_eq!(ids, g.edges().into_iter().map(|e| e.id()).collect());
+    }
+
+    #[test]
+    fn test_rewire_edges_directed() {
+        let es = HashSet::from([
+            mk_dedge("a", "b", "e1"),
+            mk_dedge("c", "d", "e2"),
+            mk_dedge("e", "f", "e3"),
+        ]);
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(es);
+        let h = rewire_edges(&g, 1.0, 2);
+        let starts: HashSet<&String> = h.edges().into_iter().map(|e| e.start().id()).collect();
+        let expected: HashSet<&String> = g.edges().into_iter().map(|e| e.start().id()).collect();
+        assert_eq!(starts, expected);
+    }
+
+    #[test]
+    fn test_add_noise_edges() {
+        let g = mk_g();
+        let h = add_noise_edges(&g, 5, 3);
+        assert_eq!(h.size(), 15);
+        assert!(h.edges().iter().all(|e| e.start() != e.end()));
+        // b -> a is not added since a -> b already joins the pair
+        let es = HashSet::from([mk_dedge("a", "b", "e1")]);
+        let nodes = HashSet::from([Node::empty("c")]);
+        let d: Graph<Node, Edge<Node>> = Graph::from_edge_node_set(es, nodes);
+        let noisy = add_noise_edges(&d, 4, 3);
+        let ids: HashSet<&String> = noisy.edges().into_iter().map(|e| e.id()).collect();
+        let expected: HashSet<String> = ["e1", "a->c", "c->a", "b->c", "c->b"]
+            .iter()
+            .map(|i| i.to_string())
+            .collect();
+        assert_eq!(ids, expected.iter().collect());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_add_noise_edges_too_many() {
+        let es = HashSet::from([mk_uedge("a", "b", "e1")]);
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(es);
+        add_noise_edges(&g, 1, 3);
+    }
+}
@@ -0,0 +1,239 @@
+//! reachability queries on directed acyclic graphs
+use crate::graph::ops::graph::search::topological_sort;
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::indexed::IndexedGraph;
+use std::collections::HashMap;
+
+/// merge sorted intervals, joining those that overlap or touch
+fn merge_intervals(mut intervals: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+    intervals.sort();
+    let mut merged: Vec<(usize, usize)> = Vec::with_capacity(intervals.len());
+    for (low, high) in intervals {
+        match merged.last_mut() {
+            Some(last) if low <= last.1 + 1 => last.1 = last.1.max(high),
+            _ => merged.push((low, high)),
+        }
+    }
+    merged
+}
+
+/// Index answering reachability queries on a directed acyclic graph
+/// # Description
+/// Built once with the interval labeling of Agrawal et al. 1989. Vertices
+/// are numbered in postorder of a spanning forest, so that the descendants
+/// of a vertex in the forest form an interval of numbers. Each vertex then
+/// stores the merged intervals of everything it can reach, including
+/// through non tree edges. A query is a binary search over the intervals
+/// of the start vertex, which are few on graphs close to forests. Every
+/// vertex reaches itself.
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::indexed::IndexedGraph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::reach::ReachabilityIndex;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Directed, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Directed, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Directed, "d", "c");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// let reach = ReachabilityIndex::new(&IndexedGraph::new(&g));
+/// reach.can_reach("a", "c"); // true
+/// !reach.can_reach("a", "d"); // true
+/// ```
+///
+/// # References
+/// Agrawal R, Borgida A, Jagadish H. Efficient management of transitive
+/// relationships in large data and knowledge bases. 1989.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReachabilityIndex {
+    /// postorder number of every vertex
+    post: HashMap<String, usize>,
+    /// vertex identifiers indexed by postorder number
+    by_post: Vec<String>,
+    /// merged intervals of postorder numbers reachable from every vertex
+    intervals: HashMap<String, Vec<(usize, usize)>>,
+}
+
+impl ReachabilityIndex {
+    /// Build the index of the graph wrapped by `index`
+    /// # Description
+    /// Panics if the graph has an undirected edge or a directed cycle.
+    pub fn new<N, E, G>(index: &IndexedGraph<N, E, G>) -> ReachabilityIndex
+    where
+        N: NodeTrait,
+        E: EdgeTrait<N>,
+        G: GraphTrait<N, E>,
+    {
+        let g = index.graph();
+        let order = match topological_sort(g) {
+            None => panic!("{g} has a directed cycle"),
+            Some(order) => order,
+        };
+        let mut successors: HashMap<&String, Vec<&String>> = HashMap::new();
+        for e in g.edges_sorted() {
+            successors
+                .entry(e.start().id())
+                .or_default()
+                .push(e.end().id());
+        }
+        // spanning forest numbered in postorder, roots taken in topological
+        // order so that every root has no incoming edge in the forest
+        let mut post: HashMap<String, usize> = HashMap::new();
+        let mut low: HashMap<&String, usize> = HashMap::new();
+        let mut by_post: Vec<String> = Vec::with_capacity(order.len());
+        for root in &order {
+            if low.contains_key(root.id()) {
+                continue;
+            }
+            low.insert(root.id(), by_post.len());
+            let mut stack: Vec<(&String, usize)> = vec![(root.id(), 0)];
+            while let Some((v, i)) = stack.pop() {
+                let next = successors.get(v).and_then(|ws| {
+                    ws[i..]
+                        .iter()
+                        .position(|w| !low.contains_key(*w))
+                        .map(|k| i + k)
+                });
+                match next {
+                    Some(k) => {
+                        let w = successors[v][k];
+                        stack.push((v, k + 1));
+                        low.insert(w, by_post.len());
+                        stack.push((w, 0));
+                    }
+                    None => {
+                        post.insert(v.clone(), by_post.len());
+                        by_post.push(v.clone());
+                    }
+                }
+            }
+        }
+        let mut intervals: HashMap<String, Vec<(usize, usize)>> = HashMap::new();
+        for v in order.iter().rev() {
+            let mut own = vec![(low[v.id()], post[v.id()])];
+            for w in successors.get(v.id()).into_iter().flatten() {
+                own.extend(intervals[*w].iter().copied());
+            }
+            intervals.insert(v.id().clone(), merge_intervals(own));
+        }
+        ReachabilityIndex {
+            post,
+            by_post,
+            intervals,
+        }
+    }
+
+    /// intervals of `id`, panics if `id` is not indexed
+    fn intervals_of(&self, id: &str) -> &Vec<(usize, usize)> {
+        match self.intervals.get(id) {
+            None => panic!("{id} is not indexed"),
+            Some(is) => is,
+        }
+    }
+
+    /// whether there is a directed path from `a` to `b`, panics if either
+    /// vertex is not indexed
+    pub fn can_reach(&self, a: &str, b: &str) -> bool {
+        let is = self.intervals_of(a);
+        let p = match self.post.get(b) {
+            None => panic!("{b} is not indexed"),
+            Some(p) => *p,
+        };
+        let k = is.partition_point(|(_, high)| *high < p);
+        k < is.len() && is[k].0 <= p
+    }
+
+    /// identifiers of the vertices reachable from `a`, `a` included, in
+    /// postorder
+    pub fn reachable_from(&self, a: &str) -> Vec<&String> {
+        self.intervals_of(a)
+            .iter()
+            .flat_map(|(low, high)| self.by_post[*low..=*high].iter())
+            .collect()
+    }
+
+    /// number of stored intervals, a measure of the size of the index
+    pub fn nb_intervals(&self) -> usize {
+        self.intervals.values().map(|is| is.len()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::ops::graph::bounded::bounded_bfs;
+    use crate::graph::ops::graph::bounded::TraversalLimits;
+    use crate::graph::ops::graph::search::EdgeNeighborhood;
+    use crate::graph::traits::graph_obj::GraphObject;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::edgetype::EdgeType;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+    use std::collections::HashSet;
+
+    fn mk_dedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Directed, n1_id, n2_id)
+    }
+
+    /// diamond a -> b, c -> d followed by d -> g, a second root e -> d and an
+    /// isolated f
+    fn mk_dag() -> Graph<Node, Edge<Node>> {
+        let es = HashSet::from([
+            mk_dedge("a", "b", "e1"),
+            mk_dedge("a", "c", "e2"),
+            mk_dedge("b", "d", "e3"),
+            mk_dedge("c", "d", "e4"),
+            mk_dedge("e", "d", "e5"),
+            mk_dedge("d", "g", "e6"),
+        ]);
+        Graph::from_edge_node_set(es, HashSet::from([Node::empty("f")]))
+    }
+
+    #[test]
+    fn test_merge_intervals() {
+        let merged = merge_intervals(vec![(4, 5), (0, 1), (2, 2), (7, 9), (8, 8)]);
+        assert_eq!(merged, vec![(0, 2), (4, 5), (7, 9)]);
+    }
+
+    #[test]
+    fn test_reachability_matches_search() {
+        let g = mk_dag();
+        let reach = ReachabilityIndex::new(&IndexedGraph::new(&g));
+        for a in g.vertices() {
+            let r = bounded_bfs(&g, a, &EdgeNeighborhood::Out, &TraversalLimits::default());
+            let expected: HashSet<&String> = r.visited.iter().collect();
+            for b in g.vertices() {
+                assert_eq!(reach.can_reach(a.id(), b.id()), expected.contains(b.id()));
+            }
+            let found: HashSet<&String> = reach.reachable_from(a.id()).into_iter().collect();
+            assert_eq!(found, expected);
+        }
+    }
+
+    #[test]
+    fn test_reachability_forest_is_compact() {
+        let es = HashSet::from([
+            mk_dedge("a", "b", "e1"),
+            mk_dedge("a", "c", "e2"),
+            mk_dedge("c", "d", "e3"),
+        ]);
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(es);
+        let reach = ReachabilityIndex::new(&IndexedGraph::new(&g));
+        assert_eq!(reach.nb_intervals(), 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_reachability_cycle() {
+        let es = HashSet::from([mk_dedge("a", "b", "e1"), mk_dedge("b", "a", "e2")]);
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(es);
+        ReachabilityIndex::new(&IndexedGraph::new(&g));
+    }
+}
@@ -0,0 +1,1007 @@
+//! recognition of graph classes
+use crate::graph::ops::graph::order::mcs_ordering;
+use crate::graph::ops::graph::order::simple_neighbors;
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+/// decomposition tree of a two terminal series-parallel graph, see
+/// [is_series_parallel]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Decomposition {
+    /// a single edge of the graph, traversed from `source` to `sink`
+    Edge {
+        /// identifier of the edge
+        id: String,
+        /// first terminal
+        source: String,
+        /// second terminal
+        sink: String,
+    },
+    /// components chained from the source to the sink, the sink of every
+    /// component being the source of the next one
+    Series(Vec<Decomposition>),
+    /// components sharing their source and their sink
+    Parallel(Vec<Decomposition>),
+}
+
+impl Decomposition {
+    /// source and sink of the component
+    pub fn terminals(&self) -> (&String, &String) {
+        match self {
+            Decomposition::Edge { source, sink, .. } => (source, sink),
+            Decomposition::Series(parts) => (
+                parts.first().unwrap().terminals().0,
+                parts.last().unwrap().terminals().1,
+            ),
+            Decomposition::Parallel(parts) => parts.first().unwrap().terminals(),
+        }
+    }
+
+    /// identifiers of the edges of the component, in the order of the
+    /// decomposition
+    pub fn edges(&self) -> Vec<&String> {
+        match self {
+            Decomposition::Edge { id, .. } => vec![id],
+            Decomposition::Series(parts) | Decomposition::Parallel(parts) => {
+                parts.iter().flat_map(|p| p.edges()).collect()
+            }
+        }
+    }
+
+    /// same component with source and sink swapped
+    fn reversed(self) -> Decomposition {
+        match self {
+            Decomposition::Edge { id, source, sink } => Decomposition::Edge {
+                id,
+                source: sink,
+                sink: source,
+            },
+            Decomposition::Series(parts) => {
+                Decomposition::Series(parts.into_iter().rev().map(|p| p.reversed()).collect())
+            }
+            Decomposition::Parallel(parts) => {
+                Decomposition::Parallel(parts.into_iter().map(|p| p.reversed()).collect())
+            }
+        }
+    }
+
+    /// component oriented from `source`
+    fn oriented_from(self, source: &String) -> Decomposition {
+        if self.terminals().0 == source {
+            self
+        } else {
+            self.reversed()
+        }
+    }
+
+    /// series composition, nested series being flattened
+    fn series(self, other: Decomposition) -> Decomposition {
+        let mut parts = match self {
+            Decomposition::Series(parts) => parts,
+            d => vec![d],
+        };
+        match other {
+            Decomposition::Series(others) => parts.extend(others),
+            d => parts.push(d),
+        }
+        Decomposition::Series(parts)
+    }
+
+    /// parallel composition, nested parallels being flattened
+    fn parallel(self, other: Decomposition) -> Decomposition {
+        let source = self.terminals().0.clone();
+        let mut parts = match self {
+            Decomposition::Parallel(parts) => parts,
+            d => vec![d],
+        };
+        match other.oriented_from(&source) {
+            Decomposition::Parallel(others) => parts.extend(others),
+            d => parts.push(d),
+        }
+        Decomposition::Parallel(parts)
+    }
+}
+
+/// multigraph under series and parallel reductions, every edge standing
+/// for the component between its end vertices
+struct Reduction {
+    /// component of every edge still present
+    edges: Vec<Option<(usize, usize, Decomposition)>>,
+    /// edges still present at every vertex
+    incident: Vec<BTreeSet<usize>>,
+    /// edge joining every adjacent pair of vertices, smaller position first
+    between: HashMap<(usize, usize), usize>,
+}
+
+impl Reduction {
+    /// add a component between `u` and `v`, merging it in parallel with
+    /// the edge already joining them
+    fn add(&mut self, u: usize, v: usize, d: Decomposition) {
+        let pair = (u.min(v), u.max(v));
+        if let Some(k) = self.between.get(&pair) {
+            let (a, b, old) = self.edges[*k].take().unwrap();
+            self.edges[*k] = Some((a, b, old.parallel(d)));
+            return;
+        }
+        let k = self.edges.len();
+        self.edges.push(Some((u, v, d)));
+        self.incident[u].insert(k);
+        self.incident[v].insert(k);
+        self.between.insert(pair, k);
+    }
+
+    /// remove an edge and return its end vertices and component
+    fn remove(&mut self, k: usize) -> (usize, usize, Decomposition) {
+        let (u, v, d) = self.edges[k].take().unwrap();
+        self.incident[u].remove(&k);
+        self.incident[v].remove(&k);
+        self.between.remove(&(u.min(v), u.max(v)));
+        (u, v, d)
+    }
+}
+
+/// Decide whether a graph is two terminal series-parallel, with its
+/// decomposition tree
+/// # Description
+/// A two terminal series-parallel graph is a single edge between its two
+/// terminals, or is built from two smaller ones by a series composition,
+/// which identifies the sink of the first with the source of the second,
+/// or by a parallel composition, which identifies both sources and both
+/// sinks. The graph is reduced by merging parallel edges and by replacing
+/// the two edges at a vertex of degree two with a single one, each
+/// remaining edge recording the component it stands for. The graph is
+/// series-parallel for some choice of terminals exactly when a single edge
+/// is left, whose component is the returned decomposition. Its terminals
+/// are given by [Decomposition::terminals], the source being the smaller
+/// identifier, and every edge of `g` appears once in it. Edge orientation
+/// is ignored, parallel edges are allowed and graphs with a loop, an
+/// isolated vertex or less than two vertices are not series-parallel.
+/// Vertices of degree two are reduced in identifier order, so the
+/// decomposition is reproducible.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - returns: the decomposition tree if `g` is series-parallel
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::recognition::is_series_parallel;
+/// use pgm_rust::graph::ops::graph::recognition::Decomposition;
+/// use std::collections::HashSet;
+///
+/// // a square a b c d
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "c", "d");
+/// let e4 = Edge::empty("e4", EdgeType::Undirected, "d", "a");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3, e4]));
+/// let d = is_series_parallel(&g).unwrap();
+/// matches!(d, Decomposition::Parallel(_)); // true
+/// d.edges().len() == 4; // true
+/// ```
+///
+/// # References
+/// Valdes J, Tarjan R, Lawler E. The recognition of series parallel
+/// digraphs. 1982.
+///
+/// Duffin R. Topology of series-parallel networks. 1965.
+pub fn is_series_parallel<N, E, G>(g: &G) -> Option<Decomposition>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let ids: Vec<String> = g
+        .vertices_sorted()
+        .into_iter()
+        .map(|v| v.id().clone())
+        .collect();
+    let n = ids.len();
+    if n < 2 {
+        return None;
+    }
+    let index: HashMap<&String, usize> = ids.iter().enumerate().map(|(i, v)| (v, i)).collect();
+    let mut r = Reduction {
+        edges: Vec::new(),
+        incident: vec![BTreeSet::new(); n],
+        between: HashMap::new(),
+    };
+    for e in g.edges_sorted() {
+        let (s, t) = (e.start().id(), e.end().id());
+        if s == t {
+            return None;
+        }
+        let d = Decomposition::Edge {
+            id: e.id().clone(),
+            source: s.clone(),
+            sink: t.clone(),
+        };
+        r.add(index[s], index[t], d);
+    }
+    let mut remaining = n;
+    let mut pending: BTreeSet<usize> = (0..n).filter(|v| r.incident[*v].len() == 2).collect();
+    while let Some(w) = pending.pop_first() {
+        if remaining == 2 || r.incident[w].len() != 2 {
+            continue;
+        }
+        let ks: Vec<usize> = r.incident[w].iter().copied().collect();
+        let (a1, b1, d1) = r.remove(ks[0]);
+        let (a2, b2, d2) = r.remove(ks[1]);
+        let x = if a1 == w { b1 } else { a1 };
+        let y = if a2 == w { b2 } else { a2 };
+        let d = d1.oriented_from(&ids[x]).series(d2.oriented_from(&ids[w]));
+        r.add(x, y, d);
+        remaining -= 1;
+        pending.extend([x, y].into_iter().filter(|v| r.incident[*v].len() == 2));
+    }
+    let mut left = r.edges.into_iter().flatten();
+    match (remaining, left.next(), left.next()) {
+        (2, Some((u, v, d)), None) => Some(d.oriented_from(&ids[u.min(v)])),
+        _ => None,
+    }
+}
+
+/// whether every vertex and its neighbors later in `order` form a clique
+pub(crate) fn is_perfect_elimination(
+    neighbors: &HashMap<&String, HashSet<&String>>,
+    order: &[&String],
+) -> bool {
+    let position: HashMap<&String, usize> =
+        order.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+    order.iter().enumerate().all(|(i, v)| {
+        let later: Vec<&String> = neighbors[v]
+            .iter()
+            .filter(|w| position[*w] > i)
+            .copied()
+            .collect();
+        // the closest later neighbor must see all the others
+        match later.iter().min_by_key(|w| position[*w]) {
+            None => true,
+            Some(u) => later.iter().all(|w| w == u || neighbors[u].contains(w)),
+        }
+    })
+}
+
+/// Decide whether a graph is chordal, with a perfect elimination ordering
+/// # Description
+/// A graph is chordal, or triangulated, if every cycle of length at least
+/// four has a chord, an edge joining two of its vertices that are not
+/// consecutive on it. Equivalently its vertices can be eliminated one by
+/// one, each having neighbors forming a clique among the vertices left: a
+/// perfect elimination ordering. The [mcs_ordering] is one whenever the
+/// graph is chordal, which is then checked. This is the condition for the
+/// cliques of a graph to form a junction tree. Edge orientation is
+/// ignored, parallel edges count as a single neighbor and self loops are
+/// not taken into account.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - returns: identifiers of the vertices in a perfect elimination ordering
+///   if `g` is chordal
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::recognition::is_chordal;
+/// use std::collections::HashSet;
+///
+/// // a square a b c d
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "c", "d");
+/// let e4 = Edge::empty("e4", EdgeType::Undirected, "d", "a");
+/// let square: Graph<Node, Edge<Node>> =
+///     Graph::from_edgeset(HashSet::from([e1.clone(), e2.clone(), e3.clone(), e4.clone()]));
+/// is_chordal(&square).is_none(); // true
+/// let e5 = Edge::empty("e5", EdgeType::Undirected, "a", "c");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3, e4, e5]));
+/// is_chordal(&g).unwrap().len() == 4; // true
+/// ```
+///
+/// # References
+/// Tarjan R, Yannakakis M. Simple linear-time algorithms to test
+/// chordality of graphs, test acyclicity of hypergraphs, and selectively
+/// reduce acyclic hypergraphs. 1984.
+pub fn is_chordal<N, E, G>(g: &G) -> Option<Vec<String>>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let neighbors = simple_neighbors(g);
+    let order = mcs_ordering(g);
+    let refs: Vec<&String> = order.iter().collect();
+    if is_perfect_elimination(&neighbors, &refs) {
+        Some(order)
+    } else {
+        None
+    }
+}
+
+/// vertices in lexicographic breadth first search order by partition
+/// refinement, ties going to the vertex coming first in `start`
+fn lex_bfs<'a>(
+    neighbors: &HashMap<&'a String, HashSet<&'a String>>,
+    start: Vec<&'a String>,
+) -> Vec<&'a String> {
+    // classes of unvisited vertices, the next vertex being the first one
+    // of the first class
+    let mut classes: Vec<Vec<&String>> = vec![start];
+    classes.retain(|c| !c.is_empty());
+    let mut order = Vec::with_capacity(neighbors.len());
+    while let Some(class) = classes.first_mut() {
+        let v = class.remove(0);
+        if class.is_empty() {
+            classes.remove(0);
+        }
+        order.push(v);
+        let ns = &neighbors[v];
+        classes = classes
+            .into_iter()
+            .flat_map(|c| {
+                let (inside, outside): (Vec<&String>, Vec<&String>) =
+                    c.into_iter().partition(|w| ns.contains(w));
+                [inside, outside]
+            })
+            .filter(|c| !c.is_empty())
+            .collect();
+    }
+    order
+}
+
+/// maximal cliques of a chordal graph from a perfect elimination ordering,
+/// every clique being sorted
+fn chordal_cliques<'a>(
+    neighbors: &HashMap<&'a String, HashSet<&'a String>>,
+    peo: &[String],
+) -> Vec<Vec<&'a String>> {
+    let position: HashMap<&String, usize> = peo.iter().enumerate().map(|(i, v)| (v, i)).collect();
+    let mut candidates: Vec<Vec<&String>> = peo
+        .iter()
+        .enumerate()
+        .map(|(i, v)| {
+            let (v, ns) = neighbors.get_key_value(v).unwrap();
+            let mut clique: Vec<&String> =
+                ns.iter().filter(|w| position[*w] > i).copied().collect();
+            clique.push(v);
+            clique.sort();
+            clique
+        })
+        .collect();
+    candidates.sort_by(|a, b| b.len().cmp(&a.len()).then(a.cmp(b)));
+    let mut cliques: Vec<Vec<&String>> = Vec::new();
+    for c in candidates {
+        let contained = cliques
+            .iter()
+            .any(|k| c.iter().all(|v| k.binary_search(v).is_ok()));
+        if !contained {
+            cliques.push(c);
+        }
+    }
+    cliques.sort();
+    cliques
+}
+
+/// ordered blocks of the union of a connected component of overlapping
+/// sets, every set being consecutive in any order of the blocks and their
+/// elements, unique up to reversal
+fn overlap_blocks(sets: &[&BTreeSet<usize>]) -> Option<Vec<BTreeSet<usize>>> {
+    let mut blocks: Vec<BTreeSet<usize>> = vec![sets[0].clone()];
+    for s in &sets[1..] {
+        let touched: Vec<usize> = (0..blocks.len())
+            .filter(|i| !blocks[*i].is_disjoint(s))
+            .collect();
+        let (f, l) = (touched[0], *touched.last().unwrap());
+        if l - f + 1 != touched.len() || (f + 1..l).any(|i| !blocks[i].is_subset(s)) {
+            return None;
+        }
+        let known: BTreeSet<usize> = blocks.iter().flatten().copied().collect();
+        let new: BTreeSet<usize> = s.difference(&known).copied().collect();
+        let at_right = l == blocks.len() - 1 && (f == l || blocks[l].is_subset(s));
+        let at_left = f == 0 && (f == l || blocks[f].is_subset(s));
+        // split a block between its elements inside and outside `s`
+        let split = |b: &BTreeSet<usize>| -> (BTreeSet<usize>, BTreeSet<usize>) {
+            b.iter().partition(|x| s.contains(x))
+        };
+        if new.is_empty() {
+            if f == l {
+                // sets overlapping a placed one never fall in one block
+                if !blocks[f].is_subset(s) {
+                    return None;
+                }
+                continue;
+            }
+            let (l_in, l_out) = split(&blocks[l]);
+            let (f_in, f_out) = split(&blocks[f]);
+            blocks.splice(l..=l, [l_in, l_out].into_iter().filter(|b| !b.is_empty()));
+            blocks.splice(f..=f, [f_out, f_in].into_iter().filter(|b| !b.is_empty()));
+        } else if at_right {
+            let (f_in, f_out) = split(&blocks[f]);
+            blocks.splice(f..=f, [f_out, f_in].into_iter().filter(|b| !b.is_empty()));
+            blocks.push(new);
+        } else if at_left {
+            let (l_in, l_out) = split(&blocks[l]);
+            blocks.splice(l..=l, [l_in, l_out].into_iter().filter(|b| !b.is_empty()));
+            blocks.insert(0, new);
+        } else {
+            return None;
+        }
+    }
+    Some(blocks)
+}
+
+/// order of `0..n` in which every set is consecutive, if there is one
+/// # Description
+/// Sets are grouped into connected components of the overlap relation,
+/// two sets overlapping when they intersect without one containing the
+/// other. The order of a component is forced up to reversal, see
+/// [overlap_blocks], and the union of a component lies within a single
+/// block of any component whose union strictly contains it, so components
+/// are nested into each other.
+fn consecutive_order(n: usize, sets: &[BTreeSet<usize>]) -> Option<Vec<usize>> {
+    let mut family: Vec<&BTreeSet<usize>> = sets.iter().filter(|s| s.len() > 1).collect();
+    family.sort();
+    family.dedup();
+    let overlap = |a: &BTreeSet<usize>, b: &BTreeSet<usize>| {
+        !a.is_disjoint(b) && !a.is_subset(b) && !b.is_subset(a)
+    };
+    // components of the overlap relation, every set overlapping an earlier
+    // one of its component
+    let mut seen = vec![false; family.len()];
+    let mut components: Vec<Vec<&BTreeSet<usize>>> = Vec::new();
+    for i in 0..family.len() {
+        if seen[i] {
+            continue;
+        }
+        seen[i] = true;
+        let mut component = vec![family[i]];
+        let mut k = 0;
+        while k < component.len() {
+            for j in 0..family.len() {
+                if !seen[j] && overlap(component[k], family[j]) {
+                    seen[j] = true;
+                    component.push(family[j]);
+                }
+            }
+            k += 1;
+        }
+        components.push(component);
+    }
+    // blocks and union of every component, the whole ground set first
+    let mut nodes: Vec<(Vec<BTreeSet<usize>>, BTreeSet<usize>)> =
+        vec![(vec![(0..n).collect()], (0..n).collect())];
+    for c in &components {
+        let blocks = overlap_blocks(c)?;
+        let union = blocks.iter().flatten().copied().collect();
+        nodes.push((blocks, union));
+    }
+    nodes[1..].sort_by(|a, b| b.1.len().cmp(&a.1.len()).then(a.0.len().cmp(&b.0.len())));
+    // components nested in every block of every component
+    let mut children: Vec<Vec<Vec<usize>>> = nodes
+        .iter()
+        .map(|(b, _)| vec![Vec::new(); b.len()])
+        .collect();
+    for c in 1..nodes.len() {
+        let union = &nodes[c].1;
+        let parent = (0..c)
+            .rev()
+            .find(|p| union.is_subset(&nodes[*p].1))
+            .unwrap();
+        let block = nodes[parent].0.iter().position(|b| union.is_subset(b))?;
+        children[parent][block].push(c);
+    }
+    /// elements of the blocks of a component in order, the components
+    /// nested in a block coming first in it
+    fn expand(
+        c: usize,
+        nodes: &[(Vec<BTreeSet<usize>>, BTreeSet<usize>)],
+        children: &[Vec<Vec<usize>>],
+        placed: &mut [bool],
+        order: &mut Vec<usize>,
+    ) {
+        for (b, block) in nodes[c].0.iter().enumerate() {
+            for child in &children[c][b] {
+                expand(*child, nodes, children, placed, order);
+            }
+            for x in block {
+                if !placed[*x] {
+                    placed[*x] = true;
+                    order.push(*x);
+                }
+            }
+        }
+    }
+    let mut order = Vec::with_capacity(n);
+    expand(0, &nodes, &children, &mut vec![false; n], &mut order);
+    let position: HashMap<usize, usize> = order.iter().enumerate().map(|(i, x)| (*x, i)).collect();
+    let consecutive = sets.iter().filter(|s| !s.is_empty()).all(|s| {
+        let ps: Vec<usize> = s.iter().map(|x| position[x]).collect();
+        ps.iter().max().unwrap() - ps.iter().min().unwrap() + 1 == s.len()
+    });
+    consecutive.then_some(order)
+}
+
+/// Decide whether a graph is an interval graph, with an interval
+/// representation
+/// # Description
+/// An interval graph has a closed interval of integers for every vertex,
+/// two vertices being adjacent exactly when their intervals intersect. A
+/// graph is an interval graph if and only if it is chordal and its maximal
+/// cliques can be ordered such that the cliques holding any vertex are
+/// consecutive. The cliques are read from the perfect elimination ordering
+/// of [is_chordal] and ordered with the overlap components of the sets of
+/// cliques holding every vertex, see the references. The interval of a
+/// vertex spans the positions of its cliques in that order. Edge
+/// orientation is ignored, parallel edges count as a single neighbor and
+/// self loops are not taken into account.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - returns: first and last position of the interval of every vertex if
+///   `g` is an interval graph
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::recognition::is_interval_graph;
+/// use std::collections::HashSet;
+///
+/// // a triangle a b c with a pendant edge c d
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "c", "a");
+/// let e4 = Edge::empty("e4", EdgeType::Undirected, "c", "d");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3, e4]));
+/// let intervals = is_interval_graph(&g).unwrap();
+/// intervals["c"] == (0, 1); // true
+/// intervals["d"] == (1, 1); // true
+/// ```
+///
+/// # References
+/// Gilmore P, Hoffman A. A characterization of comparability graphs and
+/// of interval graphs. 1964.
+///
+/// Hsu W. A simple test for the consecutive ones property. 2002.
+pub fn is_interval_graph<N, E, G>(g: &G) -> Option<HashMap<String, (usize, usize)>>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let peo = is_chordal(g)?;
+    let neighbors = simple_neighbors(g);
+    let cliques = chordal_cliques(&neighbors, &peo);
+    let mut holding: HashMap<&String, BTreeSet<usize>> = HashMap::new();
+    for (k, clique) in cliques.iter().enumerate() {
+        for v in clique {
+            holding.entry(*v).or_default().insert(k);
+        }
+    }
+    let mut vertices: Vec<&String> = holding.keys().copied().collect();
+    vertices.sort();
+    let sets: Vec<BTreeSet<usize>> = vertices.iter().map(|v| holding[v].clone()).collect();
+    let order = consecutive_order(cliques.len(), &sets)?;
+    let mut position = vec![0; cliques.len()];
+    for (i, k) in order.into_iter().enumerate() {
+        position[k] = i;
+    }
+    Some(
+        vertices
+            .into_iter()
+            .zip(sets)
+            .map(|(v, s)| {
+                let ps = s.iter().map(|k| position[*k]);
+                (v.clone(), (ps.clone().min().unwrap(), ps.max().unwrap()))
+            })
+            .collect(),
+    )
+}
+
+/// Decide whether a graph is a proper interval graph, with an interval
+/// representation
+/// # Description
+/// A proper interval graph is an interval graph, see [is_interval_graph],
+/// with a representation where no interval contains another one. These
+/// are the graphs whose vertices can be ordered such that the closed
+/// neighborhood of every vertex is consecutive, and three sweeps of
+/// lexicographic breadth first search find such an order when there is
+/// one, every sweep after the first breaking ties by the last vertex of
+/// the previous one. The interval of the vertex at position `i` goes from
+/// `i` to the last position of its neighbors, so that both ends grow with
+/// the position and no interval strictly contains another one once
+/// shared ends are slightly perturbed. Edge orientation is ignored,
+/// parallel edges count as a single neighbor and self loops are not taken
+/// into account.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - returns: first and last position of the interval of every vertex if
+///   `g` is a proper interval graph
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::recognition::is_proper_interval_graph;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let path: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1.clone(), e2.clone()]));
+/// is_proper_interval_graph(&path).unwrap().len() == 3; // true
+/// // a claw is an interval graph, but not a proper one
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "b", "d");
+/// let claw: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// is_proper_interval_graph(&claw).is_none(); // true
+/// ```
+///
+/// # References
+/// Roberts F. Indifference graphs. 1969.
+///
+/// Corneil D. A simple 3-sweep LBFS algorithm for the recognition of unit
+/// interval graphs. 2004.
+pub fn is_proper_interval_graph<N, E, G>(g: &G) -> Option<HashMap<String, (usize, usize)>>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let neighbors = simple_neighbors(g);
+    let mut start: Vec<&String> = neighbors.keys().copied().collect();
+    start.sort();
+    let mut order = lex_bfs(&neighbors, start);
+    for _ in 0..2 {
+        order = lex_bfs(&neighbors, order.into_iter().rev().collect());
+    }
+    let position: HashMap<&String, usize> =
+        order.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+    let mut intervals = HashMap::new();
+    for (i, v) in order.iter().enumerate() {
+        let ps = neighbors[v].iter().map(|w| position[w]).chain([i]);
+        let (first, last) = (ps.clone().min().unwrap(), ps.max().unwrap());
+        if last - first + 1 != neighbors[v].len() + 1 {
+            return None;
+        }
+        intervals.insert((*v).clone(), (i, last));
+    }
+    Some(intervals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::traits::graph_obj::GraphObject;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::edgetype::EdgeType;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+    use crate::rng::SplitMix64;
+
+    fn mk_uedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Undirected, n1_id, n2_id)
+    }
+
+    fn mk_g(edges: &[(&str, &str)]) -> Graph<Node, Edge<Node>> {
+        let es: HashSet<Edge<Node>> = edges
+            .iter()
+            .enumerate()
+            .map(|(i, (a, b))| mk_uedge(a, b, &format!("e{i}")))
+            .collect();
+        Graph::from_edgeset(es)
+    }
+
+    /// every component joins its terminals and uses every edge once
+    fn is_valid(g: &Graph<Node, Edge<Node>>, d: &Decomposition) -> bool {
+        fn check(g: &Graph<Node, Edge<Node>>, d: &Decomposition) -> bool {
+            match d {
+                Decomposition::Edge { id, source, sink } => {
+                    let e = g.find_edge(id).unwrap();
+                    (e.start().id(), e.end().id()) == (source, sink)
+                        || (e.start().id(), e.end().id()) == (sink, source)
+                }
+                Decomposition::Series(parts) => {
+                    parts.len() > 1
+                        && parts
+                            .windows(2)
+                            .all(|w| w[0].terminals().1 == w[1].terminals().0)
+                        && parts.iter().all(|p| check(g, p))
+                }
+                Decomposition::Parallel(parts) => {
+                    parts.len() > 1
+                        && parts.iter().all(|p| p.terminals() == d.terminals())
+                        && parts.iter().all(|p| check(g, p))
+                }
+            }
+        }
+        let mut used: Vec<&String> = d.edges();
+        used.sort();
+        let expected: Vec<&String> = g.edges_sorted().into_iter().map(|e| e.id()).collect();
+        used == expected && check(g, d)
+    }
+
+    #[test]
+    fn test_is_series_parallel() {
+        let graphs = [
+            mk_g(&[("a", "b")]),
+            mk_g(&[("a", "b"), ("b", "c"), ("c", "d")]),
+            mk_g(&[("a", "b"), ("b", "c"), ("c", "d"), ("d", "a")]),
+            // complete graph on four vertices minus an edge
+            mk_g(&[("a", "b"), ("a", "c"), ("a", "d"), ("b", "c"), ("c", "d")]),
+            // parallel edges and a pendant edge
+            mk_g(&[("a", "b"), ("b", "a"), ("a", "b"), ("b", "c")]),
+            // two triangles sharing c
+            mk_g(&[
+                ("a", "b"),
+                ("b", "c"),
+                ("c", "a"),
+                ("c", "d"),
+                ("d", "e"),
+                ("e", "c"),
+            ]),
+        ];
+        for g in &graphs {
+            let d = is_series_parallel(g).unwrap();
+            assert!(is_valid(g, &d), "{d:?}");
+        }
+        let path = is_series_parallel(&graphs[1]).unwrap();
+        assert_eq!(path.terminals(), (&"a".to_string(), &"d".to_string()));
+        assert_eq!(path.edges(), vec!["e0", "e1", "e2"]);
+    }
+
+    #[test]
+    fn test_is_not_series_parallel() {
+        let k4 = mk_g(&[
+            ("a", "b"),
+            ("a", "c"),
+            ("a", "d"),
+            ("b", "c"),
+            ("b", "d"),
+            ("c", "d"),
+        ]);
+        assert!(is_series_parallel(&k4).is_none());
+        let star = mk_g(&[("z", "a"), ("z", "b"), ("z", "c")]);
+        assert!(is_series_parallel(&star).is_none());
+        let triangles = mk_g(&[
+            ("a", "b"),
+            ("b", "z"),
+            ("z", "a"),
+            ("c", "d"),
+            ("d", "z"),
+            ("z", "c"),
+            ("e", "f"),
+            ("f", "z"),
+            ("z", "e"),
+        ]);
+        assert!(is_series_parallel(&triangles).is_none());
+        let disconnected = mk_g(&[("a", "b"), ("c", "d")]);
+        assert!(is_series_parallel(&disconnected).is_none());
+        let looped = mk_g(&[("a", "b"), ("b", "b")]);
+        assert!(is_series_parallel(&looped).is_none());
+        let single: Graph<Node, Edge<Node>> =
+            Graph::from_edge_node_set(HashSet::new(), HashSet::from([Node::empty("a")]));
+        assert!(is_series_parallel(&single).is_none());
+    }
+
+    #[test]
+    fn test_is_chordal() {
+        // two triangles a b c and b c d glued along b c, with a tail d e
+        let g = mk_g(&[
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "a"),
+            ("b", "d"),
+            ("c", "d"),
+            ("d", "e"),
+            ("e", "e"),
+        ]);
+        let peo = is_chordal(&g).unwrap();
+        let mut sorted = peo.clone();
+        sorted.sort();
+        assert_eq!(sorted, vec!["a", "b", "c", "d", "e"]);
+        let neighbors = simple_neighbors(&g);
+        let refs: Vec<&String> = peo.iter().collect();
+        assert!(is_perfect_elimination(&neighbors, &refs));
+        // trees and complete graphs are chordal
+        let star = mk_g(&[("z", "a"), ("z", "b"), ("z", "c")]);
+        assert!(is_chordal(&star).is_some());
+        let k4 = mk_g(&[
+            ("a", "b"),
+            ("a", "c"),
+            ("a", "d"),
+            ("b", "c"),
+            ("b", "d"),
+            ("c", "d"),
+        ]);
+        assert!(is_chordal(&k4).is_some());
+        let empty: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::new());
+        assert_eq!(is_chordal(&empty), Some(vec![]));
+    }
+
+    #[test]
+    fn test_is_not_chordal() {
+        let square = mk_g(&[("a", "b"), ("b", "c"), ("c", "d"), ("d", "a")]);
+        assert!(is_chordal(&square).is_none());
+        // a pentagon with the chords a c and a d is chordal, dropping a d
+        // leaves the chordless cycle a c d e
+        let fan = mk_g(&[
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "d"),
+            ("d", "e"),
+            ("e", "a"),
+            ("a", "c"),
+            ("a", "d"),
+        ]);
+        assert!(is_chordal(&fan).is_some());
+        let hole = mk_g(&[
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "d"),
+            ("d", "e"),
+            ("e", "a"),
+            ("a", "c"),
+        ]);
+        assert!(is_chordal(&hole).is_none());
+    }
+
+    /// adjacent vertices are exactly those with intersecting intervals
+    fn represents(
+        g: &Graph<Node, Edge<Node>>,
+        intervals: &HashMap<String, (usize, usize)>,
+    ) -> bool {
+        let neighbors = simple_neighbors(g);
+        intervals.len() == g.order()
+            && intervals.iter().all(|(u, (a, b))| {
+                a <= b
+                    && intervals
+                        .iter()
+                        .all(|(v, (c, d))| u == v || (a <= d && c <= b) == neighbors[u].contains(v))
+            })
+    }
+
+    /// a tree with three legs of length two around z
+    fn mk_spider() -> Graph<Node, Edge<Node>> {
+        mk_g(&[
+            ("z", "a"),
+            ("a", "x"),
+            ("z", "b"),
+            ("b", "y"),
+            ("z", "c"),
+            ("c", "w"),
+        ])
+    }
+
+    /// a triangle a b c with a pendant edge at every vertex
+    fn mk_net() -> Graph<Node, Edge<Node>> {
+        mk_g(&[
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "a"),
+            ("a", "x"),
+            ("b", "y"),
+            ("c", "w"),
+        ])
+    }
+
+    #[test]
+    fn test_is_interval_graph() {
+        let mut with_isolated = mk_g(&[("a", "b"), ("b", "c"), ("c", "a"), ("c", "d")]);
+        with_isolated = Graph::from_edge_node_set(
+            with_isolated.edges().into_iter().cloned().collect(),
+            HashSet::from([Node::empty("f")]),
+        );
+        let graphs = [
+            mk_g(&[("a", "b"), ("b", "c"), ("c", "d"), ("d", "e")]),
+            mk_g(&[("z", "a"), ("z", "b"), ("z", "c")]),
+            with_isolated,
+            // a caterpillar with a triangle on its spine
+            mk_g(&[
+                ("a", "b"),
+                ("b", "c"),
+                ("c", "d"),
+                ("b", "x"),
+                ("b", "y"),
+                ("c", "z"),
+                ("b", "d"),
+            ]),
+        ];
+        for g in &graphs {
+            let intervals = is_interval_graph(g).unwrap();
+            assert!(represents(g, &intervals), "{intervals:?}");
+        }
+        let empty: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::new());
+        assert!(is_interval_graph(&empty).unwrap().is_empty());
+        // chordless cycles and asteroidal triples
+        let square = mk_g(&[("a", "b"), ("b", "c"), ("c", "d"), ("d", "a")]);
+        assert!(is_interval_graph(&square).is_none());
+        assert!(is_interval_graph(&mk_spider()).is_none());
+        assert!(is_interval_graph(&mk_net()).is_none());
+    }
+
+    #[test]
+    fn test_is_interval_graph_random() {
+        let mut rng = SplitMix64::new(7);
+        for _ in 0..50 {
+            // intersection graph of random intervals
+            let n = 2 + rng.gen_range(10);
+            let spans: Vec<(usize, usize)> = (0..n)
+                .map(|_| {
+                    let a = rng.gen_range(20);
+                    (a, a + rng.gen_range(6))
+                })
+                .collect();
+            let mut edges = Vec::new();
+            for i in 0..n {
+                for j in i + 1..n {
+                    let ((a, b), (c, d)) = (spans[i], spans[j]);
+                    if a <= d && c <= b {
+                        edges.push(mk_uedge(
+                            &format!("v{i}"),
+                            &format!("v{j}"),
+                            &format!("e{i}_{j}"),
+                        ));
+                    }
+                }
+            }
+            let nodes = (0..n).map(|i| Node::empty(&format!("v{i}"))).collect();
+            let g = Graph::from_edge_node_set(edges.into_iter().collect(), nodes);
+            let intervals = is_interval_graph(&g).unwrap();
+            assert!(represents(&g, &intervals));
+        }
+    }
+
+    #[test]
+    fn test_is_proper_interval_graph() {
+        let graphs = [
+            mk_g(&[("a", "b"), ("b", "c"), ("c", "d"), ("d", "e")]),
+            mk_g(&[
+                ("a", "b"),
+                ("a", "c"),
+                ("a", "d"),
+                ("b", "c"),
+                ("b", "d"),
+                ("c", "d"),
+            ]),
+            // two triangles sharing the edge b c, with a tail d e
+            mk_g(&[
+                ("a", "b"),
+                ("b", "c"),
+                ("c", "a"),
+                ("b", "d"),
+                ("c", "d"),
+                ("d", "e"),
+            ]),
+            mk_g(&[("a", "b"), ("c", "d")]),
+        ];
+        for g in &graphs {
+            let intervals = is_proper_interval_graph(g).unwrap();
+            assert!(represents(g, &intervals), "{intervals:?}");
+            // ends grow together with starts
+            let mut spans: Vec<_> = intervals.values().collect();
+            spans.sort();
+            assert!(spans.windows(2).all(|w| w[0].1 <= w[1].1));
+        }
+        let claw = mk_g(&[("z", "a"), ("z", "b"), ("z", "c")]);
+        assert!(is_interval_graph(&claw).is_some());
+        assert!(is_proper_interval_graph(&claw).is_none());
+        let square = mk_g(&[("a", "b"), ("b", "c"), ("c", "d"), ("d", "a")]);
+        assert!(is_proper_interval_graph(&square).is_none());
+        assert!(is_proper_interval_graph(&mk_net()).is_none());
+    }
+}
@@ -0,0 +1,317 @@
+//! random subgraphs of graphs
+use crate::graph::ops::graph::misc::get_subgraph_by_vertices;
+use crate::graph::ops::graph::order::simple_neighbors;
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::rng::SplitMix64;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+
+/// subgraph of `g` induced by `kept`, with the identifier and data of `g`
+fn induced<N, E, G>(g: &G, kept: HashSet<&N>) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let (nodes, edges) = get_subgraph_by_vertices(g, kept, None::<fn(&E, &HashSet<&N>) -> bool>);
+    G::create_from_ref(g.id().clone(), g.data().clone(), nodes, edges)
+}
+
+/// Sample vertices of a graph uniformly
+/// # Description
+/// `k` vertices are drawn uniformly without replacement and the subgraph
+/// they induce is returned: every edge of `g` whose ends are both drawn is
+/// kept. Panics if `g` has fewer than `k` vertices. Graph identifier and
+/// data are kept. Vertices are drawn from a [SplitMix64] generator seeded
+/// by `seed`.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - k: number of vertices to draw
+/// - seed: seed of the random number generator
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+/// use pgm_rust::graph::ops::graph::sample::sample_nodes;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+/// let h = sample_nodes(&g, 2, 1);
+/// h.order() == 2; // true
+/// ```
+pub fn sample_nodes<N, E, G>(g: &G, k: usize, seed: u64) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let mut vertices = g.vertices_sorted();
+    if vertices.len() < k {
+        panic!(
+            "{g} has {} vertices, {k} can not be sampled",
+            vertices.len()
+        );
+    }
+    SplitMix64::new(seed).shuffle(&mut vertices);
+    induced(g, vertices.into_iter().take(k).collect())
+}
+
+/// Sample edges of a graph uniformly
+/// # Description
+/// `k` edges are drawn uniformly without replacement and returned with
+/// their ends as vertices. Other edges between the drawn ends are not
+/// added, so the sample has exactly `k` edges. Panics if `g` has fewer than
+/// `k` edges. Graph identifier and data are kept. Edges are drawn from a
+/// [SplitMix64] generator seeded by `seed`.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - k: number of edges to draw
+/// - seed: seed of the random number generator
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+/// use pgm_rust::graph::ops::graph::sample::sample_edges;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "c", "d");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+/// let h = sample_edges(&g, 1, 1);
+/// h.size() == 1; // true
+/// h.order() == 2; // true
+/// ```
+pub fn sample_edges<N, E, G>(g: &G, k: usize, seed: u64) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let mut edges = g.edges_sorted();
+    if edges.len() < k {
+        panic!("{g} has {} edges, {k} can not be sampled", edges.len());
+    }
+    SplitMix64::new(seed).shuffle(&mut edges);
+    let kept: HashSet<&E> = edges.into_iter().take(k).collect();
+    let nodes: HashSet<&N> = kept.iter().flat_map(|e| [e.start(), e.end()]).collect();
+    G::create_from_ref(g.id().clone(), g.data().clone(), nodes, kept)
+}
+
+/// Sample a graph by forest fire
+/// # Description
+/// A fire starts at a random vertex. Each burning vertex spreads to a
+/// number of its neighbors that are not burnt yet, drawn from a geometric
+/// distribution of mean `p / (1 - p)`, and these neighbors are picked at
+/// random. When the fire dies out before `k` vertices burnt, a new one
+/// starts at a random vertex that is not burnt. The subgraph induced by
+/// the `k` burnt vertices is returned. Contrarily to uniform vertex
+/// sampling, it keeps the neighborhoods of the vertices, hence the
+/// clustering and degree distribution of `g`, reasonably well. Edge
+/// orientation is ignored while burning. Panics if `p` is not in `[0, 1)`
+/// or if `g` has fewer than `k` vertices. Graph identifier and data are
+/// kept.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - k: number of vertices to burn
+/// - p: forward burning probability, in `[0, 1)`
+/// - seed: seed of the random number generator
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+/// use pgm_rust::graph::ops::graph::sample::forest_fire_sample;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "c", "d");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// let h = forest_fire_sample(&g, 3, 0.7, 1);
+/// h.order() == 3; // true
+/// ```
+///
+/// # References
+/// Leskovec J, Faloutsos C. Sampling from large graphs. 2006.
+pub fn forest_fire_sample<N, E, G>(g: &G, k: usize, p: f64, seed: u64) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    if !(0.0..1.0).contains(&p) {
+        panic!("burning probability {p} must be in [0, 1)");
+    }
+    let vertices = g.vertices_sorted();
+    if vertices.len() < k {
+        panic!(
+            "{g} has {} vertices, {k} can not be sampled",
+            vertices.len()
+        );
+    }
+    let neighbors = simple_neighbors(g);
+    let mut rng = SplitMix64::new(seed);
+    let mut burnt: HashSet<&String> = HashSet::new();
+    let mut front: VecDeque<&String> = VecDeque::new();
+    while burnt.len() < k {
+        let start = match front.pop_front() {
+            Some(v) => v,
+            None => {
+                // the fire died out, restart it at a vertex that is not burnt
+                let unburnt: Vec<&String> = vertices
+                    .iter()
+                    .map(|v| v.id())
+                    .filter(|v| !burnt.contains(v))
+                    .collect();
+                let v = unburnt[rng.gen_range(unburnt.len())];
+                burnt.insert(v);
+                v
+            }
+        };
+        let mut spread = 0;
+        while rng.next_f64() < p {
+            spread += 1;
+        }
+        let mut candidates: Vec<&String> = neighbors[start]
+            .iter()
+            .copied()
+            .filter(|v| !burnt.contains(v))
+            .collect();
+        candidates.sort();
+        rng.shuffle(&mut candidates);
+        for v in candidates.into_iter().take(spread) {
+            if burnt.len() == k {
+                break;
+            }
+            burnt.insert(v);
+            front.push_back(v);
+        }
+    }
+    let kept: HashSet<&N> = vertices
+        .into_iter()
+        .filter(|v| burnt.contains(v.id()))
+        .collect();
+    induced(g, kept)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::traits::graph_obj::GraphObject;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::edgetype::EdgeType;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+
+    fn mk_uedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Undirected, n1_id, n2_id)
+    }
+
+    /// two disjoint cycles of 6 vertices
+    fn mk_g() -> Graph<Node, Edge<Node>> {
+        let mut es: HashSet<Edge<Node>> = HashSet::new();
+        for c in ["a", "b"] {
+            for i in 0..6 {
+                es.insert(mk_uedge(
+                    &format!("{c}{i}"),
+                    &format!("{c}{}", (i + 1) % 6),
+                    &format!("{c}e{i}"),
+                ));
+            }
+        }
+        Graph::from_edgeset(es)
+    }
+
+    fn is_induced(h: &Graph<Node, Edge<Node>>, g: &Graph<Node, Edge<Node>>) -> bool {
+        g.edges().into_iter().all(|e| {
+            let inside = h.contains_node_id(e.start().id()) && h.contains_node_id(e.end().id());
+            inside == h.edges().contains(e)
+        })
+    }
+
+    #[test]
+    fn test_sample_nodes() {
+        let g = mk_g();
+        let h = sample_nodes(&g, 5, 3);
+        assert_eq!(h.order(), 5);
+        assert!(is_induced(&h, &g));
+        assert_eq!(h.id(), g.id());
+        assert_eq!(h, sample_nodes(&g, 5, 3));
+        assert_eq!(sample_nodes(&g, 12, 3), g);
+        assert_eq!(sample_nodes(&g, 0, 3).order(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sample_nodes_too_many() {
+        sample_nodes(&mk_g(), 13, 3);
+    }
+
+    #[test]
+    fn test_sample_edges() {
+        let g = mk_g();
+        let h = sample_edges(&g, 4, 7);
+        assert_eq!(h.size(), 4);
+        assert!(h.edges().iter().all(|e| g.edges().contains(e)));
+        assert!(h.vertices().iter().all(|v| {
+            h.edges()
+                .iter()
+                .any(|e| e.start().id() == v.id() || e.end().id() == v.id())
+        }));
+        assert_eq!(h, sample_edges(&g, 4, 7));
+        assert_eq!(sample_edges(&g, 12, 7), g);
+    }
+
+    #[test]
+    fn test_forest_fire_sample() {
+        let g = mk_g();
+        for seed in 0..20 {
+            let h = forest_fire_sample(&g, 7, 0.6, seed);
+            assert_eq!(h.order(), 7);
+            assert!(is_induced(&h, &g));
+        }
+        assert_eq!(
+            forest_fire_sample(&g, 7, 0.6, 11),
+            forest_fire_sample(&g, 7, 0.6, 11)
+        );
+        // without spreading every vertex is a new fire
+        assert_eq!(forest_fire_sample(&g, 12, 0.0, 2), g);
+    }
+
+    #[test]
+    fn test_forest_fire_sample_follows_edges() {
+        // a fire that always spreads burns a whole cycle before restarting
+        let g = mk_g();
+        let h = forest_fire_sample(&g, 6, 0.99, 4);
+        let prefixes: HashSet<char> = h
+            .vertices()
+            .iter()
+            .map(|v| v.id().chars().next().unwrap())
+            .collect();
+        assert_eq!(prefixes.len(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_forest_fire_sample_bad_probability() {
+        forest_fire_sample(&mk_g(), 3, 1.0, 0);
+    }
+}
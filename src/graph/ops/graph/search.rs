@@ -1,148 +1,855 @@
 //! graph searching
 use crate::graph::ops::edge::nodeops::get_other;
+use crate::graph::ops::graph::edge::edges_of;
+use crate::graph::ops::graph::edge::incoming_edges_of;
+use crate::graph::ops::graph::edge::outgoing_edges_of;
 use crate::graph::traits::edge::Edge as EdgeTrait;
 use crate::graph::traits::graph::Graph as GraphTrait;
-use crate::graph::traits::graph_obj::GraphObject;
 use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::edgetype::EdgeType;
+use crate::graph::types::idpool::IdPool;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::option::Option;
 
+/// edges that a traversal may follow from a vertex
+/// # Description
+/// Undirected edges can be followed from either of their end vertices with
+/// every strategy but [EdgeNeighborhood::Custom]. Directed edges are
+/// followed along their orientation with [EdgeNeighborhood::Out], against it
+/// with [EdgeNeighborhood::In] and in both directions with
+/// [EdgeNeighborhood::Both].
+pub enum EdgeNeighborhood<'a, N, E> {
+    /// outgoing directed edges, see [outgoing_edges_of]
+    Out,
+    /// incoming directed edges, see [incoming_edges_of]
+    In,
+    /// every incident edge, see [edges_of]
+    Both,
+    /// edges produced by a user defined function
+    Custom(Box<dyn Fn(&'a N) -> HashSet<&'a E> + 'a>),
+}
+
+impl<'a, N, E> EdgeNeighborhood<'a, N, E>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N> + 'a,
+{
+    /// edges of `g` that can be followed from `n`
+    /// # Example
+    /// ```
+    /// use pgm_rust::graph::types::edge::Edge;
+    /// use pgm_rust::graph::types::edgetype::EdgeType;
+    /// use pgm_rust::graph::types::graph::Graph;
+    /// use pgm_rust::graph::types::node::Node;
+    /// use pgm_rust::graph::ops::graph::search::EdgeNeighborhood;
+    /// use std::collections::HashSet;
+    ///
+    /// let e1 = Edge::empty("e1", EdgeType::Directed, "n1", "n2");
+    /// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1]));
+    /// let n2 = Node::empty("n2");
+    /// EdgeNeighborhood::Out.edges(&g, &n2).is_empty(); // true
+    /// EdgeNeighborhood::In.edges(&g, &n2).len() == 1; // true
+    /// ```
+    pub fn edges<G>(&self, g: &'a G, n: &'a N) -> HashSet<&'a E>
+    where
+        G: GraphTrait<N, E>,
+    {
+        let is_undirected = |e: &&E| e.has_type() == &EdgeType::Undirected;
+        match self {
+            EdgeNeighborhood::Out => {
+                let mut es = outgoing_edges_of(g, n);
+                es.extend(edges_of(g, n).into_iter().filter(is_undirected));
+                es
+            }
+            EdgeNeighborhood::In => {
+                let mut es = incoming_edges_of(g, n);
+                es.extend(edges_of(g, n).into_iter().filter(is_undirected));
+                es
+            }
+            EdgeNeighborhood::Both => edges_of(g, n),
+            EdgeNeighborhood::Custom(f) => f(n),
+        }
+    }
+}
+
 /// holds information about cycles in the graph
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct CycleInfo {
-    ancestor: String,
-    before: String,
-    ancestor_first_time_visit: usize,
-    ancestor_last_time_visit: Option<usize>,
-    current_final_time_visit: usize,
+    /// vertex at which the cycle starts, it is an ancestor of `before`
+    pub ancestor: String,
+    /// vertex closing the cycle with a back edge to `ancestor`
+    pub before: String,
+    /// first visit time of the ancestor
+    pub ancestor_first_time_visit: usize,
+    /// last visit time of the ancestor if it was known when the back edge
+    /// was found
+    pub ancestor_last_time_visit: Option<usize>,
+    /// last visit time of `before`
+    pub current_final_time_visit: usize,
 }
+
+/// output of a depth first search, see [dfs]
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct DepthFirstResult {
+    /// dfs forest given as the parent of every visited vertex which is not
+    /// a root
+    pub forest: HashMap<String, String>,
+    /// edge identifiers of each dfs tree indexed by the root of the tree
+    pub trees: HashMap<String, HashSet<String>>,
+    /// vertex identifiers of each dfs tree indexed by the root of the tree
+    pub components: HashMap<String, HashSet<String>>,
+    /// roots of the dfs trees in visiting order
+    pub roots: Vec<String>,
+    /// first visit time of every vertex
+    pub first_visit_times: HashMap<String, usize>,
+    /// last visit time of every vertex
+    pub last_visit_times: HashMap<String, usize>,
+    /// cycle info indexed by the vertex closing the cycle. Only filled when
+    /// cycle checking is requested
+    pub cycles: HashMap<String, Vec<CycleInfo>>,
+}
+
+/// string literal of `s`, with the escapes shared by DOT and JSON
+fn quoted(s: &str) -> String {
+    let mut q = String::with_capacity(s.len() + 2);
+    q.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => q.push_str("\\\""),
+            '\\' => q.push_str("\\\\"),
+            '\n' => q.push_str("\\n"),
+            c if (c as u32) < 0x20 => q.push_str(&format!("\\u{:04x}", c as u32)),
+            c => q.push(c),
+        }
+    }
+    q.push('"');
+    q
+}
+
+/// JSON object of `map` with sorted keys, values rendered by `f`
+fn json_object<T, F: Fn(&T) -> String>(map: &HashMap<String, T>, f: F) -> String {
+    let mut entries: Vec<(&String, &T)> = map.iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    let fields: Vec<String> = entries
+        .into_iter()
+        .map(|(k, v)| format!("{}:{}", quoted(k), f(v)))
+        .collect();
+    format!("{{{}}}", fields.join(","))
+}
+
+/// JSON array of the identifiers of `ids`, sorted
+fn json_id_set(ids: &HashSet<String>) -> String {
+    let mut ids: Vec<&String> = ids.iter().collect();
+    ids.sort();
+    let items: Vec<String> = ids.into_iter().map(|v| quoted(v)).collect();
+    format!("[{}]", items.join(","))
+}
+
+impl DepthFirstResult {
+    /// Render the search as a graph in the DOT language of Graphviz
+    /// # Description
+    /// Every visited vertex is labeled with its identifier and its first
+    /// and last visit times, roots are drawn with a double border and each
+    /// dfs tree is a cluster. Tree edges go from parent to child as solid
+    /// arrows. Back edges found by cycle checking go from the vertex closing
+    /// the cycle to its ancestor as dashed arrows, they do not constrain the
+    /// layout so that trees are drawn top down. The output only depends on
+    /// the result, not on hash order.
+    ///
+    /// # Example
+    /// ```
+    /// use pgm_rust::graph::types::edge::Edge;
+    /// use pgm_rust::graph::types::edgetype::EdgeType;
+    /// use pgm_rust::graph::types::graph::Graph;
+    /// use pgm_rust::graph::types::node::Node;
+    /// use pgm_rust::graph::ops::graph::search::dfs;
+    /// use pgm_rust::graph::ops::graph::search::EdgeNeighborhood;
+    /// use std::collections::HashSet;
+    ///
+    /// let e1 = Edge::empty("e1", EdgeType::Directed, "a", "b");
+    /// let e2 = Edge::empty("e2", EdgeType::Directed, "b", "a");
+    /// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+    /// let dot = dfs(&g, &Node::empty("a"), &EdgeNeighborhood::Out, true).to_dot();
+    /// dot.contains("\"a\" -> \"b\";"); // true: tree edge
+    /// dot.contains("\"b\" -> \"a\" [style=dashed"); // true: back edge
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let mut lines = vec!["digraph dfs {".to_string()];
+        for (i, root) in self.roots.iter().enumerate() {
+            lines.push(format!("  subgraph cluster_{i} {{"));
+            let mut members: Vec<&String> = self
+                .components
+                .get(root)
+                .map(|c| c.iter().collect())
+                .unwrap_or_default();
+            members.sort_by_key(|v| self.first_visit_times.get(*v));
+            for v in members {
+                let label = format!(
+                    "{}\n{}/{}",
+                    v,
+                    self.first_visit_times.get(v).copied().unwrap_or(0),
+                    self.last_visit_times.get(v).copied().unwrap_or(0)
+                );
+                let border = if v == root { ", peripheries=2" } else { "" };
+                lines.push(format!(
+                    "    {} [label={}{}];",
+                    quoted(v),
+                    quoted(&label),
+                    border
+                ));
+            }
+            lines.push("  }".to_string());
+        }
+        let mut tree: Vec<(&String, &String)> = self.forest.iter().collect();
+        tree.sort_by_key(|(child, parent)| (self.first_visit_times.get(*child), *parent));
+        for (child, parent) in tree {
+            lines.push(format!("  {} -> {};", quoted(parent), quoted(child)));
+        }
+        let mut back: Vec<&CycleInfo> = self.cycles.values().flatten().collect();
+        back.sort_by(|a, b| (&a.before, &a.ancestor).cmp(&(&b.before, &b.ancestor)));
+        for info in back {
+            lines.push(format!(
+                "  {} -> {} [style=dashed, constraint=false];",
+                quoted(&info.before),
+                quoted(&info.ancestor)
+            ));
+        }
+        lines.push("}".to_string());
+        lines.join("\n") + "\n"
+    }
+
+    /// Serialize the search as a JSON object
+    /// # Description
+    /// The object has one member per field of the result, named after it.
+    /// Maps become objects with sorted keys, sets of identifiers become
+    /// sorted arrays and a missing ancestor last visit time is `null`, so
+    /// that equal results give equal strings.
+    ///
+    /// # Example
+    /// ```
+    /// use pgm_rust::graph::types::edge::Edge;
+    /// use pgm_rust::graph::types::edgetype::EdgeType;
+    /// use pgm_rust::graph::types::graph::Graph;
+    /// use pgm_rust::graph::types::node::Node;
+    /// use pgm_rust::graph::ops::graph::search::dfs;
+    /// use pgm_rust::graph::ops::graph::search::EdgeNeighborhood;
+    /// use std::collections::HashSet;
+    ///
+    /// let e1 = Edge::empty("e1", EdgeType::Directed, "a", "b");
+    /// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1]));
+    /// let json = dfs(&g, &Node::empty("a"), &EdgeNeighborhood::Out, false).to_json();
+    /// json.starts_with("{\"roots\":[\"a\"],\"forest\":{\"b\":\"a\"}"); // true
+    /// ```
+    pub fn to_json(&self) -> String {
+        let roots: Vec<String> = self.roots.iter().map(|r| quoted(r)).collect();
+        let cycle = |info: &CycleInfo| {
+            let ancestor_last = match info.ancestor_last_time_visit {
+                None => "null".to_string(),
+                Some(t) => t.to_string(),
+            };
+            format!(
+                "{{\"ancestor\":{},\"before\":{},\"ancestor_first_time_visit\":{},\
+                 \"ancestor_last_time_visit\":{},\"current_final_time_visit\":{}}}",
+                quoted(&info.ancestor),
+                quoted(&info.before),
+                info.ancestor_first_time_visit,
+                ancestor_last,
+                info.current_final_time_visit
+            )
+        };
+        let members = [
+            ("roots", format!("[{}]", roots.join(","))),
+            ("forest", json_object(&self.forest, |p| quoted(p))),
+            ("trees", json_object(&self.trees, json_id_set)),
+            ("components", json_object(&self.components, json_id_set)),
+            (
+                "first_visit_times",
+                json_object(&self.first_visit_times, |t| t.to_string()),
+            ),
+            (
+                "last_visit_times",
+                json_object(&self.last_visit_times, |t| t.to_string()),
+            ),
+            (
+                "cycles",
+                json_object(&self.cycles, |infos| {
+                    let items: Vec<String> = infos.iter().map(cycle).collect();
+                    format!("[{}]", items.join(","))
+                }),
+            ),
+        ];
+        let fields: Vec<String> = members
+            .into_iter()
+            .map(|(name, value)| format!("{}:{}", quoted(name), value))
+            .collect();
+        format!("{{{}}}", fields.join(","))
+    }
+}
+
+/// state of a depth first search keyed by interned vertex identifiers
+struct DfsState<'a> {
+    pool: IdPool,
+    /// first visit time of every vertex, 0 if not visited yet
+    first: Vec<usize>,
+    /// last visit time of every vertex, 0 if not finished yet
+    last: Vec<usize>,
+    parent: Vec<Option<u32>>,
+    /// root of the dfs tree of every visited vertex
+    root_of: Vec<u32>,
+    /// tree edges with the root of their tree
+    tree_edges: Vec<(u32, &'a String)>,
+    cycles: Vec<(u32, CycleInfo)>,
+    time: usize,
+}
+
+/// vertex of the dfs stack with the edges left to follow from it
+struct DfsFrame<'a, N, E> {
+    node: &'a N,
+    key: u32,
+    parent_edge: Option<&'a String>,
+    edges: std::vec::IntoIter<&'a E>,
+    back_edges: Vec<u32>,
+}
+
 /// dfs search adapted for cycle detection
 /// # Description
 /// the algorithm comes from dfs recursive forest from Erciyes 2018, Guide Graph ..., p.152 alg. 6.7
+/// The recursion is replaced by an explicit stack of [DfsFrame], so long
+/// paths do not overflow the call stack. Visit times are the same as the
+/// recursive version.
 /// # Args
-/// - f storing last visit times per node
-/// - d storing first visit times per node
-/// - cycles storing cycle info
-/// - marked storing if node is visited
-/// - pred storing the parent of nodes
-/// - g graph we are searching for
-/// - vertices vertex set of g converted to dict for easy access
-/// - u node id
-/// - T set of pred nodes
-/// - time global visit counter
-/// - check_cycle fill cycles if it is detected
+/// - u root of the dfs tree
+/// - root key of the root of the current dfs tree
+/// - state storing the forest, visit times and cycles
 /// - edge_generator generate edges of a vertex with respect to graph type
-fn dfs_forest<N, E, G, F>(
-    g: &G,
-    vertices: &mut HashMap<String, &N>,
-    u: &String,
-    pred: &mut HashMap<String, String>,
-    marked: &mut HashMap<String, bool>,
-    d: &mut HashMap<String, usize>,
-    f: &mut HashMap<String, usize>,
-    identifiers: &mut HashSet<String>,
-    cycles: &mut HashMap<String, Vec<CycleInfo>>,
-    mut time: usize,
+/// - check_cycle fill cycles if it is detected
+fn dfs_forest<'a, N, E, F>(
+    u: &'a N,
+    root: u32,
+    state: &mut DfsState<'a>,
     edge_generator: &F,
     check_cycle: bool,
-) -> Option<(String, String)>
+) where
+    N: NodeTrait + 'a,
+    E: EdgeTrait<N> + 'a,
+    F: Fn(&'a N) -> HashSet<&'a E>,
+{
+    let discover = |node: &'a N, parent_edge: Option<&'a String>, state: &mut DfsState<'a>| {
+        let key = state.pool.key(node.id());
+        state.time += 1;
+        state.first[key as usize] = state.time;
+        state.root_of[key as usize] = root;
+        let mut edges: Vec<&E> = edge_generator(node).into_iter().collect();
+        edges.sort_by(|a, b| a.id().cmp(b.id()));
+        DfsFrame {
+            node,
+            key,
+            parent_edge,
+            edges: edges.into_iter(),
+            back_edges: Vec::new(),
+        }
+    };
+    let mut stack: Vec<DfsFrame<'a, N, E>> = vec![discover(u, None, state)];
+    while let Some(frame) = stack.last_mut() {
+        let edge = match frame.edges.next() {
+            Some(edge) => edge,
+            None => {
+                let frame = stack.pop().unwrap();
+                finish(frame, state, check_cycle);
+                continue;
+            }
+        };
+        if frame.parent_edge == Some(edge.id()) {
+            continue;
+        }
+        let vnode = get_other(edge, frame.node);
+        let vk = state.pool.key(vnode.id());
+        let vi = vk as usize;
+        if state.first[vi] == 0 {
+            state.parent[vi] = Some(frame.key);
+            state.tree_edges.push((root, edge.id()));
+            let child = discover(vnode, Some(edge.id()), state);
+            stack.push(child);
+        } else if state.last[vi] == 0 {
+            // v is visited but not finished so it is an ancestor of u
+            // edge between them is a back edge
+            // see p. 151, and p. 159-160
+            frame.back_edges.push(vk);
+        }
+    }
+}
+
+/// record the last visit time of the vertex of `frame` and its cycles
+fn finish<N: NodeTrait, E>(frame: DfsFrame<'_, N, E>, state: &mut DfsState<'_>, check_cycle: bool) {
+    let ui = frame.key as usize;
+    state.time += 1;
+    state.last[ui] = state.time;
+    if check_cycle {
+        for vk in frame.back_edges {
+            let vi = vk as usize;
+            let info = CycleInfo {
+                ancestor: state.pool.resolve(vk).clone(),
+                before: frame.node.id().clone(),
+                ancestor_first_time_visit: state.first[vi],
+                ancestor_last_time_visit: Some(state.last[vi]).filter(|t| *t > 0),
+                current_final_time_visit: state.time,
+            };
+            state.cycles.push((frame.key, info));
+        }
+    }
+}
+
+/// Depth first search of a graph
+/// # Description
+/// Visits the graph depth first starting from `start`. When no more vertices
+/// can be reached, the search restarts from the unvisited vertex with the
+/// smallest identifier, so that the output covers every vertex of the graph.
+/// Neighbors are visited in edge identifier order, hence the output is
+/// reproducible. A back edge, that is an edge from a vertex to one of its
+/// ancestors in the dfs tree, closes a cycle.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - start: the first root of the search
+/// - edge_generator: edges that can be followed from a vertex, see
+///   [EdgeNeighborhood]
+/// - check_cycle: record the cycles found in [DepthFirstResult::cycles]
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::search::dfs;
+/// use pgm_rust::graph::ops::graph::search::EdgeNeighborhood;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "n1", "n2");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "n2", "n3");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "n3", "n1");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// let result = dfs(&g, &Node::empty("n1"), &EdgeNeighborhood::Both, true);
+/// result.forest["n3"] == "n2"; // true
+/// result.cycles["n3"][0].ancestor == "n1"; // true
+/// ```
+///
+/// # References
+/// Erciyes K. Guide to Graph Algorithms. 2018.
+pub fn dfs<'a, N, E, G>(
+    g: &'a G,
+    start: &N,
+    edge_generator: &EdgeNeighborhood<'a, N, E>,
+    check_cycle: bool,
+) -> DepthFirstResult
+where
+    N: NodeTrait + 'a,
+    E: EdgeTrait<N> + 'a,
+    G: GraphTrait<N, E>,
+{
+    let vertices = g.vertices();
+    let first: &N = match vertices.get(start) {
+        None => panic!("{start} not in {g}"),
+        Some(v) => v,
+    };
+    let generator = |n: &'a N| edge_generator.edges(g, n);
+    let sorted = g.vertices_sorted();
+    let n = sorted.len();
+    let mut state = DfsState {
+        pool: sorted.iter().map(|v| v.id()).collect(),
+        first: vec![0; n],
+        last: vec![0; n],
+        parent: vec![None; n],
+        root_of: vec![0; n],
+        tree_edges: Vec::new(),
+        cycles: Vec::new(),
+        time: 0,
+    };
+    let mut roots: Vec<u32> = Vec::new();
+    for root in std::iter::once(first).chain(sorted) {
+        let rk = state.pool.key(root.id());
+        if state.first[rk as usize] != 0 {
+            continue;
+        }
+        roots.push(rk);
+        dfs_forest(root, rk, &mut state, &generator, check_cycle);
+    }
+    // identifiers are only cloned here, once per output entry
+    let id = |k: u32| state.pool.resolve(k).clone();
+    let mut result = DepthFirstResult {
+        roots: roots.iter().map(|r| id(*r)).collect(),
+        trees: roots.iter().map(|r| (id(*r), HashSet::new())).collect(),
+        ..DepthFirstResult::default()
+    };
+    for (root, e) in &state.tree_edges {
+        result
+            .trees
+            .get_mut(state.pool.resolve(*root))
+            .unwrap()
+            .insert((*e).clone());
+    }
+    for k in 0..n {
+        if state.first[k] == 0 {
+            continue;
+        }
+        let vid = id(k as u32);
+        if let Some(p) = state.parent[k] {
+            result.forest.insert(vid.clone(), id(p));
+        }
+        result
+            .components
+            .entry(id(state.root_of[k]))
+            .or_default()
+            .insert(vid.clone());
+        result.first_visit_times.insert(vid.clone(), state.first[k]);
+        result.last_visit_times.insert(vid, state.last[k]);
+    }
+    for (before, info) in state.cycles {
+        result.cycles.entry(id(before)).or_default().push(info);
+    }
+    result
+}
+
+/// Sort the vertices of a directed graph topologically
+/// # Description
+/// Every vertex comes before the end vertices of its outgoing edges. We use
+/// Kahn's algorithm and among the vertices available at each step the one
+/// with the smallest identifier comes first, so the order is reproducible.
+/// Panics if the graph has an undirected edge.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - returns: vertices in topological order, `None` if the graph has a
+///   directed cycle
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph_obj::GraphObject;
+/// use pgm_rust::graph::ops::graph::search::topological_sort;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Directed, "c", "a");
+/// let e2 = Edge::empty("e2", EdgeType::Directed, "a", "b");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+/// let order: Vec<&String> = topological_sort(&g).unwrap().into_iter().map(|v| v.id()).collect();
+/// order == vec!["c", "a", "b"]; // true
+/// ```
+///
+/// # References
+/// Kahn A. Topological sorting of large networks. 1962.
+pub fn topological_sort<N, E, G>(g: &G) -> Option<Vec<&N>>
 where
     N: NodeTrait,
     E: EdgeTrait<N>,
     G: GraphTrait<N, E>,
-    F: Fn(&N) -> HashSet<&E>,
 {
-    marked.insert(u.to_string(), true);
-    time += 1;
-    d.insert(u.to_string(), time);
-    match vertices.get(u) {
-        None => panic!("node not in vertices"),
-        Some(unode_some) => {
-            let unode: &N = unode_some.clone();
-            let edges: HashSet<&E> = edge_generator(unode);
-            for edge in &edges {
-                let vnode = get_other(edge.clone(), unode);
-                let v: &String = vnode.id();
-                match marked.get(v) {
-                    None => panic!("node not in vertices"),
-                    Some(mark) => {
-                        if !mark {
-                            pred.insert(v.clone(), u.to_string());
-                            identifiers.insert(v.clone());
-                            dfs_forest(
-                                g,
-                                vertices,
-                                v,
-                                pred,
-                                marked,
-                                d,
-                                f,
-                                identifiers,
-                                cycles,
-                                time,
-                                edge_generator,
-                                check_cycle,
-                            );
-                        }
-                    }
-                }
-            }
-            //
-            time += 1;
-            f.insert(u.to_string(), time);
-            if check_cycle {
-                // v ancestor, u visiting node
-                // edge between them is a back edge
-                // see p. 151, and p. 159-160
-                // unode = V[u]
-                for edge in &edges {
-                    let vnode = get_other(edge.clone(), unode);
-                    let vid: &String = vnode.id();
-                    match pred.get(u) {
-                        None => panic!("node not in pred parent"),
-                        Some(unode_parent) => {
-                            if vid == unode_parent {
-                                let first_visit = d.get(vid);
-                                let last_visit = f.get(vid);
-                                match f.get(u) {
-                                    None => panic!("node not in last visit times"),
-                                    Some(u_last_visit) => match first_visit {
-                                        None => panic!("node not in last visit times"),
-                                        Some(first_v) => {
-                                            if first_v < u_last_visit {
-                                                let ancestor = vid.to_string();
-                                                let fv = first_v.clone();
-                                                let lv = last_visit.copied();
-                                                let ulv = u_last_visit.clone();
-                                                let info = CycleInfo {
-                                                    ancestor: ancestor,
-                                                    before: u.to_string(),
-                                                    ancestor_first_time_visit: fv,
-                                                    ancestor_last_time_visit: lv,
-                                                    current_final_time_visit: ulv,
-                                                };
-                                                //
-                                                match cycles.get(u) {
-                                                    None => panic!("node not in cycles"),
-                                                    Some(ucyc) => {
-                                                        let mut uc = ucyc.clone();
-                                                        uc.push(info);
-                                                        cycles.insert(u.to_string(), uc.to_vec());
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    },
-                                }
-                            }
-                        }
-                    }
-                }
-                None
-            } else {
-                None
+    let vertices = g.vertices();
+    let mut in_degree: BTreeMap<&String, usize> = vertices.iter().map(|v| (v.id(), 0)).collect();
+    let by_id: HashMap<&String, &N> = vertices.iter().map(|v| (v.id(), *v)).collect();
+    let mut successors: HashMap<&String, Vec<&String>> = HashMap::new();
+    for e in g.edges() {
+        if e.has_type() == &EdgeType::Undirected {
+            panic!("{e} is undirected, {g} must be directed");
+        }
+        *in_degree.get_mut(e.end().id()).unwrap() += 1;
+        successors
+            .entry(e.start().id())
+            .or_default()
+            .push(e.end().id());
+    }
+    let mut available: BTreeSet<&String> = in_degree
+        .iter()
+        .filter(|(_, d)| **d == 0)
+        .map(|(v, _)| *v)
+        .collect();
+    let mut order = Vec::with_capacity(in_degree.len());
+    while let Some(v) = available.pop_first() {
+        order.push(by_id[v]);
+        for w in successors.get(v).into_iter().flatten() {
+            let d = in_degree.get_mut(w).unwrap();
+            *d -= 1;
+            if *d == 0 {
+                available.insert(w);
             }
         }
     }
+    if order.len() == in_degree.len() {
+        Some(order)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::traits::graph_obj::GraphObject;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+
+    fn mk_uedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Undirected, n1_id, n2_id)
+    }
+    fn mk_dedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Directed, n1_id, n2_id)
+    }
+    fn mk_edges(es: Vec<Edge<Node>>) -> HashSet<Edge<Node>> {
+        let mut hs = HashSet::new();
+        for e in es {
+            hs.insert(e);
+        }
+        hs
+    }
+    fn mk_ids(ids: Vec<&str>) -> HashSet<String> {
+        ids.into_iter().map(|i| i.to_string()).collect()
+    }
+
+    /// triangle a b c with a pendant d, an edge e f and an isolated g
+    fn mk_g1() -> Graph<Node, Edge<Node>> {
+        let e1 = mk_uedge("a", "b", "e1");
+        let e2 = mk_uedge("b", "c", "e2");
+        let e3 = mk_uedge("c", "a", "e3");
+        let e4 = mk_uedge("c", "d", "e4");
+        let e5 = mk_uedge("e", "f", "e5");
+        let mut nodes = HashSet::new();
+        nodes.insert(Node::empty("g"));
+        Graph::from_edge_node_set(mk_edges(vec![e1, e2, e3, e4, e5]), nodes)
+    }
+
+    #[test]
+    fn test_dfs_forest() {
+        let g = mk_g1();
+        let r = dfs(&g, &Node::empty("a"), &EdgeNeighborhood::Both, false);
+        assert_eq!(r.roots, vec!["a", "e", "g"]);
+        let forest = HashMap::from([
+            ("b".to_string(), "a".to_string()),
+            ("c".to_string(), "b".to_string()),
+            ("d".to_string(), "c".to_string()),
+            ("f".to_string(), "e".to_string()),
+        ]);
+        assert_eq!(r.forest, forest);
+        assert_eq!(r.trees["a"], mk_ids(vec!["e1", "e2", "e4"]));
+        assert_eq!(r.trees["e"], mk_ids(vec!["e5"]));
+        assert_eq!(r.trees["g"], HashSet::new());
+        assert_eq!(r.components["a"], mk_ids(vec!["a", "b", "c", "d"]));
+        assert_eq!(r.components["e"], mk_ids(vec!["e", "f"]));
+        assert_eq!(r.components["g"], mk_ids(vec!["g"]));
+        assert!(r.cycles.is_empty());
+    }
+
+    #[test]
+    fn test_dfs_visit_times() {
+        let g = mk_g1();
+        let r = dfs(&g, &Node::empty("a"), &EdgeNeighborhood::Both, false);
+        let firsts: Vec<usize> = ["a", "b", "c", "d", "e", "f", "g"]
+            .iter()
+            .map(|n| r.first_visit_times[*n])
+            .collect();
+        let lasts: Vec<usize> = ["a", "b", "c", "d", "e", "f", "g"]
+            .iter()
+            .map(|n| r.last_visit_times[*n])
+            .collect();
+        assert_eq!(firsts, vec![1, 2, 3, 4, 9, 10, 13]);
+        assert_eq!(lasts, vec![8, 7, 6, 5, 12, 11, 14]);
+    }
+
+    #[test]
+    fn test_dfs_long_path() {
+        let n = 100_000;
+        let es: Vec<Edge<Node>> = (1..n)
+            .map(|i| mk_uedge(&format!("n{}", i - 1), &format!("n{i}"), &format!("e{i}")))
+            .collect();
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(mk_edges(es));
+        // incident edges are indexed so that the search is linear
+        let mut incident: HashMap<&String, HashSet<&Edge<Node>>> = HashMap::new();
+        for e in g.edges() {
+            incident.entry(e.start().id()).or_default().insert(e);
+            incident.entry(e.end().id()).or_default().insert(e);
+        }
+        let neighborhood = EdgeNeighborhood::Custom(Box::new(|n: &Node| incident[n.id()].clone()));
+        let r = dfs(&g, &Node::empty("n0"), &neighborhood, true);
+        assert_eq!(r.roots, vec!["n0"]);
+        assert_eq!(r.trees["n0"].len(), n - 1);
+        assert!(r.cycles.is_empty());
+        let last = format!("n{}", n - 1);
+        assert_eq!(r.forest[&last], format!("n{}", n - 2));
+        assert_eq!(r.first_visit_times[&last], n);
+        assert_eq!(r.last_visit_times[&last], n + 1);
+        assert_eq!(r.last_visit_times["n0"], 2 * n);
+    }
+
+    #[test]
+    fn test_dfs_cycles_undirected() {
+        let g = mk_g1();
+        let r = dfs(&g, &Node::empty("a"), &EdgeNeighborhood::Both, true);
+        assert_eq!(r.cycles.len(), 1);
+        let info = CycleInfo {
+            ancestor: "a".to_string(),
+            before: "c".to_string(),
+            ancestor_first_time_visit: 1,
+            ancestor_last_time_visit: None,
+            current_final_time_visit: 6,
+        };
+        assert_eq!(r.cycles["c"], vec![info]);
+    }
+
+    #[test]
+    fn test_dfs_cycles_directed() {
+        let e1 = mk_dedge("a", "b", "e1");
+        let e2 = mk_dedge("b", "c", "e2");
+        let e3 = mk_dedge("c", "a", "e3");
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(mk_edges(vec![e1, e2, e3]));
+        let gen = EdgeNeighborhood::Out;
+        let r = dfs(&g, &Node::empty("a"), &gen, true);
+        assert_eq!(r.cycles["c"][0].ancestor, "a");
+        // starting from b the search still closes the cycle at a
+        let r2 = dfs(&g, &Node::empty("b"), &gen, true);
+        assert_eq!(r2.roots, vec!["b"]);
+        assert_eq!(r2.cycles["a"][0].ancestor, "b");
+    }
+
+    #[test]
+    fn test_dfs_dag() {
+        let e1 = mk_dedge("a", "b", "e1");
+        let e2 = mk_dedge("a", "c", "e2");
+        let e3 = mk_dedge("b", "c", "e3");
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(mk_edges(vec![e1, e2, e3]));
+        let r = dfs(&g, &Node::empty("b"), &EdgeNeighborhood::Out, true);
+        assert!(r.cycles.is_empty());
+        assert_eq!(r.roots, vec!["b", "a"]);
+        assert_eq!(r.trees["a"], HashSet::new());
+    }
+
+    #[test]
+    fn test_dfs_to_dot() {
+        let e1 = mk_dedge("a", "b", "e1");
+        let e2 = mk_dedge("b", "a", "e2");
+        let g: Graph<Node, Edge<Node>> =
+            Graph::from_edge_node_set(mk_edges(vec![e1, e2]), HashSet::from([Node::empty("c")]));
+        let r = dfs(&g, &Node::empty("a"), &EdgeNeighborhood::Out, true);
+        let expected = "digraph dfs {\n\
+            \x20 subgraph cluster_0 {\n\
+            \x20   \"a\" [label=\"a\\n1/4\", peripheries=2];\n\
+            \x20   \"b\" [label=\"b\\n2/3\"];\n\
+            \x20 }\n\
+            \x20 subgraph cluster_1 {\n\
+            \x20   \"c\" [label=\"c\\n5/6\", peripheries=2];\n\
+            \x20 }\n\
+            \x20 \"a\" -> \"b\";\n\
+            \x20 \"b\" -> \"a\" [style=dashed, constraint=false];\n\
+            }\n";
+        assert_eq!(r.to_dot(), expected);
+    }
+
+    #[test]
+    fn test_dfs_to_json() {
+        let e1 = mk_uedge("a", "b", "e1");
+        let e2 = mk_uedge("b", "c\"", "e2");
+        let e3 = mk_uedge("c\"", "a", "e3");
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(mk_edges(vec![e1, e2, e3]));
+        let r = dfs(&g, &Node::empty("a"), &EdgeNeighborhood::Both, true);
+        let expected = concat!(
+            r#"{"roots":["a"],"forest":{"b":"a","c\"":"b"},"trees":{"a":["e1","e2"]},"#,
+            r#""components":{"a":["a","b","c\""]},"first_visit_times":{"a":1,"b":2,"c\"":3},"#,
+            r#""last_visit_times":{"a":6,"b":5,"c\"":4},"cycles":{"c\"":[{"ancestor":"a","#,
+            r#""before":"c\"","ancestor_first_time_visit":1,"ancestor_last_time_visit":null,"#,
+            r#""current_final_time_visit":4}]}}"#
+        );
+        assert_eq!(r.to_json(), expected);
+        let empty = concat!(
+            r#"{"roots":[],"forest":{},"trees":{},"components":{},"#,
+            r#""first_visit_times":{},"last_visit_times":{},"cycles":{}}"#
+        );
+        assert_eq!(DepthFirstResult::default().to_json(), empty);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_dfs_missing_start() {
+        let g = mk_g1();
+        dfs(&g, &Node::empty("z"), &EdgeNeighborhood::Both, false);
+    }
+
+    /// directed path a -> b -> c with an undirected edge c - d
+    fn mk_mixed() -> Graph<Node, Edge<Node>> {
+        let e1 = mk_dedge("a", "b", "e1");
+        let e2 = mk_dedge("b", "c", "e2");
+        let e3 = mk_uedge("d", "c", "e3");
+        Graph::from_edgeset(mk_edges(vec![e1, e2, e3]))
+    }
+
+    fn edge_ids(es: HashSet<&Edge<Node>>) -> HashSet<String> {
+        es.into_iter().map(|e| e.id().clone()).collect()
+    }
+
+    #[test]
+    fn test_edge_neighborhood() {
+        let g = mk_mixed();
+        let c = Node::empty("c");
+        let out = EdgeNeighborhood::Out.edges(&g, &c);
+        assert_eq!(edge_ids(out), mk_ids(vec!["e3"]));
+        let inc = EdgeNeighborhood::In.edges(&g, &c);
+        assert_eq!(edge_ids(inc), mk_ids(vec!["e2", "e3"]));
+        let both = EdgeNeighborhood::Both.edges(&g, &c);
+        assert_eq!(edge_ids(both), mk_ids(vec!["e2", "e3"]));
+    }
+
+    #[test]
+    fn test_edge_neighborhood_custom() {
+        let g = mk_mixed();
+        let only_directed = EdgeNeighborhood::Custom(Box::new(|n: &Node| {
+            EdgeNeighborhood::Both
+                .edges(&g, n)
+                .into_iter()
+                .filter(|e| e.has_type() == &EdgeType::Directed)
+                .collect()
+        }));
+        let r = dfs(&g, &Node::empty("a"), &only_directed, false);
+        assert_eq!(r.roots, vec!["a", "d"]);
+    }
+
+    #[test]
+    fn test_dfs_in() {
+        let g = mk_mixed();
+        let r = dfs(&g, &Node::empty("c"), &EdgeNeighborhood::In, false);
+        assert_eq!(r.roots, vec!["c"]);
+        assert_eq!(r.components["c"], mk_ids(vec!["a", "b", "c", "d"]));
+    }
+
+    #[test]
+    fn test_topological_sort() {
+        let e1 = mk_dedge("a", "c", "e1");
+        let e2 = mk_dedge("b", "c", "e2");
+        let e3 = mk_dedge("c", "d", "e3");
+        let nodes = HashSet::from([Node::empty("0")]);
+        let g: Graph<Node, Edge<Node>> =
+            Graph::from_edge_node_set(mk_edges(vec![e1, e2, e3]), nodes);
+        let order: Vec<&String> = topological_sort(&g)
+            .unwrap()
+            .into_iter()
+            .map(|v| v.id())
+            .collect();
+        assert_eq!(order, vec!["0", "a", "b", "c", "d"]);
+        let e4 = mk_dedge("d", "a", "e4");
+        let cyclic: Graph<Node, Edge<Node>> = Graph::from_edgeset(mk_edges(vec![
+            mk_dedge("a", "c", "e1"),
+            mk_dedge("c", "d", "e3"),
+            e4,
+        ]));
+        assert!(topological_sort(&cyclic).is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_topological_sort_undirected() {
+        topological_sort(&mk_g1());
+    }
 }
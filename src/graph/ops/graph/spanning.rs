@@ -0,0 +1,218 @@
+//! spanning trees and forests
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::idgen::IdGenerator;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::idgen::UuidGenerator;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+fn find_root(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+/// Kruskal's algorithm keeping edges in the order given by `better`
+fn kruskal<N, E, G, F, C, I>(g: &G, weight: F, better: C, idgen: &mut I) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    F: Fn(&E) -> f64,
+    C: Fn(f64, f64) -> std::cmp::Ordering,
+    I: IdGenerator,
+{
+    let vertices = g.vertices_sorted();
+    let index: HashMap<&String, usize> = vertices
+        .iter()
+        .enumerate()
+        .map(|(i, v)| (v.id(), i))
+        .collect();
+    let mut weighted: Vec<(&E, f64)> = g
+        .edges_sorted()
+        .into_iter()
+        .map(|e| (e, weight(e)))
+        .collect();
+    // stable sort so that ties are broken by edge order
+    weighted.sort_by(|(_, w1), (_, w2)| better(*w1, *w2));
+    let mut parent: Vec<usize> = (0..vertices.len()).collect();
+    let mut kept: HashSet<&E> = HashSet::new();
+    for (e, _) in weighted {
+        let ri = find_root(&mut parent, index[e.start().id()]);
+        let rj = find_root(&mut parent, index[e.end().id()]);
+        if ri != rj {
+            parent[ri.max(rj)] = ri.min(rj);
+            kept.insert(e);
+        }
+    }
+    let nodes: HashSet<&N> = vertices.into_iter().collect();
+    let gid = idgen.generate(&nodes, &kept);
+    G::create_from_ref(gid, HashMap::new(), nodes, kept)
+}
+
+/// Compute a minimum spanning forest of a graph
+/// # Description
+/// A spanning tree of a connected graph is a tree containing all of its
+/// vertices, see Diestel 2017, p. 14. For a disconnected graph we obtain a
+/// spanning tree per component. We use Kruskal's algorithm, edge
+/// orientation is ignored, self loops are never kept and ties between equal
+/// weights are broken by edge order. The forest keeps every vertex of `g`,
+/// its identifier is a random UUID.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - weight: weight of an edge
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+/// use pgm_rust::graph::traits::graph_obj::GraphObject;
+/// use pgm_rust::graph::ops::graph::spanning::minimum_spanning_tree;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "a", "c");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// let t = minimum_spanning_tree(&g, |e: &Edge<Node>| if e.id() == "e3" { 5.0 } else { 1.0 });
+/// t.size() == 2; // true: e1 and e2
+/// ```
+///
+/// # References
+/// Kruskal J. On the shortest spanning subtree of a graph and the traveling
+/// salesman problem. 1956.
+pub fn minimum_spanning_tree<N, E, G, F>(g: &G, weight: F) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    F: Fn(&E) -> f64,
+{
+    minimum_spanning_tree_with(g, weight, &mut UuidGenerator)
+}
+
+/// Compute a minimum spanning forest of a graph, the identifier of the
+/// forest is produced by `idgen`, see [minimum_spanning_tree]
+pub fn minimum_spanning_tree_with<N, E, G, F, I>(g: &G, weight: F, idgen: &mut I) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    F: Fn(&E) -> f64,
+    I: IdGenerator,
+{
+    kruskal(g, weight, |w1, w2| w1.total_cmp(&w2), idgen)
+}
+
+/// Compute a maximum spanning forest of a graph, see
+/// [minimum_spanning_tree]
+pub fn maximum_spanning_tree<N, E, G, F>(g: &G, weight: F) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    F: Fn(&E) -> f64,
+{
+    maximum_spanning_tree_with(g, weight, &mut UuidGenerator)
+}
+
+/// Compute a maximum spanning forest of a graph, the identifier of the
+/// forest is produced by `idgen`, see [minimum_spanning_tree]
+pub fn maximum_spanning_tree_with<N, E, G, F, I>(g: &G, weight: F, idgen: &mut I) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    F: Fn(&E) -> f64,
+    I: IdGenerator,
+{
+    kruskal(g, weight, |w1, w2| w2.total_cmp(&w1), idgen)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::traits::graph_obj::GraphObject;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::edgetype::EdgeType;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::idgen::SequentialIdGenerator;
+    use crate::graph::types::node::Node;
+
+    fn mk_uedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Undirected, n1_id, n2_id)
+    }
+    fn mk_edges(es: Vec<Edge<Node>>) -> HashSet<Edge<Node>> {
+        es.into_iter().collect()
+    }
+    fn edge_ids(g: &Graph<Node, Edge<Node>>) -> Vec<String> {
+        g.edges_sorted().iter().map(|e| e.id().clone()).collect()
+    }
+
+    /// square a-b-c-d with diagonal a-c, plus an isolated vertex
+    fn mk_g1() -> Graph<Node, Edge<Node>> {
+        let es = mk_edges(vec![
+            mk_uedge("a", "b", "e1"),
+            mk_uedge("b", "c", "e2"),
+            mk_uedge("c", "d", "e3"),
+            mk_uedge("d", "a", "e4"),
+            mk_uedge("a", "c", "e5"),
+            mk_uedge("a", "a", "e6"),
+        ]);
+        let mut ns: HashSet<Node> = ["a", "b", "c", "d", "z"]
+            .iter()
+            .map(|n| Node::empty(n))
+            .collect();
+        for e in &es {
+            ns.insert(e.start().clone());
+        }
+        Graph::new("g1".to_string(), HashMap::new(), ns, es)
+    }
+
+    fn weights(e: &Edge<Node>) -> f64 {
+        match e.id().as_str() {
+            "e1" => 4.0,
+            "e2" => 2.0,
+            "e3" => 5.0,
+            "e4" => 1.0,
+            "e5" => 3.0,
+            _ => 0.0,
+        }
+    }
+
+    #[test]
+    fn test_minimum_spanning_tree() {
+        let t = minimum_spanning_tree(&mk_g1(), weights);
+        assert_eq!(edge_ids(&t), vec!["e2", "e4", "e5"]);
+        assert_eq!(t.order(), 5);
+    }
+
+    #[test]
+    fn test_maximum_spanning_tree() {
+        let t = maximum_spanning_tree(&mk_g1(), weights);
+        assert_eq!(edge_ids(&t), vec!["e1", "e3", "e5"]);
+    }
+
+    #[test]
+    fn test_spanning_tree_ties() {
+        let t = minimum_spanning_tree(&mk_g1(), |_: &Edge<Node>| 1.0);
+        assert_eq!(edge_ids(&t), vec!["e1", "e2", "e3"]);
+    }
+
+    #[test]
+    fn test_spanning_tree_with() {
+        let mut idgen = SequentialIdGenerator::new("t");
+        let t1 = minimum_spanning_tree_with(&mk_g1(), weights, &mut idgen);
+        let t2 = maximum_spanning_tree_with(&mk_g1(), weights, &mut idgen);
+        assert_eq!(t1.id(), "t0");
+        assert_eq!(t2.id(), "t1");
+        assert_eq!(edge_ids(&t2), vec!["e1", "e3", "e5"]);
+    }
+}
@@ -0,0 +1,451 @@
+//! spectral estimates of connectivity
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::maps::NodeMap;
+use crate::rng::SplitMix64;
+use std::collections::HashMap;
+
+/// largest number of inverse iterations
+pub const MAX_ITERATIONS: usize = 1_000;
+
+/// iterations stop once the residual of the eigenpair, relative to twice
+/// the largest degree which bounds the laplacian spectrum, falls below this
+/// value
+pub const EIGEN_TOLERANCE: f64 = 1e-10;
+
+/// relative residual at which the linear solves of inverse iteration stop
+const SOLVER_TOLERANCE: f64 = 1e-13;
+
+/// sparse laplacian of a graph with edge orientation ignored
+struct Laplacian {
+    /// vertex identifiers sorted
+    order: Vec<String>,
+    /// number of edges at each vertex, loops excluded
+    degrees: Vec<f64>,
+    /// neighbors of each vertex, repeated for parallel edges
+    neighbors: Vec<Vec<usize>>,
+}
+
+impl Laplacian {
+    fn new<N, E, G>(g: &G) -> Laplacian
+    where
+        N: NodeTrait,
+        E: EdgeTrait<N>,
+        G: GraphTrait<N, E>,
+    {
+        let order: Vec<String> = g
+            .vertices_sorted()
+            .into_iter()
+            .map(|v| v.id().clone())
+            .collect();
+        let index: HashMap<&String, usize> =
+            order.iter().enumerate().map(|(i, v)| (v, i)).collect();
+        let mut degrees = vec![0.0; order.len()];
+        let mut neighbors = vec![Vec::new(); order.len()];
+        for e in g.edges() {
+            let (i, j) = (index[e.start().id()], index[e.end().id()]);
+            if i != j {
+                degrees[i] += 1.0;
+                degrees[j] += 1.0;
+                neighbors[i].push(j);
+                neighbors[j].push(i);
+            }
+        }
+        Laplacian {
+            order,
+            degrees,
+            neighbors,
+        }
+    }
+
+    /// `L x`
+    fn product(&self, x: &[f64]) -> Vec<f64> {
+        x.iter()
+            .enumerate()
+            .map(|(i, xi)| {
+                let adjacent: f64 = self.neighbors[i].iter().map(|j| x[*j]).sum();
+                self.degrees[i] * xi - adjacent
+            })
+            .collect()
+    }
+
+    /// `(L + J / n) x` with `J` the all ones matrix. On a connected graph
+    /// this matrix is positive definite and acts as `L` on vectors
+    /// orthogonal to the constant vector.
+    fn grounded_product(&self, x: &[f64]) -> Vec<f64> {
+        let mean = x.iter().sum::<f64>() / x.len() as f64;
+        let mut y = self.product(x);
+        y.iter_mut().for_each(|yi| *yi += mean);
+        y
+    }
+
+    /// solve `(L + J / n) y = b` by conjugate gradient, None if the
+    /// residual does not fall below [SOLVER_TOLERANCE]
+    fn solve(&self, b: &[f64]) -> Option<Vec<f64>> {
+        let n = b.len();
+        let mut y = vec![0.0; n];
+        let mut r = b.to_vec();
+        let mut p = r.clone();
+        let mut rr = dot(&r, &r);
+        let target = SOLVER_TOLERANCE * SOLVER_TOLERANCE * rr;
+        // exact in n steps without rounding, leave room for rounding
+        for _ in 0..10 * n {
+            if rr <= target {
+                return Some(y);
+            }
+            let ap = self.grounded_product(&p);
+            let alpha = rr / dot(&p, &ap);
+            for i in 0..n {
+                y[i] += alpha * p[i];
+                r[i] -= alpha * ap[i];
+            }
+            let rr_next = dot(&r, &r);
+            let beta = rr_next / rr;
+            for i in 0..n {
+                p[i] = r[i] + beta * p[i];
+            }
+            rr = rr_next;
+        }
+        if rr <= target {
+            Some(y)
+        } else {
+            None
+        }
+    }
+
+    /// unit vector orthogonal to the constant vector and constant on the
+    /// components of the graph, None if the graph is connected
+    fn component_vector(&self) -> Option<Vec<f64>> {
+        let n = self.order.len();
+        let mut seen = vec![false; n];
+        seen[0] = true;
+        let mut stack = vec![0];
+        while let Some(i) = stack.pop() {
+            for j in &self.neighbors[i] {
+                if !seen[*j] {
+                    seen[*j] = true;
+                    stack.push(*j);
+                }
+            }
+        }
+        if seen.iter().all(|s| *s) {
+            return None;
+        }
+        let mut x: Vec<f64> = seen.iter().map(|s| if *s { 1.0 } else { 0.0 }).collect();
+        deflate_and_normalize(&mut x);
+        Some(x)
+    }
+
+    /// second smallest eigenvalue and an associated unit eigenvector, None
+    /// if inverse iteration does not converge
+    fn fiedler_pair(&self) -> Option<(f64, Vec<f64>)> {
+        let n = self.order.len();
+        if n < 2 {
+            return Some((0.0, vec![0.0; n]));
+        }
+        if let Some(x) = self.component_vector() {
+            return Some((0.0, x));
+        }
+        // inverse iteration restricted to vectors orthogonal to the
+        // constant vector, the error shrinks by the ratio of the second and
+        // third smallest eigenvalues at every step however close to zero
+        // they are
+        let scale = 2.0 * self.degrees.iter().cloned().fold(0.0, f64::max);
+        let mut rng = SplitMix64::new(n as u64);
+        let mut x: Vec<f64> = (0..n).map(|_| rng.next_f64() - 0.5).collect();
+        deflate_and_normalize(&mut x);
+        for _ in 0..MAX_ITERATIONS {
+            let lx = self.product(&x);
+            let mu = dot(&x, &lx);
+            let residual = lx
+                .iter()
+                .zip(x.iter())
+                .map(|(yi, xi)| (yi - mu * xi).powi(2))
+                .sum::<f64>()
+                .sqrt();
+            if residual < EIGEN_TOLERANCE * scale {
+                return Some((mu.max(0.0), x));
+            }
+            x = self.solve(&x)?;
+            deflate_and_normalize(&mut x);
+        }
+        None
+    }
+}
+
+fn dot(x: &[f64], y: &[f64]) -> f64 {
+    x.iter().zip(y.iter()).map(|(a, b)| a * b).sum()
+}
+
+/// remove the component along the constant vector and scale to unit norm
+fn deflate_and_normalize(x: &mut [f64]) {
+    let mean = x.iter().sum::<f64>() / x.len() as f64;
+    for xi in x.iter_mut() {
+        *xi -= mean;
+    }
+    let norm = dot(x, x).sqrt();
+    if norm > 0.0 {
+        for xi in x.iter_mut() {
+            *xi /= norm;
+        }
+    }
+}
+
+/// Compute the algebraic connectivity of a graph
+/// # Description
+/// The algebraic connectivity is the second smallest eigenvalue of the
+/// laplacian, computed here with edge orientation ignored, loops excluded
+/// and parallel edges counted with their multiplicity. It is zero exactly
+/// when the graph is disconnected and grows with how hard the graph is to
+/// cut. The eigenvalue is computed by inverse iteration restricted to
+/// vectors orthogonal to the constant vector, each step solving a linear
+/// system by conjugate gradient, until the residual of the eigenpair is
+/// within [EIGEN_TOLERANCE]. Graphs with fewer than two vertices have a
+/// zero connectivity. Returns None if the eigenpair has not converged
+/// after [MAX_ITERATIONS] steps.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::spectral::algebraic_connectivity;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "c", "a");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// (algebraic_connectivity(&g).unwrap() - 3.0).abs() < 1e-6; // true
+/// ```
+///
+/// # References
+/// Fiedler M. Algebraic connectivity of graphs. 1973.
+pub fn algebraic_connectivity<N, E, G>(g: &G) -> Option<f64>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    Laplacian::new(g).fiedler_pair().map(|(lambda, _)| lambda)
+}
+
+/// Compute a Fiedler vector of a graph
+/// # Description
+/// A Fiedler vector is a unit eigenvector of the second smallest laplacian
+/// eigenvalue, see [algebraic_connectivity]. Its entries place tightly
+/// connected vertices close to each other. The sign of an eigenvector is
+/// arbitrary, it is fixed here so that the vertex with the smallest
+/// identifier and a non zero entry has a negative entry. On a disconnected
+/// graph the vector is constant on components. Returns None if the
+/// eigenpair has not converged, see [algebraic_connectivity].
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - returns: the entry of every vertex identifier
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::spectral::fiedler_vector;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+/// let f = fiedler_vector(&g).unwrap();
+/// f["a"] < 0.0 && f["b"].abs() < 1e-6 && f["c"] > 0.0; // true
+/// ```
+pub fn fiedler_vector<N, E, G>(g: &G) -> Option<NodeMap<f64>>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let lap = Laplacian::new(g);
+    let (_, mut x) = lap.fiedler_pair()?;
+    if let Some(first) = x.iter().find(|xi| xi.abs() > EIGEN_TOLERANCE.sqrt()) {
+        if *first > 0.0 {
+            x.iter_mut().for_each(|xi| *xi = -*xi);
+        }
+    }
+    Some(lap.order.into_iter().zip(x).collect())
+}
+
+/// Split a graph in two balanced halves
+/// # Description
+/// Vertices are sorted by their entry in the [fiedler_vector], ties broken
+/// by identifier, and cut at the median. The halves differ by at most one
+/// vertex, the first one being the larger, and the cut tends to cross few
+/// edges. Recursive bisection gives partitions in any power of two.
+/// Returns None if the Fiedler vector has not converged.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - returns: the vertex identifiers of each half, sorted
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::spectral::spectral_bisection;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "c", "d");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// let (left, right) = spectral_bisection(&g).unwrap();
+/// left == vec!["a", "b"]; // true
+/// right == vec!["c", "d"]; // true
+/// ```
+///
+/// # References
+/// Pothen A, Simon H, Liou K. Partitioning sparse matrices with
+/// eigenvectors of graphs. 1990.
+pub fn spectral_bisection<N, E, G>(g: &G) -> Option<(Vec<String>, Vec<String>)>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let fiedler = fiedler_vector(g)?;
+    let mut ranked: Vec<(String, f64)> = fiedler.into_iter().collect();
+    ranked.sort_by(|(a, fa), (b, fb)| fa.total_cmp(fb).then_with(|| a.cmp(b)));
+    let half = ranked.len().div_ceil(2);
+    let mut right: Vec<String> = ranked.split_off(half).into_iter().map(|(v, _)| v).collect();
+    let mut left: Vec<String> = ranked.into_iter().map(|(v, _)| v).collect();
+    left.sort();
+    right.sort();
+    Some((left, right))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::edgetype::EdgeType;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+    use std::collections::HashSet;
+
+    fn mk_uedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Undirected, n1_id, n2_id)
+    }
+
+    fn mk_path(n: usize) -> Graph<Node, Edge<Node>> {
+        let es: HashSet<Edge<Node>> = (1..n)
+            .map(|i| {
+                mk_uedge(
+                    &format!("v{:02}", i - 1),
+                    &format!("v{i:02}"),
+                    &format!("e{i}"),
+                )
+            })
+            .collect();
+        Graph::from_edgeset(es)
+    }
+
+    /// two cliques of four vertices joined by the edge a3 -- b0
+    fn mk_barbell() -> Graph<Node, Edge<Node>> {
+        let mut es = HashSet::new();
+        for side in ["a", "b"] {
+            for i in 0..4 {
+                for j in i + 1..4 {
+                    es.insert(mk_uedge(
+                        &format!("{side}{i}"),
+                        &format!("{side}{j}"),
+                        &format!("{side}{i}{j}"),
+                    ));
+                }
+            }
+        }
+        es.insert(mk_uedge("a3", "b0", "bridge"));
+        Graph::from_edgeset(es)
+    }
+
+    #[test]
+    fn test_algebraic_connectivity_path() {
+        for n in [2, 5, 10] {
+            let expected = 2.0 * (1.0 - (std::f64::consts::PI / n as f64).cos());
+            assert!((algebraic_connectivity(&mk_path(n)).unwrap() - expected).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_algebraic_connectivity_long_path() {
+        // the gap between the two smallest eigenvalues shrinks as 1 / n^2
+        for n in [1000, 2000] {
+            let expected = 2.0 * (1.0 - (std::f64::consts::PI / n as f64).cos());
+            let lambda = algebraic_connectivity(&mk_path(n)).unwrap();
+            assert!(((lambda - expected) / expected).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_fiedler_vector_long_path() {
+        // entries of the Fiedler vector of a path are monotone
+        let n = 300;
+        let f = fiedler_vector(&mk_path(n)).unwrap();
+        let entries: Vec<f64> = (0..n).map(|i| f[format!("v{i:02}").as_str()]).collect();
+        assert!(entries.windows(2).all(|w| w[0] < w[1]));
+        let (left, _) = spectral_bisection(&mk_path(n)).unwrap();
+        assert!(left
+            .iter()
+            .all(|v| v[1..].parse::<usize>().unwrap() < n / 2));
+    }
+
+    #[test]
+    fn test_fiedler_vector_disconnected() {
+        let es = HashSet::from([mk_uedge("a", "b", "e1"), mk_uedge("c", "d", "e2")]);
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(es);
+        let f = fiedler_vector(&g).unwrap();
+        assert!(f["a"] < 0.0 && (f["a"] - f["b"]).abs() < 1e-12);
+        assert!(f["c"] > 0.0 && (f["c"] - f["d"]).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_algebraic_connectivity_disconnected() {
+        let es = HashSet::from([mk_uedge("a", "b", "e1"), mk_uedge("c", "d", "e2")]);
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(es);
+        assert!(algebraic_connectivity(&g).unwrap().abs() < 1e-6);
+        let single: Graph<Node, Edge<Node>> =
+            Graph::from_edge_node_set(HashSet::new(), HashSet::from([Node::empty("a")]));
+        assert_eq!(algebraic_connectivity(&single), Some(0.0));
+    }
+
+    #[test]
+    fn test_algebraic_connectivity_ignores_orientation() {
+        let directed: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([
+            Edge::empty("e1", EdgeType::Directed, "a", "b"),
+            Edge::empty("e2", EdgeType::Directed, "c", "b"),
+            Edge::empty("e3", EdgeType::Directed, "c", "c"),
+        ]));
+        let expected = algebraic_connectivity(&mk_path(3)).unwrap();
+        assert!((algebraic_connectivity(&directed).unwrap() - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_spectral_bisection_barbell() {
+        let (left, right) = spectral_bisection(&mk_barbell()).unwrap();
+        assert_eq!(left, vec!["a0", "a1", "a2", "a3"]);
+        assert_eq!(right, vec!["b0", "b1", "b2", "b3"]);
+    }
+
+    #[test]
+    fn test_spectral_bisection_odd() {
+        let (left, right) = spectral_bisection(&mk_path(5)).unwrap();
+        assert_eq!(left, vec!["v00", "v01", "v02"]);
+        assert_eq!(right, vec!["v03", "v04"]);
+    }
+}
@@ -0,0 +1,224 @@
+//! descriptive statistics of graphs
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::edgetype::EdgeType;
+use std::collections::HashMap;
+use std::fmt;
+
+/// overview of the structure of a graph, see [summarize]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphSummary {
+    /// identifier of the summarized graph
+    pub graph_id: String,
+    /// number of vertices
+    pub order: usize,
+    /// number of edges
+    pub size: usize,
+    /// density, see [Graph::density]
+    pub density: f64,
+    /// smallest vertex degree
+    pub min_degree: usize,
+    /// largest vertex degree
+    pub max_degree: usize,
+    /// average vertex degree
+    pub mean_degree: f64,
+    /// number of connected components, edge orientation ignored
+    pub nb_components: usize,
+    /// the graph has edges and all of them are directed
+    pub is_directed: bool,
+    /// the graph has both directed and undirected edges
+    pub is_mixed: bool,
+    /// number of edges whose end vertices are the same
+    pub nb_self_loops: usize,
+    /// number of vertices without incident edges
+    pub nb_isolated: usize,
+}
+
+impl fmt::Display for GraphSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let kind = if self.is_mixed {
+            "mixed"
+        } else if self.is_directed {
+            "directed"
+        } else {
+            "undirected"
+        };
+        writeln!(f, "Graph summary for {}", self.graph_id)?;
+        writeln!(f, "  kind:            {kind}")?;
+        writeln!(f, "  order:           {}", self.order)?;
+        writeln!(f, "  size:            {}", self.size)?;
+        writeln!(f, "  density:         {:.4}", self.density)?;
+        writeln!(
+            f,
+            "  degree:          min {}, max {}, mean {:.4}",
+            self.min_degree, self.max_degree, self.mean_degree
+        )?;
+        writeln!(f, "  components:      {}", self.nb_components)?;
+        writeln!(f, "  self loops:      {}", self.nb_self_loops)?;
+        write!(f, "  isolated nodes:  {}", self.nb_isolated)
+    }
+}
+
+/// root of `i` in a union find forest, with path halving
+fn find_root(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+/// Summarize the structure of a graph
+/// # Description
+/// Computes in one call the statistics gathered in [GraphSummary]. The
+/// degree of a vertex is the number of edges incident to it, regardless of
+/// their orientation, loops counting twice, see Diestel 2017, p. 5.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::summary::summarize;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "n1", "n2");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "n2", "n3");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+/// let summary = summarize(&g);
+/// summary.max_degree == 2; // true
+/// summary.nb_components == 1; // true
+/// println!("{summary}");
+/// ```
+pub fn summarize<N, E, G>(g: &G) -> GraphSummary
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let vertices = g.vertices_sorted();
+    let edges = g.edges();
+    let index: HashMap<&String, usize> = vertices
+        .iter()
+        .enumerate()
+        .map(|(i, v)| (v.id(), i))
+        .collect();
+    let mut degrees: Vec<usize> = vec![0; vertices.len()];
+    let mut parent: Vec<usize> = (0..vertices.len()).collect();
+    let mut nb_components = vertices.len();
+    let mut nb_directed = 0;
+    let mut nb_self_loops = 0;
+    for e in &edges {
+        let (i, j) = (index[e.start().id()], index[e.end().id()]);
+        degrees[i] += 1;
+        degrees[j] += 1;
+        let (ri, rj) = (find_root(&mut parent, i), find_root(&mut parent, j));
+        if ri != rj {
+            parent[ri] = rj;
+            nb_components -= 1;
+        }
+        if e.has_type() == &EdgeType::Directed {
+            nb_directed += 1;
+        }
+        if e.start().id() == e.end().id() {
+            nb_self_loops += 1;
+        }
+    }
+    let order = vertices.len();
+    let mean_degree = if order == 0 {
+        0.0
+    } else {
+        degrees.iter().sum::<usize>() as f64 / order as f64
+    };
+    GraphSummary {
+        graph_id: g.id().clone(),
+        order,
+        size: edges.len(),
+        density: g.density(),
+        min_degree: degrees.iter().copied().min().unwrap_or(0),
+        max_degree: degrees.iter().copied().max().unwrap_or(0),
+        mean_degree,
+        nb_components,
+        is_directed: nb_directed > 0 && nb_directed == edges.len(),
+        is_mixed: nb_directed > 0 && nb_directed < edges.len(),
+        nb_self_loops,
+        nb_isolated: degrees.iter().filter(|d| **d == 0).count(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+    use std::collections::HashSet;
+
+    fn mk_uedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Undirected, n1_id, n2_id)
+    }
+    fn mk_dedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Directed, n1_id, n2_id)
+    }
+    fn mk_edges(es: Vec<Edge<Node>>) -> HashSet<Edge<Node>> {
+        let mut hs = HashSet::new();
+        for e in es {
+            hs.insert(e);
+        }
+        hs
+    }
+
+    /// path n1 - n2 - n3, a loop at n3, an edge n4 -> n5 and an isolated n6
+    fn mk_g1() -> Graph<Node, Edge<Node>> {
+        let es = vec![
+            mk_uedge("n1", "n2", "e1"),
+            mk_uedge("n2", "n3", "e2"),
+            mk_uedge("n3", "n3", "e3"),
+            mk_dedge("n4", "n5", "e4"),
+        ];
+        let mut nodes = HashSet::new();
+        nodes.insert(Node::empty("n6"));
+        Graph::new("g1".to_string(), HashMap::new(), nodes, mk_edges(es))
+    }
+
+    #[test]
+    fn test_summarize() {
+        let g = mk_g1();
+        let s = summarize(&g);
+        assert_eq!(s.graph_id, "g1");
+        assert_eq!(s.order, 6);
+        assert_eq!(s.size, 4);
+        assert_eq!(s.min_degree, 0);
+        assert_eq!(s.max_degree, 3);
+        assert_eq!(s.mean_degree, 8.0 / 6.0);
+        assert_eq!(s.nb_components, 3);
+        assert!(!s.is_directed);
+        assert!(s.is_mixed);
+        assert_eq!(s.nb_self_loops, 1);
+        assert_eq!(s.nb_isolated, 1);
+    }
+
+    #[test]
+    fn test_summarize_empty() {
+        let g: Graph<Node, Edge<Node>> = Graph::empty("g0");
+        let s = summarize(&g);
+        assert_eq!(s.order, 0);
+        assert_eq!(s.nb_components, 0);
+        assert_eq!(s.mean_degree, 0.0);
+        assert!(!s.is_directed && !s.is_mixed);
+    }
+
+    #[test]
+    fn test_summarize_display() {
+        let g = mk_g1();
+        let report = summarize(&g).to_string();
+        assert!(report.starts_with("Graph summary for g1"));
+        assert!(report.contains("kind:            mixed"));
+        assert!(report.contains("components:      3"));
+    }
+}
@@ -0,0 +1,375 @@
+//! eulerian circuits and hamiltonian paths
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::edgetype::EdgeType;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::time::Duration;
+use std::time::Instant;
+
+/// Find an eulerian circuit of a graph
+/// # Description
+/// An eulerian circuit is a closed walk using every edge exactly once. It
+/// exists when the edges lie in a single connected component and every
+/// vertex has an even degree, or, for a directed graph, as many incoming
+/// as outgoing edges. We use Hierholzer's algorithm. The circuit starts at
+/// the vertex with the smallest identifier that has an edge, and edges are
+/// tried in identifier order, so the output is reproducible. Panics on a
+/// graph mixing directed and undirected edges.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - returns: the edges of the circuit in walking order, `None` if there is
+///   no eulerian circuit. A graph without edges has an empty circuit.
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph_obj::GraphObject;
+/// use pgm_rust::graph::ops::graph::tours::eulerian_circuit;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "c", "a");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// let circuit = eulerian_circuit(&g).unwrap();
+/// let ids: Vec<&String> = circuit.into_iter().map(|e| e.id()).collect();
+/// ids == vec!["e1", "e2", "e3"]; // true
+/// ```
+///
+/// # References
+/// Hierholzer C, Wiener C. Über die Möglichkeit, einen Linienzug ohne
+/// Wiederholung und ohne Unterbrechung zu umfahren. 1873.
+pub fn eulerian_circuit<'a, N, E, G>(g: &'a G) -> Option<Vec<&'a E>>
+where
+    N: NodeTrait + 'a,
+    E: EdgeTrait<N> + 'a,
+    G: GraphTrait<N, E>,
+{
+    let edges = g.edges_sorted();
+    if edges.is_empty() {
+        return Some(Vec::new());
+    }
+    let directed = edges[0].has_type() == &EdgeType::Directed;
+    if edges
+        .iter()
+        .any(|e| (e.has_type() == &EdgeType::Directed) != directed)
+    {
+        panic!("{g} mixes directed and undirected edges");
+    }
+    // edges that can be walked from each vertex, as (edge index, other end)
+    let mut adjacency: BTreeMap<&String, Vec<(usize, &String)>> = BTreeMap::new();
+    let mut balance: HashMap<&String, i64> = HashMap::new();
+    for (i, e) in edges.iter().enumerate() {
+        let (s, t) = (e.start().id(), e.end().id());
+        adjacency.entry(s).or_default().push((i, t));
+        *balance.entry(s).or_default() += 1;
+        if directed {
+            adjacency.entry(t).or_default();
+            *balance.entry(t).or_default() -= 1;
+        } else {
+            if s != t {
+                adjacency.entry(t).or_default().push((i, s));
+            }
+            *balance.entry(t).or_default() += 1;
+        }
+    }
+    let balanced = if directed {
+        balance.values().all(|b| *b == 0)
+    } else {
+        balance.values().all(|b| b % 2 == 0)
+    };
+    if !balanced {
+        return None;
+    }
+    let start = *adjacency.keys().next().unwrap();
+    let mut next: HashMap<&String, usize> = HashMap::new();
+    let mut used = vec![false; edges.len()];
+    // stack of (vertex, edge used to reach it)
+    let mut stack: Vec<(&String, Option<usize>)> = vec![(start, None)];
+    let mut circuit: Vec<&E> = Vec::with_capacity(edges.len());
+    while let Some((v, via)) = stack.last().copied() {
+        let arcs = &adjacency[v];
+        let k = next.entry(v).or_default();
+        while *k < arcs.len() && used[arcs[*k].0] {
+            *k += 1;
+        }
+        if *k < arcs.len() {
+            let (i, w) = arcs[*k];
+            used[i] = true;
+            stack.push((w, Some(i)));
+        } else {
+            stack.pop();
+            if let Some(i) = via {
+                circuit.push(edges[i]);
+            }
+        }
+    }
+    // edges outside the component of the start vertex are left unused
+    if circuit.len() < edges.len() {
+        return None;
+    }
+    circuit.reverse();
+    Some(circuit)
+}
+
+/// outcome of [hamiltonian_path]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HamiltonianSearch<'a, N> {
+    /// vertices of a hamiltonian path in walking order
+    Found(Vec<&'a N>),
+    /// the search is complete and there is no hamiltonian path
+    NotFound,
+    /// the search ran out of time before finding a path
+    TimedOut,
+}
+
+/// backtracking state of [hamiltonian_path]
+struct Backtrack<'a, 'b, N> {
+    successors: &'b HashMap<&'a String, Vec<&'a N>>,
+    path: Vec<&'a N>,
+    on_path: HashSet<&'a String>,
+    target: usize,
+    deadline: Instant,
+}
+
+impl<'a, N: NodeTrait> Backtrack<'a, '_, N> {
+    /// extend the path from its last vertex, `None` on timeout
+    fn extend(&mut self) -> Option<bool> {
+        if self.path.len() == self.target {
+            return Some(true);
+        }
+        if Instant::now() > self.deadline {
+            return None;
+        }
+        let last = self.path[self.path.len() - 1];
+        for w in self.successors.get(last.id()).into_iter().flatten() {
+            if self.on_path.contains(w.id()) {
+                continue;
+            }
+            self.path.push(w);
+            self.on_path.insert(w.id());
+            if self.extend()? {
+                return Some(true);
+            }
+            self.path.pop();
+            self.on_path.remove(w.id());
+        }
+        Some(false)
+    }
+}
+
+/// Find a hamiltonian path of a small graph
+/// # Description
+/// A hamiltonian path visits every vertex exactly once. Finding one is NP
+/// hard, so we backtrack over paths from every start vertex and give up
+/// after `timeout`. Directed edges are followed along their orientation,
+/// undirected edges both ways. Start vertices and successors are tried in
+/// identifier order, so the output is reproducible.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - timeout: longest time spent searching
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::tours::{hamiltonian_path, HamiltonianSearch};
+/// use std::collections::HashSet;
+/// use std::time::Duration;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Directed, "b", "a");
+/// let e2 = Edge::empty("e2", EdgeType::Directed, "c", "b");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+/// let (a, b, c) = (Node::empty("a"), Node::empty("b"), Node::empty("c"));
+/// hamiltonian_path(&g, Duration::from_secs(1)) == HamiltonianSearch::Found(vec![&c, &b, &a]); // true
+/// ```
+pub fn hamiltonian_path<'a, N, E, G>(g: &'a G, timeout: Duration) -> HamiltonianSearch<'a, N>
+where
+    N: NodeTrait + 'a,
+    E: EdgeTrait<N> + 'a,
+    G: GraphTrait<N, E>,
+{
+    let vertices = g.vertices_sorted();
+    if vertices.is_empty() {
+        return HamiltonianSearch::NotFound;
+    }
+    let mut successors: HashMap<&String, Vec<&N>> = HashMap::new();
+    for e in g.edges() {
+        let (s, t) = (e.start(), e.end());
+        successors.entry(s.id()).or_default().push(t);
+        if e.has_type() == &EdgeType::Undirected {
+            successors.entry(t.id()).or_default().push(s);
+        }
+    }
+    for ws in successors.values_mut() {
+        ws.sort_by(|a, b| a.id().cmp(b.id()));
+        ws.dedup_by(|a, b| a.id() == b.id());
+    }
+    let mut search = Backtrack {
+        successors: &successors,
+        path: Vec::with_capacity(vertices.len()),
+        on_path: HashSet::new(),
+        target: vertices.len(),
+        deadline: Instant::now() + timeout,
+    };
+    for v in vertices {
+        search.path = vec![v];
+        search.on_path = HashSet::from([v.id()]);
+        match search.extend() {
+            None => return HamiltonianSearch::TimedOut,
+            Some(true) => return HamiltonianSearch::Found(search.path),
+            Some(false) => {}
+        }
+    }
+    HamiltonianSearch::NotFound
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::traits::graph_obj::GraphObject;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+
+    fn mk_uedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Undirected, n1_id, n2_id)
+    }
+
+    fn mk_dedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Directed, n1_id, n2_id)
+    }
+
+    /// checks that consecutive edges share a vertex and the walk closes
+    fn assert_circuit(circuit: &[&Edge<Node>], directed: bool) {
+        let mut current = circuit[0].start().id().clone();
+        let first = current.clone();
+        for e in circuit {
+            let (s, t) = (e.start().id(), e.end().id());
+            current = if *s == current {
+                t.clone()
+            } else if !directed && *t == current {
+                s.clone()
+            } else {
+                panic!("{e} does not continue the walk at {current}");
+            };
+        }
+        assert_eq!(current, first);
+    }
+
+    #[test]
+    fn test_eulerian_circuit_undirected() {
+        // two triangles sharing c, with a loop on a
+        let es = HashSet::from([
+            mk_uedge("a", "b", "e1"),
+            mk_uedge("b", "c", "e2"),
+            mk_uedge("c", "a", "e3"),
+            mk_uedge("c", "d", "e4"),
+            mk_uedge("d", "e", "e5"),
+            mk_uedge("e", "c", "e6"),
+            mk_uedge("a", "a", "e7"),
+        ]);
+        let g: Graph<Node, Edge<Node>> =
+            Graph::from_edge_node_set(es, HashSet::from([Node::empty("z")]));
+        let circuit = eulerian_circuit(&g).unwrap();
+        assert_eq!(circuit.len(), 7);
+        let ids: HashSet<&String> = circuit.iter().map(|e| e.id()).collect();
+        assert_eq!(ids.len(), 7);
+        assert_circuit(&circuit, false);
+    }
+
+    #[test]
+    fn test_eulerian_circuit_directed() {
+        let es = HashSet::from([
+            mk_dedge("a", "b", "e1"),
+            mk_dedge("b", "c", "e2"),
+            mk_dedge("c", "a", "e3"),
+            mk_dedge("a", "c", "e4"),
+            mk_dedge("c", "a", "e5"),
+        ]);
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(es);
+        let circuit = eulerian_circuit(&g).unwrap();
+        assert_eq!(circuit.len(), 5);
+        assert_circuit(&circuit, true);
+    }
+
+    #[test]
+    fn test_eulerian_circuit_none() {
+        let path: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([
+            mk_uedge("a", "b", "e1"),
+            mk_uedge("b", "c", "e2"),
+        ]));
+        assert!(eulerian_circuit(&path).is_none());
+        // two disjoint triangles
+        let es = HashSet::from([
+            mk_uedge("a", "b", "e1"),
+            mk_uedge("b", "c", "e2"),
+            mk_uedge("c", "a", "e3"),
+            mk_uedge("d", "e", "e4"),
+            mk_uedge("e", "f", "e5"),
+            mk_uedge("f", "d", "e6"),
+        ]);
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(es);
+        assert!(eulerian_circuit(&g).is_none());
+        let dpath: Graph<Node, Edge<Node>> =
+            Graph::from_edgeset(HashSet::from([mk_dedge("a", "b", "e1")]));
+        assert!(eulerian_circuit(&dpath).is_none());
+    }
+
+    #[test]
+    fn test_hamiltonian_path() {
+        // star with three leaves has no hamiltonian path, adding a leaf to
+        // leaf edge gives one
+        let mut es = HashSet::from([
+            mk_uedge("c", "l1", "e1"),
+            mk_uedge("c", "l2", "e2"),
+            mk_uedge("c", "l3", "e3"),
+        ]);
+        let star: Graph<Node, Edge<Node>> = Graph::from_edgeset(es.clone());
+        assert_eq!(
+            hamiltonian_path(&star, Duration::from_secs(5)),
+            HamiltonianSearch::NotFound
+        );
+        es.insert(mk_uedge("l2", "l3", "e4"));
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(es);
+        match hamiltonian_path(&g, Duration::from_secs(5)) {
+            HamiltonianSearch::Found(path) => {
+                let ids: Vec<&String> = path.into_iter().map(|v| v.id()).collect();
+                assert_eq!(ids, vec!["l1", "c", "l2", "l3"]);
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_hamiltonian_path_timeout() {
+        // complete graph minus the edges of v0, so that every search fails
+        // slowly
+        let mut es = HashSet::new();
+        for i in 1..12 {
+            for j in i + 1..12 {
+                es.insert(mk_uedge(
+                    &format!("v{i:02}"),
+                    &format!("v{j:02}"),
+                    &format!("e{i}-{j}"),
+                ));
+            }
+        }
+        let g: Graph<Node, Edge<Node>> =
+            Graph::from_edge_node_set(es, HashSet::from([Node::empty("v00")]));
+        assert_eq!(
+            hamiltonian_path(&g, Duration::from_millis(1)),
+            HamiltonianSearch::TimedOut
+        );
+    }
+}
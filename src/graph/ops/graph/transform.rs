@@ -0,0 +1,1118 @@
+//! structure preserving graph transformations
+use crate::graph::ops::edge::transform::reverse_edge;
+use crate::graph::ops::graph::order::simple_neighbors;
+use crate::graph::ops::graph::search::topological_sort;
+use crate::graph::traits::dyngraph::DynGraph;
+use crate::graph::traits::dyngraph::EdgeDescription;
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::edgetype::EdgeType;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+
+/// Map a function over the vertices of a graph
+/// # Description
+/// Every vertex is replaced by the output of `f` and every edge is rebuilt
+/// on the replaced vertices, so the resulting graph has the same topology
+/// as `g`. Edge identifiers, data and types are kept. `f` is called once per
+/// vertex. Graph identifier and data are kept as well.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - f: function producing a new node from an old one. It must not map
+///   two vertices to the same identifier.
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph_obj::GraphObject;
+/// use pgm_rust::graph::ops::graph::transform::map_nodes;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "n1", "n2");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1]));
+/// let g2 = map_nodes(&g, |n: &Node| Node::new(format!("plate1.{}", n.id()), n.data().clone()));
+/// ```
+pub fn map_nodes<N, E, G, F>(g: &G, f: F) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    F: Fn(&N) -> N,
+{
+    let mut mapping: HashMap<&String, N> = HashMap::new();
+    let mut new_ids: HashSet<String> = HashSet::new();
+    for v in g.vertices() {
+        let nv = f(v);
+        if !new_ids.insert(nv.id().clone()) {
+            panic!("{} is produced for more than one vertex", nv.id());
+        }
+        mapping.insert(v.id(), nv);
+    }
+    let mut edges: HashSet<E> = HashSet::new();
+    for e in g.edges() {
+        let start = mapping[e.start().id()].clone();
+        let end = mapping[e.end().id()].clone();
+        edges.insert(E::create(
+            e.id().clone(),
+            e.data().clone(),
+            start,
+            end,
+            e.has_type().clone(),
+        ));
+    }
+    let nodes: HashSet<N> = mapping.into_values().collect();
+    G::create(g.id().clone(), g.data().clone(), nodes, edges)
+}
+
+/// Map a function over the edges of a graph
+/// # Description
+/// Every edge is replaced by the output of `f`. The new edge must join the
+/// same vertices as the old one, so that the topology of `g` is preserved.
+/// Vertices that are not incident to any edge are kept as they are.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - f: function producing a new edge from an old one. It must keep the
+///   identifiers of the end vertices.
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::edge::Edge as EdgeTrait;
+/// use pgm_rust::graph::traits::graph_obj::GraphObject;
+/// use pgm_rust::graph::ops::graph::transform::map_edges;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "n1", "n2");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1]));
+/// let g2 = map_edges(&g, |e: &Edge<Node>| {
+///     Edge::new(
+///         format!("plate1.{}", e.id()),
+///         e.data().clone(),
+///         e.start().clone(),
+///         e.end().clone(),
+///         e.has_type().clone(),
+///     )
+/// });
+/// ```
+pub fn map_edges<N, E, G, F>(g: &G, f: F) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    F: Fn(&E) -> E,
+{
+    let mut edges: HashSet<E> = HashSet::new();
+    for e in g.edges() {
+        let ne = f(e);
+        let same_start = ne.start().id() == e.start().id();
+        let same_end = ne.end().id() == e.end().id();
+        if !(same_start && same_end) {
+            panic!("{ne} does not join the end vertices of {e}");
+        }
+        edges.insert(ne);
+    }
+    let nodes: HashSet<N> = g.vertices().into_iter().cloned().collect();
+    G::create(g.id().clone(), g.data().clone(), nodes, edges)
+}
+
+/// Rename vertices of a graph
+/// # Description
+/// Vertices whose identifier is a key of `mapping` are renamed to the
+/// corresponding value, other vertices keep their identifier. Vertex data,
+/// edges and topology are preserved, see [map_nodes].
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - mapping: old vertex identifier to new vertex identifier
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::transform::relabel;
+/// use std::collections::HashMap;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "n1", "n2");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1]));
+/// let mapping = HashMap::from([("n1".to_string(), "a".to_string())]);
+/// let g2 = relabel(&g, &mapping);
+/// ```
+pub fn relabel<N, E, G>(g: &G, mapping: &HashMap<String, String>) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    map_nodes(g, |n: &N| match mapping.get(n.id()) {
+        None => n.clone(),
+        Some(nid) => N::create(nid.clone(), n.data().clone()),
+    })
+}
+
+/// undirected edges of `g`, one per adjacent pair of vertices, keyed by
+/// the ordered pair of end vertex identifiers
+fn undirected_edges<N, E, G>(g: &G) -> BTreeMap<(String, String), E>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let mut edges: BTreeMap<(String, String), E> = BTreeMap::new();
+    for e in g.edges_sorted() {
+        if e.start().id() == e.end().id() {
+            continue;
+        }
+        let (a, b) = if e.start().id() < e.end().id() {
+            (e.start(), e.end())
+        } else {
+            (e.end(), e.start())
+        };
+        let key = (a.id().clone(), b.id().clone());
+        // edges are sorted, so the edge with the smallest identifier wins
+        edges.entry(key).or_insert_with(|| {
+            E::create(
+                e.id().clone(),
+                e.data().clone(),
+                a.clone(),
+                b.clone(),
+                EdgeType::Undirected,
+            )
+        });
+    }
+    edges
+}
+
+/// Compute the skeleton of a graph
+/// # Description
+/// The skeleton is the undirected graph obtained by dropping edge
+/// directions. Antiparallel and parallel edges collapse into a single
+/// undirected edge, which keeps the identifier and data of the edge with
+/// the smallest identifier. Loops are dropped. Graph identifier and data
+/// are kept.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+/// use pgm_rust::graph::ops::graph::transform::skeleton;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Directed, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Directed, "b", "a");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+/// skeleton(&g).size() == 1; // true
+/// ```
+pub fn skeleton<N, E, G>(g: &G) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let edges: HashSet<E> = undirected_edges(g).into_values().collect();
+    let nodes: HashSet<N> = g.vertices().into_iter().cloned().collect();
+    G::create(g.id().clone(), g.data().clone(), nodes, edges)
+}
+
+/// Compute the moral graph of a graph
+/// # Description
+/// The moral graph is the [skeleton] in which the parents of every vertex
+/// are pairwise joined, see Lauritzen 1996, p. 7. Parents are the start
+/// vertices of directed edges, so undirected edges of a mixed graph are
+/// kept without marrying their end vertices. Added edges are named
+/// `a--b` after their end vertices in identifier order and carry no data.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+/// use pgm_rust::graph::ops::graph::transform::moral_graph;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Directed, "a", "c");
+/// let e2 = Edge::empty("e2", EdgeType::Directed, "b", "c");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+/// moral_graph(&g).size() == 3; // true: a and b are married
+/// ```
+///
+/// # References
+/// Lauritzen S. Graphical Models. 1996.
+pub fn moral_graph<N, E, G>(g: &G) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let mut edges = undirected_edges(g);
+    let mut parents: BTreeMap<&String, Vec<&N>> = BTreeMap::new();
+    for e in g.edges_sorted() {
+        if matches!(e.has_type(), EdgeType::Directed) && e.start().id() != e.end().id() {
+            parents.entry(e.end().id()).or_default().push(e.start());
+        }
+    }
+    for ps in parents.values_mut() {
+        ps.sort_by(|a, b| a.id().cmp(b.id()));
+        ps.dedup_by(|a, b| a.id() == b.id());
+        for (i, a) in ps.iter().enumerate() {
+            for b in &ps[i + 1..] {
+                let key = (a.id().clone(), b.id().clone());
+                edges.entry(key).or_insert_with(|| {
+                    E::create(
+                        format!("{}--{}", a.id(), b.id()),
+                        HashMap::new(),
+                        (*a).clone(),
+                        (*b).clone(),
+                        EdgeType::Undirected,
+                    )
+                });
+            }
+        }
+    }
+    let edges: HashSet<E> = edges.into_values().collect();
+    let nodes: HashSet<N> = g.vertices().into_iter().cloned().collect();
+    G::create(g.id().clone(), g.data().clone(), nodes, edges)
+}
+
+/// direct successors and descendants of every vertex of a directed acyclic
+/// graph, panics if `g` is not one
+fn descendants<'a, N, E, G>(g: &'a G) -> HashMap<&'a String, BTreeSet<&'a String>>
+where
+    N: NodeTrait + 'a,
+    E: EdgeTrait<N> + 'a,
+    G: GraphTrait<N, E>,
+{
+    let order = match topological_sort(g) {
+        None => panic!("{g} has a directed cycle"),
+        Some(order) => order,
+    };
+    let mut successors: HashMap<&String, BTreeSet<&String>> = HashMap::new();
+    for e in g.edges() {
+        successors
+            .entry(e.start().id())
+            .or_default()
+            .insert(e.end().id());
+    }
+    let mut desc: HashMap<&String, BTreeSet<&String>> = HashMap::new();
+    for v in order.into_iter().rev() {
+        let mut ds: BTreeSet<&String> = BTreeSet::new();
+        for w in successors.get(v.id()).into_iter().flatten() {
+            ds.insert(*w);
+            ds.extend(desc[*w].iter().copied());
+        }
+        desc.insert(v.id(), ds);
+    }
+    desc
+}
+
+/// Compute the transitive closure of a directed acyclic graph
+/// # Description
+/// An edge `u -> v` is added whenever `v` can be reached from `u` and no
+/// edge joins them yet. Added edges are named `u->v` after their end
+/// vertices and carry no data. Existing edges, graph identifier and data
+/// are kept. Panics if `g` has an undirected edge or a directed cycle.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+/// use pgm_rust::graph::ops::graph::transform::transitive_closure;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Directed, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Directed, "b", "c");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+/// transitive_closure(&g).size() == 3; // true: a->c is added
+/// ```
+pub fn transitive_closure<N, E, G>(g: &G) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let desc = descendants(g);
+    let vertices: HashMap<&String, &N> = g.vertices().into_iter().map(|v| (v.id(), v)).collect();
+    let adjacent: HashSet<(&String, &String)> = g
+        .edges()
+        .into_iter()
+        .map(|e| (e.start().id(), e.end().id()))
+        .collect();
+    let mut edges: HashSet<E> = g
+        .edges()
+        .into_iter()
+        .map(|e| {
+            E::create(
+                e.id().clone(),
+                e.data().clone(),
+                e.start().clone(),
+                e.end().clone(),
+                e.has_type().clone(),
+            )
+        })
+        .collect();
+    for (u, ds) in &desc {
+        for v in ds {
+            if !adjacent.contains(&(*u, *v)) {
+                edges.insert(E::create(
+                    format!("{u}->{v}"),
+                    HashMap::new(),
+                    vertices[u].clone(),
+                    vertices[v].clone(),
+                    EdgeType::Directed,
+                ));
+            }
+        }
+    }
+    let nodes: HashSet<N> = g.vertices().into_iter().cloned().collect();
+    G::create(g.id().clone(), g.data().clone(), nodes, edges)
+}
+
+/// Compute the transitive reduction of a directed acyclic graph
+/// # Description
+/// The transitive reduction is the smallest graph with the same
+/// reachability as `g`, see Aho et al. 1972. An edge `u -> v` is dropped
+/// when `v` can also be reached from `u` through another successor of `u`.
+/// Among parallel edges only the one with the smallest identifier is kept.
+/// Graph identifier and data are kept. Panics if `g` has an undirected edge
+/// or a directed cycle.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+/// use pgm_rust::graph::ops::graph::transform::transitive_reduction;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Directed, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Directed, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Directed, "a", "c");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// transitive_reduction(&g).size() == 2; // true: a->c is implied
+/// ```
+///
+/// # References
+/// Aho A, Garey M, Ullman J. The transitive reduction of a directed graph.
+/// 1972.
+pub fn transitive_reduction<N, E, G>(g: &G) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let desc = descendants(g);
+    let mut kept: BTreeMap<(&String, &String), &E> = BTreeMap::new();
+    for e in g.edges_sorted() {
+        let (u, v) = (e.start().id(), e.end().id());
+        if kept.contains_key(&(u, v)) {
+            continue;
+        }
+        let implied = desc[u].iter().any(|w| *w != v && desc[*w].contains(v));
+        if !implied {
+            kept.insert((u, v), e);
+        }
+    }
+    let edges: HashSet<E> = kept
+        .into_values()
+        .map(|e| {
+            E::create(
+                e.id().clone(),
+                e.data().clone(),
+                e.start().clone(),
+                e.end().clone(),
+                e.has_type().clone(),
+            )
+        })
+        .collect();
+    let nodes: HashSet<N> = g.vertices().into_iter().cloned().collect();
+    G::create(g.id().clone(), g.data().clone(), nodes, edges)
+}
+
+/// Compute the reverse of a graph
+/// # Description
+/// The reverse, or transpose, of a graph has every directed edge pointing
+/// the other way. Undirected edges are kept as they are. Edge identifiers,
+/// data and types are kept, as are the graph identifier and data. See
+/// [ReversedGraph] for a view that does not copy the graph.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::edge::Edge as EdgeTrait;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+/// use pgm_rust::graph::traits::graph_obj::GraphObject;
+/// use pgm_rust::graph::ops::graph::transform::reverse;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Directed, "a", "b");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1]));
+/// let r = reverse(&g);
+/// let e = r.edges().into_iter().next().unwrap();
+/// e.start().id() == "b"; // true
+/// ```
+pub fn reverse<N, E, G>(g: &G) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let edges: HashSet<E> = g
+        .edges()
+        .into_iter()
+        .map(|e| match e.has_type() {
+            EdgeType::Directed => reverse_edge(e),
+            EdgeType::Undirected => copy_edge(e),
+        })
+        .collect();
+    let nodes: HashSet<N> = g.vertices().into_iter().cloned().collect();
+    G::create(g.id().clone(), g.data().clone(), nodes, edges)
+}
+
+fn copy_edge<N: NodeTrait, E: EdgeTrait<N>>(e: &E) -> E {
+    E::create(
+        e.id().clone(),
+        e.data().clone(),
+        e.start().clone(),
+        e.end().clone(),
+        e.has_type().clone(),
+    )
+}
+
+/// Orient the undirected edges of a graph
+/// # Description
+/// Every undirected edge becomes a directed edge, from its start to its end
+/// vertex if `forward` returns true for it and the other way otherwise.
+/// Directed edges are kept as they are and `forward` is not called on them.
+/// Identifiers and data of the edges, vertices and graph are kept. This
+/// turns a skeleton, such as the output of a structure learning algorithm,
+/// into a directed graph once a direction is decided for every edge. See
+/// [orient_bfs] for an orientation which cannot create directed cycles.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - forward: whether an undirected edge keeps its start vertex as start
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::edge::Edge as EdgeTrait;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+/// use pgm_rust::graph::traits::graph_obj::GraphObject;
+/// use pgm_rust::graph::ops::graph::transform::orient;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "b", "a");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1]));
+/// // point every edge towards the larger identifier
+/// let d = orient(&g, |e: &Edge<Node>| e.start().id() < e.end().id());
+/// let e = d.edges().into_iter().next().unwrap();
+/// e.start().id() == "a" && e.has_type() == &EdgeType::Directed; // true
+/// ```
+pub fn orient<N, E, G, F>(g: &G, forward: F) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    F: Fn(&E) -> bool,
+{
+    let edges: HashSet<E> = g
+        .edges()
+        .into_iter()
+        .map(|e| match e.has_type() {
+            EdgeType::Directed => copy_edge(e),
+            EdgeType::Undirected => {
+                let (start, end) = if forward(e) {
+                    (e.start(), e.end())
+                } else {
+                    (e.end(), e.start())
+                };
+                E::create(
+                    e.id().clone(),
+                    e.data().clone(),
+                    start.clone(),
+                    end.clone(),
+                    EdgeType::Directed,
+                )
+            }
+        })
+        .collect();
+    let nodes: HashSet<N> = g.vertices().into_iter().cloned().collect();
+    G::create(g.id().clone(), g.data().clone(), nodes, edges)
+}
+
+/// Orient the undirected edges of a graph along a breadth first search
+/// # Description
+/// Vertices are numbered in the order a breadth first search discovers
+/// them, edge orientation being ignored. The search starts at `root`, then
+/// restarts from the smallest unvisited identifier until every vertex is
+/// numbered, and neighbors are discovered in identifier order. Every
+/// undirected edge is then directed from its lower numbered end to its
+/// higher numbered one, see [orient]. Since all these edges follow a single
+/// vertex order, they form no directed cycle: a purely undirected graph
+/// becomes a directed acyclic graph in which `root` is a source and edges
+/// of the search tree point away from it. Directed edges are kept as they
+/// are, so a mixed graph may still have directed cycles. Undirected loops
+/// become directed loops.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - root: vertex where the search starts
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::search::topological_sort;
+/// use pgm_rust::graph::ops::graph::transform::orient_bfs;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "c", "a");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// let d = orient_bfs(&g, &Node::empty("c"));
+/// topological_sort(&d).unwrap()[0] == &Node::empty("c"); // true
+/// ```
+pub fn orient_bfs<N, E, G>(g: &G, root: &N) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    if !g.contains_node_id(root.id()) {
+        panic!("{root} not in {g}");
+    }
+    let neighbors = simple_neighbors(g);
+    let mut rank: HashMap<&String, usize> = HashMap::new();
+    let sorted = g.vertices_sorted();
+    let starts = std::iter::once(root.id()).chain(sorted.iter().map(|v| v.id()));
+    for start in starts {
+        if rank.contains_key(start) {
+            continue;
+        }
+        let mut queue: VecDeque<&String> = VecDeque::from([start]);
+        rank.insert(start, rank.len());
+        while let Some(u) = queue.pop_front() {
+            let mut next: Vec<&String> = neighbors[u].iter().copied().collect();
+            next.sort();
+            for v in next {
+                if !rank.contains_key(v) {
+                    rank.insert(v, rank.len());
+                    queue.push_back(v);
+                }
+            }
+        }
+    }
+    orient(g, |e: &E| rank[e.start().id()] <= rank[e.end().id()])
+}
+
+/// Reverse of a graph seen without copying it
+/// # Description
+/// Directed edges of the wrapped graph are read the other way: the start of
+/// an edge in the view is its end in the wrapped graph and conversely.
+/// Undirected edges are read as they are. The view answers adjacency
+/// queries through its own accessors and the [DynGraph] trait, so that
+/// backward searches and ancestor queries do not pay for [reverse].
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::dyngraph::DynGraph;
+/// use pgm_rust::graph::ops::graph::transform::ReversedGraph;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Directed, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Directed, "b", "c");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+/// let r = ReversedGraph::new(&g);
+/// r.successors_of_id("b") == vec!["a".to_string()]; // true
+/// r.outgoing_edges_of(&Node::empty("c")).len() == 1; // true
+/// ```
+pub struct ReversedGraph<'a, N, E, G> {
+    graph: &'a G,
+    types: PhantomData<(N, E)>,
+}
+
+impl<'a, N, E, G> ReversedGraph<'a, N, E, G>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    /// reverse view of `graph`
+    pub fn new(graph: &'a G) -> ReversedGraph<'a, N, E, G> {
+        ReversedGraph {
+            graph,
+            types: PhantomData,
+        }
+    }
+
+    /// wrapped graph
+    pub fn graph(&self) -> &'a G {
+        self.graph
+    }
+
+    /// start vertex of `e` in the view
+    pub fn start_of<'b>(&self, e: &'b E) -> &'b N {
+        match e.has_type() {
+            EdgeType::Directed => e.end(),
+            EdgeType::Undirected => e.start(),
+        }
+    }
+
+    /// end vertex of `e` in the view
+    pub fn end_of<'b>(&self, e: &'b E) -> &'b N {
+        match e.has_type() {
+            EdgeType::Directed => e.start(),
+            EdgeType::Undirected => e.end(),
+        }
+    }
+
+    /// edges starting at `n` in the view, see
+    /// [outgoing_edges_of](crate::graph::ops::graph::edge::outgoing_edges_of)
+    pub fn outgoing_edges_of(&self, n: &N) -> HashSet<&'a E> {
+        if !self.graph.vertices().contains(n) {
+            panic!("{} does not contain {n}", self.graph);
+        }
+        self.graph
+            .edges()
+            .into_iter()
+            .filter(|e| self.start_of(e).id() == n.id())
+            .collect()
+    }
+
+    /// edges ending at `n` in the view, see
+    /// [incoming_edges_of](crate::graph::ops::graph::edge::incoming_edges_of)
+    pub fn incoming_edges_of(&self, n: &N) -> HashSet<&'a E> {
+        if !self.graph.vertices().contains(n) {
+            panic!("{} does not contain {n}", self.graph);
+        }
+        self.graph
+            .edges()
+            .into_iter()
+            .filter(|e| self.end_of(e).id() == n.id())
+            .collect()
+    }
+
+    /// copy of the view, see [reverse]
+    pub fn to_graph(&self) -> G {
+        reverse(self.graph)
+    }
+}
+
+impl<N, E, G> DynGraph for ReversedGraph<'_, N, E, G>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    fn graph_id(&self) -> String {
+        self.graph.id().clone()
+    }
+    fn vertex_ids(&self) -> Vec<String> {
+        self.graph
+            .vertices_sorted()
+            .into_iter()
+            .map(|v| v.id().clone())
+            .collect()
+    }
+    fn edge_list(&self) -> Vec<EdgeDescription> {
+        self.graph
+            .edges_sorted()
+            .into_iter()
+            .map(|e| EdgeDescription {
+                id: e.id().clone(),
+                start: self.start_of(e).id().clone(),
+                end: self.end_of(e).id().clone(),
+                edge_type: e.has_type().clone(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::traits::graph_obj::GraphObject;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::edgetype::EdgeType;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+
+    fn mk_node(n_id: &str) -> Node {
+        Node::empty(n_id)
+    }
+    fn mk_nodes(ns: Vec<&str>) -> HashSet<Node> {
+        let mut hs: HashSet<Node> = HashSet::new();
+        for n in ns {
+            hs.insert(mk_node(n));
+        }
+        hs
+    }
+    fn mk_uedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Undirected, n1_id, n2_id)
+    }
+    fn mk_edges(es: Vec<Edge<Node>>) -> HashSet<Edge<Node>> {
+        let mut hs = HashSet::new();
+        for e in es {
+            hs.insert(e);
+        }
+        hs
+    }
+    fn mk_g1() -> Graph<Node, Edge<Node>> {
+        let e1 = mk_uedge("n1", "n2", "e1");
+        let e2 = mk_uedge("n2", "n3", "e2");
+        let nset = mk_nodes(vec!["n1", "n2", "n3", "n4"]);
+        Graph::new(
+            "g1".to_string(),
+            HashMap::new(),
+            nset,
+            mk_edges(vec![e1, e2]),
+        )
+    }
+
+    fn prefixed(n: &Node) -> Node {
+        Node::new(format!("p.{}", n.id()), n.data().clone())
+    }
+
+    #[test]
+    fn test_map_nodes() {
+        let g = mk_g1();
+        let g2 = map_nodes(&g, prefixed);
+        assert_eq!(g2.id(), "g1");
+        let ns = mk_nodes(vec!["p.n1", "p.n2", "p.n3", "p.n4"]);
+        let comp: HashSet<&Node> = ns.iter().collect();
+        assert_eq!(g2.vertices(), comp);
+        let e1 = mk_uedge("p.n1", "p.n2", "e1");
+        let e2 = mk_uedge("p.n2", "p.n3", "e2");
+        assert_eq!(g2.edges(), HashSet::from([&e1, &e2]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_map_nodes_collision() {
+        let g = mk_g1();
+        map_nodes(&g, |_: &Node| mk_node("same"));
+    }
+
+    #[test]
+    fn test_map_edges() {
+        let g = mk_g1();
+        let g2 = map_edges(&g, |e: &Edge<Node>| {
+            let mut data = e.data().clone();
+            data.insert("kind".to_string(), vec!["template".to_string()]);
+            Edge::new(
+                e.id().clone(),
+                data,
+                e.start().clone(),
+                e.end().clone(),
+                e.has_type().clone(),
+            )
+        });
+        assert_eq!(g2.vertices(), g.vertices());
+        for e in g2.edges() {
+            assert_eq!(e.data()["kind"], vec!["template".to_string()]);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_map_edges_changes_topology() {
+        let g = mk_g1();
+        map_edges(&g, |e: &Edge<Node>| {
+            Edge::new(
+                e.id().clone(),
+                e.data().clone(),
+                e.start().clone(),
+                mk_node("n4"),
+                e.has_type().clone(),
+            )
+        });
+    }
+
+    #[test]
+    fn test_relabel() {
+        let g = mk_g1();
+        let mapping = HashMap::from([
+            ("n1".to_string(), "a".to_string()),
+            ("n4".to_string(), "d".to_string()),
+        ]);
+        let g2 = relabel(&g, &mapping);
+        let ns = mk_nodes(vec!["a", "n2", "n3", "d"]);
+        let comp: HashSet<&Node> = ns.iter().collect();
+        assert_eq!(g2.vertices(), comp);
+        let e1 = mk_uedge("a", "n2", "e1");
+        let e2 = mk_uedge("n2", "n3", "e2");
+        assert_eq!(g2.edges(), HashSet::from([&e1, &e2]));
+    }
+
+    fn mk_dedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Directed, n1_id, n2_id)
+    }
+
+    /// the v-structure a -> c <- b with c -> d and an isolated vertex
+    fn mk_dag() -> Graph<Node, Edge<Node>> {
+        let e1 = mk_dedge("a", "c", "e1");
+        let e2 = mk_dedge("b", "c", "e2");
+        let e3 = mk_dedge("c", "d", "e3");
+        Graph::new(
+            "dag".to_string(),
+            HashMap::new(),
+            mk_nodes(vec!["a", "b", "c", "d", "i"]),
+            mk_edges(vec![e1, e2, e3]),
+        )
+    }
+
+    #[test]
+    fn test_skeleton() {
+        let g = mk_dag();
+        let s = skeleton(&g);
+        assert_eq!(s.id(), "dag");
+        assert_eq!(s.vertices(), g.vertices());
+        let e1 = mk_uedge("a", "c", "e1");
+        let e2 = mk_uedge("b", "c", "e2");
+        let e3 = mk_uedge("c", "d", "e3");
+        assert_eq!(s.edges(), HashSet::from([&e1, &e2, &e3]));
+    }
+
+    #[test]
+    fn test_skeleton_antiparallel() {
+        let e1 = mk_dedge("a", "b", "e2");
+        let e2 = mk_dedge("b", "a", "e1");
+        let e3 = mk_dedge("a", "a", "e3");
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(mk_edges(vec![e1, e2, e3]));
+        let s = skeleton(&g);
+        let expected = mk_uedge("a", "b", "e1");
+        assert_eq!(s.edges(), HashSet::from([&expected]));
+    }
+
+    #[test]
+    fn test_moral_graph() {
+        let g = mk_dag();
+        let m = moral_graph(&g);
+        assert_eq!(m.vertices(), g.vertices());
+        let e1 = mk_uedge("a", "c", "e1");
+        let e2 = mk_uedge("b", "c", "e2");
+        let e3 = mk_uedge("c", "d", "e3");
+        let e4 = mk_uedge("a", "b", "a--b");
+        assert_eq!(m.edges(), HashSet::from([&e1, &e2, &e3, &e4]));
+    }
+
+    #[test]
+    fn test_moral_graph_married_parents_adjacent() {
+        let e1 = mk_dedge("a", "c", "e1");
+        let e2 = mk_dedge("b", "c", "e2");
+        let e3 = mk_dedge("a", "b", "e3");
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(mk_edges(vec![e1, e2, e3]));
+        let m = moral_graph(&g);
+        assert_eq!(m.size(), 3);
+        assert!(m.edges().iter().all(|e| e.id() != "a--b"));
+    }
+
+    #[test]
+    fn test_reverse() {
+        let mut es = mk_edges(vec![mk_dedge("a", "b", "e1"), mk_uedge("b", "c", "e2")]);
+        es.insert(mk_dedge("c", "a", "e3"));
+        let g = Graph::from_edgeset(es);
+        let r = reverse(&g);
+        assert_eq!(r.id(), g.id());
+        let ends: HashSet<(String, String)> = r
+            .edges()
+            .into_iter()
+            .map(|e| (e.start().id().clone(), e.end().id().clone()))
+            .collect();
+        let expected: HashSet<(String, String)> = [("b", "a"), ("b", "c"), ("a", "c")]
+            .iter()
+            .map(|(s, t)| (s.to_string(), t.to_string()))
+            .collect();
+        assert_eq!(ends, expected);
+        assert_eq!(reverse(&r), g);
+    }
+
+    fn directed_ends(g: &Graph<Node, Edge<Node>>) -> HashSet<(String, String)> {
+        g.edges()
+            .into_iter()
+            .map(|e| {
+                assert_eq!(e.has_type(), &EdgeType::Directed);
+                (e.start().id().clone(), e.end().id().clone())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_orient() {
+        let es = mk_edges(vec![
+            mk_uedge("a", "b", "e1"),
+            mk_uedge("b", "c", "e2"),
+            mk_dedge("c", "a", "e3"),
+        ]);
+        let g = Graph::from_edge_node_set(es, mk_nodes(vec!["i"]));
+        let d = orient(&g, |e: &Edge<Node>| {
+            assert_ne!(e.id(), "e3");
+            e.id() == "e1"
+        });
+        assert_eq!(d.id(), g.id());
+        assert_eq!(d.vertices(), g.vertices());
+        assert_eq!(
+            directed_ends(&d),
+            pairs(vec![("a", "b"), ("c", "b"), ("c", "a")])
+        );
+    }
+
+    #[test]
+    fn test_orient_bfs() {
+        // a square a b c d with the diagonal b d and a separate edge x y
+        let es = mk_edges(vec![
+            mk_uedge("b", "a", "e1"),
+            mk_uedge("b", "c", "e2"),
+            mk_uedge("c", "d", "e3"),
+            mk_uedge("d", "a", "e4"),
+            mk_uedge("d", "b", "e5"),
+            mk_uedge("y", "x", "e6"),
+        ]);
+        let g = Graph::from_edgeset(es);
+        let d = orient_bfs(&g, &mk_node("c"));
+        // discovery order is c, b, d, a, x, y
+        let expected = pairs(vec![
+            ("b", "a"),
+            ("c", "b"),
+            ("c", "d"),
+            ("d", "a"),
+            ("b", "d"),
+            ("x", "y"),
+        ]);
+        assert_eq!(directed_ends(&d), expected);
+        assert!(topological_sort(&d).is_some());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_orient_bfs_missing_root() {
+        orient_bfs(&mk_g1(), &mk_node("z"));
+    }
+
+    #[test]
+    fn test_reversed_graph() {
+        let g = mk_dag();
+        let view = ReversedGraph::new(&g);
+        let copy = reverse(&g);
+        for v in g.vertices() {
+            let out: HashSet<&String> = view
+                .outgoing_edges_of(v)
+                .into_iter()
+                .map(|e| e.id())
+                .collect();
+            let expected: HashSet<&String> =
+                crate::graph::ops::graph::edge::outgoing_edges_of(&copy, v)
+                    .into_iter()
+                    .map(|e| e.id())
+                    .collect();
+            assert_eq!(out, expected);
+        }
+        assert_eq!(view.edge_list(), DynGraph::edge_list(&copy));
+        assert_eq!(view.to_graph(), copy);
+    }
+
+    fn edge_pairs(g: &Graph<Node, Edge<Node>>) -> HashSet<(String, String)> {
+        g.edges()
+            .into_iter()
+            .map(|e| (e.start().id().clone(), e.end().id().clone()))
+            .collect()
+    }
+
+    fn pairs(ps: Vec<(&str, &str)>) -> HashSet<(String, String)> {
+        ps.into_iter()
+            .map(|(s, t)| (s.to_string(), t.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_transitive_closure() {
+        let g = mk_dag();
+        let c = transitive_closure(&g);
+        let expected = pairs(vec![
+            ("a", "c"),
+            ("b", "c"),
+            ("c", "d"),
+            ("a", "d"),
+            ("b", "d"),
+        ]);
+        assert_eq!(edge_pairs(&c), expected);
+        assert_eq!(c.order(), g.order());
+        assert!(c.edges().into_iter().any(|e| e.id() == "a->d"));
+    }
+
+    #[test]
+    fn test_transitive_reduction() {
+        let g = mk_dag();
+        let c = transitive_closure(&g);
+        assert_eq!(transitive_reduction(&c), g);
+        let e1 = mk_dedge("a", "b", "e1");
+        let e2 = mk_dedge("a", "b", "e0");
+        let e3 = mk_dedge("b", "c", "e3");
+        let e4 = mk_dedge("a", "c", "e4");
+        let h = Graph::from_edgeset(mk_edges(vec![e1, e2, e3, e4]));
+        let r = transitive_reduction(&h);
+        let ids: HashSet<&String> = r.edges().into_iter().map(|e| e.id()).collect();
+        assert_eq!(ids, HashSet::from([&"e0".to_string(), &"e3".to_string()]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_transitive_closure_cycle() {
+        let e1 = mk_dedge("a", "b", "e1");
+        let e2 = mk_dedge("b", "a", "e2");
+        transitive_closure(&Graph::from_edgeset(mk_edges(vec![e1, e2])));
+    }
+}
@@ -0,0 +1,216 @@
+//! rooted trees extracted from graphs
+use crate::graph::ops::graph::search::dfs;
+use crate::graph::ops::graph::search::DepthFirstResult;
+use crate::graph::ops::graph::search::EdgeNeighborhood;
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::idgen::IdGenerator;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::graph::Graph;
+use crate::graph::types::idgen::UuidGenerator;
+use crate::graph::types::tree::Tree;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+/// tree of a depth first search rooted at `root`
+fn dfs_tree<N, E, G, I>(g: &G, result: &DepthFirstResult, root: &N, idgen: &mut I) -> Tree<N, E>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N> + Clone,
+    G: GraphTrait<N, E>,
+    I: IdGenerator,
+{
+    let edge_ids = &result.trees[root.id()];
+    let vertex_ids = &result.components[root.id()];
+    let edges: HashSet<&E> = g
+        .edges()
+        .into_iter()
+        .filter(|e| edge_ids.contains(e.id()))
+        .collect();
+    let nodes: HashSet<&N> = g
+        .vertices()
+        .into_iter()
+        .filter(|v| vertex_ids.contains(v.id()))
+        .collect();
+    let tid = idgen.generate(&nodes, &edges);
+    let graph = Graph::new_refs(tid, HashMap::new(), nodes, edges);
+    Tree::from_graph(graph, root.clone())
+}
+
+/// Extract a spanning tree of the component of a vertex
+/// # Description
+/// The tree is made of the edges followed by a depth first search from
+/// `root`, see [dfs]. Edges are followed in both directions, so the tree
+/// spans the vertices connected to `root` whatever the edge orientations.
+/// Edges keep their original orientation in the tree. The tree gets a
+/// generated identifier.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - root: root of the tree, a vertex of `g`
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+/// use pgm_rust::graph::traits::tree::Tree as TreeTrait;
+/// use pgm_rust::graph::ops::graph::treeops::spanning_tree;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "c", "a");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// let t = spanning_tree(&g, &Node::empty("a"));
+/// t.size() == 2; // true
+/// t.root() == &Node::empty("a"); // true
+/// ```
+pub fn spanning_tree<N, E, G>(g: &G, root: &N) -> Tree<N, E>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N> + Clone,
+    G: GraphTrait<N, E>,
+{
+    spanning_tree_with(g, root, &mut UuidGenerator)
+}
+
+/// Extract a spanning tree of the component of a vertex, the identifier of
+/// the tree is produced by `idgen`, see [spanning_tree]
+pub fn spanning_tree_with<N, E, G, I>(g: &G, root: &N, idgen: &mut I) -> Tree<N, E>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N> + Clone,
+    G: GraphTrait<N, E>,
+    I: IdGenerator,
+{
+    let result = dfs(g, root, &EdgeNeighborhood::Both, false);
+    dfs_tree(g, &result, root, idgen)
+}
+
+/// Extract a spanning forest of a graph
+/// # Description
+/// One [spanning_tree] per component. Each tree is rooted at the vertex of
+/// its component with the smallest identifier and trees are ordered by
+/// root identifier.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::tree::Tree as TreeTrait;
+/// use pgm_rust::graph::ops::graph::treeops::spanning_forest;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Directed, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Directed, "d", "c");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+/// let forest = spanning_forest(&g);
+/// forest.len() == 2; // true
+/// forest[1].root() == &Node::empty("c"); // true
+/// ```
+pub fn spanning_forest<N, E, G>(g: &G) -> Vec<Tree<N, E>>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N> + Clone,
+    G: GraphTrait<N, E>,
+{
+    spanning_forest_with(g, &mut UuidGenerator)
+}
+
+/// Extract a spanning forest of a graph, the identifiers of the trees are
+/// produced by `idgen` in tree order, see [spanning_forest]
+pub fn spanning_forest_with<N, E, G, I>(g: &G, idgen: &mut I) -> Vec<Tree<N, E>>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N> + Clone,
+    G: GraphTrait<N, E>,
+    I: IdGenerator,
+{
+    let vertices = g.vertices_sorted();
+    if vertices.is_empty() {
+        return Vec::new();
+    }
+    // roots are visited in identifier order, starting from the smallest one
+    let result = dfs(g, vertices[0], &EdgeNeighborhood::Both, false);
+    let roots: HashSet<&String> = result.roots.iter().collect();
+    vertices
+        .into_iter()
+        .filter(|v| roots.contains(v.id()))
+        .map(|root| dfs_tree(g, &result, root, idgen))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::traits::graph_obj::GraphObject;
+    use crate::graph::traits::tree::Tree as TreeTrait;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::edgetype::EdgeType;
+    use crate::graph::types::idgen::SequentialIdGenerator;
+    use crate::graph::types::node::Node;
+
+    fn mk_uedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Undirected, n1_id, n2_id)
+    }
+
+    /// a square a b c d with a diagonal, an edge e f and an isolated g
+    fn mk_g1() -> Graph<Node, Edge<Node>> {
+        let es = HashSet::from([
+            mk_uedge("a", "b", "e1"),
+            mk_uedge("b", "c", "e2"),
+            mk_uedge("c", "d", "e3"),
+            mk_uedge("d", "a", "e4"),
+            mk_uedge("a", "c", "e5"),
+            mk_uedge("f", "e", "e6"),
+        ]);
+        Graph::new(
+            "g1".to_string(),
+            HashMap::new(),
+            HashSet::from([Node::empty("g")]),
+            es,
+        )
+    }
+
+    #[test]
+    fn test_spanning_tree() {
+        let g = mk_g1();
+        let t = spanning_tree(&g, &Node::empty("c"));
+        assert_eq!(t.root(), &Node::empty("c"));
+        assert_eq!(t.order(), 4);
+        assert_eq!(t.size(), 3);
+        assert!(t.edges().iter().all(|e| g.edges().contains(e)));
+    }
+
+    #[test]
+    fn test_spanning_forest() {
+        let g = mk_g1();
+        let forest = spanning_forest(&g);
+        let roots: Vec<&String> = forest.iter().map(|t| t.root().id()).collect();
+        assert_eq!(roots, vec!["a", "e", "g"]);
+        let sizes: Vec<usize> = forest.iter().map(|t| t.size()).collect();
+        assert_eq!(sizes, vec![3, 1, 0]);
+        let order: usize = forest.iter().map(|t| t.order()).sum();
+        assert_eq!(order, g.order());
+        let mut idgen = SequentialIdGenerator::new("t");
+        let forest = spanning_forest_with(&g, &mut idgen);
+        let ids: Vec<&String> = forest.iter().map(|t| t.id()).collect();
+        assert_eq!(ids, vec!["t0", "t1", "t2"]);
+        let t = spanning_tree_with(&g, &Node::empty("e"), &mut idgen);
+        assert_eq!(t.id(), "t3");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_spanning_tree_missing_root() {
+        spanning_tree(&mk_g1(), &Node::empty("z"));
+    }
+}
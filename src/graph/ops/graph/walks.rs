@@ -0,0 +1,240 @@
+//! random walks on graphs
+use crate::graph::ops::edge::nodeops::get_other;
+use crate::graph::ops::graph::search::EdgeNeighborhood;
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::rng::SplitMix64;
+use std::collections::HashMap;
+
+/// output of a random walk, see [random_walk]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RandomWalk {
+    /// identifiers of the visited vertices in visiting order, starting with
+    /// the start vertex
+    pub sequence: Vec<String>,
+    /// number of times each vertex appears in the sequence
+    pub visit_counts: HashMap<String, usize>,
+}
+
+impl RandomWalk {
+    /// record a visit of `id`
+    fn visit(&mut self, id: &str) {
+        self.sequence.push(id.to_string());
+        *self.visit_counts.entry(id.to_string()).or_default() += 1;
+    }
+}
+
+/// walk `steps` steps from `start`, restarting with probability `restart_p`
+/// at each step and at dead ends when `restart_p` is positive
+fn walk<'a, N, E, G>(
+    g: &'a G,
+    start: &N,
+    edge_generator: &EdgeNeighborhood<'a, N, E>,
+    restart_p: f64,
+    steps: usize,
+    seed: u64,
+) -> RandomWalk
+where
+    N: NodeTrait + 'a,
+    E: EdgeTrait<N> + 'a,
+    G: GraphTrait<N, E>,
+{
+    let first: &N = match g.vertices().get(start) {
+        None => panic!("{start} not in {g}"),
+        Some(v) => v,
+    };
+    let mut rng = SplitMix64::new(seed);
+    let mut result = RandomWalk::default();
+    result.visit(first.id());
+    let mut current = first;
+    for _ in 0..steps {
+        if restart_p > 0.0 && rng.next_f64() < restart_p {
+            current = first;
+            result.visit(current.id());
+            continue;
+        }
+        let mut edges: Vec<&E> = edge_generator.edges(g, current).into_iter().collect();
+        if edges.is_empty() {
+            if restart_p == 0.0 {
+                break;
+            }
+            current = first;
+        } else {
+            edges.sort_by(|a, b| a.id().cmp(b.id()));
+            current = get_other(edges[rng.gen_range(edges.len())], current);
+        }
+        result.visit(current.id());
+    }
+    result
+}
+
+/// Perform a random walk
+/// # Description
+/// At each step the walk follows an edge chosen uniformly among the edges
+/// that `edge_generator` allows from the current vertex, so that directed
+/// edges can be followed along, against or regardless of their
+/// orientation. The walk stops early at a vertex without such edges. Edges
+/// are drawn from a [SplitMix64] generator seeded by `seed`, hence the same
+/// seed gives the same walk.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - start: first vertex of the walk
+/// - edge_generator: edges that can be followed from a vertex, see
+///   [EdgeNeighborhood]
+/// - steps: largest number of edges followed
+/// - seed: seed of the random number generator
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::search::EdgeNeighborhood;
+/// use pgm_rust::graph::ops::graph::walks::random_walk;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Directed, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Directed, "b", "c");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+/// let w = random_walk(&g, &Node::empty("a"), &EdgeNeighborhood::Out, 10, 42);
+/// w.sequence == vec!["a", "b", "c"]; // true: c is a dead end
+/// w.visit_counts["b"] == 1; // true
+/// ```
+pub fn random_walk<'a, N, E, G>(
+    g: &'a G,
+    start: &N,
+    edge_generator: &EdgeNeighborhood<'a, N, E>,
+    steps: usize,
+    seed: u64,
+) -> RandomWalk
+where
+    N: NodeTrait + 'a,
+    E: EdgeTrait<N> + 'a,
+    G: GraphTrait<N, E>,
+{
+    walk(g, start, edge_generator, 0.0, steps, seed)
+}
+
+/// Perform a random walk with restart
+/// # Description
+/// Like [random_walk], but at each step the walk jumps back to `start` with
+/// probability `restart_p`, and it also jumps back from dead ends. A jump
+/// counts as a step and visits `start`. Visit counts of long walks with
+/// restart approximate personalized PageRank scores of `start`.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+/// - start: first vertex of the walk, and restart vertex
+/// - edge_generator: edges that can be followed from a vertex, see
+///   [EdgeNeighborhood]
+/// - restart_p: probability of jumping back to `start` at each step, in
+///   `(0, 1]`
+/// - steps: number of steps
+/// - seed: seed of the random number generator
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::graph::search::EdgeNeighborhood;
+/// use pgm_rust::graph::ops::graph::walks::random_walk_with_restart;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Directed, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Directed, "b", "c");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+/// let w = random_walk_with_restart(&g, &Node::empty("a"), &EdgeNeighborhood::Out, 0.2, 100, 42);
+/// w.sequence.len() == 101; // true
+/// w.visit_counts["a"] > w.visit_counts["c"]; // true
+/// ```
+///
+/// # References
+/// Tong H, Faloutsos C, Pan J. Fast random walk with restart and its
+/// applications. 2006.
+pub fn random_walk_with_restart<'a, N, E, G>(
+    g: &'a G,
+    start: &N,
+    edge_generator: &EdgeNeighborhood<'a, N, E>,
+    restart_p: f64,
+    steps: usize,
+    seed: u64,
+) -> RandomWalk
+where
+    N: NodeTrait + 'a,
+    E: EdgeTrait<N> + 'a,
+    G: GraphTrait<N, E>,
+{
+    if !(restart_p > 0.0 && restart_p <= 1.0) {
+        panic!("restart probability {restart_p} must be in (0, 1]");
+    }
+    walk(g, start, edge_generator, restart_p, steps, seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::edgetype::EdgeType;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+    use std::collections::HashSet;
+
+    /// star centered on c with leaves l1 l2 l3, l1 -> c being directed
+    fn mk_star() -> Graph<Node, Edge<Node>> {
+        let es = HashSet::from([
+            Edge::empty("e1", EdgeType::Directed, "l1", "c"),
+            Edge::empty("e2", EdgeType::Undirected, "c", "l2"),
+            Edge::empty("e3", EdgeType::Undirected, "c", "l3"),
+        ]);
+        Graph::from_edgeset(es)
+    }
+
+    #[test]
+    fn test_random_walk() {
+        let g = mk_star();
+        let w = random_walk(&g, &Node::empty("c"), &EdgeNeighborhood::Out, 200, 7);
+        assert_eq!(w.sequence.len(), 201);
+        // l1 can only be reached against the orientation of e1
+        assert!(!w.visit_counts.contains_key("l1"));
+        assert_eq!(w.visit_counts["c"], 101);
+        let counts: usize = w.visit_counts.values().sum();
+        assert_eq!(counts, w.sequence.len());
+        let both = random_walk(&g, &Node::empty("c"), &EdgeNeighborhood::Both, 200, 7);
+        assert!(both.visit_counts.contains_key("l1"));
+    }
+
+    #[test]
+    fn test_random_walk_reproducible() {
+        let g = mk_star();
+        let c = Node::empty("c");
+        let w1 = random_walk(&g, &c, &EdgeNeighborhood::Both, 50, 3);
+        let w2 = random_walk(&g, &c, &EdgeNeighborhood::Both, 50, 3);
+        assert_eq!(w1, w2);
+    }
+
+    #[test]
+    fn test_random_walk_with_restart() {
+        let g = mk_star();
+        let l1 = Node::empty("l1");
+        // l1 has no incoming edge, the walk only comes back by restarting
+        let w = random_walk_with_restart(&g, &l1, &EdgeNeighborhood::Out, 0.5, 1000, 11);
+        assert_eq!(w.sequence.len(), 1001);
+        assert_eq!(w.visit_counts.len(), 4);
+        let restarts = w.sequence[1..].iter().filter(|v| *v == "l1").count();
+        assert!(restarts > 400 && restarts < 600);
+        let always = random_walk_with_restart(&g, &l1, &EdgeNeighborhood::Out, 1.0, 10, 11);
+        assert_eq!(always.visit_counts["l1"], 11);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_random_walk_with_restart_probability() {
+        let g = mk_star();
+        random_walk_with_restart(&g, &Node::empty("c"), &EdgeNeighborhood::Both, 0.0, 10, 1);
+    }
+}
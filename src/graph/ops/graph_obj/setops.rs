@@ -23,23 +23,23 @@ pub fn set_op_graph_obj_ref_set<'a, T: GraphObject>(
     let mut hset = HashSet::new();
     match set_op_kind {
         SetOpKind::Intersection => {
-            for c in a.intersection(&b) {
-                hset.insert(c.clone());
+            for c in a.intersection(b) {
+                hset.insert(*c);
             }
         }
         SetOpKind::Union => {
-            for c in a.union(&b) {
-                hset.insert(c.clone());
+            for c in a.union(b) {
+                hset.insert(*c);
             }
         }
         SetOpKind::Difference => {
-            for c in a.difference(&b) {
-                hset.insert(c.clone());
+            for c in a.difference(b) {
+                hset.insert(*c);
             }
         }
         SetOpKind::SymmetricDifference => {
-            for c in a.symmetric_difference(&b) {
-                hset.insert(c.clone());
+            for c in a.symmetric_difference(b) {
+                hset.insert(*c);
             }
         }
     }
@@ -55,25 +55,25 @@ pub fn set_op_graph_obj_set<T: GraphObject + Clone>(
     let mut hset: HashSet<T> = HashSet::new();
     match set_op_kind {
         SetOpKind::Intersection => {
-            for c in a.intersection(&b) {
+            for c in a.intersection(b) {
                 let cref: T = c.clone();
                 hset.insert(cref);
             }
         }
         SetOpKind::Union => {
-            for c in a.union(&b) {
+            for c in a.union(b) {
                 let cref: T = c.clone();
                 hset.insert(cref);
             }
         }
         SetOpKind::Difference => {
-            for c in a.difference(&b) {
+            for c in a.difference(b) {
                 let cref: T = c.clone();
                 hset.insert(cref);
             }
         }
         SetOpKind::SymmetricDifference => {
-            for c in a.symmetric_difference(&b) {
+            for c in a.symmetric_difference(b) {
                 let cref: T = c.clone();
                 hset.insert(cref);
             }
@@ -1,14 +1,17 @@
 //! Set operation functions defined on graphs
 
+use crate::graph::ops::graph::transform::map_edges;
+use crate::graph::ops::graph::transform::relabel;
 use crate::graph::traits::edge::Edge as EdgeTrait;
 use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::idgen::IdGenerator;
 use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::idgen::UuidGenerator;
 use std::collections::HashMap;
 use std::collections::HashSet;
-use uuid::Uuid;
 
 /// # Intersection Operations
-
+///
 /// ## Intersection of Two Edges
 /// ### Description
 /// Since the edges are defined as sets of vertices with two members, it is
@@ -34,7 +37,7 @@ use uuid::Uuid;
 /// fn mk_uedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
 ///     Edge::empty(e_id, EdgeType::Undirected, n1_id, n2_id)
 /// }
-
+///
 /// let e1 = mk_uedge("n1", "n2", "e1");
 /// let e2 = mk_uedge("n2", "n30", "e2");
 /// let einter = intersection_edge(&e1, &e2);
@@ -57,7 +60,7 @@ where
     hset2.insert(a2.end());
     let mut inters = HashSet::new();
     for i in hset1.intersection(&hset2) {
-        let vref: &'a N = i.clone();
+        let vref: &'a N = i;
         inters.insert(vref);
     }
     inters
@@ -147,7 +150,7 @@ where
     let mut inter = HashSet::new();
     for i in a1.intersection(&a2) {
         // instead of moving the reference we copy the reference
-        let tref: &'a E = i.clone();
+        let tref: &'a E = i;
         inter.insert(tref);
     }
     inter
@@ -202,7 +205,7 @@ pub fn intersection_nodes<'a, T: NodeTrait>(
     let mut inter = HashSet::new();
     for i in a1.intersection(&a2) {
         // instead of moving the reference we copy the reference
-        let tref: &'a T = i.clone();
+        let tref: &'a T = i;
         inter.insert(tref);
     }
     inter
@@ -232,7 +235,7 @@ pub fn intersection_nodes<'a, T: NodeTrait>(
 /// fn mk_uedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
 ///     Edge::empty(e_id, EdgeType::Undirected, n1_id, n2_id)
 /// }
-
+///
 /// fn mk_nodes(ns: Vec<&str>) -> HashSet<Node> {
 ///     let mut hs: HashSet<Node> = HashSet::new();
 ///     for n in ns {
@@ -294,6 +297,19 @@ where
     N: NodeTrait,
     E: EdgeTrait<N>,
     G: GraphTrait<N, E>,
+{
+    intersection_with(a1, a2, &mut UuidGenerator)
+}
+
+/// ## Intersection of Graphs with Identifier Strategy
+/// Same as [intersection] but the identifier of the resulting graph is
+/// produced by `idgen`.
+pub fn intersection_with<'a, N, E, G, I>(a1: &'a G, a2: &'a G, idgen: &mut I) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    I: IdGenerator,
 {
     //
     let vs1 = a1.vertices();
@@ -303,7 +319,7 @@ where
     let es2 = a2.edges();
     let vs = intersection_nodes(vs1, vs2);
     let es = intersection_edges(es1, es2);
-    let gid = Uuid::new_v4().to_string();
+    let gid = idgen.generate(&vs, &es);
     G::create_from_ref(gid, HashMap::new(), vs, es)
 }
 
@@ -359,7 +375,7 @@ pub fn union_nodes<'a, T: NodeTrait>(a1: HashSet<&'a T>, a2: HashSet<&'a T>) ->
     let mut inter = HashSet::new();
     for i in a1.union(&a2) {
         // instead of moving the reference we copy the reference
-        inter.insert(i.clone());
+        inter.insert(*i);
     }
     inter
 }
@@ -374,8 +390,8 @@ pub fn union_nodes<'a, T: NodeTrait>(a1: HashSet<&'a T>, a2: HashSet<&'a T>) ->
 /// - a1: something that implements the [Edge] trait.
 /// - a2: something that implements the [Edge] trait.
 /// - returns: a set of node references. Notice that this is not a type
-/// conserving operation. We output a specific type and not something that
-/// implements a node trait.
+///   conserving operation. We output a specific type and not something that
+///   implements a node trait.
 ///
 /// ### Example
 /// ```
@@ -385,7 +401,7 @@ pub fn union_nodes<'a, T: NodeTrait>(a1: HashSet<&'a T>, a2: HashSet<&'a T>) ->
 /// use pgm_rust::graph::types::node::Node;
 /// use pgm_rust::graph::ops::setops::union_edge;
 /// use std::collections::HashSet;
-
+///
 /// fn mk_node_refs<'a>(es: &'a Vec<Node>) -> HashSet<&'a Node> {
 ///     let mut hs = HashSet::new();
 ///     for e in es {
@@ -428,7 +444,7 @@ where
 /// - a1: set of things that implement the [Edge] trait.
 /// - a2: set of things that implement the [Edge] trait.
 /// - returns: a set of things that implement the [Edge] trait.
-/// Notice that this is a type conserving operation.
+///   Notice that this is a type conserving operation.
 ///
 /// ### Example
 /// ```
@@ -441,7 +457,7 @@ where
 /// use pgm_rust::graph::ops::setops::union_edges;
 /// use std::collections::HashSet;
 /// use std::collections::HashMap;
-
+///
 /// fn mk_uedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
 ///     Edge::empty(e_id, EdgeType::Undirected, n1_id, n2_id)
 /// }
@@ -499,7 +515,7 @@ where
     let mut inter = HashSet::new();
     for i in a1.union(&a2) {
         // instead of moving the reference we copy the reference
-        inter.insert(i.clone());
+        inter.insert(*i);
     }
     inter
 }
@@ -512,7 +528,7 @@ where
 /// - a1: something that implements the [Graph] trait
 /// - a2: something that implements the [Graph] trait
 /// - returns: a [Graph] type.
-/// Notice that this operation does not conserve types.
+///   Notice that this operation does not conserve types.
 ///
 /// ### Example
 /// ```
@@ -524,7 +540,7 @@ where
 /// use pgm_rust::graph::ops::setops::union_graph;
 /// use std::collections::HashSet;
 /// use std::collections::HashMap;
-
+///
 /// fn mk_uedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
 ///     Edge::empty(e_id, EdgeType::Undirected, n1_id, n2_id)
 /// }
@@ -587,6 +603,19 @@ where
     N: NodeTrait,
     E: EdgeTrait<N>,
     G: GraphTrait<N, E>,
+{
+    union_graph_with(a1, a2, &mut UuidGenerator)
+}
+
+/// ## Union of Graphs with Identifier Strategy
+/// Same as [union_graph] but the identifier of the resulting graph is
+/// produced by `idgen`.
+pub fn union_graph_with<'a, N, E, G, I>(a1: &'a G, a2: &'a G, idgen: &mut I) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    I: IdGenerator,
 {
     //
     let vs1 = a1.vertices();
@@ -596,10 +625,281 @@ where
     let es2 = a2.edges();
     let vs = union_nodes(vs1, vs2);
     let es = union_edges(es1, es2);
-    let gid = Uuid::new_v4().to_string();
+    let gid = idgen.generate(&vs, &es);
     G::create_from_ref(gid, HashMap::new(), vs, es)
 }
 
+/// find a fresh identifier for `id` by prefixing it with `prefix` until it
+/// no longer collides with `taken`
+fn fresh_id(id: &str, prefix: &str, taken: &HashSet<String>) -> String {
+    let mut nid = format!("{prefix}.{id}");
+    while taken.contains(&nid) {
+        nid = format!("{prefix}.{nid}");
+    }
+    nid
+}
+
+/// ## Disjoint Union of Graphs
+/// ### Description
+/// Unlike [union_graph], vertices and edges of `a2` that share an identifier
+/// with a vertex or an edge of `a1` are not conflated. Their identifiers are
+/// prefixed with the identifier of `a2` before taking the union, so the
+/// result always has `a1.order() + a2.order()` vertices and
+/// `a1.size() + a2.size()` edges.
+///
+/// ### Args
+///
+/// - a1: something that implements the [Graph] trait
+/// - a2: something that implements the [Graph] trait
+/// - returns: the union graph, the applied vertex identifier mapping and the
+///   applied edge identifier mapping of `a2`. Only renamed identifiers are
+///   present in the mappings.
+///
+/// ### Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::setops::disjoint_union;
+/// use std::collections::HashSet;
+/// use std::collections::HashMap;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "n1", "n2");
+/// let e2 = Edge::empty("e1", EdgeType::Undirected, "n1", "n3");
+/// let g1: Graph<Node, Edge<Node>> = Graph::new("g1".to_string(), HashMap::new(), HashSet::new(), HashSet::from([e1]));
+/// let g2: Graph<Node, Edge<Node>> = Graph::new("g2".to_string(), HashMap::new(), HashSet::new(), HashSet::from([e2]));
+/// let (g, node_map, edge_map) = disjoint_union(&g1, &g2);
+/// g.order() == 4; // true
+/// node_map["n1"] == "g2.n1"; // true
+/// edge_map["e1"] == "g2.e1"; // true
+/// ```
+pub fn disjoint_union<N, E, G>(
+    a1: &G,
+    a2: &G,
+) -> (G, HashMap<String, String>, HashMap<String, String>)
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    disjoint_union_with(a1, a2, &mut UuidGenerator)
+}
+
+/// ## Disjoint Union of Graphs with Identifier Strategy
+/// Same as [disjoint_union] but the identifier of the resulting graph is
+/// produced by `idgen`.
+pub fn disjoint_union_with<N, E, G, I>(
+    a1: &G,
+    a2: &G,
+    idgen: &mut I,
+) -> (G, HashMap<String, String>, HashMap<String, String>)
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    I: IdGenerator,
+{
+    let prefix = a2.id();
+    let mut taken_nodes: HashSet<String> = HashSet::new();
+    for v in a1.vertices().into_iter().chain(a2.vertices()) {
+        taken_nodes.insert(v.id().clone());
+    }
+    let mut node_map = HashMap::new();
+    let a1_nodes: HashSet<&String> = a1.vertices().into_iter().map(|v| v.id()).collect();
+    for v in a2.vertices() {
+        if a1_nodes.contains(v.id()) {
+            let nid = fresh_id(v.id(), prefix, &taken_nodes);
+            taken_nodes.insert(nid.clone());
+            node_map.insert(v.id().clone(), nid);
+        }
+    }
+    let mut taken_edges: HashSet<String> = HashSet::new();
+    for e in a1.edges().into_iter().chain(a2.edges()) {
+        taken_edges.insert(e.id().clone());
+    }
+    let mut edge_map = HashMap::new();
+    let a1_edges: HashSet<&String> = a1.edges().into_iter().map(|e| e.id()).collect();
+    for e in a2.edges() {
+        if a1_edges.contains(e.id()) {
+            let nid = fresh_id(e.id(), prefix, &taken_edges);
+            taken_edges.insert(nid.clone());
+            edge_map.insert(e.id().clone(), nid);
+        }
+    }
+    let renamed_nodes = relabel(a2, &node_map);
+    let renamed = map_edges(&renamed_nodes, |e: &E| match edge_map.get(e.id()) {
+        None => E::create(
+            e.id().clone(),
+            e.data().clone(),
+            e.start().clone(),
+            e.end().clone(),
+            e.has_type().clone(),
+        ),
+        Some(nid) => E::create(
+            nid.clone(),
+            e.data().clone(),
+            e.start().clone(),
+            e.end().clone(),
+            e.has_type().clone(),
+        ),
+    });
+    let g = union_graph_with(a1, &renamed, idgen);
+    (g, node_map, edge_map)
+}
+
+/// data map of graph objects
+type DataMap = HashMap<String, Vec<String>>;
+
+/// ## Data Conflict Resolution: Union
+/// Keys of both maps are kept. Values of a shared key are those of `left`
+/// followed by the values of `right` not already present. Meant to be
+/// passed to [merge].
+pub fn union_data(left: &DataMap, right: &DataMap) -> DataMap {
+    let mut merged = left.clone();
+    for (k, vs) in right {
+        let values = merged.entry(k.clone()).or_default();
+        for v in vs {
+            if !values.contains(v) {
+                values.push(v.clone());
+            }
+        }
+    }
+    merged
+}
+
+/// ## Data Conflict Resolution: Prefer Left
+/// Keeps the data of the first graph, see [merge].
+pub fn prefer_left(left: &DataMap, _right: &DataMap) -> DataMap {
+    left.clone()
+}
+
+/// ## Data Conflict Resolution: Prefer Right
+/// Keeps the data of the second graph, see [merge].
+pub fn prefer_right(_left: &DataMap, right: &DataMap) -> DataMap {
+    right.clone()
+}
+
+/// ## Merge of Graphs
+/// ### Description
+/// Like [union_graph], vertices and edges sharing an identifier are
+/// conflated, but their data maps are combined by the given callbacks
+/// instead of one side being dropped. Callbacks get the data of `a1` first
+/// and are called for every identifier present in both graphs. See
+/// [union_data], [prefer_left] and [prefer_right] for common policies.
+/// Edges are rebuilt on the merged vertices. Panics if two edges sharing an
+/// identifier do not join the same vertices with the same type.
+///
+/// ### Args
+///
+/// - a1: something that implements the [Graph] trait
+/// - a2: something that implements the [Graph] trait
+/// - on_node_conflict: combines the data of two vertices with the same
+///   identifier
+/// - on_edge_conflict: combines the data of two edges with the same
+///   identifier
+///
+/// ### Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+/// use pgm_rust::graph::traits::graph_obj::GraphObject;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::ops::setops::{merge, prefer_left, union_data};
+/// use std::collections::HashSet;
+/// use std::collections::HashMap;
+///
+/// let n1 = Node::new("n1".to_string(), HashMap::from([("color".to_string(), vec!["red".to_string()])]));
+/// let m1 = Node::new("n1".to_string(), HashMap::from([("size".to_string(), vec!["2".to_string()])]));
+/// let g1: Graph<Node, Edge<Node>> = Graph::new("g1".to_string(), HashMap::new(), HashSet::from([n1]), HashSet::new());
+/// let g2: Graph<Node, Edge<Node>> = Graph::new("g2".to_string(), HashMap::new(), HashSet::from([m1]), HashSet::new());
+/// let g = merge(&g1, &g2, union_data, prefer_left);
+/// let n = g.vertices().into_iter().next().unwrap();
+/// n.data().len() == 2; // true
+/// ```
+pub fn merge<N, E, G, F1, F2>(a1: &G, a2: &G, on_node_conflict: F1, on_edge_conflict: F2) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    F1: Fn(&DataMap, &DataMap) -> DataMap,
+    F2: Fn(&DataMap, &DataMap) -> DataMap,
+{
+    merge_with(
+        a1,
+        a2,
+        on_node_conflict,
+        on_edge_conflict,
+        &mut UuidGenerator,
+    )
+}
+
+/// ## Merge of Graphs with Identifier Strategy
+/// Same as [merge] but the identifier of the resulting graph is produced by
+/// `idgen`.
+pub fn merge_with<N, E, G, F1, F2, I>(
+    a1: &G,
+    a2: &G,
+    on_node_conflict: F1,
+    on_edge_conflict: F2,
+    idgen: &mut I,
+) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    F1: Fn(&DataMap, &DataMap) -> DataMap,
+    F2: Fn(&DataMap, &DataMap) -> DataMap,
+    I: IdGenerator,
+{
+    let mut nodes: HashMap<String, N> = HashMap::new();
+    for v in a1.vertices() {
+        nodes.insert(v.id().clone(), v.clone());
+    }
+    for v in a2.vertices() {
+        let merged = match nodes.get(v.id()) {
+            None => v.clone(),
+            Some(u) => N::create(v.id().clone(), on_node_conflict(u.data(), v.data())),
+        };
+        nodes.insert(v.id().clone(), merged);
+    }
+    let mut edges: HashMap<String, (&E, DataMap)> = HashMap::new();
+    for e in a1.edges() {
+        edges.insert(e.id().clone(), (e, e.data().clone()));
+    }
+    for e in a2.edges() {
+        let merged = match edges.get(e.id()) {
+            None => (e, e.data().clone()),
+            Some((f, data)) => {
+                let same_ends = f.start().id() == e.start().id() && f.end().id() == e.end().id();
+                if !same_ends || f.has_type() != e.has_type() {
+                    panic!("{f} and {e} share an identifier but not their end vertices");
+                }
+                (*f, on_edge_conflict(data, e.data()))
+            }
+        };
+        edges.insert(e.id().clone(), merged);
+    }
+    let edges: HashSet<E> = edges
+        .into_values()
+        .map(|(e, data)| {
+            E::create(
+                e.id().clone(),
+                data,
+                nodes[e.start().id()].clone(),
+                nodes[e.end().id()].clone(),
+                e.has_type().clone(),
+            )
+        })
+        .collect();
+    let nodes: HashSet<N> = nodes.into_values().collect();
+    let gid = idgen.generate(&nodes.iter().collect(), &edges.iter().collect());
+    G::create(gid, HashMap::new(), nodes, edges)
+}
+
 /// # Difference Operations
 /// ## Difference of nodes
 /// ### Description
@@ -611,7 +911,7 @@ where
 /// - a1: a set of things that implement the [Node] trait.
 /// - a2: a set of things that implement the [Node] trait.
 /// - returns: a set of things that implement the [Node] trait.
-/// Notice that this is a type conserving operation.
+///   Notice that this is a type conserving operation.
 ///
 /// ### Example
 /// ```
@@ -677,7 +977,7 @@ pub fn difference_nodes<'a, T: NodeTrait>(
     let mut inter = HashSet::new();
     for i in a1.difference(&a2) {
         // instead of moving the reference we copy the reference
-        inter.insert(i.clone());
+        inter.insert(*i);
     }
     inter
 }
@@ -692,7 +992,7 @@ pub fn difference_nodes<'a, T: NodeTrait>(
 /// - a1: something that implements the [Edge] trait
 /// - a2: something that implements the [Edge] trait
 /// - returns: a set of nodes
-/// Notice that this operation does not conserve types.
+///   Notice that this operation does not conserve types.
 ///
 /// ### Example
 ///
@@ -739,7 +1039,7 @@ pub fn difference_edge<'a, N: NodeTrait, E: EdgeTrait<N>>(a1: &'a E, a2: &'a E)
 /// - a1: set of something that implements the [Edge] trait
 /// - a2: set of something that implements the [Edge] trait
 /// - returns: a set of something that implements the [Edge] trait
-/// Notice that this operation conserve types.
+///   Notice that this operation conserve types.
 ///
 /// ### Example
 /// ```
@@ -782,7 +1082,7 @@ pub fn difference_edges<'a, N: NodeTrait, E: EdgeTrait<N>>(
     let mut inter = HashSet::new();
     for i in a1.difference(&a2) {
         // instead of moving the reference we copy the reference
-        inter.insert(i.clone());
+        inter.insert(*i);
     }
     inter
 }
@@ -791,6 +1091,17 @@ pub fn difference<'a, N: NodeTrait, E: EdgeTrait<N>, G: GraphTrait<N, E>>(
     a1: &'a G,
     a2: &'a G,
 ) -> G {
+    difference_with(a1, a2, &mut UuidGenerator)
+}
+
+/// difference of graph with an identifier strategy, see [difference]
+pub fn difference_with<'a, N, E, G, I>(a1: &'a G, a2: &'a G, idgen: &mut I) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    I: IdGenerator,
+{
     //
     let vs1 = a1.vertices();
     let vs2 = a2.vertices();
@@ -799,7 +1110,7 @@ pub fn difference<'a, N: NodeTrait, E: EdgeTrait<N>, G: GraphTrait<N, E>>(
     let es2 = a2.edges();
     let vs = difference_nodes(vs1, vs2);
     let es = difference_edges(es1, es2);
-    let gid = Uuid::new_v4().to_string();
+    let gid = idgen.generate(&vs, &es);
     G::create_from_ref(gid, HashMap::new(), vs, es)
 }
 
@@ -811,7 +1122,7 @@ pub fn symmetric_difference_edges<'a, N: NodeTrait, E: EdgeTrait<N>>(
     let mut inter = HashSet::new();
     for i in a1.symmetric_difference(&a2) {
         // instead of moving the reference we copy the reference
-        inter.insert(i.clone());
+        inter.insert(*i);
     }
     inter
 }
@@ -823,7 +1134,7 @@ pub fn symmetric_difference_node<'a, T: NodeTrait>(
     let mut inter = HashSet::new();
     for i in a1.symmetric_difference(&a2) {
         // instead of moving the reference we copy the reference
-        inter.insert(i.clone());
+        inter.insert(*i);
     }
     inter
 }
@@ -832,6 +1143,18 @@ pub fn symmetric_difference<'a, N: NodeTrait, E: EdgeTrait<N>, G: GraphTrait<N,
     a1: &'a G,
     a2: &'a G,
 ) -> G {
+    symmetric_difference_with(a1, a2, &mut UuidGenerator)
+}
+
+/// symmetric difference of graph with an identifier strategy, see
+/// [symmetric_difference]
+pub fn symmetric_difference_with<'a, N, E, G, I>(a1: &'a G, a2: &'a G, idgen: &mut I) -> G
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    I: IdGenerator,
+{
     //
     let vs1 = a1.vertices();
     let vs2 = a2.vertices();
@@ -840,12 +1163,12 @@ pub fn symmetric_difference<'a, N: NodeTrait, E: EdgeTrait<N>, G: GraphTrait<N,
     let es2 = a2.edges();
     let vs = symmetric_difference_node(vs1, vs2);
     let es = symmetric_difference_edges(es1, es2);
-    let gid = Uuid::new_v4().to_string();
+    let gid = idgen.generate(&vs, &es);
     G::create_from_ref(gid, HashMap::new(), vs, es)
 }
 
 /// contains
-
+///
 /// contains of edges
 pub fn contains_edges<'a, N: NodeTrait, E: EdgeTrait<N>>(
     a1: HashSet<&'a E>,
@@ -879,10 +1202,13 @@ where
 mod tests {
     use super::*;
     use crate::graph::traits::graph::Graph as GraphTrait;
+    use crate::graph::traits::graph_obj::GraphObject;
     use crate::graph::traits::node::VertexSet;
     use crate::graph::types::edge::Edge;
     use crate::graph::types::edgetype::EdgeType;
     use crate::graph::types::graph::Graph;
+    use crate::graph::types::idgen::ContentHashIdGenerator;
+    use crate::graph::types::idgen::SequentialIdGenerator;
     use crate::graph::types::node::Node;
     use crate::graph::types::node::Vertices;
     use std::collections::HashMap;
@@ -909,14 +1235,14 @@ mod tests {
         hs
     }
 
-    fn mk_edge_refs<'a>(es: &'a Vec<Edge<Node>>) -> HashSet<&'a Edge<Node>> {
+    fn mk_edge_refs(es: &Vec<Edge<Node>>) -> HashSet<&Edge<Node>> {
         let mut hs = HashSet::new();
         for e in es {
             hs.insert(e);
         }
         hs
     }
-    fn mk_node_refs<'a>(es: &'a Vec<Node>) -> HashSet<&'a Node> {
+    fn mk_node_refs(es: &Vec<Node>) -> HashSet<&Node> {
         let mut hs = HashSet::new();
         for e in es {
             hs.insert(e);
@@ -1068,6 +1394,45 @@ mod tests {
         assert_eq!(union_e, comp_e);
     }
 
+    #[test]
+    fn test_union_graph_with() {
+        let g1 = mk_g1();
+        let g2 = mk_g2();
+        let mut idgen = SequentialIdGenerator::new("u");
+        let u1 = union_graph_with(&g1, &g2, &mut idgen);
+        let u2 = union_graph_with(&g1, &g2, &mut idgen);
+        assert_eq!(u1.id(), "u0");
+        assert_eq!(u2.id(), "u1");
+        let c1 = union_graph_with(&g1, &g2, &mut ContentHashIdGenerator);
+        let c2 = union_graph_with(&g2, &g1, &mut ContentHashIdGenerator);
+        assert_eq!(c1.id(), c2.id());
+        let c3 = intersection_with(&g1, &g2, &mut ContentHashIdGenerator);
+        assert_ne!(c1.id(), c3.id());
+    }
+
+    #[test]
+    fn test_disjoint_union() {
+        let g1 = mk_g1();
+        let g2 = mk_g2();
+        let (g, node_map, edge_map) = disjoint_union(&g1, &g2);
+        assert_eq!(g.order(), g1.order() + g2.order());
+        assert_eq!(g.size(), g1.size() + g2.size());
+        let comp_nodes = HashMap::from([
+            ("n1".to_string(), "g2.n1".to_string()),
+            ("n2".to_string(), "g2.n2".to_string()),
+            ("n3".to_string(), "g2.n3".to_string()),
+        ]);
+        assert_eq!(node_map, comp_nodes);
+        let comp_edges = HashMap::from([
+            ("e1".to_string(), "g2.e1".to_string()),
+            ("e2".to_string(), "g2.e2".to_string()),
+            ("e3".to_string(), "g2.e3".to_string()),
+        ]);
+        assert_eq!(edge_map, comp_edges);
+        let e1 = Edge::empty("g2.e1", EdgeType::Undirected, "g2.n1", "g2.n3");
+        assert!(g.edges().contains(&e1));
+    }
+
     #[test]
     fn test_difference_edge() {
         let e2 = mk_uedge("n20", "n30", "e2");
@@ -1133,4 +1498,63 @@ mod tests {
     //    assert_eq!(difference_v, comp_v);
     //    assert_eq!(difference_e, comp_e);
     //}
+
+    fn mk_data(k: &str, vs: Vec<&str>) -> HashMap<String, Vec<String>> {
+        HashMap::from([(
+            k.to_string(),
+            vs.into_iter().map(|v| v.to_string()).collect(),
+        )])
+    }
+
+    #[test]
+    fn test_merge() {
+        let n1 = Node::new("n1".to_string(), mk_data("k", vec!["a"]));
+        let m1 = Node::new("n1".to_string(), mk_data("k", vec!["a", "b"]));
+        let e1 = Edge::new(
+            "e1".to_string(),
+            mk_data("w", vec!["1"]),
+            n1.clone(),
+            mk_node("n2"),
+            EdgeType::Undirected,
+        );
+        let f1 = Edge::new(
+            "e1".to_string(),
+            mk_data("w", vec!["2"]),
+            m1.clone(),
+            mk_node("n2"),
+            EdgeType::Undirected,
+        );
+        let f2 = mk_uedge("n2", "n3", "e2");
+        let g1 = Graph::new(
+            "g1".to_string(),
+            HashMap::new(),
+            HashSet::new(),
+            mk_edges(vec![e1]),
+        );
+        let g2 = Graph::new(
+            "g2".to_string(),
+            HashMap::new(),
+            HashSet::new(),
+            mk_edges(vec![f1, f2]),
+        );
+        let g = merge(&g1, &g2, union_data, prefer_right);
+        assert_eq!(g.order(), 3);
+        assert_eq!(g.size(), 2);
+        let merged = Node::new("n1".to_string(), mk_data("k", vec!["a", "b"]));
+        assert!(g.vertices().contains(&merged));
+        let e = g.edges().into_iter().find(|e| e.id() == "e1").unwrap();
+        assert_eq!(e.data(), &mk_data("w", vec!["2"]));
+        // the edge is rebuilt on the merged vertex
+        assert_eq!(e.start().data(), merged.data());
+        let kept = merge(&g1, &g2, prefer_left, prefer_left);
+        assert!(kept.vertices().contains(&n1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_merge_edge_ends_conflict() {
+        let g1 = Graph::from_edgeset(mk_edges(vec![mk_uedge("n1", "n2", "e1")]));
+        let g2 = Graph::from_edgeset(mk_edges(vec![mk_uedge("n1", "n3", "e1")]));
+        merge(&g1, &g2, union_data, union_data);
+    }
 }
@@ -0,0 +1,7 @@
+//! Tree operations
+
+/// traversal orders of rooted trees
+pub mod traversal;
+
+/// choosing the root of a tree
+pub mod rooting;
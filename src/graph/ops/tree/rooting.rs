@@ -0,0 +1,146 @@
+//! choosing the root of a tree
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::traits::tree::Tree as TreeTrait;
+use crate::graph::types::tree::Tree;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+
+/// Compute the parent of every vertex of a tree rooted at a given vertex
+/// # Description
+/// The tree is traversed from `root`, edge orientations being ignored, so
+/// `root` may differ from the root of `tree`. Every vertex is mapped to
+/// the identifier of its parent, the root to `None`. Panics if `root` is
+/// not a vertex of `tree`.
+///
+/// # Args
+/// - tree: something that implements [Tree] trait
+/// - root: vertex whose parent is `None`
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::types::tree::Tree;
+/// use pgm_rust::graph::ops::tree::rooting::parent_map;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+/// let t = Tree::from_graph(g, Node::empty("a"));
+/// let parents = parent_map(&t, &Node::empty("c"));
+/// parents["a"] == Some("b".to_string()); // true
+/// parents["c"] == None; // true
+/// ```
+pub fn parent_map<N, E, T>(tree: &T, root: &N) -> HashMap<String, Option<String>>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    T: TreeTrait<N, E>,
+{
+    if !tree.vertices().contains(root) {
+        panic!("{root} not in {tree}");
+    }
+    let mut adj: HashMap<&String, Vec<&String>> = HashMap::new();
+    for e in tree.edges() {
+        adj.entry(e.start().id()).or_default().push(e.end().id());
+        adj.entry(e.end().id()).or_default().push(e.start().id());
+    }
+    let mut parents: HashMap<String, Option<String>> = HashMap::from([(root.id().clone(), None)]);
+    let mut queue: VecDeque<&String> = VecDeque::from([root.id()]);
+    while let Some(v) = queue.pop_front() {
+        for w in adj.get(v).into_iter().flatten() {
+            if !parents.contains_key(*w) {
+                parents.insert((*w).clone(), Some(v.clone()));
+                queue.push_back(w);
+            }
+        }
+    }
+    parents
+}
+
+/// Root a tree at another vertex
+/// # Description
+/// The tree keeps its identifier, data, vertices and edges, only the root
+/// and the derived tree order change. This costs a single traversal of the
+/// tree. Panics if `new_root` is not a vertex of `tree`.
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::types::tree::Tree;
+/// use pgm_rust::graph::traits::tree::Tree as TreeTrait;
+/// use pgm_rust::graph::ops::tree::rooting::reroot;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1]));
+/// let t = reroot(&Tree::from_graph(g, Node::empty("a")), &Node::empty("b"));
+/// t.root() == &Node::empty("b"); // true
+/// ```
+pub fn reroot<N, E>(tree: &Tree<N, E>, new_root: &N) -> Tree<N, E>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N> + Clone,
+{
+    Tree::from_graph(tree.graph().clone(), new_root.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::traits::graph_obj::GraphObject;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::edgetype::EdgeType;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+    use std::collections::HashSet;
+
+    /// path a - b - c with d hanging from b
+    fn mk_t1() -> Tree<Node, Edge<Node>> {
+        let es = HashSet::from([
+            Edge::empty("e1", EdgeType::Undirected, "a", "b"),
+            Edge::empty("e2", EdgeType::Directed, "b", "c"),
+            Edge::empty("e3", EdgeType::Undirected, "d", "b"),
+        ]);
+        Tree::from_graph(Graph::from_edgeset(es), Node::empty("a"))
+    }
+
+    #[test]
+    fn test_parent_map() {
+        let t = mk_t1();
+        let parents = parent_map(&t, &Node::empty("d"));
+        let expected: HashMap<String, Option<String>> = HashMap::from([
+            ("d".to_string(), None),
+            ("b".to_string(), Some("d".to_string())),
+            ("a".to_string(), Some("b".to_string())),
+            ("c".to_string(), Some("b".to_string())),
+        ]);
+        assert_eq!(parents, expected);
+    }
+
+    #[test]
+    fn test_reroot() {
+        let t = mk_t1();
+        let c = Node::empty("c");
+        let r = reroot(&t, &c);
+        assert_eq!(r.id(), t.id());
+        assert_eq!(r.root(), &c);
+        assert_eq!(r.height_of(&Node::empty("a")), 2);
+        assert!(r.less_than_or_equal(&Node::empty("b"), &Node::empty("d")));
+        let parents = parent_map(&r, r.root());
+        assert_eq!(parents["b"], Some("c".to_string()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_reroot_missing_vertex() {
+        reroot(&mk_t1(), &Node::empty("z"));
+    }
+}
@@ -0,0 +1,328 @@
+//! traversal orders of rooted trees
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::traits::tree::Tree as TreeTrait;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+
+/// neighbors of every vertex of a tree ordered by identifier, edge
+/// orientations being ignored
+fn adjacency<'a, N, E, T>(tree: &'a T) -> HashMap<&'a String, Vec<&'a N>>
+where
+    N: NodeTrait + 'a,
+    E: EdgeTrait<N> + 'a,
+    T: TreeTrait<N, E>,
+{
+    let mut adj: HashMap<&String, Vec<&N>> = tree
+        .vertices()
+        .into_iter()
+        .map(|v| (v.id(), Vec::new()))
+        .collect();
+    for e in tree.edges() {
+        adj.get_mut(e.start().id()).unwrap().push(e.end());
+        adj.get_mut(e.end().id()).unwrap().push(e.start());
+    }
+    for ns in adj.values_mut() {
+        ns.sort_by(|a, b| a.id().cmp(b.id()));
+    }
+    adj
+}
+
+/// vertex of `tree` equal to `root`, panics if there is none
+fn start<'a, N, E, T>(tree: &'a T, root: &N) -> &'a N
+where
+    N: NodeTrait + 'a,
+    E: EdgeTrait<N> + 'a,
+    T: TreeTrait<N, E>,
+{
+    match tree.vertices().get(root) {
+        None => panic!("{root} not in {tree}"),
+        Some(r) => r,
+    }
+}
+
+/// children of `v` when coming from `parent`
+fn children<'a, 'b, N: NodeTrait>(
+    adj: &'b HashMap<&'a String, Vec<&'a N>>,
+    v: &N,
+    parent: Option<&'a N>,
+) -> impl DoubleEndedIterator<Item = &'a N> + 'b {
+    let parent_id = parent.map(|p| p.id().clone());
+    adj[v.id()]
+        .iter()
+        .copied()
+        .filter(move |w| Some(w.id()) != parent_id.as_ref())
+}
+
+/// Iterator over the vertices of a tree in preorder, see [preorder]
+pub struct Preorder<'a, N> {
+    adj: HashMap<&'a String, Vec<&'a N>>,
+    /// vertices to visit with their parent
+    stack: Vec<(&'a N, Option<&'a N>)>,
+}
+
+impl<'a, N: NodeTrait> Iterator for Preorder<'a, N> {
+    type Item = &'a N;
+
+    fn next(&mut self) -> Option<&'a N> {
+        let (v, parent) = self.stack.pop()?;
+        let next: Vec<(&'a N, Option<&'a N>)> = children(&self.adj, v, parent)
+            .rev()
+            .map(|w| (w, Some(v)))
+            .collect();
+        self.stack.extend(next);
+        Some(v)
+    }
+}
+
+/// Iterator over the vertices of a tree in postorder, see [postorder]
+pub struct Postorder<'a, N> {
+    adj: HashMap<&'a String, Vec<&'a N>>,
+    /// path from the start vertex with the children of each vertex that
+    /// remain to be visited
+    stack: Vec<(&'a N, Vec<&'a N>)>,
+}
+
+impl<'a, N: NodeTrait> Postorder<'a, N> {
+    /// push `v` with its children in reverse order, so that popping yields
+    /// them in identifier order
+    fn push(&mut self, v: &'a N, parent: Option<&'a N>) {
+        let remaining: Vec<&'a N> = children(&self.adj, v, parent).rev().collect();
+        self.stack.push((v, remaining));
+    }
+}
+
+impl<'a, N: NodeTrait> Iterator for Postorder<'a, N> {
+    type Item = &'a N;
+
+    fn next(&mut self) -> Option<&'a N> {
+        loop {
+            let (v, remaining) = self.stack.last_mut()?;
+            let v = *v;
+            match remaining.pop() {
+                None => {
+                    self.stack.pop();
+                    return Some(v);
+                }
+                Some(w) => self.push(w, Some(v)),
+            }
+        }
+    }
+}
+
+/// Iterator over the vertices of a tree in level order, see [level_order]
+pub struct LevelOrder<'a, N> {
+    adj: HashMap<&'a String, Vec<&'a N>>,
+    /// vertices to visit with their parent
+    queue: VecDeque<(&'a N, Option<&'a N>)>,
+}
+
+impl<'a, N: NodeTrait> Iterator for LevelOrder<'a, N> {
+    type Item = &'a N;
+
+    fn next(&mut self) -> Option<&'a N> {
+        let (v, parent) = self.queue.pop_front()?;
+        let next: Vec<(&'a N, Option<&'a N>)> = children(&self.adj, v, parent)
+            .map(|w| (w, Some(v)))
+            .collect();
+        self.queue.extend(next);
+        Some(v)
+    }
+}
+
+/// Traverse a tree in preorder
+/// # Description
+/// Every vertex comes before its descendants, children being visited in
+/// identifier order. The tree is traversed as if rooted at `root`, which
+/// may differ from the root of `tree`, edge orientations being ignored.
+/// Panics if `root` is not a vertex of `tree`.
+///
+/// # Args
+/// - tree: something that implements [Tree] trait
+/// - root: vertex the traversal starts from
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::types::tree::Tree;
+/// use pgm_rust::graph::traits::graph_obj::GraphObject;
+/// use pgm_rust::graph::ops::tree::traversal::preorder;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "a", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "b", "d");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// let t = Tree::from_graph(g, Node::empty("a"));
+/// let order: Vec<&String> = preorder(&t, &Node::empty("a")).map(|n| n.id()).collect();
+/// order == vec!["a", "b", "d", "c"]; // true
+/// ```
+pub fn preorder<'a, N, E, T>(tree: &'a T, root: &N) -> Preorder<'a, N>
+where
+    N: NodeTrait + 'a,
+    E: EdgeTrait<N> + 'a,
+    T: TreeTrait<N, E>,
+{
+    let first = start(tree, root);
+    Preorder {
+        adj: adjacency(tree),
+        stack: vec![(first, None)],
+    }
+}
+
+/// Traverse a tree in postorder
+/// # Description
+/// Every vertex comes after its descendants, children being visited in
+/// identifier order. Leaves come first and `root` last, which is the
+/// schedule of the inward pass of message passing algorithms. See
+/// [preorder] for the handling of `root`.
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::types::tree::Tree;
+/// use pgm_rust::graph::traits::graph_obj::GraphObject;
+/// use pgm_rust::graph::ops::tree::traversal::postorder;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "a", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "b", "d");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// let t = Tree::from_graph(g, Node::empty("a"));
+/// let order: Vec<&String> = postorder(&t, &Node::empty("a")).map(|n| n.id()).collect();
+/// order == vec!["d", "b", "c", "a"]; // true
+/// ```
+pub fn postorder<'a, N, E, T>(tree: &'a T, root: &N) -> Postorder<'a, N>
+where
+    N: NodeTrait + 'a,
+    E: EdgeTrait<N> + 'a,
+    T: TreeTrait<N, E>,
+{
+    let first = start(tree, root);
+    let mut it = Postorder {
+        adj: adjacency(tree),
+        stack: Vec::new(),
+    };
+    it.push(first, None);
+    it
+}
+
+/// Traverse a tree in level order
+/// # Description
+/// Vertices are visited by increasing distance to `root`, a breadth first
+/// traversal. Vertices at the same distance come in the order of their
+/// parents, siblings in identifier order. See [preorder] for the handling
+/// of `root`.
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::types::tree::Tree;
+/// use pgm_rust::graph::traits::graph_obj::GraphObject;
+/// use pgm_rust::graph::ops::tree::traversal::level_order;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "a", "c");
+/// let e3 = Edge::empty("e3", EdgeType::Undirected, "b", "d");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+/// let t = Tree::from_graph(g, Node::empty("a"));
+/// let order: Vec<&String> = level_order(&t, &Node::empty("d")).map(|n| n.id()).collect();
+/// order == vec!["d", "b", "a", "c"]; // true
+/// ```
+pub fn level_order<'a, N, E, T>(tree: &'a T, root: &N) -> LevelOrder<'a, N>
+where
+    N: NodeTrait + 'a,
+    E: EdgeTrait<N> + 'a,
+    T: TreeTrait<N, E>,
+{
+    let first = start(tree, root);
+    LevelOrder {
+        adj: adjacency(tree),
+        queue: VecDeque::from([(first, None)]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::traits::graph_obj::GraphObject;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::edgetype::EdgeType;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+    use crate::graph::types::tree::Tree;
+    use std::collections::HashSet;
+
+    /// r with children a and b, a with children c and d, d with child e
+    fn mk_t1() -> Tree<Node, Edge<Node>> {
+        let es = HashSet::from([
+            Edge::empty("e1", EdgeType::Directed, "r", "a"),
+            Edge::empty("e2", EdgeType::Directed, "b", "r"),
+            Edge::empty("e3", EdgeType::Undirected, "a", "c"),
+            Edge::empty("e4", EdgeType::Undirected, "d", "a"),
+            Edge::empty("e5", EdgeType::Directed, "d", "e"),
+        ]);
+        Tree::from_graph(Graph::from_edgeset(es), Node::empty("r"))
+    }
+
+    fn ids<'a>(it: impl Iterator<Item = &'a Node>) -> Vec<&'a str> {
+        it.map(|n| n.id().as_str()).collect()
+    }
+
+    #[test]
+    fn test_preorder() {
+        let t = mk_t1();
+        let r = Node::empty("r");
+        assert_eq!(ids(preorder(&t, &r)), vec!["r", "a", "c", "d", "e", "b"]);
+        let d = Node::empty("d");
+        assert_eq!(ids(preorder(&t, &d)), vec!["d", "a", "c", "r", "b", "e"]);
+    }
+
+    #[test]
+    fn test_postorder() {
+        let t = mk_t1();
+        let r = Node::empty("r");
+        assert_eq!(ids(postorder(&t, &r)), vec!["c", "e", "d", "a", "b", "r"]);
+        let c = Node::empty("c");
+        assert_eq!(ids(postorder(&t, &c)), vec!["e", "d", "b", "r", "a", "c"]);
+    }
+
+    #[test]
+    fn test_level_order() {
+        let t = mk_t1();
+        let r = Node::empty("r");
+        assert_eq!(ids(level_order(&t, &r)), vec!["r", "a", "b", "c", "d", "e"]);
+        let e = Node::empty("e");
+        assert_eq!(ids(level_order(&t, &e).take(2)), vec!["e", "d"]);
+    }
+
+    #[test]
+    fn test_single_vertex() {
+        let g: Graph<Node, Edge<Node>> = Graph::new(
+            "g".to_string(),
+            HashMap::new(),
+            HashSet::from([Node::empty("x")]),
+            HashSet::new(),
+        );
+        let t = Tree::from_graph(g, Node::empty("x"));
+        let x = Node::empty("x");
+        assert_eq!(ids(postorder(&t, &x)), vec!["x"]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_missing_root() {
+        preorder(&mk_t1(), &Node::empty("z"));
+    }
+}
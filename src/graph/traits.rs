@@ -23,3 +23,9 @@ pub mod tree;
 
 /// behaviors that defines a [Path]
 pub mod path;
+
+/// behaviors that defines an [IdGenerator]
+pub mod idgen;
+
+/// object safe facade over [Graph] for dynamic dispatch
+pub mod dyngraph;
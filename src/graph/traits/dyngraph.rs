@@ -0,0 +1,301 @@
+// object safe graph facade
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::graph_obj::GraphObject;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::edgetype::EdgeType;
+use crate::graph::types::graph::Graph;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+/// Edge of a [DynGraph] described by identifiers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EdgeDescription {
+    /// edge identifier
+    pub id: String,
+    /// identifier of the start vertex
+    pub start: String,
+    /// identifier of the end vertex
+    pub end: String,
+    /// edge type
+    pub edge_type: EdgeType,
+}
+
+/// Object safe view of a graph through identifiers
+/// # Description
+/// [Graph](GraphTrait) has associated constructors and generic node and edge
+/// types, so it can not be used as a trait object. This trait exposes the
+/// structure of a graph with owned identifiers only, so that graphs of
+/// different types can be stored as `Box<dyn DynGraph>`. Only the first
+/// three methods are required, the others scan [DynGraph::edge_list] and
+/// cost `O(E)` per call. Outputs are sorted by identifier.
+///
+/// It is implemented by [Graph] and by the bayesian network type. Other
+/// implementors of [Graph](GraphTrait) can be wrapped in a [DynView].
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::traits::dyngraph::DynGraph;
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::pgm::bayesnet::BayesianNetwork;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1]));
+/// let bn = BayesianNetwork::from_arcs("bn", &["a", "b"], &[("a", "b")]);
+/// let graphs: Vec<Box<dyn DynGraph>> = vec![Box::new(g), Box::new(bn)];
+/// graphs.iter().all(|g| g.neighbors_of_id("a") == vec!["b".to_string()]); // true
+/// graphs[1].predecessors_of_id("a").is_empty(); // true
+/// ```
+pub trait DynGraph {
+    /// graph identifier
+    fn graph_id(&self) -> String;
+
+    /// vertex identifiers in order
+    fn vertex_ids(&self) -> Vec<String>;
+
+    /// edges ordered by identifier
+    fn edge_list(&self) -> Vec<EdgeDescription>;
+
+    /// edge identifiers in order
+    fn edge_ids(&self) -> Vec<String> {
+        self.edge_list().into_iter().map(|e| e.id).collect()
+    }
+
+    /// number of vertices
+    fn vertex_count(&self) -> usize {
+        self.vertex_ids().len()
+    }
+
+    /// number of edges
+    fn edge_count(&self) -> usize {
+        self.edge_list().len()
+    }
+
+    /// true if the graph has a vertex with identifier `id`
+    fn contains_vertex_id(&self, id: &str) -> bool {
+        self.vertex_ids().iter().any(|v| v == id)
+    }
+
+    /// true if the graph has an edge with identifier `id`
+    fn contains_edge_id(&self, id: &str) -> bool {
+        self.edge_list().iter().any(|e| e.id == id)
+    }
+
+    /// edge with identifier `id` if any
+    fn edge_description(&self, id: &str) -> Option<EdgeDescription> {
+        self.edge_list().into_iter().find(|e| e.id == id)
+    }
+
+    /// vertices joined to `id` by an edge, whatever its orientation.
+    /// Panics if `id` is not a vertex of the graph.
+    fn neighbors_of_id(&self, id: &str) -> Vec<String> {
+        adjacent_ids(self, id, true, true)
+    }
+
+    /// vertices reachable from `id` through an outgoing directed edge or an
+    /// undirected edge. Panics if `id` is not a vertex of the graph.
+    fn successors_of_id(&self, id: &str) -> Vec<String> {
+        adjacent_ids(self, id, true, false)
+    }
+
+    /// vertices reaching `id` through an incoming directed edge or an
+    /// undirected edge. Panics if `id` is not a vertex of the graph.
+    fn predecessors_of_id(&self, id: &str) -> Vec<String> {
+        adjacent_ids(self, id, false, true)
+    }
+}
+
+/// vertices adjacent to `id` along (`forward`) or against (`backward`)
+/// edge orientations
+fn adjacent_ids<D: DynGraph + ?Sized>(
+    g: &D,
+    id: &str,
+    forward: bool,
+    backward: bool,
+) -> Vec<String> {
+    if !g.contains_vertex_id(id) {
+        panic!("{id} not contained in {}", g.graph_id());
+    }
+    let mut ids: BTreeSet<String> = BTreeSet::new();
+    for e in g.edge_list() {
+        let undirected = e.edge_type == EdgeType::Undirected;
+        if e.start == id && (forward || undirected) {
+            ids.insert(e.end.clone());
+        }
+        if e.end == id && (backward || undirected) {
+            ids.insert(e.start.clone());
+        }
+    }
+    ids.into_iter().collect()
+}
+
+/// identifiers of the vertices of any [Graph](GraphTrait)
+fn vertex_ids_of<N, E, G>(g: &G) -> Vec<String>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    g.vertices_sorted()
+        .into_iter()
+        .map(|v| v.id().clone())
+        .collect()
+}
+
+/// edge descriptions of any [Graph](GraphTrait)
+fn edge_list_of<N, E, G>(g: &G) -> Vec<EdgeDescription>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    g.edges_sorted()
+        .into_iter()
+        .map(|e| EdgeDescription {
+            id: e.id().clone(),
+            start: e.start().id().clone(),
+            end: e.end().id().clone(),
+            edge_type: e.has_type().clone(),
+        })
+        .collect()
+}
+
+/// Borrowing [DynGraph] adapter for any implementor of [Graph](GraphTrait)
+/// # Description
+/// A blanket implementation of [DynGraph] over [Graph](GraphTrait) is not
+/// possible since the node and edge types are trait parameters rather than
+/// associated types. The view fixes them.
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::traits::dyngraph::{DynGraph, DynView};
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Directed, "a", "b");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1]));
+/// let view = DynView::new(&g);
+/// let d: &dyn DynGraph = &view;
+/// d.successors_of_id("a") == vec!["b".to_string()]; // true
+/// ```
+pub struct DynView<'a, N, E, G> {
+    graph: &'a G,
+    types: PhantomData<(N, E)>,
+}
+
+impl<'a, N, E, G> DynView<'a, N, E, G>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    /// view `graph` as a [DynGraph]
+    pub fn new(graph: &'a G) -> DynView<'a, N, E, G> {
+        DynView {
+            graph,
+            types: PhantomData,
+        }
+    }
+
+    /// data of the wrapped graph
+    pub fn graph_data(&self) -> &HashMap<String, Vec<String>> {
+        self.graph.data()
+    }
+}
+
+impl<N, E, G> DynGraph for DynView<'_, N, E, G>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    fn graph_id(&self) -> String {
+        self.graph.id().clone()
+    }
+    fn vertex_ids(&self) -> Vec<String> {
+        vertex_ids_of(self.graph)
+    }
+    fn edge_list(&self) -> Vec<EdgeDescription> {
+        edge_list_of(self.graph)
+    }
+}
+
+impl<N, E> DynGraph for Graph<N, E>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N> + Clone,
+{
+    fn graph_id(&self) -> String {
+        self.id().clone()
+    }
+    fn vertex_ids(&self) -> Vec<String> {
+        vertex_ids_of(self)
+    }
+    fn edge_list(&self) -> Vec<EdgeDescription> {
+        edge_list_of(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::node::Node;
+    use std::collections::HashSet;
+
+    fn mk_g1() -> Graph<Node, Edge<Node>> {
+        let e1 = Edge::empty("e1", EdgeType::Directed, "a", "b");
+        let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+        let e3 = Edge::empty("e3", EdgeType::Directed, "d", "b");
+        let nodes = HashSet::from([Node::empty("i")]);
+        Graph::new(
+            "g1".to_string(),
+            HashMap::new(),
+            nodes,
+            HashSet::from([e1, e2, e3]),
+        )
+    }
+
+    fn ids(v: &[&str]) -> Vec<String> {
+        v.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_dyn_graph() {
+        let g = mk_g1();
+        let d: &dyn DynGraph = &g;
+        assert_eq!(d.graph_id(), "g1");
+        assert_eq!(d.vertex_ids(), ids(&["a", "b", "c", "d", "i"]));
+        assert_eq!(d.edge_ids(), ids(&["e1", "e2", "e3"]));
+        assert_eq!((d.vertex_count(), d.edge_count()), (5, 3));
+        assert_eq!(d.neighbors_of_id("b"), ids(&["a", "c", "d"]));
+        assert_eq!(d.successors_of_id("b"), ids(&["c"]));
+        assert_eq!(d.predecessors_of_id("b"), ids(&["a", "c", "d"]));
+        assert!(d.neighbors_of_id("i").is_empty());
+        assert_eq!(d.edge_description("e3").unwrap().start, "d");
+        assert!(!d.contains_edge_id("e4"));
+    }
+
+    #[test]
+    fn test_dyn_view() {
+        let g = mk_g1();
+        let view = DynView::new(&g);
+        let boxed: Vec<Box<dyn DynGraph>> = vec![Box::new(g.clone())];
+        assert_eq!(view.edge_list(), boxed[0].edge_list());
+        assert!(view.graph_data().is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_neighbors_of_missing_id() {
+        mk_g1().neighbors_of_id("z");
+    }
+}
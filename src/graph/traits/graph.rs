@@ -2,6 +2,7 @@
 use crate::graph::traits::edge::Edge;
 use crate::graph::traits::graph_obj::GraphObject;
 use crate::graph::traits::node::Node;
+use crate::graph::types::edgetype::EdgeType as EdgeKind;
 use std::collections::HashMap;
 use std::collections::HashSet;
 
@@ -32,5 +33,85 @@ pub trait Graph<NodeType: Node, EdgeType: Edge<NodeType>>: GraphObject {
         _: HashSet<&NodeType>,
         _: HashSet<&EdgeType>,
     ) -> Self;
+
+    /// vertices ordered by their identifier.
+    /// Iterating over [Graph::vertices] follows the hash order which changes
+    /// between runs. Algorithms of the crate whose output depends on the
+    /// visiting order always iterate over this instead, so that their
+    /// results are reproducible. There is no option to turn this off, the
+    /// cost of sorting is small next to the algorithms themselves.
+    fn vertices_sorted(&self) -> Vec<&NodeType> {
+        let mut vs: Vec<&NodeType> = self.vertices().into_iter().collect();
+        vs.sort_by(|a, b| a.id().cmp(b.id()));
+        vs
+    }
+
+    /// edges ordered by their identifier, then by the identifiers of their
+    /// start and end vertices, see [Graph::vertices_sorted]
+    fn edges_sorted(&self) -> Vec<&EdgeType> {
+        let mut es: Vec<&EdgeType> = self.edges().into_iter().collect();
+        es.sort_by(|a, b| {
+            (a.id(), a.start().id(), a.end().id()).cmp(&(b.id(), b.start().id(), b.end().id()))
+        });
+        es
+    }
+
+    /// number of vertices of the graph, see Diestel 2017, p. 2
+    fn order(&self) -> usize {
+        self.vertices().len()
+    }
+
+    /// number of edges of the graph, see Diestel 2017, p. 2
+    fn size(&self) -> usize {
+        self.edges().len()
+    }
+
+    /// ratio of the number of edges to the maximum number of edges the graph
+    /// can have with its current order.
+    /// If every edge is directed, the maximum is `n(n-1)`, otherwise it is
+    /// `n(n-1)/2`. Graphs with less than two vertices have zero density.
+    fn density(&self) -> f64 {
+        let n = self.order() as f64;
+        if n < 2.0 {
+            return 0.0;
+        }
+        let edges = self.edges();
+        let is_directed =
+            !edges.is_empty() && edges.iter().all(|e| e.has_type() == &EdgeKind::Directed);
+        let max_size = if is_directed {
+            n * (n - 1.0)
+        } else {
+            n * (n - 1.0) / 2.0
+        };
+        (edges.len() as f64) / max_size
+    }
+
+    /// a graph of order 0 or 1 is trivial, see Diestel 2017, p. 2
+    fn is_trivial(&self) -> bool {
+        self.order() <= 1
+    }
+
+    /// vertex whose identifier is `id` if any.
+    /// Unlike [vertex_by_id](crate::graph::ops::graph::node::vertex_by_id),
+    /// which panics, this lets callers branch on a missing vertex. The
+    /// default scans the vertices, graphs indexing them override it.
+    fn find_node(&self, id: &str) -> Option<&NodeType> {
+        self.vertices().into_iter().find(|v| v.id() == id)
+    }
+
+    /// edge whose identifier is `id` if any, see [Graph::find_node]
+    fn find_edge(&self, id: &str) -> Option<&EdgeType> {
+        self.edges().into_iter().find(|e| e.id() == id)
+    }
+
+    /// whether a vertex of the graph has the identifier `id`
+    fn contains_node_id(&self, id: &str) -> bool {
+        self.find_node(id).is_some()
+    }
+
+    /// whether an edge of the graph has the identifier `id`
+    fn contains_edge_id(&self, id: &str) -> bool {
+        self.find_edge(id).is_some()
+    }
 }
 //
@@ -0,0 +1,16 @@
+//! identifier generation for produced graphs
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use std::collections::HashSet;
+
+/// Produces identifiers for graphs created by graph operations.
+/// Operations that build a new graph, such as set operations, take an
+/// implementor of this trait in their `_with` variant. The variants without
+/// suffix use random UUIDs.
+pub trait IdGenerator {
+    /// identifier of a graph built from the given vertices and edges
+    fn generate<N, E>(&mut self, vertices: &HashSet<&N>, edges: &HashSet<&E>) -> String
+    where
+        N: NodeTrait,
+        E: EdgeTrait<N>;
+}
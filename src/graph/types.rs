@@ -14,3 +14,46 @@ pub mod graph;
 
 /// path object implements [Path] trait.
 pub mod path;
+
+/// identifier strategies implementing the [IdGenerator] trait.
+pub mod idgen;
+
+/// graph wrapper with constant time identifier lookups.
+pub mod indexed;
+
+/// proptest strategies for nodes, edges and graphs.
+#[cfg(any(test, feature = "proptest"))]
+pub mod arbitrary;
+
+/// rooted tree implements [Tree] trait.
+pub mod tree;
+
+/// graph view filtering vertices and edges with predicates.
+pub mod filtered;
+
+/// reference counted graph shared across threads.
+pub mod shared;
+
+/// compressed sparse row graph for read only analytics.
+pub mod csr;
+
+/// interned identifiers.
+pub mod idpool;
+
+/// values attached to vertices or edges by identifier.
+pub mod maps;
+
+/// nodes and edges carrying a typed payload.
+pub mod typed;
+
+/// values attached to a graph without changing it.
+pub mod annotation;
+
+/// mutable graph recording its changes in an event log.
+pub mod observable;
+
+/// mutable graph with undo, redo and checkpoints.
+pub mod versioned;
+
+/// persistent graph sharing structure between versions.
+pub mod persistent;
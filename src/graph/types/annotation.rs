@@ -0,0 +1,318 @@
+//! values attached to a graph without changing it
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::maps::{EdgeMap, NodeMap};
+use std::fmt;
+use std::io;
+use std::io::BufRead;
+use std::io::Write;
+use std::str::FromStr;
+
+const CSV_HEADER: &str = "kind,id,value";
+
+fn invalid_line(line_nb: usize, msg: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("line {}: {}", line_nb + 1, msg),
+    )
+}
+
+/// Named values of the vertices and edges of a graph
+/// # Description
+/// Algorithm outputs such as inferred marginals, colors or partitions are
+/// usually transient and do not belong in the data maps of the graph. A
+/// layer keeps them next to the graph instead: it records the identifier of
+/// the graph it was created for and stores one value per vertex or edge
+/// identifier, so the graph itself is never mutated. Several layers can
+/// describe the same graph.
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::annotation::AnnotationLayer;
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "n1", "n2");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1]));
+/// let mut colors: AnnotationLayer<u8> = AnnotationLayer::new("colors", &g);
+/// colors.set_node("n1", 0);
+/// colors.set_node("n2", 1);
+/// colors.get_node("n2") == Some(&1); // true
+/// colors.is_consistent_with(&g); // true
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnnotationLayer<T> {
+    name: String,
+    graph_id: String,
+    nodes: NodeMap<T>,
+    edges: EdgeMap<T>,
+}
+
+impl<T> AnnotationLayer<T> {
+    /// empty layer named `name` attached to `g`
+    pub fn new<N, E, G>(name: &str, g: &G) -> AnnotationLayer<T>
+    where
+        N: NodeTrait,
+        E: EdgeTrait<N>,
+        G: GraphTrait<N, E>,
+    {
+        AnnotationLayer {
+            name: name.to_string(),
+            graph_id: g.id().clone(),
+            nodes: NodeMap::new(),
+            edges: EdgeMap::new(),
+        }
+    }
+
+    /// name of the layer
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// identifier of the graph the layer is attached to
+    pub fn graph_id(&self) -> &String {
+        &self.graph_id
+    }
+
+    /// set the value of a vertex, returning the previous one if any
+    pub fn set_node(&mut self, id: &str, value: T) -> Option<T> {
+        self.nodes.insert(id, value)
+    }
+
+    /// set the value of an edge, returning the previous one if any
+    pub fn set_edge(&mut self, id: &str, value: T) -> Option<T> {
+        self.edges.insert(id, value)
+    }
+
+    /// value of a vertex
+    pub fn get_node(&self, id: &str) -> Option<&T> {
+        self.nodes.get(id)
+    }
+
+    /// value of an edge
+    pub fn get_edge(&self, id: &str) -> Option<&T> {
+        self.edges.get(id)
+    }
+
+    /// remove the value of a vertex and return it
+    pub fn remove_node(&mut self, id: &str) -> Option<T> {
+        self.nodes.remove(id)
+    }
+
+    /// remove the value of an edge and return it
+    pub fn remove_edge(&mut self, id: &str) -> Option<T> {
+        self.edges.remove(id)
+    }
+
+    /// values of the vertices
+    pub fn nodes(&self) -> &NodeMap<T> {
+        &self.nodes
+    }
+
+    /// values of the edges
+    pub fn edges(&self) -> &EdgeMap<T> {
+        &self.edges
+    }
+
+    /// whether the layer has no value
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty() && self.edges.is_empty()
+    }
+
+    /// Merge another layer of the same graph into this one
+    /// # Description
+    /// Values of `other` are added to the layer and replace the values
+    /// already set for the same identifiers. The name of the layer is kept.
+    /// Panics if the layers are attached to different graphs.
+    pub fn merge(&mut self, other: AnnotationLayer<T>) {
+        if self.graph_id != other.graph_id {
+            panic!(
+                "layer {} of graph {} can not be merged into layer {} of graph {}",
+                other.name, other.graph_id, self.name, self.graph_id
+            );
+        }
+        for (id, value) in other.nodes {
+            self.nodes.insert(&id, value);
+        }
+        for (id, value) in other.edges {
+            self.edges.insert(&id, value);
+        }
+    }
+
+    /// Whether the layer describes `g`
+    /// # Description
+    /// True if the layer is attached to `g` and every identifier it holds
+    /// is a vertex or an edge of `g`. Removing graph members does not update
+    /// the layers describing the graph, this tells whether one is stale.
+    pub fn is_consistent_with<N, E, G>(&self, g: &G) -> bool
+    where
+        N: NodeTrait,
+        E: EdgeTrait<N>,
+        G: GraphTrait<N, E>,
+    {
+        g.id() == &self.graph_id
+            && self.nodes.iter().all(|(id, _)| g.contains_node_id(id))
+            && self.edges.iter().all(|(id, _)| g.contains_edge_id(id))
+    }
+
+    /// Write the layer as comma separated values
+    /// # Description
+    /// Writes a `kind,id,value` header followed by one line per value, where
+    /// kind is `node` or `edge`. Vertex values come first, each group sorted
+    /// by identifier. The name and graph of the layer are not written.
+    pub fn to_csv<W: Write>(&self, writer: &mut W) -> io::Result<()>
+    where
+        T: fmt::Display,
+    {
+        writeln!(writer, "{CSV_HEADER}")?;
+        for (id, value) in self.nodes.sorted() {
+            writeln!(writer, "node,{id},{value}")?;
+        }
+        for (id, value) in self.edges.sorted() {
+            writeln!(writer, "edge,{id},{value}")?;
+        }
+        Ok(())
+    }
+
+    /// Read a layer written by [to_csv](AnnotationLayer::to_csv)
+    /// # Description
+    /// The layer is named `name` and attached to `g`. The header line is
+    /// optional, empty lines are skipped. Everything after the second comma
+    /// of a line is the value, so values may contain commas but identifiers
+    /// may not.
+    ///
+    /// # Errors
+    /// Returns an [io::ErrorKind::InvalidData] error if a line has less than
+    /// three fields, if its kind is neither `node` nor `edge` or if its value
+    /// can not be parsed.
+    pub fn from_csv<N, E, G, R>(name: &str, g: &G, reader: R) -> io::Result<AnnotationLayer<T>>
+    where
+        N: NodeTrait,
+        E: EdgeTrait<N>,
+        G: GraphTrait<N, E>,
+        R: BufRead,
+        T: FromStr,
+    {
+        let mut layer = AnnotationLayer::new(name, g);
+        for (line_nb, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() || (line_nb == 0 && line.trim() == CSV_HEADER) {
+                continue;
+            }
+            let fields: Vec<&str> = line.splitn(3, ',').collect();
+            if fields.len() != 3 {
+                return Err(invalid_line(line_nb, "expected kind,id,value"));
+            }
+            let value = fields[2]
+                .parse::<T>()
+                .map_err(|_| invalid_line(line_nb, "value can not be parsed"))?;
+            match fields[0].trim() {
+                "node" => layer.set_node(fields[1].trim(), value),
+                "edge" => layer.set_edge(fields[1].trim(), value),
+                _ => return Err(invalid_line(line_nb, "kind is neither node nor edge")),
+            };
+        }
+        Ok(layer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::traits::graph_obj::GraphObject;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::edgetype::EdgeType;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+    use std::collections::HashSet;
+
+    fn mk_g1() -> Graph<Node, Edge<Node>> {
+        let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+        let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+        Graph::from_edgeset(HashSet::from([e1, e2]))
+    }
+
+    #[test]
+    fn test_set_get_remove() {
+        let g = mk_g1();
+        let mut layer: AnnotationLayer<f64> = AnnotationLayer::new("marginals", &g);
+        assert!(layer.is_empty());
+        assert_eq!(layer.set_node("a", 0.25), None);
+        assert_eq!(layer.set_node("a", 0.5), Some(0.25));
+        layer.set_edge("e1", 2.0);
+        assert_eq!(layer.get_node("a"), Some(&0.5));
+        assert_eq!(layer.get_edge("e1"), Some(&2.0));
+        assert_eq!(layer.get_node("e1"), None);
+        assert_eq!(layer.remove_edge("e1"), Some(2.0));
+        assert_eq!(layer.edges().len(), 0);
+        assert_eq!(layer.name(), "marginals");
+        assert_eq!(layer.graph_id(), g.id());
+    }
+
+    #[test]
+    fn test_merge() {
+        let g = mk_g1();
+        let mut l1: AnnotationLayer<usize> = AnnotationLayer::new("parts", &g);
+        l1.set_node("a", 0);
+        l1.set_node("b", 0);
+        let mut l2: AnnotationLayer<usize> = AnnotationLayer::new("refined", &g);
+        l2.set_node("b", 1);
+        l2.set_edge("e2", 1);
+        l1.merge(l2);
+        assert_eq!(l1.name(), "parts");
+        assert_eq!(l1.get_node("a"), Some(&0));
+        assert_eq!(l1.get_node("b"), Some(&1));
+        assert_eq!(l1.get_edge("e2"), Some(&1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_merge_other_graph() {
+        let g = mk_g1();
+        let h = mk_g1();
+        let mut l1: AnnotationLayer<usize> = AnnotationLayer::new("parts", &g);
+        l1.merge(AnnotationLayer::new("parts", &h));
+    }
+
+    #[test]
+    fn test_is_consistent_with() {
+        let g = mk_g1();
+        let mut layer: AnnotationLayer<u8> = AnnotationLayer::new("colors", &g);
+        layer.set_node("a", 1);
+        layer.set_edge("e2", 3);
+        assert!(layer.is_consistent_with(&g));
+        assert!(!layer.is_consistent_with(&mk_g1()));
+        layer.set_node("z", 2);
+        assert!(!layer.is_consistent_with(&g));
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let g = mk_g1();
+        let mut layer: AnnotationLayer<String> = AnnotationLayer::new("labels", &g);
+        layer.set_node("b", "x,y".to_string());
+        layer.set_node("a", "z".to_string());
+        layer.set_edge("e1", "w".to_string());
+        let mut out: Vec<u8> = Vec::new();
+        layer.to_csv(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text, "kind,id,value\nnode,a,z\nnode,b,x,y\nedge,e1,w\n");
+        let back = AnnotationLayer::from_csv("labels", &g, text.as_bytes()).unwrap();
+        assert_eq!(back, layer);
+    }
+
+    #[test]
+    fn test_from_csv_errors() {
+        let g = mk_g1();
+        let bad_kind = AnnotationLayer::<u8>::from_csv("l", &g, "vertex,a,1\n".as_bytes());
+        assert_eq!(bad_kind.unwrap_err().kind(), io::ErrorKind::InvalidData);
+        let bad_value = AnnotationLayer::<u8>::from_csv("l", &g, "node,a,one\n".as_bytes());
+        assert!(bad_value.is_err());
+        let short = AnnotationLayer::<u8>::from_csv("l", &g, "node,a\n".as_bytes());
+        assert!(short.is_err());
+    }
+}
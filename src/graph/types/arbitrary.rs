@@ -0,0 +1,189 @@
+//! proptest strategies for graph types
+//!
+//! Available with the `proptest` feature so that downstream crates can
+//! reuse them in their own property tests. Generated graphs draw vertex
+//! identifiers from a small pool so that independently generated graphs
+//! share vertices and edges, which exercises set operations.
+use crate::graph::types::edge::Edge;
+use crate::graph::types::edgetype::EdgeType;
+use crate::graph::types::graph::Graph;
+use crate::graph::types::node::Node;
+use proptest::arbitrary::Arbitrary;
+use proptest::collection::{btree_set, hash_map, vec};
+use proptest::prelude::*;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+/// number of vertex identifiers used by [Arbitrary] graphs
+pub const DEFAULT_POOL_SIZE: usize = 12;
+
+/// maximum number of edges of [Arbitrary] graphs
+pub const DEFAULT_MAX_EDGES: usize = 24;
+
+/// strategy for small data maps of graph objects
+pub fn arb_data() -> impl Strategy<Value = HashMap<String, Vec<String>>> {
+    hash_map("[a-c]", vec("[a-z]{0,3}", 0..3), 0..3)
+}
+
+/// strategy for edge types
+pub fn arb_edge_type() -> impl Strategy<Value = EdgeType> {
+    prop_oneof![Just(EdgeType::Directed), Just(EdgeType::Undirected)]
+}
+
+/// identifier of the vertex `i` of the pool
+fn pool_id(i: usize) -> String {
+    format!("n{i}")
+}
+
+/// identifier of an edge determined by its end vertices and type, so that
+/// two generated edges with the same identifier are equal
+fn edge_id(start: &str, end: &str, edge_type: &EdgeType) -> String {
+    match edge_type {
+        EdgeType::Directed => format!("{start}->{end}"),
+        EdgeType::Undirected => format!("{start}--{end}"),
+    }
+}
+
+/// Strategy for graphs over the vertices `n0` to `n{pool_size - 1}`
+/// # Description
+/// Vertices carry no data so that a vertex identifier always denotes the
+/// same vertex. Edges are named after their end vertices and type, see
+/// [Arbitrary] for [Edge], and may be loops. Some pool vertices are
+/// isolated vertices of the graph, others are not in the graph at all.
+///
+/// # Args
+/// - pool_size: number of vertex identifiers to draw from, at least one
+/// - max_edges: maximum number of edges
+pub fn arb_graph(
+    pool_size: usize,
+    max_edges: usize,
+) -> impl Strategy<Value = Graph<Node, Edge<Node>>> {
+    let edge = (0..pool_size, 0..pool_size, arb_edge_type());
+    (
+        "[a-z]{1,8}",
+        btree_set(0..pool_size, 0..=pool_size),
+        vec(edge, 0..=max_edges),
+    )
+        .prop_map(|(gid, isolated, edges)| {
+            let nodes: HashSet<Node> = isolated
+                .into_iter()
+                .map(|i| Node::empty(&pool_id(i)))
+                .collect();
+            let edges: HashSet<Edge<Node>> = edges
+                .into_iter()
+                .map(|(s, t, edge_type)| {
+                    let (s, t) = (pool_id(s), pool_id(t));
+                    Edge::empty(&edge_id(&s, &t, &edge_type), edge_type, &s, &t)
+                })
+                .collect();
+            Graph::new(gid, HashMap::new(), nodes, edges)
+        })
+}
+
+/// Nodes have an identifier from a small pool and arbitrary data
+impl Arbitrary for Node {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Node>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (0..DEFAULT_POOL_SIZE, arb_data())
+            .prop_map(|(i, data)| Node::new(pool_id(i), data))
+            .boxed()
+    }
+}
+
+/// Edges join vertices without data and are named after their end
+/// vertices and type, e.g. `n1->n2` or `n1--n2`
+impl Arbitrary for Edge<Node> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Edge<Node>>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (
+            0..DEFAULT_POOL_SIZE,
+            0..DEFAULT_POOL_SIZE,
+            arb_edge_type(),
+            arb_data(),
+        )
+            .prop_map(|(s, t, edge_type, data)| {
+                let (s, t) = (pool_id(s), pool_id(t));
+                let id = edge_id(&s, &t, &edge_type);
+                Edge::new(id, data, Node::empty(&s), Node::empty(&t), edge_type)
+            })
+            .boxed()
+    }
+}
+
+/// Graphs from [arb_graph] with [DEFAULT_POOL_SIZE] and [DEFAULT_MAX_EDGES]
+impl Arbitrary for Graph<Node, Edge<Node>> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Graph<Node, Edge<Node>>>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        arb_graph(DEFAULT_POOL_SIZE, DEFAULT_MAX_EDGES).boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::ops::graph::search::{dfs, EdgeNeighborhood};
+    use crate::graph::ops::setops::{intersection, union_graph};
+    use crate::graph::traits::graph::Graph as GraphTrait;
+    use crate::graph::traits::graph_obj::GraphObject;
+    use std::collections::BTreeSet;
+
+    type G = Graph<Node, Edge<Node>>;
+
+    fn ids<T: GraphObject>(objects: HashSet<&T>) -> BTreeSet<String> {
+        objects.into_iter().map(|o| o.id().clone()).collect()
+    }
+
+    proptest! {
+        #[test]
+        fn prop_union_is_commutative(g1 in any::<G>(), g2 in any::<G>()) {
+            let u1 = union_graph(&g1, &g2);
+            let u2 = union_graph(&g2, &g1);
+            prop_assert_eq!(u1.vertices(), u2.vertices());
+            prop_assert_eq!(u1.edges(), u2.edges());
+        }
+
+        #[test]
+        fn prop_union_contains_both(g1 in any::<G>(), g2 in any::<G>()) {
+            let u = union_graph(&g1, &g2);
+            prop_assert!(ids(g1.edges()).is_subset(&ids(u.edges())));
+            prop_assert!(ids(g2.vertices()).is_subset(&ids(u.vertices())));
+        }
+
+        #[test]
+        fn prop_intersection_is_subset(g1 in any::<G>(), g2 in any::<G>()) {
+            let i = intersection(&g1, &g2);
+            for g in [&g1, &g2] {
+                prop_assert!(ids(i.vertices()).is_subset(&ids(g.vertices())));
+                prop_assert!(ids(i.edges()).is_subset(&ids(g.edges())));
+            }
+        }
+
+        #[test]
+        fn prop_dfs_visits_every_vertex_once(g in arb_graph(10, 20)) {
+            let vertices = g.vertices_sorted();
+            prop_assume!(!vertices.is_empty());
+            let result = dfs(&g, vertices[0], &EdgeNeighborhood::Both, false);
+            prop_assert_eq!(
+                result.first_visit_times.keys().cloned().collect::<BTreeSet<_>>(),
+                ids(g.vertices())
+            );
+            let times: BTreeSet<usize> = result.first_visit_times.values().copied().collect();
+            prop_assert_eq!(times.len(), vertices.len());
+            let visited: usize = result.components.values().map(|c| c.len()).sum();
+            prop_assert_eq!(visited, vertices.len());
+        }
+
+        #[test]
+        fn prop_edges_are_consistent(e in any::<Edge<Node>>()) {
+            let g: G = Graph::from_edgeset(HashSet::from([e.clone()]));
+            prop_assert!(g.edges().contains(&e));
+            prop_assert!(g.order() <= 2);
+        }
+    }
+}
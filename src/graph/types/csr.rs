@@ -0,0 +1,355 @@
+//! compressed sparse row graph for read only analytics
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::graph_obj::GraphObject;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::edgetype::EdgeType;
+use crate::graph::types::idpool::IdPool;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// Graph stored in contiguous vectors with integer indices
+/// # Description
+/// Vertices and edges are kept in vectors sorted by identifier, so that the
+/// index of a vertex or an edge is its rank. Adjacency is stored in
+/// compressed sparse row form: the arcs leaving vertex `i` are the slice
+/// `out_arcs[out_offsets[i]..out_offsets[i + 1]]`, each arc being a pair
+/// (neighbor index, edge index), and likewise for entering arcs. A directed
+/// edge gives an arc from its start to its end, an undirected edge gives
+/// arcs both ways. Lookups by identifier go through two [IdPool]s built
+/// once.
+///
+/// Algorithms that walk adjacency by index avoid hashing and the per call
+/// sets of [Graph], which makes this type suited to large read only
+/// graphs. It implements [Graph] too, so every generic algorithm accepts
+/// it. The structure is immutable: build a new snapshot after changes.
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::csr::CsrGraph;
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Directed, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+/// let csr = CsrGraph::from_graph(&g);
+/// let b = csr.node_index("b").unwrap();
+/// csr.out_arcs(b) == &[(2, 1)]; // true: b -> c through e2
+/// csr.in_degree(b) == 2; // true
+/// csr.order() == 3; // true
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsrGraph<N, E> {
+    graph_id: String,
+    graph_data: HashMap<String, Vec<String>>,
+    /// vertices sorted by identifier
+    nodes: Vec<N>,
+    /// edges sorted by identifier
+    edges: Vec<E>,
+    node_ids: IdPool,
+    edge_ids: IdPool,
+    out_offsets: Vec<usize>,
+    out_arcs: Vec<(usize, usize)>,
+    in_offsets: Vec<usize>,
+    in_arcs: Vec<(usize, usize)>,
+}
+
+/// offsets and arcs of a compressed sparse row adjacency
+fn compress(n: usize, mut arcs: Vec<(usize, usize, usize)>) -> (Vec<usize>, Vec<(usize, usize)>) {
+    arcs.sort();
+    let mut offsets = vec![0; n + 1];
+    for (v, _, _) in &arcs {
+        offsets[v + 1] += 1;
+    }
+    for i in 0..n {
+        offsets[i + 1] += offsets[i];
+    }
+    (offsets, arcs.into_iter().map(|(_, w, e)| (w, e)).collect())
+}
+
+impl<N, E> CsrGraph<N, E>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+{
+    /// constructor for the [CsrGraph] object. End vertices of the edges are
+    /// added to `nodes`, a vertex being kept once per identifier.
+    pub fn new(
+        graph_id: String,
+        graph_data: HashMap<String, Vec<String>>,
+        nodes: HashSet<N>,
+        edges: HashSet<E>,
+    ) -> CsrGraph<N, E> {
+        let mut by_id: BTreeMap<String, N> =
+            nodes.into_iter().map(|v| (v.id().clone(), v)).collect();
+        for e in &edges {
+            for v in [e.start(), e.end()] {
+                if !by_id.contains_key(v.id()) {
+                    by_id.insert(v.id().clone(), v.clone());
+                }
+            }
+        }
+        let nodes: Vec<N> = by_id.into_values().collect();
+        let mut edges: Vec<E> = edges.into_iter().collect();
+        edges.sort_by(|a, b| a.id().cmp(b.id()));
+        let node_ids: IdPool = nodes.iter().map(|v| v.id()).collect();
+        let edge_ids: IdPool = edges.iter().map(|e| e.id()).collect();
+        let mut outgoing: Vec<(usize, usize, usize)> = Vec::with_capacity(edges.len());
+        let mut incoming: Vec<(usize, usize, usize)> = Vec::with_capacity(edges.len());
+        for (k, e) in edges.iter().enumerate() {
+            let s = node_ids.key(e.start().id()) as usize;
+            let t = node_ids.key(e.end().id()) as usize;
+            outgoing.push((s, t, k));
+            incoming.push((t, s, k));
+            if e.has_type() == &EdgeType::Undirected && s != t {
+                outgoing.push((t, s, k));
+                incoming.push((s, t, k));
+            }
+        }
+        let (out_offsets, out_arcs) = compress(nodes.len(), outgoing);
+        let (in_offsets, in_arcs) = compress(nodes.len(), incoming);
+        CsrGraph {
+            graph_id,
+            graph_data,
+            nodes,
+            edges,
+            node_ids,
+            edge_ids,
+            out_offsets,
+            out_arcs,
+            in_offsets,
+            in_arcs,
+        }
+    }
+
+    /// snapshot of any [Graph] sharing its identifier and data
+    pub fn from_graph<G>(g: &G) -> CsrGraph<N, E>
+    where
+        E: Clone,
+        G: GraphTrait<N, E>,
+    {
+        CsrGraph::new(
+            g.id().clone(),
+            g.data().clone(),
+            g.vertices().into_iter().cloned().collect(),
+            g.edges().into_iter().cloned().collect(),
+        )
+    }
+
+    /// index of the vertex `id` if any
+    pub fn node_index(&self, id: &str) -> Option<usize> {
+        self.node_ids.get(id).map(|k| k as usize)
+    }
+
+    /// index of the edge `id` if any
+    pub fn edge_index(&self, id: &str) -> Option<usize> {
+        self.edge_ids.get(id).map(|k| k as usize)
+    }
+
+    /// vertex of index `i`, panics if out of range
+    pub fn node_at(&self, i: usize) -> &N {
+        &self.nodes[i]
+    }
+
+    /// edge of index `i`, panics if out of range
+    pub fn edge_at(&self, i: usize) -> &E {
+        &self.edges[i]
+    }
+
+    /// vertices sorted by identifier, indexed by their index
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    /// edges sorted by identifier, indexed by their index
+    pub fn edge_slice(&self) -> &[E] {
+        &self.edges
+    }
+
+    /// arcs leaving vertex `i` as (neighbor index, edge index), sorted
+    pub fn out_arcs(&self, i: usize) -> &[(usize, usize)] {
+        &self.out_arcs[self.out_offsets[i]..self.out_offsets[i + 1]]
+    }
+
+    /// arcs entering vertex `i` as (neighbor index, edge index), sorted
+    pub fn in_arcs(&self, i: usize) -> &[(usize, usize)] {
+        &self.in_arcs[self.in_offsets[i]..self.in_offsets[i + 1]]
+    }
+
+    /// number of arcs leaving vertex `i`
+    pub fn out_degree(&self, i: usize) -> usize {
+        self.out_offsets[i + 1] - self.out_offsets[i]
+    }
+
+    /// number of arcs entering vertex `i`
+    pub fn in_degree(&self, i: usize) -> usize {
+        self.in_offsets[i + 1] - self.in_offsets[i]
+    }
+}
+
+/// Csr graphs are hashed using their identifier, vertices and edges
+impl<N: NodeTrait, E: EdgeTrait<N>> Hash for CsrGraph<N, E> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.graph_id.hash(state);
+        for v in &self.nodes {
+            v.hash(state);
+        }
+        for e in &self.edges {
+            e.hash(state);
+        }
+    }
+}
+
+/// Csr graphs display their identifier when serialized to string.
+impl<N: NodeTrait, E: EdgeTrait<N>> fmt::Display for CsrGraph<N, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CsrGraph[ id: {} ]", self.graph_id)
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait<N>> GraphObject for CsrGraph<N, E> {
+    fn id(&self) -> &String {
+        &self.graph_id
+    }
+
+    fn data(&self) -> &HashMap<String, Vec<String>> {
+        &self.graph_data
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait<N> + Clone> GraphTrait<N, E> for CsrGraph<N, E> {
+    fn vertices(&self) -> HashSet<&N> {
+        self.nodes.iter().collect()
+    }
+
+    fn edges(&self) -> HashSet<&E> {
+        self.edges.iter().collect()
+    }
+
+    fn create(
+        graph_id: String,
+        graph_data: HashMap<String, Vec<String>>,
+        nodes: HashSet<N>,
+        edges: HashSet<E>,
+    ) -> CsrGraph<N, E> {
+        CsrGraph::new(graph_id, graph_data, nodes, edges)
+    }
+
+    fn create_from_ref(
+        graph_id: String,
+        graph_data: HashMap<String, Vec<String>>,
+        nodes: HashSet<&N>,
+        edges: HashSet<&E>,
+    ) -> CsrGraph<N, E> {
+        CsrGraph::new(
+            graph_id,
+            graph_data,
+            nodes.into_iter().cloned().collect(),
+            edges.into_iter().cloned().collect(),
+        )
+    }
+
+    fn vertices_sorted(&self) -> Vec<&N> {
+        self.nodes.iter().collect()
+    }
+
+    fn edges_sorted(&self) -> Vec<&E> {
+        self.edges.iter().collect()
+    }
+
+    fn order(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn size(&self) -> usize {
+        self.edges.len()
+    }
+
+    fn find_node(&self, id: &str) -> Option<&N> {
+        self.node_index(id).map(|i| self.node_at(i))
+    }
+
+    fn find_edge(&self, id: &str) -> Option<&E> {
+        self.edge_index(id).map(|i| self.edge_at(i))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::ops::graph::decompose::k_core;
+    use crate::graph::ops::graph::search::topological_sort;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+
+    fn mk_g() -> Graph<Node, Edge<Node>> {
+        let es = HashSet::from([
+            Edge::empty("e1", EdgeType::Directed, "a", "b"),
+            Edge::empty("e2", EdgeType::Directed, "a", "c"),
+            Edge::empty("e3", EdgeType::Undirected, "b", "c"),
+            Edge::empty("e4", EdgeType::Undirected, "c", "c"),
+        ]);
+        Graph::from_edge_node_set(es, HashSet::from([Node::empty("d")]))
+    }
+
+    #[test]
+    fn test_csr_graph_matches_graph() {
+        let g = mk_g();
+        let csr = CsrGraph::from_graph(&g);
+        assert_eq!(csr.id(), g.id());
+        assert_eq!(csr.vertices(), g.vertices());
+        assert_eq!(csr.edges(), g.edges());
+        assert_eq!(csr.vertices_sorted(), g.vertices_sorted());
+        assert_eq!(csr.edges_sorted(), g.edges_sorted());
+        for (i, v) in csr.nodes().iter().enumerate() {
+            assert_eq!(csr.node_index(v.id()), Some(i));
+        }
+        assert_eq!(csr.edge_at(csr.edge_index("e3").unwrap()).id(), "e3");
+        assert_eq!(csr.node_index("z"), None);
+        assert_eq!(csr.find_node("d"), g.find_node("d"));
+        assert_eq!(csr.find_edge("e4"), g.find_edge("e4"));
+        assert!(!csr.contains_edge_id("e5"));
+    }
+
+    #[test]
+    fn test_csr_graph_arcs() {
+        let csr = CsrGraph::from_graph(&mk_g());
+        let (a, b, c, d) = (0, 1, 2, 3);
+        assert_eq!(csr.out_arcs(a), &[(b, 0), (c, 1)]);
+        assert_eq!(csr.in_arcs(a), &[]);
+        assert_eq!(csr.out_arcs(b), &[(c, 2)]);
+        assert_eq!(csr.in_arcs(b), &[(a, 0), (c, 2)]);
+        // the undirected loop gives a single arc
+        assert_eq!(csr.out_arcs(c), &[(b, 2), (c, 3)]);
+        assert_eq!(csr.in_degree(c), 3);
+        assert_eq!(csr.out_degree(d), 0);
+    }
+
+    #[test]
+    fn test_csr_graph_in_algorithms() {
+        let es = HashSet::from([
+            Edge::empty("e1", EdgeType::Directed, "a", "b"),
+            Edge::empty("e2", EdgeType::Directed, "b", "c"),
+        ]);
+        let dag: Graph<Node, Edge<Node>> = Graph::from_edgeset(es);
+        let csr = CsrGraph::from_graph(&dag);
+        let order: Vec<&String> = topological_sort(&csr)
+            .unwrap()
+            .into_iter()
+            .map(|v| v.id())
+            .collect();
+        assert_eq!(order, vec!["a", "b", "c"]);
+        let core: CsrGraph<Node, Edge<Node>> = k_core(&CsrGraph::from_graph(&mk_g()), 2);
+        assert_eq!(core.order(), 3);
+        assert_eq!(core.out_degree(core.node_index("a").unwrap()), 2);
+    }
+}
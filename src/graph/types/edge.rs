@@ -42,7 +42,7 @@ impl<N: NodeTrait, E: EdgeTrait<N> + Clone> EdgeSetTrait<N, E> for Edges<N, E> {
     fn members(&self) -> HashSet<&E> {
         let mut es: HashSet<&E> = HashSet::new();
         for e in &self.edge_set {
-            es.insert(&e);
+            es.insert(e);
         }
         es
     }
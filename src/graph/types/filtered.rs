@@ -0,0 +1,329 @@
+//! graph view hiding vertices and edges rejected by predicates
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::graph_obj::GraphObject;
+use crate::graph::traits::node::Node as NodeTrait;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+/// graph seen through the view, borrowed when the view wraps an existing
+/// graph and owned when the view is built by [GraphTrait::create]
+enum Source<'a, G> {
+    Borrowed(&'a G),
+    Owned(G),
+}
+
+/// Graph view keeping only the vertices and edges accepted by predicates
+/// # Description
+/// The wrapped graph is not copied, vertices and edges are filtered each
+/// time they are requested. An edge is kept only if the edge predicate
+/// accepts it and both of its end vertices are kept, so that the view is
+/// always a valid subgraph of the wrapped graph. Since the view implements
+/// the [Graph](GraphTrait) trait, graph algorithms can run on "the graph
+/// without some edges" without materializing it. Use
+/// [FilteredGraph::to_graph] to get an owned copy.
+///
+/// A view built through [GraphTrait::create] owns its graph and keeps every
+/// vertex and edge.
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::types::filtered::FilteredGraph;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+/// use pgm_rust::graph::traits::graph_obj::GraphObject;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "n1", "n2");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "n2", "n3");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+/// let view = FilteredGraph::new(&g, |_: &Node| true, |e: &Edge<Node>| e.id() != "e2");
+/// view.order() == 3; // true
+/// view.size() == 1; // true
+/// let no_n1 = FilteredGraph::new(&g, |n: &Node| n.id() != "n1", |_: &Edge<Node>| true);
+/// no_n1.size() == 1; // true: e1 loses an end vertex
+/// ```
+pub struct FilteredGraph<'a, N, E, G, FN, FE> {
+    source: Source<'a, G>,
+    node_filter: Option<FN>,
+    edge_filter: Option<FE>,
+    marker: PhantomData<(N, E)>,
+}
+
+impl<'a, N, E, G, FN, FE> FilteredGraph<'a, N, E, G, FN, FE>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    FN: Fn(&N) -> bool,
+    FE: Fn(&E) -> bool,
+{
+    /// view of `graph` keeping the vertices accepted by `node_filter` and
+    /// the edges accepted by `edge_filter`
+    pub fn new(
+        graph: &'a G,
+        node_filter: FN,
+        edge_filter: FE,
+    ) -> FilteredGraph<'a, N, E, G, FN, FE> {
+        FilteredGraph {
+            source: Source::Borrowed(graph),
+            node_filter: Some(node_filter),
+            edge_filter: Some(edge_filter),
+            marker: PhantomData,
+        }
+    }
+
+    /// wrapped graph, including filtered out vertices and edges
+    pub fn graph(&self) -> &G {
+        match &self.source {
+            Source::Borrowed(g) => g,
+            Source::Owned(g) => g,
+        }
+    }
+
+    /// whether the view keeps the vertex `n`
+    pub fn keeps_vertex(&self, n: &N) -> bool {
+        self.node_filter.as_ref().is_none_or(|f| f(n))
+    }
+
+    /// whether the view keeps the edge `e`
+    pub fn keeps_edge(&self, e: &E) -> bool {
+        self.edge_filter.as_ref().is_none_or(|f| f(e))
+            && self.keeps_vertex(e.start())
+            && self.keeps_vertex(e.end())
+    }
+
+    /// copy of the view as a graph of the wrapped type, keeping the
+    /// identifier and data of the wrapped graph
+    pub fn to_graph(&self) -> G {
+        let g = self.graph();
+        G::create_from_ref(
+            g.id().clone(),
+            g.data().clone(),
+            self.vertices(),
+            self.edges(),
+        )
+    }
+}
+
+/// Filtered graphs are hashed using the identifiers of their graph and of
+/// their visible vertices and edges
+impl<N, E, G, FN, FE> Hash for FilteredGraph<'_, N, E, G, FN, FE>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    FN: Fn(&N) -> bool,
+    FE: Fn(&E) -> bool,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id().hash(state);
+        for v in self.vertices_sorted() {
+            v.id().hash(state);
+        }
+        for e in self.edges_sorted() {
+            e.id().hash(state);
+        }
+    }
+}
+
+/// Filtered graphs are equal if they have the same identifier and show the
+/// same vertices and edges
+impl<N, E, G, FN, FE> PartialEq for FilteredGraph<'_, N, E, G, FN, FE>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    FN: Fn(&N) -> bool,
+    FE: Fn(&E) -> bool,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.id() == other.id()
+            && self.vertices() == other.vertices()
+            && self.edges() == other.edges()
+    }
+}
+
+impl<N, E, G, FN, FE> Eq for FilteredGraph<'_, N, E, G, FN, FE>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    FN: Fn(&N) -> bool,
+    FE: Fn(&E) -> bool,
+{
+}
+
+/// Filtered graphs display the identifier of their graph when serialized to
+/// string.
+impl<N, E, G, FN, FE> fmt::Display for FilteredGraph<'_, N, E, G, FN, FE>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    FN: Fn(&N) -> bool,
+    FE: Fn(&E) -> bool,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "FilteredGraph[ id: {} ]", self.id())
+    }
+}
+
+impl<N, E, G, FN, FE> GraphObject for FilteredGraph<'_, N, E, G, FN, FE>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    FN: Fn(&N) -> bool,
+    FE: Fn(&E) -> bool,
+{
+    fn id(&self) -> &String {
+        self.graph().id()
+    }
+
+    fn data(&self) -> &HashMap<String, Vec<String>> {
+        self.graph().data()
+    }
+}
+
+impl<N, E, G, FN, FE> GraphTrait<N, E> for FilteredGraph<'_, N, E, G, FN, FE>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+    FN: Fn(&N) -> bool,
+    FE: Fn(&E) -> bool,
+{
+    fn vertices(&self) -> HashSet<&N> {
+        self.graph()
+            .vertices()
+            .into_iter()
+            .filter(|v| self.keeps_vertex(v))
+            .collect()
+    }
+
+    fn edges(&self) -> HashSet<&E> {
+        self.graph()
+            .edges()
+            .into_iter()
+            .filter(|e| self.keeps_edge(e))
+            .collect()
+    }
+
+    fn create(
+        graph_id: String,
+        graph_data: HashMap<String, Vec<String>>,
+        nodes: HashSet<N>,
+        edges: HashSet<E>,
+    ) -> Self {
+        FilteredGraph {
+            source: Source::Owned(G::create(graph_id, graph_data, nodes, edges)),
+            node_filter: None,
+            edge_filter: None,
+            marker: PhantomData,
+        }
+    }
+
+    fn create_from_ref(
+        graph_id: String,
+        graph_data: HashMap<String, Vec<String>>,
+        nodes: HashSet<&N>,
+        edges: HashSet<&E>,
+    ) -> Self {
+        FilteredGraph {
+            source: Source::Owned(G::create_from_ref(graph_id, graph_data, nodes, edges)),
+            node_filter: None,
+            edge_filter: None,
+            marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::ops::graph::search::dfs;
+    use crate::graph::ops::graph::search::EdgeNeighborhood;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::edgetype::EdgeType;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+
+    fn mk_uedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Undirected, n1_id, n2_id)
+    }
+
+    /// path a - b - c - d with an isolated e
+    fn mk_g() -> Graph<Node, Edge<Node>> {
+        let es = HashSet::from([
+            mk_uedge("a", "b", "e1"),
+            mk_uedge("b", "c", "e2"),
+            mk_uedge("c", "d", "e3"),
+        ]);
+        Graph::from_edge_node_set(es, HashSet::from([Node::empty("e")]))
+    }
+
+    #[test]
+    fn test_filtered_edges() {
+        let g = mk_g();
+        let view = FilteredGraph::new(&g, |_: &Node| true, |e: &Edge<Node>| e.id() != "e2");
+        assert_eq!(view.order(), 5);
+        assert_eq!(view.size(), 2);
+        assert_eq!(view.id(), g.id());
+        // algorithms see two components where the wrapped graph has one
+        let r = dfs(&view, &Node::empty("a"), &EdgeNeighborhood::Both, false);
+        assert_eq!(r.roots, vec!["a", "c", "e"]);
+        assert_eq!(g.size(), 3);
+    }
+
+    #[test]
+    fn test_filtered_vertices() {
+        let g = mk_g();
+        let view = FilteredGraph::new(&g, |n: &Node| n.id() != "c", |_: &Edge<Node>| true);
+        assert_eq!(view.order(), 4);
+        let ids: HashSet<&String> = view.edges().into_iter().map(|e| e.id()).collect();
+        assert_eq!(ids, HashSet::from([&"e1".to_string()]));
+        assert!(!view.keeps_edge(&mk_uedge("b", "c", "e2")));
+    }
+
+    #[test]
+    fn test_filtered_to_graph() {
+        let g = mk_g();
+        let view = FilteredGraph::new(
+            &g,
+            |n: &Node| n.id() != "e",
+            |e: &Edge<Node>| e.id() == "e1",
+        );
+        let copy = view.to_graph();
+        assert_eq!(copy.id(), g.id());
+        assert_eq!(copy.order(), 4);
+        assert_eq!(copy.size(), 1);
+    }
+
+    #[test]
+    fn test_filtered_create_keeps_everything() {
+        type View<'a> = FilteredGraph<
+            'a,
+            Node,
+            Edge<Node>,
+            Graph<Node, Edge<Node>>,
+            fn(&Node) -> bool,
+            fn(&Edge<Node>) -> bool,
+        >;
+        let v = View::create(
+            "v".to_string(),
+            HashMap::new(),
+            HashSet::from([Node::empty("a")]),
+            HashSet::from([mk_uedge("a", "b", "e1")]),
+        );
+        assert_eq!(v.order(), 2);
+        assert_eq!(v.size(), 1);
+        assert_eq!(v.to_string(), "FilteredGraph[ id: v ]");
+    }
+}
@@ -16,6 +16,10 @@ use std::hash::{Hash, Hasher};
 /// Basic graph type which implements the relative [trait](GraphTrait)
 /// Formally defined as a set with two members which are also sets,
 /// see Diestel 2017, p. 2
+///
+/// A graph holds no interior mutability: it is `Send` and `Sync` whenever
+/// its vertex and edge types are, and can be read from several threads at
+/// once, see [ArcGraph](crate::graph::types::shared::ArcGraph).
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct Graph<NodeType: NodeTrait, EdgeType: EdgeTrait<NodeType>> {
     /// graph identifier required for [GraphObject] trait
@@ -110,7 +114,7 @@ fn get_vertices<T: NodeTrait, E: EdgeTrait<T>>(
     }
     let mut mset: HashSet<T> = HashSet::new();
     for n in nodes {
-        if nset.contains(&n) == false {
+        if !nset.contains(&n) {
             mset.insert(n);
         }
     }
@@ -130,7 +134,7 @@ fn get_vertices_from_refset<T: NodeTrait, E: EdgeTrait<T> + Clone>(
     }
     let mut mset: HashSet<T> = HashSet::new();
     for n in nodes {
-        if nset.contains(n) == false {
+        if !nset.contains(n) {
             mset.insert(n.clone());
         }
     }
@@ -306,6 +310,74 @@ mod tests {
         //
         assert_eq!(edges, es);
     }
+    #[test]
+    fn test_order() {
+        let g = mk_g("g1");
+        assert_eq!(g.order(), 4);
+    }
+
+    #[test]
+    fn test_vertices_sorted() {
+        let g = mk_g("g1");
+        let ids: Vec<&String> = g.vertices_sorted().into_iter().map(|v| v.id()).collect();
+        assert_eq!(ids, vec!["n1", "n2", "n3", "n4"]);
+    }
+
+    #[test]
+    fn test_edges_sorted() {
+        let g = mk_g("g1");
+        let ids: Vec<&String> = g.edges_sorted().into_iter().map(|e| e.id()).collect();
+        assert_eq!(ids, vec!["e1", "e2"]);
+    }
+
+    #[test]
+    fn test_size() {
+        let g = mk_g("g1");
+        assert_eq!(g.size(), 2);
+    }
+
+    #[test]
+    fn test_density() {
+        let g = mk_g("g1");
+        // 2 edges out of 4 * 3 / 2 possible
+        assert_eq!(g.density(), 2.0 / 6.0);
+    }
+
+    #[test]
+    fn test_density_directed() {
+        let n1 = mk_node("n1");
+        let n2 = mk_node("n2");
+        let e1 = Edge::directed("e1".to_string(), n1, n2, HashMap::new());
+        let g = Graph::from_edgeset(HashSet::from([e1]));
+        assert_eq!(g.density(), 0.5);
+    }
+
+    #[test]
+    fn test_is_trivial() {
+        let g = mk_g("g1");
+        assert!(!g.is_trivial());
+        let g2: Graph<Node, Edge<Node>> = Graph::new(
+            "g2".to_string(),
+            HashMap::new(),
+            mk_nodes(vec!["n1"]),
+            HashSet::new(),
+        );
+        assert!(g2.is_trivial());
+    }
+
+    #[test]
+    fn test_find_node_and_edge() {
+        let g = mk_g("g1");
+        assert_eq!(g.find_node("n1"), Some(&mk_node("n1")));
+        assert_eq!(g.find_node("n9"), None);
+        assert_eq!(g.find_edge("e2").map(|e| e.id().as_str()), Some("e2"));
+        assert!(g.find_edge("n1").is_none());
+        assert!(g.contains_node_id("n4"));
+        assert!(!g.contains_node_id("e1"));
+        assert!(g.contains_edge_id("e1"));
+        assert!(!g.contains_edge_id("e9"));
+    }
+
     #[test]
     fn test_from_graphish_ref() {
         let g1 = mk_g("g1");
@@ -0,0 +1,198 @@
+//! identifier generation strategies
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::idgen::IdGenerator;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::edgetype::EdgeType;
+use crate::rng::SplitMix64;
+use std::collections::HashSet;
+use uuid::Builder;
+use uuid::Uuid;
+
+/// Random UUID v4 identifiers. This is the default strategy.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UuidGenerator;
+
+impl IdGenerator for UuidGenerator {
+    fn generate<N, E>(&mut self, _: &HashSet<&N>, _: &HashSet<&E>) -> String
+    where
+        N: NodeTrait,
+        E: EdgeTrait<N>,
+    {
+        Uuid::new_v4().to_string()
+    }
+}
+
+/// Sequential identifiers `prefix0`, `prefix1`, ...
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SequentialIdGenerator {
+    prefix: String,
+    counter: usize,
+}
+
+impl SequentialIdGenerator {
+    /// create a generator whose first identifier is `prefix0`
+    /// # Example
+    /// ```
+    /// use pgm_rust::graph::types::idgen::SequentialIdGenerator;
+    /// use pgm_rust::graph::traits::idgen::IdGenerator;
+    /// use pgm_rust::graph::types::edge::Edge;
+    /// use pgm_rust::graph::types::node::Node;
+    /// use std::collections::HashSet;
+    ///
+    /// let mut idgen = SequentialIdGenerator::new("g");
+    /// let (vs, es): (HashSet<&Node>, HashSet<&Edge<Node>>) = (HashSet::new(), HashSet::new());
+    /// idgen.generate(&vs, &es) == "g0"; // true
+    /// idgen.generate(&vs, &es) == "g1"; // true
+    /// ```
+    pub fn new(prefix: &str) -> SequentialIdGenerator {
+        SequentialIdGenerator {
+            prefix: prefix.to_string(),
+            counter: 0,
+        }
+    }
+}
+
+impl IdGenerator for SequentialIdGenerator {
+    fn generate<N, E>(&mut self, _: &HashSet<&N>, _: &HashSet<&E>) -> String
+    where
+        N: NodeTrait,
+        E: EdgeTrait<N>,
+    {
+        let id = format!("{}{}", self.prefix, self.counter);
+        self.counter += 1;
+        id
+    }
+}
+
+/// UUID v4 shaped identifiers drawn from a seeded generator, so that the
+/// same seed yields the same sequence of identifiers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeededIdGenerator {
+    rng: SplitMix64,
+}
+
+impl SeededIdGenerator {
+    /// create a generator from a seed
+    pub fn new(seed: u64) -> SeededIdGenerator {
+        SeededIdGenerator {
+            rng: SplitMix64::new(seed),
+        }
+    }
+}
+
+impl IdGenerator for SeededIdGenerator {
+    fn generate<N, E>(&mut self, _: &HashSet<&N>, _: &HashSet<&E>) -> String
+    where
+        N: NodeTrait,
+        E: EdgeTrait<N>,
+    {
+        let mut bytes = [0u8; 16];
+        bytes[..8].copy_from_slice(&self.rng.next_u64().to_le_bytes());
+        bytes[8..].copy_from_slice(&self.rng.next_u64().to_le_bytes());
+        Builder::from_random_bytes(bytes).into_uuid().to_string()
+    }
+}
+
+/// Identifiers derived from the content of the graph.
+/// Graphs with the same vertex identifiers and the same edges, that is the
+/// same edge identifiers, end vertices and edge types, get the same
+/// identifier. Data is not taken into account. The identifier is the
+/// 64 bit FNV-1a hash of the content, which does not depend on the platform
+/// or the compiler version.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContentHashIdGenerator;
+
+pub(crate) const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// fold `bytes` into the 64 bit FNV-1a hash `hash`
+pub(crate) fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    let mut h = hash;
+    for b in bytes {
+        h ^= *b as u64;
+        h = h.wrapping_mul(FNV_PRIME);
+    }
+    h
+}
+
+impl IdGenerator for ContentHashIdGenerator {
+    fn generate<N, E>(&mut self, vertices: &HashSet<&N>, edges: &HashSet<&E>) -> String
+    where
+        N: NodeTrait,
+        E: EdgeTrait<N>,
+    {
+        let mut vids: Vec<&String> = vertices.iter().map(|v| v.id()).collect();
+        vids.sort();
+        let mut eids: Vec<String> = edges
+            .iter()
+            .map(|e| {
+                let kind = match e.has_type() {
+                    EdgeType::Directed => "->",
+                    EdgeType::Undirected => "--",
+                };
+                format!("{}:{}{}{}", e.id(), e.start().id(), kind, e.end().id())
+            })
+            .collect();
+        eids.sort();
+        let mut h = FNV_OFFSET;
+        for v in vids {
+            h = fnv1a(h, v.as_bytes());
+            h = fnv1a(h, &[0]);
+        }
+        h = fnv1a(h, &[1]);
+        for e in eids {
+            h = fnv1a(h, e.as_bytes());
+            h = fnv1a(h, &[0]);
+        }
+        format!("{h:016x}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::node::Node;
+
+    fn mk_content() -> (Vec<Node>, Vec<Edge<Node>>) {
+        let nodes = vec![Node::empty("n1"), Node::empty("n2")];
+        let edges = vec![Edge::empty("e1", EdgeType::Undirected, "n1", "n2")];
+        (nodes, edges)
+    }
+
+    #[test]
+    fn test_sequential() {
+        let (nodes, edges) = mk_content();
+        let vs: HashSet<&Node> = nodes.iter().collect();
+        let es: HashSet<&Edge<Node>> = edges.iter().collect();
+        let mut idgen = SequentialIdGenerator::new("g");
+        assert_eq!(idgen.generate(&vs, &es), "g0");
+        assert_eq!(idgen.generate(&vs, &es), "g1");
+    }
+
+    #[test]
+    fn test_seeded() {
+        let (nodes, edges) = mk_content();
+        let vs: HashSet<&Node> = nodes.iter().collect();
+        let es: HashSet<&Edge<Node>> = edges.iter().collect();
+        let mut g1 = SeededIdGenerator::new(11);
+        let mut g2 = SeededIdGenerator::new(11);
+        let id1 = g1.generate(&vs, &es);
+        assert_eq!(id1, g2.generate(&vs, &es));
+        assert_ne!(id1, g1.generate(&vs, &es));
+        assert!(Uuid::parse_str(&id1).is_ok());
+    }
+
+    #[test]
+    fn test_content_hash() {
+        let (nodes, edges) = mk_content();
+        let vs: HashSet<&Node> = nodes.iter().collect();
+        let es: HashSet<&Edge<Node>> = edges.iter().collect();
+        let mut idgen = ContentHashIdGenerator;
+        let id1 = idgen.generate(&vs, &es);
+        assert_eq!(id1, idgen.generate(&vs, &es));
+        let other = [Edge::empty("e1", EdgeType::Directed, "n1", "n2")];
+        let es2: HashSet<&Edge<Node>> = other.iter().collect();
+        assert_ne!(id1, idgen.generate(&vs, &es2));
+    }
+}
@@ -0,0 +1,123 @@
+//! interned identifiers
+use std::collections::HashMap;
+
+/// Bidirectional mapping between identifiers and dense integers
+/// # Description
+/// Identifiers are strings throughout the public interface of the crate.
+/// Algorithms that store per vertex state keyed by identifier pay for
+/// hashing and cloning a string at every access. Interning every identifier
+/// once lets them key their state by a `u32` instead, usually a vector
+/// index, and turn back to strings only when building their output.
+/// Integers are given in interning order, starting from 0.
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::idpool::IdPool;
+///
+/// let mut pool = IdPool::new();
+/// let a = pool.intern("a");
+/// let b = pool.intern("b");
+/// pool.intern("a") == a; // true
+/// pool.resolve(b) == "b"; // true
+/// pool.get("c").is_none(); // true
+/// pool.len() == 2; // true
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IdPool {
+    ids: Vec<String>,
+    index: HashMap<String, u32>,
+}
+
+impl IdPool {
+    /// empty pool
+    pub fn new() -> IdPool {
+        IdPool::default()
+    }
+
+    /// integer of `id`, interning it if needed. Panics past `u32::MAX`
+    /// identifiers.
+    pub fn intern(&mut self, id: &str) -> u32 {
+        if let Some(k) = self.index.get(id) {
+            return *k;
+        }
+        let k = match u32::try_from(self.ids.len()) {
+            Err(_) => panic!("more than {} identifiers interned", u32::MAX),
+            Ok(k) => k,
+        };
+        self.ids.push(id.to_string());
+        self.index.insert(id.to_string(), k);
+        k
+    }
+
+    /// integer of `id` if it is interned
+    pub fn get(&self, id: &str) -> Option<u32> {
+        self.index.get(id).copied()
+    }
+
+    /// integer of `id`, panics if it is not interned
+    pub fn key(&self, id: &str) -> u32 {
+        match self.get(id) {
+            None => panic!("{id} is not interned"),
+            Some(k) => k,
+        }
+    }
+
+    /// identifier of integer `k`, panics if no identifier has it
+    pub fn resolve(&self, k: u32) -> &String {
+        &self.ids[k as usize]
+    }
+
+    /// whether `id` is interned
+    pub fn contains(&self, id: &str) -> bool {
+        self.index.contains_key(id)
+    }
+
+    /// number of interned identifiers
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// whether no identifier is interned
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// interned identifiers ordered by their integer
+    pub fn ids(&self) -> &[String] {
+        &self.ids
+    }
+}
+
+/// Identifiers are interned in iteration order
+impl<S: AsRef<str>> FromIterator<S> for IdPool {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        let mut pool = IdPool::new();
+        for id in iter {
+            pool.intern(id.as_ref());
+        }
+        pool
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_id_pool() {
+        let pool: IdPool = ["b", "a", "b", "c"].into_iter().collect();
+        assert_eq!(pool.len(), 3);
+        assert_eq!(pool.ids(), &["b", "a", "c"]);
+        assert_eq!(pool.key("c"), 2);
+        assert_eq!(pool.resolve(1), "a");
+        assert!(pool.contains("a"));
+        assert!(!pool.contains("d"));
+        assert!(IdPool::new().is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_id_pool_missing_key() {
+        IdPool::new().key("a");
+    }
+}
@@ -0,0 +1,171 @@
+//! graph wrapper indexing vertices and edges by identifier
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::idpool::IdPool;
+
+/// Graph with its vertices and edges indexed by identifier
+/// # Description
+/// [vertex_by_id] and [edge_by_id] go through the vertex or edge set of
+/// the graph on every call, which costs `O(V)` or `O(E)`. This wrapper
+/// builds both identifier maps once, so that repeated lookups are
+/// constant time. The wrapped graph is borrowed, hence it cannot change
+/// while the index is alive and the maps never go stale.
+///
+/// Identifiers are interned in an [IdPool], vertices and edges getting
+/// their rank in identifier order as key, so that algorithms can keep per
+/// vertex state in vectors indexed by [IndexedGraph::vertex_key].
+///
+/// [vertex_by_id]: crate::graph::ops::graph::node::vertex_by_id
+/// [edge_by_id]: crate::graph::ops::graph::edge::edge_by_id
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::indexed::IndexedGraph;
+/// use pgm_rust::graph::types::node::Node;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "n1", "n2");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1.clone()]));
+/// let index = IndexedGraph::new(&g);
+/// index.vertex_by_id("n1") == &Node::empty("n1"); // true
+/// index.edge_by_id("e1") == &e1; // true
+/// index.get_vertex("n3").is_none(); // true
+/// ```
+#[derive(Debug, Clone)]
+pub struct IndexedGraph<'a, N, E, G> {
+    graph: &'a G,
+    vertex_ids: IdPool,
+    /// vertices indexed by key
+    vertices: Vec<&'a N>,
+    edge_ids: IdPool,
+    /// edges indexed by key
+    edges: Vec<&'a E>,
+}
+
+impl<'a, N, E, G> IndexedGraph<'a, N, E, G>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    /// index the vertices and edges of `graph`
+    pub fn new(graph: &'a G) -> IndexedGraph<'a, N, E, G> {
+        let vertices = graph.vertices_sorted();
+        let edges = graph.edges_sorted();
+        IndexedGraph {
+            graph,
+            vertex_ids: vertices.iter().map(|v| v.id()).collect(),
+            vertices,
+            edge_ids: edges.iter().map(|e| e.id()).collect(),
+            edges,
+        }
+    }
+
+    /// indexed graph
+    pub fn graph(&self) -> &'a G {
+        self.graph
+    }
+
+    /// vertex with identifier `id` if any
+    pub fn get_vertex(&self, id: &str) -> Option<&'a N> {
+        self.vertex_key(id).map(|k| self.vertex_at(k))
+    }
+
+    /// edge with identifier `id` if any
+    pub fn get_edge(&self, id: &str) -> Option<&'a E> {
+        self.edge_key(id).map(|k| self.edge_at(k))
+    }
+
+    /// key of the vertex `id` if any, its rank in identifier order
+    pub fn vertex_key(&self, id: &str) -> Option<u32> {
+        self.vertex_ids.get(id)
+    }
+
+    /// key of the edge `id` if any, its rank in identifier order
+    pub fn edge_key(&self, id: &str) -> Option<u32> {
+        self.edge_ids.get(id)
+    }
+
+    /// vertex of key `k`, panics if no vertex has it
+    pub fn vertex_at(&self, k: u32) -> &'a N {
+        self.vertices[k as usize]
+    }
+
+    /// edge of key `k`, panics if no edge has it
+    pub fn edge_at(&self, k: u32) -> &'a E {
+        self.edges[k as usize]
+    }
+
+    /// interned vertex identifiers
+    pub fn vertex_pool(&self) -> &IdPool {
+        &self.vertex_ids
+    }
+
+    /// interned edge identifiers
+    pub fn edge_pool(&self) -> &IdPool {
+        &self.edge_ids
+    }
+
+    /// vertex with identifier `id`, panics if there is none
+    pub fn vertex_by_id(&self, id: &str) -> &'a N {
+        match self.get_vertex(id) {
+            None => panic!("{id} not contained in {}", self.graph),
+            Some(v) => v,
+        }
+    }
+
+    /// edge with identifier `id`, panics if there is none
+    pub fn edge_by_id(&self, id: &str) -> &'a E {
+        match self.get_edge(id) {
+            None => panic!("{id} not contained in {}", self.graph),
+            Some(e) => e,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::edgetype::EdgeType;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+    use std::collections::HashMap;
+    use std::collections::HashSet;
+
+    fn mk_g1() -> Graph<Node, Edge<Node>> {
+        let e1 = Edge::empty("e1", EdgeType::Undirected, "n1", "n2");
+        let e2 = Edge::empty("e2", EdgeType::Directed, "n2", "n3");
+        let nodes = HashSet::from([Node::empty("n4")]);
+        Graph::new(
+            "g1".to_string(),
+            HashMap::new(),
+            nodes,
+            HashSet::from([e1, e2]),
+        )
+    }
+
+    #[test]
+    fn test_indexed_graph() {
+        let g = mk_g1();
+        let index = IndexedGraph::new(&g);
+        assert_eq!(index.vertex_by_id("n4"), &Node::empty("n4"));
+        assert_eq!(index.edge_by_id("e2").end(), &Node::empty("n3"));
+        assert!(index.get_edge("e3").is_none());
+        assert_eq!(index.graph(), &g);
+        assert_eq!(index.vertex_key("n3"), Some(2));
+        assert_eq!(index.vertex_at(3), &Node::empty("n4"));
+        assert_eq!(index.edge_pool().ids(), &["e1", "e2"]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_indexed_graph_missing_vertex() {
+        let g = mk_g1();
+        IndexedGraph::new(&g).vertex_by_id("n5");
+    }
+}
@@ -0,0 +1,455 @@
+//! values attached to vertices or edges by identifier
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::collections::hash_map;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::io;
+use std::io::Write;
+use std::ops::Index;
+
+/// entries of `map` sorted by identifier
+fn sorted_entries<T>(map: &HashMap<String, T>) -> Vec<(&String, &T)> {
+    let mut entries: Vec<(&String, &T)> = map.iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    entries
+}
+
+/// `k` entries of `map` with the largest values, largest first, ties broken
+/// by identifier. Incomparable values such as NaN count as ties.
+fn top_k_entries<T: PartialOrd>(map: &HashMap<String, T>, k: usize) -> Vec<(&String, &T)> {
+    let mut entries = sorted_entries(map);
+    entries.sort_by(|a, b| b.1.partial_cmp(a.1).unwrap_or(Ordering::Equal));
+    entries.truncate(k);
+    entries
+}
+
+fn write_csv<T: fmt::Display, W: Write>(
+    map: &HashMap<String, T>,
+    writer: &mut W,
+) -> io::Result<()> {
+    writeln!(writer, "id,value")?;
+    for (id, value) in sorted_entries(map) {
+        writeln!(writer, "{id},{value}")?;
+    }
+    Ok(())
+}
+
+fn write_entries<T: fmt::Display>(
+    name: &str,
+    map: &HashMap<String, T>,
+    f: &mut fmt::Formatter,
+) -> fmt::Result {
+    let entries: Vec<String> = sorted_entries(map)
+        .into_iter()
+        .map(|(id, value)| format!("{id}: {value}"))
+        .collect();
+    write!(f, "{name}[ {} ]", entries.join(", "))
+}
+
+/// Value of every vertex of a graph, keyed by vertex identifier
+/// # Description
+/// Output type of algorithms computing a value per vertex, such as core
+/// numbers, distances or part assignments. It wraps a [HashMap] and can be
+/// indexed by identifier like one, but offers helpers common to such
+/// outputs: [top_k](NodeMap::top_k) ranks vertices and
+/// [to_csv](NodeMap::to_csv) exports the values. Entries are listed by
+/// identifier when displayed or exported, so that the output is stable.
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::maps::NodeMap;
+///
+/// let scores: NodeMap<f64> = [("a", 0.5), ("b", 2.0), ("c", 1.0)].into_iter().collect();
+/// scores["b"] == 2.0; // true
+/// scores.top_k(2) == vec![(&"b".to_string(), &2.0), (&"c".to_string(), &1.0)]; // true
+/// scores.to_string() == "NodeMap[ a: 0.5, b: 2, c: 1 ]"; // true
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeMap<T> {
+    values: HashMap<String, T>,
+}
+
+impl<T> Default for NodeMap<T> {
+    fn default() -> Self {
+        NodeMap {
+            values: HashMap::new(),
+        }
+    }
+}
+
+impl<T> NodeMap<T> {
+    /// map without vertices
+    pub fn new() -> NodeMap<T> {
+        NodeMap::default()
+    }
+
+    /// value of the vertex `id`
+    pub fn get(&self, id: &str) -> Option<&T> {
+        self.values.get(id)
+    }
+
+    /// mutable value of the vertex `id`
+    pub fn get_mut(&mut self, id: &str) -> Option<&mut T> {
+        self.values.get_mut(id)
+    }
+
+    /// set the value of the vertex `id`, returns its previous value
+    pub fn insert(&mut self, id: &str, value: T) -> Option<T> {
+        self.values.insert(id.to_string(), value)
+    }
+
+    /// remove the vertex `id`, returns its value
+    pub fn remove(&mut self, id: &str) -> Option<T> {
+        self.values.remove(id)
+    }
+
+    /// whether the vertex `id` has a value
+    pub fn contains(&self, id: &str) -> bool {
+        self.values.contains_key(id)
+    }
+
+    /// number of vertices with a value
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// whether no vertex has a value
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// vertex identifiers and values, in arbitrary order
+    pub fn iter(&self) -> hash_map::Iter<'_, String, T> {
+        self.values.iter()
+    }
+
+    /// values, in arbitrary order
+    pub fn values(&self) -> hash_map::Values<'_, String, T> {
+        self.values.values()
+    }
+
+    /// vertex identifiers and values sorted by identifier
+    pub fn sorted(&self) -> Vec<(&String, &T)> {
+        sorted_entries(&self.values)
+    }
+
+    /// underlying map
+    pub fn as_map(&self) -> &HashMap<String, T> {
+        &self.values
+    }
+
+    /// unwrap the underlying map
+    pub fn into_map(self) -> HashMap<String, T> {
+        self.values
+    }
+
+    /// `k` vertices with the largest values, largest first, ties broken by
+    /// identifier
+    pub fn top_k(&self, k: usize) -> Vec<(&String, &T)>
+    where
+        T: PartialOrd,
+    {
+        top_k_entries(&self.values, k)
+    }
+
+    /// Write the values as comma separated values with an `id,value`
+    /// header, sorted by identifier
+    pub fn to_csv<W: Write>(&self, writer: &mut W) -> io::Result<()>
+    where
+        T: fmt::Display,
+    {
+        write_csv(&self.values, writer)
+    }
+}
+
+impl<T> From<HashMap<String, T>> for NodeMap<T> {
+    fn from(values: HashMap<String, T>) -> Self {
+        NodeMap { values }
+    }
+}
+
+impl<S: AsRef<str>, T> FromIterator<(S, T)> for NodeMap<T> {
+    fn from_iter<I: IntoIterator<Item = (S, T)>>(iter: I) -> Self {
+        let values = iter
+            .into_iter()
+            .map(|(id, value)| (id.as_ref().to_string(), value))
+            .collect();
+        NodeMap { values }
+    }
+}
+
+impl<T> IntoIterator for NodeMap<T> {
+    type Item = (String, T);
+    type IntoIter = hash_map::IntoIter<String, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a NodeMap<T> {
+    type Item = (&'a String, &'a T);
+    type IntoIter = hash_map::Iter<'a, String, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.iter()
+    }
+}
+
+/// Indexing panics if the vertex has no value, like for a [HashMap]
+impl<T, Q> Index<&Q> for NodeMap<T>
+where
+    String: Borrow<Q>,
+    Q: Hash + Eq + ?Sized,
+{
+    type Output = T;
+
+    fn index(&self, id: &Q) -> &T {
+        &self.values[id]
+    }
+}
+
+/// Maps are equal to hash maps holding the same entries
+impl<T: PartialEq> PartialEq<HashMap<String, T>> for NodeMap<T> {
+    fn eq(&self, other: &HashMap<String, T>) -> bool {
+        self.values == *other
+    }
+}
+
+/// Maps list their entries sorted by identifier when serialized to string.
+impl<T: fmt::Display> fmt::Display for NodeMap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_entries("NodeMap", &self.values, f)
+    }
+}
+
+/// Value of every edge of a graph, keyed by edge identifier
+/// # Description
+/// Output type of algorithms computing a value per edge, such as flows or
+/// edge weights. It is the counterpart of [NodeMap] for edges and offers
+/// the same helpers.
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::maps::EdgeMap;
+///
+/// let mut flows: EdgeMap<usize> = EdgeMap::new();
+/// flows.insert("e1", 3);
+/// flows.insert("e2", 1);
+/// flows["e1"] == 3; // true
+/// let mut csv = Vec::new();
+/// flows.to_csv(&mut csv).unwrap();
+/// csv == b"id,value\ne1,3\ne2,1\n"; // true
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EdgeMap<T> {
+    values: HashMap<String, T>,
+}
+
+impl<T> Default for EdgeMap<T> {
+    fn default() -> Self {
+        EdgeMap {
+            values: HashMap::new(),
+        }
+    }
+}
+
+impl<T> EdgeMap<T> {
+    /// map without edges
+    pub fn new() -> EdgeMap<T> {
+        EdgeMap::default()
+    }
+
+    /// value of the edge `id`
+    pub fn get(&self, id: &str) -> Option<&T> {
+        self.values.get(id)
+    }
+
+    /// mutable value of the edge `id`
+    pub fn get_mut(&mut self, id: &str) -> Option<&mut T> {
+        self.values.get_mut(id)
+    }
+
+    /// set the value of the edge `id`, returns its previous value
+    pub fn insert(&mut self, id: &str, value: T) -> Option<T> {
+        self.values.insert(id.to_string(), value)
+    }
+
+    /// remove the edge `id`, returns its value
+    pub fn remove(&mut self, id: &str) -> Option<T> {
+        self.values.remove(id)
+    }
+
+    /// whether the edge `id` has a value
+    pub fn contains(&self, id: &str) -> bool {
+        self.values.contains_key(id)
+    }
+
+    /// number of edges with a value
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// whether no edge has a value
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// edge identifiers and values, in arbitrary order
+    pub fn iter(&self) -> hash_map::Iter<'_, String, T> {
+        self.values.iter()
+    }
+
+    /// values, in arbitrary order
+    pub fn values(&self) -> hash_map::Values<'_, String, T> {
+        self.values.values()
+    }
+
+    /// edge identifiers and values sorted by identifier
+    pub fn sorted(&self) -> Vec<(&String, &T)> {
+        sorted_entries(&self.values)
+    }
+
+    /// underlying map
+    pub fn as_map(&self) -> &HashMap<String, T> {
+        &self.values
+    }
+
+    /// unwrap the underlying map
+    pub fn into_map(self) -> HashMap<String, T> {
+        self.values
+    }
+
+    /// `k` edges with the largest values, largest first, ties broken by
+    /// identifier
+    pub fn top_k(&self, k: usize) -> Vec<(&String, &T)>
+    where
+        T: PartialOrd,
+    {
+        top_k_entries(&self.values, k)
+    }
+
+    /// Write the values as comma separated values with an `id,value`
+    /// header, sorted by identifier
+    pub fn to_csv<W: Write>(&self, writer: &mut W) -> io::Result<()>
+    where
+        T: fmt::Display,
+    {
+        write_csv(&self.values, writer)
+    }
+}
+
+impl<T> From<HashMap<String, T>> for EdgeMap<T> {
+    fn from(values: HashMap<String, T>) -> Self {
+        EdgeMap { values }
+    }
+}
+
+impl<S: AsRef<str>, T> FromIterator<(S, T)> for EdgeMap<T> {
+    fn from_iter<I: IntoIterator<Item = (S, T)>>(iter: I) -> Self {
+        let values = iter
+            .into_iter()
+            .map(|(id, value)| (id.as_ref().to_string(), value))
+            .collect();
+        EdgeMap { values }
+    }
+}
+
+impl<T> IntoIterator for EdgeMap<T> {
+    type Item = (String, T);
+    type IntoIter = hash_map::IntoIter<String, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a EdgeMap<T> {
+    type Item = (&'a String, &'a T);
+    type IntoIter = hash_map::Iter<'a, String, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.iter()
+    }
+}
+
+/// Indexing panics if the edge has no value, like for a [HashMap]
+impl<T, Q> Index<&Q> for EdgeMap<T>
+where
+    String: Borrow<Q>,
+    Q: Hash + Eq + ?Sized,
+{
+    type Output = T;
+
+    fn index(&self, id: &Q) -> &T {
+        &self.values[id]
+    }
+}
+
+/// Maps are equal to hash maps holding the same entries
+impl<T: PartialEq> PartialEq<HashMap<String, T>> for EdgeMap<T> {
+    fn eq(&self, other: &HashMap<String, T>) -> bool {
+        self.values == *other
+    }
+}
+
+/// Maps list their entries sorted by identifier when serialized to string.
+impl<T: fmt::Display> fmt::Display for EdgeMap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_entries("EdgeMap", &self.values, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_node_map_top_k() {
+        let m: NodeMap<f64> = [("a", 1.0), ("b", 3.0), ("c", 1.0), ("d", f64::NAN)]
+            .into_iter()
+            .collect();
+        let top: Vec<&String> = m.top_k(3).into_iter().map(|(id, _)| id).collect();
+        assert_eq!(top, vec!["b", "a", "c"]);
+        assert_eq!(m.top_k(10).len(), 4);
+        assert!(NodeMap::<f64>::new().top_k(2).is_empty());
+    }
+
+    #[test]
+    fn test_node_map_access() {
+        let mut m: NodeMap<usize> = NodeMap::new();
+        assert!(m.is_empty());
+        assert_eq!(m.insert("a", 1), None);
+        assert_eq!(m.insert("a", 2), Some(1));
+        m.insert("b", 5);
+        *m.get_mut("b").unwrap() += 1;
+        assert_eq!(m["b"], 6);
+        assert_eq!(m[&"b".to_string()], 6);
+        assert!(m.contains("a"));
+        assert_eq!(m.remove("a"), Some(2));
+        assert_eq!(m.len(), 1);
+        assert_eq!(m, HashMap::from([("b".to_string(), 6)]));
+        assert_eq!(m.to_string(), "NodeMap[ b: 6 ]");
+    }
+
+    #[test]
+    fn test_edge_map_csv_and_display() {
+        let m: EdgeMap<f64> =
+            HashMap::from([("e2".to_string(), 0.5), ("e1".to_string(), 2.0)]).into();
+        let mut csv = Vec::new();
+        m.to_csv(&mut csv).unwrap();
+        assert_eq!(String::from_utf8(csv).unwrap(), "id,value\ne1,2\ne2,0.5\n");
+        assert_eq!(m.to_string(), "EdgeMap[ e1: 2, e2: 0.5 ]");
+        assert_eq!(m.sorted()[0], (&"e1".to_string(), &2.0));
+        assert_eq!(m.top_k(1), vec![(&"e1".to_string(), &2.0)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_node_map_missing_index() {
+        let m: NodeMap<usize> = NodeMap::new();
+        let _ = m["a"];
+    }
+}
@@ -39,7 +39,7 @@ impl<N: NodeTrait> VertexSetTrait<N> for Vertices<N> {
     fn create(vs: HashSet<&N>) -> Self {
         let mut ms: HashSet<N> = HashSet::new();
         for v in &vs {
-            let m: &N = v.clone();
+            let m: &N = v;
             ms.insert(m.clone());
         }
         Vertices { vertex_set: ms }
@@ -0,0 +1,363 @@
+//! mutable graph recording its changes in an event log
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::graph_obj::GraphObject;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::graph::Graph;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// change made to an [ObservableGraph]
+#[derive(Debug, Clone, PartialEq)]
+pub enum GraphEvent<N, E> {
+    /// a vertex was added
+    NodeAdded(N),
+    /// a vertex was removed
+    NodeRemoved(N),
+    /// an edge was added
+    EdgeAdded(E),
+    /// an edge was removed
+    EdgeRemoved(E),
+}
+
+/// Graph supporting in place changes and recording them
+/// # Description
+/// [Graph] is immutable, algorithms build new graphs instead of changing
+/// their input. Structures derived from a graph that changes over time,
+/// such as indexes, caches or drawings, would then have to compare whole
+/// graphs to find what changed. This graph can be changed in place and
+/// appends a [GraphEvent] to its log for every change, so that dependents
+/// replay only the events they have not seen yet.
+///
+/// The log is append only: its length is the version of the graph. A
+/// dependent remembers the version it last synchronized with and asks for
+/// [events_since](ObservableGraph::events_since) that version. Any number
+/// of dependents can follow the same graph this way, and unlike callbacks
+/// the log keeps the graph `Send`, `Sync` and [Clone]. The graph implements
+/// [Graph](GraphTrait), so every algorithm accepts it.
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::types::observable::{GraphEvent, ObservableGraph};
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+///
+/// let mut g: ObservableGraph<Node, Edge<Node>> = ObservableGraph::empty("g");
+/// g.add_node(Node::empty("n1"));
+/// let seen = g.version();
+/// g.add_edge(Edge::empty("e1", EdgeType::Undirected, "n1", "n2"));
+/// g.events_since(seen) == &[
+///     GraphEvent::NodeAdded(Node::empty("n2")),
+///     GraphEvent::EdgeAdded(Edge::empty("e1", EdgeType::Undirected, "n1", "n2")),
+/// ]; // true
+/// g.order() == 2; // true
+/// ```
+#[derive(Debug, Clone)]
+pub struct ObservableGraph<N: NodeTrait, E: EdgeTrait<N>> {
+    graph_id: String,
+    graph_data: HashMap<String, Vec<String>>,
+    nodes: HashMap<String, N>,
+    edges: HashMap<String, E>,
+    log: Vec<GraphEvent<N, E>>,
+}
+
+impl<N: NodeTrait, E: EdgeTrait<N> + Clone> ObservableGraph<N, E> {
+    /// constructor, the log starts empty whatever the initial content
+    pub fn new(
+        graph_id: String,
+        graph_data: HashMap<String, Vec<String>>,
+        nodes: HashSet<N>,
+        edges: HashSet<E>,
+    ) -> ObservableGraph<N, E> {
+        let mut g = ObservableGraph {
+            graph_id,
+            graph_data,
+            nodes: HashMap::new(),
+            edges: HashMap::new(),
+            log: Vec::new(),
+        };
+        for n in nodes {
+            g.add_node(n);
+        }
+        for e in edges {
+            g.add_edge(e);
+        }
+        g.log.clear();
+        g
+    }
+
+    /// empty graph constructor
+    pub fn empty(graph_id: &str) -> ObservableGraph<N, E> {
+        ObservableGraph::new(
+            graph_id.to_string(),
+            HashMap::new(),
+            HashSet::new(),
+            HashSet::new(),
+        )
+    }
+
+    /// copy the content of a graph, see [ObservableGraph::new]
+    pub fn from_graphish<G: GraphTrait<N, E>>(g: &G) -> ObservableGraph<N, E> {
+        ObservableGraph::new(
+            g.id().clone(),
+            g.data().clone(),
+            g.vertices().into_iter().cloned().collect(),
+            g.edges().into_iter().cloned().collect(),
+        )
+    }
+
+    /// immutable copy of the current content
+    pub fn snapshot(&self) -> Graph<N, E> {
+        Graph::new_refs(
+            self.graph_id.clone(),
+            self.graph_data.clone(),
+            self.nodes.values().collect(),
+            self.edges.values().collect(),
+        )
+    }
+
+    /// Add a vertex
+    /// # Description
+    /// Returns false and records nothing if the graph already has a vertex
+    /// with the same identifier.
+    pub fn add_node(&mut self, n: N) -> bool {
+        if self.nodes.contains_key(n.id()) {
+            return false;
+        }
+        self.nodes.insert(n.id().clone(), n.clone());
+        self.log.push(GraphEvent::NodeAdded(n));
+        true
+    }
+
+    /// Add an edge
+    /// # Description
+    /// End vertices missing from the graph are added first, each recording
+    /// its own event. Returns false and records nothing if the graph already
+    /// has an edge with the same identifier.
+    pub fn add_edge(&mut self, e: E) -> bool {
+        if self.edges.contains_key(e.id()) {
+            return false;
+        }
+        self.add_node(e.start().clone());
+        self.add_node(e.end().clone());
+        self.edges.insert(e.id().clone(), e.clone());
+        self.log.push(GraphEvent::EdgeAdded(e));
+        true
+    }
+
+    /// Remove an edge and return it
+    /// # Description
+    /// Its end vertices stay in the graph.
+    pub fn remove_edge(&mut self, id: &str) -> Option<E> {
+        let e = self.edges.remove(id)?;
+        self.log.push(GraphEvent::EdgeRemoved(e.clone()));
+        Some(e)
+    }
+
+    /// Remove a vertex and return it
+    /// # Description
+    /// Edges incident to the vertex are removed first, in identifier order,
+    /// each recording its own event.
+    pub fn remove_node(&mut self, id: &str) -> Option<N> {
+        if !self.nodes.contains_key(id) {
+            return None;
+        }
+        let mut incident: Vec<String> = self
+            .edges
+            .values()
+            .filter(|e| e.start().id() == id || e.end().id() == id)
+            .map(|e| e.id().clone())
+            .collect();
+        incident.sort();
+        for eid in incident {
+            self.remove_edge(&eid);
+        }
+        let n = self.nodes.remove(id)?;
+        self.log.push(GraphEvent::NodeRemoved(n.clone()));
+        Some(n)
+    }
+
+    /// number of changes recorded so far
+    pub fn version(&self) -> usize {
+        self.log.len()
+    }
+
+    /// every change recorded so far, oldest first
+    pub fn events(&self) -> &[GraphEvent<N, E>] {
+        &self.log
+    }
+
+    /// changes recorded after the graph reached `version`, oldest first.
+    /// Panics if `version` is ahead of the graph.
+    pub fn events_since(&self, version: usize) -> &[GraphEvent<N, E>] {
+        if version > self.log.len() {
+            panic!(
+                "version {version} is ahead of {} at version {}",
+                self,
+                self.log.len()
+            );
+        }
+        &self.log[version..]
+    }
+}
+
+/// Observable graphs are hashed using their identifiers
+impl<N: NodeTrait, E: EdgeTrait<N>> Hash for ObservableGraph<N, E> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.graph_id.hash(state);
+    }
+}
+
+/// Observable graphs are equal if their content is, their logs are ignored
+impl<N: NodeTrait, E: EdgeTrait<N>> PartialEq for ObservableGraph<N, E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.graph_id == other.graph_id
+            && self.graph_data == other.graph_data
+            && self.nodes == other.nodes
+            && self.edges == other.edges
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait<N>> Eq for ObservableGraph<N, E> {}
+
+/// Observable graphs display their identifier when serialized to string.
+impl<N: NodeTrait, E: EdgeTrait<N>> fmt::Display for ObservableGraph<N, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ObservableGraph[ id: {} ]", self.graph_id)
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait<N>> GraphObject for ObservableGraph<N, E> {
+    fn id(&self) -> &String {
+        &self.graph_id
+    }
+
+    fn data(&self) -> &HashMap<String, Vec<String>> {
+        &self.graph_data
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait<N> + Clone> GraphTrait<N, E> for ObservableGraph<N, E> {
+    fn vertices(&self) -> HashSet<&N> {
+        self.nodes.values().collect()
+    }
+
+    fn edges(&self) -> HashSet<&E> {
+        self.edges.values().collect()
+    }
+
+    fn create(
+        graph_id: String,
+        graph_data: HashMap<String, Vec<String>>,
+        nodes: HashSet<N>,
+        edges: HashSet<E>,
+    ) -> Self {
+        ObservableGraph::new(graph_id, graph_data, nodes, edges)
+    }
+
+    fn create_from_ref(
+        graph_id: String,
+        graph_data: HashMap<String, Vec<String>>,
+        nodes: HashSet<&N>,
+        edges: HashSet<&E>,
+    ) -> Self {
+        ObservableGraph::new(
+            graph_id,
+            graph_data,
+            nodes.into_iter().cloned().collect(),
+            edges.into_iter().cloned().collect(),
+        )
+    }
+
+    fn find_node(&self, id: &str) -> Option<&N> {
+        self.nodes.get(id)
+    }
+
+    fn find_edge(&self, id: &str) -> Option<&E> {
+        self.edges.get(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::ops::graph::boolops::is_connected;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::edgetype::EdgeType;
+    use crate::graph::types::node::Node;
+
+    fn mk_uedge(n1: &str, n2: &str, id: &str) -> Edge<Node> {
+        Edge::empty(id, EdgeType::Undirected, n1, n2)
+    }
+
+    #[test]
+    fn test_events_of_changes() {
+        let mut g: ObservableGraph<Node, Edge<Node>> = ObservableGraph::empty("g");
+        assert!(g.add_edge(mk_uedge("a", "b", "e1")));
+        assert!(!g.add_edge(mk_uedge("a", "b", "e1")));
+        assert!(!g.add_node(Node::empty("a")));
+        assert!(g.add_edge(mk_uedge("b", "c", "e2")));
+        assert_eq!(g.version(), 5);
+        let seen = g.version();
+        assert_eq!(g.remove_node("b"), Some(Node::empty("b")));
+        assert_eq!(g.remove_node("b"), None);
+        assert_eq!(
+            g.events_since(seen),
+            &[
+                GraphEvent::EdgeRemoved(mk_uedge("a", "b", "e1")),
+                GraphEvent::EdgeRemoved(mk_uedge("b", "c", "e2")),
+                GraphEvent::NodeRemoved(Node::empty("b")),
+            ]
+        );
+        assert_eq!(g.order(), 2);
+        assert_eq!(g.size(), 0);
+        assert!(g.events_since(g.version()).is_empty());
+    }
+
+    #[test]
+    fn test_replay_events() {
+        let mut g: ObservableGraph<Node, Edge<Node>> = ObservableGraph::empty("g");
+        g.add_edge(mk_uedge("a", "b", "e1"));
+        g.add_edge(mk_uedge("b", "c", "e2"));
+        g.remove_edge("e1");
+        // a dependent mirroring the graph from its log
+        let mut mirror: HashSet<String> = HashSet::new();
+        for event in g.events() {
+            match event {
+                GraphEvent::EdgeAdded(e) => mirror.insert(e.id().clone()),
+                GraphEvent::EdgeRemoved(e) => mirror.remove(e.id()),
+                _ => false,
+            };
+        }
+        let ids: HashSet<String> = g.edges().into_iter().map(|e| e.id().clone()).collect();
+        assert_eq!(mirror, ids);
+    }
+
+    #[test]
+    fn test_snapshot_and_algorithms() {
+        let base: Graph<Node, Edge<Node>> = Graph::from_edge_node_set(
+            HashSet::from([mk_uedge("a", "b", "e1")]),
+            HashSet::from([Node::empty("c")]),
+        );
+        let mut g = ObservableGraph::from_graphish(&base);
+        assert_eq!(g.version(), 0);
+        assert!(!is_connected(&g));
+        g.add_edge(mk_uedge("b", "c", "e2"));
+        assert!(is_connected(&g));
+        let snap = g.snapshot();
+        assert_eq!(snap.order(), 3);
+        assert_eq!(snap.edges(), g.edges());
+        assert_eq!(g.find_edge("e2"), Some(&mk_uedge("b", "c", "e2")));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_events_since_future_version() {
+        let g: ObservableGraph<Node, Edge<Node>> = ObservableGraph::empty("g");
+        g.events_since(1);
+    }
+}
@@ -2,12 +2,12 @@
 //! operations
 
 use crate::graph::traits::edge::Edge as EdgeTrait;
-use crate::graph::traits::edge::EdgeSet as EdgeSetTrait;
 use crate::graph::traits::graph::Graph as GraphTrait;
 use crate::graph::traits::graph_obj::GraphObject as GraphObjectTrait;
+use crate::graph::traits::idgen::IdGenerator;
 use crate::graph::traits::node::Node as NodeTrait;
-use crate::graph::traits::node::VertexSet as VertexSetTrait;
 use crate::graph::traits::path::Path as PathTrait;
+use crate::graph::types::idgen::UuidGenerator;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt;
@@ -15,15 +15,14 @@ use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
 
 /// checks if containers has two members or less
-fn has_two_or_less<N: NodeTrait>(nodes: &Vec<&N>) {
+fn has_two_or_less<N: NodeTrait>(nodes: &[&N]) {
     let c1 = nodes.len() <= 2;
     if !c1 {
         panic!("nodes have more than 2 elements")
-    } else {
     }
 }
 
-fn extract_two_nodes<N: NodeTrait>(nodes: &Vec<&N>) -> (N, N) {
+fn extract_two_nodes<N: NodeTrait>(nodes: &[&N]) -> (N, N) {
     let arr: [&N; 2] = nodes[0..2].try_into().unwrap();
     (arr[0].clone(), arr[1].clone())
 }
@@ -35,7 +34,7 @@ where
     E: EdgeTrait<N>,
 {
     let mut ns: Vec<N> = Vec::new();
-    let e_opt = edges.get(0);
+    let e_opt = edges.first();
     match e_opt {
         None => panic!("empty edge list"),
         Some(e) => {
@@ -55,7 +54,7 @@ where
         nodes.insert(e_start);
         nodes.insert(e_end);
 
-        let has_not_end = !ns.contains(&e_end);
+        let has_not_end = !ns.contains(e_end);
         if has_not_end {
             ns.push(e_end.clone());
         }
@@ -77,7 +76,7 @@ where
         let start_end = extract_two_nodes(&end_nodes);
         (node_lst, node_set, start_end)
     } else {
-        start_nodes.extend(end_nodes.into_iter());
+        start_nodes.extend(end_nodes);
         let start_end = extract_two_nodes(&start_nodes);
         (node_lst, node_set, start_end)
     }
@@ -113,11 +112,11 @@ impl<T: NodeTrait, E: EdgeTrait<T>, G: GraphTrait<T, E> + GraphObjectTrait> Grap
     for Path<T, E, G>
 {
     fn id(&self) -> &String {
-        &self.graph.id()
+        self.graph.id()
     }
 
     fn data(&self) -> &HashMap<String, Vec<String>> {
-        &self.graph.data()
+        self.graph.data()
     }
 }
 
@@ -137,11 +136,11 @@ impl<T: NodeTrait, E: EdgeTrait<T> + Clone, G: GraphTrait<T, E> + GraphObjectTra
         edges: HashSet<E>,
     ) -> Path<T, E, G> {
         let graph = G::create(graph_id, graph_data, nodes, edges.clone());
-        let edges: Vec<E> = edges.iter().map(|x| x.clone()).collect();
+        let edges: Vec<E> = edges.iter().cloned().collect();
         let group = get_end_vertices_and_nodes::<T, E>(edges);
         let (_, _, (start, end)) = group;
         Path {
-            graph: graph,
+            graph,
             ends: (start, end),
             edge_type: PhantomData,
         }
@@ -157,7 +156,7 @@ impl<T: NodeTrait, E: EdgeTrait<T> + Clone, G: GraphTrait<T, E> + GraphObjectTra
         let group = get_end_vertices_and_nodes::<T, E>(edges);
         let (_, _, (start, end)) = group;
         Path {
-            graph: graph,
+            graph,
             ends: (start, end),
             edge_type: PhantomData,
         }
@@ -179,6 +178,207 @@ impl<T: NodeTrait, E: EdgeTrait<T> + Clone, G: GraphTrait<T, E> + GraphObjectTra
     }
 }
 
+impl<T: NodeTrait, E: EdgeTrait<T> + Clone, G: GraphTrait<T, E> + GraphObjectTrait> Path<T, E, G> {
+    /// create a path from its vertex sequence and its edge sequence
+    /// # Description
+    /// The i-th edge must join the i-th and the (i+1)-th vertices, in either
+    /// orientation, and vertices must be distinct. Unlike
+    /// [GraphTrait::create], the end vertices are the first and the last
+    /// vertices of the sequence, and a single vertex gives a trivial path of
+    /// length 0.
+    pub fn from_sequence(
+        graph_id: String,
+        graph_data: HashMap<String, Vec<String>>,
+        nodes: Vec<T>,
+        edges: Vec<E>,
+    ) -> Path<T, E, G> {
+        if nodes.is_empty() {
+            panic!("empty vertex sequence");
+        }
+        if edges.len() + 1 != nodes.len() {
+            panic!(
+                "{} edges can not join {} vertices",
+                edges.len(),
+                nodes.len()
+            );
+        }
+        for (i, e) in edges.iter().enumerate() {
+            let (a, b) = (nodes[i].id(), nodes[i + 1].id());
+            let (s, t) = (e.start().id(), e.end().id());
+            if !((s == a && t == b) || (s == b && t == a)) {
+                panic!("{e} does not join {} and {}", nodes[i], nodes[i + 1]);
+            }
+        }
+        let node_set: HashSet<T> = nodes.iter().cloned().collect();
+        if node_set.len() != nodes.len() {
+            panic!("vertices of a path must be distinct");
+        }
+        let ends = (nodes[0].clone(), nodes[nodes.len() - 1].clone());
+        let graph = G::create(graph_id, graph_data, node_set, edges.into_iter().collect());
+        Path {
+            graph,
+            ends,
+            edge_type: PhantomData,
+        }
+    }
+
+    /// path from its sequences with a random UUID as identifier
+    fn from_sequence_refs(nodes: Vec<&T>, edges: Vec<&E>) -> Path<T, E, G> {
+        let gid = UuidGenerator.generate(
+            &nodes.iter().copied().collect(),
+            &edges.iter().copied().collect(),
+        );
+        Path::from_sequence(
+            gid,
+            HashMap::new(),
+            nodes.into_iter().cloned().collect(),
+            edges.into_iter().cloned().collect(),
+        )
+    }
+
+    /// vertices and edges of the path from its first end vertex to its
+    /// last one
+    fn sequences(&self) -> (Vec<&T>, Vec<&E>) {
+        let mut incident: HashMap<&String, Vec<&E>> = HashMap::new();
+        for e in self.graph.edges() {
+            incident.entry(e.start().id()).or_default().push(e);
+            incident.entry(e.end().id()).or_default().push(e);
+        }
+        let vertices = self.graph.vertices();
+        let mut current: &T = vertices.get(&self.ends.0).unwrap();
+        let mut nodes: Vec<&T> = vec![current];
+        let mut edges: Vec<&E> = Vec::new();
+        while let Some(e) = incident
+            .get(current.id())
+            .into_iter()
+            .flatten()
+            .find(|e| edges.last().is_none_or(|last| last.id() != e.id()))
+        {
+            current = if e.start().id() == current.id() {
+                vertices.get(e.end()).unwrap()
+            } else {
+                vertices.get(e.start()).unwrap()
+            };
+            nodes.push(current);
+            edges.push(e);
+        }
+        (nodes, edges)
+    }
+
+    /// position of `n` along the path, panics if `n` is not on the path
+    fn position_of(nodes: &[&T], n: &T) -> usize {
+        match nodes.iter().position(|v| v.id() == n.id()) {
+            None => panic!("{n} is not on the path"),
+            Some(i) => i,
+        }
+    }
+
+    /// vertices of the path from its first end vertex to its last one
+    pub fn nodes_in_order(&self) -> Vec<&T> {
+        self.sequences().0
+    }
+
+    /// edges of the path from its first end vertex to its last one, the
+    /// i-th edge joins the i-th and the (i+1)-th vertices of
+    /// [nodes_in_order](Path::nodes_in_order)
+    pub fn edges_in_order(&self) -> Vec<&E> {
+        self.sequences().1
+    }
+
+    /// the same path traversed from its last end vertex to its first one.
+    /// Identifier, data and edges are kept, edge orientations included.
+    pub fn reverse(&self) -> Path<T, E, G> {
+        Path {
+            graph: G::create_from_ref(
+                self.graph.id().clone(),
+                self.graph.data().clone(),
+                self.graph.vertices(),
+                self.graph.edges(),
+            ),
+            ends: (self.ends.1.clone(), self.ends.0.clone()),
+            edge_type: PhantomData,
+        }
+    }
+
+    /// Sub-path between two vertices of the path
+    /// # Description
+    /// The sub-path starts at `from` and ends at `to`, so it runs against
+    /// the path when `to` comes before `from`. Its identifier is a random
+    /// UUID and it has no data. Panics if one of the vertices is not on the
+    /// path.
+    ///
+    /// # Example
+    /// ```
+    /// use pgm_rust::graph::types::edge::Edge;
+    /// use pgm_rust::graph::types::graph::Graph;
+    /// use pgm_rust::graph::types::node::Node;
+    /// use pgm_rust::graph::types::path::Path;
+    /// use pgm_rust::graph::traits::path::Path as PathTrait;
+    /// use std::collections::HashMap;
+    ///
+    /// let ns = vec![Node::empty("a"), Node::empty("b"), Node::empty("c")];
+    /// let e1 = Edge::undirected("e1".to_string(), ns[0].clone(), ns[1].clone(), HashMap::new());
+    /// let e2 = Edge::undirected("e2".to_string(), ns[1].clone(), ns[2].clone(), HashMap::new());
+    /// let p: Path<Node, Edge<Node>, Graph<Node, Edge<Node>>> =
+    ///     Path::from_sequence("p".to_string(), HashMap::new(), ns, vec![e1, e2]);
+    /// let q = p.slice(&Node::empty("c"), &Node::empty("b"));
+    /// q.length() == 1; // true
+    /// q.endvertices() == (&Node::empty("c"), &Node::empty("b")); // true
+    /// ```
+    pub fn slice(&self, from: &T, to: &T) -> Path<T, E, G> {
+        let (nodes, edges) = self.sequences();
+        let (i, j) = (
+            Path::<T, E, G>::position_of(&nodes, from),
+            Path::<T, E, G>::position_of(&nodes, to),
+        );
+        if i <= j {
+            Path::from_sequence_refs(nodes[i..=j].to_vec(), edges[i..j].to_vec())
+        } else {
+            let ns: Vec<&T> = nodes[j..=i].iter().rev().copied().collect();
+            let es: Vec<&E> = edges[j..i].iter().rev().copied().collect();
+            Path::from_sequence_refs(ns, es)
+        }
+    }
+
+    /// Path followed by another path
+    /// # Description
+    /// The last end vertex of the path must be the first end vertex of
+    /// `other`, and the paths must share no other vertex, so that the result
+    /// is a path. Its identifier is a random UUID and it has no data.
+    /// Panics otherwise.
+    ///
+    /// # Example
+    /// ```
+    /// use pgm_rust::graph::types::edge::Edge;
+    /// use pgm_rust::graph::types::graph::Graph;
+    /// use pgm_rust::graph::types::node::Node;
+    /// use pgm_rust::graph::types::path::Path;
+    /// use pgm_rust::graph::traits::path::Path as PathTrait;
+    /// use std::collections::HashMap;
+    ///
+    /// let (a, b, c) = (Node::empty("a"), Node::empty("b"), Node::empty("c"));
+    /// let e1 = Edge::undirected("e1".to_string(), a.clone(), b.clone(), HashMap::new());
+    /// let e2 = Edge::undirected("e2".to_string(), b.clone(), c.clone(), HashMap::new());
+    /// let p: Path<Node, Edge<Node>, Graph<Node, Edge<Node>>> =
+    ///     Path::from_sequence("p".to_string(), HashMap::new(), vec![a, b.clone()], vec![e1]);
+    /// let q = Path::from_sequence("q".to_string(), HashMap::new(), vec![b, c], vec![e2]);
+    /// p.concat(&q).length() == 2; // true
+    /// ```
+    pub fn concat(&self, other: &Path<T, E, G>) -> Path<T, E, G> {
+        if self.ends.1.id() != other.ends.0.id() {
+            panic!(
+                "{} ends at {} but {} starts at {}",
+                self, self.ends.1, other, other.ends.0
+            );
+        }
+        let (mut nodes, mut edges) = self.sequences();
+        let (other_nodes, other_edges) = other.sequences();
+        nodes.extend(other_nodes.into_iter().skip(1));
+        edges.extend(other_edges);
+        Path::from_sequence_refs(nodes, edges)
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -220,8 +420,8 @@ mod tests {
         let e5 = mk_uedge("n5", "n6", "e5");
         let e6 = mk_uedge("n6", "n7", "e6");
         let es = HashSet::from([e1, e2, e3, e4, e5, e6]);
-        let p = Path::create("mpath".to_string(), HashMap::new(), ns, es);
-        p
+
+        Path::create("mpath".to_string(), HashMap::new(), ns, es)
     }
     #[test]
     fn test_id() {
@@ -242,4 +442,97 @@ mod tests {
         let n7 = mk_node("n7");
         assert_eq!(p.ends, (n1, n7));
     }
+
+    #[test]
+    fn test_from_sequence() {
+        let ns = vec![mk_node("n3"), mk_node("n2"), mk_node("n1")];
+        let es = vec![mk_uedge("n2", "n3", "e2"), mk_uedge("n1", "n2", "e1")];
+        let p: Path<Node, Edge<Node>, Graph<Node, Edge<Node>>> =
+            Path::from_sequence("p".to_string(), HashMap::new(), ns, es);
+        assert_eq!(p.length(), 2);
+        assert_eq!(p.endvertices(), (&mk_node("n3"), &mk_node("n1")));
+    }
+
+    #[test]
+    fn test_from_sequence_trivial() {
+        let p: Path<Node, Edge<Node>, Graph<Node, Edge<Node>>> =
+            Path::from_sequence("p".to_string(), HashMap::new(), vec![mk_node("n1")], vec![]);
+        assert_eq!(p.length(), 0);
+        assert_eq!(p.endvertices(), (&mk_node("n1"), &mk_node("n1")));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_sequence_disconnected() {
+        let ns = vec![mk_node("n1"), mk_node("n2"), mk_node("n4")];
+        let es = vec![mk_uedge("n1", "n2", "e1"), mk_uedge("n2", "n3", "e2")];
+        let _: Path<Node, Edge<Node>, Graph<Node, Edge<Node>>> =
+            Path::from_sequence("p".to_string(), HashMap::new(), ns, es);
+    }
+
+    fn ids(ns: Vec<&Node>) -> Vec<&str> {
+        ns.into_iter().map(|n| n.id().as_str()).collect()
+    }
+
+    #[test]
+    fn test_nodes_in_order() {
+        let p = mk_path();
+        assert_eq!(
+            ids(p.nodes_in_order()),
+            vec!["n1", "n2", "n3", "n4", "n5", "n6", "n7"]
+        );
+        let es: Vec<&String> = p.edges_in_order().into_iter().map(|e| e.id()).collect();
+        assert_eq!(es, vec!["e1", "e2", "e3", "e4", "e5", "e6"]);
+        let r = p.reverse();
+        assert_eq!(r.id(), p.id());
+        assert_eq!(ids(r.nodes_in_order())[..2], ["n7", "n6"]);
+        assert_eq!(r.reverse(), p);
+    }
+
+    #[test]
+    fn test_slice() {
+        let p = mk_path();
+        let q = p.slice(&mk_node("n2"), &mk_node("n5"));
+        assert_eq!(q.length(), 3);
+        assert_eq!(ids(q.nodes_in_order()), vec!["n2", "n3", "n4", "n5"]);
+        let r = p.slice(&mk_node("n5"), &mk_node("n2"));
+        assert_eq!(ids(r.nodes_in_order()), vec!["n5", "n4", "n3", "n2"]);
+        let t = p.slice(&mk_node("n4"), &mk_node("n4"));
+        assert_eq!(t.length(), 0);
+        assert_eq!(t.endvertices(), (&mk_node("n4"), &mk_node("n4")));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_slice_outside() {
+        mk_path().slice(&mk_node("n1"), &mk_node("n9"));
+    }
+
+    #[test]
+    fn test_concat() {
+        let p = mk_path();
+        let head = p.slice(&mk_node("n1"), &mk_node("n3"));
+        let tail = p.slice(&mk_node("n3"), &mk_node("n7"));
+        let whole = head.concat(&tail);
+        assert_eq!(whole.length(), 6);
+        assert_eq!(whole.nodes_in_order(), p.nodes_in_order());
+        let trivial = p.slice(&mk_node("n7"), &mk_node("n7"));
+        assert_eq!(p.concat(&trivial).length(), 6);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_concat_without_shared_end() {
+        let p = mk_path();
+        p.slice(&mk_node("n1"), &mk_node("n3"))
+            .concat(&p.slice(&mk_node("n4"), &mk_node("n7")));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_concat_repeated_vertex() {
+        let p = mk_path();
+        p.slice(&mk_node("n1"), &mk_node("n3"))
+            .concat(&p.slice(&mk_node("n3"), &mk_node("n2")));
+    }
 }
@@ -0,0 +1,680 @@
+//! persistent graph sharing structure between versions
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::graph_obj::GraphObject;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::graph::Graph;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+/// bits of the hash consumed by each level of the trie
+const BITS: u32 = 5;
+/// levels of branches, the 13th consumes the last 4 bits of the hash
+const MAX_DEPTH: u32 = 13;
+
+fn hash_key(key: &str) -> u64 {
+    let mut h = DefaultHasher::new();
+    key.hash(&mut h);
+    h.finish()
+}
+
+/// index of the child holding `hash` in a branch at `depth`
+fn fragment(hash: u64, depth: u32) -> u32 {
+    ((hash >> (BITS * depth)) & 0x1f) as u32
+}
+
+#[derive(Debug)]
+enum TrieNode<V> {
+    /// children of the set bits of `bitmap`, in increasing bit order
+    Branch { bitmap: u32, slots: Vec<Slot<V>> },
+    /// entries whose keys have the same hash
+    Collision {
+        hash: u64,
+        entries: Vec<Arc<(String, V)>>,
+    },
+}
+
+#[derive(Debug)]
+enum Slot<V> {
+    Leaf(u64, Arc<(String, V)>),
+    Node(Arc<TrieNode<V>>),
+}
+
+impl<V> Clone for Slot<V> {
+    fn clone(&self) -> Self {
+        match self {
+            Slot::Leaf(h, kv) => Slot::Leaf(*h, Arc::clone(kv)),
+            Slot::Node(n) => Slot::Node(Arc::clone(n)),
+        }
+    }
+}
+
+/// trie holding two leaves whose hashes agree below `depth`
+fn merge_leaves<V>(
+    depth: u32,
+    h1: u64,
+    kv1: Arc<(String, V)>,
+    h2: u64,
+    kv2: Arc<(String, V)>,
+) -> TrieNode<V> {
+    if depth >= MAX_DEPTH {
+        return TrieNode::Collision {
+            hash: h1,
+            entries: vec![kv1, kv2],
+        };
+    }
+    let (i1, i2) = (fragment(h1, depth), fragment(h2, depth));
+    if i1 == i2 {
+        let child = merge_leaves(depth + 1, h1, kv1, h2, kv2);
+        return TrieNode::Branch {
+            bitmap: 1 << i1,
+            slots: vec![Slot::Node(Arc::new(child))],
+        };
+    }
+    let (a, b) = (Slot::Leaf(h1, kv1), Slot::Leaf(h2, kv2));
+    TrieNode::Branch {
+        bitmap: (1 << i1) | (1 << i2),
+        slots: if i1 < i2 { vec![a, b] } else { vec![b, a] },
+    }
+}
+
+/// copy of `node` holding `kv`, and whether the key is new
+fn insert_node<V>(
+    node: &TrieNode<V>,
+    depth: u32,
+    hash: u64,
+    kv: Arc<(String, V)>,
+) -> (TrieNode<V>, bool) {
+    match node {
+        TrieNode::Branch { bitmap, slots } => {
+            let bit = 1u32 << fragment(hash, depth);
+            let pos = (bitmap & (bit - 1)).count_ones() as usize;
+            let mut slots = slots.clone();
+            if bitmap & bit == 0 {
+                slots.insert(pos, Slot::Leaf(hash, kv));
+                let node = TrieNode::Branch {
+                    bitmap: bitmap | bit,
+                    slots,
+                };
+                return (node, true);
+            }
+            let added = match &slots[pos] {
+                Slot::Leaf(h, old) if old.0 == kv.0 => {
+                    slots[pos] = Slot::Leaf(*h, kv);
+                    false
+                }
+                Slot::Leaf(h, old) => {
+                    let child = merge_leaves(depth + 1, *h, Arc::clone(old), hash, kv);
+                    slots[pos] = Slot::Node(Arc::new(child));
+                    true
+                }
+                Slot::Node(child) => {
+                    let (child, added) = insert_node(child, depth + 1, hash, kv);
+                    slots[pos] = Slot::Node(Arc::new(child));
+                    added
+                }
+            };
+            let node = TrieNode::Branch {
+                bitmap: *bitmap,
+                slots,
+            };
+            (node, added)
+        }
+        TrieNode::Collision { hash: h, entries } => {
+            let mut entries = entries.clone();
+            let added = match entries.iter().position(|old| old.0 == kv.0) {
+                Some(i) => {
+                    entries[i] = kv;
+                    false
+                }
+                None => {
+                    entries.push(kv);
+                    true
+                }
+            };
+            (TrieNode::Collision { hash: *h, entries }, added)
+        }
+    }
+}
+
+/// copy of `node` without `key`, `None` if the key is absent
+fn remove_node<V>(node: &TrieNode<V>, depth: u32, hash: u64, key: &str) -> Option<TrieNode<V>> {
+    match node {
+        TrieNode::Branch { bitmap, slots } => {
+            let bit = 1u32 << fragment(hash, depth);
+            if bitmap & bit == 0 {
+                return None;
+            }
+            let pos = (bitmap & (bit - 1)).count_ones() as usize;
+            let replacement = match &slots[pos] {
+                Slot::Leaf(_, kv) if kv.0 == key => None,
+                Slot::Leaf(..) => return None,
+                Slot::Node(child) => match remove_node(child, depth + 1, hash, key)? {
+                    // children left with a single leaf are lifted into this
+                    // branch, so that the trie does not keep empty levels
+                    TrieNode::Branch { slots: sub, .. } if sub.is_empty() => None,
+                    TrieNode::Branch { slots: sub, .. }
+                        if sub.len() == 1 && matches!(sub[0], Slot::Leaf(..)) =>
+                    {
+                        Some(sub[0].clone())
+                    }
+                    TrieNode::Collision { hash: h, entries } if entries.len() == 1 => {
+                        Some(Slot::Leaf(h, Arc::clone(&entries[0])))
+                    }
+                    child => Some(Slot::Node(Arc::new(child))),
+                },
+            };
+            let mut slots = slots.clone();
+            let bitmap = match replacement {
+                Some(slot) => {
+                    slots[pos] = slot;
+                    *bitmap
+                }
+                None => {
+                    slots.remove(pos);
+                    bitmap & !bit
+                }
+            };
+            Some(TrieNode::Branch { bitmap, slots })
+        }
+        TrieNode::Collision { hash: h, entries } => {
+            let i = entries.iter().position(|kv| kv.0 == key)?;
+            let mut entries = entries.clone();
+            entries.remove(i);
+            Some(TrieNode::Collision { hash: *h, entries })
+        }
+    }
+}
+
+fn collect_values<'a, V>(node: &'a TrieNode<V>, out: &mut Vec<&'a V>) {
+    match node {
+        TrieNode::Branch { slots, .. } => {
+            for slot in slots {
+                match slot {
+                    Slot::Leaf(_, kv) => out.push(&kv.1),
+                    Slot::Node(child) => collect_values(child, out),
+                }
+            }
+        }
+        TrieNode::Collision { entries, .. } => out.extend(entries.iter().map(|kv| &kv.1)),
+    }
+}
+
+fn collect_keys<'a, V>(node: &'a TrieNode<V>, out: &mut Vec<&'a String>) {
+    match node {
+        TrieNode::Branch { slots, .. } => {
+            for slot in slots {
+                match slot {
+                    Slot::Leaf(_, kv) => out.push(&kv.0),
+                    Slot::Node(child) => collect_keys(child, out),
+                }
+            }
+        }
+        TrieNode::Collision { entries, .. } => out.extend(entries.iter().map(|kv| &kv.0)),
+    }
+}
+
+/// Persistent map from identifiers to values, as a hash array mapped trie
+/// (Bagwell 2001). Updates copy the path from the root to the changed
+/// entry, at most 13 nodes of at most 32 slots, and share the rest.
+#[derive(Debug)]
+struct Hamt<V> {
+    root: Arc<TrieNode<V>>,
+    len: usize,
+}
+
+impl<V> Clone for Hamt<V> {
+    fn clone(&self) -> Self {
+        Hamt {
+            root: Arc::clone(&self.root),
+            len: self.len,
+        }
+    }
+}
+
+impl<V> Hamt<V> {
+    fn new() -> Hamt<V> {
+        Hamt {
+            root: Arc::new(TrieNode::Branch {
+                bitmap: 0,
+                slots: Vec::new(),
+            }),
+            len: 0,
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<&V> {
+        self.get_hashed(hash_key(key), key)
+    }
+
+    fn get_hashed(&self, hash: u64, key: &str) -> Option<&V> {
+        let mut node = self.root.as_ref();
+        let mut depth = 0;
+        loop {
+            match node {
+                TrieNode::Branch { bitmap, slots } => {
+                    let bit = 1u32 << fragment(hash, depth);
+                    if bitmap & bit == 0 {
+                        return None;
+                    }
+                    match &slots[(bitmap & (bit - 1)).count_ones() as usize] {
+                        Slot::Leaf(_, kv) => return (kv.0 == key).then_some(&kv.1),
+                        Slot::Node(child) => node = child,
+                    }
+                    depth += 1;
+                }
+                TrieNode::Collision { entries, .. } => {
+                    return entries.iter().find(|kv| kv.0 == key).map(|kv| &kv.1);
+                }
+            }
+        }
+    }
+
+    fn insert(&self, key: &str, value: V) -> Hamt<V> {
+        self.insert_hashed(hash_key(key), key, value)
+    }
+
+    fn insert_hashed(&self, hash: u64, key: &str, value: V) -> Hamt<V> {
+        let kv = Arc::new((key.to_string(), value));
+        let (root, added) = insert_node(&self.root, 0, hash, kv);
+        Hamt {
+            root: Arc::new(root),
+            len: self.len + usize::from(added),
+        }
+    }
+
+    fn remove(&self, key: &str) -> Hamt<V> {
+        self.remove_hashed(hash_key(key), key)
+    }
+
+    fn remove_hashed(&self, hash: u64, key: &str) -> Hamt<V> {
+        match remove_node(&self.root, 0, hash, key) {
+            Some(root) => Hamt {
+                root: Arc::new(root),
+                len: self.len - 1,
+            },
+            None => self.clone(),
+        }
+    }
+
+    fn values(&self) -> Vec<&V> {
+        let mut out = Vec::with_capacity(self.len);
+        collect_values(&self.root, &mut out);
+        out
+    }
+
+    fn keys(&self) -> Vec<&String> {
+        let mut out = Vec::with_capacity(self.len);
+        collect_keys(&self.root, &mut out);
+        out
+    }
+}
+
+/// Graph whose updates return new graphs sharing memory with the old one
+/// # Description
+/// Vertices, edges and the incident edges of every vertex are stored in
+/// hash array mapped tries. Adding or removing an edge returns a new graph
+/// in `O(log n)` time and space: only the trie nodes on the path to the
+/// changed entries are copied, everything else, vertices and edges
+/// included, is shared with the previous graph through reference counts.
+/// Removing a vertex costs that for each incident edge. Cloning is
+/// constant time.
+///
+/// This suits searches over graph structures, which derive many sibling
+/// graphs from a common parent and keep them alive at the same time. Unlike
+/// [VersionedGraph](crate::graph::types::versioned::VersionedGraph), every
+/// version stays available without undoing anything. The graph implements
+/// [Graph](GraphTrait), so every algorithm accepts it.
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::types::persistent::PersistentGraph;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+///
+/// let g0: PersistentGraph<Node, Edge<Node>> = PersistentGraph::empty("g")
+///     .add_edge(Edge::empty("e1", EdgeType::Undirected, "n1", "n2"));
+/// let g1 = g0.add_edge(Edge::empty("e2", EdgeType::Undirected, "n2", "n3"));
+/// let g2 = g0.remove_node("n1");
+/// g0.size() == 1 && g1.size() == 2 && g2.size() == 0; // true
+/// g2.order() == 1; // true
+/// ```
+///
+/// # References
+/// Bagwell P. Ideal Hash Trees. 2001.
+#[derive(Debug, Clone)]
+pub struct PersistentGraph<N: NodeTrait, E: EdgeTrait<N>> {
+    graph_id: String,
+    graph_data: Arc<HashMap<String, Vec<String>>>,
+    nodes: Hamt<N>,
+    edges: Hamt<E>,
+    /// identifiers of the edges incident to each vertex
+    incidence: Hamt<Hamt<()>>,
+}
+
+impl<N: NodeTrait, E: EdgeTrait<N> + Clone> PersistentGraph<N, E> {
+    /// constructor for the [PersistentGraph] object
+    pub fn new(
+        graph_id: String,
+        graph_data: HashMap<String, Vec<String>>,
+        nodes: HashSet<N>,
+        edges: HashSet<E>,
+    ) -> PersistentGraph<N, E> {
+        let mut g = PersistentGraph {
+            graph_id,
+            graph_data: Arc::new(graph_data),
+            nodes: Hamt::new(),
+            edges: Hamt::new(),
+            incidence: Hamt::new(),
+        };
+        for n in nodes {
+            g = g.add_node(n);
+        }
+        for e in edges {
+            g = g.add_edge(e);
+        }
+        g
+    }
+
+    /// empty graph constructor
+    pub fn empty(graph_id: &str) -> PersistentGraph<N, E> {
+        PersistentGraph::new(
+            graph_id.to_string(),
+            HashMap::new(),
+            HashSet::new(),
+            HashSet::new(),
+        )
+    }
+
+    /// copy the content of a graph
+    pub fn from_graphish<G: GraphTrait<N, E>>(g: &G) -> PersistentGraph<N, E> {
+        PersistentGraph::new(
+            g.id().clone(),
+            g.data().clone(),
+            g.vertices().into_iter().cloned().collect(),
+            g.edges().into_iter().cloned().collect(),
+        )
+    }
+
+    /// copy of the content as a [Graph]
+    pub fn to_graph(&self) -> Graph<N, E> {
+        Graph::new_refs(
+            self.graph_id.clone(),
+            self.graph_data.as_ref().clone(),
+            self.vertices(),
+            self.edges(),
+        )
+    }
+
+    /// Graph with a vertex added
+    /// # Description
+    /// The graph is returned unchanged if it already has a vertex with the
+    /// same identifier.
+    pub fn add_node(&self, n: N) -> PersistentGraph<N, E> {
+        let mut g = self.clone();
+        if self.nodes.get(n.id()).is_none() {
+            g.incidence = g.incidence.insert(n.id(), Hamt::new());
+            g.nodes = g.nodes.insert(&n.id().clone(), n);
+        }
+        g
+    }
+
+    /// Graph with an edge added
+    /// # Description
+    /// End vertices missing from the graph are added too. The graph is
+    /// returned unchanged if it already has an edge with the same
+    /// identifier.
+    pub fn add_edge(&self, e: E) -> PersistentGraph<N, E> {
+        if self.edges.get(e.id()).is_some() {
+            return self.clone();
+        }
+        let mut g = self.add_node(e.start().clone()).add_node(e.end().clone());
+        for vid in [e.start().id(), e.end().id()] {
+            let incident = g.incidence.get(vid).unwrap().insert(e.id(), ());
+            g.incidence = g.incidence.insert(vid, incident);
+        }
+        g.edges = g.edges.insert(&e.id().clone(), e);
+        g
+    }
+
+    /// Graph with an edge removed, its end vertices are kept
+    pub fn remove_edge(&self, id: &str) -> PersistentGraph<N, E> {
+        let mut g = self.clone();
+        if let Some(e) = self.edges.get(id) {
+            for vid in [e.start().id(), e.end().id()] {
+                let incident = g.incidence.get(vid).unwrap().remove(id);
+                g.incidence = g.incidence.insert(vid, incident);
+            }
+            g.edges = g.edges.remove(id);
+        }
+        g
+    }
+
+    /// Graph with a vertex and its incident edges removed
+    pub fn remove_node(&self, id: &str) -> PersistentGraph<N, E> {
+        let Some(incident) = self.incidence.get(id) else {
+            return self.clone();
+        };
+        let mut g = self.clone();
+        for eid in incident.keys() {
+            g = g.remove_edge(eid);
+        }
+        g.incidence = g.incidence.remove(id);
+        g.nodes = g.nodes.remove(id);
+        g
+    }
+
+    /// edges having the vertex `id` as an end, empty if there is no such
+    /// vertex
+    pub fn incident_edges(&self, id: &str) -> Vec<&E> {
+        match self.incidence.get(id) {
+            Some(incident) => incident
+                .keys()
+                .into_iter()
+                .filter_map(|eid| self.edges.get(eid))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+/// Persistent graphs are hashed using their identifiers
+impl<N: NodeTrait, E: EdgeTrait<N>> Hash for PersistentGraph<N, E> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.graph_id.hash(state);
+    }
+}
+
+/// Persistent graphs are equal if they have the same identifier, data,
+/// vertices and edges
+impl<N: NodeTrait, E: EdgeTrait<N>> PartialEq for PersistentGraph<N, E> {
+    fn eq(&self, other: &Self) -> bool {
+        let vs: HashSet<&N> = self.nodes.values().into_iter().collect();
+        let es: HashSet<&E> = self.edges.values().into_iter().collect();
+        self.graph_id == other.graph_id
+            && self.graph_data == other.graph_data
+            && self.nodes.len == other.nodes.len
+            && self.edges.len == other.edges.len
+            && other.nodes.values().into_iter().all(|v| vs.contains(v))
+            && other.edges.values().into_iter().all(|e| es.contains(e))
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait<N>> Eq for PersistentGraph<N, E> {}
+
+/// Persistent graphs display their identifier when serialized to string.
+impl<N: NodeTrait, E: EdgeTrait<N>> fmt::Display for PersistentGraph<N, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PersistentGraph[ id: {} ]", self.graph_id)
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait<N>> GraphObject for PersistentGraph<N, E> {
+    fn id(&self) -> &String {
+        &self.graph_id
+    }
+
+    fn data(&self) -> &HashMap<String, Vec<String>> {
+        &self.graph_data
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait<N> + Clone> GraphTrait<N, E> for PersistentGraph<N, E> {
+    fn vertices(&self) -> HashSet<&N> {
+        self.nodes.values().into_iter().collect()
+    }
+
+    fn edges(&self) -> HashSet<&E> {
+        self.edges.values().into_iter().collect()
+    }
+
+    fn create(
+        graph_id: String,
+        graph_data: HashMap<String, Vec<String>>,
+        nodes: HashSet<N>,
+        edges: HashSet<E>,
+    ) -> Self {
+        PersistentGraph::new(graph_id, graph_data, nodes, edges)
+    }
+
+    fn create_from_ref(
+        graph_id: String,
+        graph_data: HashMap<String, Vec<String>>,
+        nodes: HashSet<&N>,
+        edges: HashSet<&E>,
+    ) -> Self {
+        PersistentGraph::new(
+            graph_id,
+            graph_data,
+            nodes.into_iter().cloned().collect(),
+            edges.into_iter().cloned().collect(),
+        )
+    }
+
+    fn order(&self) -> usize {
+        self.nodes.len
+    }
+
+    fn size(&self) -> usize {
+        self.edges.len
+    }
+
+    fn find_node(&self, id: &str) -> Option<&N> {
+        self.nodes.get(id)
+    }
+
+    fn find_edge(&self, id: &str) -> Option<&E> {
+        self.edges.get(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::ops::graph::boolops::is_connected;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::edgetype::EdgeType;
+    use crate::graph::types::node::Node;
+
+    fn mk_uedge(n1: &str, n2: &str, id: &str) -> Edge<Node> {
+        Edge::empty(id, EdgeType::Undirected, n1, n2)
+    }
+
+    #[test]
+    fn test_hamt_insert_get_remove() {
+        let mut versions: Vec<Hamt<usize>> = vec![Hamt::new()];
+        for i in 0..2000 {
+            let next = versions.last().unwrap().insert(&format!("k{i}"), i);
+            versions.push(next);
+        }
+        let full = versions.last().unwrap().clone();
+        assert_eq!(full.len, 2000);
+        assert_eq!(full.get("k1234"), Some(&1234));
+        assert_eq!(full.get("k2000"), None);
+        // older versions are untouched
+        assert_eq!(versions[10].len, 10);
+        assert_eq!(versions[10].get("k9"), Some(&9));
+        assert_eq!(versions[10].get("k10"), None);
+        let replaced = full.insert("k5", 50);
+        assert_eq!(replaced.len, 2000);
+        assert_eq!(replaced.get("k5"), Some(&50));
+        assert_eq!(full.get("k5"), Some(&5));
+        let mut h = full.clone();
+        for i in (0..2000).step_by(2) {
+            h = h.remove(&format!("k{i}"));
+        }
+        assert_eq!(h.len, 1000);
+        assert_eq!(h.get("k4"), None);
+        assert_eq!(h.get("k5"), Some(&5));
+        assert_eq!(h.remove("k4").len, 1000);
+        let mut values: Vec<usize> = h.values().into_iter().copied().collect();
+        values.sort();
+        assert_eq!(values, (1..2000).step_by(2).collect::<Vec<usize>>());
+        let mut empty = h;
+        for i in (1..2000).step_by(2) {
+            empty = empty.remove(&format!("k{i}"));
+        }
+        assert_eq!(empty.len, 0);
+        assert!(empty.values().is_empty());
+    }
+
+    #[test]
+    fn test_hamt_collisions() {
+        let h = Hamt::new()
+            .insert_hashed(7, "a", 1)
+            .insert_hashed(7, "b", 2)
+            .insert_hashed(7, "c", 3)
+            .insert_hashed(7 | (1 << 40), "d", 4);
+        assert_eq!(h.len, 4);
+        assert_eq!(h.get_hashed(7, "b"), Some(&2));
+        assert_eq!(h.get_hashed(7, "d"), None);
+        assert_eq!(h.get_hashed(7 | (1 << 40), "d"), Some(&4));
+        let h = h.remove_hashed(7, "a").remove_hashed(7, "c");
+        assert_eq!(h.len, 2);
+        assert_eq!(h.get_hashed(7, "b"), Some(&2));
+        assert_eq!(h.get_hashed(7, "a"), None);
+        assert!(matches!(
+            h.root.as_ref(),
+            TrieNode::Branch { slots, .. } if slots.len() == 1
+        ));
+    }
+
+    #[test]
+    fn test_versions_are_independent() {
+        let g0: PersistentGraph<Node, Edge<Node>> =
+            PersistentGraph::empty("g").add_edge(mk_uedge("a", "b", "e1"));
+        let g1 = g0.add_edge(mk_uedge("b", "c", "e2"));
+        let g2 = g1.remove_edge("e1");
+        let g3 = g1.remove_node("b");
+        assert_eq!((g0.order(), g0.size()), (2, 1));
+        assert_eq!((g1.order(), g1.size()), (3, 2));
+        assert_eq!((g2.order(), g2.size()), (3, 1));
+        assert_eq!((g3.order(), g3.size()), (2, 0));
+        assert!(is_connected(&g1));
+        assert!(!is_connected(&g2));
+        assert_eq!(g1.incident_edges("b").len(), 2);
+        assert_eq!(g2.incident_edges("b"), vec![&mk_uedge("b", "c", "e2")]);
+        assert!(g3.incident_edges("b").is_empty());
+        assert!(g3.incident_edges("a").is_empty());
+        assert_eq!(g1.add_edge(mk_uedge("c", "a", "e1")), g1);
+    }
+
+    #[test]
+    fn test_round_trip_with_graph() {
+        let base: Graph<Node, Edge<Node>> = Graph::from_edge_node_set(
+            HashSet::from([mk_uedge("a", "b", "e1"), mk_uedge("b", "c", "e2")]),
+            HashSet::from([Node::empty("d")]),
+        );
+        let g = PersistentGraph::from_graphish(&base);
+        assert_eq!(g.vertices(), base.vertices());
+        assert_eq!(g.edges(), base.edges());
+        assert_eq!(g.find_node("d"), Some(&Node::empty("d")));
+        assert_eq!(g.to_graph(), base);
+    }
+}
@@ -0,0 +1,222 @@
+//! reference counted graph shared across threads
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::graph_obj::GraphObject;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::edge::Edge;
+use crate::graph::types::graph::Graph;
+use crate::graph::types::indexed::IndexedGraph;
+use crate::graph::types::node::Node;
+use crate::graph::types::observable::ObservableGraph;
+use crate::graph::types::persistent::PersistentGraph;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+// Graphs, vertices and edges are plain owned data without interior
+// mutability, so they are Send and Sync. Compilation fails here if a change
+// breaks this.
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Node>();
+    assert_send_sync::<Edge<Node>>();
+    assert_send_sync::<Graph<Node, Edge<Node>>>();
+    assert_send_sync::<IndexedGraph<Node, Edge<Node>, Graph<Node, Edge<Node>>>>();
+    assert_send_sync::<ArcGraph<Graph<Node, Edge<Node>>>>();
+    assert_send_sync::<ObservableGraph<Node, Edge<Node>>>();
+    assert_send_sync::<PersistentGraph<Node, Edge<Node>>>();
+};
+
+/// Graph shared between threads through an [Arc]
+/// # Description
+/// Algorithms only need `&G` and never mutate a graph, and the graph types
+/// of the crate hold no interior mutability, so a graph can be read from
+/// many threads at once as soon as its vertices and edges are `Send` and
+/// `Sync`. Scoped threads can borrow a graph directly. When the threads
+/// outlive the scope that built the graph, for example tasks of a thread
+/// pool, wrap it in an `ArcGraph`: cloning it only increments a reference
+/// count. The wrapper implements [Graph] itself, so that every algorithm
+/// accepts it.
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::types::shared::ArcGraph;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+/// use std::collections::HashSet;
+/// use std::thread;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Undirected, "n1", "n2");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1]));
+/// let shared = ArcGraph::new(g);
+/// let handles: Vec<_> = (0..4)
+///     .map(|_| {
+///         let local = shared.clone();
+///         thread::spawn(move || local.order())
+///     })
+///     .collect();
+/// handles.into_iter().all(|h| h.join().unwrap() == 2); // true
+/// ```
+#[derive(Debug)]
+pub struct ArcGraph<G> {
+    graph: Arc<G>,
+}
+
+impl<G> ArcGraph<G> {
+    /// move `graph` behind a reference count
+    pub fn new(graph: G) -> ArcGraph<G> {
+        ArcGraph {
+            graph: Arc::new(graph),
+        }
+    }
+
+    /// share a graph that is already reference counted
+    pub fn from_arc(graph: Arc<G>) -> ArcGraph<G> {
+        ArcGraph { graph }
+    }
+
+    /// shared graph
+    pub fn graph(&self) -> &G {
+        &self.graph
+    }
+
+    /// reference counted pointer to the shared graph
+    pub fn as_arc(&self) -> &Arc<G> {
+        &self.graph
+    }
+}
+
+/// Cloning shares the same graph
+impl<G> Clone for ArcGraph<G> {
+    fn clone(&self) -> Self {
+        ArcGraph {
+            graph: Arc::clone(&self.graph),
+        }
+    }
+}
+
+/// Shared graphs are hashed like the graph they share
+impl<G: Hash> Hash for ArcGraph<G> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.graph().hash(state);
+    }
+}
+
+/// Shared graphs are equal if the graphs they share are equal
+impl<G: PartialEq> PartialEq for ArcGraph<G> {
+    fn eq(&self, other: &Self) -> bool {
+        self.graph() == other.graph()
+    }
+}
+
+impl<G: Eq> Eq for ArcGraph<G> {}
+
+/// Shared graphs display the identifier of their graph when serialized to
+/// string.
+impl<G: GraphObject> fmt::Display for ArcGraph<G> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ArcGraph[ id: {} ]", self.id())
+    }
+}
+
+impl<G: GraphObject> GraphObject for ArcGraph<G> {
+    fn id(&self) -> &String {
+        self.graph().id()
+    }
+
+    fn data(&self) -> &HashMap<String, Vec<String>> {
+        self.graph().data()
+    }
+}
+
+impl<N, E, G> GraphTrait<N, E> for ArcGraph<G>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    fn vertices(&self) -> HashSet<&N> {
+        self.graph().vertices()
+    }
+
+    fn edges(&self) -> HashSet<&E> {
+        self.graph().edges()
+    }
+
+    fn create(
+        graph_id: String,
+        graph_data: HashMap<String, Vec<String>>,
+        nodes: HashSet<N>,
+        edges: HashSet<E>,
+    ) -> Self {
+        ArcGraph::new(G::create(graph_id, graph_data, nodes, edges))
+    }
+
+    fn create_from_ref(
+        graph_id: String,
+        graph_data: HashMap<String, Vec<String>>,
+        nodes: HashSet<&N>,
+        edges: HashSet<&E>,
+    ) -> Self {
+        ArcGraph::new(G::create_from_ref(graph_id, graph_data, nodes, edges))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::ops::graph::bounded::bounded_bfs;
+    use crate::graph::ops::graph::bounded::TraversalLimits;
+    use crate::graph::ops::graph::search::EdgeNeighborhood;
+    use crate::graph::ops::graph::transform::reverse;
+    use crate::graph::types::edgetype::EdgeType;
+    use std::thread;
+
+    fn mk_g() -> Graph<Node, Edge<Node>> {
+        let es = HashSet::from([
+            Edge::empty("e1", EdgeType::Directed, "a", "b"),
+            Edge::empty("e2", EdgeType::Directed, "b", "c"),
+            Edge::empty("e3", EdgeType::Undirected, "c", "d"),
+        ]);
+        Graph::from_edgeset(es)
+    }
+
+    #[test]
+    fn test_arc_graph_delegates() {
+        let g = mk_g();
+        let shared = ArcGraph::new(g.clone());
+        assert_eq!(shared.id(), g.id());
+        assert_eq!(shared.vertices(), g.vertices());
+        assert_eq!(shared.edges(), g.edges());
+        let copy = shared.clone();
+        assert!(Arc::ptr_eq(copy.as_arc(), shared.as_arc()));
+        assert_eq!(copy, shared);
+        // algorithms producing graphs produce shared graphs
+        let reversed: ArcGraph<Graph<Node, Edge<Node>>> = reverse(&shared);
+        assert_eq!(reversed.graph(), &reverse(&g));
+    }
+
+    #[test]
+    fn test_arc_graph_across_threads() {
+        let shared = ArcGraph::new(mk_g());
+        let handles: Vec<thread::JoinHandle<usize>> = ["a", "b", "c", "d"]
+            .into_iter()
+            .map(|start| {
+                let local = shared.clone();
+                thread::spawn(move || {
+                    let limits = TraversalLimits::default();
+                    bounded_bfs(&local, &Node::empty(start), &EdgeNeighborhood::Out, &limits)
+                        .visited
+                        .len()
+                })
+            })
+            .collect();
+        let reached: Vec<usize> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        assert_eq!(reached, vec![4, 3, 2, 2]);
+    }
+}
@@ -0,0 +1,423 @@
+//! A rooted tree which implements the Tree trait
+
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::graph_obj::GraphObject;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::traits::tree::Tree as TreeTrait;
+use crate::graph::types::edgetype::EdgeType;
+use crate::graph::types::graph::Graph;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// Rooted tree implementing the relative [trait](TreeTrait)
+/// # Description
+/// A tree is a connected acyclic graph, see Diestel 2017, p. 13. Fixing a
+/// root orders its vertices: `x <= y` if `x` lies on the path from the
+/// root to `y`, see Diestel 2017, p. 15. Edge orientations are ignored when
+/// checking the tree structure, so edges may point towards the root, away
+/// from it or be undirected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tree<N: NodeTrait, E: EdgeTrait<N>> {
+    /// underlying graph
+    graph: Graph<N, E>,
+    /// root vertex
+    root: N,
+    /// parent identifier of every vertex but the root
+    parents: HashMap<String, String>,
+    /// distance to the root of every vertex
+    depths: HashMap<String, usize>,
+}
+
+/// Trees are hashed using their graph and their root
+impl<N: NodeTrait, E: EdgeTrait<N>> Hash for Tree<N, E> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.graph.hash(state);
+        self.root.hash(state);
+    }
+}
+
+/// Trees display their identifier and root when serialized to string.
+impl<N: NodeTrait, E: EdgeTrait<N>> fmt::Display for Tree<N, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Tree[ id: {}, root: {} ]",
+            self.graph.id(),
+            self.root.id()
+        )
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait<N>> GraphObject for Tree<N, E> {
+    fn id(&self) -> &String {
+        self.graph.id()
+    }
+
+    fn data(&self) -> &HashMap<String, Vec<String>> {
+        self.graph.data()
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait<N> + Clone> GraphTrait<N, E> for Tree<N, E> {
+    fn vertices(&self) -> HashSet<&N> {
+        self.graph.vertices()
+    }
+    fn edges(&self) -> HashSet<&E> {
+        self.graph.edges()
+    }
+    /// the root is the only vertex without incoming edge if every edge is
+    /// directed, the vertex with the smallest identifier otherwise
+    fn create(
+        graph_id: String,
+        graph_data: HashMap<String, Vec<String>>,
+        nodes: HashSet<N>,
+        edges: HashSet<E>,
+    ) -> Tree<N, E> {
+        let graph = Graph::new(graph_id, graph_data, nodes, edges);
+        let root = default_root(&graph).clone();
+        Tree::from_graph(graph, root)
+    }
+    /// see [Tree::create]
+    fn create_from_ref(
+        graph_id: String,
+        graph_data: HashMap<String, Vec<String>>,
+        nodes: HashSet<&N>,
+        edges: HashSet<&E>,
+    ) -> Tree<N, E> {
+        let graph = Graph::new_refs(graph_id, graph_data, nodes, edges);
+        let root = default_root(&graph).clone();
+        Tree::from_graph(graph, root)
+    }
+}
+
+/// root of a tree built without an explicit root
+fn default_root<N: NodeTrait, E: EdgeTrait<N> + Clone>(graph: &Graph<N, E>) -> &N {
+    let vertices = graph.vertices_sorted();
+    if vertices.is_empty() {
+        panic!("a tree needs at least one vertex");
+    }
+    let edges = graph.edges();
+    if edges.iter().all(|e| e.has_type() == &EdgeType::Directed) {
+        let heads: HashSet<&String> = edges.iter().map(|e| e.end().id()).collect();
+        let sources: Vec<&&N> = vertices
+            .iter()
+            .filter(|v| !heads.contains(v.id()))
+            .collect();
+        if let [root] = sources.as_slice() {
+            return root;
+        }
+    }
+    vertices[0]
+}
+
+impl<N: NodeTrait, E: EdgeTrait<N> + Clone> Tree<N, E> {
+    /// Create a tree rooted at `root`
+    /// # Description
+    /// Panics if `root` is not a vertex of the graph, if the graph is not
+    /// connected or if it has a cycle, loops and parallel edges included.
+    ///
+    /// # Example
+    /// ```
+    /// use pgm_rust::graph::types::edge::Edge;
+    /// use pgm_rust::graph::types::edgetype::EdgeType;
+    /// use pgm_rust::graph::types::graph::Graph;
+    /// use pgm_rust::graph::types::node::Node;
+    /// use pgm_rust::graph::types::tree::Tree;
+    /// use pgm_rust::graph::traits::tree::Tree as TreeTrait;
+    /// use std::collections::HashSet;
+    ///
+    /// let e1 = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+    /// let e2 = Edge::empty("e2", EdgeType::Undirected, "a", "c");
+    /// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+    /// let t = Tree::from_graph(g, Node::empty("a"));
+    /// t.leaves().len() == 2; // true
+    /// ```
+    pub fn from_graph(graph: Graph<N, E>, root: N) -> Tree<N, E> {
+        let root = match graph.vertices().get(&root) {
+            None => panic!("{root} not in {graph}"),
+            Some(r) => (*r).clone(),
+        };
+        let mut adjacency: HashMap<&String, Vec<&String>> = HashMap::new();
+        for e in graph.edges() {
+            adjacency
+                .entry(e.start().id())
+                .or_default()
+                .push(e.end().id());
+            adjacency
+                .entry(e.end().id())
+                .or_default()
+                .push(e.start().id());
+        }
+        let mut parents: HashMap<String, String> = HashMap::new();
+        let mut depths: HashMap<String, usize> = HashMap::from([(root.id().clone(), 0)]);
+        let mut queue: VecDeque<&String> = VecDeque::from([root.id()]);
+        while let Some(v) = queue.pop_front() {
+            let d = depths[v];
+            for w in adjacency.get(v).into_iter().flatten() {
+                if parents.get(v) == Some(*w) {
+                    continue;
+                }
+                if depths.contains_key(*w) {
+                    panic!("{graph} has a cycle through {w}");
+                }
+                depths.insert((*w).clone(), d + 1);
+                parents.insert((*w).clone(), v.clone());
+                queue.push_back(w);
+            }
+        }
+        if depths.len() != graph.order() {
+            panic!("{graph} is not connected");
+        }
+        if graph.size() + 1 != graph.order() {
+            panic!("{graph} has a cycle");
+        }
+        Tree {
+            graph,
+            root,
+            parents,
+            depths,
+        }
+    }
+
+    /// Create a tree from its identifier, data, vertices and edges, see
+    /// [Tree::from_graph]
+    pub fn new(
+        tree_id: String,
+        tree_data: HashMap<String, Vec<String>>,
+        root: N,
+        nodes: HashSet<N>,
+        edges: HashSet<E>,
+    ) -> Tree<N, E> {
+        Tree::from_graph(Graph::new(tree_id, tree_data, nodes, edges), root)
+    }
+
+    /// underlying graph
+    pub fn graph(&self) -> &Graph<N, E> {
+        &self.graph
+    }
+
+    /// vertex with identifier `id`
+    fn vertex(&self, id: &str) -> &N {
+        match self.graph.vertices().into_iter().find(|v| v.id() == id) {
+            None => panic!("{id} not in {self}"),
+            Some(v) => v,
+        }
+    }
+
+    /// identifier of a vertex of the tree, panics if `n` is not one
+    fn checked_id<'a>(&self, n: &'a N) -> &'a String {
+        if !self.depths.contains_key(n.id()) {
+            panic!("{n} not in {self}");
+        }
+        n.id()
+    }
+
+    /// parent of `n`, `None` for the root
+    pub fn parent_of(&self, n: &N) -> Option<&N> {
+        let id = self.checked_id(n);
+        self.parents.get(id).map(|p| self.vertex(p))
+    }
+
+    /// children of `n` ordered by identifier
+    pub fn children_of(&self, n: &N) -> Vec<&N> {
+        let id = self.checked_id(n);
+        let mut children: Vec<&N> = self
+            .graph
+            .vertices()
+            .into_iter()
+            .filter(|v| self.parents.get(v.id()) == Some(id))
+            .collect();
+        children.sort_by(|a, b| a.id().cmp(b.id()));
+        children
+    }
+
+    /// identifiers of the vertices on the path from the root to `n`
+    fn ancestor_ids(&self, n: &N) -> Vec<&String> {
+        let id = match self.depths.get_key_value(n.id()) {
+            None => panic!("{n} not in {self}"),
+            Some((id, _)) => id,
+        };
+        let mut ids = vec![id];
+        while let Some(p) = self.parents.get(*ids.last().unwrap()) {
+            ids.push(p);
+        }
+        ids
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait<N> + Clone> TreeTrait<N, E> for Tree<N, E> {
+    /// true if `x_src` is in the up-closure of `y_dst`, that is
+    /// `y_dst <= x_src`
+    fn is_upclosure_of(&self, x_src: &N, y_dst: &N) -> bool {
+        self.less_than_or_equal(y_dst, x_src)
+    }
+
+    /// true if `x_src` is in the down-closure of `y_dst`, that is
+    /// `x_src <= y_dst`
+    fn is_downclosure_of(&self, x_src: &N, y_dst: &N) -> bool {
+        self.less_than_or_equal(x_src, y_dst)
+    }
+
+    /// vertices `y` with `x_src <= y`, the subtree rooted at `x_src`
+    fn upset_of(&self, x_src: &N) -> HashSet<&N> {
+        self.checked_id(x_src);
+        self.graph
+            .vertices()
+            .into_iter()
+            .filter(|y| self.less_than_or_equal(x_src, y))
+            .collect()
+    }
+
+    /// vertices `y` with `y <= x_src`, the path from the root to `x_src`
+    fn downset_of(&self, x_src: &N) -> HashSet<&N> {
+        self.ancestor_ids(x_src)
+            .into_iter()
+            .map(|id| self.vertex(id))
+            .collect()
+    }
+
+    fn root(&self) -> &N {
+        &self.root
+    }
+
+    /// vertices without children. The root of a single vertex tree is a leaf.
+    fn leaves(&self) -> HashSet<&N> {
+        let parents: HashSet<&String> = self.parents.values().collect();
+        self.graph
+            .vertices()
+            .into_iter()
+            .filter(|v| !parents.contains(v.id()))
+            .collect()
+    }
+
+    /// distance from the root
+    fn height_of(&self, n: &N) -> i32 {
+        self.depths[self.checked_id(n)] as i32
+    }
+
+    fn nodes_per_height(&self, height: i32) -> HashSet<&N> {
+        self.graph
+            .vertices()
+            .into_iter()
+            .filter(|v| self.depths[v.id()] as i32 == height)
+            .collect()
+    }
+
+    /// true if `first` lies on the path from the root to `second`
+    fn less_than_or_equal(&self, first: &N, second: &N) -> bool {
+        let id = self.checked_id(first);
+        self.ancestor_ids(second).contains(&id)
+    }
+
+    /// true if `second` lies on the path from the root to `first`
+    fn greater_than_or_equal(&self, first: &N, second: &N) -> bool {
+        self.less_than_or_equal(second, first)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::node::Node;
+
+    fn mk_uedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Undirected, n1_id, n2_id)
+    }
+
+    /// a with children b and c, b with children d and e
+    fn mk_t1() -> Tree<Node, Edge<Node>> {
+        let es = HashSet::from([
+            mk_uedge("a", "b", "e1"),
+            mk_uedge("c", "a", "e2"),
+            mk_uedge("b", "d", "e3"),
+            mk_uedge("b", "e", "e4"),
+        ]);
+        Tree::new(
+            "t1".to_string(),
+            HashMap::new(),
+            Node::empty("a"),
+            HashSet::new(),
+            es,
+        )
+    }
+
+    fn ids(ns: HashSet<&Node>) -> Vec<String> {
+        let mut v: Vec<String> = ns.into_iter().map(|n| n.id().clone()).collect();
+        v.sort();
+        v
+    }
+
+    #[test]
+    fn test_tree_order() {
+        let t = mk_t1();
+        let (a, b, d) = (Node::empty("a"), Node::empty("b"), Node::empty("d"));
+        assert_eq!(t.root(), &a);
+        assert!(t.less_than_or_equal(&a, &d));
+        assert!(t.less_than_or_equal(&b, &b));
+        assert!(!t.less_than_or_equal(&d, &b));
+        assert!(t.greater_than_or_equal(&d, &b));
+        assert!(t.is_upclosure_of(&d, &b));
+        assert!(t.is_downclosure_of(&b, &d));
+        assert_eq!(ids(t.upset_of(&b)), vec!["b", "d", "e"]);
+        assert_eq!(ids(t.downset_of(&d)), vec!["a", "b", "d"]);
+    }
+
+    #[test]
+    fn test_tree_levels() {
+        let t = mk_t1();
+        assert_eq!(ids(t.leaves()), vec!["c", "d", "e"]);
+        assert_eq!(t.height_of(&Node::empty("e")), 2);
+        assert_eq!(ids(t.nodes_per_height(1)), vec!["b", "c"]);
+        assert_eq!(t.parent_of(&Node::empty("c")), Some(&Node::empty("a")));
+        assert_eq!(t.parent_of(&Node::empty("a")), None);
+        let children: Vec<&String> = t
+            .children_of(&Node::empty("b"))
+            .iter()
+            .map(|n| n.id())
+            .collect();
+        assert_eq!(children, vec!["d", "e"]);
+    }
+
+    #[test]
+    fn test_tree_create_directed_root() {
+        let e1 = Edge::empty("e1", EdgeType::Directed, "r", "a");
+        let e2 = Edge::empty("e2", EdgeType::Directed, "r", "b");
+        let t: Tree<Node, Edge<Node>> = Tree::create(
+            "t".to_string(),
+            HashMap::new(),
+            HashSet::new(),
+            HashSet::from([e1, e2]),
+        );
+        assert_eq!(t.root(), &Node::empty("r"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_tree_cycle() {
+        let es = HashSet::from([
+            mk_uedge("a", "b", "e1"),
+            mk_uedge("b", "c", "e2"),
+            mk_uedge("c", "a", "e3"),
+        ]);
+        Tree::new(
+            "t".to_string(),
+            HashMap::new(),
+            Node::empty("a"),
+            HashSet::new(),
+            es,
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_tree_disconnected() {
+        let es = HashSet::from([mk_uedge("a", "b", "e1")]);
+        let ns = HashSet::from([Node::empty("c")]);
+        Tree::new("t".to_string(), HashMap::new(), Node::empty("a"), ns, es);
+    }
+}
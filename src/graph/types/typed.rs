@@ -0,0 +1,303 @@
+// nodes and edges carrying a typed payload
+
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph_obj::GraphObject;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::edgetype::EdgeType;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// Node object carrying a payload of any type.
+/// Behaves like [Node](crate::graph::types::node::Node) but stores a value
+/// of type `P` next to the string data, so that variable metadata,
+/// coordinates or enum kinds need not be encoded as strings. Equality and
+/// hashing only look at the identifier. Operations building new vertices
+/// through [NodeTrait::create] give them the default payload.
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::typed::{TypedEdge, TypedNode};
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+/// use std::collections::HashSet;
+///
+/// #[derive(Debug, Clone, Default, PartialEq)]
+/// enum Kind {
+///     #[default]
+///     Latent,
+///     Observed,
+/// }
+///
+/// let a = TypedNode::from_payload("a", Kind::Observed);
+/// let b = TypedNode::from_payload("b", Kind::Latent);
+/// let e = TypedEdge::directed("e1", a.clone(), b, 0.5);
+/// let g: Graph<TypedNode<Kind>, TypedEdge<TypedNode<Kind>, f64>> =
+///     Graph::from_edgeset(HashSet::from([e]));
+/// let observed: Vec<_> = g.vertices().into_iter().filter(|v| v.payload() == &Kind::Observed).collect();
+/// observed == vec![&a]; // true
+/// ```
+#[derive(Debug, Clone)]
+pub struct TypedNode<P> {
+    node_id: String,
+    node_data: HashMap<String, Vec<String>>,
+    payload: P,
+}
+
+impl<P> TypedNode<P> {
+    /// constructor for typed node
+    pub fn new(nid: String, ndata: HashMap<String, Vec<String>>, payload: P) -> TypedNode<P> {
+        TypedNode {
+            node_id: nid,
+            node_data: ndata,
+            payload,
+        }
+    }
+    /// constructor for typed node without string data
+    pub fn from_payload(nid: &str, payload: P) -> TypedNode<P> {
+        TypedNode::new(nid.to_string(), HashMap::new(), payload)
+    }
+    /// payload of the node
+    pub fn payload(&self) -> &P {
+        &self.payload
+    }
+    /// mutable payload of the node
+    pub fn payload_mut(&mut self) -> &mut P {
+        &mut self.payload
+    }
+    /// consume the node and return its payload
+    pub fn into_payload(self) -> P {
+        self.payload
+    }
+}
+
+impl<P> fmt::Display for TypedNode<P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TypedNode[ id: {} ]", self.node_id)
+    }
+}
+
+impl<P> PartialEq for TypedNode<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.node_id == other.node_id
+    }
+}
+
+impl<P> Eq for TypedNode<P> {}
+
+impl<P> Hash for TypedNode<P> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.node_id.hash(state);
+    }
+}
+
+impl<P> GraphObject for TypedNode<P> {
+    fn id(&self) -> &String {
+        &self.node_id
+    }
+
+    fn data(&self) -> &HashMap<String, Vec<String>> {
+        &self.node_data
+    }
+}
+
+impl<P: Clone + Default> NodeTrait for TypedNode<P> {
+    fn create(nid: String, ndata: HashMap<String, Vec<String>>) -> TypedNode<P> {
+        TypedNode::new(nid, ndata, P::default())
+    }
+}
+
+/// Edge object carrying a payload of any type.
+/// The edge counterpart of [TypedNode]: equality and hashing only look at
+/// the identifier, and edges built through [EdgeTrait::create] get the
+/// default payload.
+#[derive(Debug, Clone)]
+pub struct TypedEdge<T: NodeTrait, P> {
+    edge_id: String,
+    edge_data: HashMap<String, Vec<String>>,
+    edge_type: EdgeType,
+    start_node: T,
+    end_node: T,
+    payload: P,
+}
+
+impl<T: NodeTrait, P> TypedEdge<T, P> {
+    /// constructor for typed edge
+    pub fn new(
+        eid: String,
+        e_data: HashMap<String, Vec<String>>,
+        snode: T,
+        enode: T,
+        etype: EdgeType,
+        payload: P,
+    ) -> TypedEdge<T, P> {
+        TypedEdge {
+            edge_id: eid,
+            edge_data: e_data,
+            edge_type: etype,
+            start_node: snode,
+            end_node: enode,
+            payload,
+        }
+    }
+    /// directed typed edge constructor without string data
+    pub fn directed(eid: &str, snode: T, enode: T, payload: P) -> TypedEdge<T, P> {
+        TypedEdge::new(
+            eid.to_string(),
+            HashMap::new(),
+            snode,
+            enode,
+            EdgeType::Directed,
+            payload,
+        )
+    }
+    /// undirected typed edge constructor without string data
+    pub fn undirected(eid: &str, snode: T, enode: T, payload: P) -> TypedEdge<T, P> {
+        TypedEdge::new(
+            eid.to_string(),
+            HashMap::new(),
+            snode,
+            enode,
+            EdgeType::Undirected,
+            payload,
+        )
+    }
+    /// payload of the edge
+    pub fn payload(&self) -> &P {
+        &self.payload
+    }
+    /// mutable payload of the edge
+    pub fn payload_mut(&mut self) -> &mut P {
+        &mut self.payload
+    }
+    /// consume the edge and return its payload
+    pub fn into_payload(self) -> P {
+        self.payload
+    }
+}
+
+impl<T: NodeTrait, P> fmt::Display for TypedEdge<T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "TypedEdge[ id: {}, start: {}, end: {}, type: {} ]",
+            self.edge_id, self.start_node, self.end_node, self.edge_type
+        )
+    }
+}
+
+impl<T: NodeTrait, P> PartialEq for TypedEdge<T, P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.edge_id == other.edge_id
+    }
+}
+
+impl<T: NodeTrait, P> Eq for TypedEdge<T, P> {}
+
+impl<T: NodeTrait, P> Hash for TypedEdge<T, P> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.edge_id.hash(state);
+    }
+}
+
+impl<T: NodeTrait, P> GraphObject for TypedEdge<T, P> {
+    fn id(&self) -> &String {
+        &self.edge_id
+    }
+
+    fn data(&self) -> &HashMap<String, Vec<String>> {
+        &self.edge_data
+    }
+}
+
+impl<T: NodeTrait, P: Default> EdgeTrait<T> for TypedEdge<T, P> {
+    fn start(&self) -> &T {
+        &self.start_node
+    }
+    fn end(&self) -> &T {
+        &self.end_node
+    }
+    fn has_type(&self) -> &EdgeType {
+        &self.edge_type
+    }
+    fn create(
+        eid: String,
+        e_data: HashMap<String, Vec<String>>,
+        snode: T,
+        enode: T,
+        etype: EdgeType,
+    ) -> TypedEdge<T, P> {
+        TypedEdge::new(eid, e_data, snode, enode, etype, P::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::ops::graph::node::vertex_by_id;
+    use crate::graph::traits::graph::Graph as GraphTrait;
+    use crate::graph::types::graph::Graph;
+    use std::collections::HashSet;
+
+    #[derive(Debug, Clone, Default, PartialEq)]
+    struct Coord {
+        x: f64,
+        y: f64,
+    }
+
+    type CoordNode = TypedNode<Coord>;
+
+    fn mk_node(id: &str, x: f64, y: f64) -> CoordNode {
+        TypedNode::from_payload(id, Coord { x, y })
+    }
+
+    #[test]
+    fn test_eq_and_hash_on_id() {
+        let n1 = mk_node("a", 0.0, 0.0);
+        let n2 = mk_node("a", 1.0, 1.0);
+        assert_eq!(n1, n2);
+        assert_eq!(HashSet::from([n1, n2]).len(), 1);
+        let e1 = TypedEdge::directed("e", mk_node("a", 0.0, 0.0), mk_node("b", 0.0, 0.0), 1);
+        let e2 = TypedEdge::directed("e", mk_node("b", 0.0, 0.0), mk_node("a", 0.0, 0.0), 2);
+        assert_eq!(e1, e2);
+    }
+
+    #[test]
+    fn test_payload_access() {
+        let mut n = mk_node("a", 1.0, 2.0);
+        n.payload_mut().x = 3.0;
+        assert_eq!(n.payload(), &Coord { x: 3.0, y: 2.0 });
+        assert_eq!(n.id(), "a");
+        assert!(n.data().is_empty());
+        assert_eq!(n.into_payload().y, 2.0);
+    }
+
+    #[test]
+    fn test_create_uses_default_payload() {
+        let n: CoordNode = NodeTrait::create("a".to_string(), HashMap::new());
+        assert_eq!(n.payload(), &Coord::default());
+        let e: TypedEdge<CoordNode, u32> = EdgeTrait::create(
+            "e".to_string(),
+            HashMap::new(),
+            n.clone(),
+            n,
+            EdgeType::Undirected,
+        );
+        assert_eq!(e.payload(), &0);
+    }
+
+    #[test]
+    fn test_typed_graph() {
+        let a = mk_node("a", 0.0, 0.0);
+        let b = mk_node("b", 3.0, 4.0);
+        let e = TypedEdge::undirected("e1", a, b, "road".to_string());
+        let g: Graph<CoordNode, TypedEdge<CoordNode, String>> =
+            Graph::from_edgeset(HashSet::from([e]));
+        assert_eq!(g.order(), 2);
+        let b = vertex_by_id(&g, "b");
+        assert_eq!(b.payload(), &Coord { x: 3.0, y: 4.0 });
+        let e = g.find_edge("e1").unwrap();
+        assert_eq!(e.payload(), "road");
+    }
+}
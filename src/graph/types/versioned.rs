@@ -0,0 +1,408 @@
+//! mutable graph with undo, redo and checkpoints
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::graph_obj::GraphObject;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::observable::{GraphEvent, ObservableGraph};
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// position in the history of a [VersionedGraph], see
+/// [checkpoint](VersionedGraph::checkpoint)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Checkpoint {
+    /// serial number of the last change applied, `None` before any change
+    step: Option<usize>,
+}
+
+/// events produced by one change
+#[derive(Debug, Clone)]
+struct Step<N, E> {
+    serial: usize,
+    events: Vec<GraphEvent<N, E>>,
+}
+
+/// Graph whose changes can be undone and redone
+/// # Description
+/// Every change applied through the graph is kept as the list of
+/// [GraphEvent]s it produced, so rolling back costs as much as the change
+/// itself rather than a copy of the whole graph. [undo](VersionedGraph::undo)
+/// reverts the last change and [redo](VersionedGraph::redo) applies it
+/// again. A new change discards the changes that were undone, as in an
+/// editor.
+///
+/// A [Checkpoint] marks the current position in the history, and
+/// [restore](VersionedGraph::restore) undoes or redoes changes until that
+/// position is reached again, which suits search procedures that backtrack.
+///
+/// The graph wraps an [ObservableGraph]: undoing and redoing are changes
+/// too and are recorded in its log, so dependents following it stay in
+/// sync.
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::graph::types::versioned::VersionedGraph;
+/// use pgm_rust::graph::traits::graph::Graph as GraphTrait;
+///
+/// let mut g: VersionedGraph<Node, Edge<Node>> = VersionedGraph::empty("g");
+/// g.add_edge(Edge::empty("e1", EdgeType::Undirected, "n1", "n2"));
+/// let before = g.checkpoint();
+/// g.add_edge(Edge::empty("e2", EdgeType::Undirected, "n2", "n3"));
+/// g.remove_node("n1");
+/// g.size() == 1; // true
+/// g.restore(before);
+/// g.size() == 1 && g.contains_edge_id("e1"); // true
+/// g.redo();
+/// g.contains_edge_id("e2"); // true
+/// ```
+#[derive(Debug, Clone)]
+pub struct VersionedGraph<N: NodeTrait, E: EdgeTrait<N>> {
+    graph: ObservableGraph<N, E>,
+    done: Vec<Step<N, E>>,
+    undone: Vec<Step<N, E>>,
+    next_serial: usize,
+}
+
+impl<N: NodeTrait, E: EdgeTrait<N> + Clone> VersionedGraph<N, E> {
+    /// constructor, the history starts empty whatever the initial content
+    pub fn new(
+        graph_id: String,
+        graph_data: HashMap<String, Vec<String>>,
+        nodes: HashSet<N>,
+        edges: HashSet<E>,
+    ) -> VersionedGraph<N, E> {
+        VersionedGraph::from_observable(ObservableGraph::new(graph_id, graph_data, nodes, edges))
+    }
+
+    /// empty graph constructor
+    pub fn empty(graph_id: &str) -> VersionedGraph<N, E> {
+        VersionedGraph::from_observable(ObservableGraph::empty(graph_id))
+    }
+
+    /// copy the content of a graph, see [VersionedGraph::new]
+    pub fn from_graphish<G: GraphTrait<N, E>>(g: &G) -> VersionedGraph<N, E> {
+        VersionedGraph::from_observable(ObservableGraph::from_graphish(g))
+    }
+
+    /// track the changes of an observable graph from now on
+    pub fn from_observable(graph: ObservableGraph<N, E>) -> VersionedGraph<N, E> {
+        VersionedGraph {
+            graph,
+            done: Vec::new(),
+            undone: Vec::new(),
+            next_serial: 0,
+        }
+    }
+
+    /// the underlying graph and its event log
+    pub fn graph(&self) -> &ObservableGraph<N, E> {
+        &self.graph
+    }
+
+    /// record the events `change` produces on the graph as one step
+    fn record<T>(&mut self, change: impl FnOnce(&mut ObservableGraph<N, E>) -> T) -> T {
+        let version = self.graph.version();
+        let result = change(&mut self.graph);
+        let events = self.graph.events_since(version).to_vec();
+        if !events.is_empty() {
+            self.done.push(Step {
+                serial: self.next_serial,
+                events,
+            });
+            self.next_serial += 1;
+            self.undone.clear();
+        }
+        result
+    }
+
+    /// add a vertex, see [ObservableGraph::add_node]
+    pub fn add_node(&mut self, n: N) -> bool {
+        self.record(|g| g.add_node(n))
+    }
+
+    /// add an edge and its missing end vertices, see
+    /// [ObservableGraph::add_edge]
+    pub fn add_edge(&mut self, e: E) -> bool {
+        self.record(|g| g.add_edge(e))
+    }
+
+    /// remove an edge, see [ObservableGraph::remove_edge]
+    pub fn remove_edge(&mut self, id: &str) -> Option<E> {
+        self.record(|g| g.remove_edge(id))
+    }
+
+    /// remove a vertex and its incident edges, see
+    /// [ObservableGraph::remove_node]
+    pub fn remove_node(&mut self, id: &str) -> Option<N> {
+        self.record(|g| g.remove_node(id))
+    }
+
+    /// whether there is a change to undo
+    pub fn can_undo(&self) -> bool {
+        !self.done.is_empty()
+    }
+
+    /// whether there is an undone change to redo
+    pub fn can_redo(&self) -> bool {
+        !self.undone.is_empty()
+    }
+
+    /// Revert the last change
+    /// # Description
+    /// Returns false if there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        let Some(step) = self.done.pop() else {
+            return false;
+        };
+        for event in step.events.iter().rev() {
+            match event {
+                GraphEvent::NodeAdded(n) => {
+                    self.graph.remove_node(n.id());
+                }
+                GraphEvent::NodeRemoved(n) => {
+                    self.graph.add_node(n.clone());
+                }
+                GraphEvent::EdgeAdded(e) => {
+                    self.graph.remove_edge(e.id());
+                }
+                GraphEvent::EdgeRemoved(e) => {
+                    self.graph.add_edge(e.clone());
+                }
+            }
+        }
+        self.undone.push(step);
+        true
+    }
+
+    /// Apply the last undone change again
+    /// # Description
+    /// Returns false if there is nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        let Some(step) = self.undone.pop() else {
+            return false;
+        };
+        for event in step.events.iter() {
+            match event {
+                GraphEvent::NodeAdded(n) => {
+                    self.graph.add_node(n.clone());
+                }
+                GraphEvent::NodeRemoved(n) => {
+                    self.graph.remove_node(n.id());
+                }
+                GraphEvent::EdgeAdded(e) => {
+                    self.graph.add_edge(e.clone());
+                }
+                GraphEvent::EdgeRemoved(e) => {
+                    self.graph.remove_edge(e.id());
+                }
+            }
+        }
+        self.done.push(step);
+        true
+    }
+
+    /// current position in the history
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            step: self.done.last().map(|s| s.serial),
+        }
+    }
+
+    /// Go back or forth to a checkpoint
+    /// # Description
+    /// Undoes or redoes changes until the graph is in the state it had when
+    /// `checkpoint` was taken. Changes undone this way can still be redone.
+    /// Panics if the checkpoint was taken on changes that have since been
+    /// discarded by a new change.
+    pub fn restore(&mut self, checkpoint: Checkpoint) {
+        let Some(serial) = checkpoint.step else {
+            while self.undo() {}
+            return;
+        };
+        if self.done.iter().any(|s| s.serial == serial) {
+            while self.done.last().map(|s| s.serial) != Some(serial) {
+                self.undo();
+            }
+        } else if self.undone.iter().any(|s| s.serial == serial) {
+            while self.done.last().map(|s| s.serial) != Some(serial) {
+                self.redo();
+            }
+        } else {
+            panic!("checkpoint is not in the history of {self}");
+        }
+    }
+}
+
+/// Versioned graphs are hashed like the graph they hold
+impl<N: NodeTrait, E: EdgeTrait<N>> Hash for VersionedGraph<N, E> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.graph.hash(state);
+    }
+}
+
+/// Versioned graphs are equal if their content is, their histories are
+/// ignored
+impl<N: NodeTrait, E: EdgeTrait<N>> PartialEq for VersionedGraph<N, E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.graph == other.graph
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait<N>> Eq for VersionedGraph<N, E> {}
+
+/// Versioned graphs display their identifier when serialized to string.
+impl<N: NodeTrait, E: EdgeTrait<N>> fmt::Display for VersionedGraph<N, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "VersionedGraph[ id: {} ]", self.graph.id())
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait<N>> GraphObject for VersionedGraph<N, E> {
+    fn id(&self) -> &String {
+        self.graph.id()
+    }
+
+    fn data(&self) -> &HashMap<String, Vec<String>> {
+        self.graph.data()
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait<N> + Clone> GraphTrait<N, E> for VersionedGraph<N, E> {
+    fn vertices(&self) -> HashSet<&N> {
+        self.graph.vertices()
+    }
+
+    fn edges(&self) -> HashSet<&E> {
+        self.graph.edges()
+    }
+
+    fn create(
+        graph_id: String,
+        graph_data: HashMap<String, Vec<String>>,
+        nodes: HashSet<N>,
+        edges: HashSet<E>,
+    ) -> Self {
+        VersionedGraph::new(graph_id, graph_data, nodes, edges)
+    }
+
+    fn create_from_ref(
+        graph_id: String,
+        graph_data: HashMap<String, Vec<String>>,
+        nodes: HashSet<&N>,
+        edges: HashSet<&E>,
+    ) -> Self {
+        VersionedGraph::from_observable(ObservableGraph::create_from_ref(
+            graph_id, graph_data, nodes, edges,
+        ))
+    }
+
+    fn find_node(&self, id: &str) -> Option<&N> {
+        self.graph.find_node(id)
+    }
+
+    fn find_edge(&self, id: &str) -> Option<&E> {
+        self.graph.find_edge(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::edgetype::EdgeType;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+
+    fn mk_uedge(n1: &str, n2: &str, id: &str) -> Edge<Node> {
+        Edge::empty(id, EdgeType::Undirected, n1, n2)
+    }
+
+    fn ids(g: &VersionedGraph<Node, Edge<Node>>) -> (Vec<String>, Vec<String>) {
+        let vs = g.vertices_sorted().iter().map(|v| v.id().clone()).collect();
+        let es = g.edges_sorted().iter().map(|e| e.id().clone()).collect();
+        (vs, es)
+    }
+
+    #[test]
+    fn test_undo_redo() {
+        let mut g: VersionedGraph<Node, Edge<Node>> = VersionedGraph::empty("g");
+        assert!(!g.undo());
+        g.add_edge(mk_uedge("a", "b", "e1"));
+        g.add_edge(mk_uedge("b", "c", "e2"));
+        let full = ids(&g);
+        // removing b also removes both edges, undone as one change
+        g.remove_node("b");
+        assert_eq!(g.size(), 0);
+        assert!(g.undo());
+        assert_eq!(ids(&g), full);
+        assert!(g.undo());
+        assert_eq!(ids(&g), (vec!["a".into(), "b".into()], vec!["e1".into()]));
+        assert!(g.redo());
+        assert!(g.redo());
+        assert_eq!(g.order(), 2);
+        assert!(!g.redo());
+        assert!(g.can_undo());
+    }
+
+    #[test]
+    fn test_no_op_changes_are_not_recorded() {
+        let mut g: VersionedGraph<Node, Edge<Node>> = VersionedGraph::empty("g");
+        g.add_node(Node::empty("a"));
+        assert!(!g.add_node(Node::empty("a")));
+        assert_eq!(g.remove_edge("e1"), None);
+        assert!(g.undo());
+        assert!(!g.can_undo());
+        assert_eq!(g.order(), 0);
+    }
+
+    #[test]
+    fn test_new_change_discards_redo() {
+        let mut g: VersionedGraph<Node, Edge<Node>> = VersionedGraph::empty("g");
+        g.add_node(Node::empty("a"));
+        g.undo();
+        assert!(g.can_redo());
+        g.add_node(Node::empty("b"));
+        assert!(!g.can_redo());
+    }
+
+    #[test]
+    fn test_checkpoint_restore() {
+        let base: Graph<Node, Edge<Node>> =
+            Graph::from_edgeset(HashSet::from([mk_uedge("a", "b", "e1")]));
+        let mut g = VersionedGraph::from_graphish(&base);
+        let start = g.checkpoint();
+        g.add_edge(mk_uedge("b", "c", "e2"));
+        let middle = g.checkpoint();
+        let at_middle = ids(&g);
+        g.remove_edge("e1");
+        g.add_edge(mk_uedge("c", "d", "e3"));
+        let end = ids(&g);
+        g.restore(middle);
+        assert_eq!(ids(&g), at_middle);
+        g.restore(start);
+        assert_eq!(g.edges(), base.edges());
+        g.restore(middle);
+        assert_eq!(ids(&g), at_middle);
+        g.redo();
+        g.redo();
+        assert_eq!(ids(&g), end);
+        // undoing is visible in the event log of the underlying graph
+        assert!(g.graph().version() > 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_restore_discarded_checkpoint() {
+        let mut g: VersionedGraph<Node, Edge<Node>> = VersionedGraph::empty("g");
+        g.add_node(Node::empty("a"));
+        let cp = g.checkpoint();
+        g.undo();
+        g.add_node(Node::empty("b"));
+        g.restore(cp);
+    }
+}
@@ -11,3 +11,6 @@ pub mod graph;
 
 /// probabilistic graphs
 pub mod pgm;
+
+/// seeded pseudo random number generation
+pub mod rng;
@@ -1,2 +1,43 @@
 // pgm module declarations
 
+/// chain graphs mixing directed and undirected edges
+pub mod chaingraph;
+
+/// bipartite graphs of variables and factors
+pub mod factorgraph;
+
+/// discrete random variables
+pub mod variable;
+
+/// conditional probability distributions
+pub mod cpd;
+
+/// discrete data sets
+pub mod data;
+
+/// bayesian networks
+pub mod bayesnet;
+
+/// learning models from data
+pub mod learning;
+
+/// queries shared by inference engines
+pub mod query;
+
+/// inference algorithms
+pub mod inference;
+
+/// discrete time markov chains
+pub mod markovchain;
+
+/// influence diagrams
+pub mod influence;
+
+/// template models repeating structures over plates
+pub mod template;
+
+/// model input output
+pub mod io;
+
+/// model evaluation
+pub mod eval;
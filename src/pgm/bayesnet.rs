@@ -0,0 +1,504 @@
+//! bayesian networks
+use crate::graph::ops::graph::search::topological_sort;
+use crate::graph::traits::dyngraph::{DynGraph, DynView, EdgeDescription};
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::graph_obj::GraphObject as GraphObjectTrait;
+use crate::graph::types::edge::Edge;
+use crate::graph::types::edgetype::EdgeType;
+use crate::graph::types::graph::Graph;
+use crate::graph::types::node::Node;
+use crate::pgm::cpd::TabularCPD;
+use crate::pgm::query::Query;
+use crate::pgm::query::{
+    Distribution, Evidence, InferenceEngine, LikelihoodWeighting, VariableElimination,
+};
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// largest number of entries of a factor built by exact inference for which
+/// [BayesianNetwork::posterior] uses it, see
+/// [VariableElimination::largest_factor_size]
+pub const EXACT_INFERENCE_ENTRIES: usize = 1 << 22;
+
+/// number of samples drawn by [BayesianNetwork::posterior] when inference
+/// is approximate
+pub const POSTERIOR_SAMPLES: usize = 100_000;
+
+/// vertices of a directed graph in topological order, smallest identifier
+/// first among available vertices. None if the graph has a cycle.
+fn topological_ids(g: &Graph<Node, Edge<Node>>) -> Option<Vec<&String>> {
+    topological_sort(g).map(|vs| vs.into_iter().map(|v| v.id()).collect())
+}
+
+/// Bayesian network
+/// # Description
+/// A directed acyclic graph whose vertices are random variables, together
+/// with a conditional probability table per variable given its parents.
+/// Tables are optional so that a network can hold a structure before its
+/// parameters are known, see [BayesianNetwork::is_fully_specified].
+///
+/// # References
+/// Koller D, Friedman N. Probabilistic Graphical Models. 2009, p. 62.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BayesianNetwork {
+    graph: Graph<Node, Edge<Node>>,
+    cpds: HashMap<String, TabularCPD>,
+}
+
+impl BayesianNetwork {
+    /// Create a network without tables from its graph
+    /// # Description
+    /// Panics if an edge of `graph` is undirected or if `graph` has a
+    /// directed cycle.
+    pub fn new(graph: Graph<Node, Edge<Node>>) -> BayesianNetwork {
+        for e in graph.edges() {
+            if e.has_type() != &EdgeType::Directed {
+                panic!("{e} of {graph} is not directed");
+            }
+        }
+        if topological_ids(&graph).is_none() {
+            panic!("{graph} has a directed cycle");
+        }
+        BayesianNetwork {
+            graph,
+            cpds: HashMap::new(),
+        }
+    }
+
+    /// Create a network without tables from variable names and arcs
+    /// # Description
+    /// The arc from `a` to `b` gets the identifier `a->b`.
+    ///
+    /// # Example
+    /// ```
+    /// use pgm_rust::pgm::bayesnet::BayesianNetwork;
+    ///
+    /// let bn = BayesianNetwork::from_arcs("sprinkler", &["rain", "sprinkler", "wet"],
+    ///     &[("rain", "wet"), ("sprinkler", "wet")]);
+    /// bn.parents("wet").len() == 2; // true
+    /// ```
+    pub fn from_arcs(id: &str, variables: &[&str], arcs: &[(&str, &str)]) -> BayesianNetwork {
+        match BayesianNetwork::try_from_arcs(id, variables, arcs) {
+            Err(msg) => panic!("{msg}"),
+            Ok(bn) => bn,
+        }
+    }
+
+    /// Create a network from variable names and arcs, see
+    /// [BayesianNetwork::from_arcs]
+    /// # Description
+    /// Returns the reason as an error instead of panicking when an arc
+    /// mentions an unknown variable or when the arcs have a directed cycle.
+    ///
+    /// # Example
+    /// ```
+    /// use pgm_rust::pgm::bayesnet::BayesianNetwork;
+    ///
+    /// let arcs = [("a", "b"), ("b", "a")];
+    /// BayesianNetwork::try_from_arcs("bn", &["a", "b"], &arcs).is_err(); // true
+    /// ```
+    pub fn try_from_arcs(
+        id: &str,
+        variables: &[&str],
+        arcs: &[(&str, &str)],
+    ) -> Result<BayesianNetwork, String> {
+        let nodes: HashSet<Node> = variables.iter().map(|v| Node::empty(v)).collect();
+        let mut edges: HashSet<Edge<Node>> = HashSet::new();
+        for (a, b) in arcs {
+            for v in [a, b] {
+                if !variables.contains(v) {
+                    return Err(format!("{v} of arc {a}->{b} is not a variable"));
+                }
+            }
+            edges.insert(Edge::empty(&format!("{a}->{b}"), EdgeType::Directed, a, b));
+        }
+        let graph = Graph::new(id.to_string(), HashMap::new(), nodes, edges);
+        if topological_ids(&graph).is_none() {
+            return Err(format!("{graph} has a directed cycle"));
+        }
+        Ok(BayesianNetwork {
+            graph,
+            cpds: HashMap::new(),
+        })
+    }
+
+    /// underlying directed acyclic graph
+    pub fn graph(&self) -> &Graph<Node, Edge<Node>> {
+        &self.graph
+    }
+
+    fn check_variable(&self, variable: &str) {
+        if !self.graph.vertices().iter().any(|v| v.id() == variable) {
+            panic!("{variable} not in {self}");
+        }
+    }
+
+    /// parents of a variable ordered by identifier
+    pub fn parents(&self, variable: &str) -> Vec<&Node> {
+        self.check_variable(variable);
+        let mut ps: Vec<&Node> = self
+            .graph
+            .edges()
+            .into_iter()
+            .filter(|e| e.end().id() == variable)
+            .map(|e| e.start())
+            .collect();
+        ps.sort_by(|a, b| a.id().cmp(b.id()));
+        ps.dedup_by(|a, b| a.id() == b.id());
+        ps
+    }
+
+    /// children of a variable ordered by identifier
+    pub fn children(&self, variable: &str) -> Vec<&Node> {
+        self.check_variable(variable);
+        let mut cs: Vec<&Node> = self
+            .graph
+            .edges()
+            .into_iter()
+            .filter(|e| e.start().id() == variable)
+            .map(|e| e.end())
+            .collect();
+        cs.sort_by(|a, b| a.id().cmp(b.id()));
+        cs.dedup_by(|a, b| a.id() == b.id());
+        cs
+    }
+
+    /// variables in topological order, ties broken by identifier
+    pub fn topological_order(&self) -> Vec<&String> {
+        topological_ids(&self.graph).unwrap()
+    }
+
+    /// Attach a table to its variable
+    /// # Description
+    /// Replaces the previous table of the variable if any. Panics if the
+    /// variable is not in the network or if the parents of the table are
+    /// not the parents of the variable in the graph. The parents of the
+    /// table may be in any order.
+    pub fn add_cpd(&mut self, cpd: TabularCPD) {
+        let variable = cpd.variable().name();
+        let expected: HashSet<&String> =
+            self.parents(variable).into_iter().map(|p| p.id()).collect();
+        let given: HashSet<&String> = cpd.parents().iter().map(|p| p.name()).collect();
+        if expected != given || given.len() != cpd.parents().len() {
+            panic!("parents of the cpd of {variable} do not match {self}");
+        }
+        self.cpds.insert(variable.clone(), cpd);
+    }
+
+    /// table of a variable if it is known
+    pub fn cpd(&self, variable: &str) -> Option<&TabularCPD> {
+        self.cpds.get(variable)
+    }
+
+    /// tables by variable name
+    pub fn cpds(&self) -> &HashMap<String, TabularCPD> {
+        &self.cpds
+    }
+
+    /// every variable has a table
+    pub fn is_fully_specified(&self) -> bool {
+        self.graph
+            .vertices()
+            .iter()
+            .all(|v| self.cpds.contains_key(v.id()))
+    }
+    /// Posterior joint distribution of some variables given evidence
+    /// # Description
+    /// Picks the inference engine on behalf of the user. When the largest
+    /// factor built along the min-fill elimination order of the query has
+    /// at most [EXACT_INFERENCE_ENTRIES] entries the answer is exact, see
+    /// [VariableElimination]. Otherwise it is estimated from
+    /// [POSTERIOR_SAMPLES] samples with a fixed seed, see
+    /// [LikelihoodWeighting]. The network must be fully specified.
+    ///
+    /// # Example
+    /// ```
+    /// use pgm_rust::pgm::bayesnet::BayesianNetwork;
+    /// use pgm_rust::pgm::cpd::TabularCPD;
+    /// use pgm_rust::pgm::query::Evidence;
+    /// use pgm_rust::pgm::variable::DiscreteVariable;
+    ///
+    /// let rain = DiscreteVariable::new("rain", &["no", "yes"]);
+    /// let wet = DiscreteVariable::new("wet", &["no", "yes"]);
+    /// let mut bn = BayesianNetwork::from_arcs("bn", &["rain", "wet"], &[("rain", "wet")]);
+    /// bn.add_cpd(TabularCPD::marginal(rain.clone(), vec![0.5, 0.5]));
+    /// bn.add_cpd(TabularCPD::new(wet, vec![rain], vec![vec![0.9, 0.1], vec![0.1, 0.9]]));
+    /// let d = bn.posterior(&["rain"], &Evidence::from([("wet", "yes")]));
+    /// (d.probability(&["yes"]) - 0.9).abs() < 1e-12; // true
+    /// ```
+    pub fn posterior(&self, variables: &[&str], evidence: &Evidence) -> Distribution {
+        self.posterior_within(variables, evidence, EXACT_INFERENCE_ENTRIES)
+    }
+
+    /// posterior answered exactly when no factor built has more than
+    /// `max_entries` entries
+    fn posterior_within(
+        &self,
+        variables: &[&str],
+        evidence: &Evidence,
+        max_entries: usize,
+    ) -> Distribution {
+        if !self.is_fully_specified() {
+            panic!("{self} has variables without table");
+        }
+        let query = Query::marginal(variables.iter().copied()).given(evidence.clone());
+        let exact = VariableElimination::new(self);
+        let result = if exact.largest_factor_size(&query) <= max_entries {
+            exact.query(&query)
+        } else {
+            LikelihoodWeighting::new(self, POSTERIOR_SAMPLES, 0).query(&query)
+        };
+        match result.into_marginal() {
+            None => panic!("marginal query answered by a map assignment"),
+            Some(d) => d,
+        }
+    }
+}
+
+impl Hash for BayesianNetwork {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.graph.hash(state);
+    }
+}
+
+impl fmt::Display for BayesianNetwork {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<BayesianNetwork id='{}'>", self.graph.id())
+    }
+}
+
+impl GraphObjectTrait for BayesianNetwork {
+    fn id(&self) -> &String {
+        self.graph.id()
+    }
+
+    fn data(&self) -> &HashMap<String, Vec<String>> {
+        self.graph.data()
+    }
+}
+
+impl GraphTrait<Node, Edge<Node>> for BayesianNetwork {
+    fn vertices(&self) -> HashSet<&Node> {
+        self.graph.vertices()
+    }
+    fn edges(&self) -> HashSet<&Edge<Node>> {
+        self.graph.edges()
+    }
+    fn create(
+        graph_id: String,
+        graph_data: HashMap<String, Vec<String>>,
+        nodes: HashSet<Node>,
+        edges: HashSet<Edge<Node>>,
+    ) -> BayesianNetwork {
+        BayesianNetwork::new(Graph::create(graph_id, graph_data, nodes, edges))
+    }
+    fn create_from_ref(
+        graph_id: String,
+        graph_data: HashMap<String, Vec<String>>,
+        nodes: HashSet<&Node>,
+        edges: HashSet<&Edge<Node>>,
+    ) -> BayesianNetwork {
+        BayesianNetwork::new(Graph::create_from_ref(graph_id, graph_data, nodes, edges))
+    }
+}
+
+impl DynGraph for BayesianNetwork {
+    fn graph_id(&self) -> String {
+        self.graph.id().clone()
+    }
+    fn vertex_ids(&self) -> Vec<String> {
+        DynView::new(&self.graph).vertex_ids()
+    }
+    fn edge_list(&self) -> Vec<EdgeDescription> {
+        DynView::new(&self.graph).edge_list()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pgm::variable::DiscreteVariable;
+
+    fn mk_bn() -> BayesianNetwork {
+        BayesianNetwork::from_arcs(
+            "bn",
+            &["c", "r", "s", "w"],
+            &[("c", "r"), ("c", "s"), ("r", "w"), ("s", "w")],
+        )
+    }
+    fn ids(ns: Vec<&Node>) -> Vec<&String> {
+        ns.into_iter().map(|n| n.id()).collect()
+    }
+
+    #[test]
+    fn test_structure() {
+        let bn = mk_bn();
+        assert_eq!(ids(bn.parents("w")), vec!["r", "s"]);
+        assert_eq!(ids(bn.children("c")), vec!["r", "s"]);
+        assert!(bn.parents("c").is_empty());
+        assert_eq!(bn.topological_order(), vec!["c", "r", "s", "w"]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_cycle() {
+        BayesianNetwork::from_arcs("bn", &["a", "b"], &[("a", "b"), ("b", "a")]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_undirected() {
+        let e = Edge::empty("e1", EdgeType::Undirected, "a", "b");
+        BayesianNetwork::new(Graph::from_edgeset(HashSet::from([e])));
+    }
+
+    #[test]
+    fn test_add_cpd() {
+        let mut bn = mk_bn();
+        let binary = |n: &str| DiscreteVariable::new(n, &["f", "t"]);
+        bn.add_cpd(TabularCPD::marginal(binary("c"), vec![0.5, 0.5]));
+        assert!(!bn.is_fully_specified());
+        bn.add_cpd(TabularCPD::new(
+            binary("r"),
+            vec![binary("c")],
+            vec![vec![0.8, 0.2], vec![0.2, 0.8]],
+        ));
+        assert_eq!(bn.cpd("r").unwrap().probability("t", &["t"]), 0.8);
+        assert!(bn.cpd("w").is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_add_cpd_wrong_parents() {
+        let mut bn = mk_bn();
+        let binary = |n: &str| DiscreteVariable::new(n, &["f", "t"]);
+        bn.add_cpd(TabularCPD::marginal(binary("r"), vec![0.5, 0.5]));
+    }
+
+    #[test]
+    fn test_posterior() {
+        let mut bn = BayesianNetwork::from_arcs("bn", &["a", "b"], &[("a", "b")]);
+        let binary = |n: &str| DiscreteVariable::new(n, &["f", "t"]);
+        bn.add_cpd(TabularCPD::marginal(binary("a"), vec![0.2, 0.8]));
+        bn.add_cpd(TabularCPD::new(
+            binary("b"),
+            vec![binary("a")],
+            vec![vec![0.5, 0.5], vec![0.25, 0.75]],
+        ));
+        let d = bn.posterior(&["a"], &Evidence::from([("b", "f")]));
+        // P(a | b = f) = 0.8 * 0.25 / (0.2 * 0.5 + 0.8 * 0.25)
+        assert!((d.probability(&["t"]) - 2.0 / 3.0).abs() < 1e-12);
+        let d = bn.posterior(&["b"], &Evidence::new());
+        assert!((d.probability(&["t"]) - 0.7).abs() < 1e-12);
+    }
+
+    /// binary chain v00 -> v01 -> ... where every variable copies its
+    /// parent with probability 0.9
+    fn mk_chain(n: usize) -> BayesianNetwork {
+        let names: Vec<String> = (0..n).map(|i| format!("v{i:02}")).collect();
+        let refs: Vec<&str> = names.iter().map(|v| v.as_str()).collect();
+        let arcs: Vec<(&str, &str)> = refs.windows(2).map(|w| (w[0], w[1])).collect();
+        let mut bn = BayesianNetwork::from_arcs("chain", &refs, &arcs);
+        let binary = |n: &str| DiscreteVariable::new(n, &["f", "t"]);
+        bn.add_cpd(TabularCPD::marginal(binary(refs[0]), vec![0.5, 0.5]));
+        for w in refs.windows(2) {
+            bn.add_cpd(TabularCPD::new(
+                binary(w[1]),
+                vec![binary(w[0])],
+                vec![vec![0.9, 0.1], vec![0.1, 0.9]],
+            ));
+        }
+        bn
+    }
+
+    #[test]
+    fn test_posterior_long_chain() {
+        // 2^25 joint states, but the induced width is one
+        let bn = mk_chain(25);
+        let d = bn.posterior(&["v24"], &Evidence::new());
+        assert!((d.probability(&["t"]) - 0.5).abs() < 1e-12);
+        let d = bn.posterior(&["v00"], &Evidence::from([("v01", "t")]));
+        assert!((d.probability(&["t"]) - 0.9).abs() < 1e-12);
+    }
+
+    /// `side` by `side` grid whose variables have `card` states, each one
+    /// having the variables above and to its left as parents
+    fn mk_grid(side: usize, card: usize) -> BayesianNetwork {
+        let name = |i: usize, j: usize| format!("g{i}{j}");
+        let states: Vec<String> = (0..card).map(|s| s.to_string()).collect();
+        let state_refs: Vec<&str> = states.iter().map(|s| s.as_str()).collect();
+        let var = |v: &str| DiscreteVariable::new(v, &state_refs);
+        let names: Vec<String> = (0..side * side).map(|k| name(k / side, k % side)).collect();
+        let mut arcs: Vec<(String, String)> = Vec::new();
+        for i in 0..side {
+            for j in 0..side {
+                if i > 0 {
+                    arcs.push((name(i - 1, j), name(i, j)));
+                }
+                if j > 0 {
+                    arcs.push((name(i, j - 1), name(i, j)));
+                }
+            }
+        }
+        let refs: Vec<&str> = names.iter().map(|v| v.as_str()).collect();
+        let arc_refs: Vec<(&str, &str)> =
+            arcs.iter().map(|(a, b)| (a.as_str(), b.as_str())).collect();
+        let mut bn = BayesianNetwork::from_arcs("grid", &refs, &arc_refs);
+        for v in &names {
+            let parents: Vec<DiscreteVariable> = arcs
+                .iter()
+                .filter(|(_, b)| b == v)
+                .map(|(a, _)| var(a))
+                .collect();
+            let rows: usize = parents.iter().map(|p| p.cardinality()).product();
+            let table = (0..rows)
+                .map(|r| {
+                    let weights: Vec<f64> = (0..card)
+                        .map(|s| 1.0 + ((r + 2 * s) % card) as f64)
+                        .collect();
+                    let total: f64 = weights.iter().sum();
+                    weights.iter().map(|w| w / total).collect()
+                })
+                .collect();
+            bn.add_cpd(TabularCPD::new(var(v), parents, table));
+        }
+        bn
+    }
+
+    #[test]
+    fn test_posterior_budget() {
+        // the induced width is below 20 but the variables have ten states,
+        // so the factors outgrow the budget and the answer is sampled
+        let bn = mk_grid(7, 10);
+        let q = Query::marginal(["g66"]);
+        let exact = VariableElimination::new(&bn);
+        assert!(exact.induced_width(&q) < 20);
+        assert!(exact.largest_factor_size(&q) > EXACT_INFERENCE_ENTRIES);
+        let d = bn.posterior(&["g66"], &Evidence::new());
+        let sampled = LikelihoodWeighting::new(&bn, POSTERIOR_SAMPLES, 0).query(&q);
+        assert_eq!(sampled.into_marginal(), Some(d.clone()));
+        assert_eq!(d.values().len(), 10);
+        assert!((d.values().iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        // with two states the same grid is answered exactly
+        let small = mk_grid(7, 2);
+        let d = small.posterior(&["g66"], &Evidence::new());
+        let answer = VariableElimination::new(&small).query(&Query::marginal(["g66"]));
+        assert_eq!(answer.into_marginal(), Some(d));
+    }
+
+    #[test]
+    fn test_posterior_sampling() {
+        let bn = mk_chain(5);
+        let evidence = Evidence::from([("v04", "t")]);
+        let d = bn.posterior_within(&["v00"], &evidence, 0);
+        let q = Query::marginal(["v00"]).given(evidence.clone());
+        let sampled = LikelihoodWeighting::new(&bn, POSTERIOR_SAMPLES, 0).query(&q);
+        assert_eq!(sampled.into_marginal(), Some(d.clone()));
+        let exact = bn.posterior(&["v00"], &evidence);
+        assert!((d.probability(&["t"]) - exact.probability(&["t"])).abs() < 0.01);
+        assert_ne!(d, exact);
+    }
+}
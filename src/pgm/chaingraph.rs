@@ -0,0 +1,330 @@
+//! chain graphs mixing directed and undirected edges
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::graph_obj::GraphObject as GraphObjectTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::edgetype::EdgeType;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+/// root of `i` in a union find forest, with path halving
+fn find_root(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+/// index of the chain component of every vertex, vertices being sorted by
+/// identifier, and the number of components
+fn component_index<N, E, G>(g: &G) -> (Vec<&N>, Vec<usize>, usize)
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let vertices = g.vertices_sorted();
+    let index: HashMap<&String, usize> = vertices
+        .iter()
+        .enumerate()
+        .map(|(i, v)| (v.id(), i))
+        .collect();
+    let mut parent: Vec<usize> = (0..vertices.len()).collect();
+    for e in g.edges() {
+        if e.has_type() == &EdgeType::Undirected {
+            let ri = find_root(&mut parent, index[e.start().id()]);
+            let rj = find_root(&mut parent, index[e.end().id()]);
+            if ri != rj {
+                parent[ri.max(rj)] = ri.min(rj);
+            }
+        }
+    }
+    // number components in the order of their smallest vertex
+    let mut numbering: HashMap<usize, usize> = HashMap::new();
+    let mut component = vec![0; vertices.len()];
+    for (i, c) in component.iter_mut().enumerate() {
+        let r = find_root(&mut parent, i);
+        let next = numbering.len();
+        *c = *numbering.entry(r).or_insert(next);
+    }
+    let nb_components = numbering.len();
+    (vertices, component, nb_components)
+}
+
+/// Check if a graph is a chain graph
+/// # Description
+/// A chain graph has no partially directed cycle, that is no cycle with at
+/// least one directed edge where directed edges are followed along their
+/// orientation. Equivalently, no directed edge joins two vertices of the same
+/// chain component, and the directed edges between chain components form a
+/// directed acyclic graph.
+///
+/// # Args
+/// - g: something that implements [Graph] trait
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::types::edge::Edge;
+/// use pgm_rust::graph::types::edgetype::EdgeType;
+/// use pgm_rust::graph::types::graph::Graph;
+/// use pgm_rust::graph::types::node::Node;
+/// use pgm_rust::pgm::chaingraph::is_chain_graph;
+/// use std::collections::HashSet;
+///
+/// let e1 = Edge::empty("e1", EdgeType::Directed, "a", "b");
+/// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+/// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+/// is_chain_graph(&g); // true
+/// ```
+///
+/// # References
+/// Lauritzen S. Graphical Models. 1996, p. 7.
+pub fn is_chain_graph<N, E, G>(g: &G) -> bool
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let (vertices, component, nb_components) = component_index(g);
+    let index: HashMap<&String, usize> = vertices
+        .iter()
+        .enumerate()
+        .map(|(i, v)| (v.id(), i))
+        .collect();
+    let mut successors: Vec<HashSet<usize>> = vec![HashSet::new(); nb_components];
+    for e in g.edges() {
+        if e.has_type() == &EdgeType::Directed {
+            let cs = component[index[e.start().id()]];
+            let ct = component[index[e.end().id()]];
+            if cs == ct {
+                return false;
+            }
+            successors[cs].insert(ct);
+        }
+    }
+    // Kahn's algorithm on the component graph
+    let mut in_degree = vec![0; nb_components];
+    for succ in &successors {
+        for c in succ {
+            in_degree[*c] += 1;
+        }
+    }
+    let mut queue: VecDeque<usize> = (0..nb_components).filter(|c| in_degree[*c] == 0).collect();
+    let mut nb_sorted = 0;
+    while let Some(c) = queue.pop_front() {
+        nb_sorted += 1;
+        for d in &successors[c] {
+            in_degree[*d] -= 1;
+            if in_degree[*d] == 0 {
+                queue.push_back(*d);
+            }
+        }
+    }
+    nb_sorted == nb_components
+}
+
+/// Chain graph as used by chain graph models.
+/// A graph with directed and undirected edges and no partially directed
+/// cycle, see [is_chain_graph]. Bayesian networks and Markov networks are
+/// special cases with no undirected and no directed edges respectively.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainGraph<N: NodeTrait, E: EdgeTrait<N>, G: GraphTrait<N, E>> {
+    graph: G,
+    node_type: PhantomData<N>,
+    edge_type: PhantomData<E>,
+}
+
+impl<N: NodeTrait, E: EdgeTrait<N>, G: GraphTrait<N, E>> ChainGraph<N, E, G> {
+    /// create a chain graph from a graph, panics if the graph has a
+    /// partially directed cycle
+    pub fn new(graph: G) -> ChainGraph<N, E, G> {
+        if !is_chain_graph(&graph) {
+            panic!("{graph} has a partially directed cycle");
+        }
+        ChainGraph {
+            graph,
+            node_type: PhantomData,
+            edge_type: PhantomData,
+        }
+    }
+
+    /// Get the chain components
+    /// # Description
+    /// Chain components are the connected components of the graph obtained
+    /// by removing directed edges. They are ordered by their smallest vertex
+    /// identifier.
+    ///
+    /// # Example
+    /// ```
+    /// use pgm_rust::graph::types::edge::Edge;
+    /// use pgm_rust::graph::types::edgetype::EdgeType;
+    /// use pgm_rust::graph::types::graph::Graph;
+    /// use pgm_rust::graph::types::node::Node;
+    /// use pgm_rust::pgm::chaingraph::ChainGraph;
+    /// use std::collections::HashSet;
+    ///
+    /// let e1 = Edge::empty("e1", EdgeType::Directed, "a", "b");
+    /// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+    /// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+    /// let cg = ChainGraph::new(g);
+    /// cg.chain_components().len() == 2; // true: {a} and {b, c}
+    /// ```
+    pub fn chain_components(&self) -> Vec<HashSet<&N>> {
+        let (vertices, component, nb_components) = component_index(&self.graph);
+        let mut components: Vec<HashSet<&N>> = vec![HashSet::new(); nb_components];
+        for (v, c) in vertices.into_iter().zip(component) {
+            components[c].insert(v);
+        }
+        components
+    }
+}
+
+/// Chain graphs are hashed using their graphs
+impl<N: NodeTrait, E: EdgeTrait<N>, G: GraphTrait<N, E>> Hash for ChainGraph<N, E, G> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.graph.hash(state);
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait<N>, G: GraphTrait<N, E>> fmt::Display for ChainGraph<N, E, G> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<ChainGraph id='{}'>", self.graph.id())
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait<N>, G: GraphTrait<N, E>> GraphObjectTrait for ChainGraph<N, E, G> {
+    fn id(&self) -> &String {
+        self.graph.id()
+    }
+
+    fn data(&self) -> &HashMap<String, Vec<String>> {
+        self.graph.data()
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait<N>, G: GraphTrait<N, E>> GraphTrait<N, E> for ChainGraph<N, E, G> {
+    fn vertices(&self) -> HashSet<&N> {
+        self.graph.vertices()
+    }
+    fn edges(&self) -> HashSet<&E> {
+        self.graph.edges()
+    }
+    fn create(
+        graph_id: String,
+        graph_data: HashMap<String, Vec<String>>,
+        nodes: HashSet<N>,
+        edges: HashSet<E>,
+    ) -> ChainGraph<N, E, G> {
+        ChainGraph::new(G::create(graph_id, graph_data, nodes, edges))
+    }
+    fn create_from_ref(
+        graph_id: String,
+        graph_data: HashMap<String, Vec<String>>,
+        nodes: HashSet<&N>,
+        edges: HashSet<&E>,
+    ) -> ChainGraph<N, E, G> {
+        ChainGraph::new(G::create_from_ref(graph_id, graph_data, nodes, edges))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+
+    fn mk_uedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Undirected, n1_id, n2_id)
+    }
+    fn mk_dedge(n1_id: &str, n2_id: &str, e_id: &str) -> Edge<Node> {
+        Edge::empty(e_id, EdgeType::Directed, n1_id, n2_id)
+    }
+    fn mk_graph(es: Vec<Edge<Node>>) -> Graph<Node, Edge<Node>> {
+        Graph::from_edgeset(es.into_iter().collect())
+    }
+    fn ids(c: &HashSet<&Node>) -> HashSet<String> {
+        c.iter().map(|n| n.id().clone()).collect()
+    }
+
+    /// a -> b - c - d, a -> e -> d and f - e
+    fn mk_g1() -> Graph<Node, Edge<Node>> {
+        mk_graph(vec![
+            mk_dedge("a", "b", "e1"),
+            mk_uedge("b", "c", "e2"),
+            mk_uedge("c", "d", "e3"),
+            mk_dedge("a", "e", "e4"),
+            mk_dedge("e", "d", "e5"),
+            mk_uedge("f", "e", "e6"),
+        ])
+    }
+
+    #[test]
+    fn test_is_chain_graph() {
+        assert!(is_chain_graph(&mk_g1()));
+    }
+
+    #[test]
+    fn test_is_chain_graph_within_component() {
+        // b - c - d with b -> d closes a partially directed cycle
+        let g = mk_graph(vec![
+            mk_uedge("b", "c", "e1"),
+            mk_uedge("c", "d", "e2"),
+            mk_dedge("b", "d", "e3"),
+        ]);
+        assert!(!is_chain_graph(&g));
+    }
+
+    #[test]
+    fn test_is_chain_graph_between_components() {
+        // a -> b - c -> d - e -> a
+        let g = mk_graph(vec![
+            mk_dedge("a", "b", "e1"),
+            mk_uedge("b", "c", "e2"),
+            mk_dedge("c", "d", "e3"),
+            mk_uedge("d", "e", "e4"),
+            mk_dedge("e", "a", "e5"),
+        ]);
+        assert!(!is_chain_graph(&g));
+    }
+
+    #[test]
+    fn test_chain_components() {
+        let cg = ChainGraph::new(mk_g1());
+        let comps: Vec<HashSet<String>> = cg.chain_components().iter().map(ids).collect();
+        let expected: Vec<HashSet<String>> = vec![vec!["a"], vec!["b", "c", "d"], vec!["e", "f"]]
+            .into_iter()
+            .map(|c| c.into_iter().map(|s| s.to_string()).collect())
+            .collect();
+        assert_eq!(comps, expected);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_invalid() {
+        ChainGraph::new(mk_graph(vec![
+            mk_dedge("a", "b", "e1"),
+            mk_dedge("b", "a", "e2"),
+        ]));
+    }
+
+    #[test]
+    fn test_graph_trait() {
+        let cg = ChainGraph::new(mk_g1());
+        assert_eq!(cg.order(), 6);
+        assert_eq!(cg.size(), 6);
+        let cg2: ChainGraph<Node, Edge<Node>, Graph<Node, Edge<Node>>> =
+            ChainGraph::create_from_ref(
+                "cg".to_string(),
+                HashMap::new(),
+                cg.vertices(),
+                cg.edges(),
+            );
+        assert_eq!(cg2.id(), "cg");
+    }
+}
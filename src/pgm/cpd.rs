@@ -0,0 +1,690 @@
+//! conditional probability distributions
+use crate::pgm::variable::DiscreteVariable;
+use std::fmt;
+
+/// tolerance used when checking that probabilities sum to one
+pub const SUM_TOLERANCE: f64 = 1e-6;
+
+/// joint states of `variables` in lexicographic order of state indices, the
+/// last variable varying fastest
+fn configurations(variables: &[DiscreteVariable]) -> Vec<Vec<usize>> {
+    let mut configs: Vec<Vec<usize>> = vec![Vec::new()];
+    for v in variables {
+        let mut next = Vec::with_capacity(configs.len() * v.cardinality());
+        for c in &configs {
+            for s in 0..v.cardinality() {
+                let mut nc = c.clone();
+                nc.push(s);
+                next.push(nc);
+            }
+        }
+        configs = next;
+    }
+    configs
+}
+
+/// Conditional distribution of a discrete variable given its parents
+/// # Description
+/// Structured distributions compute their probabilities on demand instead
+/// of storing a table whose size is exponential in the number of parents.
+/// [ConditionalDistribution::to_tabular] materializes the table when it is
+/// needed.
+pub trait ConditionalDistribution {
+    /// variable whose distribution is given
+    fn variable(&self) -> &DiscreteVariable;
+
+    /// parents of the variable
+    fn parents(&self) -> &[DiscreteVariable];
+
+    /// probability of a state of the variable given the states of its
+    /// parents, all given as state indices
+    fn probability_at(&self, state: usize, parent_states: &[usize]) -> f64;
+
+    /// table with the same probabilities, see [TabularCPD]
+    fn to_tabular(&self) -> TabularCPD {
+        let table = configurations(self.parents())
+            .iter()
+            .map(|config| {
+                (0..self.variable().cardinality())
+                    .map(|s| self.probability_at(s, config))
+                    .collect()
+            })
+            .collect();
+        TabularCPD::new(self.variable().clone(), self.parents().to_vec(), table)
+    }
+}
+
+/// Conditional probability table
+/// # Description
+/// Stores `P(X | U1, ..., Uk)` for a discrete variable `X` and its parents
+/// `U1, ..., Uk`. The table has one row per parent configuration, each row
+/// being a distribution over the states of `X`. Parent configurations are
+/// enumerated in lexicographic order of state indices, the last parent
+/// varying fastest. A table converts into a [crate::factor::Factor] over
+/// its parents followed by its variable.
+///
+/// # References
+/// Koller D, Friedman N. Probabilistic Graphical Models. 2009, p. 157.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TabularCPD {
+    variable: DiscreteVariable,
+    parents: Vec<DiscreteVariable>,
+    table: Vec<Vec<f64>>,
+}
+
+impl TabularCPD {
+    /// Create a conditional probability table
+    /// # Description
+    /// Panics if the table does not have one row per parent configuration,
+    /// if a row does not have one entry per state of `variable`, if an entry
+    /// is not a probability or if a row does not sum to one within
+    /// [SUM_TOLERANCE].
+    ///
+    /// # Example
+    /// ```
+    /// use pgm_rust::pgm::cpd::TabularCPD;
+    /// use pgm_rust::pgm::variable::DiscreteVariable;
+    ///
+    /// let rain = DiscreteVariable::new("rain", &["no", "yes"]);
+    /// let wet = DiscreteVariable::new("wet", &["no", "yes"]);
+    /// let cpd = TabularCPD::new(wet, vec![rain], vec![vec![0.9, 0.1], vec![0.2, 0.8]]);
+    /// cpd.probability("yes", &["yes"]) == 0.8; // true
+    /// println!("{cpd}");
+    /// ```
+    pub fn new(
+        variable: DiscreteVariable,
+        parents: Vec<DiscreteVariable>,
+        table: Vec<Vec<f64>>,
+    ) -> TabularCPD {
+        match TabularCPD::try_new(variable, parents, table) {
+            Err(msg) => panic!("{msg}"),
+            Ok(cpd) => cpd,
+        }
+    }
+
+    /// Create a conditional probability table, see [TabularCPD::new]
+    /// # Description
+    /// Returns the reason as an error instead of panicking when the table
+    /// is not valid, for tables read from untrusted input.
+    ///
+    /// # Example
+    /// ```
+    /// use pgm_rust::pgm::cpd::TabularCPD;
+    /// use pgm_rust::pgm::variable::DiscreteVariable;
+    ///
+    /// let rain = DiscreteVariable::new("rain", &["no", "yes"]);
+    /// TabularCPD::try_new(rain, vec![], vec![vec![0.5, 0.6]]).is_err(); // true
+    /// ```
+    pub fn try_new(
+        variable: DiscreteVariable,
+        parents: Vec<DiscreteVariable>,
+        table: Vec<Vec<f64>>,
+    ) -> Result<TabularCPD, String> {
+        for (i, p) in parents.iter().enumerate() {
+            if p.name() == variable.name() || parents[..i].iter().any(|q| q.name() == p.name()) {
+                return Err(format!(
+                    "{} appears more than once in the cpd of {}",
+                    p.name(),
+                    variable.name()
+                ));
+            }
+        }
+        let nb_rows: usize = parents.iter().map(|p| p.cardinality()).product();
+        if table.len() != nb_rows {
+            return Err(format!(
+                "cpd of {} has {} rows, {} parent configurations expected",
+                variable.name(),
+                table.len(),
+                nb_rows
+            ));
+        }
+        for (i, row) in table.iter().enumerate() {
+            if row.len() != variable.cardinality() {
+                return Err(format!(
+                    "row {} of the cpd of {} has {} entries, {} expected",
+                    i,
+                    variable.name(),
+                    row.len(),
+                    variable.cardinality()
+                ));
+            }
+            if row.iter().any(|p| !(0.0..=1.0).contains(p)) {
+                return Err(format!(
+                    "row {} of the cpd of {} has an entry outside [0, 1]",
+                    i,
+                    variable.name()
+                ));
+            }
+            let total: f64 = row.iter().sum();
+            if (total - 1.0).abs() > SUM_TOLERANCE {
+                return Err(format!(
+                    "row {} of the cpd of {} sums to {}",
+                    i,
+                    variable.name(),
+                    total
+                ));
+            }
+        }
+        Ok(TabularCPD {
+            variable,
+            parents,
+            table,
+        })
+    }
+
+    /// Create the table of a variable without parents
+    /// # Example
+    /// ```
+    /// use pgm_rust::pgm::cpd::TabularCPD;
+    /// use pgm_rust::pgm::variable::DiscreteVariable;
+    ///
+    /// let rain = DiscreteVariable::new("rain", &["no", "yes"]);
+    /// let cpd = TabularCPD::marginal(rain, vec![0.7, 0.3]);
+    /// cpd.probability("no", &[]) == 0.7; // true
+    /// ```
+    pub fn marginal(variable: DiscreteVariable, distribution: Vec<f64>) -> TabularCPD {
+        TabularCPD::new(variable, Vec::new(), vec![distribution])
+    }
+
+    /// variable whose distribution is given by the table
+    pub fn variable(&self) -> &DiscreteVariable {
+        &self.variable
+    }
+
+    /// parents of the variable in table order
+    pub fn parents(&self) -> &[DiscreteVariable] {
+        &self.parents
+    }
+
+    /// rows of the table, see [TabularCPD]
+    pub fn table(&self) -> &[Vec<f64>] {
+        &self.table
+    }
+
+    /// index of the row of a parent configuration given as state indices
+    pub fn row_index(&self, parent_states: &[usize]) -> usize {
+        if parent_states.len() != self.parents.len() {
+            panic!(
+                "{} parent states given, {} expected",
+                parent_states.len(),
+                self.parents.len()
+            );
+        }
+        let mut index = 0;
+        for (p, s) in self.parents.iter().zip(parent_states) {
+            if *s >= p.cardinality() {
+                panic!("{} has no state {}", p.name(), s);
+            }
+            index = index * p.cardinality() + s;
+        }
+        index
+    }
+
+    /// distribution of the variable for a parent configuration given as
+    /// state names
+    pub fn distribution(&self, parent_states: &[&str]) -> &[f64] {
+        let indices: Vec<usize> = self
+            .parents
+            .iter()
+            .zip(parent_states)
+            .map(|(p, s)| match p.state_index(s) {
+                None => panic!("{} has no state {}", p.name(), s),
+                Some(i) => i,
+            })
+            .collect();
+        if indices.len() != self.parents.len() {
+            panic!(
+                "{} parent states given, {} expected",
+                parent_states.len(),
+                self.parents.len()
+            );
+        }
+        &self.table[self.row_index(&indices)]
+    }
+
+    /// probability of a state of the variable given the states of its
+    /// parents, all given by name
+    pub fn probability(&self, state: &str, parent_states: &[&str]) -> f64 {
+        match self.variable.state_index(state) {
+            None => panic!("{} has no state {}", self.variable.name(), state),
+            Some(i) => self.distribution(parent_states)[i],
+        }
+    }
+
+    /// parent configurations in row order, given as state indices
+    pub fn parent_configurations(&self) -> Vec<Vec<usize>> {
+        configurations(&self.parents)
+    }
+}
+
+// entries are checked to lie in [0, 1] so no entry is NaN
+impl Eq for TabularCPD {}
+
+impl fmt::Display for TabularCPD {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let parent_names: Vec<&str> = self.parents.iter().map(|p| p.name().as_str()).collect();
+        if parent_names.is_empty() {
+            writeln!(f, "P({})", self.variable.name())?;
+        } else {
+            writeln!(
+                f,
+                "P({} | {})",
+                self.variable.name(),
+                parent_names.join(", ")
+            )?;
+        }
+        let mut header: Vec<String> = parent_names.iter().map(|p| p.to_string()).collect();
+        let value_header: Vec<String> = self
+            .variable
+            .states()
+            .iter()
+            .map(|s| format!("{}={}", self.variable.name(), s))
+            .collect();
+        let nb_parents = header.len();
+        header.extend(value_header);
+        let mut rows: Vec<Vec<String>> = Vec::new();
+        for (config, row) in self.parent_configurations().iter().zip(&self.table) {
+            let mut cells: Vec<String> = self
+                .parents
+                .iter()
+                .zip(config)
+                .map(|(p, s)| p.states()[*s].clone())
+                .collect();
+            cells.extend(row.iter().map(|x| format!("{x:.4}")));
+            rows.push(cells);
+        }
+        let widths: Vec<usize> = (0..header.len())
+            .map(|j| {
+                rows.iter()
+                    .map(|r| r[j].len())
+                    .chain(std::iter::once(header[j].len()))
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        let fmt_line = |cells: &[String]| -> String {
+            let mut line = String::new();
+            for (j, c) in cells.iter().enumerate() {
+                if j == nb_parents && nb_parents > 0 {
+                    line.push_str("| ");
+                }
+                line.push_str(&format!("{:<w$} ", c, w = widths[j]));
+            }
+            line.trim_end().to_string()
+        };
+        write!(f, "{}", fmt_line(&header))?;
+        for r in &rows {
+            write!(f, "\n{}", fmt_line(r))?;
+        }
+        Ok(())
+    }
+}
+
+impl ConditionalDistribution for TabularCPD {
+    fn variable(&self) -> &DiscreteVariable {
+        &self.variable
+    }
+
+    fn parents(&self) -> &[DiscreteVariable] {
+        &self.parents
+    }
+
+    fn probability_at(&self, state: usize, parent_states: &[usize]) -> f64 {
+        self.table[self.row_index(parent_states)][state]
+    }
+
+    fn to_tabular(&self) -> TabularCPD {
+        self.clone()
+    }
+}
+
+fn check_binary(v: &DiscreteVariable) {
+    if v.cardinality() != 2 {
+        panic!("{} must have two states", v.name());
+    }
+}
+
+/// Noisy-OR distribution of a binary variable
+/// # Description
+/// Every parent in its second state independently turns the variable on,
+/// to its second state, with its activation probability. The leak
+/// probability turns it on regardless of the parents. Hence
+/// `P(off | u) = (1 - leak) prod_{i: u_i on} (1 - activation_i)`. The model
+/// has one parameter per parent instead of a table row per parent
+/// configuration.
+///
+/// # Example
+/// ```
+/// use pgm_rust::pgm::cpd::{ConditionalDistribution, NoisyOr};
+/// use pgm_rust::pgm::variable::DiscreteVariable;
+///
+/// let b = |n: &str| DiscreteVariable::new(n, &["no", "yes"]);
+/// let fever = NoisyOr::new(b("fever"), vec![b("flu"), b("cold")], vec![0.8, 0.4], 0.1);
+/// let p_off = fever.probability_at(0, &[1, 1]);
+/// (p_off - 0.9 * 0.2 * 0.6).abs() < 1e-12; // true
+/// fever.to_tabular().table().len() == 4; // true
+/// ```
+///
+/// # References
+/// Koller D, Friedman N. Probabilistic Graphical Models. 2009, p. 175.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoisyOr {
+    variable: DiscreteVariable,
+    parents: Vec<DiscreteVariable>,
+    activations: Vec<f64>,
+    leak: f64,
+}
+
+impl NoisyOr {
+    /// Create a noisy-OR distribution
+    /// # Description
+    /// Panics if a variable is not binary, if there is not one activation
+    /// probability per parent or if a parameter is not a probability.
+    pub fn new(
+        variable: DiscreteVariable,
+        parents: Vec<DiscreteVariable>,
+        activations: Vec<f64>,
+        leak: f64,
+    ) -> NoisyOr {
+        check_binary(&variable);
+        parents.iter().for_each(check_binary);
+        if activations.len() != parents.len() {
+            panic!(
+                "{} activation probabilities given, {} expected",
+                activations.len(),
+                parents.len()
+            );
+        }
+        if activations
+            .iter()
+            .chain([&leak])
+            .any(|p| !(0.0..=1.0).contains(p))
+        {
+            panic!(
+                "noisy-or parameters of {} must be in [0, 1]",
+                variable.name()
+            );
+        }
+        NoisyOr {
+            variable,
+            parents,
+            activations,
+            leak,
+        }
+    }
+
+    /// activation probability of every parent
+    pub fn activations(&self) -> &[f64] {
+        &self.activations
+    }
+
+    /// probability that the variable is on when every parent is off
+    pub fn leak(&self) -> f64 {
+        self.leak
+    }
+}
+
+impl ConditionalDistribution for NoisyOr {
+    fn variable(&self) -> &DiscreteVariable {
+        &self.variable
+    }
+
+    fn parents(&self) -> &[DiscreteVariable] {
+        &self.parents
+    }
+
+    fn probability_at(&self, state: usize, parent_states: &[usize]) -> f64 {
+        if parent_states.len() != self.parents.len() {
+            panic!(
+                "{} parent states given, {} expected",
+                parent_states.len(),
+                self.parents.len()
+            );
+        }
+        let mut off = 1.0 - self.leak;
+        for (a, s) in self.activations.iter().zip(parent_states) {
+            if *s == 1 {
+                off *= 1.0 - a;
+            }
+        }
+        match state {
+            0 => off,
+            1 => 1.0 - off,
+            _ => panic!("{} has no state {}", self.variable.name(), state),
+        }
+    }
+}
+
+/// Deterministic distribution
+/// # Description
+/// The state of the variable is a function of the states of its parents,
+/// given as state indices. The function must return a state index of the
+/// variable.
+///
+/// # Example
+/// ```
+/// use pgm_rust::pgm::cpd::{ConditionalDistribution, Deterministic};
+/// use pgm_rust::pgm::variable::DiscreteVariable;
+///
+/// let b = |n: &str| DiscreteVariable::new(n, &["0", "1"]);
+/// let xor = Deterministic::new(b("x"), vec![b("a"), b("b")], |u: &[usize]| u[0] ^ u[1]);
+/// xor.probability_at(1, &[1, 0]) == 1.0; // true
+/// ```
+#[derive(Debug, Clone)]
+pub struct Deterministic {
+    variable: DiscreteVariable,
+    parents: Vec<DiscreteVariable>,
+    function: fn(&[usize]) -> usize,
+}
+
+impl Deterministic {
+    /// Create a deterministic distribution from the function computing the
+    /// state of the variable
+    pub fn new(
+        variable: DiscreteVariable,
+        parents: Vec<DiscreteVariable>,
+        function: fn(&[usize]) -> usize,
+    ) -> Deterministic {
+        Deterministic {
+            variable,
+            parents,
+            function,
+        }
+    }
+
+    /// state of the variable given the states of its parents
+    pub fn state(&self, parent_states: &[usize]) -> usize {
+        let s = (self.function)(parent_states);
+        if s >= self.variable.cardinality() {
+            panic!("{} has no state {}", self.variable.name(), s);
+        }
+        s
+    }
+}
+
+impl ConditionalDistribution for Deterministic {
+    fn variable(&self) -> &DiscreteVariable {
+        &self.variable
+    }
+
+    fn parents(&self) -> &[DiscreteVariable] {
+        &self.parents
+    }
+
+    fn probability_at(&self, state: usize, parent_states: &[usize]) -> f64 {
+        if self.state(parent_states) == state {
+            1.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Uniform distribution over the states of a variable, whatever the states
+/// of its parents
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Uniform {
+    variable: DiscreteVariable,
+    parents: Vec<DiscreteVariable>,
+}
+
+impl Uniform {
+    /// Create a uniform distribution
+    pub fn new(variable: DiscreteVariable, parents: Vec<DiscreteVariable>) -> Uniform {
+        Uniform { variable, parents }
+    }
+}
+
+impl ConditionalDistribution for Uniform {
+    fn variable(&self) -> &DiscreteVariable {
+        &self.variable
+    }
+
+    fn parents(&self) -> &[DiscreteVariable] {
+        &self.parents
+    }
+
+    fn probability_at(&self, state: usize, _parent_states: &[usize]) -> f64 {
+        if state >= self.variable.cardinality() {
+            panic!("{} has no state {}", self.variable.name(), state);
+        }
+        1.0 / self.variable.cardinality() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mk_cpd() -> TabularCPD {
+        let a = DiscreteVariable::new("A", &["a0", "a1"]);
+        let b = DiscreteVariable::new("B", &["b0", "b1", "b2"]);
+        let x = DiscreteVariable::new("X", &["x0", "x1"]);
+        TabularCPD::new(
+            x,
+            vec![a, b],
+            vec![
+                vec![0.1, 0.9],
+                vec![0.2, 0.8],
+                vec![0.3, 0.7],
+                vec![0.4, 0.6],
+                vec![0.5, 0.5],
+                vec![0.6, 0.4],
+            ],
+        )
+    }
+
+    #[test]
+    fn test_row_order() {
+        let cpd = mk_cpd();
+        assert_eq!(cpd.row_index(&[0, 2]), 2);
+        assert_eq!(cpd.row_index(&[1, 0]), 3);
+        assert_eq!(cpd.probability("x1", &["a1", "b2"]), 0.4);
+        assert_eq!(cpd.distribution(&["a0", "b1"]), &[0.2, 0.8]);
+        assert_eq!(cpd.parent_configurations()[4], vec![1, 1]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_bad_sum() {
+        let x = DiscreteVariable::new("X", &["x0", "x1"]);
+        TabularCPD::marginal(x, vec![0.5, 0.6]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_bad_rows() {
+        let a = DiscreteVariable::new("A", &["a0", "a1"]);
+        let x = DiscreteVariable::new("X", &["x0", "x1"]);
+        TabularCPD::new(x, vec![a], vec![vec![0.5, 0.5]]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_negative() {
+        let x = DiscreteVariable::new("X", &["x0", "x1"]);
+        TabularCPD::marginal(x, vec![-0.5, 1.5]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_parent_is_variable() {
+        let x = DiscreteVariable::new("X", &["x0", "x1"]);
+        TabularCPD::new(x.clone(), vec![x], vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+    }
+
+    #[test]
+    fn test_display() {
+        let x = DiscreteVariable::new("X", &["x0", "x1"]);
+        let a = DiscreteVariable::new("A", &["a0", "a1"]);
+        let cpd = TabularCPD::new(x, vec![a], vec![vec![0.25, 0.75], vec![1.0, 0.0]]);
+        let expected = "P(X | A)\nA  | X=x0   X=x1\na0 | 0.2500 0.7500\na1 | 1.0000 0.0000";
+        assert_eq!(cpd.to_string(), expected);
+    }
+
+    #[test]
+    fn test_display_marginal() {
+        let x = DiscreteVariable::new("X", &["x0", "x1"]);
+        let cpd = TabularCPD::marginal(x, vec![0.5, 0.5]);
+        assert_eq!(cpd.to_string(), "P(X)\nX=x0   X=x1\n0.5000 0.5000");
+    }
+
+    fn binary(n: &str) -> DiscreteVariable {
+        DiscreteVariable::new(n, &["off", "on"])
+    }
+
+    #[test]
+    fn test_noisy_or() {
+        let cpd = NoisyOr::new(
+            binary("X"),
+            vec![binary("A"), binary("B")],
+            vec![0.5, 0.8],
+            0.0,
+        );
+        let t = cpd.to_tabular();
+        assert_eq!(t.distribution(&["off", "off"]), &[1.0, 0.0]);
+        assert_eq!(t.probability("on", &["on", "off"]), 0.5);
+        assert!((t.probability("off", &["on", "on"]) - 0.1).abs() < 1e-12);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_noisy_or_not_binary() {
+        let x = DiscreteVariable::new("X", &["a", "b", "c"]);
+        NoisyOr::new(x, vec![], vec![], 0.1);
+    }
+
+    #[test]
+    fn test_deterministic() {
+        let cpd = Deterministic::new(
+            binary("X"),
+            vec![binary("A"), binary("B")],
+            |u: &[usize]| u[0] & u[1],
+        );
+        let t = cpd.to_tabular();
+        assert_eq!(
+            t.table(),
+            &[
+                vec![1.0, 0.0],
+                vec![1.0, 0.0],
+                vec![1.0, 0.0],
+                vec![0.0, 1.0]
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_deterministic_out_of_range() {
+        let cpd = Deterministic::new(binary("X"), vec![], |_: &[usize]| 2);
+        cpd.to_tabular();
+    }
+
+    #[test]
+    fn test_uniform() {
+        let x = DiscreteVariable::new("X", &["a", "b", "c", "d"]);
+        let t = Uniform::new(x, vec![binary("A")]).to_tabular();
+        assert_eq!(t.table(), &[vec![0.25; 4], vec![0.25; 4]]);
+        assert_eq!(t.to_tabular(), t);
+    }
+}
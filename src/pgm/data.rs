@@ -0,0 +1,366 @@
+//! discrete data sets
+use crate::pgm::variable::DiscreteVariable;
+use crate::rng::SplitMix64;
+use std::collections::BTreeSet;
+use std::io;
+use std::io::BufRead;
+use std::io::Write;
+
+fn invalid_line(line_nb: usize, msg: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("line {}: {}", line_nb + 1, msg),
+    )
+}
+
+/// Discrete table loaded from labeled observations, see [DataSet::from_csv]
+pub type DiscreteTable = DataSet;
+
+/// Discrete data set
+/// # Description
+/// Columns are discrete variables and every row holds one observation as
+/// state indices of the columns, see [DiscreteVariable::state_index].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataSet {
+    variables: Vec<DiscreteVariable>,
+    rows: Vec<Vec<usize>>,
+}
+
+impl DataSet {
+    /// Create a data set from its columns and rows
+    /// # Description
+    /// Panics if two columns have the same name, if a row does not have
+    /// one entry per column or if an entry is not a state of its column.
+    ///
+    /// # Example
+    /// ```
+    /// use pgm_rust::pgm::data::DataSet;
+    /// use pgm_rust::pgm::variable::DiscreteVariable;
+    ///
+    /// let rain = DiscreteVariable::new("rain", &["no", "yes"]);
+    /// let wet = DiscreteVariable::new("wet", &["no", "yes"]);
+    /// let data = DataSet::new(vec![rain, wet], vec![vec![0, 0], vec![1, 1], vec![1, 0]]);
+    /// data.counts(&["rain"]) == vec![1, 2]; // true
+    /// ```
+    pub fn new(variables: Vec<DiscreteVariable>, rows: Vec<Vec<usize>>) -> DataSet {
+        for (i, v) in variables.iter().enumerate() {
+            if variables[..i].iter().any(|w| w.name() == v.name()) {
+                panic!("column {} is repeated", v.name());
+            }
+        }
+        for (i, row) in rows.iter().enumerate() {
+            if row.len() != variables.len() {
+                panic!(
+                    "row {} has {} entries, {} expected",
+                    i,
+                    row.len(),
+                    variables.len()
+                );
+            }
+            for (v, s) in variables.iter().zip(row) {
+                if *s >= v.cardinality() {
+                    panic!("row {} has state {} for {}", i, s, v.name());
+                }
+            }
+        }
+        DataSet { variables, rows }
+    }
+
+    /// columns of the data set
+    pub fn variables(&self) -> &[DiscreteVariable] {
+        &self.variables
+    }
+
+    /// rows of the data set as state indices
+    pub fn rows(&self) -> &[Vec<usize>] {
+        &self.rows
+    }
+
+    /// number of rows
+    pub fn nb_rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// position of a column
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.variables.iter().position(|v| v.name() == name)
+    }
+
+    /// column by name
+    pub fn variable(&self, name: &str) -> Option<&DiscreteVariable> {
+        self.variables.iter().find(|v| v.name() == name)
+    }
+
+    /// Read a data set from comma separated values
+    /// # Description
+    /// The first line holds the column names, every other line an
+    /// observation. Fields are trimmed, empty lines are skipped and quoting
+    /// is not supported. The states of a column are the distinct labels
+    /// found in it, sorted.
+    ///
+    /// # Example
+    /// ```
+    /// use pgm_rust::pgm::data::DiscreteTable;
+    ///
+    /// let csv = "rain,wet\nyes,yes\nno,no\nno,yes\n";
+    /// let data = DiscreteTable::from_csv(csv.as_bytes()).unwrap();
+    /// data.variable("wet").unwrap().states() == ["no", "yes"]; // true
+    /// data.rows()[2] == vec![0, 1]; // true
+    /// ```
+    pub fn from_csv<R: BufRead>(reader: R) -> io::Result<DataSet> {
+        let mut names: Vec<String> = Vec::new();
+        let mut records: Vec<Vec<String>> = Vec::new();
+        for (line_nb, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let fields: Vec<String> = line.split(',').map(|f| f.trim().to_string()).collect();
+            if names.is_empty() {
+                for (i, f) in fields.iter().enumerate() {
+                    if f.is_empty() || fields[..i].contains(f) {
+                        return Err(invalid_line(
+                            line_nb,
+                            "column names must be distinct and not empty",
+                        ));
+                    }
+                }
+                names = fields;
+            } else if fields.len() != names.len() {
+                let msg = format!("{} fields, {} expected", fields.len(), names.len());
+                return Err(invalid_line(line_nb, &msg));
+            } else {
+                records.push(fields);
+            }
+        }
+        if names.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "missing header"));
+        }
+        let variables: Vec<DiscreteVariable> = names
+            .iter()
+            .enumerate()
+            .map(|(j, n)| {
+                let labels: BTreeSet<&str> = records.iter().map(|r| r[j].as_str()).collect();
+                let states: Vec<&str> = labels.into_iter().collect();
+                if states.is_empty() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("no observation of {n}"),
+                    ));
+                }
+                Ok(DiscreteVariable::new(n, &states))
+            })
+            .collect::<io::Result<_>>()?;
+        let rows = records
+            .iter()
+            .map(|r| {
+                variables
+                    .iter()
+                    .zip(r)
+                    .map(|(v, label)| v.state_index(label).unwrap())
+                    .collect()
+            })
+            .collect();
+        Ok(DataSet { variables, rows })
+    }
+
+    /// Write the data set as comma separated values readable by
+    /// [DataSet::from_csv]
+    pub fn to_csv<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let names: Vec<&str> = self.variables.iter().map(|v| v.name().as_str()).collect();
+        writeln!(writer, "{}", names.join(","))?;
+        for labels in self.labeled_rows() {
+            writeln!(writer, "{}", labels.join(","))?;
+        }
+        Ok(())
+    }
+
+    /// rows of the data set as state names
+    pub fn labeled_rows(&self) -> impl Iterator<Item = Vec<&str>> + '_ {
+        self.rows.iter().map(|row| {
+            self.variables
+                .iter()
+                .zip(row)
+                .map(|(v, s)| v.states()[*s].as_str())
+                .collect()
+        })
+    }
+
+    /// Keep some columns, in the given order. Panics if a name is not a
+    /// column.
+    pub fn select(&self, names: &[&str]) -> DataSet {
+        let cols = self.columns(names);
+        let variables = cols.iter().map(|c| self.variables[*c].clone()).collect();
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| cols.iter().map(|c| row[*c]).collect())
+            .collect();
+        DataSet::new(variables, rows)
+    }
+
+    /// Split rows into a training and a test set
+    /// # Description
+    /// Rows are shuffled with a generator seeded by `seed`, then the first
+    /// `round(test_fraction * nb_rows)` of them go to the test set. Both
+    /// sets keep every column and their states, and rows keep their
+    /// original relative order.
+    ///
+    /// # Example
+    /// ```
+    /// use pgm_rust::pgm::data::DiscreteTable;
+    ///
+    /// let csv = "a\n0\n1\n0\n1\n";
+    /// let data = DiscreteTable::from_csv(csv.as_bytes()).unwrap();
+    /// let (train, test) = data.train_test_split(0.25, 42);
+    /// train.nb_rows() == 3; // true
+    /// test.nb_rows() == 1; // true
+    /// ```
+    pub fn train_test_split(&self, test_fraction: f64, seed: u64) -> (DataSet, DataSet) {
+        if !(0.0..=1.0).contains(&test_fraction) {
+            panic!("test fraction must be in [0, 1], got {test_fraction}");
+        }
+        let mut order: Vec<usize> = (0..self.rows.len()).collect();
+        SplitMix64::new(seed).shuffle(&mut order);
+        let nb_test = (test_fraction * self.rows.len() as f64).round() as usize;
+        let mut is_test = vec![false; self.rows.len()];
+        for i in &order[..nb_test] {
+            is_test[*i] = true;
+        }
+        let (mut train, mut test) = (Vec::new(), Vec::new());
+        for (row, t) in self.rows.iter().zip(is_test) {
+            if t {
+                test.push(row.clone());
+            } else {
+                train.push(row.clone());
+            }
+        }
+        (
+            DataSet {
+                variables: self.variables.clone(),
+                rows: train,
+            },
+            DataSet {
+                variables: self.variables.clone(),
+                rows: test,
+            },
+        )
+    }
+
+    fn columns(&self, names: &[&str]) -> Vec<usize> {
+        names
+            .iter()
+            .map(|n| match self.column_index(n) {
+                None => panic!("{n} is not a column of the data set"),
+                Some(i) => i,
+            })
+            .collect()
+    }
+
+    /// Count joint configurations of some columns
+    /// # Description
+    /// Returns a contingency table flattened in lexicographic order of
+    /// state indices, the last column varying fastest. This is the row
+    /// order of [TabularCPD](crate::pgm::cpd::TabularCPD) when the parents
+    /// come first and the variable comes last. Panics if a name is not a
+    /// column.
+    pub fn counts(&self, names: &[&str]) -> Vec<usize> {
+        let cols = self.columns(names);
+        let size: usize = cols
+            .iter()
+            .map(|c| self.variables[*c].cardinality())
+            .product();
+        let mut table = vec![0; size];
+        for row in &self.rows {
+            let mut index = 0;
+            for c in &cols {
+                index = index * self.variables[*c].cardinality() + row[*c];
+            }
+            table[index] += 1;
+        }
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mk_data() -> DataSet {
+        let a = DiscreteVariable::new("A", &["a0", "a1"]);
+        let b = DiscreteVariable::new("B", &["b0", "b1", "b2"]);
+        DataSet::new(
+            vec![a, b],
+            vec![vec![0, 0], vec![0, 2], vec![1, 2], vec![1, 2], vec![0, 0]],
+        )
+    }
+
+    #[test]
+    fn test_counts() {
+        let d = mk_data();
+        assert_eq!(d.counts(&["A"]), vec![3, 2]);
+        assert_eq!(d.counts(&["B"]), vec![2, 0, 3]);
+        assert_eq!(d.counts(&["A", "B"]), vec![2, 0, 1, 0, 0, 2]);
+        assert_eq!(d.counts(&["B", "A"]), vec![2, 0, 0, 0, 1, 2]);
+        assert_eq!(d.counts(&[]), vec![5]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_bad_state() {
+        let a = DiscreteVariable::new("A", &["a0", "a1"]);
+        DataSet::new(vec![a], vec![vec![2]]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_counts_unknown_column() {
+        mk_data().counts(&["C"]);
+    }
+
+    #[test]
+    fn test_from_csv() {
+        let csv = "A, B\n\na1,b2\na0,b0\na1,b0\n";
+        let d = DiscreteTable::from_csv(csv.as_bytes()).unwrap();
+        assert_eq!(d.variable("A").unwrap().states(), ["a0", "a1"]);
+        assert_eq!(d.variable("B").unwrap().states(), ["b0", "b2"]);
+        assert_eq!(d.rows(), &[vec![1, 1], vec![0, 0], vec![1, 0]]);
+        let labels: Vec<Vec<&str>> = d.labeled_rows().collect();
+        assert_eq!(labels[0], vec!["a1", "b2"]);
+        let mut out = Vec::new();
+        d.to_csv(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "A,B\na1,b2\na0,b0\na1,b0\n"
+        );
+    }
+
+    #[test]
+    fn test_from_csv_invalid() {
+        assert!(DiscreteTable::from_csv("A,B\na0\n".as_bytes()).is_err());
+        assert!(DiscreteTable::from_csv("A,A\na0,a1\n".as_bytes()).is_err());
+        assert!(DiscreteTable::from_csv("".as_bytes()).is_err());
+        assert!(DiscreteTable::from_csv("A\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_select() {
+        let d = mk_data().select(&["B"]);
+        assert_eq!(d.variables().len(), 1);
+        assert_eq!(d.counts(&["B"]), vec![2, 0, 3]);
+    }
+
+    #[test]
+    fn test_train_test_split() {
+        let d = mk_data();
+        let (train, test) = d.train_test_split(0.4, 7);
+        assert_eq!(train.nb_rows(), 3);
+        assert_eq!(test.nb_rows(), 2);
+        assert_eq!(d.train_test_split(0.4, 7), (train.clone(), test.clone()));
+        let mut all: Vec<Vec<usize>> = train.rows().iter().chain(test.rows()).cloned().collect();
+        let mut expected = d.rows().to_vec();
+        all.sort();
+        expected.sort();
+        assert_eq!(all, expected);
+    }
+}
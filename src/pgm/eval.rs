@@ -0,0 +1,474 @@
+//! evaluating learned models
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::edgetype::EdgeType;
+use crate::pgm::bayesnet::BayesianNetwork;
+use crate::pgm::cpd::TabularCPD;
+use crate::pgm::data::DiscreteTable;
+use crate::pgm::query::Distribution;
+use crate::pgm::variable::DiscreteVariable;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+/// differences between the edges of a reference graph and a learned graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EdgeConfusion {
+    /// adjacencies of the learned graph missing from the reference graph
+    pub added: usize,
+    /// adjacencies of the reference graph missing from the learned graph
+    pub removed: usize,
+    /// adjacencies of both graphs with a different orientation
+    pub reversed: usize,
+    /// adjacencies of both graphs with the same orientation
+    pub correct: usize,
+}
+
+/// orientations of the edges between each pair of vertices `(a, b)` with
+/// `a < b`: 1 for `a -> b`, -1 for `b -> a` and 0 for undirected edges
+fn orientations<N, E, G>(g: &G) -> BTreeMap<(String, String), BTreeSet<i8>>
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let mut pairs: BTreeMap<(String, String), BTreeSet<i8>> = BTreeMap::new();
+    for e in g.edges() {
+        let (s, t) = (e.start().id(), e.end().id());
+        if s == t {
+            continue;
+        }
+        let forward = s < t;
+        let key = if forward {
+            (s.clone(), t.clone())
+        } else {
+            (t.clone(), s.clone())
+        };
+        let orientation = match (e.has_type(), forward) {
+            (EdgeType::Undirected, _) => 0,
+            (EdgeType::Directed, true) => 1,
+            (EdgeType::Directed, false) => -1,
+        };
+        pairs.entry(key).or_default().insert(orientation);
+    }
+    pairs
+}
+
+/// Compare the edges of a learned graph with those of a reference graph
+/// # Description
+/// Adjacencies are compared between vertices with the same identifier.
+/// An adjacency present in both graphs is reversed when its orientation
+/// differs, including an arc against an undirected edge, so that directed
+/// and partially directed graphs can be compared. Loops are ignored.
+///
+/// # Args
+/// - g_true: reference graph
+/// - g_learned: learned graph
+///
+/// # Example
+/// ```
+/// use pgm_rust::pgm::bayesnet::BayesianNetwork;
+/// use pgm_rust::pgm::eval::edge_confusion;
+///
+/// let g_true = BayesianNetwork::from_arcs("t", &["a", "b", "c"], &[("a", "b"), ("b", "c")]);
+/// let g_learned = BayesianNetwork::from_arcs("l", &["a", "b", "c"], &[("b", "a"), ("a", "c")]);
+/// let confusion = edge_confusion(&g_true, &g_learned);
+/// confusion.added == 1 && confusion.removed == 1 && confusion.reversed == 1; // true
+/// ```
+pub fn edge_confusion<N, E, G1, G2>(g_true: &G1, g_learned: &G2) -> EdgeConfusion
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G1: GraphTrait<N, E>,
+    G2: GraphTrait<N, E>,
+{
+    let reference = orientations(g_true);
+    let learned = orientations(g_learned);
+    let mut confusion = EdgeConfusion {
+        added: 0,
+        removed: 0,
+        reversed: 0,
+        correct: 0,
+    };
+    for (pair, orientation) in &reference {
+        match learned.get(pair) {
+            None => confusion.removed += 1,
+            Some(o) if o == orientation => confusion.correct += 1,
+            Some(_) => confusion.reversed += 1,
+        }
+    }
+    confusion.added = learned
+        .keys()
+        .filter(|pair| !reference.contains_key(*pair))
+        .count();
+    confusion
+}
+
+/// Compute the structural hamming distance between two graphs
+/// # Description
+/// Number of edge additions, removals and reversals turning `g1` into
+/// `g2`, see [edge_confusion]. The distance is symmetric. Comparing the
+/// CPDAGs of two networks, see [to_cpdag], gives a distance between their
+/// equivalence classes.
+///
+/// [to_cpdag]: crate::pgm::learning::pdag::to_cpdag
+///
+/// # Example
+/// ```
+/// use pgm_rust::pgm::bayesnet::BayesianNetwork;
+/// use pgm_rust::pgm::eval::structural_hamming_distance;
+///
+/// let g1 = BayesianNetwork::from_arcs("g1", &["a", "b", "c"], &[("a", "b"), ("b", "c")]);
+/// let g2 = BayesianNetwork::from_arcs("g2", &["a", "b", "c"], &[("b", "a"), ("a", "c")]);
+/// structural_hamming_distance(&g1, &g2) == 3; // true
+/// ```
+///
+/// # References
+/// Tsamardinos I, Brown L, Aliferis C. The max-min hill-climbing bayesian
+/// network structure learning algorithm. 2006.
+pub fn structural_hamming_distance<N, E, G1, G2>(g1: &G1, g2: &G2) -> usize
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G1: GraphTrait<N, E>,
+    G2: GraphTrait<N, E>,
+{
+    let confusion = edge_confusion(g1, g2);
+    confusion.added + confusion.removed + confusion.reversed
+}
+
+/// column of `variable` in `data` with, for each state of the column, the
+/// index of the state in `variable`
+fn state_map(data: &DiscreteTable, variable: &DiscreteVariable) -> (usize, Vec<usize>) {
+    let name = variable.name();
+    let column = match data.column_index(name) {
+        None => panic!("{name} is not a column of the data set"),
+        Some(i) => i,
+    };
+    let states = data.variables()[column]
+        .states()
+        .iter()
+        .map(|s| match variable.state_index(s) {
+            None => panic!("{name} has no state {s}"),
+            Some(i) => i,
+        })
+        .collect();
+    (column, states)
+}
+
+/// Compute the log-likelihood of data under a network
+/// # Description
+/// Sum over the rows of `data` of the natural logarithm of the probability
+/// of the row, that is of the probabilities of each variable given its
+/// parents. Columns are matched to variables by name and states by name,
+/// so the columns may list states in another order than the tables. Columns
+/// that are not variables of the network are ignored. The result is
+/// negative infinity if a row has probability zero. Panics if the network
+/// is not fully specified or if a variable is not a column of `data`.
+///
+/// # Example
+/// ```
+/// use pgm_rust::pgm::bayesnet::BayesianNetwork;
+/// use pgm_rust::pgm::cpd::TabularCPD;
+/// use pgm_rust::pgm::data::DataSet;
+/// use pgm_rust::pgm::eval::log_likelihood;
+/// use pgm_rust::pgm::variable::DiscreteVariable;
+///
+/// let a = DiscreteVariable::new("A", &["0", "1"]);
+/// let mut bn = BayesianNetwork::from_arcs("bn", &["A"], &[]);
+/// bn.add_cpd(TabularCPD::marginal(a.clone(), vec![0.5, 0.5]));
+/// let data = DataSet::new(vec![a], vec![vec![0], vec![1]]);
+/// log_likelihood(&bn, &data) == 2.0 * 0.5_f64.ln(); // true
+/// ```
+pub fn log_likelihood(bn: &BayesianNetwork, data: &DiscreteTable) -> f64 {
+    if !bn.is_fully_specified() {
+        panic!("{bn} has variables without table");
+    }
+    let mut ll = 0.0;
+    for v in bn.topological_order() {
+        let cpd: &TabularCPD = bn.cpd(v).unwrap();
+        let (column, states) = state_map(data, cpd.variable());
+        let parents: Vec<(usize, Vec<usize>)> =
+            cpd.parents().iter().map(|p| state_map(data, p)).collect();
+        for row in data.rows() {
+            let config: Vec<usize> = parents.iter().map(|(c, m)| m[row[*c]]).collect();
+            ll += cpd.table()[cpd.row_index(&config)][states[row[column]]].ln();
+        }
+    }
+    ll
+}
+
+/// number of free parameters of the tables of a network
+fn nb_parameters(bn: &BayesianNetwork) -> usize {
+    bn.cpds()
+        .values()
+        .map(|cpd| cpd.table().len() * (cpd.variable().cardinality() - 1))
+        .sum()
+}
+
+/// Compute the bayesian information criterion of a network given data
+/// # Description
+/// `ll - ln(N) k / 2` where `ll` is the [log_likelihood] of `data`, `N`
+/// its number of rows and `k` the number of free parameters of the tables.
+/// Higher is better, as with [Score::Bic], which is the same score for the
+/// tables fitted by maximum likelihood.
+///
+/// [Score::Bic]: crate::pgm::learning::Score::Bic
+///
+/// # Example
+/// ```
+/// use pgm_rust::pgm::bayesnet::BayesianNetwork;
+/// use pgm_rust::pgm::data::DataSet;
+/// use pgm_rust::pgm::eval::{bic, log_likelihood};
+/// use pgm_rust::pgm::learning::fit_mle;
+/// use pgm_rust::pgm::variable::DiscreteVariable;
+///
+/// let a = DiscreteVariable::new("A", &["0", "1"]);
+/// let b = DiscreteVariable::new("B", &["0", "1"]);
+/// let rows: Vec<Vec<usize>> = (0..20).map(|i| vec![i % 2, i % 3 % 2]).collect();
+/// let data = DataSet::new(vec![a, b], rows);
+/// let bn = fit_mle(&BayesianNetwork::from_arcs("bn", &["A", "B"], &[("A", "B")]), &data);
+/// bic(&bn, &data) < log_likelihood(&bn, &data); // true
+/// ```
+///
+/// # References
+/// Schwarz G. Estimating the dimension of a model. 1978.
+pub fn bic(bn: &BayesianNetwork, data: &DiscreteTable) -> f64 {
+    let n = data.nb_rows().max(1) as f64;
+    log_likelihood(bn, data) - 0.5 * n.ln() * nb_parameters(bn) as f64
+}
+
+/// Compute the akaike information criterion of a network given data
+/// # Description
+/// `ll - k` where `ll` is the [log_likelihood] of `data` and `k` the number
+/// of free parameters of the tables. This is the usual `2k - 2 ll` divided
+/// by `-2`, so that higher is better as with [bic].
+///
+/// # References
+/// Akaike H. A new look at the statistical model identification. 1974.
+pub fn aic(bn: &BayesianNetwork, data: &DiscreteTable) -> f64 {
+    log_likelihood(bn, data) - nb_parameters(bn) as f64
+}
+
+/// pairs of probabilities of `p` and `q` for each joint state, `q` being
+/// reordered to the variables and states of `p`
+fn aligned(p: &Distribution, q: &Distribution) -> Vec<(f64, f64)> {
+    let same_scope = p.variables().len() == q.variables().len()
+        && p.variables().iter().all(|v| {
+            q.variables().iter().any(|w| {
+                w.name() == v.name()
+                    && w.cardinality() == v.cardinality()
+                    && v.states().iter().all(|s| w.state_index(s).is_some())
+            })
+        });
+    if !same_scope {
+        panic!("distributions are not over the same variables");
+    }
+    let cards: Vec<usize> = p.variables().iter().map(|v| v.cardinality()).collect();
+    let mut pairs = Vec::with_capacity(p.values().len());
+    let mut states = vec![0; cards.len()];
+    for pv in p.values() {
+        let names: Vec<(&String, &str)> = p
+            .variables()
+            .iter()
+            .zip(&states)
+            .map(|(v, s)| (v.name(), v.states()[*s].as_str()))
+            .collect();
+        let q_states: Vec<&str> = q
+            .variables()
+            .iter()
+            .map(|w| names.iter().find(|(n, _)| *n == w.name()).unwrap().1)
+            .collect();
+        pairs.push((*pv, q.probability(&q_states)));
+        // next joint state, last variable fastest
+        for k in (0..cards.len()).rev() {
+            states[k] += 1;
+            if states[k] < cards[k] {
+                break;
+            }
+            states[k] = 0;
+        }
+    }
+    pairs
+}
+
+/// Compute the cross-entropy of a distribution relative to another
+/// # Description
+/// `H(p, q) = - sum p(x) ln q(x)` in nats. Terms with `p(x) = 0` are zero,
+/// a term with `p(x) > 0` and `q(x) = 0` makes the cross-entropy infinite.
+/// Both distributions must be over the same variables with the same
+/// states, possibly in another order. Panics otherwise.
+///
+/// # Example
+/// ```
+/// use pgm_rust::pgm::eval::cross_entropy;
+/// use pgm_rust::pgm::query::Distribution;
+/// use pgm_rust::pgm::variable::DiscreteVariable;
+///
+/// let a = DiscreteVariable::new("A", &["0", "1"]);
+/// let p = Distribution::new(vec![a.clone()], vec![0.5, 0.5]);
+/// let q = Distribution::new(vec![a], vec![1.0, 0.0]);
+/// cross_entropy(&p, &p) == 2.0_f64.ln(); // true
+/// cross_entropy(&p, &q) == f64::INFINITY; // true
+/// ```
+pub fn cross_entropy(p: &Distribution, q: &Distribution) -> f64 {
+    aligned(p, q)
+        .into_iter()
+        .filter(|(pv, _)| *pv > 0.0)
+        .map(|(pv, qv)| {
+            if qv == 0.0 {
+                f64::INFINITY
+            } else {
+                -pv * qv.ln()
+            }
+        })
+        .sum()
+}
+
+/// Compute the Kullback-Leibler divergence of a distribution from another
+/// # Description
+/// `KL(p || q) = sum p(x) ln (p(x) / q(x))` in nats, with the zero
+/// conventions of [cross_entropy]: states with `p(x) = 0` do not count and
+/// the divergence is infinite if `q` gives probability zero to a state of
+/// positive probability under `p`. The divergence is not symmetric. It is
+/// typically used with `p` an exact marginal and `q` an approximation.
+///
+/// # Example
+/// ```
+/// use pgm_rust::pgm::eval::kl_divergence;
+/// use pgm_rust::pgm::query::Distribution;
+/// use pgm_rust::pgm::variable::DiscreteVariable;
+///
+/// let a = DiscreteVariable::new("A", &["0", "1"]);
+/// let p = Distribution::new(vec![a.clone()], vec![1.0, 0.0]);
+/// let q = Distribution::new(vec![a], vec![0.5, 0.5]);
+/// kl_divergence(&p, &q) == 2.0_f64.ln(); // true
+/// kl_divergence(&q, &p) == f64::INFINITY; // true
+/// ```
+///
+/// # References
+/// Cover T, Thomas J. Elements of Information Theory. 2006, p. 19.
+pub fn kl_divergence(p: &Distribution, q: &Distribution) -> f64 {
+    aligned(p, q)
+        .into_iter()
+        .filter(|(pv, _)| *pv > 0.0)
+        .map(|(pv, qv)| {
+            if qv == 0.0 {
+                f64::INFINITY
+            } else {
+                pv * (pv / qv).ln()
+            }
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pgm::bayesnet::BayesianNetwork;
+    use crate::pgm::data::DataSet;
+    use crate::pgm::learning::pdag::to_cpdag;
+    use crate::pgm::learning::{fit_mle, network_score, Score};
+
+    fn mk_bn(arcs: &[(&str, &str)]) -> BayesianNetwork {
+        BayesianNetwork::from_arcs("bn", &["a", "b", "c", "d"], arcs)
+    }
+
+    #[test]
+    fn test_edge_confusion() {
+        let g_true = mk_bn(&[("a", "b"), ("b", "c"), ("c", "d")]);
+        let g_learned = mk_bn(&[("a", "b"), ("c", "b"), ("a", "d")]);
+        let confusion = edge_confusion(&g_true, &g_learned);
+        assert_eq!(
+            confusion,
+            EdgeConfusion {
+                added: 1,
+                removed: 1,
+                reversed: 1,
+                correct: 1
+            }
+        );
+        assert_eq!(structural_hamming_distance(&g_true, &g_learned), 3);
+        assert_eq!(structural_hamming_distance(&g_learned, &g_true), 3);
+        assert_eq!(structural_hamming_distance(&g_true, &g_true), 0);
+    }
+
+    #[test]
+    fn test_structural_hamming_distance_cpdag() {
+        // a -> b -> c and a <- b <- c are equivalent, a -> b <- c is not
+        let chain = mk_bn(&[("a", "b"), ("b", "c")]);
+        let reversed = mk_bn(&[("b", "a"), ("c", "b")]);
+        let collider = mk_bn(&[("a", "b"), ("c", "b")]);
+        assert_eq!(structural_hamming_distance(&chain, &reversed), 2);
+        let (c1, c2) = (to_cpdag(&chain).to_graph(), to_cpdag(&reversed).to_graph());
+        assert_eq!(structural_hamming_distance(&c1, &c2), 0);
+        let c3 = to_cpdag(&collider).to_graph();
+        assert_eq!(structural_hamming_distance(&c1, &c3), 2);
+    }
+
+    fn mk_data() -> DataSet {
+        let a = DiscreteVariable::new("a", &["0", "1"]);
+        let b = DiscreteVariable::new("b", &["0", "1", "2"]);
+        let rows: Vec<Vec<usize>> = (0..30).map(|i| vec![i % 2, (i / 2 + i % 2) % 3]).collect();
+        DataSet::new(vec![a, b], rows)
+    }
+
+    #[test]
+    fn test_bic_matches_network_score() {
+        let data = mk_data();
+        let bn = fit_mle(
+            &BayesianNetwork::from_arcs("bn", &["a", "b"], &[("a", "b")]),
+            &data,
+        );
+        let expected = network_score(&data, &bn, Score::Bic);
+        assert!((bic(&bn, &data) - expected).abs() < 1e-9);
+        // 1 parameter for a, 2 * 2 for b
+        assert!((log_likelihood(&bn, &data) - aic(&bn, &data) - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_log_likelihood_state_names() {
+        // the column lists the states of a in the reverse order
+        let a = DiscreteVariable::new("a", &["0", "1"]);
+        let mut bn = BayesianNetwork::from_arcs("bn", &["a"], &[]);
+        bn.add_cpd(TabularCPD::marginal(a, vec![0.25, 0.75]));
+        let column = DiscreteVariable::new("a", &["1", "0"]);
+        let data = DataSet::new(vec![column], vec![vec![0], vec![0]]);
+        assert!((log_likelihood(&bn, &data) - 2.0 * 0.75_f64.ln()).abs() < 1e-12);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_log_likelihood_unspecified() {
+        log_likelihood(&mk_bn(&[("a", "b")]), &mk_data());
+    }
+
+    #[test]
+    fn test_kl_divergence_reordered_scope() {
+        let a = DiscreteVariable::new("a", &["0", "1"]);
+        let b = DiscreteVariable::new("b", &["x", "y", "z"]);
+        let p = Distribution::new(
+            vec![a.clone(), b.clone()],
+            vec![0.1, 0.2, 0.0, 0.3, 0.1, 0.3],
+        );
+        // same joint distribution over (b, a) with the states of a reversed
+        let a_rev = DiscreteVariable::new("a", &["1", "0"]);
+        let q = Distribution::new(vec![b, a_rev], vec![0.3, 0.1, 0.1, 0.2, 0.3, 0.0]);
+        assert!(kl_divergence(&p, &q).abs() < 1e-12);
+        assert!((cross_entropy(&p, &q) - cross_entropy(&p, &p)).abs() < 1e-12);
+        let uniform = Distribution::new(vec![a.clone()], vec![0.5, 0.5]);
+        let skewed = Distribution::new(vec![a], vec![0.9, 0.1]);
+        let expected = 0.5 * (0.5_f64 / 0.9).ln() + 0.5 * (0.5_f64 / 0.1).ln();
+        assert!((kl_divergence(&uniform, &skewed) - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_kl_divergence_different_scope() {
+        let a = DiscreteVariable::new("a", &["0", "1"]);
+        let b = DiscreteVariable::new("b", &["0", "1"]);
+        let p = Distribution::new(vec![a], vec![0.5, 0.5]);
+        let q = Distribution::new(vec![b], vec![0.5, 0.5]);
+        kl_divergence(&p, &q);
+    }
+}
@@ -0,0 +1,692 @@
+//! bipartite graphs of variables and factors
+use crate::factor::Factor;
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::graph_obj::GraphObject as GraphObjectTrait;
+use crate::graph::traits::idgen::IdGenerator;
+use crate::graph::types::edge::Edge;
+use crate::graph::types::edgetype::EdgeType;
+use crate::graph::types::graph::Graph;
+use crate::graph::types::idgen::UuidGenerator;
+use crate::graph::types::node::Node;
+use crate::pgm::variable::DiscreteVariable;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// node data key holding the class of a node of a factor graph
+pub const KIND_KEY: &str = "kind";
+/// class of variable nodes
+pub const VARIABLE_KIND: &str = "variable";
+/// class of factor nodes
+pub const FACTOR_KIND: &str = "factor";
+
+/// prefix of the identifiers given to factors by [FactorGraph::from_factors]
+pub const FACTOR_PREFIX: &str = "phi";
+
+fn mk_kind_node(nid: &str, kind: &str) -> Node {
+    let data = HashMap::from([(KIND_KEY.to_string(), vec![kind.to_string()])]);
+    Node::new(nid.to_string(), data)
+}
+
+fn has_kind(n: &Node, kind: &str) -> bool {
+    match n.data().get(KIND_KEY) {
+        None => false,
+        Some(ks) => ks.len() == 1 && ks[0] == kind,
+    }
+}
+
+/// identifier of the edge between the nodes `a` and `b`
+/// # Description
+/// The identifier is `a--b` where `-` and `\` are escaped with a `\` in
+/// both identifiers. An escaped identifier never contains `--`, hence two
+/// different pairs never share an edge identifier. Identifiers without `-`
+/// or `\` are left as is.
+fn pair_id(a: &str, b: &str) -> String {
+    let escape = |s: &str| s.replace('\\', "\\\\").replace('-', "\\-");
+    format!("{}--{}", escape(a), escape(b))
+}
+
+/// variables of the scopes of `factors` by name, panics if two factors
+/// disagree on the states of a variable
+fn scope_variables<'a, I>(factors: I) -> HashMap<&'a String, &'a DiscreteVariable>
+where
+    I: IntoIterator<Item = &'a Factor>,
+{
+    let mut variables: HashMap<&String, &DiscreteVariable> = HashMap::new();
+    for f in factors {
+        for v in f.scope() {
+            match variables.get(v.name()) {
+                Some(w) if *w != v => panic!("{} has different states in two factors", v.name()),
+                _ => variables.insert(v.name(), v),
+            };
+        }
+    }
+    variables
+}
+
+/// maximal cliques of the graph given by sorted adjacency lists, each
+/// clique being sorted, found by the Bron-Kerbosch algorithm with pivoting
+fn maximal_cliques(neighbors: &[BTreeSet<usize>]) -> Vec<Vec<usize>> {
+    let mut cliques = Vec::new();
+    let mut stack: Vec<(Vec<usize>, BTreeSet<usize>, BTreeSet<usize>)> =
+        vec![(Vec::new(), (0..neighbors.len()).collect(), BTreeSet::new())];
+    while let Some((clique, mut candidates, mut excluded)) = stack.pop() {
+        if candidates.is_empty() {
+            if excluded.is_empty() {
+                let mut c = clique;
+                c.sort();
+                cliques.push(c);
+            }
+            continue;
+        }
+        let pivot = *candidates
+            .iter()
+            .chain(excluded.iter())
+            .max_by_key(|u| neighbors[**u].intersection(&candidates).count())
+            .unwrap();
+        let branches: Vec<usize> = candidates.difference(&neighbors[pivot]).copied().collect();
+        for v in branches {
+            let mut c = clique.clone();
+            c.push(v);
+            stack.push((
+                c,
+                candidates.intersection(&neighbors[v]).copied().collect(),
+                excluded.intersection(&neighbors[v]).copied().collect(),
+            ));
+            candidates.remove(&v);
+            excluded.insert(v);
+        }
+    }
+    cliques.sort();
+    cliques
+}
+
+/// Factor graph
+/// # Description
+/// A factor graph is a bipartite graph whose vertex classes are the random
+/// variables and the factors of a model. A factor is adjacent to the
+/// variables of its scope. The class of a node is stored in its data under
+/// [KIND_KEY]. Edges are undirected. A factor graph built from [Factor]s
+/// keeps the values of each factor with its node, see
+/// [FactorGraph::factor], while one built from scopes only holds the
+/// structure.
+///
+/// # References
+/// Koller D, Friedman N. Probabilistic Graphical Models. 2009, p. 123.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FactorGraph {
+    graph: Graph<Node, Edge<Node>>,
+    factors: HashMap<String, Factor>,
+}
+
+impl FactorGraph {
+    /// Create a factor graph from factor scopes
+    /// # Description
+    /// `scopes` maps factor identifiers to the identifiers of the variables
+    /// in their scope. Factor and variable identifiers must be distinct.
+    /// Every scope must be non empty. The factor graph holds no factor
+    /// values. The identifier of the factor graph is a random UUID, see
+    /// [FactorGraph::from_scopes_with].
+    ///
+    /// # Example
+    /// ```
+    /// use pgm_rust::pgm::factorgraph::FactorGraph;
+    /// use std::collections::HashMap;
+    /// use std::collections::HashSet;
+    ///
+    /// let scopes = HashMap::from([
+    ///     ("f1".to_string(), HashSet::from(["A".to_string(), "B".to_string()])),
+    ///     ("f2".to_string(), HashSet::from(["B".to_string()])),
+    /// ]);
+    /// let fg = FactorGraph::from_scopes(&scopes);
+    /// fg.variable_nodes().len() == 2; // true
+    /// fg.factor_nodes().len() == 2; // true
+    /// ```
+    pub fn from_scopes(scopes: &HashMap<String, HashSet<String>>) -> FactorGraph {
+        FactorGraph::from_scopes_with(scopes, &mut UuidGenerator)
+    }
+
+    /// Create a factor graph from factor scopes, the identifier of the
+    /// factor graph is produced by `idgen`, see [FactorGraph::from_scopes]
+    pub fn from_scopes_with<I: IdGenerator>(
+        scopes: &HashMap<String, HashSet<String>>,
+        idgen: &mut I,
+    ) -> FactorGraph {
+        let mut nodes: HashSet<Node> = HashSet::new();
+        let mut edges: HashSet<Edge<Node>> = HashSet::new();
+        for (fid, scope) in scopes {
+            if scope.is_empty() {
+                panic!("scope of factor {fid} is empty");
+            }
+            let fnode = mk_kind_node(fid, FACTOR_KIND);
+            nodes.insert(fnode.clone());
+            for vid in scope {
+                if scopes.contains_key(vid) {
+                    panic!("{vid} is both a variable and a factor");
+                }
+                let vnode = mk_kind_node(vid, VARIABLE_KIND);
+                edges.insert(Edge::undirected(
+                    pair_id(fid, vid),
+                    fnode.clone(),
+                    vnode,
+                    HashMap::new(),
+                ));
+            }
+        }
+        let gid = idgen.generate(&nodes.iter().collect(), &edges.iter().collect());
+        FactorGraph {
+            graph: Graph::new(gid, HashMap::new(), nodes, edges),
+            factors: HashMap::new(),
+        }
+    }
+
+    /// Create a factor graph from factors
+    /// # Description
+    /// The factor at position `i` of `factors` gets the identifier
+    /// [FACTOR_PREFIX] followed by `i` and is kept on its node. Variables
+    /// are identified by their name. Panics if a scope is empty, if a
+    /// variable has the identifier of a factor or if two factors give a
+    /// variable different states. The identifier of the factor graph is a
+    /// random UUID, see [FactorGraph::from_factors_with].
+    ///
+    /// # Example
+    /// ```
+    /// use pgm_rust::factor::Factor;
+    /// use pgm_rust::pgm::factorgraph::FactorGraph;
+    /// use pgm_rust::pgm::variable::DiscreteVariable;
+    ///
+    /// let a = DiscreteVariable::new("A", &["a0", "a1"]);
+    /// let b = DiscreteVariable::new("B", &["b0", "b1"]);
+    /// let f = Factor::new(vec![a.clone(), b], vec![1.0, 2.0, 3.0, 4.0]);
+    /// let g = Factor::new(vec![a], vec![0.5, 0.5]);
+    /// let fg = FactorGraph::from_factors(&[f.clone(), g]);
+    /// fg.variable_nodes().len() == 2; // true
+    /// fg.factor("phi0") == Some(&f); // true
+    /// ```
+    pub fn from_factors(factors: &[Factor]) -> FactorGraph {
+        FactorGraph::from_factors_with(factors, &mut UuidGenerator)
+    }
+
+    /// Create a factor graph from factors, the identifier of the factor
+    /// graph is produced by `idgen`, see [FactorGraph::from_factors]
+    pub fn from_factors_with<I: IdGenerator>(factors: &[Factor], idgen: &mut I) -> FactorGraph {
+        scope_variables(factors);
+        let named: HashMap<String, Factor> = factors
+            .iter()
+            .enumerate()
+            .map(|(i, f)| (format!("{FACTOR_PREFIX}{i}"), f.clone()))
+            .collect();
+        let scopes: HashMap<String, HashSet<String>> = named
+            .iter()
+            .map(|(fid, f)| {
+                (
+                    fid.clone(),
+                    f.scope().iter().map(|v| v.name().clone()).collect(),
+                )
+            })
+            .collect();
+        let mut fg = FactorGraph::from_scopes_with(&scopes, idgen);
+        fg.factors = named;
+        fg
+    }
+
+    /// Create a factor graph from the structure of a Markov network
+    /// # Description
+    /// Every maximal clique of `graph` becomes a factor whose values are all
+    /// one, over the variables of the clique sorted by name. Cliques are
+    /// ordered by their sorted vertex identifiers and numbered as in
+    /// [FactorGraph::from_factors]. `variables` gives the states of the
+    /// vertices of `graph`. Panics if an edge of `graph` is directed or if
+    /// a vertex has no variable. This is the converse of
+    /// [FactorGraph::to_markov_graph]. The identifier of the factor graph is
+    /// a random UUID, see [FactorGraph::from_markov_graph_with].
+    ///
+    /// # Example
+    /// ```
+    /// use pgm_rust::graph::types::edge::Edge;
+    /// use pgm_rust::graph::types::edgetype::EdgeType;
+    /// use pgm_rust::graph::types::graph::Graph;
+    /// use pgm_rust::graph::types::node::Node;
+    /// use pgm_rust::pgm::factorgraph::FactorGraph;
+    /// use pgm_rust::pgm::variable::DiscreteVariable;
+    /// use std::collections::HashSet;
+    ///
+    /// let e1 = Edge::empty("e1", EdgeType::Undirected, "A", "B");
+    /// let e2 = Edge::empty("e2", EdgeType::Undirected, "B", "C");
+    /// let e3 = Edge::empty("e3", EdgeType::Undirected, "C", "A");
+    /// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2, e3]));
+    /// let vs: Vec<DiscreteVariable> = ["A", "B", "C"]
+    ///     .iter()
+    ///     .map(|v| DiscreteVariable::new(v, &["0", "1"]))
+    ///     .collect();
+    /// let fg = FactorGraph::from_markov_graph(&g, &vs);
+    /// fg.factor_nodes().len() == 1; // true
+    /// fg.factor("phi0").unwrap().values().len() == 8; // true
+    /// ```
+    pub fn from_markov_graph(
+        graph: &Graph<Node, Edge<Node>>,
+        variables: &[DiscreteVariable],
+    ) -> FactorGraph {
+        FactorGraph::from_markov_graph_with(graph, variables, &mut UuidGenerator)
+    }
+
+    /// Create a factor graph from the structure of a Markov network, the
+    /// identifier of the factor graph is produced by `idgen`, see
+    /// [FactorGraph::from_markov_graph]
+    pub fn from_markov_graph_with<I: IdGenerator>(
+        graph: &Graph<Node, Edge<Node>>,
+        variables: &[DiscreteVariable],
+        idgen: &mut I,
+    ) -> FactorGraph {
+        let vertices = graph.vertices_sorted();
+        let index: HashMap<&String, usize> = vertices
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (v.id(), i))
+            .collect();
+        let mut neighbors: Vec<BTreeSet<usize>> = vec![BTreeSet::new(); vertices.len()];
+        for e in graph.edges() {
+            if e.has_type() != &EdgeType::Undirected {
+                panic!("{e} of {graph} is not undirected");
+            }
+            let (u, v) = (index[e.start().id()], index[e.end().id()]);
+            if u != v {
+                neighbors[u].insert(v);
+                neighbors[v].insert(u);
+            }
+        }
+        let variable = |vid: &String| match variables.iter().find(|v| v.name() == vid) {
+            None => panic!("{vid} of {graph} has no variable"),
+            Some(v) => v.clone(),
+        };
+        let factors: Vec<Factor> = maximal_cliques(&neighbors)
+            .into_iter()
+            .map(|clique| {
+                let scope: Vec<DiscreteVariable> =
+                    clique.iter().map(|i| variable(vertices[*i].id())).collect();
+                let size: usize = scope.iter().map(|v| v.cardinality()).product();
+                Factor::new(scope, vec![1.0; size])
+            })
+            .collect();
+        FactorGraph::from_factors_with(&factors, idgen)
+    }
+
+    /// factor kept on the node `fid`, None if there is no such factor node
+    /// or if the factor graph was built from scopes
+    pub fn factor(&self, fid: &str) -> Option<&Factor> {
+        self.factors.get(fid)
+    }
+
+    /// factors kept on the nodes of the factor graph by node identifier
+    pub fn factors(&self) -> &HashMap<String, Factor> {
+        &self.factors
+    }
+
+    /// variable nodes of the factor graph
+    pub fn variable_nodes(&self) -> HashSet<&Node> {
+        self.graph
+            .vertices()
+            .into_iter()
+            .filter(|n| has_kind(n, VARIABLE_KIND))
+            .collect()
+    }
+
+    /// factor nodes of the factor graph
+    pub fn factor_nodes(&self) -> HashSet<&Node> {
+        self.graph
+            .vertices()
+            .into_iter()
+            .filter(|n| has_kind(n, FACTOR_KIND))
+            .collect()
+    }
+
+    /// Get the neighbors of a node
+    /// # Description
+    /// The neighbors of a factor are the variables of its scope, and the
+    /// neighbors of a variable are the factors whose scope contains it.
+    ///
+    /// # Example
+    /// ```
+    /// use pgm_rust::pgm::factorgraph::FactorGraph;
+    /// use pgm_rust::graph::traits::graph_obj::GraphObject;
+    /// use pgm_rust::graph::types::node::Node;
+    /// use std::collections::HashMap;
+    /// use std::collections::HashSet;
+    ///
+    /// let scopes = HashMap::from([
+    ///     ("f1".to_string(), HashSet::from(["A".to_string(), "B".to_string()])),
+    /// ]);
+    /// let fg = FactorGraph::from_scopes(&scopes);
+    /// let f1 = Node::empty("f1");
+    /// fg.neighbors(&f1).len() == 2; // true
+    /// ```
+    pub fn neighbors(&self, n: &Node) -> HashSet<&Node> {
+        let nid = n.id();
+        let mut ns: HashSet<&Node> = HashSet::new();
+        let mut found = false;
+        for e in self.graph.edges() {
+            if e.start().id() == nid {
+                found = true;
+                ns.insert(e.end());
+            } else if e.end().id() == nid {
+                found = true;
+                ns.insert(e.start());
+            }
+        }
+        if !found && !self.graph.vertices().contains(n) {
+            panic!("{n} not in {self}");
+        }
+        ns
+    }
+
+    /// Get the Markov network structure of the factor graph
+    /// # Description
+    /// The returned undirected graph has the variables as vertices, and two
+    /// variables are adjacent if they appear together in the scope of a
+    /// factor. Edge identifiers are `A--B` with `A < B`, where `-` and `\`
+    /// are escaped as in the edges of the factor graph. The identifier of
+    /// the graph is a random UUID, see [FactorGraph::to_markov_graph_with].
+    pub fn to_markov_graph(&self) -> Graph<Node, Edge<Node>> {
+        self.to_markov_graph_with(&mut UuidGenerator)
+    }
+
+    /// Get the Markov network structure of the factor graph, the identifier
+    /// of the graph is produced by `idgen`, see [FactorGraph::to_markov_graph]
+    pub fn to_markov_graph_with<I: IdGenerator>(&self, idgen: &mut I) -> Graph<Node, Edge<Node>> {
+        let variables: HashSet<Node> = self.variable_nodes().into_iter().cloned().collect();
+        let mut edges: HashSet<Edge<Node>> = HashSet::new();
+        for f in self.factor_nodes() {
+            let mut scope: Vec<&Node> = self.neighbors(f).into_iter().collect();
+            scope.sort_by(|a, b| a.id().cmp(b.id()));
+            for (i, a) in scope.iter().enumerate() {
+                for b in &scope[i + 1..] {
+                    edges.insert(Edge::undirected(
+                        pair_id(a.id(), b.id()),
+                        (*a).clone(),
+                        (*b).clone(),
+                        HashMap::new(),
+                    ));
+                }
+            }
+        }
+        let gid = idgen.generate(&variables.iter().collect(), &edges.iter().collect());
+        Graph::new(gid, HashMap::new(), variables, edges)
+    }
+}
+
+/// check that a graph is a valid factor graph
+fn check_factor_graph(g: &Graph<Node, Edge<Node>>) {
+    for n in g.vertices() {
+        if !has_kind(n, VARIABLE_KIND) && !has_kind(n, FACTOR_KIND) {
+            panic!("{n} is neither a variable nor a factor");
+        }
+    }
+    for e in g.edges() {
+        let s_var = has_kind(e.start(), VARIABLE_KIND);
+        let t_var = has_kind(e.end(), VARIABLE_KIND);
+        if s_var == t_var {
+            panic!("{e} does not join a variable and a factor");
+        }
+        if e.has_type() != &EdgeType::Undirected {
+            panic!("{e} is not undirected");
+        }
+    }
+}
+
+impl Hash for FactorGraph {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.graph.hash(state);
+    }
+}
+
+impl fmt::Display for FactorGraph {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<FactorGraph id='{}'>", self.graph.id())
+    }
+}
+
+impl GraphObjectTrait for FactorGraph {
+    fn id(&self) -> &String {
+        self.graph.id()
+    }
+
+    fn data(&self) -> &HashMap<String, Vec<String>> {
+        self.graph.data()
+    }
+}
+
+impl GraphTrait<Node, Edge<Node>> for FactorGraph {
+    fn vertices(&self) -> HashSet<&Node> {
+        self.graph.vertices()
+    }
+    fn edges(&self) -> HashSet<&Edge<Node>> {
+        self.graph.edges()
+    }
+    fn create(
+        graph_id: String,
+        graph_data: HashMap<String, Vec<String>>,
+        nodes: HashSet<Node>,
+        edges: HashSet<Edge<Node>>,
+    ) -> FactorGraph {
+        let graph = Graph::new(graph_id, graph_data, nodes, edges);
+        check_factor_graph(&graph);
+        FactorGraph {
+            graph,
+            factors: HashMap::new(),
+        }
+    }
+    fn create_from_ref(
+        graph_id: String,
+        graph_data: HashMap<String, Vec<String>>,
+        nodes: HashSet<&Node>,
+        edges: HashSet<&Edge<Node>>,
+    ) -> FactorGraph {
+        let graph = Graph::new_refs(graph_id, graph_data, nodes, edges);
+        check_factor_graph(&graph);
+        FactorGraph {
+            graph,
+            factors: HashMap::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::types::idgen::SequentialIdGenerator;
+
+    fn mk_scopes(scopes: Vec<(&str, Vec<&str>)>) -> HashMap<String, HashSet<String>> {
+        scopes
+            .into_iter()
+            .map(|(f, vs)| {
+                (
+                    f.to_string(),
+                    vs.into_iter().map(|v| v.to_string()).collect(),
+                )
+            })
+            .collect()
+    }
+    fn ids(ns: HashSet<&Node>) -> HashSet<String> {
+        ns.into_iter().map(|n| n.id().clone()).collect()
+    }
+    fn mk_ids(ids: Vec<&str>) -> HashSet<String> {
+        ids.into_iter().map(|i| i.to_string()).collect()
+    }
+
+    /// f1(A, B), f2(B, C), f3(C)
+    fn mk_fg() -> FactorGraph {
+        FactorGraph::from_scopes(&mk_scopes(vec![
+            ("f1", vec!["A", "B"]),
+            ("f2", vec!["B", "C"]),
+            ("f3", vec!["C"]),
+        ]))
+    }
+
+    #[test]
+    fn test_node_classes() {
+        let fg = mk_fg();
+        assert_eq!(ids(fg.variable_nodes()), mk_ids(vec!["A", "B", "C"]));
+        assert_eq!(ids(fg.factor_nodes()), mk_ids(vec!["f1", "f2", "f3"]));
+        assert_eq!(fg.size(), 5);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let fg = mk_fg();
+        assert_eq!(
+            ids(fg.neighbors(&Node::empty("B"))),
+            mk_ids(vec!["f1", "f2"])
+        );
+        assert_eq!(
+            ids(fg.neighbors(&Node::empty("f2"))),
+            mk_ids(vec!["B", "C"])
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_neighbors_missing() {
+        mk_fg().neighbors(&Node::empty("Z"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_scopes_collision() {
+        FactorGraph::from_scopes(&mk_scopes(vec![("f1", vec!["A"]), ("A", vec!["B"])]));
+    }
+
+    #[test]
+    fn test_to_markov_graph() {
+        let mg = mk_fg().to_markov_graph();
+        let vs: HashSet<String> = mg.vertices().into_iter().map(|n| n.id().clone()).collect();
+        assert_eq!(vs, mk_ids(vec!["A", "B", "C"]));
+        let es: HashSet<String> = mg.edges().into_iter().map(|e| e.id().clone()).collect();
+        assert_eq!(es, mk_ids(vec!["A--B", "B--C"]));
+    }
+
+    #[test]
+    fn test_with_idgen() {
+        let mut idgen = SequentialIdGenerator::new("fg");
+        let fg = FactorGraph::from_scopes_with(
+            &mk_scopes(vec![("f1", vec!["A", "B"]), ("f2", vec!["B"])]),
+            &mut idgen,
+        );
+        assert_eq!(fg.id(), "fg0");
+        assert_eq!(fg.to_markov_graph_with(&mut idgen).id(), "fg1");
+    }
+
+    #[test]
+    fn test_edge_ids_do_not_collide() {
+        let fg =
+            FactorGraph::from_scopes(&mk_scopes(vec![("a--b", vec!["c"]), ("a", vec!["b--c"])]));
+        let es: HashSet<String> = fg.edges().into_iter().map(|e| e.id().clone()).collect();
+        assert_eq!(es, mk_ids(vec!["a\\-\\-b--c", "a--b\\-\\-c"]));
+    }
+
+    fn mk_var(name: &str, card: usize) -> DiscreteVariable {
+        let states: Vec<String> = (0..card).map(|i| i.to_string()).collect();
+        let refs: Vec<&str> = states.iter().map(|s| s.as_str()).collect();
+        DiscreteVariable::new(name, &refs)
+    }
+
+    #[test]
+    fn test_from_factors() {
+        let (a, b) = (mk_var("A", 2), mk_var("B", 3));
+        let f = Factor::new(vec![a.clone(), b.clone()], vec![1.0; 6]);
+        let g = Factor::new(vec![b], vec![0.2, 0.3, 0.5]);
+        let fg = FactorGraph::from_factors(&[f.clone(), g.clone()]);
+        assert_eq!(ids(fg.variable_nodes()), mk_ids(vec!["A", "B"]));
+        assert_eq!(ids(fg.factor_nodes()), mk_ids(vec!["phi0", "phi1"]));
+        assert_eq!(fg.factor("phi0"), Some(&f));
+        assert_eq!(fg.factor("phi1"), Some(&g));
+        assert_eq!(fg.factor("A"), None);
+        assert_eq!(
+            ids(fg.neighbors(&Node::empty("phi0"))),
+            mk_ids(vec!["A", "B"])
+        );
+        assert!(mk_fg().factors().is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_factors_collision() {
+        let f = Factor::new(vec![mk_var("phi1", 2)], vec![1.0, 1.0]);
+        FactorGraph::from_factors(&[f.clone(), f]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_factors_states_differ() {
+        let f = Factor::new(vec![mk_var("A", 2)], vec![1.0, 1.0]);
+        let g = Factor::new(vec![mk_var("A", 3)], vec![1.0, 1.0, 1.0]);
+        FactorGraph::from_factors(&[f, g]);
+    }
+
+    #[test]
+    fn test_from_markov_graph() {
+        // triangle A B C with a pendant D and an isolated E
+        let mk_edge =
+            |a: &str, b: &str| Edge::empty(&format!("{a}{b}"), EdgeType::Undirected, a, b);
+        let g: Graph<Node, Edge<Node>> = Graph::from_edge_node_set(
+            HashSet::from([
+                mk_edge("A", "B"),
+                mk_edge("B", "C"),
+                mk_edge("C", "A"),
+                mk_edge("C", "D"),
+            ]),
+            HashSet::from([Node::empty("E")]),
+        );
+        let vs: Vec<DiscreteVariable> = ["A", "B", "C", "D", "E"]
+            .iter()
+            .map(|v| mk_var(v, 2))
+            .collect();
+        let fg = FactorGraph::from_markov_graph(&g, &vs);
+        let scope = |fid: &str| -> Vec<String> {
+            fg.factor(fid)
+                .unwrap()
+                .scope()
+                .iter()
+                .map(|v| v.name().clone())
+                .collect()
+        };
+        assert_eq!(fg.factors().len(), 3);
+        assert_eq!(scope("phi0"), vec!["A", "B", "C"]);
+        assert_eq!(scope("phi1"), vec!["C", "D"]);
+        assert_eq!(scope("phi2"), vec!["E"]);
+        assert!(fg
+            .factor("phi0")
+            .unwrap()
+            .values()
+            .iter()
+            .all(|x| *x == 1.0));
+        let mg = fg.to_markov_graph();
+        let es: HashSet<String> = mg.edges().into_iter().map(|e| e.id().clone()).collect();
+        assert_eq!(es, mk_ids(vec!["A--B", "A--C", "B--C", "C--D"]));
+        assert_eq!(mg.order(), 5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_markov_graph_missing_variable() {
+        let e = Edge::empty("e", EdgeType::Undirected, "A", "B");
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e]));
+        FactorGraph::from_markov_graph(&g, &[mk_var("A", 2)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_create_not_bipartite() {
+        let a = mk_kind_node("A", VARIABLE_KIND);
+        let b = mk_kind_node("B", VARIABLE_KIND);
+        let e = Edge::undirected("e1".to_string(), a, b, HashMap::new());
+        FactorGraph::create(
+            "fg".to_string(),
+            HashMap::new(),
+            HashSet::new(),
+            HashSet::from([e]),
+        );
+    }
+}
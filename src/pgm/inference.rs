@@ -0,0 +1,7 @@
+//! inference algorithms
+
+/// sampling based approximate inference
+pub mod sampling;
+
+/// loopy belief propagation on factor graphs
+pub mod loopy_bp;
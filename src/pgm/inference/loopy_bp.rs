@@ -0,0 +1,466 @@
+//! loopy belief propagation on factor graphs
+use crate::factor::Factor;
+use crate::graph::traits::graph_obj::GraphObject;
+use crate::pgm::factorgraph::FactorGraph;
+use crate::pgm::query::Distribution;
+use crate::pgm::variable::DiscreteVariable;
+use std::collections::HashMap;
+
+/// order in which [loopy_bp] updates messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Schedule {
+    /// every message of an iteration is computed from the messages of the
+    /// previous iteration
+    Synchronous,
+    /// factors are visited in identifier order and each message is computed
+    /// from the latest messages, including those of the current iteration
+    Asynchronous,
+}
+
+/// output of [loopy_bp]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BeliefPropagationResult {
+    /// approximate marginal of every variable indexed by its name
+    pub marginals: HashMap<String, Distribution>,
+    /// number of iterations run, an iteration updates every message once
+    pub iterations: usize,
+    /// largest change of a message entry during the last iteration,
+    /// infinite if no iteration was run
+    pub residual: f64,
+    /// the residual fell below the tolerance before the iteration limit
+    pub converged: bool,
+}
+
+/// edge of the factor graph joining a factor to the variable at position
+/// `position` of its scope
+struct Link {
+    factor: usize,
+    variable: usize,
+    position: usize,
+}
+
+/// messages of loopy belief propagation and the structure they live on
+struct Messages<'a> {
+    factors: Vec<&'a Factor>,
+    variables: Vec<&'a DiscreteVariable>,
+    links: Vec<Link>,
+    /// links of every factor in scope order
+    factor_links: Vec<Vec<usize>>,
+    /// links of every variable
+    variable_links: Vec<Vec<usize>>,
+    /// message from the factor of a link to its variable
+    to_variable: Vec<Vec<f64>>,
+    /// message from the variable of a link to its factor
+    to_factor: Vec<Vec<f64>>,
+}
+
+/// scale `values` to sum to one, panics if they sum to zero
+fn normalize(mut values: Vec<f64>, variable: &DiscreteVariable) -> Vec<f64> {
+    let total: f64 = values.iter().sum();
+    if total <= 0.0 {
+        panic!(
+            "factors of {} give zero weight to every state",
+            variable.name()
+        );
+    }
+    for x in values.iter_mut() {
+        *x /= total;
+    }
+    values
+}
+
+/// replace `old` by `new` damped towards `old`, return the largest change
+fn update(old: &mut [f64], new: &[f64], damping: f64) -> f64 {
+    let mut change: f64 = 0.0;
+    for (o, n) in old.iter_mut().zip(new) {
+        let damped = (1.0 - damping) * n + damping * *o;
+        change = change.max((damped - *o).abs());
+        *o = damped;
+    }
+    change
+}
+
+impl<'a> Messages<'a> {
+    /// uniform messages over the factors of `fg`
+    fn new(fg: &'a FactorGraph) -> Messages<'a> {
+        if fg.factors().len() != fg.factor_nodes().len() {
+            panic!("{} holds no factor values", fg.id());
+        }
+        let mut named: Vec<(&String, &Factor)> = fg.factors().iter().collect();
+        named.sort_by(|a, b| a.0.cmp(b.0));
+        let factors: Vec<&Factor> = named.into_iter().map(|(_, f)| f).collect();
+        let mut variables: Vec<&DiscreteVariable> = Vec::new();
+        for f in &factors {
+            for v in f.scope() {
+                if !variables.iter().any(|w| w.name() == v.name()) {
+                    variables.push(v);
+                }
+            }
+        }
+        variables.sort_by(|a, b| a.name().cmp(b.name()));
+        let index: HashMap<&String, usize> = variables
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (v.name(), i))
+            .collect();
+        let mut links = Vec::new();
+        let mut factor_links = vec![Vec::new(); factors.len()];
+        let mut variable_links = vec![Vec::new(); variables.len()];
+        for (fi, f) in factors.iter().enumerate() {
+            for (position, v) in f.scope().iter().enumerate() {
+                let vi = index[v.name()];
+                factor_links[fi].push(links.len());
+                variable_links[vi].push(links.len());
+                links.push(Link {
+                    factor: fi,
+                    variable: vi,
+                    position,
+                });
+            }
+        }
+        let uniform = |l: &Link| {
+            let card = variables[l.variable].cardinality();
+            vec![1.0 / card as f64; card]
+        };
+        let to_variable = links.iter().map(uniform).collect();
+        let to_factor = links.iter().map(uniform).collect();
+        Messages {
+            factors,
+            variables,
+            links,
+            factor_links,
+            variable_links,
+            to_variable,
+            to_factor,
+        }
+    }
+
+    /// message from the variable of link `l` to its factor: the product of
+    /// the messages sent to the variable by its other factors
+    fn variable_message(&self, l: usize) -> Vec<f64> {
+        let variable = self.variables[self.links[l].variable];
+        let mut message = vec![1.0; variable.cardinality()];
+        for k in &self.variable_links[self.links[l].variable] {
+            if *k != l {
+                for (m, x) in message.iter_mut().zip(&self.to_variable[*k]) {
+                    *m *= x;
+                }
+            }
+        }
+        normalize(message, variable)
+    }
+
+    /// message from the factor of link `l` to its variable: the factor
+    /// times the messages of its other variables, those variables summed out
+    fn factor_message(&self, l: usize) -> Vec<f64> {
+        let link = &self.links[l];
+        let factor = self.factors[link.factor];
+        let variable = self.variables[link.variable];
+        let incoming = &self.factor_links[link.factor];
+        let cardinalities = factor.cardinalities();
+        let mut message = vec![0.0; variable.cardinality()];
+        let mut states = vec![0; cardinalities.len()];
+        for value in factor.values() {
+            let mut weight = *value;
+            for (q, k) in incoming.iter().enumerate() {
+                if q != link.position {
+                    weight *= self.to_factor[*k][states[q]];
+                }
+            }
+            message[states[link.position]] += weight;
+            for i in (0..states.len()).rev() {
+                states[i] += 1;
+                if states[i] < cardinalities[i] {
+                    break;
+                }
+                states[i] = 0;
+            }
+        }
+        normalize(message, variable)
+    }
+
+    /// update every message from the messages of the previous iteration
+    fn synchronous_sweep(&mut self, damping: f64) -> f64 {
+        let to_factor: Vec<Vec<f64>> = (0..self.links.len())
+            .map(|l| self.variable_message(l))
+            .collect();
+        let to_variable: Vec<Vec<f64>> = (0..self.links.len())
+            .map(|l| self.factor_message(l))
+            .collect();
+        let mut residual: f64 = 0.0;
+        for l in 0..self.links.len() {
+            residual = residual.max(update(&mut self.to_factor[l], &to_factor[l], damping));
+            residual = residual.max(update(&mut self.to_variable[l], &to_variable[l], damping));
+        }
+        residual
+    }
+
+    /// update the messages of every factor in turn from the latest messages
+    fn asynchronous_sweep(&mut self, damping: f64) -> f64 {
+        let mut residual: f64 = 0.0;
+        for fi in 0..self.factors.len() {
+            for l in self.factor_links[fi].clone() {
+                let message = self.variable_message(l);
+                residual = residual.max(update(&mut self.to_factor[l], &message, damping));
+            }
+            for l in self.factor_links[fi].clone() {
+                let message = self.factor_message(l);
+                residual = residual.max(update(&mut self.to_variable[l], &message, damping));
+            }
+        }
+        residual
+    }
+
+    /// normalized product of the messages sent to every variable
+    fn beliefs(&self) -> HashMap<String, Distribution> {
+        let mut marginals = HashMap::new();
+        for (vi, variable) in self.variables.iter().enumerate() {
+            let mut belief = vec![1.0; variable.cardinality()];
+            for l in &self.variable_links[vi] {
+                for (b, x) in belief.iter_mut().zip(&self.to_variable[*l]) {
+                    *b *= x;
+                }
+            }
+            let belief = normalize(belief, variable);
+            let distribution = Distribution::new(vec![(*variable).clone()], belief);
+            marginals.insert(variable.name().clone(), distribution);
+        }
+        marginals
+    }
+}
+
+/// Approximate the marginals of a factor graph by loopy belief propagation
+/// # Description
+/// Factors send to each of their variables the sum, over the states of
+/// their other variables, of their values times the messages received from
+/// those variables. Variables send to each of their factors the product of
+/// the messages received from their other factors. Messages start uniform
+/// and are normalized. Each iteration updates every message once in the
+/// order given by `schedule`, and every new message is damped towards the
+/// previous one: it is `(1 - damping) * new + damping * old`. Iterations
+/// stop once the largest change of a message entry is below `tolerance`
+/// or after `max_iter` iterations. The belief of a variable, the
+/// normalized product of its incoming messages, approximates its marginal
+/// under the normalized product of the factors. On a factor graph without
+/// cycle the beliefs are exact once the messages have converged.
+///
+/// Panics if `fg` holds no factor values, see [FactorGraph::from_factors],
+/// if `damping` is not in `[0, 1)` or if the factors of a variable give
+/// zero weight to every one of its states.
+///
+/// # Args
+/// - fg: factor graph whose factors are kept on its nodes
+/// - schedule: order of the message updates, see [Schedule]
+/// - damping: weight of the previous message in the update
+/// - tolerance: largest message change at which iterations stop
+/// - max_iter: largest number of iterations
+///
+/// # Example
+/// ```
+/// use pgm_rust::factor::Factor;
+/// use pgm_rust::pgm::factorgraph::FactorGraph;
+/// use pgm_rust::pgm::inference::loopy_bp::{loopy_bp, Schedule};
+/// use pgm_rust::pgm::variable::DiscreteVariable;
+///
+/// let a = DiscreteVariable::new("A", &["a0", "a1"]);
+/// let b = DiscreteVariable::new("B", &["b0", "b1"]);
+/// let prior = Factor::new(vec![a.clone()], vec![0.2, 0.8]);
+/// let pair = Factor::new(vec![a, b], vec![0.9, 0.1, 0.3, 0.7]);
+/// let fg = FactorGraph::from_factors(&[prior, pair]);
+/// let result = loopy_bp(&fg, Schedule::Synchronous, 0.0, 1e-10, 100);
+/// result.converged; // true
+/// (result.marginals["B"].probability(&["b1"]) - 0.58).abs() < 1e-9; // true
+/// ```
+///
+/// # References
+/// Koller D, Friedman N. Probabilistic Graphical Models. 2009, p. 391.
+///
+/// Murphy K, Weiss Y, Jordan M. Loopy Belief Propagation for Approximate
+/// Inference: An Empirical Study. UAI 1999.
+pub fn loopy_bp(
+    fg: &FactorGraph,
+    schedule: Schedule,
+    damping: f64,
+    tolerance: f64,
+    max_iter: usize,
+) -> BeliefPropagationResult {
+    if !(0.0..1.0).contains(&damping) {
+        panic!("damping {damping} is not in [0, 1)");
+    }
+    let mut messages = Messages::new(fg);
+    let mut residual = f64::INFINITY;
+    let mut iterations = 0;
+    while iterations < max_iter && residual >= tolerance {
+        residual = match schedule {
+            Schedule::Synchronous => messages.synchronous_sweep(damping),
+            Schedule::Asynchronous => messages.asynchronous_sweep(damping),
+        };
+        iterations += 1;
+    }
+    BeliefPropagationResult {
+        marginals: messages.beliefs(),
+        iterations,
+        residual,
+        converged: residual < tolerance,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pgm::bayesnet::BayesianNetwork;
+    use crate::pgm::cpd::TabularCPD;
+    use crate::pgm::query::{InferenceEngine, Query, VariableElimination};
+
+    fn binary(n: &str) -> DiscreteVariable {
+        DiscreteVariable::new(n, &["f", "t"])
+    }
+
+    /// tree a -> b, a -> c, c -> d, c -> e with a three state c
+    fn mk_tree() -> BayesianNetwork {
+        let c = DiscreteVariable::new("c", &["x", "y", "z"]);
+        let mut bn = BayesianNetwork::from_arcs(
+            "tree",
+            &["a", "b", "c", "d", "e"],
+            &[("a", "b"), ("a", "c"), ("c", "d"), ("c", "e")],
+        );
+        bn.add_cpd(TabularCPD::marginal(binary("a"), vec![0.3, 0.7]));
+        bn.add_cpd(TabularCPD::new(
+            binary("b"),
+            vec![binary("a")],
+            vec![vec![0.9, 0.1], vec![0.25, 0.75]],
+        ));
+        bn.add_cpd(TabularCPD::new(
+            c.clone(),
+            vec![binary("a")],
+            vec![vec![0.2, 0.5, 0.3], vec![0.6, 0.1, 0.3]],
+        ));
+        bn.add_cpd(TabularCPD::new(
+            binary("d"),
+            vec![c.clone()],
+            vec![vec![0.5, 0.5], vec![0.95, 0.05], vec![0.1, 0.9]],
+        ));
+        bn.add_cpd(TabularCPD::new(
+            binary("e"),
+            vec![c],
+            vec![vec![0.7, 0.3], vec![0.4, 0.6], vec![0.15, 0.85]],
+        ));
+        bn
+    }
+
+    #[test]
+    fn test_loopy_bp_tree() {
+        let bn = mk_tree();
+        let factors: Vec<Factor> = bn
+            .topological_order()
+            .iter()
+            .map(|v| Factor::from(bn.cpd(v).unwrap()))
+            .collect();
+        let fg = FactorGraph::from_factors(&factors);
+        let ve = VariableElimination::new(&bn);
+        for schedule in [Schedule::Synchronous, Schedule::Asynchronous] {
+            let result = loopy_bp(&fg, schedule, 0.0, 1e-12, 100);
+            assert!(result.converged);
+            assert!(result.residual < 1e-12);
+            assert!(result.iterations < 100);
+            for v in ["a", "b", "c", "d", "e"] {
+                let exact = ve.query(&Query::marginal([v])).into_marginal().unwrap();
+                let approx = &result.marginals[v];
+                assert_eq!(approx.variables(), exact.variables());
+                for (p, q) in approx.values().iter().zip(exact.values()) {
+                    assert!((p - q).abs() < 1e-9);
+                }
+            }
+        }
+    }
+
+    /// pairwise factors of a `side` by `side` grid with a field on every
+    /// variable, the marginals of the product being computed exactly
+    fn mk_grid(side: usize) -> (FactorGraph, Factor) {
+        let var = |i: usize, j: usize| binary(&format!("x{i}{j}"));
+        let mut factors = Vec::new();
+        for i in 0..side {
+            for j in 0..side {
+                let field = 0.3 + 0.1 * ((i + 2 * j) % 3) as f64;
+                factors.push(Factor::new(vec![var(i, j)], vec![1.0 - field, field]));
+                if i + 1 < side {
+                    factors.push(Factor::new(
+                        vec![var(i, j), var(i + 1, j)],
+                        vec![1.5, 1.0, 1.0, 1.5],
+                    ));
+                }
+                if j + 1 < side {
+                    factors.push(Factor::new(
+                        vec![var(i, j), var(i, j + 1)],
+                        vec![1.2, 1.0, 1.0, 1.2],
+                    ));
+                }
+            }
+        }
+        let joint = factors[1..]
+            .iter()
+            .fold(factors[0].clone(), |acc, f| acc.product(f));
+        (FactorGraph::from_factors(&factors), joint)
+    }
+
+    #[test]
+    fn test_loopy_bp_grid() {
+        let (fg, joint) = mk_grid(3);
+        let total = joint.total();
+        let sync = loopy_bp(&fg, Schedule::Synchronous, 0.5, 1e-10, 1000);
+        let asynchronous = loopy_bp(&fg, Schedule::Asynchronous, 0.0, 1e-10, 1000);
+        for result in [&sync, &asynchronous] {
+            assert!(result.converged);
+            assert_eq!(result.marginals.len(), 9);
+            for v in joint.scope() {
+                let mut exact = joint.clone();
+                for w in joint.scope() {
+                    if w.name() != v.name() {
+                        exact = exact.sum_out(w.name());
+                    }
+                }
+                let approx = &result.marginals[v.name()];
+                assert!((approx.values().iter().sum::<f64>() - 1.0).abs() < 1e-12);
+                // weak couplings keep the loopy beliefs close to the marginals
+                assert!((approx.values()[1] - exact.values()[1] / total).abs() < 0.02);
+            }
+        }
+        for v in sync.marginals.keys() {
+            let (p, q) = (&sync.marginals[v], &asynchronous.marginals[v]);
+            assert!((p.values()[1] - q.values()[1]).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_loopy_bp_max_iter() {
+        let (fg, _) = mk_grid(3);
+        let result = loopy_bp(&fg, Schedule::Synchronous, 0.0, 0.0, 3);
+        assert_eq!(result.iterations, 3);
+        assert!(!result.converged);
+        assert!(result.residual > 0.0);
+        let none = loopy_bp(&fg, Schedule::Synchronous, 0.0, 1e-10, 0);
+        assert_eq!(none.iterations, 0);
+        assert!(none.residual.is_infinite());
+        assert!((none.marginals["x00"].values()[0] - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_loopy_bp_without_factors() {
+        let scopes = HashMap::from([("f".to_string(), ["A".to_string()].into())]);
+        loopy_bp(
+            &FactorGraph::from_scopes(&scopes),
+            Schedule::Synchronous,
+            0.0,
+            1e-6,
+            10,
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_loopy_bp_damping() {
+        let (fg, _) = mk_grid(2);
+        loopy_bp(&fg, Schedule::Synchronous, 1.0, 1e-6, 10);
+    }
+}
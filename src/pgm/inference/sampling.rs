@@ -0,0 +1,520 @@
+//! sampling based approximate inference
+use crate::pgm::bayesnet::BayesianNetwork;
+use crate::pgm::cpd::TabularCPD;
+use crate::pgm::data::DataSet;
+use crate::pgm::query::{Distribution, Evidence};
+use crate::pgm::variable::DiscreteVariable;
+use crate::rng::SplitMix64;
+use std::collections::HashMap;
+
+/// draw a state from a distribution by inversion of its cumulative sum
+pub(crate) fn draw(distribution: &[f64], rng: &mut SplitMix64) -> usize {
+    let u = rng.next_f64();
+    let mut cumulative = 0.0;
+    for (s, p) in distribution.iter().enumerate() {
+        cumulative += p;
+        if u < cumulative {
+            return s;
+        }
+    }
+    distribution.len() - 1
+}
+
+/// Tables of a fully specified network in topological order, with the
+/// parents of every variable resolved to positions in that order
+pub(crate) struct Forward<'a> {
+    cpds: Vec<&'a TabularCPD>,
+    parents: Vec<Vec<usize>>,
+}
+
+impl<'a> Forward<'a> {
+    /// panics if a table of `bn` is missing
+    pub(crate) fn new(bn: &'a BayesianNetwork) -> Forward<'a> {
+        if !bn.is_fully_specified() {
+            panic!("{bn} has variables without table");
+        }
+        let order = bn.topological_order();
+        let position: HashMap<&String, usize> =
+            order.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+        let cpds: Vec<&TabularCPD> = order.iter().map(|v| bn.cpd(v).unwrap()).collect();
+        let parents = cpds
+            .iter()
+            .map(|cpd| cpd.parents().iter().map(|u| position[u.name()]).collect())
+            .collect();
+        Forward { cpds, parents }
+    }
+
+    /// variables in topological order
+    pub(crate) fn variables(&self) -> Vec<DiscreteVariable> {
+        self.cpds.iter().map(|cpd| cpd.variable().clone()).collect()
+    }
+
+    /// position of every variable in topological order
+    pub(crate) fn positions(&self) -> HashMap<&'a String, usize> {
+        self.cpds
+            .iter()
+            .enumerate()
+            .map(|(i, cpd)| (cpd.variable().name(), i))
+            .collect()
+    }
+
+    /// distribution of the variable at position `i` given the states of
+    /// its parents in `state`
+    pub(crate) fn row(&self, i: usize, state: &[usize]) -> &'a [f64] {
+        let parent_states: Vec<usize> = self.parents[i].iter().map(|p| state[*p]).collect();
+        let cpd = self.cpds[i];
+        &cpd.table()[cpd.row_index(&parent_states)]
+    }
+
+    /// Draw a joint state into `state` in topological order
+    /// # Description
+    /// Observed variables are fixed to their observation and the returned
+    /// weight is the probability of the observations given the other
+    /// states. Only unobserved variables consume random numbers.
+    pub(crate) fn sample(
+        &self,
+        rng: &mut SplitMix64,
+        observed: &[Option<usize>],
+        state: &mut [usize],
+    ) -> f64 {
+        let mut w = 1.0;
+        for i in 0..self.cpds.len() {
+            let row = self.row(i, state);
+            match observed[i] {
+                Some(s) => {
+                    state[i] = s;
+                    w *= row[s];
+                }
+                None => state[i] = draw(row, rng),
+            }
+        }
+        w
+    }
+
+    /// observed state of every variable in topological order, panics if
+    /// `evidence` mentions unknown variables or states
+    pub(crate) fn observed(&self, evidence: &Evidence) -> Vec<Option<usize>> {
+        let position = self.positions();
+        let mut observed = vec![None; self.cpds.len()];
+        for (v, s) in evidence.iter() {
+            let i = match position.get(v) {
+                None => panic!("{v} is not a variable of the network"),
+                Some(i) => *i,
+            };
+            match self.cpds[i].variable().state_index(s) {
+                None => panic!("{v} has no state {s}"),
+                Some(k) => observed[i] = Some(k),
+            }
+        }
+        observed
+    }
+}
+
+/// Draw independent joint states of a Bayesian network
+/// # Description
+/// Every variable is drawn from its table given the states already drawn
+/// for its parents, following [BayesianNetwork::topological_order]. The
+/// columns of the returned data set are the variables in that order. Draws
+/// come from a [SplitMix64] seeded by `seed`, so the data set is
+/// reproducible. Panics if a table of `bn` is missing.
+///
+/// # Example
+/// ```
+/// use pgm_rust::pgm::bayesnet::BayesianNetwork;
+/// use pgm_rust::pgm::cpd::TabularCPD;
+/// use pgm_rust::pgm::inference::sampling::forward_sample;
+/// use pgm_rust::pgm::variable::DiscreteVariable;
+///
+/// let rain = DiscreteVariable::new("rain", &["no", "yes"]);
+/// let wet = DiscreteVariable::new("wet", &["no", "yes"]);
+/// let mut bn = BayesianNetwork::from_arcs("bn", &["rain", "wet"], &[("rain", "wet")]);
+/// bn.add_cpd(TabularCPD::marginal(rain.clone(), vec![0.5, 0.5]));
+/// bn.add_cpd(TabularCPD::new(wet, vec![rain], vec![vec![0.9, 0.1], vec![0.1, 0.9]]));
+/// let data = forward_sample(&bn, 1000, 7);
+/// data.nb_rows() == 1000; // true
+/// data.column_index("wet") == Some(1); // true
+/// ```
+///
+/// # References
+/// Koller D, Friedman N. Probabilistic Graphical Models. 2009, p. 489.
+pub fn forward_sample(bn: &BayesianNetwork, n: usize, seed: u64) -> DataSet {
+    let forward = Forward::new(bn);
+    let variables = forward.variables();
+    let observed = vec![None; variables.len()];
+    let mut rng = SplitMix64::new(seed);
+    let mut rows = Vec::with_capacity(n);
+    for _ in 0..n {
+        let mut state = vec![0; variables.len()];
+        forward.sample(&mut rng, &observed, &mut state);
+        rows.push(state);
+    }
+    DataSet::new(variables, rows)
+}
+
+/// Draw joint states of a Bayesian network weighted by evidence
+/// # Description
+/// Samples are drawn as in [forward_sample] except that observed variables
+/// are fixed to their observation. Every sample is weighted by the
+/// probability of the observations given its other states, so weighted
+/// frequencies estimate the posterior given `evidence`. Returns the
+/// samples and their weights, in the same order. This is the sampling
+/// loop of [crate::pgm::query::LikelihoodWeighting]. Panics if a table of
+/// `bn` is missing or if `evidence` mentions unknown variables or states.
+///
+/// # Example
+/// ```
+/// use pgm_rust::pgm::bayesnet::BayesianNetwork;
+/// use pgm_rust::pgm::cpd::TabularCPD;
+/// use pgm_rust::pgm::inference::sampling::likelihood_weighting;
+/// use pgm_rust::pgm::query::Evidence;
+/// use pgm_rust::pgm::variable::DiscreteVariable;
+///
+/// let rain = DiscreteVariable::new("rain", &["no", "yes"]);
+/// let wet = DiscreteVariable::new("wet", &["no", "yes"]);
+/// let mut bn = BayesianNetwork::from_arcs("bn", &["rain", "wet"], &[("rain", "wet")]);
+/// bn.add_cpd(TabularCPD::marginal(rain.clone(), vec![0.5, 0.5]));
+/// bn.add_cpd(TabularCPD::new(wet, vec![rain], vec![vec![0.9, 0.1], vec![0.1, 0.9]]));
+/// let evidence = Evidence::from([("wet", "yes")]);
+/// let (data, weights) = likelihood_weighting(&bn, &evidence, 10_000, 7);
+/// let total: f64 = weights.iter().sum();
+/// let rain_yes: f64 = data
+///     .rows()
+///     .iter()
+///     .zip(&weights)
+///     .filter(|(row, _)| row[0] == 1)
+///     .map(|(_, w)| w)
+///     .sum();
+/// (rain_yes / total - 0.9).abs() < 0.02; // true
+/// ```
+///
+/// # References
+/// Koller D, Friedman N. Probabilistic Graphical Models. 2009, p. 493.
+pub fn likelihood_weighting(
+    bn: &BayesianNetwork,
+    evidence: &Evidence,
+    n: usize,
+    seed: u64,
+) -> (DataSet, Vec<f64>) {
+    let forward = Forward::new(bn);
+    let variables = forward.variables();
+    let observed = forward.observed(evidence);
+    let mut rng = SplitMix64::new(seed);
+    let mut rows = Vec::with_capacity(n);
+    let mut weights = Vec::with_capacity(n);
+    for _ in 0..n {
+        let mut state = vec![0; variables.len()];
+        weights.push(forward.sample(&mut rng, &observed, &mut state));
+        rows.push(state);
+    }
+    (DataSet::new(variables, rows), weights)
+}
+
+/// number of forward draws tried to find a starting state of a chain
+/// consistent with the evidence
+pub const MAX_START_DRAWS: usize = 10_000;
+
+/// Common interface of samplers drawing joint states of a model
+/// # Description
+/// Samplers keep their state between calls, so successive calls of
+/// [Sampler::sample] continue the same sequence of draws.
+pub trait Sampler {
+    /// variables of the drawn joint states in column order
+    fn variables(&self) -> Vec<DiscreteVariable>;
+
+    /// draw `n` joint states, one row per draw
+    fn sample(&mut self, n: usize) -> DataSet;
+
+    /// Empirical joint distribution of some variables over `n` draws
+    /// # Description
+    /// Panics if `n` is zero or if a name is not a variable of the
+    /// sampler.
+    fn marginal(&mut self, variables: &[&str], n: usize) -> Distribution {
+        if n == 0 {
+            panic!("an empirical distribution needs at least one draw");
+        }
+        let data = self.sample(n);
+        let columns: Vec<DiscreteVariable> = variables
+            .iter()
+            .map(|v| match data.variable(v) {
+                None => panic!("{v} is not a variable of the sampler"),
+                Some(var) => var.clone(),
+            })
+            .collect();
+        let values = data
+            .counts(variables)
+            .iter()
+            .map(|c| *c as f64 / n as f64)
+            .collect();
+        Distribution::new(columns, values)
+    }
+}
+
+/// Gibbs sampler of a Bayesian network given evidence
+/// # Description
+/// The chain starts from the first forward draw consistent with the
+/// evidence, at most [MAX_START_DRAWS] draws are tried. A sweep resamples
+/// every unobserved variable in topological order from its distribution
+/// given its Markov blanket, which is proportional to its own table times
+/// the tables of its children. The first `burn_in` sweeps are discarded
+/// and one joint state is kept every `thinning` sweeps. Draws come from a
+/// [SplitMix64] seeded by `seed`, so samples are reproducible. Columns are
+/// the variables in [BayesianNetwork::topological_order]. Markov networks
+/// are not supported since the crate has no Markov network type.
+///
+/// # Example
+/// ```
+/// use pgm_rust::pgm::bayesnet::BayesianNetwork;
+/// use pgm_rust::pgm::cpd::TabularCPD;
+/// use pgm_rust::pgm::inference::sampling::{GibbsSampler, Sampler};
+/// use pgm_rust::pgm::query::Evidence;
+/// use pgm_rust::pgm::variable::DiscreteVariable;
+///
+/// let rain = DiscreteVariable::new("rain", &["no", "yes"]);
+/// let wet = DiscreteVariable::new("wet", &["no", "yes"]);
+/// let mut bn = BayesianNetwork::from_arcs("bn", &["rain", "wet"], &[("rain", "wet")]);
+/// bn.add_cpd(TabularCPD::marginal(rain.clone(), vec![0.5, 0.5]));
+/// bn.add_cpd(TabularCPD::new(wet, vec![rain], vec![vec![0.9, 0.1], vec![0.1, 0.9]]));
+/// let evidence = Evidence::from([("wet", "yes")]);
+/// let mut gibbs = GibbsSampler::new(&bn, &evidence, 100, 1, 7);
+/// let d = gibbs.marginal(&["rain"], 10_000);
+/// (d.probability(&["yes"]) - 0.9).abs() < 0.02; // true
+/// ```
+///
+/// # References
+/// Koller D, Friedman N. Probabilistic Graphical Models. 2009, p. 505.
+pub struct GibbsSampler<'a> {
+    forward: Forward<'a>,
+    children: Vec<Vec<usize>>,
+    observed: Vec<Option<usize>>,
+    burn_in: usize,
+    thinning: usize,
+    rng: SplitMix64,
+    state: Vec<usize>,
+    burnt: bool,
+}
+
+impl<'a> GibbsSampler<'a> {
+    /// Create a sampler of `bn` given `evidence`
+    /// # Description
+    /// Panics if a table of `bn` is missing, if `evidence` mentions unknown
+    /// variables or states, if `thinning` is zero or if no starting state
+    /// consistent with the evidence is found.
+    pub fn new(
+        bn: &'a BayesianNetwork,
+        evidence: &Evidence,
+        burn_in: usize,
+        thinning: usize,
+        seed: u64,
+    ) -> GibbsSampler<'a> {
+        if thinning == 0 {
+            panic!("thinning must be positive");
+        }
+        let forward = Forward::new(bn);
+        let observed = forward.observed(evidence);
+        let mut children = vec![Vec::new(); observed.len()];
+        for (i, ps) in forward.parents.iter().enumerate() {
+            for p in ps {
+                children[*p].push(i);
+            }
+        }
+        let mut rng = SplitMix64::new(seed);
+        let mut state = vec![0; observed.len()];
+        let mut found = false;
+        for _ in 0..MAX_START_DRAWS {
+            if forward.sample(&mut rng, &observed, &mut state) > 0.0 {
+                found = true;
+                break;
+            }
+        }
+        if !found {
+            panic!("no state consistent with the evidence found");
+        }
+        GibbsSampler {
+            forward,
+            children,
+            observed,
+            burn_in,
+            thinning,
+            rng,
+            state,
+            burnt: false,
+        }
+    }
+
+    /// resample the variable at position `i` given its Markov blanket
+    fn resample(&mut self, i: usize) {
+        let card = self.forward.cpds[i].variable().cardinality();
+        let mut weights = Vec::with_capacity(card);
+        for s in 0..card {
+            self.state[i] = s;
+            let mut w = self.forward.row(i, &self.state)[s];
+            for c in &self.children[i] {
+                w *= self.forward.row(*c, &self.state)[self.state[*c]];
+            }
+            weights.push(w);
+        }
+        let total: f64 = weights.iter().sum();
+        let distribution: Vec<f64> = weights.iter().map(|w| w / total).collect();
+        self.state[i] = draw(&distribution, &mut self.rng);
+    }
+
+    /// resample every unobserved variable once
+    fn sweep(&mut self) {
+        for i in 0..self.state.len() {
+            if self.observed[i].is_none() {
+                self.resample(i);
+            }
+        }
+    }
+}
+
+impl Sampler for GibbsSampler<'_> {
+    fn variables(&self) -> Vec<DiscreteVariable> {
+        self.forward.variables()
+    }
+
+    fn sample(&mut self, n: usize) -> DataSet {
+        if !self.burnt {
+            for _ in 0..self.burn_in {
+                self.sweep();
+            }
+            self.burnt = true;
+        }
+        let mut rows = Vec::with_capacity(n);
+        for _ in 0..n {
+            for _ in 0..self.thinning {
+                self.sweep();
+            }
+            rows.push(self.state.clone());
+        }
+        DataSet::new(self.variables(), rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pgm::query::{InferenceEngine, LikelihoodWeighting, Query, QueryResult};
+
+    fn binary(n: &str) -> DiscreteVariable {
+        DiscreteVariable::new(n, &["f", "t"])
+    }
+
+    /// cloudy -> rain, cloudy -> sprinkler, rain -> wet, sprinkler -> wet
+    fn mk_sprinkler() -> BayesianNetwork {
+        let mut bn = BayesianNetwork::from_arcs(
+            "sprinkler",
+            &["c", "r", "s", "w"],
+            &[("c", "r"), ("c", "s"), ("r", "w"), ("s", "w")],
+        );
+        bn.add_cpd(TabularCPD::marginal(binary("c"), vec![0.5, 0.5]));
+        bn.add_cpd(TabularCPD::new(
+            binary("s"),
+            vec![binary("c")],
+            vec![vec![0.5, 0.5], vec![0.9, 0.1]],
+        ));
+        bn.add_cpd(TabularCPD::new(
+            binary("r"),
+            vec![binary("c")],
+            vec![vec![0.8, 0.2], vec![0.2, 0.8]],
+        ));
+        bn.add_cpd(TabularCPD::new(
+            binary("w"),
+            vec![binary("s"), binary("r")],
+            vec![
+                vec![1.0, 0.0],
+                vec![0.1, 0.9],
+                vec![0.1, 0.9],
+                vec![0.01, 0.99],
+            ],
+        ));
+        bn
+    }
+
+    #[test]
+    fn test_forward_sample() {
+        let bn = mk_sprinkler();
+        let data = forward_sample(&bn, 20_000, 5);
+        let names: Vec<&String> = data.variables().iter().map(|v| v.name()).collect();
+        assert_eq!(names, vec!["c", "r", "s", "w"]);
+        let n = data.nb_rows() as f64;
+        // P(r) = 0.5 and P(s, r) = 0.09
+        assert!((data.counts(&["r"])[1] as f64 / n - 0.5).abs() < 0.02);
+        assert!((data.counts(&["s", "r"])[3] as f64 / n - 0.09).abs() < 0.01);
+        // w is false whenever s and r are
+        let r = data.column_index("r").unwrap();
+        let s = data.column_index("s").unwrap();
+        let w = data.column_index("w").unwrap();
+        assert!(data
+            .rows()
+            .iter()
+            .all(|row| row[r] + row[s] > 0 || row[w] == 0));
+        assert_eq!(forward_sample(&bn, 20_000, 5), data);
+    }
+
+    #[test]
+    fn test_likelihood_weighting() {
+        let bn = mk_sprinkler();
+        let evidence = Evidence::from([("w", "t")]);
+        let (data, weights) = likelihood_weighting(&bn, &evidence, 20_000, 3);
+        assert_eq!(weights.len(), data.nb_rows());
+        let w = data.column_index("w").unwrap();
+        let r = data.column_index("r").unwrap();
+        assert!(data.rows().iter().all(|row| row[w] == 1));
+        let total: f64 = weights.iter().sum();
+        let rain: f64 = data
+            .rows()
+            .iter()
+            .zip(&weights)
+            .filter(|(row, _)| row[r] == 1)
+            .map(|(_, x)| x)
+            .sum();
+        // the engine shares the sampling loop, so estimates agree exactly
+        let q = Query::marginal(["r"]).given(evidence);
+        match LikelihoodWeighting::new(&bn, 20_000, 3).query(&q) {
+            QueryResult::Marginal(d) => {
+                assert!((d.probability(&["t"]) - rain / total).abs() < 1e-12)
+            }
+            _ => panic!("marginal expected"),
+        }
+        assert!((rain / total - 0.707_927_677_329_624_5).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_gibbs_sampler() {
+        let bn = mk_sprinkler();
+        let evidence = Evidence::from([("w", "t")]);
+        let mut gibbs = GibbsSampler::new(&bn, &evidence, 500, 2, 11);
+        let data = gibbs.sample(10);
+        assert_eq!(data.nb_rows(), 10);
+        let w = data.column_index("w").unwrap();
+        assert!(data.rows().iter().all(|row| row[w] == 1));
+        let d = gibbs.marginal(&["r"], 20_000);
+        assert!((d.probability(&["t"]) - 0.707_927_677_329_624_5).abs() < 0.02);
+        let again = GibbsSampler::new(&bn, &evidence, 500, 2, 11).sample(10);
+        assert_eq!(again, data);
+    }
+
+    #[test]
+    fn test_gibbs_sampler_deterministic_table() {
+        // s and r false forces w false, so w true rules that state out
+        let bn = mk_sprinkler();
+        let evidence = Evidence::from([("w", "t")]);
+        let mut gibbs = GibbsSampler::new(&bn, &evidence, 0, 1, 0);
+        let d = gibbs.marginal(&["s", "r"], 5_000);
+        assert_eq!(d.probability(&["f", "f"]), 0.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gibbs_sampler_impossible_evidence() {
+        let evidence = Evidence::from([("s", "f"), ("r", "f"), ("w", "t")]);
+        GibbsSampler::new(&mk_sprinkler(), &evidence, 0, 1, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_likelihood_weighting_unknown_state() {
+        likelihood_weighting(&mk_sprinkler(), &Evidence::from([("w", "x")]), 10, 0);
+    }
+}
@@ -0,0 +1,461 @@
+//! influence diagrams
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::graph_obj::GraphObject;
+use crate::graph::types::edge::Edge;
+use crate::graph::types::edgetype::EdgeType;
+use crate::graph::types::graph::Graph;
+use crate::graph::types::node::Node;
+use crate::pgm::bayesnet::BayesianNetwork;
+use crate::pgm::cpd::{ConditionalDistribution, Deterministic, TabularCPD};
+use crate::pgm::factorgraph::KIND_KEY;
+use crate::pgm::query::{Enumeration, InferenceEngine, Query};
+use crate::pgm::variable::DiscreteVariable;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+
+/// kinds of the nodes of an influence diagram, stored in the node data
+/// under [KIND_KEY]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NodeKind {
+    /// random variable with a conditional probability table
+    Chance,
+    /// variable whose state is chosen by the decision maker
+    Decision,
+    /// deterministic utility of the states of its parents
+    Utility,
+}
+
+impl NodeKind {
+    /// value stored in the node data
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NodeKind::Chance => "chance",
+            NodeKind::Decision => "decision",
+            NodeKind::Utility => "utility",
+        }
+    }
+
+    fn of(n: &Node) -> Option<NodeKind> {
+        let kinds = n.data().get(KIND_KEY)?;
+        [NodeKind::Chance, NodeKind::Decision, NodeKind::Utility]
+            .into_iter()
+            .find(|k| kinds.len() == 1 && kinds[0] == k.as_str())
+    }
+
+    fn node(&self, nid: &str) -> Node {
+        let data = HashMap::from([(KIND_KEY.to_string(), vec![self.as_str().to_string()])]);
+        Node::new(nid.to_string(), data)
+    }
+}
+
+impl fmt::Display for NodeKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// utility of every configuration of the parents of a utility node
+#[derive(Debug, Clone, PartialEq)]
+struct UtilityTable {
+    parents: Vec<DiscreteVariable>,
+    values: Vec<f64>,
+}
+
+impl UtilityTable {
+    fn value_at(&self, parent_states: &[usize]) -> f64 {
+        let mut index = 0;
+        for (p, s) in self.parents.iter().zip(parent_states) {
+            index = index * p.cardinality() + s;
+        }
+        self.values[index]
+    }
+}
+
+/// Influence diagram
+/// # Description
+/// A bayesian network extended with decision nodes, whose parents are the
+/// information available when the decision is made, and utility nodes,
+/// which have no children. Nodes are added one at a time and their parents
+/// must already be in the diagram, so the diagram is always acyclic. A
+/// policy gives a decision rule per decision, as a table of the decision
+/// given its parents.
+///
+/// # Example
+/// ```
+/// use pgm_rust::pgm::cpd::TabularCPD;
+/// use pgm_rust::pgm::influence::InfluenceDiagram;
+/// use pgm_rust::pgm::variable::DiscreteVariable;
+///
+/// let weather = DiscreteVariable::new("weather", &["dry", "rain"]);
+/// let umbrella = DiscreteVariable::new("umbrella", &["leave", "take"]);
+/// let mut id = InfluenceDiagram::new("umbrella");
+/// id.add_chance(TabularCPD::marginal(weather.clone(), vec![0.7, 0.3]));
+/// id.add_decision(umbrella.clone(), &[]);
+/// id.add_utility("comfort", vec![weather, umbrella], vec![100.0, 20.0, 0.0, 70.0]);
+/// let (policy, eu) = id.optimal_policy("umbrella");
+/// policy.probability("leave", &[]) == 1.0; // true
+/// (eu - 70.0).abs() < 1e-9; // true
+/// ```
+///
+/// # References
+/// Koller D, Friedman N. Probabilistic Graphical Models. 2009, p. 1090.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InfluenceDiagram {
+    graph: Graph<Node, Edge<Node>>,
+    variables: BTreeMap<String, DiscreteVariable>,
+    cpds: HashMap<String, TabularCPD>,
+    decisions: Vec<String>,
+    utilities: BTreeMap<String, UtilityTable>,
+}
+
+impl InfluenceDiagram {
+    /// empty influence diagram
+    pub fn new(id: &str) -> InfluenceDiagram {
+        InfluenceDiagram {
+            graph: Graph::empty(id),
+            variables: BTreeMap::new(),
+            cpds: HashMap::new(),
+            decisions: Vec::new(),
+            utilities: BTreeMap::new(),
+        }
+    }
+
+    /// underlying graph, node kinds are stored under [KIND_KEY]
+    pub fn graph(&self) -> &Graph<Node, Edge<Node>> {
+        &self.graph
+    }
+
+    /// kind of a node
+    pub fn node_kind(&self, name: &str) -> NodeKind {
+        match self.graph.vertices().into_iter().find(|n| n.id() == name) {
+            None => panic!("{name} not in {}", self.graph),
+            Some(n) => NodeKind::of(n).unwrap(),
+        }
+    }
+
+    /// decision nodes in the order they were added
+    pub fn decisions(&self) -> &[String] {
+        &self.decisions
+    }
+
+    /// add a node whose parents are chance or decision nodes of the diagram
+    fn add_node(&mut self, name: &str, kind: NodeKind, parents: &[DiscreteVariable]) {
+        if self.graph.vertices().iter().any(|n| n.id() == name) {
+            panic!("{name} is already in {}", self.graph);
+        }
+        let node = kind.node(name);
+        let mut nodes: HashSet<Node> = self.graph.vertices().into_iter().cloned().collect();
+        let mut edges: HashSet<Edge<Node>> = self.graph.edges().into_iter().cloned().collect();
+        for p in parents {
+            if self.variables.get(p.name()) != Some(p) {
+                panic!(
+                    "parent {} of {name} is not a chance or decision node",
+                    p.name()
+                );
+            }
+            let pnode = self.node_kind(p.name()).node(p.name());
+            edges.insert(Edge::new(
+                format!("{}->{name}", p.name()),
+                HashMap::new(),
+                pnode,
+                node.clone(),
+                EdgeType::Directed,
+            ));
+        }
+        nodes.insert(node);
+        self.graph = Graph::new(
+            self.graph.id().clone(),
+            self.graph.data().clone(),
+            nodes,
+            edges,
+        );
+    }
+
+    /// add a chance node with its table, the parents of the table must be
+    /// in the diagram
+    pub fn add_chance(&mut self, cpd: TabularCPD) {
+        let variable = cpd.variable().clone();
+        self.add_node(variable.name(), NodeKind::Chance, cpd.parents());
+        self.variables
+            .insert(variable.name().clone(), variable.clone());
+        self.cpds.insert(variable.name().clone(), cpd);
+    }
+
+    /// add a decision node whose alternatives are the states of `variable`
+    /// and which is made knowing the states of `information`
+    pub fn add_decision(&mut self, variable: DiscreteVariable, information: &[&str]) {
+        let parents: Vec<DiscreteVariable> = information
+            .iter()
+            .map(|p| match self.variables.get(*p) {
+                None => panic!("{p} is not a chance or decision node"),
+                Some(v) => v.clone(),
+            })
+            .collect();
+        self.add_node(variable.name(), NodeKind::Decision, &parents);
+        self.decisions.push(variable.name().clone());
+        self.variables.insert(variable.name().clone(), variable);
+    }
+
+    /// add a utility node, `values` holds the utility of every parent
+    /// configuration in the row order of [TabularCPD]
+    pub fn add_utility(&mut self, name: &str, parents: Vec<DiscreteVariable>, values: Vec<f64>) {
+        let size: usize = parents.iter().map(|p| p.cardinality()).product();
+        if values.len() != size {
+            panic!(
+                "{} utilities given for {name}, {} expected",
+                values.len(),
+                size
+            );
+        }
+        self.add_node(name, NodeKind::Utility, &parents);
+        self.utilities
+            .insert(name.to_string(), UtilityTable { parents, values });
+    }
+
+    /// information parents of a decision ordered by identifier
+    fn information(&self, decision: &str) -> Vec<DiscreteVariable> {
+        if !self.decisions.iter().any(|d| d == decision) {
+            panic!("{decision} is not a decision of {}", self.graph);
+        }
+        let mut parents: Vec<DiscreteVariable> = self
+            .graph
+            .edges()
+            .into_iter()
+            .filter(|e| e.end().id() == decision)
+            .map(|e| self.variables[e.start().id()].clone())
+            .collect();
+        parents.sort_by(|a, b| a.name().cmp(b.name()));
+        parents
+    }
+
+    /// Bayesian network of the chance and decision nodes, decisions being
+    /// made according to `policy`
+    /// # Description
+    /// Panics if a decision has no rule in `policy` or if the parents of a
+    /// rule are not the information parents of its decision.
+    pub fn to_bayesian_network(&self, policy: &HashMap<String, TabularCPD>) -> BayesianNetwork {
+        let names: Vec<&str> = self.variables.keys().map(|v| v.as_str()).collect();
+        let arcs: Vec<(&str, &str)> = self
+            .graph
+            .edges()
+            .into_iter()
+            .filter(|e| self.variables.contains_key(e.end().id()))
+            .map(|e| (e.start().id().as_str(), e.end().id().as_str()))
+            .collect();
+        let mut bn = BayesianNetwork::from_arcs(self.graph.id(), &names, &arcs);
+        for cpd in self.cpds.values() {
+            bn.add_cpd(cpd.clone());
+        }
+        for d in &self.decisions {
+            match policy.get(d) {
+                None => panic!("policy has no rule for {d}"),
+                Some(rule) if rule.variable() != &self.variables[d] => {
+                    panic!("rule of {d} is about {}", rule.variable().name())
+                }
+                Some(rule) => bn.add_cpd(rule.clone()),
+            }
+        }
+        bn
+    }
+
+    /// Expected utility of a policy
+    /// # Description
+    /// Sum over the utility nodes of the expectation of their utility under
+    /// the joint distribution of [InfluenceDiagram::to_bayesian_network].
+    /// Inference is exact, by enumeration.
+    pub fn expected_utility(&self, policy: &HashMap<String, TabularCPD>) -> f64 {
+        let bn = self.to_bayesian_network(policy);
+        let engine = Enumeration::new(&bn);
+        let mut eu = 0.0;
+        for table in self.utilities.values() {
+            if table.parents.is_empty() {
+                eu += table.values[0];
+                continue;
+            }
+            let names: Vec<&str> = table.parents.iter().map(|p| p.name().as_str()).collect();
+            let distribution = match engine.query(&Query::marginal(names)).into_marginal() {
+                None => panic!("marginal query answered by a map assignment"),
+                Some(d) => d,
+            };
+            for (i, p) in distribution.values().iter().enumerate() {
+                eu += p * table.values[i];
+            }
+        }
+        eu
+    }
+
+    /// Find the best decision rule of a decision
+    /// # Description
+    /// The diagram must have a single decision. For every configuration of
+    /// the information parents of the decision, the alternative maximizing
+    /// the expected utility is chosen, the first one in case of ties.
+    /// Returns the deterministic rule and the expected utility it achieves.
+    pub fn optimal_policy(&self, decision: &str) -> (TabularCPD, f64) {
+        if self.decisions.len() != 1 {
+            panic!(
+                "{} has {} decisions, one expected",
+                self.graph,
+                self.decisions.len()
+            );
+        }
+        let variable = self.variables[decision].clone();
+        let information = self.information(decision);
+        let nb_configurations: usize = information.iter().map(|p| p.cardinality()).product();
+        let constant = |a: usize| -> TabularCPD {
+            let mut row = vec![0.0; variable.cardinality()];
+            row[a] = 1.0;
+            TabularCPD::new(
+                variable.clone(),
+                information.clone(),
+                vec![row; nb_configurations],
+            )
+        };
+        // contribution of every information configuration to the expected
+        // utility of an alternative
+        let contributions = |a: usize| -> Vec<f64> {
+            let mut scores = vec![0.0; nb_configurations];
+            let policy = HashMap::from([(decision.to_string(), constant(a))]);
+            let bn = self.to_bayesian_network(&policy);
+            let engine = Enumeration::new(&bn);
+            for table in self.utilities.values() {
+                let mut scope: Vec<DiscreteVariable> = information.clone();
+                for p in &table.parents {
+                    if !scope.contains(p) {
+                        scope.push(p.clone());
+                    }
+                }
+                let names: Vec<&str> = scope.iter().map(|p| p.name().as_str()).collect();
+                let distribution = match engine.query(&Query::marginal(names)).into_marginal() {
+                    None => panic!("marginal query answered by a map assignment"),
+                    Some(d) => d,
+                };
+                let rest: usize = scope[information.len()..]
+                    .iter()
+                    .map(|p| p.cardinality())
+                    .product();
+                for (i, p) in distribution.values().iter().enumerate() {
+                    let states = decompose(i, &scope);
+                    let parent_states: Vec<usize> = table
+                        .parents
+                        .iter()
+                        .map(|v| states[scope.iter().position(|w| w == v).unwrap()])
+                        .collect();
+                    scores[i / rest] += p * table.value_at(&parent_states);
+                }
+            }
+            scores
+        };
+        let by_alternative: Vec<Vec<f64>> =
+            (0..variable.cardinality()).map(contributions).collect();
+        let mut eu = 0.0;
+        let mut rows: Vec<Vec<f64>> = Vec::with_capacity(nb_configurations);
+        for c in 0..nb_configurations {
+            let mut best = 0;
+            for (a, scores) in by_alternative.iter().enumerate() {
+                if scores[c] > by_alternative[best][c] + 1e-12 {
+                    best = a;
+                }
+            }
+            eu += by_alternative[best][c];
+            let mut row = vec![0.0; variable.cardinality()];
+            row[best] = 1.0;
+            rows.push(row);
+        }
+        (TabularCPD::new(variable, information, rows), eu)
+    }
+}
+
+/// state indices of the joint state at `index`, see [TabularCPD] row order
+fn decompose(mut index: usize, variables: &[DiscreteVariable]) -> Vec<usize> {
+    let mut states = vec![0; variables.len()];
+    for (i, v) in variables.iter().enumerate().rev() {
+        states[i] = index % v.cardinality();
+        index /= v.cardinality();
+    }
+    states
+}
+
+impl fmt::Display for InfluenceDiagram {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<InfluenceDiagram id='{}'>", self.graph.id())
+    }
+}
+
+/// Decision rule choosing an alternative from the information parents of a
+/// decision, see [Deterministic]
+pub fn decision_rule(
+    decision: DiscreteVariable,
+    information: Vec<DiscreteVariable>,
+    rule: fn(&[usize]) -> usize,
+) -> TabularCPD {
+    Deterministic::new(decision, information, rule).to_tabular()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// oil wildcatter: test the soil, then decide to drill
+    fn mk_wildcatter() -> InfluenceDiagram {
+        let oil = DiscreteVariable::new("oil", &["dry", "wet"]);
+        let test = DiscreteVariable::new("test", &["bad", "good"]);
+        let drill = DiscreteVariable::new("drill", &["no", "yes"]);
+        let mut id = InfluenceDiagram::new("wildcatter");
+        id.add_chance(TabularCPD::marginal(oil.clone(), vec![0.6, 0.4]));
+        id.add_chance(TabularCPD::new(
+            test,
+            vec![oil.clone()],
+            vec![vec![0.8, 0.2], vec![0.1, 0.9]],
+        ));
+        id.add_decision(drill.clone(), &["test"]);
+        id.add_utility("payoff", vec![oil, drill], vec![0.0, -70.0, 0.0, 50.0]);
+        id
+    }
+
+    #[test]
+    fn test_node_kinds() {
+        let id = mk_wildcatter();
+        assert_eq!(id.node_kind("oil"), NodeKind::Chance);
+        assert_eq!(id.node_kind("drill"), NodeKind::Decision);
+        assert_eq!(id.node_kind("payoff"), NodeKind::Utility);
+        assert_eq!(id.graph().size(), 4);
+    }
+
+    #[test]
+    fn test_expected_utility() {
+        let id = mk_wildcatter();
+        let test = DiscreteVariable::new("test", &["bad", "good"]);
+        let drill = DiscreteVariable::new("drill", &["no", "yes"]);
+        let always = decision_rule(drill.clone(), vec![test.clone()], |_: &[usize]| 1);
+        let eu = id.expected_utility(&HashMap::from([("drill".to_string(), always)]));
+        assert!((eu - (0.6 * -70.0 + 0.4 * 50.0)).abs() < 1e-9);
+        let follow = decision_rule(drill, vec![test], |u: &[usize]| u[0]);
+        let eu = id.expected_utility(&HashMap::from([("drill".to_string(), follow)]));
+        // drill on a good test: 0.6 * 0.2 * -70 + 0.4 * 0.9 * 50
+        assert!((eu - 9.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_optimal_policy() {
+        let (rule, eu) = mk_wildcatter().optimal_policy("drill");
+        assert_eq!(rule.probability("yes", &["good"]), 1.0);
+        assert_eq!(rule.probability("no", &["bad"]), 1.0);
+        assert!((eu - 9.6).abs() < 1e-9);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_add_unknown_parent() {
+        let mut id = InfluenceDiagram::new("id");
+        id.add_decision(DiscreteVariable::new("d", &["a", "b"]), &["x"]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_expected_utility_missing_rule() {
+        mk_wildcatter().expected_utility(&HashMap::new());
+    }
+}
@@ -0,0 +1,10 @@
+//! model input output
+
+/// bayesian interchange format
+pub mod bif;
+
+/// xml version of the bayesian interchange format
+pub mod xmlbif;
+
+/// uai inference competition format
+pub mod uai;
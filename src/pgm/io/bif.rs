@@ -0,0 +1,671 @@
+//! read and write bayesian networks in the bayesian interchange format
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::graph_obj::GraphObject;
+use crate::pgm::bayesnet::BayesianNetwork;
+use crate::pgm::cpd::TabularCPD;
+use crate::pgm::variable::DiscreteVariable;
+use std::collections::HashMap;
+use std::io;
+use std::io::BufRead;
+use std::io::Write;
+
+fn invalid(line_nb: usize, msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("line {line_nb}: {msg}"))
+}
+
+/// split the input into words and punctuation, with their line numbers.
+/// Comments are dropped.
+fn tokenize(text: &str) -> Vec<(String, usize)> {
+    let mut tokens = Vec::new();
+    let mut in_block_comment = false;
+    for (i, line) in text.lines().enumerate() {
+        let line_nb = i + 1;
+        let mut word = String::new();
+        let chars: Vec<char> = line.chars().collect();
+        let mut k = 0;
+        while k < chars.len() {
+            let c = chars[k];
+            let next = chars.get(k + 1).copied();
+            if in_block_comment {
+                if c == '*' && next == Some('/') {
+                    in_block_comment = false;
+                    k += 1;
+                }
+                k += 1;
+                continue;
+            }
+            if c == '/' && next == Some('/') {
+                break;
+            }
+            if c == '/' && next == Some('*') {
+                in_block_comment = true;
+                k += 2;
+                continue;
+            }
+            if c.is_whitespace() || "{}()[];,|".contains(c) {
+                if !word.is_empty() {
+                    tokens.push((std::mem::take(&mut word), line_nb));
+                }
+                if !c.is_whitespace() {
+                    tokens.push((c.to_string(), line_nb));
+                }
+            } else {
+                word.push(c);
+            }
+            k += 1;
+        }
+        if !word.is_empty() {
+            tokens.push((word, line_nb));
+        }
+    }
+    tokens
+}
+
+/// cursor over the tokens of a bif document
+struct Parser {
+    tokens: Vec<(String, usize)>,
+    pos: usize,
+}
+
+impl Parser {
+    fn line_nb(&self) -> usize {
+        match self.tokens.get(self.pos).or(self.tokens.last()) {
+            None => 0,
+            Some((_, l)) => *l,
+        }
+    }
+
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(|(t, _)| t.as_str())
+    }
+
+    fn next(&mut self) -> io::Result<String> {
+        match self.tokens.get(self.pos) {
+            None => Err(invalid(self.line_nb(), "unexpected end of input")),
+            Some((t, _)) => {
+                self.pos += 1;
+                Ok(t.clone())
+            }
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> io::Result<()> {
+        let line_nb = self.line_nb();
+        let t = self.next()?;
+        if t != token {
+            return Err(invalid(
+                line_nb,
+                &format!("expected '{token}', found '{t}'"),
+            ));
+        }
+        Ok(())
+    }
+
+    fn number(&mut self) -> io::Result<f64> {
+        let line_nb = self.line_nb();
+        let t = self.next()?;
+        t.parse::<f64>()
+            .map_err(|_| invalid(line_nb, &format!("'{t}' is not a number")))
+    }
+
+    /// words separated by commas until `end`, which is consumed
+    fn list(&mut self, end: &str) -> io::Result<Vec<String>> {
+        let mut words = Vec::new();
+        loop {
+            let t = self.next()?;
+            if t == end {
+                return Ok(words);
+            }
+            if t != "," {
+                words.push(t);
+            }
+        }
+    }
+
+    /// skip a statement ending with a semicolon, or a block
+    fn skip_block(&mut self) -> io::Result<()> {
+        let mut depth = 0;
+        loop {
+            let t = self.next()?;
+            match t.as_str() {
+                "{" => depth += 1,
+                "}" => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(());
+                    }
+                }
+                ";" if depth == 0 => return Ok(()),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// probability block before conversion to a table
+struct ProbabilityBlock {
+    variable: String,
+    parents: Vec<String>,
+    table: Option<Vec<f64>>,
+    default: Option<Vec<f64>>,
+    rows: Vec<(Vec<String>, Vec<f64>)>,
+    line_nb: usize,
+}
+
+fn parse_variable(p: &mut Parser) -> io::Result<DiscreteVariable> {
+    let name = p.next()?;
+    p.expect("{")?;
+    let mut states: Option<Vec<String>> = None;
+    loop {
+        let line_nb = p.line_nb();
+        match p.next()?.as_str() {
+            "}" => break,
+            "type" => {
+                p.expect("discrete")?;
+                p.expect("[")?;
+                let n = p.number()? as usize;
+                p.expect("]")?;
+                p.expect("{")?;
+                let s = p.list("}")?;
+                p.expect(";")?;
+                if s.len() != n {
+                    let msg = format!("{name} declares {n} states and lists {}", s.len());
+                    return Err(invalid(line_nb, &msg));
+                }
+                states = Some(s);
+            }
+            "property" => {
+                p.pos -= 1;
+                p.skip_block()?;
+            }
+            t => {
+                return Err(invalid(
+                    line_nb,
+                    &format!("unexpected '{t}' in variable {name}"),
+                ))
+            }
+        }
+    }
+    let states = states.ok_or_else(|| invalid(p.line_nb(), &format!("{name} has no type")))?;
+    let refs: Vec<&str> = states.iter().map(|s| s.as_str()).collect();
+    DiscreteVariable::try_new(&name, &refs).map_err(|msg| invalid(p.line_nb(), &msg))
+}
+
+fn parse_probability(p: &mut Parser) -> io::Result<ProbabilityBlock> {
+    let line_nb = p.line_nb();
+    p.expect("(")?;
+    let mut names = Vec::new();
+    let mut parents = Vec::new();
+    let mut after_bar = false;
+    loop {
+        let t = p.next()?;
+        match t.as_str() {
+            ")" => break,
+            "," => {}
+            "|" => after_bar = true,
+            _ if after_bar => parents.push(t),
+            _ => names.push(t),
+        }
+    }
+    if names.len() != 1 {
+        return Err(invalid(
+            line_nb,
+            "a probability block must have one variable",
+        ));
+    }
+    let mut block = ProbabilityBlock {
+        variable: names.remove(0),
+        parents,
+        table: None,
+        default: None,
+        rows: Vec::new(),
+        line_nb,
+    };
+    p.expect("{")?;
+    loop {
+        match p.peek() {
+            None => return Err(invalid(p.line_nb(), "unexpected end of input")),
+            Some("}") => {
+                p.pos += 1;
+                return Ok(block);
+            }
+            Some("table") | Some("default") => {
+                let keyword = p.next()?;
+                let values: Vec<f64> = p
+                    .list(";")?
+                    .iter()
+                    .map(|v| {
+                        v.parse::<f64>()
+                            .map_err(|_| invalid(p.line_nb(), "not a number"))
+                    })
+                    .collect::<io::Result<_>>()?;
+                if keyword == "table" {
+                    block.table = Some(values);
+                } else {
+                    block.default = Some(values);
+                }
+            }
+            Some("property") => p.skip_block()?,
+            Some("(") => {
+                p.pos += 1;
+                let states = p.list(")")?;
+                let values: Vec<f64> = p
+                    .list(";")?
+                    .iter()
+                    .map(|v| {
+                        v.parse::<f64>()
+                            .map_err(|_| invalid(p.line_nb(), "not a number"))
+                    })
+                    .collect::<io::Result<_>>()?;
+                block.rows.push((states, values));
+            }
+            Some(t) => {
+                let msg = format!("unexpected '{t}' in probability of {}", block.variable);
+                return Err(invalid(p.line_nb(), &msg));
+            }
+        }
+    }
+}
+
+/// table of a probability block in the row order of [TabularCPD]
+fn block_table(
+    block: &ProbabilityBlock,
+    variable: &DiscreteVariable,
+    parents: &[DiscreteVariable],
+) -> io::Result<Vec<Vec<f64>>> {
+    let r = variable.cardinality();
+    let nb_rows: usize = parents.iter().map(|p| p.cardinality()).product();
+    let mut table: Vec<Option<Vec<f64>>> = vec![block.default.clone(); nb_rows];
+    if let Some(values) = &block.table {
+        if values.len() != r * nb_rows {
+            return Err(invalid(
+                block.line_nb,
+                &format!("table of {} has the wrong size", variable.name()),
+            ));
+        }
+        // the variable varies slowest in a table statement
+        for (row, entry) in table.iter_mut().enumerate() {
+            *entry = Some((0..r).map(|s| values[s * nb_rows + row]).collect());
+        }
+    }
+    for (states, values) in &block.rows {
+        if states.len() != parents.len() {
+            return Err(invalid(block.line_nb, "wrong number of parent states"));
+        }
+        let mut index = 0;
+        for (p, s) in parents.iter().zip(states) {
+            match p.state_index(s) {
+                None => {
+                    return Err(invalid(
+                        block.line_nb,
+                        &format!("{} has no state {s}", p.name()),
+                    ))
+                }
+                Some(i) => index = index * p.cardinality() + i,
+            }
+        }
+        table[index] = Some(values.clone());
+    }
+    table
+        .into_iter()
+        .map(|row| match row {
+            Some(values) if values.len() == r => Ok(values),
+            Some(_) => Err(invalid(
+                block.line_nb,
+                &format!("a row of {} has the wrong size", variable.name()),
+            )),
+            None => Err(invalid(
+                block.line_nb,
+                &format!("a row of {} is missing", variable.name()),
+            )),
+        })
+        .collect()
+}
+
+/// Read a bayesian network in the bayesian interchange format
+/// # Description
+/// Reads the `network`, `variable` and `probability` blocks of BIF 0.15 as
+/// used by the bnlearn repository. Only discrete variables are supported
+/// and properties are ignored. Conditional tables may be given row by row,
+/// with a `default` row, or as a single `table` statement in which the
+/// variable varies slowest and the last parent fastest. The network gets
+/// the name of the `network` block as identifier.
+///
+/// # Errors
+/// Returns an [io::ErrorKind::InvalidData] error if the input is not
+/// valid BIF, if a variable is declared twice, if a variable has no or
+/// several probability blocks, if a table is incomplete or is not a
+/// conditional distribution, see [TabularCPD::try_new], or if the
+/// probability blocks have a directed cycle.
+///
+/// # Example
+/// ```
+/// use pgm_rust::pgm::io::bif::from_bif;
+///
+/// let text = "network rain { }
+/// variable rain { type discrete [ 2 ] { no, yes }; }
+/// variable wet { type discrete [ 2 ] { no, yes }; }
+/// probability ( rain ) { table 0.8, 0.2; }
+/// probability ( wet | rain ) { (no) 0.9, 0.1; (yes) 0.2, 0.8; }
+/// ";
+/// let bn = from_bif(text.as_bytes()).unwrap();
+/// bn.cpd("wet").unwrap().probability("yes", &["yes"]) == 0.8; // true
+/// ```
+///
+/// # References
+/// Cozman F. The Interchange Format for Bayesian Networks. 1998.
+pub fn from_bif<R: BufRead>(mut reader: R) -> io::Result<BayesianNetwork> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    let mut p = Parser {
+        tokens: tokenize(&text),
+        pos: 0,
+    };
+    let mut name = String::from("network");
+    let mut variables: Vec<DiscreteVariable> = Vec::new();
+    let mut blocks: Vec<ProbabilityBlock> = Vec::new();
+    while let Some(t) = p.peek() {
+        let line_nb = p.line_nb();
+        match t {
+            "network" => {
+                p.pos += 1;
+                name = p.next()?;
+                p.pos -= 1;
+                p.skip_block()?;
+            }
+            "variable" => {
+                p.pos += 1;
+                let variable = parse_variable(&mut p)?;
+                if variables.iter().any(|v| v.name() == variable.name()) {
+                    let msg = format!("{} is declared twice", variable.name());
+                    return Err(invalid(line_nb, &msg));
+                }
+                variables.push(variable);
+            }
+            "probability" => {
+                p.pos += 1;
+                let block = parse_probability(&mut p)?;
+                if blocks.iter().any(|b| b.variable == block.variable) {
+                    let msg = format!("{} has several probability blocks", block.variable);
+                    return Err(invalid(block.line_nb, &msg));
+                }
+                blocks.push(block);
+            }
+            t => return Err(invalid(line_nb, &format!("unexpected '{t}'"))),
+        }
+    }
+    let by_name: HashMap<&String, &DiscreteVariable> =
+        variables.iter().map(|v| (v.name(), v)).collect();
+    let lookup = |n: &String, line_nb: usize| -> io::Result<DiscreteVariable> {
+        match by_name.get(n) {
+            None => Err(invalid(line_nb, &format!("{n} is not declared"))),
+            Some(v) => Ok((*v).clone()),
+        }
+    };
+    let mut arcs: Vec<(&str, &str)> = Vec::new();
+    let mut cpds: Vec<TabularCPD> = Vec::new();
+    for block in &blocks {
+        let variable = lookup(&block.variable, block.line_nb)?;
+        let parents: Vec<DiscreteVariable> = block
+            .parents
+            .iter()
+            .map(|n| lookup(n, block.line_nb))
+            .collect::<io::Result<_>>()?;
+        for par in &block.parents {
+            arcs.push((par.as_str(), block.variable.as_str()));
+        }
+        let table = block_table(block, &variable, &parents)?;
+        let cpd = TabularCPD::try_new(variable, parents, table)
+            .map_err(|msg| invalid(block.line_nb, &msg))?;
+        cpds.push(cpd);
+    }
+    for v in &variables {
+        if !blocks.iter().any(|b| &b.variable == v.name()) {
+            return Err(invalid(
+                p.line_nb(),
+                &format!("{} has no probability block", v.name()),
+            ));
+        }
+    }
+    let names: Vec<&str> = variables.iter().map(|v| v.name().as_str()).collect();
+    let mut bn = BayesianNetwork::try_from_arcs(&name, &names, &arcs)
+        .map_err(|msg| invalid(p.line_nb(), &msg))?;
+    for cpd in cpds {
+        bn.add_cpd(cpd);
+    }
+    Ok(bn)
+}
+
+/// Write a bayesian network in the bayesian interchange format
+/// # Description
+/// Variables are written in topological order and conditional tables row
+/// by row, so that [from_bif] reads the network back. Probabilities are
+/// written with the shortest representation that reads back exactly.
+///
+/// # Errors
+/// Returns an [io::ErrorKind::InvalidInput] error if a variable has no
+/// table, since its states are unknown.
+///
+/// # Example
+/// ```
+/// use pgm_rust::pgm::io::bif::{from_bif, to_bif};
+///
+/// let text = "variable a { type discrete [ 2 ] { f, t }; }
+/// probability ( a ) { table 0.25, 0.75; }";
+/// let bn = from_bif(text.as_bytes()).unwrap();
+/// let mut out: Vec<u8> = Vec::new();
+/// to_bif(&bn, &mut out).unwrap();
+/// from_bif(out.as_slice()).unwrap() == bn; // true
+/// ```
+pub fn to_bif<W: Write>(bn: &BayesianNetwork, writer: &mut W) -> io::Result<()> {
+    if !bn.is_fully_specified() {
+        let msg = format!("{bn} has variables without table");
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, msg));
+    }
+    writeln!(writer, "network {} {{\n}}", bn.id())?;
+    let order = bn.topological_order();
+    for v in &order {
+        let variable = bn.cpd(v).unwrap().variable();
+        writeln!(writer, "variable {} {{", v)?;
+        writeln!(
+            writer,
+            "  type discrete [ {} ] {{ {} }};",
+            variable.cardinality(),
+            variable.states().join(", ")
+        )?;
+        writeln!(writer, "}}")?;
+    }
+    for v in &order {
+        let cpd = bn.cpd(v).unwrap();
+        let row_text = |row: &[f64]| {
+            row.iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        if cpd.parents().is_empty() {
+            writeln!(writer, "probability ( {} ) {{", v)?;
+            writeln!(writer, "  table {};", row_text(&cpd.table()[0]))?;
+        } else {
+            let parents: Vec<&str> = cpd.parents().iter().map(|p| p.name().as_str()).collect();
+            writeln!(writer, "probability ( {} | {} ) {{", v, parents.join(", "))?;
+            for (config, row) in cpd.parent_configurations().iter().zip(cpd.table()) {
+                let states: Vec<&str> = cpd
+                    .parents()
+                    .iter()
+                    .zip(config)
+                    .map(|(p, s)| p.states()[*s].as_str())
+                    .collect();
+                writeln!(writer, "  ({}) {};", states.join(", "), row_text(row))?;
+            }
+        }
+        writeln!(writer, "}}")?;
+    }
+    debug_assert_eq!(order.len(), bn.order());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// the asia network of Lauritzen and Spiegelhalter as distributed by bnlearn
+    const ASIA: &str = "network unknown {
+}
+variable asia {
+  type discrete [ 2 ] { yes, no };
+}
+variable tub {
+  type discrete [ 2 ] { yes, no };
+}
+variable smoke {
+  type discrete [ 2 ] { yes, no };
+}
+variable lung {
+  type discrete [ 2 ] { yes, no };
+}
+variable bronc {
+  type discrete [ 2 ] { yes, no };
+}
+variable either {
+  type discrete [ 2 ] { yes, no };
+}
+variable xray {
+  type discrete [ 2 ] { yes, no };
+}
+variable dysp {
+  type discrete [ 2 ] { yes, no };
+}
+probability ( asia ) {
+  table 0.01, 0.99;
+}
+probability ( tub | asia ) {
+  (yes) 0.05, 0.95;
+  (no) 0.01, 0.99;
+}
+probability ( smoke ) {
+  table 0.5, 0.5;
+}
+probability ( lung | smoke ) {
+  (yes) 0.1, 0.9;
+  (no) 0.01, 0.99;
+}
+probability ( bronc | smoke ) {
+  (yes) 0.6, 0.4;
+  (no) 0.3, 0.7;
+}
+probability ( either | lung, tub ) {
+  (yes, yes) 1.0, 0.0;
+  (no, yes) 1.0, 0.0;
+  (yes, no) 1.0, 0.0;
+  (no, no) 0.0, 1.0;
+}
+probability ( xray | either ) {
+  (yes) 0.98, 0.02;
+  (no) 0.05, 0.95;
+}
+probability ( dysp | bronc, either ) {
+  (yes, yes) 0.9, 0.1;
+  (no, yes) 0.7, 0.3;
+  (yes, no) 0.8, 0.2;
+  (no, no) 0.1, 0.9;
+}
+";
+
+    #[test]
+    fn test_from_bif_asia() {
+        let bn = from_bif(ASIA.as_bytes()).unwrap();
+        assert_eq!(bn.id(), "unknown");
+        assert_eq!(bn.order(), 8);
+        assert_eq!(bn.size(), 8);
+        let either = bn.cpd("either").unwrap();
+        assert_eq!(either.probability("no", &["no", "no"]), 1.0);
+        let dysp = bn.cpd("dysp").unwrap();
+        assert_eq!(dysp.probability("yes", &["no", "yes"]), 0.7);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let bn = from_bif(ASIA.as_bytes()).unwrap();
+        let mut out: Vec<u8> = Vec::new();
+        to_bif(&bn, &mut out).unwrap();
+        assert_eq!(from_bif(out.as_slice()).unwrap(), bn);
+    }
+
+    #[test]
+    fn test_from_bif_table_and_default() {
+        let text = "/* header */ network n { property \"x\" ; }
+variable a { type discrete [ 2 ] { f, t }; property p; }
+variable b { type discrete [ 3 ] { x, y, z }; }
+probability ( a ) { table 0.5, 0.5; } // comment
+probability ( b | a ) { table 0.2, 0.4, 0.3, 0.4, 0.5, 0.2; }
+";
+        let bn = from_bif(text.as_bytes()).unwrap();
+        assert_eq!(
+            bn.cpd("b").unwrap().table(),
+            &[vec![0.2, 0.3, 0.5], vec![0.4, 0.4, 0.2]]
+        );
+        let text = "variable a { type discrete [ 2 ] { f, t }; }
+variable b { type discrete [ 2 ] { f, t }; }
+probability ( a ) { table 0.5, 0.5; }
+probability ( b | a ) { default 0.5, 0.5; (t) 0.1, 0.9; }
+";
+        let bn = from_bif(text.as_bytes()).unwrap();
+        assert_eq!(
+            bn.cpd("b").unwrap().table(),
+            &[vec![0.5, 0.5], vec![0.1, 0.9]]
+        );
+    }
+
+    #[test]
+    fn test_from_bif_invalid() {
+        let missing = "variable a { type discrete [ 2 ] { f, t }; }";
+        assert!(from_bif(missing.as_bytes()).is_err());
+        let undeclared = "variable a { type discrete [ 2 ] { f, t }; }
+probability ( a | b ) { table 0.5, 0.5; }";
+        assert!(from_bif(undeclared.as_bytes()).is_err());
+        let incomplete = "variable a { type discrete [ 2 ] { f, t }; }
+variable b { type discrete [ 2 ] { f, t }; }
+probability ( a ) { table 0.5, 0.5; }
+probability ( b | a ) { (t) 0.1, 0.9; }";
+        assert!(from_bif(incomplete.as_bytes()).is_err());
+        assert!(from_bif("variable a { type discrete [ 3 ] { f, t }; }".as_bytes()).is_err());
+    }
+
+    fn invalid_data(text: &str) -> bool {
+        match from_bif(text.as_bytes()) {
+            Err(e) => e.kind() == io::ErrorKind::InvalidData,
+            Ok(_) => false,
+        }
+    }
+
+    #[test]
+    fn test_from_bif_invalid_tables() {
+        let a = "variable a { type discrete [ 2 ] { f, t }; }\n";
+        let ab = "variable a { type discrete [ 2 ] { f, t }; }
+variable b { type discrete [ 2 ] { f, t }; }\n";
+        // not normalized, negative entry
+        assert!(invalid_data(&format!(
+            "{a}probability ( a ) {{ table 0.5, 0.6; }}"
+        )));
+        assert!(invalid_data(&format!(
+            "{a}probability ( a ) {{ table -0.5, 1.5; }}"
+        )));
+        // repeated state
+        assert!(invalid_data("variable a { type discrete [ 2 ] { f, f }; }"));
+        // cyclic probability blocks
+        assert!(invalid_data(&format!(
+            "{ab}probability ( a | b ) {{ table 0.5, 0.5, 0.5, 0.5; }}
+probability ( b | a ) {{ table 0.5, 0.5, 0.5, 0.5; }}"
+        )));
+        // several blocks for the same variable
+        assert!(invalid_data(&format!(
+            "{a}probability ( a ) {{ table 0.5, 0.5; }}
+probability ( a ) {{ table 0.1, 0.9; }}"
+        )));
+        // variable declared twice
+        assert!(invalid_data(&format!(
+            "{a}{a}probability ( a ) {{ table 0.5, 0.5; }}"
+        )));
+    }
+}
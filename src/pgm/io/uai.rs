@@ -0,0 +1,499 @@
+//! read and write models in the uai inference competition format
+//!
+//! `BAYES` files are read into a [BayesianNetwork] by [from_uai] and
+//! `MARKOV` files into a [FactorGraph] by [from_uai_markov], the crate
+//! having no dedicated markov network model.
+use crate::factor::Factor;
+use crate::graph::traits::graph_obj::GraphObject;
+use crate::pgm::bayesnet::BayesianNetwork;
+use crate::pgm::cpd::TabularCPD;
+use crate::pgm::factorgraph::FactorGraph;
+use crate::pgm::variable::DiscreteVariable;
+use std::io;
+use std::io::BufRead;
+use std::io::Write;
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
+
+/// name given to the variable at `index` of a uai file
+pub fn variable_name(index: usize) -> String {
+    format!("x{index}")
+}
+
+/// cursor over the whitespace separated tokens of a uai file
+struct Tokens<'a> {
+    iter: std::str::SplitWhitespace<'a>,
+}
+
+impl Tokens<'_> {
+    fn next_usize(&mut self) -> io::Result<usize> {
+        let t = self
+            .iter
+            .next()
+            .ok_or_else(|| invalid("unexpected end of input"))?;
+        t.parse::<usize>()
+            .map_err(|_| invalid(&format!("'{t}' is not a count")))
+    }
+
+    fn next_f64(&mut self) -> io::Result<f64> {
+        let t = self
+            .iter
+            .next()
+            .ok_or_else(|| invalid("unexpected end of input"))?;
+        t.parse::<f64>()
+            .map_err(|_| invalid(&format!("'{t}' is not a number")))
+    }
+}
+
+/// content of a uai file: its network type, the variables named by
+/// [variable_name] and the scope and table of every function
+struct Model {
+    bayes: bool,
+    variables: Vec<DiscreteVariable>,
+    scopes: Vec<Vec<usize>>,
+    tables: Vec<Vec<f64>>,
+}
+
+/// read the preamble and the tables of a uai file, checking that scopes
+/// mention declared variables at most once and that tables have one entry
+/// per joint state of their scope
+fn parse<R: BufRead>(mut reader: R) -> io::Result<Model> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    let mut tokens = Tokens {
+        iter: text.split_whitespace(),
+    };
+    let bayes = match tokens.iter.next() {
+        Some("BAYES") => true,
+        Some("MARKOV") => false,
+        Some(t) => return Err(invalid(&format!("unknown network type '{t}'"))),
+        None => return Err(invalid("empty input")),
+    };
+    let nb_variables = tokens.next_usize()?;
+    let mut variables: Vec<DiscreteVariable> = Vec::new();
+    for i in 0..nb_variables {
+        let card = tokens.next_usize()?;
+        if card == 0 {
+            return Err(invalid(&format!("variable {i} has no state")));
+        }
+        let states: Vec<String> = (0..card).map(|s| s.to_string()).collect();
+        let refs: Vec<&str> = states.iter().map(|s| s.as_str()).collect();
+        variables.push(DiscreteVariable::new(&variable_name(i), &refs));
+    }
+    let nb_functions = tokens.next_usize()?;
+    let mut scopes: Vec<Vec<usize>> = Vec::new();
+    for _ in 0..nb_functions {
+        let size = tokens.next_usize()?;
+        if size == 0 {
+            return Err(invalid("a function has an empty scope"));
+        }
+        let mut scope = Vec::new();
+        for _ in 0..size {
+            let v = tokens.next_usize()?;
+            if v >= nb_variables {
+                return Err(invalid(&format!("variable {v} is not declared")));
+            }
+            if scope.contains(&v) {
+                return Err(invalid(&format!("variable {v} is repeated in a scope")));
+            }
+            scope.push(v);
+        }
+        scopes.push(scope);
+    }
+    let mut tables: Vec<Vec<f64>> = Vec::new();
+    for scope in &scopes {
+        let size = tokens.next_usize()?;
+        let expected: usize = scope.iter().map(|v| variables[*v].cardinality()).product();
+        if size != expected {
+            let msg = format!("a table has {size} entries, expected {expected}");
+            return Err(invalid(&msg));
+        }
+        let values: Vec<f64> = (0..size)
+            .map(|_| tokens.next_f64())
+            .collect::<io::Result<_>>()?;
+        tables.push(values);
+    }
+    if tokens.iter.next().is_some() {
+        return Err(invalid("trailing content after the last table"));
+    }
+    Ok(Model {
+        bayes,
+        variables,
+        scopes,
+        tables,
+    })
+}
+
+/// Read a bayesian network in the uai format
+/// # Description
+/// The uai format of the UAI inference competitions describes a network by
+/// a preamble, listing the cardinality of each variable and the scope of
+/// each function, followed by the function tables. In a `BAYES` network the
+/// last variable of a scope is the child and the others are its parents.
+/// Tables are listed with the last scope variable varying fastest, which
+/// is the row order of [TabularCPD]. Variables have no names in this
+/// format, they are named by [variable_name] and their states are `0`,
+/// `1`, etc.
+///
+/// # Errors
+/// Returns an [io::ErrorKind::InvalidData] error if the input is not a
+/// valid `BAYES` file, if a scope repeats a variable, if a table is not a
+/// conditional distribution, see [TabularCPD::try_new], or if the scopes
+/// have a directed cycle. `MARKOV` files are rejected as well, they are
+/// read by [from_uai_markov].
+///
+/// # Example
+/// ```
+/// use pgm_rust::pgm::io::uai::from_uai;
+///
+/// let text = "BAYES
+/// 2
+/// 2 2
+/// 2
+/// 1 0
+/// 2 0 1
+///
+/// 2 0.8 0.2
+/// 4 0.9 0.1 0.2 0.8
+/// ";
+/// let bn = from_uai(text.as_bytes()).unwrap();
+/// bn.cpd("x1").unwrap().probability("1", &["1"]) == 0.8; // true
+/// ```
+///
+/// # References
+/// UAI 2008 probabilistic inference evaluation, model file format.
+pub fn from_uai<R: BufRead>(reader: R) -> io::Result<BayesianNetwork> {
+    let model = parse(reader)?;
+    if !model.bayes {
+        return Err(invalid("markov networks are read by from_uai_markov"));
+    }
+    let variables = &model.variables;
+    let nb_variables = variables.len();
+    if model.scopes.len() != nb_variables {
+        return Err(invalid(
+            "a bayesian network needs one function per variable",
+        ));
+    }
+    let mut defined = vec![false; nb_variables];
+    let mut arcs: Vec<(String, String)> = Vec::new();
+    let mut cpds: Vec<TabularCPD> = Vec::new();
+    for (scope, values) in model.scopes.iter().zip(&model.tables) {
+        let (child, parents) = scope.split_last().unwrap();
+        if defined[*child] {
+            return Err(invalid(&format!("variable {child} has two functions")));
+        }
+        defined[*child] = true;
+        for p in parents {
+            arcs.push((variable_name(*p), variable_name(*child)));
+        }
+        let table: Vec<Vec<f64>> = values
+            .chunks(variables[*child].cardinality())
+            .map(|row| row.to_vec())
+            .collect();
+        let parent_vars: Vec<DiscreteVariable> =
+            parents.iter().map(|p| variables[*p].clone()).collect();
+        let cpd = TabularCPD::try_new(variables[*child].clone(), parent_vars, table)
+            .map_err(|msg| invalid(&msg))?;
+        cpds.push(cpd);
+    }
+    let names: Vec<String> = (0..nb_variables).map(variable_name).collect();
+    let name_refs: Vec<&str> = names.iter().map(|s| s.as_str()).collect();
+    let arc_refs: Vec<(&str, &str)> = arcs.iter().map(|(a, b)| (a.as_str(), b.as_str())).collect();
+    let mut bn = BayesianNetwork::try_from_arcs("uai", &name_refs, &arc_refs)
+        .map_err(|msg| invalid(&msg))?;
+    for cpd in cpds {
+        bn.add_cpd(cpd);
+    }
+    Ok(bn)
+}
+
+/// Read a markov network in the uai format as a factor graph
+/// # Description
+/// Every function of the file becomes a [Factor] over its scope, in the
+/// order of the scope, kept on the factor node numbered as in
+/// [FactorGraph::from_factors]. Variables are named by [variable_name] and
+/// their states are `0`, `1`, etc. The tables of a `BAYES` file are read
+/// as factors as well.
+///
+/// # Errors
+/// Returns an [io::ErrorKind::InvalidData] error if the input is not a
+/// valid uai file, if a scope repeats a variable, if a table has a
+/// negative or non finite entry, or if a variable is in no scope since a
+/// factor graph only holds the variables of its factors.
+///
+/// # Example
+/// ```
+/// use pgm_rust::pgm::io::uai::from_uai_markov;
+///
+/// let text = "MARKOV
+/// 2
+/// 2 3
+/// 2
+/// 1 0
+/// 2 0 1
+///
+/// 2 1.5 0.5
+/// 6 1 2 3 4 5 6
+/// ";
+/// let fg = from_uai_markov(text.as_bytes()).unwrap();
+/// fg.factor("phi1").unwrap().value(&[1, 2]) == 6.0; // true
+/// ```
+///
+/// # References
+/// UAI 2008 probabilistic inference evaluation, model file format.
+pub fn from_uai_markov<R: BufRead>(reader: R) -> io::Result<FactorGraph> {
+    let model = parse(reader)?;
+    for i in 0..model.variables.len() {
+        if !model.scopes.iter().any(|scope| scope.contains(&i)) {
+            return Err(invalid(&format!("variable {i} is in no scope")));
+        }
+    }
+    let mut factors: Vec<Factor> = Vec::new();
+    for (scope, values) in model.scopes.iter().zip(model.tables) {
+        if values.iter().any(|x| !x.is_finite() || *x < 0.0) {
+            return Err(invalid("a table has a negative or non finite entry"));
+        }
+        let vars: Vec<DiscreteVariable> =
+            scope.iter().map(|v| model.variables[*v].clone()).collect();
+        factors.push(Factor::new(vars, values));
+    }
+    Ok(FactorGraph::from_factors(&factors))
+}
+
+/// Write a bayesian network in the uai format
+/// # Description
+/// Variables are numbered in topological order. Since the format has no
+/// names, variable and state names are lost: [from_uai] reads back a
+/// network with the same tables on renamed variables.
+///
+/// # Errors
+/// Returns an [io::ErrorKind::InvalidInput] error if a variable has no
+/// table.
+///
+/// # Example
+/// ```
+/// use pgm_rust::pgm::io::uai::{from_uai, to_uai};
+/// use pgm_rust::pgm::bayesnet::BayesianNetwork;
+/// use pgm_rust::pgm::cpd::TabularCPD;
+/// use pgm_rust::pgm::variable::DiscreteVariable;
+///
+/// let mut bn = BayesianNetwork::from_arcs("coin", &["a"], &[]);
+/// bn.add_cpd(TabularCPD::marginal(DiscreteVariable::new("a", &["h", "t"]), vec![0.4, 0.6]));
+/// let mut out: Vec<u8> = Vec::new();
+/// to_uai(&bn, &mut out).unwrap();
+/// from_uai(out.as_slice()).unwrap().cpd("x0").unwrap().table() == &[vec![0.4, 0.6]]; // true
+/// ```
+pub fn to_uai<W: Write>(bn: &BayesianNetwork, writer: &mut W) -> io::Result<()> {
+    if !bn.is_fully_specified() {
+        let msg = format!("{bn} has variables without table");
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, msg));
+    }
+    let order = bn.topological_order();
+    let index = |name: &String| order.iter().position(|v| *v == name).unwrap();
+    writeln!(writer, "BAYES")?;
+    writeln!(writer, "{}", order.len())?;
+    let cards: Vec<String> = order
+        .iter()
+        .map(|v| bn.cpd(v).unwrap().variable().cardinality().to_string())
+        .collect();
+    writeln!(writer, "{}", cards.join(" "))?;
+    writeln!(writer, "{}", order.len())?;
+    for v in &order {
+        let cpd = bn.cpd(v).unwrap();
+        let mut scope: Vec<String> = cpd
+            .parents()
+            .iter()
+            .map(|p| index(p.name()).to_string())
+            .collect();
+        scope.push(index(v).to_string());
+        writeln!(writer, "{} {}", scope.len(), scope.join(" "))?;
+    }
+    for v in &order {
+        let cpd = bn.cpd(v).unwrap();
+        let size = cpd.table().len() * cpd.variable().cardinality();
+        writeln!(writer, "\n{size}")?;
+        for row in cpd.table() {
+            let values: Vec<String> = row.iter().map(|x| x.to_string()).collect();
+            writeln!(writer, " {}", values.join(" "))?;
+        }
+    }
+    Ok(())
+}
+
+/// Write a factor graph as a markov network in the uai format
+/// # Description
+/// Variables are numbered in name order and factors in identifier order.
+/// Each factor is written over its scope in scope order, so [from_uai_markov]
+/// reads back the same factors on renamed variables.
+///
+/// # Errors
+/// Returns an [io::ErrorKind::InvalidInput] error if a factor node holds
+/// no factor, see [FactorGraph::factor].
+///
+/// # Example
+/// ```
+/// use pgm_rust::factor::Factor;
+/// use pgm_rust::pgm::factorgraph::FactorGraph;
+/// use pgm_rust::pgm::io::uai::{from_uai_markov, to_uai_markov};
+/// use pgm_rust::pgm::variable::DiscreteVariable;
+///
+/// let a = DiscreteVariable::new("a", &["h", "t"]);
+/// let fg = FactorGraph::from_factors(&[Factor::new(vec![a], vec![2.0, 3.0])]);
+/// let mut out: Vec<u8> = Vec::new();
+/// to_uai_markov(&fg, &mut out).unwrap();
+/// from_uai_markov(out.as_slice()).unwrap().factor("phi0").unwrap().values() == &[2.0, 3.0]; // true
+/// ```
+pub fn to_uai_markov<W: Write>(fg: &FactorGraph, writer: &mut W) -> io::Result<()> {
+    if fg.factors().len() != fg.factor_nodes().len() {
+        let msg = format!("{} has factor nodes without factor", fg.id());
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, msg));
+    }
+    let mut factors: Vec<(&String, &Factor)> = fg.factors().iter().collect();
+    factors.sort_by(|a, b| a.0.cmp(b.0));
+    let mut variables: Vec<&DiscreteVariable> = Vec::new();
+    for (_, f) in &factors {
+        for v in f.scope() {
+            if !variables.iter().any(|w| w.name() == v.name()) {
+                variables.push(v);
+            }
+        }
+    }
+    variables.sort_by(|a, b| a.name().cmp(b.name()));
+    let index = |v: &DiscreteVariable| variables.iter().position(|w| w.name() == v.name()).unwrap();
+    writeln!(writer, "MARKOV")?;
+    writeln!(writer, "{}", variables.len())?;
+    let cards: Vec<String> = variables
+        .iter()
+        .map(|v| v.cardinality().to_string())
+        .collect();
+    writeln!(writer, "{}", cards.join(" "))?;
+    writeln!(writer, "{}", factors.len())?;
+    for (_, f) in &factors {
+        let scope: Vec<String> = f.scope().iter().map(|v| index(v).to_string()).collect();
+        writeln!(writer, "{} {}", scope.len(), scope.join(" "))?;
+    }
+    for (_, f) in &factors {
+        writeln!(writer, "\n{}", f.values().len())?;
+        let values: Vec<String> = f.values().iter().map(|x| x.to_string()).collect();
+        writeln!(writer, " {}", values.join(" "))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::traits::graph::Graph as GraphTrait;
+
+    const NETWORK: &str = "BAYES
+3
+2 2 3
+3
+1 0
+2 0 1
+3 0 1 2
+
+2
+ 0.436 0.564
+
+4
+ 0.128 0.872
+ 0.920 0.080
+
+12
+ 0.210 0.333 0.457
+ 0.811 0.000 0.189
+ 0.900 0.000 0.100
+ 0.333 0.333 0.334
+";
+
+    #[test]
+    fn test_from_uai() {
+        let bn = from_uai(NETWORK.as_bytes()).unwrap();
+        assert_eq!(bn.order(), 3);
+        assert_eq!(bn.size(), 3);
+        let x2 = bn.cpd("x2").unwrap();
+        assert_eq!(x2.variable().cardinality(), 3);
+        assert_eq!(x2.probability("2", &["1", "0"]), 0.1);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let bn = from_uai(NETWORK.as_bytes()).unwrap();
+        let mut out: Vec<u8> = Vec::new();
+        to_uai(&bn, &mut out).unwrap();
+        assert_eq!(from_uai(out.as_slice()).unwrap(), bn);
+    }
+
+    #[test]
+    fn test_from_uai_markov() {
+        let markov = NETWORK.replace("BAYES", "MARKOV");
+        assert!(from_uai(markov.as_bytes()).is_err());
+        let fg = from_uai_markov(markov.as_bytes()).unwrap();
+        assert_eq!(fg.factors().len(), 3);
+        assert_eq!(fg.variable_nodes().len(), 3);
+        let f = fg.factor("phi2").unwrap();
+        let names: Vec<&String> = f.scope().iter().map(|v| v.name()).collect();
+        assert_eq!(names, vec!["x0", "x1", "x2"]);
+        assert_eq!(f.value(&[1, 0, 2]), 0.1);
+        // tables of a bayesian network are factors too
+        let bayes = from_uai_markov(NETWORK.as_bytes()).unwrap();
+        assert_eq!(bayes.factors(), fg.factors());
+    }
+
+    #[test]
+    fn test_markov_round_trip() {
+        let fg = from_uai_markov(NETWORK.replace("BAYES", "MARKOV").as_bytes()).unwrap();
+        let mut out: Vec<u8> = Vec::new();
+        to_uai_markov(&fg, &mut out).unwrap();
+        assert_eq!(
+            from_uai_markov(out.as_slice()).unwrap().factors(),
+            fg.factors()
+        );
+        let scopes = std::collections::HashMap::from([("f".to_string(), ["A".to_string()].into())]);
+        let err = to_uai_markov(&FactorGraph::from_scopes(&scopes), &mut out).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_from_uai_markov_invalid() {
+        let markov = NETWORK.replace("BAYES", "MARKOV");
+        let invalid_data = |text: &str| match from_uai_markov(text.as_bytes()) {
+            Err(e) => e.kind() == io::ErrorKind::InvalidData,
+            Ok(_) => false,
+        };
+        assert!(invalid_data(
+            &markov.replace(" 0.436 0.564", " -0.436 0.564")
+        ));
+        assert!(invalid_data(&markov.replace("2 0 1\n", "2 1 1\n")));
+        assert!(invalid_data(&markov.replace("\n12\n", "\n11\n")));
+        let unused = "MARKOV\n2\n2 2\n1\n1 0\n2 1 1\n";
+        assert!(invalid_data(unused));
+    }
+
+    #[test]
+    fn test_from_uai_invalid() {
+        let markov = NETWORK.replace("BAYES", "MARKOV");
+        assert!(from_uai(markov.as_bytes()).is_err());
+        let short = NETWORK.replace("\n12\n", "\n11\n");
+        assert!(from_uai(short.as_bytes()).is_err());
+        let unknown = NETWORK.replace("2 0 1\n", "2 0 5\n");
+        assert!(from_uai(unknown.as_bytes()).is_err());
+        let invalid_data = |text: &str| match from_uai(text.as_bytes()) {
+            Err(e) => e.kind() == io::ErrorKind::InvalidData,
+            Ok(_) => false,
+        };
+        assert!(invalid_data(
+            &NETWORK.replace(" 0.436 0.564", " 0.436 0.664")
+        ));
+        assert!(invalid_data(
+            &NETWORK.replace(" 0.128 0.872", " -0.128 1.128")
+        ));
+        let repeated = NETWORK.replace("2 0 1\n", "2 1 1\n");
+        assert!(invalid_data(&repeated));
+        let cyclic = "BAYES\n2\n2 2\n2\n2 1 0\n2 0 1\n4 0.5 0.5 0.5 0.5\n4 0.5 0.5 0.5 0.5\n";
+        assert!(invalid_data(cyclic));
+    }
+}
@@ -0,0 +1,383 @@
+//! read and write bayesian networks in the xml bayesian interchange format
+use crate::graph::traits::graph_obj::GraphObject;
+use crate::pgm::bayesnet::BayesianNetwork;
+use crate::pgm::cpd::TabularCPD;
+use crate::pgm::variable::DiscreteVariable;
+use std::collections::HashMap;
+use std::io;
+use std::io::BufRead;
+use std::io::Write;
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
+
+fn unescape(text: &str) -> String {
+    text.trim()
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// remove comments and processing instructions
+fn strip(text: &str) -> io::Result<String> {
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("<!--").into_iter().chain(rest.find("<?")).min() {
+        out.push_str(&rest[..start]);
+        let (close, skip) = if rest[start..].starts_with("<!--") {
+            ("-->", 3)
+        } else {
+            ("?>", 2)
+        };
+        match rest[start..].find(close) {
+            None => return Err(invalid("unterminated comment")),
+            Some(end) => rest = &rest[start + end + skip..],
+        }
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// contents of the successive `tag` elements of `text`. Tag names are
+/// matched regardless of case and elements must not nest in themselves.
+fn elements<'a>(text: &'a str, tag: &str) -> io::Result<Vec<&'a str>> {
+    // ascii lowercasing keeps byte offsets
+    let lower = text.to_ascii_lowercase();
+    let open = format!("<{}", tag.to_ascii_lowercase());
+    let close = format!("</{}>", tag.to_ascii_lowercase());
+    let mut contents = Vec::new();
+    let mut pos = 0;
+    while let Some(i) = lower[pos..].find(&open) {
+        let start = pos + i;
+        let after = start + open.len();
+        // skip longer tag names sharing the prefix
+        match lower[after..].chars().next() {
+            Some(c) if c == '>' || c == '/' || c.is_whitespace() => {}
+            _ => {
+                pos = after;
+                continue;
+            }
+        }
+        let gt = match lower[after..].find('>') {
+            None => return Err(invalid(&format!("unterminated <{tag}> tag"))),
+            Some(j) => after + j,
+        };
+        if lower[..gt].ends_with('/') {
+            contents.push("");
+            pos = gt + 1;
+            continue;
+        }
+        match lower[gt + 1..].find(&close) {
+            None => return Err(invalid(&format!("<{tag}> is not closed"))),
+            Some(j) => {
+                contents.push(&text[gt + 1..gt + 1 + j]);
+                pos = gt + 1 + j + close.len();
+            }
+        }
+    }
+    Ok(contents)
+}
+
+/// text of the single `tag` element of `text`
+fn element_text(text: &str, tag: &str) -> io::Result<String> {
+    match elements(text, tag)?.as_slice() {
+        [content] => Ok(unescape(content)),
+        _ => Err(invalid(&format!("expected a single <{tag}> element"))),
+    }
+}
+
+/// Read a bayesian network in the xml bayesian interchange format
+/// # Description
+/// Reads the `VARIABLE` and `DEFINITION` elements of XMLBIF 0.3, the format
+/// written by Weka, SamIam and pgmpy among others. Only `nature` variables
+/// are supported and properties are ignored. The `TABLE` of a definition
+/// lists a distribution of the variable per parent configuration, parents
+/// being ordered as the `GIVEN` elements with the last one varying fastest.
+/// The network gets the content of the `NAME` element as identifier.
+///
+/// # Errors
+/// Returns an [io::ErrorKind::InvalidData] error if the document is not
+/// valid XMLBIF, if a variable is declared twice or has repeated outcomes,
+/// if a variable has no or several definitions, if a table has the wrong
+/// size or is not a conditional distribution, see [TabularCPD::try_new],
+/// or if the definitions have a directed cycle.
+///
+/// # Example
+/// ```
+/// use pgm_rust::pgm::io::xmlbif::from_xmlbif;
+///
+/// let text = "<BIF VERSION=\"0.3\"><NETWORK><NAME>rain</NAME>
+/// <VARIABLE TYPE=\"nature\"><NAME>rain</NAME><OUTCOME>no</OUTCOME><OUTCOME>yes</OUTCOME></VARIABLE>
+/// <DEFINITION><FOR>rain</FOR><TABLE>0.8 0.2</TABLE></DEFINITION>
+/// </NETWORK></BIF>";
+/// let bn = from_xmlbif(text.as_bytes()).unwrap();
+/// bn.cpd("rain").unwrap().probability("yes", &[]) == 0.2; // true
+/// ```
+///
+/// # References
+/// Cozman F. The Interchange Format for Bayesian Networks. 1998.
+pub fn from_xmlbif<R: BufRead>(mut reader: R) -> io::Result<BayesianNetwork> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    let text = strip(&text)?;
+    let networks = elements(&text, "NETWORK")?;
+    let network = match networks.as_slice() {
+        [network] => *network,
+        _ => return Err(invalid("expected a single <NETWORK> element")),
+    };
+    let variable_blocks = elements(network, "VARIABLE")?;
+    let definition_blocks = elements(network, "DEFINITION")?;
+    // the network name is the name outside of variables
+    let mut header = network;
+    for cut in ["<VARIABLE", "<variable", "<DEFINITION", "<definition"] {
+        if let Some(i) = header.find(cut) {
+            header = &header[..i];
+        }
+    }
+    let name = match elements(header, "NAME")?.first() {
+        None => String::from("network"),
+        Some(n) => unescape(n),
+    };
+    let mut variables: Vec<DiscreteVariable> = Vec::new();
+    for block in variable_blocks {
+        let vname = element_text(block, "NAME")?;
+        let outcomes: Vec<String> = elements(block, "OUTCOME")?
+            .iter()
+            .map(|o| unescape(o))
+            .collect();
+        if outcomes.is_empty() {
+            return Err(invalid(&format!("{vname} has no outcome")));
+        }
+        if variables.iter().any(|v| v.name() == &vname) {
+            return Err(invalid(&format!("{vname} is declared twice")));
+        }
+        let refs: Vec<&str> = outcomes.iter().map(|s| s.as_str()).collect();
+        variables.push(DiscreteVariable::try_new(&vname, &refs).map_err(|msg| invalid(&msg))?);
+    }
+    let by_name: HashMap<&String, &DiscreteVariable> =
+        variables.iter().map(|v| (v.name(), v)).collect();
+    let lookup = |n: &String| -> io::Result<DiscreteVariable> {
+        match by_name.get(n) {
+            None => Err(invalid(&format!("{n} is not declared"))),
+            Some(v) => Ok((*v).clone()),
+        }
+    };
+    let mut arcs: Vec<(String, String)> = Vec::new();
+    let mut cpds: Vec<TabularCPD> = Vec::new();
+    for block in definition_blocks {
+        let vname = element_text(block, "FOR")?;
+        let variable = lookup(&vname)?;
+        if cpds.iter().any(|c| c.variable().name() == &vname) {
+            return Err(invalid(&format!("{vname} has several definitions")));
+        }
+        let parents: Vec<DiscreteVariable> = elements(block, "GIVEN")?
+            .iter()
+            .map(|p| lookup(&unescape(p)))
+            .collect::<io::Result<_>>()?;
+        let values: Vec<f64> = element_text(block, "TABLE")?
+            .split_whitespace()
+            .map(|v| {
+                v.parse::<f64>()
+                    .map_err(|_| invalid(&format!("'{v}' is not a number")))
+            })
+            .collect::<io::Result<_>>()?;
+        let r = variable.cardinality();
+        let nb_rows: usize = parents.iter().map(|p| p.cardinality()).product();
+        if values.len() != r * nb_rows {
+            return Err(invalid(&format!("table of {vname} has the wrong size")));
+        }
+        for p in &parents {
+            arcs.push((p.name().clone(), vname.clone()));
+        }
+        let table: Vec<Vec<f64>> = values.chunks(r).map(|row| row.to_vec()).collect();
+        cpds.push(TabularCPD::try_new(variable, parents, table).map_err(|msg| invalid(&msg))?);
+    }
+    for v in &variables {
+        if !cpds.iter().any(|c| c.variable().name() == v.name()) {
+            return Err(invalid(&format!("{} has no definition", v.name())));
+        }
+    }
+    let names: Vec<&str> = variables.iter().map(|v| v.name().as_str()).collect();
+    let arc_refs: Vec<(&str, &str)> = arcs.iter().map(|(a, b)| (a.as_str(), b.as_str())).collect();
+    let mut bn =
+        BayesianNetwork::try_from_arcs(&name, &names, &arc_refs).map_err(|msg| invalid(&msg))?;
+    for cpd in cpds {
+        bn.add_cpd(cpd);
+    }
+    Ok(bn)
+}
+
+/// Write a bayesian network in the xml bayesian interchange format
+/// # Description
+/// Variables and definitions are written in topological order so that
+/// [from_xmlbif] reads the network back.
+///
+/// # Errors
+/// Returns an [io::ErrorKind::InvalidInput] error if a variable has no
+/// table, since its states are unknown.
+///
+/// # Example
+/// ```
+/// use pgm_rust::pgm::io::xmlbif::{from_xmlbif, to_xmlbif};
+/// use pgm_rust::pgm::bayesnet::BayesianNetwork;
+/// use pgm_rust::pgm::cpd::TabularCPD;
+/// use pgm_rust::pgm::variable::DiscreteVariable;
+///
+/// let mut bn = BayesianNetwork::from_arcs("coin", &["a"], &[]);
+/// bn.add_cpd(TabularCPD::marginal(DiscreteVariable::new("a", &["h", "t"]), vec![0.5, 0.5]));
+/// let mut out: Vec<u8> = Vec::new();
+/// to_xmlbif(&bn, &mut out).unwrap();
+/// from_xmlbif(out.as_slice()).unwrap() == bn; // true
+/// ```
+pub fn to_xmlbif<W: Write>(bn: &BayesianNetwork, writer: &mut W) -> io::Result<()> {
+    if !bn.is_fully_specified() {
+        let msg = format!("{bn} has variables without table");
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, msg));
+    }
+    let order = bn.topological_order();
+    writeln!(writer, "<?xml version=\"1.0\"?>")?;
+    writeln!(writer, "<BIF VERSION=\"0.3\">")?;
+    writeln!(writer, "<NETWORK>")?;
+    writeln!(writer, "<NAME>{}</NAME>", escape(bn.id()))?;
+    for v in &order {
+        let variable = bn.cpd(v).unwrap().variable();
+        writeln!(writer, "<VARIABLE TYPE=\"nature\">")?;
+        writeln!(writer, "  <NAME>{}</NAME>", escape(v))?;
+        for s in variable.states() {
+            writeln!(writer, "  <OUTCOME>{}</OUTCOME>", escape(s))?;
+        }
+        writeln!(writer, "</VARIABLE>")?;
+    }
+    for v in &order {
+        let cpd = bn.cpd(v).unwrap();
+        writeln!(writer, "<DEFINITION>")?;
+        writeln!(writer, "  <FOR>{}</FOR>", escape(v))?;
+        for p in cpd.parents() {
+            writeln!(writer, "  <GIVEN>{}</GIVEN>", escape(p.name()))?;
+        }
+        let rows: Vec<String> = cpd
+            .table()
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|x| x.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect();
+        writeln!(writer, "  <TABLE>{}</TABLE>", rows.join(" "))?;
+        writeln!(writer, "</DEFINITION>")?;
+    }
+    writeln!(writer, "</NETWORK>")?;
+    writeln!(writer, "</BIF>")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::traits::graph::Graph as GraphTrait;
+
+    const SPRINKLER: &str = "<?xml version=\"1.0\"?>
+<!-- the sprinkler network -->
+<BIF VERSION=\"0.3\">
+<NETWORK>
+<NAME>sprinkler</NAME>
+<VARIABLE TYPE=\"nature\">
+  <NAME>rain</NAME>
+  <OUTCOME>no</OUTCOME>
+  <OUTCOME>yes</OUTCOME>
+  <PROPERTY>position = (0, 0)</PROPERTY>
+</VARIABLE>
+<VARIABLE TYPE=\"nature\">
+  <NAME>sprinkler</NAME>
+  <OUTCOME>off</OUTCOME>
+  <OUTCOME>on</OUTCOME>
+</VARIABLE>
+<VARIABLE TYPE=\"nature\">
+  <NAME>wet</NAME>
+  <OUTCOME>no</OUTCOME>
+  <OUTCOME>yes</OUTCOME>
+</VARIABLE>
+<DEFINITION>
+  <FOR>rain</FOR>
+  <TABLE>0.8 0.2</TABLE>
+</DEFINITION>
+<DEFINITION>
+  <FOR>sprinkler</FOR>
+  <GIVEN>rain</GIVEN>
+  <TABLE>0.6 0.4 0.99 0.01</TABLE>
+</DEFINITION>
+<DEFINITION>
+  <FOR>wet</FOR>
+  <GIVEN>sprinkler</GIVEN>
+  <GIVEN>rain</GIVEN>
+  <TABLE>1.0 0.0 0.2 0.8 0.1 0.9 0.01 0.99</TABLE>
+</DEFINITION>
+</NETWORK>
+</BIF>
+";
+
+    #[test]
+    fn test_from_xmlbif() {
+        let bn = from_xmlbif(SPRINKLER.as_bytes()).unwrap();
+        assert_eq!(bn.id(), "sprinkler");
+        assert_eq!(bn.order(), 3);
+        assert_eq!(bn.size(), 3);
+        let wet = bn.cpd("wet").unwrap();
+        assert_eq!(wet.probability("yes", &["off", "yes"]), 0.8);
+        assert_eq!(wet.probability("yes", &["on", "no"]), 0.9);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let bn = from_xmlbif(SPRINKLER.as_bytes()).unwrap();
+        let mut out: Vec<u8> = Vec::new();
+        to_xmlbif(&bn, &mut out).unwrap();
+        assert_eq!(from_xmlbif(out.as_slice()).unwrap(), bn);
+    }
+
+    #[test]
+    fn test_from_xmlbif_invalid() {
+        let wrong_size = SPRINKLER.replace("0.8 0.2", "0.8 0.2 0.0");
+        assert!(from_xmlbif(wrong_size.as_bytes()).is_err());
+        let undeclared = SPRINKLER.replace(
+            "<GIVEN>rain</GIVEN>\n  <TABLE>0.6",
+            "<GIVEN>cloudy</GIVEN>\n  <TABLE>0.6",
+        );
+        assert!(from_xmlbif(undeclared.as_bytes()).is_err());
+        let unclosed = SPRINKLER.replace("</NETWORK>", "");
+        assert!(from_xmlbif(unclosed.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_from_xmlbif_invalid_tables() {
+        let invalid_data = |text: &str| match from_xmlbif(text.as_bytes()) {
+            Err(e) => e.kind() == io::ErrorKind::InvalidData,
+            Ok(_) => false,
+        };
+        assert!(invalid_data(&SPRINKLER.replace("0.8 0.2", "0.8 0.3")));
+        assert!(invalid_data(&SPRINKLER.replace("0.8 0.2", "1.2 -0.2")));
+        let repeated_outcome = SPRINKLER.replace("<OUTCOME>on</OUTCOME>", "<OUTCOME>off</OUTCOME>");
+        assert!(invalid_data(&repeated_outcome));
+        let cyclic = SPRINKLER.replace(
+            "<FOR>rain</FOR>\n  <TABLE>0.8 0.2</TABLE>",
+            "<FOR>rain</FOR>\n  <GIVEN>wet</GIVEN>\n  <TABLE>0.8 0.2 0.8 0.2</TABLE>",
+        );
+        assert!(invalid_data(&cyclic));
+        let twice = SPRINKLER.replace(
+            "<DEFINITION>\n  <FOR>sprinkler</FOR>",
+            "<DEFINITION>\n  <FOR>rain</FOR>\n  <TABLE>0.5 0.5</TABLE>\n</DEFINITION>\n<DEFINITION>\n  <FOR>sprinkler</FOR>",
+        );
+        assert!(invalid_data(&twice));
+    }
+}
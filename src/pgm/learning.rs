@@ -0,0 +1,1169 @@
+//! learning models from data
+use crate::graph::ops::graph::search::dfs;
+use crate::graph::ops::graph::search::EdgeNeighborhood;
+use crate::graph::ops::graph::spanning::maximum_spanning_tree;
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::graph_obj::GraphObject;
+use crate::graph::traits::idgen::IdGenerator;
+use crate::graph::types::edge::Edge;
+use crate::graph::types::edgetype::EdgeType;
+use crate::graph::types::graph::Graph;
+use crate::graph::types::idgen::UuidGenerator;
+use crate::graph::types::node::Node;
+use crate::pgm::bayesnet::BayesianNetwork;
+use crate::pgm::cpd::TabularCPD;
+use crate::pgm::data::DataSet;
+use crate::pgm::learning::pdag::Pdag;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+
+/// partially directed acyclic graphs
+pub mod pdag;
+
+/// Empirical mutual information between two columns
+/// # Description
+/// Computes `I(X; Y) = sum p(x, y) log(p(x, y) / (p(x) p(y)))` in nats from
+/// the relative frequencies of `data`. It is zero for an empty data set.
+///
+/// # Example
+/// ```
+/// use pgm_rust::pgm::data::DataSet;
+/// use pgm_rust::pgm::learning::mutual_information;
+/// use pgm_rust::pgm::variable::DiscreteVariable;
+///
+/// let a = DiscreteVariable::new("A", &["0", "1"]);
+/// let b = DiscreteVariable::new("B", &["0", "1"]);
+/// let data = DataSet::new(vec![a, b], vec![vec![0, 0], vec![1, 1]]);
+/// (mutual_information(&data, "A", "B") - 2f64.ln()).abs() < 1e-12; // true
+/// ```
+pub fn mutual_information(data: &DataSet, x: &str, y: &str) -> f64 {
+    let n = data.nb_rows() as f64;
+    if n == 0.0 {
+        return 0.0;
+    }
+    let cx = data.counts(&[x]);
+    let cy = data.counts(&[y]);
+    let cxy = data.counts(&[x, y]);
+    let mut mi = 0.0;
+    for (i, nx) in cx.iter().enumerate() {
+        for (j, ny) in cy.iter().enumerate() {
+            let nxy = cxy[i * cy.len() + j];
+            if nxy > 0 {
+                let nxy = nxy as f64;
+                mi += nxy / n * (nxy * n / (*nx as f64 * *ny as f64)).ln();
+            }
+        }
+    }
+    mi
+}
+
+/// Learn a tree shaped bayesian network with the Chow-Liu algorithm
+/// # Description
+/// The tree maximizing the likelihood of `data` among tree shaped networks
+/// is a maximum spanning tree of the complete graph over `variables`
+/// weighted by pairwise [mutual_information]. The tree is oriented away
+/// from the first variable. The resulting network has no tables and a
+/// random identifier, see [chow_liu_with].
+///
+/// # Args
+/// - data: discrete observations
+/// - variables: columns of `data` to include, the first one is the root
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::traits::graph_obj::GraphObject;
+/// use pgm_rust::pgm::data::DataSet;
+/// use pgm_rust::pgm::learning::chow_liu;
+/// use pgm_rust::pgm::variable::DiscreteVariable;
+///
+/// let vs: Vec<DiscreteVariable> = ["A", "B", "C"].iter()
+///     .map(|n| DiscreteVariable::new(n, &["0", "1"])).collect();
+/// let rows = vec![vec![0, 0, 0], vec![1, 1, 0], vec![1, 1, 1], vec![0, 0, 1]];
+/// let bn = chow_liu(&DataSet::new(vs, rows), &["A", "B", "C"]);
+/// bn.parents("B")[0].id() == "A"; // true
+/// ```
+///
+/// # References
+/// Chow C, Liu C. Approximating discrete probability distributions with
+/// dependence trees. 1968.
+pub fn chow_liu(data: &DataSet, variables: &[&str]) -> BayesianNetwork {
+    chow_liu_with(data, variables, &mut UuidGenerator)
+}
+
+/// Learn a tree shaped bayesian network with the Chow-Liu algorithm, the
+/// identifier of the network is produced by `idgen`, see [chow_liu]
+pub fn chow_liu_with<I: IdGenerator>(
+    data: &DataSet,
+    variables: &[&str],
+    idgen: &mut I,
+) -> BayesianNetwork {
+    if variables.is_empty() {
+        panic!("chow_liu needs at least one variable");
+    }
+    for (i, v) in variables.iter().enumerate() {
+        if data.column_index(v).is_none() {
+            panic!("{v} is not a column of the data set");
+        }
+        if variables[..i].contains(v) {
+            panic!("{v} is given more than once");
+        }
+    }
+    let nodes: HashSet<Node> = variables.iter().map(|v| Node::empty(v)).collect();
+    let mut edges: HashSet<Edge<Node>> = HashSet::new();
+    let mut weights: HashMap<String, f64> = HashMap::new();
+    for (i, x) in variables.iter().enumerate() {
+        for y in &variables[i + 1..] {
+            let eid = format!("{x}--{y}");
+            weights.insert(eid.clone(), mutual_information(data, x, y));
+            edges.insert(Edge::empty(&eid, EdgeType::Undirected, x, y));
+        }
+    }
+    let complete = Graph::new("complete".to_string(), HashMap::new(), nodes, edges);
+    let tree = maximum_spanning_tree(&complete, |e: &Edge<Node>| weights[e.id()]);
+
+    // orient the tree away from the root
+    let mut adjacency: HashMap<&String, Vec<&String>> = HashMap::new();
+    for e in tree.edges_sorted() {
+        adjacency
+            .entry(e.start().id())
+            .or_default()
+            .push(e.end().id());
+        adjacency
+            .entry(e.end().id())
+            .or_default()
+            .push(e.start().id());
+    }
+    let root = variables[0].to_string();
+    let mut visited: HashSet<&String> = HashSet::from([&root]);
+    let mut queue: VecDeque<&String> = VecDeque::from([&root]);
+    let mut arcs: HashSet<Edge<Node>> = HashSet::new();
+    while let Some(u) = queue.pop_front() {
+        for v in adjacency.get(u).into_iter().flatten() {
+            if visited.insert(v) {
+                arcs.insert(Edge::empty(&format!("{u}->{v}"), EdgeType::Directed, u, v));
+                queue.push_back(v);
+            }
+        }
+    }
+    let vertices: HashSet<Node> = tree.vertices().into_iter().cloned().collect();
+    let gid = idgen.generate(&vertices.iter().collect(), &arcs.iter().collect());
+    BayesianNetwork::new(Graph::new(gid, HashMap::new(), vertices, arcs))
+}
+
+/// natural logarithm of the gamma function for positive arguments, Lanczos
+/// approximation with g = 7
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x < 0.5 {
+        // reflection formula
+        let pi = std::f64::consts::PI;
+        return (pi / (pi * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+    let x = x - 1.0;
+    let mut a = COEFFICIENTS[0];
+    let t = x + 7.5;
+    for (i, c) in COEFFICIENTS.iter().enumerate().skip(1) {
+        a += c / (x + i as f64);
+    }
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + a.ln()
+}
+
+/// scoring functions of network structures given data
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Score {
+    /// Bayesian information criterion, log likelihood penalized by half the
+    /// number of free parameters times the log of the number of rows
+    Bic,
+    /// Bayesian Dirichlet equivalent uniform score, log marginal
+    /// likelihood under a uniform Dirichlet prior
+    BDeu {
+        /// total pseudo count of the prior
+        equivalent_sample_size: f64,
+    },
+}
+
+/// Score a variable given its parents
+/// # Description
+/// Scores of decomposable functions such as [Score::Bic] and
+/// [Score::BDeu] are sums of the local scores of the variables, see
+/// [network_score]. Higher is better.
+///
+/// # Example
+/// ```
+/// use pgm_rust::pgm::data::DataSet;
+/// use pgm_rust::pgm::learning::{local_score, Score};
+/// use pgm_rust::pgm::variable::DiscreteVariable;
+///
+/// let a = DiscreteVariable::new("A", &["0", "1"]);
+/// let b = DiscreteVariable::new("B", &["0", "1"]);
+/// let data = DataSet::new(vec![a, b], vec![vec![0, 0], vec![1, 1], vec![0, 0], vec![1, 1]]);
+/// local_score(&data, "B", &["A"], Score::Bic) > local_score(&data, "B", &[], Score::Bic); // true
+/// ```
+///
+/// # References
+/// Koller D, Friedman N. Probabilistic Graphical Models. 2009, p. 790-808.
+pub fn local_score(data: &DataSet, variable: &str, parents: &[&str], score: Score) -> f64 {
+    let mut columns: Vec<&str> = parents.to_vec();
+    columns.push(variable);
+    let counts = data.counts(&columns);
+    let r = match data.variable(variable) {
+        None => panic!("{variable} is not a column of the data set"),
+        Some(v) => v.cardinality(),
+    };
+    let q = counts.len() / r;
+    match score {
+        Score::Bic => {
+            let mut ll = 0.0;
+            for row in counts.chunks(r) {
+                let nj: usize = row.iter().sum();
+                for njk in row.iter().filter(|c| **c > 0) {
+                    ll += *njk as f64 * (*njk as f64 / nj as f64).ln();
+                }
+            }
+            let n = data.nb_rows().max(1) as f64;
+            ll - 0.5 * n.ln() * (q * (r - 1)) as f64
+        }
+        Score::BDeu {
+            equivalent_sample_size,
+        } => {
+            let aj = equivalent_sample_size / q as f64;
+            let ajk = aj / r as f64;
+            let mut s = 0.0;
+            for row in counts.chunks(r) {
+                let nj: usize = row.iter().sum();
+                s += ln_gamma(aj) - ln_gamma(aj + nj as f64);
+                for njk in row {
+                    s += ln_gamma(ajk + *njk as f64) - ln_gamma(ajk);
+                }
+            }
+            s
+        }
+    }
+}
+
+/// Score the structure of a network given data, sum of the
+/// [local_score] of its variables
+pub fn network_score(data: &DataSet, bn: &BayesianNetwork, score: Score) -> f64 {
+    bn.topological_order()
+        .into_iter()
+        .map(|v| {
+            let parents: Vec<&str> = bn.parents(v).iter().map(|p| p.id().as_str()).collect();
+            local_score(data, v, &parents, score)
+        })
+        .sum()
+}
+
+/// restrictions on the arcs of learned structures
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StructureConstraints {
+    /// arcs `(from, to)` that must be in the structure
+    pub whitelist: HashSet<(String, String)>,
+    /// arcs `(from, to)` that must not be in the structure
+    pub blacklist: HashSet<(String, String)>,
+    /// largest number of parents of a variable
+    pub max_parents: Option<usize>,
+}
+
+impl StructureConstraints {
+    fn allows(&self, from: &str, to: &str) -> bool {
+        !self.blacklist.contains(&(from.to_string(), to.to_string()))
+    }
+
+    fn requires(&self, from: &str, to: &str) -> bool {
+        self.whitelist.contains(&(from.to_string(), to.to_string()))
+    }
+
+    fn has_room(&self, nb_parents: usize) -> bool {
+        self.max_parents.is_none_or(|m| nb_parents < m)
+    }
+}
+
+/// network of the given parent sets, with an identifier produced by
+/// `idgen`
+fn network_from_parents<I: IdGenerator>(
+    parents: &BTreeMap<String, BTreeSet<String>>,
+    idgen: &mut I,
+) -> BayesianNetwork {
+    let graph = graph_from_parents(parents, None);
+    let vertices: HashSet<&Node> = graph.vertices();
+    let edges: HashSet<&Edge<Node>> = graph.edges();
+    let gid = idgen.generate(&vertices, &edges);
+    BayesianNetwork::new(Graph::new_refs(gid, HashMap::new(), vertices, edges))
+}
+
+/// directed graph of the given parent sets, without the arc `skip`
+fn graph_from_parents(
+    parents: &BTreeMap<String, BTreeSet<String>>,
+    skip: Option<(&str, &str)>,
+) -> Graph<Node, Edge<Node>> {
+    let nodes: HashSet<Node> = parents.keys().map(|v| Node::empty(v)).collect();
+    let mut edges: HashSet<Edge<Node>> = HashSet::new();
+    for (v, ps) in parents {
+        for p in ps {
+            if skip != Some((p.as_str(), v.as_str())) {
+                edges.insert(Edge::empty(&format!("{p}->{v}"), EdgeType::Directed, p, v));
+            }
+        }
+    }
+    Graph::new("structure".to_string(), HashMap::new(), nodes, edges)
+}
+
+/// `to` can be reached from `from` following arcs, the arc `skip` excepted
+fn has_directed_path(
+    parents: &BTreeMap<String, BTreeSet<String>>,
+    from: &str,
+    to: &str,
+    skip: Option<(&str, &str)>,
+) -> bool {
+    let g = graph_from_parents(parents, skip);
+    let result = dfs(&g, &Node::empty(from), &EdgeNeighborhood::Out, false);
+    result.components[from].contains(to)
+}
+
+/// moves of the hill climbing search
+#[derive(Debug, Clone, PartialEq)]
+enum Move {
+    Add(String, String),
+    Remove(String, String),
+    Reverse(String, String),
+}
+
+/// Learn the structure of a bayesian network by hill climbing
+/// # Description
+/// Starts from the network made of the whitelisted arcs over every column
+/// of `data`. At each step, applies the arc addition, removal or reversal
+/// that improves `scoring` the most while keeping the graph acyclic and
+/// satisfying `constraints`. Stops when no move improves the score. Moves
+/// are enumerated in variable name order and ties keep the first move, so
+/// the output is reproducible. The resulting network has no tables and a
+/// random identifier, see [hill_climb_with].
+///
+/// # Args
+/// - data: discrete observations
+/// - scoring: decomposable score, see [local_score]
+/// - constraints: white and black lists of arcs and maximum in-degree
+///
+/// # Example
+/// ```
+/// use pgm_rust::pgm::data::DataSet;
+/// use pgm_rust::pgm::learning::{hill_climb, Score, StructureConstraints};
+/// use pgm_rust::pgm::variable::DiscreteVariable;
+///
+/// let a = DiscreteVariable::new("A", &["0", "1"]);
+/// let b = DiscreteVariable::new("B", &["0", "1"]);
+/// let rows: Vec<Vec<usize>> = (0..20).map(|i| vec![i % 2, i % 2]).collect();
+/// let bn = hill_climb(&DataSet::new(vec![a, b], rows), Score::Bic, &StructureConstraints::default());
+/// bn.parents("B").len() + bn.parents("A").len() == 1; // true
+/// ```
+///
+/// # References
+/// Koller D, Friedman N. Probabilistic Graphical Models. 2009, p. 813.
+pub fn hill_climb(
+    data: &DataSet,
+    scoring: Score,
+    constraints: &StructureConstraints,
+) -> BayesianNetwork {
+    hill_climb_with(data, scoring, constraints, &mut UuidGenerator)
+}
+
+/// Learn the structure of a bayesian network by hill climbing, the
+/// identifier of the network is produced by `idgen`, see [hill_climb]
+pub fn hill_climb_with<I: IdGenerator>(
+    data: &DataSet,
+    scoring: Score,
+    constraints: &StructureConstraints,
+    idgen: &mut I,
+) -> BayesianNetwork {
+    let mut parents: BTreeMap<String, BTreeSet<String>> = data
+        .variables()
+        .iter()
+        .map(|v| (v.name().clone(), BTreeSet::new()))
+        .collect();
+    for (from, to) in &constraints.whitelist {
+        if !parents.contains_key(from) || !parents.contains_key(to) {
+            panic!("whitelisted arc {from}->{to} is not between columns");
+        }
+        if !constraints.allows(from, to) {
+            panic!("arc {from}->{to} is both white and black listed");
+        }
+        parents.get_mut(to).unwrap().insert(from.clone());
+    }
+    // panics if the whitelist has a cycle
+    BayesianNetwork::new(graph_from_parents(&parents, None));
+
+    let score_of = |v: &str, ps: &BTreeSet<String>| -> f64 {
+        let ps: Vec<&str> = ps.iter().map(|p| p.as_str()).collect();
+        local_score(data, v, &ps, scoring)
+    };
+    let mut scores: BTreeMap<String, f64> = parents
+        .iter()
+        .map(|(v, ps)| (v.clone(), score_of(v, ps)))
+        .collect();
+    let names: Vec<String> = parents.keys().cloned().collect();
+    loop {
+        let mut best: Option<(f64, Move)> = None;
+        let mut consider = |delta: f64, m: Move| {
+            if delta > 1e-10 && best.as_ref().is_none_or(|(d, _)| delta > *d) {
+                best = Some((delta, m));
+            }
+        };
+        for x in &names {
+            for y in &names {
+                if x == y {
+                    continue;
+                }
+                let present = parents[y].contains(x);
+                if !present {
+                    let allowed =
+                        constraints.allows(x, y) && constraints.has_room(parents[y].len());
+                    if allowed
+                        && !parents[x].contains(y)
+                        && !has_directed_path(&parents, y, x, None)
+                    {
+                        let mut ps = parents[y].clone();
+                        ps.insert(x.clone());
+                        consider(
+                            score_of(y, &ps) - scores[y],
+                            Move::Add(x.clone(), y.clone()),
+                        );
+                    }
+                    continue;
+                }
+                if constraints.requires(x, y) {
+                    continue;
+                }
+                let mut ps_y = parents[y].clone();
+                ps_y.remove(x);
+                let removal = score_of(y, &ps_y) - scores[y];
+                consider(removal, Move::Remove(x.clone(), y.clone()));
+                let reversible = constraints.allows(y, x) && constraints.has_room(parents[x].len());
+                if reversible && !has_directed_path(&parents, x, y, Some((x, y))) {
+                    let mut ps_x = parents[x].clone();
+                    ps_x.insert(y.clone());
+                    let delta = removal + score_of(x, &ps_x) - scores[x];
+                    consider(delta, Move::Reverse(x.clone(), y.clone()));
+                }
+            }
+        }
+        let m = match best {
+            None => break,
+            Some((_, m)) => m,
+        };
+        let changed: Vec<String> = match m {
+            Move::Add(x, y) => {
+                parents.get_mut(&y).unwrap().insert(x);
+                vec![y]
+            }
+            Move::Remove(x, y) => {
+                parents.get_mut(&y).unwrap().remove(&x);
+                vec![y]
+            }
+            Move::Reverse(x, y) => {
+                parents.get_mut(&y).unwrap().remove(&x);
+                parents.get_mut(&x).unwrap().insert(y.clone());
+                vec![x, y]
+            }
+        };
+        for v in changed {
+            let s = score_of(&v, &parents[&v]);
+            scores.insert(v, s);
+        }
+    }
+    network_from_parents(&parents, idgen)
+}
+
+/// upper regularized incomplete gamma function `Q(a, x)`, by its series
+/// for small `x` and by its continued fraction otherwise
+fn regularized_gamma_q(a: f64, x: f64) -> f64 {
+    const EPS: f64 = 1e-14;
+    const TINY: f64 = 1e-300;
+    if x <= 0.0 {
+        return 1.0;
+    }
+    let prefactor = (-x + a * x.ln() - ln_gamma(a)).exp();
+    if x < a + 1.0 {
+        let mut ap = a;
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        for _ in 0..1000 {
+            ap += 1.0;
+            term *= x / ap;
+            sum += term;
+            if term.abs() < sum.abs() * EPS {
+                break;
+            }
+        }
+        (1.0 - sum * prefactor).max(0.0)
+    } else {
+        // modified Lentz's method
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / TINY;
+        let mut d = 1.0 / b;
+        let mut h = d;
+        for i in 1..1000 {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.0;
+            d = an * d + b;
+            if d.abs() < TINY {
+                d = TINY;
+            }
+            c = b + an / c;
+            if c.abs() < TINY {
+                c = TINY;
+            }
+            d = 1.0 / d;
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1.0).abs() < EPS {
+                break;
+            }
+        }
+        prefactor * h
+    }
+}
+
+/// conditional independence tests of discrete data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndependenceTest {
+    /// Pearson's chi-square statistic
+    ChiSquare,
+    /// likelihood ratio statistic, also known as G-test
+    GTest,
+}
+
+/// outcome of a conditional independence test, see [ci_test]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IndependenceResult {
+    /// value of the test statistic
+    pub statistic: f64,
+    /// degrees of freedom of the chi-square reference distribution
+    pub dof: usize,
+    /// probability of a statistic at least as large under independence
+    pub p_value: f64,
+}
+
+/// Test whether two columns are independent given other columns
+/// # Description
+/// The statistic is summed over the configurations of `z`, expected counts
+/// coming from the marginals of each configuration. Degrees of freedom
+/// only account for the states observed in each configuration. When they
+/// are zero there is no evidence of dependence and the p-value is one.
+///
+/// # Args
+/// - data: discrete observations
+/// - x: first column
+/// - y: second column
+/// - z: conditioning columns
+/// - test: statistic to use
+///
+/// # Example
+/// ```
+/// use pgm_rust::pgm::data::DataSet;
+/// use pgm_rust::pgm::learning::{ci_test, IndependenceTest};
+/// use pgm_rust::pgm::variable::DiscreteVariable;
+///
+/// let a = DiscreteVariable::new("A", &["0", "1"]);
+/// let b = DiscreteVariable::new("B", &["0", "1"]);
+/// let rows: Vec<Vec<usize>> = (0..40).map(|i| vec![i % 2, i % 2]).collect();
+/// let r = ci_test(&DataSet::new(vec![a, b], rows), "A", "B", &[], IndependenceTest::ChiSquare);
+/// r.p_value < 0.05; // true
+/// ```
+///
+/// # References
+/// Agresti A. Categorical Data Analysis. 2002, p. 78.
+pub fn ci_test(
+    data: &DataSet,
+    x: &str,
+    y: &str,
+    z: &[&str],
+    test: IndependenceTest,
+) -> IndependenceResult {
+    let mut columns: Vec<&str> = z.to_vec();
+    columns.push(x);
+    columns.push(y);
+    let counts = data.counts(&columns);
+    let cardinality = |n: &str| data.variable(n).unwrap().cardinality();
+    let (rx, ry) = (cardinality(x), cardinality(y));
+    let mut statistic = 0.0;
+    let mut dof = 0;
+    for table in counts.chunks(rx * ry) {
+        let row_sums: Vec<usize> = table.chunks(ry).map(|r| r.iter().sum()).collect();
+        let col_sums: Vec<usize> = (0..ry)
+            .map(|j| (0..rx).map(|i| table[i * ry + j]).sum())
+            .collect();
+        let n: usize = row_sums.iter().sum();
+        if n == 0 {
+            continue;
+        }
+        for (i, ni) in row_sums.iter().enumerate() {
+            for (j, nj) in col_sums.iter().enumerate() {
+                let expected = (*ni * *nj) as f64 / n as f64;
+                if expected == 0.0 {
+                    continue;
+                }
+                let observed = table[i * ry + j] as f64;
+                statistic += match test {
+                    IndependenceTest::ChiSquare => (observed - expected).powi(2) / expected,
+                    IndependenceTest::GTest if observed > 0.0 => {
+                        2.0 * observed * (observed / expected).ln()
+                    }
+                    IndependenceTest::GTest => 0.0,
+                };
+            }
+        }
+        let nonzero = |sums: &[usize]| sums.iter().filter(|s| **s > 0).count();
+        dof += (nonzero(&row_sums) - 1) * (nonzero(&col_sums) - 1);
+    }
+    let p_value = if dof == 0 {
+        1.0
+    } else {
+        regularized_gamma_q(dof as f64 / 2.0, statistic / 2.0)
+    };
+    IndependenceResult {
+        statistic,
+        dof,
+        p_value,
+    }
+}
+
+/// subsets of `items` of size `k` in lexicographic order
+fn combinations<'a>(items: &[&'a String], k: usize) -> Vec<Vec<&'a String>> {
+    if k == 0 {
+        return vec![Vec::new()];
+    }
+    let mut result = Vec::new();
+    for i in 0..items.len() {
+        for mut rest in combinations(&items[i + 1..], k - 1) {
+            rest.insert(0, items[i]);
+            result.push(rest);
+        }
+    }
+    result
+}
+
+/// Learn the equivalence class of a bayesian network with the PC algorithm
+/// # Description
+/// Starting from the complete graph over the columns of `data`, removes the
+/// edge between two variables as soon as they are found independent given
+/// a subset of the neighbors of one of them, with subsets of growing size.
+/// Neighbor sets are frozen at each size, as in the order independent
+/// PC-stable variant. Unshielded triples `x - z - y` whose middle vertex is
+/// not in the separating set of `x` and `y` are oriented as v-structures,
+/// then Meek's rules orient the edges whose direction is implied.
+///
+/// # Args
+/// - data: discrete observations
+/// - independence_test: conditional independence test, see [ci_test]
+/// - alpha: significance level, variables are independent when the p-value
+///   is above it
+/// - returns: a completed partially directed acyclic graph. Arcs have
+///   identifiers `x->y`, undirected edges `x--y` with `x < y`. The graph
+///   has a random identifier, see [pc_with].
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::traits::graph::Graph;
+/// use pgm_rust::pgm::data::DataSet;
+/// use pgm_rust::pgm::learning::{pc, IndependenceTest};
+/// use pgm_rust::pgm::variable::DiscreteVariable;
+///
+/// let a = DiscreteVariable::new("A", &["0", "1"]);
+/// let b = DiscreteVariable::new("B", &["0", "1"]);
+/// let rows: Vec<Vec<usize>> = (0..40).map(|i| vec![i % 2, i % 2]).collect();
+/// let cpdag = pc(&DataSet::new(vec![a, b], rows), IndependenceTest::GTest, 0.05);
+/// cpdag.size() == 1; // true: A--B
+/// ```
+///
+/// # References
+/// Spirtes P, Glymour C, Scheines R. Causation, Prediction, and Search.
+/// 2000, p. 84.
+///
+/// Colombo D, Maathuis M. Order-independent constraint-based causal
+/// structure learning. 2014.
+///
+/// Meek C. Causal inference and causal explanation with background
+/// knowledge. 1995.
+pub fn pc(
+    data: &DataSet,
+    independence_test: IndependenceTest,
+    alpha: f64,
+) -> Graph<Node, Edge<Node>> {
+    pc_with(data, independence_test, alpha, &mut UuidGenerator)
+}
+
+/// Learn the equivalence class of a bayesian network with the PC
+/// algorithm, the identifier of the graph is produced by `idgen`, see [pc]
+pub fn pc_with<I: IdGenerator>(
+    data: &DataSet,
+    independence_test: IndependenceTest,
+    alpha: f64,
+    idgen: &mut I,
+) -> Graph<Node, Edge<Node>> {
+    let names: Vec<String> = data.variables().iter().map(|v| v.name().clone()).collect();
+    let mut adjacent: BTreeMap<String, BTreeSet<String>> = names
+        .iter()
+        .map(|x| {
+            (
+                x.clone(),
+                names.iter().filter(|y| *y != x).cloned().collect(),
+            )
+        })
+        .collect();
+    let mut sepsets: HashMap<(String, String), Vec<String>> = HashMap::new();
+
+    // skeleton
+    let mut level = 0;
+    loop {
+        let frozen = adjacent.clone();
+        let mut testable = false;
+        for x in &names {
+            for y in &frozen[x] {
+                if !adjacent[x].contains(y) {
+                    continue;
+                }
+                let candidates: Vec<&String> = frozen[x].iter().filter(|v| *v != y).collect();
+                if candidates.len() < level {
+                    continue;
+                }
+                testable = true;
+                for s in combinations(&candidates, level) {
+                    let z: Vec<&str> = s.iter().map(|v| v.as_str()).collect();
+                    if ci_test(data, x, y, &z, independence_test).p_value > alpha {
+                        adjacent.get_mut(x).unwrap().remove(y);
+                        adjacent.get_mut(y).unwrap().remove(x);
+                        let sepset: Vec<String> = s.into_iter().cloned().collect();
+                        sepsets.insert((x.clone(), y.clone()), sepset.clone());
+                        sepsets.insert((y.clone(), x.clone()), sepset);
+                        break;
+                    }
+                }
+            }
+        }
+        if !testable {
+            break;
+        }
+        level += 1;
+    }
+
+    // v-structures
+    let mut pattern = Pdag::new(&names.iter().map(|v| v.as_str()).collect::<Vec<_>>());
+    for (x, ys) in &adjacent {
+        for y in ys {
+            if x < y {
+                pattern.add_undirected(x, y);
+            }
+        }
+    }
+    for z in &names {
+        let neighbors: Vec<String> = adjacent[z].iter().cloned().collect();
+        for (i, x) in neighbors.iter().enumerate() {
+            for y in &neighbors[i + 1..] {
+                if pattern.is_adjacent(x, y) {
+                    continue;
+                }
+                let separated = sepsets
+                    .get(&(x.clone(), y.clone()))
+                    .is_some_and(|s| s.contains(z));
+                if !separated {
+                    pattern.orient(x, z);
+                    pattern.orient(y, z);
+                }
+            }
+        }
+    }
+    pattern.apply_meek_rules();
+    pattern.to_graph_with(idgen)
+}
+
+/// tables of the variables of `bn` from pseudo counts added to the counts
+/// of `data`
+fn fit_with_prior(bn: &BayesianNetwork, data: &DataSet, prior_count: f64) -> BayesianNetwork {
+    let column = |name: &str| match data.variable(name) {
+        None => panic!("{name} of {bn} is not a column of the data set"),
+        Some(v) => v.clone(),
+    };
+    let mut fitted = bn.clone();
+    for v in bn.topological_order() {
+        let parents: Vec<&str> = bn.parents(v).iter().map(|p| p.id().as_str()).collect();
+        let mut columns = parents.clone();
+        columns.push(v);
+        let variable = column(v);
+        let r = variable.cardinality();
+        let table: Vec<Vec<f64>> = data
+            .counts(&columns)
+            .chunks(r)
+            .map(|row| {
+                let total = row.iter().sum::<usize>() as f64 + prior_count * r as f64;
+                if total == 0.0 {
+                    // unobserved parent configuration
+                    vec![1.0 / r as f64; r]
+                } else {
+                    row.iter()
+                        .map(|c| (*c as f64 + prior_count) / total)
+                        .collect()
+                }
+            })
+            .collect();
+        let parent_variables = parents.iter().map(|p| column(p)).collect();
+        fitted.add_cpd(TabularCPD::new(variable, parent_variables, table));
+    }
+    fitted
+}
+
+/// Estimate the tables of a network by maximum likelihood
+/// # Description
+/// Every table is filled with the relative frequencies of the states of
+/// its variable for each configuration of its parents in `data`. Parents
+/// are ordered by identifier. Configurations that never occur get a uniform
+/// distribution. Existing tables are replaced.
+///
+/// # Args
+/// - bn_structure: network whose variables are columns of `data`
+/// - data: discrete observations
+///
+/// # Example
+/// ```
+/// use pgm_rust::pgm::bayesnet::BayesianNetwork;
+/// use pgm_rust::pgm::data::DataSet;
+/// use pgm_rust::pgm::learning::fit_mle;
+/// use pgm_rust::pgm::variable::DiscreteVariable;
+///
+/// let a = DiscreteVariable::new("A", &["0", "1"]);
+/// let b = DiscreteVariable::new("B", &["0", "1"]);
+/// let data = DataSet::new(vec![a, b], vec![vec![0, 0], vec![0, 1], vec![1, 1], vec![0, 0]]);
+/// let bn = fit_mle(&BayesianNetwork::from_arcs("bn", &["A", "B"], &[("A", "B")]), &data);
+/// bn.cpd("A").unwrap().probability("0", &[]) == 0.75; // true
+/// ```
+///
+/// # References
+/// Koller D, Friedman N. Probabilistic Graphical Models. 2009, p. 727.
+pub fn fit_mle(bn_structure: &BayesianNetwork, data: &DataSet) -> BayesianNetwork {
+    fit_with_prior(bn_structure, data, 0.0)
+}
+
+/// Estimate the tables of a network by their posterior mean
+/// # Description
+/// Uses a symmetric Dirichlet prior over each distribution, that is
+/// `prior_counts` is added to the count of every state before normalizing.
+/// A prior count of 1 gives Laplace smoothing, 0 gives [fit_mle].
+///
+/// # Args
+/// - bn_structure: network whose variables are columns of `data`
+/// - data: discrete observations
+/// - prior_counts: pseudo count of every state, non negative
+///
+/// # References
+/// Koller D, Friedman N. Probabilistic Graphical Models. 2009, p. 741.
+pub fn fit_bayesian(
+    bn_structure: &BayesianNetwork,
+    data: &DataSet,
+    prior_counts: f64,
+) -> BayesianNetwork {
+    if prior_counts.is_nan() || prior_counts < 0.0 {
+        panic!("prior counts must be non negative, got {prior_counts}");
+    }
+    fit_with_prior(bn_structure, data, prior_counts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::types::idgen::SequentialIdGenerator;
+    use crate::pgm::variable::DiscreteVariable;
+
+    fn mk_data() -> DataSet {
+        // B copies A, C copies B except in two rows, D is independent noise
+        let vs: Vec<DiscreteVariable> = ["A", "B", "C", "D"]
+            .iter()
+            .map(|n| DiscreteVariable::new(n, &["0", "1"]))
+            .collect();
+        let rows = vec![
+            vec![0, 0, 0, 0],
+            vec![0, 0, 0, 1],
+            vec![1, 1, 1, 0],
+            vec![1, 1, 1, 1],
+            vec![0, 0, 1, 0],
+            vec![1, 1, 0, 1],
+            vec![0, 0, 0, 1],
+            vec![1, 1, 1, 0],
+        ];
+        DataSet::new(vs, rows)
+    }
+
+    #[test]
+    fn test_mutual_information() {
+        let d = mk_data();
+        assert!((mutual_information(&d, "A", "B") - 2f64.ln()).abs() < 1e-12);
+        assert!(mutual_information(&d, "A", "D").abs() < 1e-12);
+        assert!(mutual_information(&d, "B", "C") > mutual_information(&d, "C", "D"));
+    }
+
+    #[test]
+    fn test_chow_liu() {
+        let bn = chow_liu(&mk_data(), &["A", "B", "C", "D"]);
+        let mut arcs: Vec<&String> = bn.edges().into_iter().map(|e| e.id()).collect();
+        arcs.sort();
+        assert_eq!(arcs.len(), 3);
+        assert_eq!(arcs[0], "A->B");
+        assert!(arcs.contains(&&"A->C".to_string()) || arcs.contains(&&"B->C".to_string()));
+        assert!(bn.parents("A").is_empty());
+        assert!(bn.cpds().is_empty());
+        let mut idgen = SequentialIdGenerator::new("cl");
+        let again = chow_liu_with(&mk_data(), &["A", "B", "C", "D"], &mut idgen);
+        assert_eq!(again.id(), "cl0");
+        assert_eq!(again.edges(), bn.edges());
+    }
+
+    #[test]
+    fn test_chow_liu_single_variable() {
+        let bn = chow_liu(&mk_data(), &["D"]);
+        assert_eq!(bn.order(), 1);
+        assert_eq!(bn.size(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_chow_liu_unknown_column() {
+        chow_liu(&mk_data(), &["A", "E"]);
+    }
+
+    #[test]
+    fn test_ln_gamma() {
+        assert!(ln_gamma(1.0).abs() < 1e-12);
+        assert!((ln_gamma(5.0) - 24f64.ln()).abs() < 1e-12);
+        assert!((ln_gamma(0.5) - std::f64::consts::PI.sqrt().ln()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_local_score_bdeu() {
+        // one binary variable, no parent, ess 2: log(B(1 + n0, 1 + n1) / B(1, 1))
+        let a = DiscreteVariable::new("A", &["0", "1"]);
+        let d = DataSet::new(vec![a], vec![vec![0], vec![0], vec![1]]);
+        let s = local_score(
+            &d,
+            "A",
+            &[],
+            Score::BDeu {
+                equivalent_sample_size: 2.0,
+            },
+        );
+        assert!((s - (1.0f64 / 12.0).ln()).abs() < 1e-10);
+    }
+
+    /// A causes B and C, B and C are independent given A
+    fn mk_fork_data() -> DataSet {
+        let vs: Vec<DiscreteVariable> = ["A", "B", "C"]
+            .iter()
+            .map(|n| DiscreteVariable::new(n, &["0", "1"]))
+            .collect();
+        let mut rows = Vec::new();
+        for a in 0..2 {
+            for (b, nb) in [(a, 9), (1 - a, 1)] {
+                for (c, nc) in [(a, 9), (1 - a, 1)] {
+                    for _ in 0..nb * nc {
+                        rows.push(vec![a, b, c]);
+                    }
+                }
+            }
+        }
+        DataSet::new(vs, rows)
+    }
+
+    fn skeleton(bn: &BayesianNetwork) -> HashSet<(String, String)> {
+        bn.edges()
+            .into_iter()
+            .map(|e| {
+                let (s, t) = (e.start().id().clone(), e.end().id().clone());
+                if s < t {
+                    (s, t)
+                } else {
+                    (t, s)
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_hill_climb() {
+        let d = mk_fork_data();
+        for score in [
+            Score::Bic,
+            Score::BDeu {
+                equivalent_sample_size: 1.0,
+            },
+        ] {
+            let bn = hill_climb(&d, score, &StructureConstraints::default());
+            let expected = HashSet::from([
+                ("A".to_string(), "B".to_string()),
+                ("A".to_string(), "C".to_string()),
+            ]);
+            assert_eq!(skeleton(&bn), expected);
+            let empty = BayesianNetwork::from_arcs("empty", &["A", "B", "C"], &[]);
+            assert!(network_score(&d, &bn, score) > network_score(&d, &empty, score));
+        }
+        let mut idgen = SequentialIdGenerator::new("hc");
+        let bn = hill_climb_with(&d, Score::Bic, &StructureConstraints::default(), &mut idgen);
+        assert_eq!(bn.id(), "hc0");
+    }
+
+    #[test]
+    fn test_hill_climb_constraints() {
+        let d = mk_fork_data();
+        let constraints = StructureConstraints {
+            whitelist: HashSet::from([("B".to_string(), "A".to_string())]),
+            blacklist: HashSet::from([("A".to_string(), "C".to_string())]),
+            max_parents: Some(1),
+        };
+        let bn = hill_climb(&d, Score::Bic, &constraints);
+        assert_eq!(bn.parents("A")[0].id(), "B");
+        assert!(bn.edges().iter().all(|e| e.id() != "A->C"));
+        for v in ["A", "B", "C"] {
+            assert!(bn.parents(v).len() <= 1);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_hill_climb_cyclic_whitelist() {
+        let constraints = StructureConstraints {
+            whitelist: HashSet::from([
+                ("B".to_string(), "A".to_string()),
+                ("A".to_string(), "B".to_string()),
+            ]),
+            ..Default::default()
+        };
+        hill_climb(&mk_fork_data(), Score::Bic, &constraints);
+    }
+
+    #[test]
+    fn test_regularized_gamma_q() {
+        // chi-square survival function, 1 and 4 degrees of freedom
+        assert!((regularized_gamma_q(0.5, 3.841_458_820_694_124 / 2.0) - 0.05).abs() < 1e-9);
+        assert!((regularized_gamma_q(2.0, 9.487_729_036_781_154 / 2.0) - 0.05).abs() < 1e-9);
+        assert_eq!(regularized_gamma_q(1.0, 0.0), 1.0);
+    }
+
+    #[test]
+    fn test_ci_test() {
+        let a = DiscreteVariable::new("A", &["0", "1"]);
+        let b = DiscreteVariable::new("B", &["0", "1"]);
+        let mut rows = Vec::new();
+        for (r, n) in [
+            (vec![0, 0], 10),
+            (vec![0, 1], 20),
+            (vec![1, 0], 30),
+            (vec![1, 1], 40),
+        ] {
+            rows.extend(std::iter::repeat_n(r, n));
+        }
+        let d = DataSet::new(vec![a, b], rows);
+        let chi = ci_test(&d, "A", "B", &[], IndependenceTest::ChiSquare);
+        assert!((chi.statistic - 0.793_650_793_650_793_6).abs() < 1e-12);
+        assert_eq!(chi.dof, 1);
+        let g = ci_test(&d, "A", "B", &[], IndependenceTest::GTest);
+        assert!(g.p_value > 0.05);
+        // B and C are independent given A in the fork
+        let f = mk_fork_data();
+        assert!(ci_test(&f, "B", "C", &[], IndependenceTest::GTest).p_value < 0.05);
+        let given_a = ci_test(&f, "B", "C", &["A"], IndependenceTest::GTest);
+        assert!(given_a.statistic.abs() < 1e-9);
+        assert_eq!(given_a.dof, 2);
+    }
+
+    #[test]
+    fn test_pc_fork() {
+        let g = pc(&mk_fork_data(), IndependenceTest::ChiSquare, 0.05);
+        let mut ids: Vec<&String> = g.edges().into_iter().map(|e| e.id()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["A--B", "A--C"]);
+        let mut idgen = SequentialIdGenerator::new("pc");
+        let g = pc_with(
+            &mk_fork_data(),
+            IndependenceTest::ChiSquare,
+            0.05,
+            &mut idgen,
+        );
+        assert_eq!(g.id(), "pc0");
+    }
+
+    /// A -> C <- B and C -> D
+    fn mk_collider_data() -> DataSet {
+        let vs: Vec<DiscreteVariable> = ["A", "B", "C", "D"]
+            .iter()
+            .map(|n| DiscreteVariable::new(n, &["0", "1"]))
+            .collect();
+        let mut rows = Vec::new();
+        for a in 0..2 {
+            for b in 0..2 {
+                let c_likely = a | b;
+                for (c, nc) in [(c_likely, 9), (1 - c_likely, 1)] {
+                    for (d, nd) in [(c, 9), (1 - c, 1)] {
+                        rows.extend(std::iter::repeat_n(vec![a, b, c, d], nc * nd));
+                    }
+                }
+            }
+        }
+        DataSet::new(vs, rows)
+    }
+
+    #[test]
+    fn test_pc_collider() {
+        let g = pc(&mk_collider_data(), IndependenceTest::GTest, 0.01);
+        let mut ids: Vec<&String> = g.edges().into_iter().map(|e| e.id()).collect();
+        ids.sort();
+        // the v-structure is oriented and rule 1 orients C -> D
+        assert_eq!(ids, vec!["A->C", "B->C", "C->D"]);
+    }
+
+    #[test]
+    fn test_fit_mle() {
+        let bn = BayesianNetwork::from_arcs("bn", &["A", "B", "C"], &[("A", "B"), ("A", "C")]);
+        let fitted = fit_mle(&bn, &mk_fork_data());
+        assert!(fitted.is_fully_specified());
+        let b = fitted.cpd("B").unwrap();
+        assert!((b.probability("1", &["1"]) - 0.9).abs() < 1e-12);
+        assert!((b.probability("1", &["0"]) - 0.1).abs() < 1e-12);
+        assert_eq!(fitted.cpd("A").unwrap().table(), &[vec![0.5, 0.5]]);
+    }
+
+    #[test]
+    fn test_fit_mle_unobserved_configuration() {
+        let a = DiscreteVariable::new("A", &["0", "1", "2"]);
+        let b = DiscreteVariable::new("B", &["0", "1"]);
+        let d = DataSet::new(vec![a, b], vec![vec![0, 1], vec![1, 0]]);
+        let bn = BayesianNetwork::from_arcs("bn", &["A", "B"], &[("A", "B")]);
+        let fitted = fit_mle(&bn, &d);
+        assert_eq!(fitted.cpd("B").unwrap().distribution(&["2"]), &[0.5, 0.5]);
+    }
+
+    #[test]
+    fn test_fit_bayesian() {
+        let a = DiscreteVariable::new("A", &["0", "1"]);
+        let d = DataSet::new(vec![a], vec![vec![0], vec![0], vec![0]]);
+        let bn = BayesianNetwork::from_arcs("bn", &["A"], &[]);
+        let fitted = fit_bayesian(&bn, &d, 1.0);
+        assert_eq!(fitted.cpd("A").unwrap().table(), &[vec![0.8, 0.2]]);
+        assert_eq!(fit_bayesian(&bn, &d, 0.0), fit_mle(&bn, &d));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_fit_mle_missing_column() {
+        let bn = BayesianNetwork::from_arcs("bn", &["A", "E"], &[]);
+        fit_mle(&bn, &mk_fork_data());
+    }
+}
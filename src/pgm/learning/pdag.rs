@@ -0,0 +1,509 @@
+//! partially directed acyclic graphs and markov equivalence classes
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::idgen::IdGenerator;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::edge::Edge;
+use crate::graph::types::edgetype::EdgeType;
+use crate::graph::types::graph::Graph;
+use crate::graph::types::idgen::UuidGenerator;
+use crate::graph::types::node::Node;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+/// Partially directed acyclic graph over named variables
+/// # Description
+/// Every adjacency is either an arc `a -> b` or an undirected edge `a - b`.
+/// A PDAG represents a set of directed acyclic graphs, those obtained by
+/// orienting its undirected edges. Completed PDAGs (CPDAGs) represent the
+/// markov equivalence classes of directed acyclic graphs, see [to_cpdag].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pdag {
+    adjacent: BTreeMap<String, BTreeSet<String>>,
+    /// arcs `(from, to)`, an adjacency without arc is undirected
+    arcs: BTreeSet<(String, String)>,
+}
+
+impl Pdag {
+    /// PDAG without edges over `variables`
+    pub fn new(variables: &[&str]) -> Pdag {
+        Pdag {
+            adjacent: variables
+                .iter()
+                .map(|v| (v.to_string(), BTreeSet::new()))
+                .collect(),
+            arcs: BTreeSet::new(),
+        }
+    }
+
+    /// PDAG of a graph
+    /// # Description
+    /// Directed edges become arcs and undirected edges undirected edges.
+    /// Panics if two vertices are joined by more than one edge or if the
+    /// graph has a loop. Acyclicity of the arcs is not checked.
+    ///
+    /// # Example
+    /// ```
+    /// use pgm_rust::graph::types::edge::Edge;
+    /// use pgm_rust::graph::types::edgetype::EdgeType;
+    /// use pgm_rust::graph::types::graph::Graph;
+    /// use pgm_rust::graph::types::node::Node;
+    /// use pgm_rust::pgm::learning::pdag::Pdag;
+    /// use std::collections::HashSet;
+    ///
+    /// let e1 = Edge::empty("e1", EdgeType::Directed, "a", "b");
+    /// let e2 = Edge::empty("e2", EdgeType::Undirected, "b", "c");
+    /// let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e1, e2]));
+    /// let p = Pdag::from_graph(&g);
+    /// p.has_arc("a", "b") && p.is_undirected("c", "b"); // true
+    /// ```
+    pub fn from_graph<N, E, G>(g: &G) -> Pdag
+    where
+        N: NodeTrait,
+        E: EdgeTrait<N>,
+        G: GraphTrait<N, E>,
+    {
+        let ids: Vec<&str> = g.vertices().iter().map(|v| v.id().as_str()).collect();
+        let mut pdag = Pdag::new(&ids);
+        for e in g.edges() {
+            let (a, b) = (e.start().id(), e.end().id());
+            if e.has_type() == &EdgeType::Directed {
+                pdag.add_arc(a, b);
+            } else {
+                pdag.add_undirected(a, b);
+            }
+        }
+        pdag
+    }
+
+    fn join(&mut self, a: &str, b: &str) {
+        if a == b {
+            panic!("loop on {a}");
+        }
+        if !self.adjacent.contains_key(a) || !self.adjacent.contains_key(b) {
+            panic!("{a} or {b} is not a variable of the graph");
+        }
+        if self.is_adjacent(a, b) {
+            panic!("{a} and {b} are already adjacent");
+        }
+        self.adjacent.get_mut(a).unwrap().insert(b.to_string());
+        self.adjacent.get_mut(b).unwrap().insert(a.to_string());
+    }
+
+    /// add the arc `a -> b`. Panics if `a` and `b` are adjacent.
+    pub fn add_arc(&mut self, a: &str, b: &str) {
+        self.join(a, b);
+        self.arcs.insert((a.to_string(), b.to_string()));
+    }
+
+    /// add the undirected edge `a - b`. Panics if `a` and `b` are adjacent.
+    pub fn add_undirected(&mut self, a: &str, b: &str) {
+        self.join(a, b);
+    }
+
+    /// variables in identifier order
+    pub fn variables(&self) -> Vec<&String> {
+        self.adjacent.keys().collect()
+    }
+
+    /// variables adjacent to `a` in identifier order
+    pub fn neighbors(&self, a: &str) -> Vec<&String> {
+        self.adjacent[a].iter().collect()
+    }
+
+    /// arcs `(from, to)` in identifier order
+    pub fn arcs(&self) -> Vec<(&String, &String)> {
+        self.arcs.iter().map(|(a, b)| (a, b)).collect()
+    }
+
+    /// undirected edges `(a, b)` with `a < b` in identifier order
+    pub fn undirected_edges(&self) -> Vec<(&String, &String)> {
+        let mut edges = Vec::new();
+        for (a, bs) in &self.adjacent {
+            for b in bs {
+                if a < b && self.is_undirected(a, b) {
+                    edges.push((a, b));
+                }
+            }
+        }
+        edges
+    }
+
+    /// true if `a` and `b` are joined by an arc or an undirected edge
+    pub fn is_adjacent(&self, a: &str, b: &str) -> bool {
+        self.adjacent.get(a).is_some_and(|bs| bs.contains(b))
+    }
+
+    /// true if the arc `a -> b` is in the graph
+    pub fn has_arc(&self, a: &str, b: &str) -> bool {
+        self.arcs.contains(&(a.to_string(), b.to_string()))
+    }
+
+    /// true if the undirected edge `a - b` is in the graph
+    pub fn is_undirected(&self, a: &str, b: &str) -> bool {
+        self.is_adjacent(a, b) && !self.has_arc(a, b) && !self.has_arc(b, a)
+    }
+
+    /// orient the undirected edge `a - b` as `a -> b`, returns false if the
+    /// adjacency is already oriented
+    pub fn orient(&mut self, a: &str, b: &str) -> bool {
+        if !self.is_undirected(a, b) {
+            return false;
+        }
+        self.arcs.insert((a.to_string(), b.to_string()))
+    }
+
+    /// apply Meek's rules 1 to 3 once, returns true if an edge was oriented
+    fn meek_step(&mut self) -> bool {
+        let names: Vec<String> = self.adjacent.keys().cloned().collect();
+        let mut changed = false;
+        for b in &names {
+            let neighbors: Vec<String> = self.adjacent[b].iter().cloned().collect();
+            for c in &neighbors {
+                if !self.is_undirected(b, c) {
+                    continue;
+                }
+                // rule 1: a -> b - c, a and c not adjacent
+                let r1 = neighbors
+                    .iter()
+                    .any(|a| a != c && self.has_arc(a, b) && !self.is_adjacent(a, c));
+                // rule 2: b -> a -> c
+                let r2 = neighbors
+                    .iter()
+                    .any(|a| self.has_arc(b, a) && self.has_arc(a, c));
+                // rule 3: b - a1 -> c, b - a2 -> c, a1 and a2 not adjacent
+                let parents: Vec<&String> = neighbors
+                    .iter()
+                    .filter(|a| self.is_undirected(b, a) && self.has_arc(a, c))
+                    .collect();
+                let r3 = parents
+                    .iter()
+                    .enumerate()
+                    .any(|(i, a1)| parents[i + 1..].iter().any(|a2| !self.is_adjacent(a1, a2)));
+                if r1 || r2 || r3 {
+                    changed |= self.orient(b, c);
+                }
+            }
+        }
+        changed
+    }
+
+    /// Orient the undirected edges implied by the arcs
+    /// # Description
+    /// Applies Meek's rules until none applies: an undirected edge is
+    /// oriented when the other orientation would create a new v-structure
+    /// (rule 1), a directed cycle (rule 2), or force one of them (rule 3).
+    /// Without background knowledge, these rules turn the pattern of a
+    /// directed acyclic graph into its CPDAG. Returns true if some edge was
+    /// oriented.
+    ///
+    /// # Example
+    /// ```
+    /// use pgm_rust::pgm::learning::pdag::Pdag;
+    ///
+    /// let mut p = Pdag::new(&["a", "b", "c"]);
+    /// p.add_arc("a", "b");
+    /// p.add_undirected("b", "c");
+    /// p.apply_meek_rules();
+    /// p.has_arc("b", "c"); // true
+    /// ```
+    ///
+    /// # References
+    /// Meek C. Causal inference and causal explanation with background
+    /// knowledge. 1995.
+    pub fn apply_meek_rules(&mut self) -> bool {
+        let mut changed = false;
+        while self.meek_step() {
+            changed = true;
+        }
+        changed
+    }
+
+    /// v-structures `(a, c, b)` with `a -> c <- b`, `a < b` and `a`, `b`
+    /// not adjacent
+    pub fn v_structures(&self) -> BTreeSet<(String, String, String)> {
+        let mut result = BTreeSet::new();
+        for c in self.adjacent.keys() {
+            let parents: Vec<&String> = self.adjacent[c]
+                .iter()
+                .filter(|a| self.has_arc(a, c))
+                .collect();
+            for (i, a) in parents.iter().enumerate() {
+                for b in &parents[i + 1..] {
+                    if !self.is_adjacent(a, b) {
+                        result.insert(((*a).clone(), c.clone(), (*b).clone()));
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    /// true if the arcs form a directed cycle
+    fn has_directed_cycle(&self) -> bool {
+        // Kahn's algorithm on the arcs only
+        let mut in_degree: HashMap<&String, usize> = self.adjacent.keys().map(|v| (v, 0)).collect();
+        for (_, b) in &self.arcs {
+            *in_degree.get_mut(b).unwrap() += 1;
+        }
+        let mut stack: Vec<&String> = in_degree
+            .iter()
+            .filter(|(_, d)| **d == 0)
+            .map(|(v, _)| *v)
+            .collect();
+        let mut seen = 0;
+        while let Some(v) = stack.pop() {
+            seen += 1;
+            for w in &self.adjacent[v] {
+                if self.has_arc(v, w) {
+                    let d = in_degree.get_mut(w).unwrap();
+                    *d -= 1;
+                    if *d == 0 {
+                        stack.push(w);
+                    }
+                }
+            }
+        }
+        seen != self.adjacent.len()
+    }
+
+    /// Convert to a graph
+    /// # Description
+    /// Arcs become directed edges with identifiers `x->y` and undirected
+    /// edges become undirected edges with identifiers `x--y`, `x < y`.
+    /// The graph gets a generated identifier, see [Pdag::to_graph_with].
+    pub fn to_graph(&self) -> Graph<Node, Edge<Node>> {
+        self.to_graph_with(&mut UuidGenerator)
+    }
+
+    /// Convert to a graph whose identifier is produced by `idgen`, see
+    /// [Pdag::to_graph]
+    pub fn to_graph_with<I: IdGenerator>(&self, idgen: &mut I) -> Graph<Node, Edge<Node>> {
+        let nodes: HashSet<Node> = self.adjacent.keys().map(|v| Node::empty(v)).collect();
+        let mut edges: HashSet<Edge<Node>> = HashSet::new();
+        for (x, y) in &self.arcs {
+            edges.insert(Edge::empty(&format!("{x}->{y}"), EdgeType::Directed, x, y));
+        }
+        for (x, y) in self.undirected_edges() {
+            edges.insert(Edge::empty(
+                &format!("{x}--{y}"),
+                EdgeType::Undirected,
+                x,
+                y,
+            ));
+        }
+        let gid = idgen.generate(&nodes.iter().collect(), &edges.iter().collect());
+        Graph::new(gid, HashMap::new(), nodes, edges)
+    }
+}
+
+/// Compute the CPDAG of a directed acyclic graph
+/// # Description
+/// Two directed acyclic graphs are markov equivalent if and only if they
+/// have the same skeleton and the same v-structures. The CPDAG keeps the
+/// arcs that every member of the equivalence class shares and replaces the
+/// others by undirected edges. It is obtained by orienting the v-structures
+/// of the skeleton and applying Meek's rules. Panics if `dag` has an
+/// undirected edge or a directed cycle.
+///
+/// # Example
+/// ```
+/// use pgm_rust::pgm::bayesnet::BayesianNetwork;
+/// use pgm_rust::pgm::learning::pdag::to_cpdag;
+///
+/// let chain = BayesianNetwork::from_arcs("chain", &["a", "b", "c"], &[("a", "b"), ("b", "c")]);
+/// to_cpdag(&chain).arcs().is_empty(); // true
+/// let collider = BayesianNetwork::from_arcs("collider", &["a", "b", "c"], &[("a", "b"), ("c", "b")]);
+/// to_cpdag(&collider).arcs().len() == 2; // true
+/// ```
+///
+/// # References
+/// Chickering D. A transformational characterization of equivalent
+/// bayesian network structures. 1995.
+pub fn to_cpdag<N, E, G>(dag: &G) -> Pdag
+where
+    N: NodeTrait,
+    E: EdgeTrait<N>,
+    G: GraphTrait<N, E>,
+{
+    let directed = Pdag::from_graph(dag);
+    if !directed.undirected_edges().is_empty() {
+        panic!("{} has undirected edges", dag.id());
+    }
+    if directed.has_directed_cycle() {
+        panic!("{} has a directed cycle", dag.id());
+    }
+    let mut cpdag = Pdag {
+        adjacent: directed.adjacent.clone(),
+        arcs: BTreeSet::new(),
+    };
+    for (a, c, b) in directed.v_structures() {
+        cpdag.arcs.insert((a, c.clone()));
+        cpdag.arcs.insert((b, c));
+    }
+    cpdag.apply_meek_rules();
+    cpdag
+}
+
+/// extend `pdag` in every way compatible with the v-structures of `target`
+fn extend(
+    pdag: Pdag,
+    target: &BTreeSet<(String, String, String)>,
+    limit: usize,
+    out: &mut Vec<Pdag>,
+) {
+    if out.len() >= limit || pdag.has_directed_cycle() {
+        return;
+    }
+    if !pdag.v_structures().is_subset(target) {
+        return;
+    }
+    let edge = pdag
+        .undirected_edges()
+        .first()
+        .map(|(a, b)| ((*a).clone(), (*b).clone()));
+    match edge {
+        None => out.push(pdag),
+        Some((a, b)) => {
+            for (x, y) in [(&a, &b), (&b, &a)] {
+                let mut p = pdag.clone();
+                p.orient(x, y);
+                p.apply_meek_rules();
+                extend(p, target, limit, out);
+            }
+        }
+    }
+}
+
+/// Enumerate the directed acyclic graphs represented by a CPDAG
+/// # Description
+/// Undirected edges are oriented one at a time, in identifier order, and
+/// the implied orientations are propagated with Meek's rules. Branches
+/// creating a directed cycle or a v-structure absent from `cpdag` are
+/// pruned, so every produced graph is a member of the equivalence class.
+/// Graphs use the edge identifiers of [Pdag::to_graph].
+///
+/// # Args
+/// - cpdag: a completed PDAG, see [to_cpdag]
+/// - limit: maximum number of graphs produced
+///
+/// # Example
+/// ```
+/// use pgm_rust::pgm::bayesnet::BayesianNetwork;
+/// use pgm_rust::pgm::learning::pdag::{enumerate_dags, to_cpdag};
+///
+/// let chain = BayesianNetwork::from_arcs("chain", &["a", "b", "c"], &[("a", "b"), ("b", "c")]);
+/// // a -> b -> c, a <- b <- c and a <- b -> c
+/// enumerate_dags(&to_cpdag(&chain), 10).len() == 3; // true
+/// ```
+pub fn enumerate_dags(cpdag: &Pdag, limit: usize) -> Vec<Graph<Node, Edge<Node>>> {
+    enumerate_dags_with(cpdag, limit, &mut UuidGenerator)
+}
+
+/// Enumerate members of a markov equivalence class, the identifiers of the
+/// graphs are produced by `idgen` in enumeration order, see
+/// [enumerate_dags]
+pub fn enumerate_dags_with<I: IdGenerator>(
+    cpdag: &Pdag,
+    limit: usize,
+    idgen: &mut I,
+) -> Vec<Graph<Node, Edge<Node>>> {
+    let mut members = Vec::new();
+    extend(cpdag.clone(), &cpdag.v_structures(), limit, &mut members);
+    members.iter().map(|p| p.to_graph_with(idgen)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::traits::graph_obj::GraphObject;
+    use crate::graph::types::idgen::SequentialIdGenerator;
+    use crate::pgm::bayesnet::BayesianNetwork;
+
+    fn arcs(p: &Pdag) -> Vec<(&str, &str)> {
+        p.arcs()
+            .into_iter()
+            .map(|(a, b)| (a.as_str(), b.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_to_cpdag_collider_descendant() {
+        // a -> c <- b, c -> d: the v-structure forces c -> d by rule 1
+        let bn = BayesianNetwork::from_arcs(
+            "g",
+            &["a", "b", "c", "d"],
+            &[("a", "c"), ("b", "c"), ("c", "d")],
+        );
+        let cpdag = to_cpdag(&bn);
+        assert_eq!(arcs(&cpdag), vec![("a", "c"), ("b", "c"), ("c", "d")]);
+        assert!(cpdag.undirected_edges().is_empty());
+        assert_eq!(enumerate_dags(&cpdag, 10).len(), 1);
+    }
+
+    #[test]
+    fn test_to_cpdag_rule_2() {
+        // a -> b, a -> c, b -> c, c <- d: a -> c <- d and b -> c <- d are
+        // v-structures, a - b stays undirected
+        let bn = BayesianNetwork::from_arcs(
+            "g",
+            &["a", "b", "c", "d"],
+            &[("a", "b"), ("a", "c"), ("b", "c"), ("d", "c")],
+        );
+        let cpdag = to_cpdag(&bn);
+        assert_eq!(arcs(&cpdag), vec![("a", "c"), ("b", "c"), ("d", "c")]);
+        let undirected: Vec<(&String, &String)> = cpdag.undirected_edges();
+        assert_eq!(undirected.len(), 1);
+        assert_eq!(enumerate_dags(&cpdag, 10).len(), 2);
+    }
+
+    #[test]
+    fn test_enumerate_dags_complete() {
+        // the complete graph on 3 variables is equivalent to its 6
+        // topological orderings
+        let bn = BayesianNetwork::from_arcs(
+            "g",
+            &["a", "b", "c"],
+            &[("a", "b"), ("a", "c"), ("b", "c")],
+        );
+        let cpdag = to_cpdag(&bn);
+        assert_eq!(cpdag.undirected_edges().len(), 3);
+        let dags = enumerate_dags(&cpdag, 100);
+        assert_eq!(dags.len(), 6);
+        for dag in &dags {
+            assert_eq!(to_cpdag(dag), cpdag);
+        }
+        assert_eq!(enumerate_dags(&cpdag, 4).len(), 4);
+        let mut idgen = SequentialIdGenerator::new("dag");
+        let ids: Vec<String> = enumerate_dags_with(&cpdag, 2, &mut idgen)
+            .iter()
+            .map(|g| g.id().clone())
+            .collect();
+        assert_eq!(ids, vec!["dag0", "dag1"]);
+        assert_eq!(cpdag.to_graph_with(&mut idgen).id(), "dag2");
+    }
+
+    #[test]
+    fn test_apply_meek_rule_3() {
+        // b - a1 -> c, b - a2 -> c, b - c with a1, a2 not adjacent
+        let mut p = Pdag::new(&["a1", "a2", "b", "c"]);
+        p.add_undirected("b", "a1");
+        p.add_undirected("b", "a2");
+        p.add_undirected("b", "c");
+        p.add_arc("a1", "c");
+        p.add_arc("a2", "c");
+        assert!(p.apply_meek_rules());
+        assert!(p.has_arc("b", "c"));
+        assert!(p.is_undirected("a1", "b"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_to_cpdag_cycle() {
+        let mut p = Pdag::new(&["a", "b"]);
+        p.add_undirected("a", "b");
+        to_cpdag(&p.to_graph());
+    }
+}
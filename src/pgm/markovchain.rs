@@ -0,0 +1,304 @@
+//! discrete time markov chains
+use crate::graph::io::edgelist::WEIGHT_KEY;
+use crate::graph::traits::edge::Edge as EdgeTrait;
+use crate::graph::traits::graph::Graph as GraphTrait;
+use crate::graph::traits::node::Node as NodeTrait;
+use crate::graph::types::edgetype::EdgeType;
+use crate::pgm::cpd::SUM_TOLERANCE;
+use std::collections::HashMap;
+
+/// largest number of power iterations of [MarkovChain::stationary_distribution]
+const MAX_ITERATIONS: usize = 100_000;
+
+/// product of two square matrices
+fn matmul(a: &[Vec<f64>], b: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let n = a.len();
+    let mut c = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        for k in 0..n {
+            if a[i][k] == 0.0 {
+                continue;
+            }
+            for j in 0..n {
+                c[i][j] += a[i][k] * b[k][j];
+            }
+        }
+    }
+    c
+}
+
+/// Finite discrete time markov chain
+/// # Description
+/// States are identified by strings and ordered by identifier. The
+/// transition matrix holds in row `i` and column `j` the probability of
+/// moving from state `i` to state `j` in one step, so every row sums to one.
+///
+/// # References
+/// Norris J. Markov Chains. 1997.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkovChain {
+    states: Vec<String>,
+    transition: Vec<Vec<f64>>,
+}
+
+impl MarkovChain {
+    /// Create a chain from its states and its transition matrix
+    /// # Description
+    /// Panics if states are repeated, if the matrix is not square with one
+    /// row per state, if an entry is negative or if a row does not sum to
+    /// one within [SUM_TOLERANCE].
+    pub fn new(states: Vec<String>, transition: Vec<Vec<f64>>) -> MarkovChain {
+        let n = states.len();
+        if transition.len() != n || transition.iter().any(|r| r.len() != n) {
+            panic!("transition matrix must be {n} by {n}");
+        }
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|i, j| states[*i].cmp(&states[*j]));
+        for w in order.windows(2) {
+            if states[w[0]] == states[w[1]] {
+                panic!("state {} is repeated", states[w[0]]);
+            }
+        }
+        for (s, row) in states.iter().zip(&transition) {
+            let total: f64 = row.iter().sum();
+            if row.iter().any(|p| p.is_nan() || *p < 0.0) || (total - 1.0).abs() > SUM_TOLERANCE {
+                panic!("transition probabilities out of {s} do not form a distribution");
+            }
+        }
+        // keep states ordered by identifier
+        let sorted_states = order.iter().map(|i| states[*i].clone()).collect();
+        let sorted = order
+            .iter()
+            .map(|i| order.iter().map(|j| transition[*i][*j]).collect())
+            .collect();
+        MarkovChain {
+            states: sorted_states,
+            transition: sorted,
+        }
+    }
+
+    /// Create a chain from a directed graph weighted by probabilities
+    /// # Description
+    /// Vertices are states and every edge carries the probability of
+    /// moving from its start to its end under [WEIGHT_KEY] in its data.
+    /// Parallel edges add up. Panics if an edge is undirected, if a weight
+    /// is missing or not a number, or if the out-weights of a vertex do not
+    /// sum to one, see [MarkovChain::new]. An absorbing state needs a self
+    /// loop of weight one.
+    ///
+    /// # Example
+    /// ```
+    /// use pgm_rust::graph::io::edgelist::from_edge_list;
+    /// use pgm_rust::pgm::markovchain::MarkovChain;
+    ///
+    /// let text = "sun,sun,0.9\nsun,rain,0.1\nrain,sun,0.5\nrain,rain,0.5\n";
+    /// let chain = MarkovChain::from_graph(&from_edge_list(text.as_bytes(), true).unwrap());
+    /// let pi = chain.stationary_distribution();
+    /// (pi[1] - 5.0 / 6.0).abs() < 1e-9; // true: states are rain, sun
+    /// ```
+    pub fn from_graph<N, E, G>(g: &G) -> MarkovChain
+    where
+        N: NodeTrait,
+        E: EdgeTrait<N>,
+        G: GraphTrait<N, E>,
+    {
+        let states: Vec<String> = g
+            .vertices_sorted()
+            .into_iter()
+            .map(|v| v.id().clone())
+            .collect();
+        let index: HashMap<&String, usize> =
+            states.iter().enumerate().map(|(i, s)| (s, i)).collect();
+        let mut transition = vec![vec![0.0; states.len()]; states.len()];
+        for e in g.edges() {
+            if e.has_type() != &EdgeType::Directed {
+                panic!("{e} is not directed");
+            }
+            let weight = e
+                .data()
+                .get(WEIGHT_KEY)
+                .and_then(|ws| ws.first())
+                .and_then(|w| w.parse::<f64>().ok());
+            match weight {
+                None => panic!("{e} has no numeric {WEIGHT_KEY}"),
+                Some(w) => transition[index[e.start().id()]][index[e.end().id()]] += w,
+            }
+        }
+        MarkovChain::new(states, transition)
+    }
+
+    /// states ordered by identifier
+    pub fn states(&self) -> &[String] {
+        &self.states
+    }
+
+    /// one step transition matrix, rows and columns follow
+    /// [MarkovChain::states]
+    pub fn transition_matrix(&self) -> &[Vec<f64>] {
+        &self.transition
+    }
+
+    fn state_index(&self, state: &str) -> usize {
+        match self.states.iter().position(|s| s == state) {
+            None => panic!("{state} is not a state of the chain"),
+            Some(i) => i,
+        }
+    }
+
+    /// states that are never left
+    pub fn absorbing_states(&self) -> Vec<&String> {
+        self.states
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| (self.transition[*i][*i] - 1.0).abs() <= SUM_TOLERANCE)
+            .map(|(_, s)| s)
+            .collect()
+    }
+
+    /// transition matrix of `n` steps, by repeated squaring
+    pub fn n_step_matrix(&self, n: usize) -> Vec<Vec<f64>> {
+        let size = self.states.len();
+        let mut result: Vec<Vec<f64>> = (0..size)
+            .map(|i| (0..size).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+            .collect();
+        let mut power = self.transition.clone();
+        let mut k = n;
+        while k > 0 {
+            if k % 2 == 1 {
+                result = matmul(&result, &power);
+            }
+            k /= 2;
+            if k > 0 {
+                power = matmul(&power, &power);
+            }
+        }
+        result
+    }
+
+    /// Probability of being in `to` after `n` steps from `from`
+    /// # Example
+    /// ```
+    /// use pgm_rust::pgm::markovchain::MarkovChain;
+    ///
+    /// let states = vec!["a".to_string(), "b".to_string()];
+    /// let chain = MarkovChain::new(states, vec![vec![0.0, 1.0], vec![1.0, 0.0]]);
+    /// chain.transition_probability("a", "a", 2) == 1.0; // true
+    /// chain.transition_probability("a", "a", 3) == 0.0; // true
+    /// ```
+    pub fn transition_probability(&self, from: &str, to: &str, n: usize) -> f64 {
+        let (i, j) = (self.state_index(from), self.state_index(to));
+        self.n_step_matrix(n)[i][j]
+    }
+
+    /// Stationary distribution of the chain
+    /// # Description
+    /// Power iteration on the lazy chain `(I + P) / 2`, which has the same
+    /// stationary distributions as `P` and is aperiodic, starting from the
+    /// uniform distribution. For an irreducible chain the distribution is
+    /// unique. Otherwise the one reached from the uniform distribution is
+    /// returned. Probabilities follow [MarkovChain::states].
+    pub fn stationary_distribution(&self) -> Vec<f64> {
+        let n = self.states.len();
+        if n == 0 {
+            return Vec::new();
+        }
+        let mut pi = vec![1.0 / n as f64; n];
+        for _ in 0..MAX_ITERATIONS {
+            let mut next: Vec<f64> = pi.iter().map(|p| p / 2.0).collect();
+            for (i, row) in self.transition.iter().enumerate() {
+                for (j, p) in row.iter().enumerate() {
+                    next[j] += pi[i] * p / 2.0;
+                }
+            }
+            let change: f64 = next.iter().zip(&pi).map(|(a, b)| (a - b).abs()).sum();
+            pi = next;
+            if change < 1e-14 {
+                break;
+            }
+        }
+        let total: f64 = pi.iter().sum();
+        pi.iter().map(|p| p / total).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::types::edge::Edge;
+    use crate::graph::types::graph::Graph;
+    use crate::graph::types::node::Node;
+    use std::collections::HashSet;
+
+    fn mk_wedge(n1_id: &str, n2_id: &str, e_id: &str, w: f64) -> Edge<Node> {
+        let data = HashMap::from([(WEIGHT_KEY.to_string(), vec![w.to_string()])]);
+        Edge::new(
+            e_id.to_string(),
+            data,
+            Node::empty(n1_id),
+            Node::empty(n2_id),
+            EdgeType::Directed,
+        )
+    }
+
+    /// gambler's ruin on 0..3 with absorbing ends
+    fn mk_ruin() -> MarkovChain {
+        let es: HashSet<Edge<Node>> = HashSet::from([
+            mk_wedge("s0", "s0", "e1", 1.0),
+            mk_wedge("s1", "s0", "e2", 0.5),
+            mk_wedge("s1", "s2", "e3", 0.5),
+            mk_wedge("s2", "s1", "e4", 0.5),
+            mk_wedge("s2", "s3", "e5", 0.5),
+            mk_wedge("s3", "s3", "e6", 1.0),
+        ]);
+        MarkovChain::from_graph(&Graph::from_edgeset(es))
+    }
+
+    #[test]
+    fn test_absorbing_states() {
+        assert_eq!(mk_ruin().absorbing_states(), vec!["s0", "s3"]);
+    }
+
+    #[test]
+    fn test_n_step() {
+        let chain = mk_ruin();
+        assert_eq!(chain.transition_probability("s1", "s2", 1), 0.5);
+        assert_eq!(chain.transition_probability("s1", "s1", 2), 0.25);
+        // absorption from s1 in s0 has probability 2/3
+        assert!((chain.transition_probability("s1", "s0", 200) - 2.0 / 3.0).abs() < 1e-12);
+        assert_eq!(chain.n_step_matrix(0)[2][2], 1.0);
+    }
+
+    #[test]
+    fn test_stationary_distribution() {
+        let states = vec!["c".to_string(), "a".to_string(), "b".to_string()];
+        // cyclic chain c -> a -> b -> c, periodic with uniform stationary distribution
+        let chain = MarkovChain::new(
+            states,
+            vec![
+                vec![0.0, 1.0, 0.0],
+                vec![0.0, 0.0, 1.0],
+                vec![1.0, 0.0, 0.0],
+            ],
+        );
+        assert_eq!(chain.states(), ["a", "b", "c"]);
+        assert_eq!(chain.transition_matrix()[0], vec![0.0, 1.0, 0.0]);
+        for p in chain.stationary_distribution() {
+            assert!((p - 1.0 / 3.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_graph_not_stochastic() {
+        let es = HashSet::from([mk_wedge("a", "b", "e1", 0.5)]);
+        MarkovChain::from_graph(&Graph::from_edgeset(es));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_graph_missing_weight() {
+        let e = Edge::empty("e1", EdgeType::Directed, "a", "a");
+        let g: Graph<Node, Edge<Node>> = Graph::from_edgeset(HashSet::from([e]));
+        MarkovChain::from_graph(&g);
+    }
+}
@@ -0,0 +1,1113 @@
+//! queries shared by inference engines
+use crate::factor::Factor;
+use crate::pgm::bayesnet::BayesianNetwork;
+use crate::pgm::cpd::SUM_TOLERANCE;
+use crate::pgm::inference::sampling::Forward;
+use crate::pgm::variable::DiscreteVariable;
+use crate::rng::SplitMix64;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+
+/// Observed states of some variables
+/// # Example
+/// ```
+/// use pgm_rust::pgm::query::Evidence;
+///
+/// let evidence = Evidence::from([("rain", "yes"), ("wet", "yes")]);
+/// evidence.get("rain") == Some("yes"); // true
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Evidence {
+    observations: BTreeMap<String, String>,
+}
+
+impl Evidence {
+    /// evidence without observation
+    pub fn new() -> Evidence {
+        Evidence::default()
+    }
+
+    /// record the state of a variable, replacing a previous observation
+    pub fn observe(&mut self, variable: &str, state: &str) {
+        self.observations
+            .insert(variable.to_string(), state.to_string());
+    }
+
+    /// observed state of a variable
+    pub fn get(&self, variable: &str) -> Option<&str> {
+        self.observations.get(variable).map(|s| s.as_str())
+    }
+
+    /// observations ordered by variable name
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.observations.iter()
+    }
+
+    /// number of observed variables
+    pub fn len(&self) -> usize {
+        self.observations.len()
+    }
+
+    /// nothing is observed
+    pub fn is_empty(&self) -> bool {
+        self.observations.is_empty()
+    }
+}
+
+impl<'a, const K: usize> From<[(&'a str, &'a str); K]> for Evidence {
+    fn from(observations: [(&'a str, &'a str); K]) -> Evidence {
+        let mut evidence = Evidence::new();
+        for (v, s) in observations {
+            evidence.observe(v, s);
+        }
+        evidence
+    }
+}
+
+/// kinds of questions asked to an inference engine
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QueryKind {
+    /// joint posterior distribution of the query variables
+    Marginal,
+    /// most probable joint state of the query variables
+    Map,
+}
+
+/// Question asked to an inference engine
+/// # Description
+/// A query is built from its variables then restricted by evidence, for
+/// example `Query::marginal(["A", "B"]).given([("C", "true")])`. Engines
+/// answer it through [InferenceEngine::query].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Query {
+    kind: QueryKind,
+    variables: Vec<String>,
+    evidence: Evidence,
+}
+
+impl Query {
+    fn build<'a, I: IntoIterator<Item = &'a str>>(kind: QueryKind, variables: I) -> Query {
+        let mut vs: Vec<String> = Vec::new();
+        for v in variables {
+            if vs.iter().any(|w| w == v) {
+                panic!("{v} is queried more than once");
+            }
+            vs.push(v.to_string());
+        }
+        Query {
+            kind,
+            variables: vs,
+            evidence: Evidence::new(),
+        }
+    }
+
+    /// ask for the joint posterior distribution of `variables`
+    pub fn marginal<'a, I: IntoIterator<Item = &'a str>>(variables: I) -> Query {
+        Query::build(QueryKind::Marginal, variables)
+    }
+
+    /// ask for the most probable joint state of `variables`
+    pub fn map<'a, I: IntoIterator<Item = &'a str>>(variables: I) -> Query {
+        Query::build(QueryKind::Map, variables)
+    }
+
+    /// Condition the query on observations
+    /// # Description
+    /// Panics if a queried variable is observed.
+    ///
+    /// # Example
+    /// ```
+    /// use pgm_rust::pgm::query::Query;
+    ///
+    /// let q = Query::marginal(["A", "B"]).given([("C", "true")]);
+    /// q.evidence().get("C") == Some("true"); // true
+    /// ```
+    pub fn given<E: Into<Evidence>>(mut self, evidence: E) -> Query {
+        for (v, s) in evidence.into().iter() {
+            if self.variables.contains(v) {
+                panic!("{v} is both queried and observed");
+            }
+            self.evidence.observe(v, s);
+        }
+        self
+    }
+
+    /// kind of the query
+    pub fn kind(&self) -> QueryKind {
+        self.kind
+    }
+
+    /// queried variables in the order they were given
+    pub fn variables(&self) -> &[String] {
+        &self.variables
+    }
+
+    /// observations the query is conditioned on
+    pub fn evidence(&self) -> &Evidence {
+        &self.evidence
+    }
+}
+
+/// Joint distribution of discrete variables
+/// # Description
+/// Probabilities are stored in lexicographic order of state indices, the
+/// last variable varying fastest.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Distribution {
+    variables: Vec<DiscreteVariable>,
+    values: Vec<f64>,
+}
+
+impl Distribution {
+    /// Create a distribution, panics if there is not one probability per
+    /// joint state or if they do not sum to one within [SUM_TOLERANCE]
+    pub fn new(variables: Vec<DiscreteVariable>, values: Vec<f64>) -> Distribution {
+        let size: usize = variables.iter().map(|v| v.cardinality()).product();
+        if values.len() != size {
+            panic!("{} probabilities given, {} expected", values.len(), size);
+        }
+        let total: f64 = values.iter().sum();
+        if values.iter().any(|p| *p < 0.0) || (total - 1.0).abs() > SUM_TOLERANCE {
+            panic!("probabilities must be non negative and sum to one, sum is {total}");
+        }
+        Distribution { variables, values }
+    }
+
+    /// variables of the distribution
+    pub fn variables(&self) -> &[DiscreteVariable] {
+        &self.variables
+    }
+
+    /// probabilities of the joint states, see [Distribution]
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// probability of a joint state given as state names in variable order
+    pub fn probability(&self, states: &[&str]) -> f64 {
+        if states.len() != self.variables.len() {
+            panic!(
+                "{} states given, {} expected",
+                states.len(),
+                self.variables.len()
+            );
+        }
+        let mut index = 0;
+        for (v, s) in self.variables.iter().zip(states) {
+            match v.state_index(s) {
+                None => panic!("{} has no state {}", v.name(), s),
+                Some(i) => index = index * v.cardinality() + i,
+            }
+        }
+        self.values[index]
+    }
+
+    /// distribution of a single variable, other variables summed out
+    pub fn marginal(&self, variable: &str) -> Distribution {
+        let position = match self.variables.iter().position(|v| v.name() == variable) {
+            None => panic!("{variable} is not a variable of the distribution"),
+            Some(p) => p,
+        };
+        let card = self.variables[position].cardinality();
+        let stride: usize = self.variables[position + 1..]
+            .iter()
+            .map(|v| v.cardinality())
+            .product();
+        let mut values = vec![0.0; card];
+        for (i, p) in self.values.iter().enumerate() {
+            values[(i / stride) % card] += p;
+        }
+        Distribution {
+            variables: vec![self.variables[position].clone()],
+            values,
+        }
+    }
+}
+
+/// answer of an inference engine to a [Query]
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryResult {
+    /// answer to a [QueryKind::Marginal] query
+    Marginal(Distribution),
+    /// answer to a [QueryKind::Map] query
+    Map {
+        /// most probable state of every queried variable
+        assignment: BTreeMap<String, String>,
+        /// posterior probability of the assignment
+        probability: f64,
+    },
+}
+
+impl QueryResult {
+    /// distribution of a [QueryResult::Marginal] answer, `None` for a map
+    /// assignment
+    pub fn into_marginal(self) -> Option<Distribution> {
+        match self {
+            QueryResult::Marginal(d) => Some(d),
+            QueryResult::Map { .. } => None,
+        }
+    }
+
+    /// assignment and probability of a [QueryResult::Map] answer, `None`
+    /// for a marginal
+    pub fn into_map(self) -> Option<(BTreeMap<String, String>, f64)> {
+        match self {
+            QueryResult::Map {
+                assignment,
+                probability,
+            } => Some((assignment, probability)),
+            QueryResult::Marginal(_) => None,
+        }
+    }
+}
+
+/// common interface of inference engines
+pub trait InferenceEngine {
+    /// answer a query, panics if the query mentions unknown variables or
+    /// states, or if the evidence has probability zero
+    fn query(&self, query: &Query) -> QueryResult;
+}
+
+/// Exact inference by enumeration of the joint distribution
+/// # Description
+/// Sums the product of the tables over every joint state of the network.
+/// The cost is exponential in the number of variables, so this engine is
+/// meant for small networks and as a reference for other engines. The
+/// network must be fully specified.
+///
+/// # Example
+/// ```
+/// use pgm_rust::pgm::bayesnet::BayesianNetwork;
+/// use pgm_rust::pgm::cpd::TabularCPD;
+/// use pgm_rust::pgm::query::{Enumeration, InferenceEngine, Query, QueryResult};
+/// use pgm_rust::pgm::variable::DiscreteVariable;
+///
+/// let rain = DiscreteVariable::new("rain", &["no", "yes"]);
+/// let wet = DiscreteVariable::new("wet", &["no", "yes"]);
+/// let mut bn = BayesianNetwork::from_arcs("bn", &["rain", "wet"], &[("rain", "wet")]);
+/// bn.add_cpd(TabularCPD::marginal(rain.clone(), vec![0.5, 0.5]));
+/// bn.add_cpd(TabularCPD::new(wet, vec![rain], vec![vec![0.9, 0.1], vec![0.1, 0.9]]));
+/// let q = Query::marginal(["rain"]).given([("wet", "yes")]);
+/// if let QueryResult::Marginal(d) = Enumeration::new(&bn).query(&q) {
+///     (d.probability(&["yes"]) - 0.9).abs() < 1e-12; // true
+/// }
+/// ```
+///
+/// # References
+/// Russell S, Norvig P. Artificial Intelligence: A Modern Approach. 2010,
+/// p. 523.
+#[derive(Debug, Clone, Copy)]
+pub struct Enumeration<'a> {
+    bn: &'a BayesianNetwork,
+}
+
+/// variables of a fully specified network in topological order, with the
+/// observations and queried variables of a query resolved to positions
+struct Layout<'a> {
+    bn: &'a BayesianNetwork,
+    order: Vec<&'a String>,
+    variables: Vec<&'a DiscreteVariable>,
+    position: HashMap<&'a String, usize>,
+    observed: Vec<Option<usize>>,
+    queried: Vec<DiscreteVariable>,
+    query_positions: Vec<usize>,
+}
+
+impl<'a> Layout<'a> {
+    fn new(bn: &'a BayesianNetwork, query: &Query) -> Layout<'a> {
+        if !bn.is_fully_specified() {
+            panic!("{bn} has variables without table");
+        }
+        let variable = |name: &str| -> &'a DiscreteVariable {
+            match bn.cpd(name) {
+                None => panic!("{name} not in {bn}"),
+                Some(cpd) => cpd.variable(),
+            }
+        };
+        let order: Vec<&String> = bn.topological_order();
+        let variables: Vec<&DiscreteVariable> = order.iter().map(|v| variable(v)).collect();
+        let position: HashMap<&String, usize> =
+            order.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+        let mut observed: Vec<Option<usize>> = vec![None; order.len()];
+        for (v, s) in query.evidence().iter() {
+            match variable(v).state_index(s) {
+                None => panic!("{v} has no state {s}"),
+                Some(i) => observed[position[v]] = Some(i),
+            }
+        }
+        let queried: Vec<DiscreteVariable> = query
+            .variables()
+            .iter()
+            .map(|v| variable(v).clone())
+            .collect();
+        let query_positions: Vec<usize> = query.variables().iter().map(|v| position[v]).collect();
+        Layout {
+            bn,
+            order,
+            variables,
+            position,
+            observed,
+            queried,
+            query_positions,
+        }
+    }
+
+    /// distribution of the variable at position `i` given the states of
+    /// its parents in `state`
+    fn row(&self, i: usize, state: &[usize]) -> &'a [f64] {
+        let cpd = self.bn.cpd(self.order[i]).unwrap();
+        let parent_states: Vec<usize> = cpd
+            .parents()
+            .iter()
+            .map(|u| state[self.position[u.name()]])
+            .collect();
+        &cpd.table()[cpd.row_index(&parent_states)]
+    }
+
+    /// number of joint states of the queried variables
+    fn query_size(&self) -> usize {
+        self.queried.iter().map(|v| v.cardinality()).product()
+    }
+
+    /// index of the joint state of the queried variables in `state`
+    fn query_index(&self, state: &[usize]) -> usize {
+        let mut index = 0;
+        for (q, var) in self.query_positions.iter().zip(&self.queried) {
+            index = index * var.cardinality() + state[*q];
+        }
+        index
+    }
+}
+
+/// normalize posterior weights of the joint states of the queried
+/// variables into the answer to a query
+fn answer(kind: QueryKind, variables: Vec<DiscreteVariable>, weights: Vec<f64>) -> QueryResult {
+    let total: f64 = weights.iter().sum();
+    if total == 0.0 {
+        panic!("evidence has probability zero");
+    }
+    let values: Vec<f64> = weights.iter().map(|p| p / total).collect();
+    match kind {
+        QueryKind::Marginal => QueryResult::Marginal(Distribution::new(variables, values)),
+        QueryKind::Map => {
+            // first joint state among the most probable ones
+            let mut best = 0;
+            for (i, p) in values.iter().enumerate() {
+                if *p > values[best] {
+                    best = i;
+                }
+            }
+            let mut assignment = BTreeMap::new();
+            let mut rest = best;
+            for v in variables.iter().rev() {
+                assignment.insert(v.name().clone(), v.states()[rest % v.cardinality()].clone());
+                rest /= v.cardinality();
+            }
+            QueryResult::Map {
+                assignment,
+                probability: values[best],
+            }
+        }
+    }
+}
+
+impl<'a> Enumeration<'a> {
+    /// engine answering queries on `bn`, panics if a table is missing
+    pub fn new(bn: &'a BayesianNetwork) -> Enumeration<'a> {
+        if !bn.is_fully_specified() {
+            panic!("{bn} has variables without table");
+        }
+        Enumeration { bn }
+    }
+}
+
+impl InferenceEngine for Enumeration<'_> {
+    fn query(&self, query: &Query) -> QueryResult {
+        let layout = Layout::new(self.bn, query);
+        let mut weights = vec![0.0; layout.query_size()];
+        // odometer over the joint states consistent with the evidence
+        let mut state: Vec<usize> = layout.observed.iter().map(|o| o.unwrap_or(0)).collect();
+        loop {
+            let mut p = 1.0;
+            for i in 0..layout.order.len() {
+                p *= layout.row(i, &state)[state[i]];
+                if p == 0.0 {
+                    break;
+                }
+            }
+            weights[layout.query_index(&state)] += p;
+
+            let mut i = layout.order.len();
+            loop {
+                if i == 0 {
+                    return answer(query.kind(), layout.queried, weights);
+                }
+                i -= 1;
+                if layout.observed[i].is_some() {
+                    continue;
+                }
+                state[i] += 1;
+                if state[i] < layout.variables[i].cardinality() {
+                    break;
+                }
+                state[i] = 0;
+            }
+        }
+    }
+}
+
+/// Exact inference by variable elimination
+/// # Description
+/// Turns every table into a [Factor], reduces the factors by the evidence
+/// and sums the variables that are neither observed nor queried out one at
+/// a time. The elimination order is built greedily: the next variable is
+/// the one whose elimination joins the fewest non adjacent pairs of its
+/// neighbors (min-fill), ties being broken by identifier. The cost is
+/// exponential in the induced width of this order instead of the number of
+/// variables, see [VariableElimination::induced_width]. The network must
+/// be fully specified.
+///
+/// # Example
+/// ```
+/// use pgm_rust::pgm::bayesnet::BayesianNetwork;
+/// use pgm_rust::pgm::cpd::TabularCPD;
+/// use pgm_rust::pgm::query::{InferenceEngine, Query, VariableElimination};
+/// use pgm_rust::pgm::variable::DiscreteVariable;
+///
+/// let rain = DiscreteVariable::new("rain", &["no", "yes"]);
+/// let wet = DiscreteVariable::new("wet", &["no", "yes"]);
+/// let mut bn = BayesianNetwork::from_arcs("bn", &["rain", "wet"], &[("rain", "wet")]);
+/// bn.add_cpd(TabularCPD::marginal(rain.clone(), vec![0.5, 0.5]));
+/// bn.add_cpd(TabularCPD::new(wet, vec![rain], vec![vec![0.9, 0.1], vec![0.1, 0.9]]));
+/// let q = Query::marginal(["wet"]);
+/// let engine = VariableElimination::new(&bn);
+/// engine.elimination_order(&q) == vec!["rain"]; // true
+/// let d = engine.query(&q).into_marginal().unwrap();
+/// (d.probability(&["yes"]) - 0.5).abs() < 1e-12; // true
+/// ```
+///
+/// # References
+/// Koller D, Friedman N. Probabilistic Graphical Models. 2009, p. 297 and
+/// p. 314.
+#[derive(Debug, Clone, Copy)]
+pub struct VariableElimination<'a> {
+    bn: &'a BayesianNetwork,
+}
+
+impl<'a> VariableElimination<'a> {
+    /// engine answering queries on `bn`, panics if a table is missing
+    pub fn new(bn: &'a BayesianNetwork) -> VariableElimination<'a> {
+        if !bn.is_fully_specified() {
+            panic!("{bn} has variables without table");
+        }
+        VariableElimination { bn }
+    }
+
+    /// variables summed out to answer `query`, in elimination order
+    pub fn elimination_order(&self, query: &Query) -> Vec<String> {
+        self.plan(query).0
+    }
+
+    /// Induced width of the elimination order of a query
+    /// # Description
+    /// Largest number of variables in the scope of a factor built while
+    /// answering `query`, minus one. The last factor, over the queried
+    /// variables, is taken into account. Tables of the engine have at most
+    /// `k^(w + 1)` entries when variables have at most `k` states and `w`
+    /// is the induced width.
+    pub fn induced_width(&self, query: &Query) -> usize {
+        self.plan(query).1
+    }
+
+    /// Number of entries of the largest factor built to answer a query
+    /// # Description
+    /// Product of the numbers of states of the variables of the largest
+    /// factor built along [VariableElimination::elimination_order],
+    /// including the last factor over the queried variables. Unlike the
+    /// induced width it accounts for the numbers of states, so it bounds
+    /// the memory and time needed by [InferenceEngine::query]. Saturates
+    /// at [usize::MAX].
+    pub fn largest_factor_size(&self, query: &Query) -> usize {
+        self.plan(query).2
+    }
+
+    /// elimination order, induced width and largest factor size of a query
+    fn plan(&self, query: &Query) -> (Vec<String>, usize, usize) {
+        // interaction graph of the factors once reduced by the evidence
+        let mut neighbors: BTreeMap<&String, BTreeSet<&String>> = BTreeMap::new();
+        for cpd in self.bn.cpds().values() {
+            let scope: Vec<&String> = cpd
+                .parents()
+                .iter()
+                .chain(std::iter::once(cpd.variable()))
+                .map(|v| v.name())
+                .filter(|v| query.evidence().get(v).is_none())
+                .collect();
+            for a in &scope {
+                let ns = neighbors.entry(*a).or_default();
+                ns.extend(scope.iter().filter(|b| *b != a));
+            }
+        }
+        let mut hidden: BTreeSet<&String> = neighbors
+            .keys()
+            .filter(|v| !query.variables().contains(v))
+            .copied()
+            .collect();
+        let cardinality = |v: &String| self.bn.cpd(v).unwrap().variable().cardinality();
+        let mut order = Vec::with_capacity(hidden.len());
+        let mut width = 0;
+        let mut largest = 0;
+        while !hidden.is_empty() {
+            let mut best: Option<(usize, &String)> = None;
+            for v in &hidden {
+                let ns: Vec<&String> = neighbors[v].iter().copied().collect();
+                let mut fill = 0;
+                for (i, a) in ns.iter().enumerate() {
+                    fill += ns[i + 1..]
+                        .iter()
+                        .filter(|b| !neighbors[a].contains(*b))
+                        .count();
+                }
+                if best.is_none_or(|(f, _)| fill < f) {
+                    best = Some((fill, v));
+                }
+            }
+            let v = best.unwrap().1;
+            let ns = neighbors.remove(v).unwrap();
+            width = width.max(ns.len());
+            let entries = ns
+                .iter()
+                .fold(cardinality(v), |acc, u| acc.saturating_mul(cardinality(u)));
+            largest = largest.max(entries);
+            for a in &ns {
+                let na = neighbors.get_mut(a).unwrap();
+                na.remove(v);
+                na.extend(ns.iter().filter(|b| *b != a));
+            }
+            hidden.remove(v);
+            order.push(v.clone());
+        }
+        // the queried variables left end up in a single factor
+        width = width.max(neighbors.len().saturating_sub(1));
+        let entries = neighbors
+            .keys()
+            .fold(1, |acc: usize, u| acc.saturating_mul(cardinality(u)));
+        largest = largest.max(entries);
+        (order, width, largest)
+    }
+
+    /// tables of the network as factors reduced by the observations
+    fn reduced_factors(&self, layout: &Layout) -> Vec<Factor> {
+        let mut factors: Vec<Factor> = Vec::with_capacity(layout.order.len());
+        for v in &layout.order {
+            let mut f = Factor::from(self.bn.cpd(v).unwrap());
+            let names: Vec<String> = f.scope().iter().map(|u| u.name().clone()).collect();
+            for u in &names {
+                if let Some(s) = layout.observed[layout.position[u]] {
+                    f = f.reduce(u, s);
+                }
+            }
+            factors.push(f);
+        }
+        factors
+    }
+}
+
+/// product of the factors mentioning `v` and the factors left
+fn split_product(factors: Vec<Factor>, v: &str) -> (Factor, Vec<Factor>) {
+    let (with, without): (Vec<Factor>, Vec<Factor>) =
+        factors.into_iter().partition(|f| f.position(v).is_some());
+    // the table of v always mentions it
+    let product = with[1..]
+        .iter()
+        .fold(with[0].clone(), |acc, f| acc.product(f));
+    (product, without)
+}
+
+impl InferenceEngine for VariableElimination<'_> {
+    fn query(&self, query: &Query) -> QueryResult {
+        let layout = Layout::new(self.bn, query);
+        let mut factors = self.reduced_factors(&layout);
+        for v in self.elimination_order(query) {
+            let (product, rest) = split_product(factors, &v);
+            factors = rest;
+            factors.push(product.sum_out(&v));
+        }
+        let joint = factors
+            .iter()
+            .fold(Factor::new(Vec::new(), vec![1.0]), |acc, f| acc.product(f));
+        let positions: Vec<usize> = layout
+            .queried
+            .iter()
+            .map(|q| joint.position(q.name()).unwrap())
+            .collect();
+        let mut weights = vec![0.0; layout.query_size()];
+        let mut states = vec![0; positions.len()];
+        for (index, w) in weights.iter_mut().enumerate() {
+            let mut rest = index;
+            for (q, var) in layout.queried.iter().enumerate().rev() {
+                states[positions[q]] = rest % var.cardinality();
+                rest /= var.cardinality();
+            }
+            *w = joint.value(&states);
+        }
+        answer(query.kind(), layout.queried, weights)
+    }
+}
+
+/// Most probable explanation of evidence
+/// # Description
+/// Returns the most probable joint state of every unobserved variable of
+/// `bn` given `evidence`, with its posterior probability as score. The
+/// answer is computed by max-product variable elimination: variables are
+/// eliminated in the order of [VariableElimination::elimination_order],
+/// maximizing instead of summing, and the maximizing state of every
+/// eliminated variable given the variables left is kept for the traceback.
+/// The traceback assigns the variables in reverse elimination order, ties
+/// being broken towards the first state of a variable. The probability of
+/// the evidence comes from a sum-product elimination in the same order.
+/// The cost is exponential in the induced width of the order, not in the
+/// number of variables. Panics if a table of `bn` is missing, if
+/// `evidence` mentions unknown variables or states, or if it has
+/// probability zero.
+///
+/// # Example
+/// ```
+/// use pgm_rust::pgm::bayesnet::BayesianNetwork;
+/// use pgm_rust::pgm::cpd::TabularCPD;
+/// use pgm_rust::pgm::query::{map_query, Evidence};
+/// use pgm_rust::pgm::variable::DiscreteVariable;
+///
+/// let rain = DiscreteVariable::new("rain", &["no", "yes"]);
+/// let wet = DiscreteVariable::new("wet", &["no", "yes"]);
+/// let mut bn = BayesianNetwork::from_arcs("bn", &["rain", "wet"], &[("rain", "wet")]);
+/// bn.add_cpd(TabularCPD::marginal(rain.clone(), vec![0.5, 0.5]));
+/// bn.add_cpd(TabularCPD::new(wet, vec![rain], vec![vec![0.9, 0.1], vec![0.1, 0.9]]));
+/// let (assignment, score) = map_query(&bn, &Evidence::from([("wet", "yes")]));
+/// assignment["rain"] == "yes"; // true
+/// (score - 0.9).abs() < 1e-12; // true
+/// ```
+///
+/// # References
+/// Koller D, Friedman N. Probabilistic Graphical Models. 2009, p. 552.
+pub fn map_query(bn: &BayesianNetwork, evidence: &Evidence) -> (BTreeMap<String, String>, f64) {
+    let engine = VariableElimination::new(bn);
+    let query = Query::map(Vec::<&str>::new()).given(evidence.clone());
+    let layout = Layout::new(bn, &query);
+    let order = engine.elimination_order(&query);
+    let mut summed = engine.reduced_factors(&layout);
+    let mut maximized = summed.clone();
+    let mut traceback: Vec<(Vec<DiscreteVariable>, Vec<usize>)> = Vec::new();
+    for v in &order {
+        let (product, rest) = split_product(summed, v);
+        summed = rest;
+        summed.push(product.sum_out(v));
+        let (product, rest) = split_product(maximized, v);
+        maximized = rest;
+        let max = product.maximize_out(v);
+        traceback.push((max.scope().to_vec(), product.argmax(v)));
+        maximized.push(max);
+    }
+    // every variable left is observed, so factors have an empty scope
+    let total = |fs: &[Factor]| fs.iter().map(|f| f.value(&[])).product::<f64>();
+    let evidence_probability = total(&summed);
+    if evidence_probability == 0.0 {
+        panic!("evidence has probability zero");
+    }
+    let mut states: HashMap<&String, usize> = HashMap::new();
+    for (v, (scope, maximizers)) in order.iter().zip(&traceback).rev() {
+        let mut index = 0;
+        for u in scope {
+            index = index * u.cardinality() + states[u.name()];
+        }
+        states.insert(v, maximizers[index]);
+    }
+    let assignment = states
+        .into_iter()
+        .map(|(v, s)| {
+            let variable = layout.variables[layout.position[v]];
+            (v.clone(), variable.states()[s].clone())
+        })
+        .collect();
+    (assignment, total(&maximized) / evidence_probability)
+}
+
+/// Approximate inference by likelihood weighting
+/// # Description
+/// Draws joint states forward in topological order, observed variables
+/// being fixed to their observation. Every sample is weighted by the
+/// probability of the observations given its other states. The posterior
+/// is estimated by the normalized weights. Samples are drawn from a
+/// [SplitMix64] seeded by `seed`, so answers are reproducible. The samples
+/// themselves are available from
+/// [crate::pgm::inference::sampling::likelihood_weighting].
+///
+/// # Example
+/// ```
+/// use pgm_rust::pgm::bayesnet::BayesianNetwork;
+/// use pgm_rust::pgm::cpd::TabularCPD;
+/// use pgm_rust::pgm::query::{InferenceEngine, LikelihoodWeighting, Query, QueryResult};
+/// use pgm_rust::pgm::variable::DiscreteVariable;
+///
+/// let rain = DiscreteVariable::new("rain", &["no", "yes"]);
+/// let wet = DiscreteVariable::new("wet", &["no", "yes"]);
+/// let mut bn = BayesianNetwork::from_arcs("bn", &["rain", "wet"], &[("rain", "wet")]);
+/// bn.add_cpd(TabularCPD::marginal(rain.clone(), vec![0.5, 0.5]));
+/// bn.add_cpd(TabularCPD::new(wet, vec![rain], vec![vec![0.9, 0.1], vec![0.1, 0.9]]));
+/// let q = Query::marginal(["rain"]).given([("wet", "yes")]);
+/// if let QueryResult::Marginal(d) = LikelihoodWeighting::new(&bn, 10_000, 7).query(&q) {
+///     (d.probability(&["yes"]) - 0.9).abs() < 0.02; // true
+/// }
+/// ```
+///
+/// # References
+/// Koller D, Friedman N. Probabilistic Graphical Models. 2009, p. 493.
+#[derive(Debug, Clone, Copy)]
+pub struct LikelihoodWeighting<'a> {
+    bn: &'a BayesianNetwork,
+    nb_samples: usize,
+    seed: u64,
+}
+
+impl<'a> LikelihoodWeighting<'a> {
+    /// engine drawing `nb_samples` samples per query, panics if a table of
+    /// `bn` is missing
+    pub fn new(bn: &'a BayesianNetwork, nb_samples: usize, seed: u64) -> LikelihoodWeighting<'a> {
+        if !bn.is_fully_specified() {
+            panic!("{bn} has variables without table");
+        }
+        LikelihoodWeighting {
+            bn,
+            nb_samples,
+            seed,
+        }
+    }
+}
+
+impl InferenceEngine for LikelihoodWeighting<'_> {
+    fn query(&self, query: &Query) -> QueryResult {
+        let layout = Layout::new(self.bn, query);
+        let forward = Forward::new(self.bn);
+        let mut weights = vec![0.0; layout.query_size()];
+        let mut rng = SplitMix64::new(self.seed);
+        let mut state = vec![0; layout.order.len()];
+        for _ in 0..self.nb_samples {
+            let w = forward.sample(&mut rng, &layout.observed, &mut state);
+            weights[layout.query_index(&state)] += w;
+        }
+        answer(query.kind(), layout.queried, weights)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pgm::cpd::TabularCPD;
+
+    fn binary(n: &str) -> DiscreteVariable {
+        DiscreteVariable::new(n, &["f", "t"])
+    }
+
+    /// cloudy -> rain, cloudy -> sprinkler, rain -> wet, sprinkler -> wet
+    fn mk_sprinkler() -> BayesianNetwork {
+        let mut bn = BayesianNetwork::from_arcs(
+            "sprinkler",
+            &["c", "r", "s", "w"],
+            &[("c", "r"), ("c", "s"), ("r", "w"), ("s", "w")],
+        );
+        bn.add_cpd(TabularCPD::marginal(binary("c"), vec![0.5, 0.5]));
+        bn.add_cpd(TabularCPD::new(
+            binary("s"),
+            vec![binary("c")],
+            vec![vec![0.5, 0.5], vec![0.9, 0.1]],
+        ));
+        bn.add_cpd(TabularCPD::new(
+            binary("r"),
+            vec![binary("c")],
+            vec![vec![0.8, 0.2], vec![0.2, 0.8]],
+        ));
+        bn.add_cpd(TabularCPD::new(
+            binary("w"),
+            vec![binary("s"), binary("r")],
+            vec![
+                vec![1.0, 0.0],
+                vec![0.1, 0.9],
+                vec![0.1, 0.9],
+                vec![0.01, 0.99],
+            ],
+        ));
+        bn
+    }
+
+    #[test]
+    fn test_query_builder() {
+        let q = Query::map(["A"])
+            .given([("B", "x")])
+            .given(Evidence::from([("C", "y")]));
+        assert_eq!(q.kind(), QueryKind::Map);
+        assert_eq!(q.variables(), ["A"]);
+        assert_eq!(q.evidence().len(), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_query_observed_variable() {
+        Query::marginal(["A"]).given([("A", "x")]);
+    }
+
+    #[test]
+    fn test_enumeration_marginal() {
+        let bn = mk_sprinkler();
+        let engine = Enumeration::new(&bn);
+        let q = Query::marginal(["r"]).given([("w", "t")]);
+        let d = match engine.query(&q) {
+            QueryResult::Marginal(d) => d,
+            _ => panic!("marginal expected"),
+        };
+        // Russell and Norvig sprinkler network, P(r | w) = 0.7079
+        assert!((d.probability(&["t"]) - 0.707_927_677_329_624_5).abs() < 1e-9);
+        let q = Query::marginal(["s", "r"]);
+        if let QueryResult::Marginal(d) = engine.query(&q) {
+            assert!((d.marginal("r").probability(&["t"]) - 0.5).abs() < 1e-12);
+            assert!((d.probability(&["t", "t"]) - 0.09).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_enumeration_map() {
+        let bn = mk_sprinkler();
+        let q = Query::map(["c", "s"]).given([("w", "t"), ("r", "f")]);
+        match Enumeration::new(&bn).query(&q) {
+            QueryResult::Map {
+                assignment,
+                probability,
+            } => {
+                assert_eq!(assignment["c"], "f");
+                assert_eq!(assignment["s"], "t");
+                assert!(probability > 0.5);
+            }
+            _ => panic!("map expected"),
+        }
+    }
+
+    #[test]
+    fn test_map_query() {
+        let bn = mk_sprinkler();
+        let (assignment, score) = map_query(&bn, &Evidence::from([("w", "t")]));
+        assert_eq!(assignment.len(), 3);
+        assert_eq!(assignment["c"], "t");
+        assert_eq!(assignment["s"], "f");
+        assert_eq!(assignment["r"], "t");
+        let p_w = match Enumeration::new(&bn).query(&Query::marginal(["w"])) {
+            QueryResult::Marginal(d) => d.probability(&["t"]),
+            _ => panic!("marginal expected"),
+        };
+        // P(c, not s, r, w) = 0.5 * 0.9 * 0.8 * 0.9
+        assert!((score - 0.324 / p_w).abs() < 1e-12);
+        let (all_observed, score) = map_query(
+            &bn,
+            &Evidence::from([("c", "t"), ("s", "f"), ("r", "t"), ("w", "t")]),
+        );
+        assert!(all_observed.is_empty());
+        assert_eq!(score, 1.0);
+        assert_eq!(
+            Enumeration::new(&bn)
+                .query(&Query::marginal(["c"]))
+                .into_map(),
+            None
+        );
+    }
+
+    /// `k` disconnected copies of the sprinkler network, the variables of
+    /// copy `i` being suffixed by `i`
+    fn mk_sprinkler_copies(k: usize) -> BayesianNetwork {
+        let one = mk_sprinkler();
+        let names: Vec<String> = (0..k)
+            .flat_map(|i| ["c", "r", "s", "w"].map(|v| format!("{v}{i}")))
+            .collect();
+        let arcs: Vec<(String, String)> = (0..k)
+            .flat_map(|i| {
+                [("c", "r"), ("c", "s"), ("r", "w"), ("s", "w")]
+                    .map(|(a, b)| (format!("{a}{i}"), format!("{b}{i}")))
+            })
+            .collect();
+        let name_refs: Vec<&str> = names.iter().map(|v| v.as_str()).collect();
+        let arc_refs: Vec<(&str, &str)> =
+            arcs.iter().map(|(a, b)| (a.as_str(), b.as_str())).collect();
+        let mut bn = BayesianNetwork::from_arcs("copies", &name_refs, &arc_refs);
+        for i in 0..k {
+            for v in ["c", "r", "s", "w"] {
+                let cpd = one.cpd(v).unwrap();
+                let rename = |u: &DiscreteVariable| binary(&format!("{}{i}", u.name()));
+                bn.add_cpd(TabularCPD::new(
+                    rename(cpd.variable()),
+                    cpd.parents().iter().map(rename).collect(),
+                    cpd.table().to_vec(),
+                ));
+            }
+        }
+        bn
+    }
+
+    #[test]
+    fn test_map_query_large() {
+        // 2^40 joint states, out of reach of enumeration, but every copy is
+        // independent of the others so its part of the answer is checked
+        // by enumeration on a single copy
+        let k = 10;
+        let bn = mk_sprinkler_copies(k);
+        let observations: Vec<(String, &str)> = (0..k)
+            .filter(|i| i % 3 != 2)
+            .map(|i| (format!("{}{i}", if i % 3 == 0 { "w" } else { "s" }), "t"))
+            .collect();
+        let mut evidence = Evidence::new();
+        for (v, s) in &observations {
+            evidence.observe(v, s);
+        }
+        let (assignment, score) = map_query(&bn, &evidence);
+        assert_eq!(assignment.len(), 4 * k - observations.len());
+        let one = mk_sprinkler();
+        let mut expected_score = 1.0;
+        for i in 0..k {
+            let local: Vec<(&str, &str)> = observations
+                .iter()
+                .filter(|(v, _)| v.ends_with(&i.to_string()))
+                .map(|(v, s)| (&v[..1], *s))
+                .collect();
+            let variables: Vec<&str> = ["c", "r", "s", "w"]
+                .into_iter()
+                .filter(|v| local.iter().all(|(u, _)| u != v))
+                .collect();
+            let mut local_evidence = Evidence::new();
+            for (v, s) in &local {
+                local_evidence.observe(v, s);
+            }
+            let q = Query::map(variables.clone()).given(local_evidence);
+            let (expected, p) = Enumeration::new(&one).query(&q).into_map().unwrap();
+            for v in variables {
+                assert_eq!(assignment[&format!("{v}{i}")], expected[v]);
+            }
+            expected_score *= p;
+        }
+        assert!((score - expected_score).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_map_query_against_enumeration() {
+        // x_i has parents x_(i-1) and x_(i-3), 4096 joint states
+        let n = 12;
+        let names: Vec<String> = (0..n).map(|i| format!("x{i:02}")).collect();
+        let mut arcs: Vec<(&str, &str)> = Vec::new();
+        for i in 1..n {
+            arcs.push((&names[i - 1], &names[i]));
+            if i >= 3 {
+                arcs.push((&names[i - 3], &names[i]));
+            }
+        }
+        let name_refs: Vec<&str> = names.iter().map(|v| v.as_str()).collect();
+        let mut bn = BayesianNetwork::from_arcs("chain", &name_refs, &arcs);
+        for (i, v) in names.iter().enumerate() {
+            let parents: Vec<DiscreteVariable> = arcs
+                .iter()
+                .filter(|(_, b)| b == v)
+                .map(|(a, _)| binary(a))
+                .collect();
+            let table = (0..1 << parents.len())
+                .map(|r| {
+                    let p = ((7 * i + 3 * r) % 10) as f64 / 11.0 + 0.04;
+                    vec![1.0 - p, p]
+                })
+                .collect();
+            bn.add_cpd(TabularCPD::new(binary(v), parents, table));
+        }
+        let exact = Enumeration::new(&bn);
+        for observations in [vec![], vec![("x11", "t"), ("x05", "f")], vec![("x00", "t")]] {
+            let evidence: Evidence = {
+                let mut e = Evidence::new();
+                for (v, s) in &observations {
+                    e.observe(v, s);
+                }
+                e
+            };
+            let variables: Vec<&str> = name_refs
+                .iter()
+                .copied()
+                .filter(|v| evidence.get(v).is_none())
+                .collect();
+            let q = Query::map(variables).given(evidence.clone());
+            let (expected, p) = exact.query(&q).into_map().unwrap();
+            let (assignment, score) = map_query(&bn, &evidence);
+            assert_eq!(assignment, expected);
+            assert!((score - p).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_enumeration_impossible_evidence() {
+        let bn = mk_sprinkler();
+        Enumeration::new(&bn).query(&Query::marginal(["c"]).given([
+            ("s", "f"),
+            ("r", "f"),
+            ("w", "t"),
+        ]));
+    }
+
+    #[test]
+    fn test_variable_elimination() {
+        let bn = mk_sprinkler();
+        let exact = Enumeration::new(&bn);
+        let ve = VariableElimination::new(&bn);
+        let queries = [
+            Query::marginal(["r"]).given([("w", "t")]),
+            Query::marginal(["w", "c"]),
+            Query::marginal(["s", "r"]).given([("c", "t")]),
+            Query::map(["c", "s"]).given([("w", "t"), ("r", "f")]),
+            Query::marginal(Vec::<&str>::new()).given([("w", "t")]),
+        ];
+        for q in &queries {
+            match (ve.query(q), exact.query(q)) {
+                (QueryResult::Marginal(d1), QueryResult::Marginal(d2)) => {
+                    assert_eq!(d1.variables(), d2.variables());
+                    for (p1, p2) in d1.values().iter().zip(d2.values()) {
+                        assert!((p1 - p2).abs() < 1e-12);
+                    }
+                }
+                (
+                    QueryResult::Map { assignment: a1, .. },
+                    QueryResult::Map { assignment: a2, .. },
+                ) => assert_eq!(a1, a2),
+                _ => panic!("answers of different kinds"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_elimination_order() {
+        let bn = mk_sprinkler();
+        let ve = VariableElimination::new(&bn);
+        let q = Query::marginal(["w"]);
+        // r and s share the child w, so eliminating c adds no edge
+        assert_eq!(ve.elimination_order(&q), vec!["c", "r", "s"]);
+        assert_eq!(ve.induced_width(&q), 2);
+        assert_eq!(ve.largest_factor_size(&q), 8);
+        let q = Query::marginal(["c"]).given([("r", "t"), ("s", "t")]);
+        assert_eq!(ve.elimination_order(&q), vec!["w"]);
+        assert_eq!(ve.induced_width(&q), 0);
+        assert_eq!(ve.largest_factor_size(&q), 2);
+    }
+
+    #[test]
+    fn test_likelihood_weighting() {
+        let bn = mk_sprinkler();
+        let q = Query::marginal(["r"]).given([("w", "t")]);
+        let d = match LikelihoodWeighting::new(&bn, 20_000, 3).query(&q) {
+            QueryResult::Marginal(d) => d,
+            _ => panic!("marginal expected"),
+        };
+        assert!((d.probability(&["t"]) - 0.707_927_677_329_624_5).abs() < 0.02);
+        let again = LikelihoodWeighting::new(&bn, 20_000, 3).query(&q);
+        assert_eq!(again, QueryResult::Marginal(d));
+    }
+}
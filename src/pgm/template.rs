@@ -0,0 +1,285 @@
+//! template models repeating structures over plates
+use crate::pgm::bayesnet::BayesianNetwork;
+use crate::pgm::cpd::TabularCPD;
+use crate::pgm::variable::DiscreteVariable;
+use std::collections::HashMap;
+
+/// variable of a template, repeated over the plates containing it
+#[derive(Debug, Clone, PartialEq)]
+struct TemplateNode {
+    variable: DiscreteVariable,
+    plates: Vec<String>,
+    parents: Vec<String>,
+    table: Option<Vec<Vec<f64>>>,
+}
+
+/// every assignment of indices to `plates` in lexicographic order
+fn assignments(plates: &[String], bindings: &HashMap<String, usize>) -> Vec<Vec<usize>> {
+    let mut result: Vec<Vec<usize>> = vec![Vec::new()];
+    for p in plates {
+        let size = match bindings.get(p) {
+            None => panic!("plate {p} has no size"),
+            Some(s) => *s,
+        };
+        result = result
+            .into_iter()
+            .flat_map(|a| {
+                (0..size).map(move |i| {
+                    let mut b = a.clone();
+                    b.push(i);
+                    b
+                })
+            })
+            .collect();
+    }
+    result
+}
+
+/// identifier of the instance of `name` at `indices`, such as `X[3]` or
+/// `X[1,2]`
+pub fn ground_name(name: &str, indices: &[usize]) -> String {
+    if indices.is_empty() {
+        return name.to_string();
+    }
+    let idx: Vec<String> = indices.iter().map(|i| i.to_string()).collect();
+    format!("{}[{}]", name, idx.join(","))
+}
+
+/// Plate model
+/// # Description
+/// A template of a bayesian network whose variables may be declared inside
+/// plates. Grounding the template with a size per plate creates one
+/// instance of a variable per index of the plates containing it. A parent
+/// must belong to a subset of the plates of its child, and an instance
+/// depends on the instance of its parent with the same indices on the
+/// shared plates. Tables are shared by all instances of a variable.
+///
+/// # Example
+/// ```
+/// use pgm_rust::graph::traits::graph::Graph;
+/// use pgm_rust::graph::traits::graph_obj::GraphObject;
+/// use pgm_rust::pgm::template::PlateModel;
+/// use pgm_rust::pgm::variable::DiscreteVariable;
+/// use std::collections::HashMap;
+///
+/// let mut model = PlateModel::new("coins");
+/// model.add_plate("tosses");
+/// model.add_node(DiscreteVariable::new("bias", &["fair", "loaded"]), &[]);
+/// model.add_node(DiscreteVariable::new("X", &["h", "t"]), &["tosses"]);
+/// model.add_arc("bias", "X");
+/// let bn = model.ground(&HashMap::from([("tosses".to_string(), 3)]));
+/// bn.order() == 4; // true
+/// bn.parents("X[2]")[0].id() == "bias"; // true
+/// ```
+///
+/// # References
+/// Koller D, Friedman N. Probabilistic Graphical Models. 2009, p. 216.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlateModel {
+    id: String,
+    plates: Vec<String>,
+    nodes: Vec<TemplateNode>,
+}
+
+impl PlateModel {
+    /// empty template, grounded networks get `id` as identifier
+    pub fn new(id: &str) -> PlateModel {
+        PlateModel {
+            id: id.to_string(),
+            plates: Vec::new(),
+            nodes: Vec::new(),
+        }
+    }
+
+    /// declare a plate
+    pub fn add_plate(&mut self, name: &str) {
+        if self.plates.iter().any(|p| p == name) {
+            panic!("plate {name} is already declared");
+        }
+        self.plates.push(name.to_string());
+    }
+
+    fn node_index(&self, name: &str) -> usize {
+        match self.nodes.iter().position(|n| n.variable.name() == name) {
+            None => panic!("{name} is not a variable of the template {}", self.id),
+            Some(i) => i,
+        }
+    }
+
+    /// declare a variable repeated over `plates`, instance indices follow
+    /// the order of `plates`
+    pub fn add_node(&mut self, variable: DiscreteVariable, plates: &[&str]) {
+        if self
+            .nodes
+            .iter()
+            .any(|n| n.variable.name() == variable.name())
+        {
+            panic!("{} is already declared", variable.name());
+        }
+        for p in plates {
+            if !self.plates.iter().any(|q| q == p) {
+                panic!("plate {p} is not declared");
+            }
+        }
+        self.nodes.push(TemplateNode {
+            variable,
+            plates: plates.iter().map(|p| p.to_string()).collect(),
+            parents: Vec::new(),
+            table: None,
+        });
+    }
+
+    /// Add an arc of the template
+    /// # Description
+    /// Panics if `from` belongs to a plate that does not contain `to`.
+    /// Parents are kept in the order of their arcs, which is the order of
+    /// the parents of the tables, see [PlateModel::set_table].
+    pub fn add_arc(&mut self, from: &str, to: &str) {
+        let (i, j) = (self.node_index(from), self.node_index(to));
+        for p in &self.nodes[i].plates {
+            if !self.nodes[j].plates.contains(p) {
+                panic!("{from} is in plate {p} but {to} is not");
+            }
+        }
+        if self.nodes[j].parents.iter().any(|q| q == from) {
+            panic!("arc {from}->{to} is already declared");
+        }
+        self.nodes[j].parents.push(from.to_string());
+        self.nodes[j].table = None;
+    }
+
+    /// Set the table shared by the instances of a variable
+    /// # Description
+    /// Rows are configurations of the parents in arc order, see
+    /// [TabularCPD]. Panics if the table is not valid for the variable.
+    pub fn set_table(&mut self, name: &str, table: Vec<Vec<f64>>) {
+        let i = self.node_index(name);
+        let parents: Vec<DiscreteVariable> = self.nodes[i]
+            .parents
+            .iter()
+            .map(|p| self.nodes[self.node_index(p)].variable.clone())
+            .collect();
+        // validate with the template variables
+        TabularCPD::new(self.nodes[i].variable.clone(), parents, table.clone());
+        self.nodes[i].table = Some(table);
+    }
+
+    /// Expand the template into a bayesian network
+    /// # Description
+    /// `bindings` gives the size of every plate, an instance index ranges
+    /// from 0 to the size minus one. Instances are named with
+    /// [ground_name]. Instances of variables with a table get a copy of it.
+    /// Panics if a plate is not bound or if the template has a cycle.
+    pub fn ground(&self, bindings: &HashMap<String, usize>) -> BayesianNetwork {
+        let mut names: Vec<String> = Vec::new();
+        let mut arcs: Vec<(String, String)> = Vec::new();
+        let mut cpds: Vec<TabularCPD> = Vec::new();
+        for node in &self.nodes {
+            for indices in assignments(&node.plates, bindings) {
+                let name = ground_name(node.variable.name(), &indices);
+                let mut parents: Vec<DiscreteVariable> = Vec::new();
+                for p in &node.parents {
+                    let parent = &self.nodes[self.node_index(p)];
+                    let parent_indices: Vec<usize> = parent
+                        .plates
+                        .iter()
+                        .map(|q| indices[node.plates.iter().position(|r| r == q).unwrap()])
+                        .collect();
+                    let parent_name = ground_name(p, &parent_indices);
+                    arcs.push((parent_name.clone(), name.clone()));
+                    let states: Vec<&str> = parent
+                        .variable
+                        .states()
+                        .iter()
+                        .map(|s| s.as_str())
+                        .collect();
+                    parents.push(DiscreteVariable::new(&parent_name, &states));
+                }
+                if let Some(table) = &node.table {
+                    let states: Vec<&str> =
+                        node.variable.states().iter().map(|s| s.as_str()).collect();
+                    let variable = DiscreteVariable::new(&name, &states);
+                    cpds.push(TabularCPD::new(variable, parents, table.clone()));
+                }
+                names.push(name);
+            }
+        }
+        let variables: Vec<&str> = names.iter().map(|n| n.as_str()).collect();
+        let arcs: Vec<(&str, &str)> = arcs.iter().map(|(a, b)| (a.as_str(), b.as_str())).collect();
+        let mut bn = BayesianNetwork::from_arcs(&self.id, &variables, &arcs);
+        for cpd in cpds {
+            bn.add_cpd(cpd);
+        }
+        bn
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::traits::graph::Graph as GraphTrait;
+    use crate::graph::traits::graph_obj::GraphObject;
+
+    /// students with a grade per course, the grade depends on the
+    /// intelligence of the student and the difficulty of the course
+    fn mk_university() -> PlateModel {
+        let level = |n: &str| DiscreteVariable::new(n, &["low", "high"]);
+        let mut model = PlateModel::new("university");
+        model.add_plate("student");
+        model.add_plate("course");
+        model.add_node(level("I"), &["student"]);
+        model.add_node(level("D"), &["course"]);
+        model.add_node(
+            DiscreteVariable::new("G", &["a", "b"]),
+            &["student", "course"],
+        );
+        model.add_arc("I", "G");
+        model.add_arc("D", "G");
+        model.set_table("I", vec![vec![0.7, 0.3]]);
+        model.set_table("D", vec![vec![0.6, 0.4]]);
+        model.set_table(
+            "G",
+            vec![
+                vec![0.3, 0.7],
+                vec![0.05, 0.95],
+                vec![0.9, 0.1],
+                vec![0.5, 0.5],
+            ],
+        );
+        model
+    }
+
+    #[test]
+    fn test_ground() {
+        let bindings = HashMap::from([("student".to_string(), 2), ("course".to_string(), 3)]);
+        let bn = mk_university().ground(&bindings);
+        assert_eq!(bn.id(), "university");
+        assert_eq!(bn.order(), 2 + 3 + 6);
+        assert_eq!(bn.size(), 12);
+        let parents: Vec<&String> = bn.parents("G[1,2]").iter().map(|p| p.id()).collect();
+        assert_eq!(parents, vec!["D[2]", "I[1]"]);
+        assert!(bn.is_fully_specified());
+        let g = bn.cpd("G[0,1]").unwrap();
+        assert_eq!(g.probability("b", &["high", "low"]), 0.1);
+    }
+
+    #[test]
+    fn test_ground_empty_plate() {
+        let bindings = HashMap::from([("student".to_string(), 0), ("course".to_string(), 2)]);
+        let bn = mk_university().ground(&bindings);
+        assert_eq!(bn.order(), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_add_arc_out_of_plate() {
+        let mut model = mk_university();
+        model.add_arc("G", "D");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_ground_unbound_plate() {
+        mk_university().ground(&HashMap::from([("student".to_string(), 2)]));
+    }
+}
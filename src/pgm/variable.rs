@@ -0,0 +1,112 @@
+//! discrete random variables
+use std::fmt;
+
+/// Discrete random variable with named states
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DiscreteVariable {
+    name: String,
+    states: Vec<String>,
+}
+
+impl DiscreteVariable {
+    /// Create a variable from its name and its states
+    /// # Description
+    /// A variable must have at least one state and its states must be
+    /// distinct. The order of the states is kept, it is the order used by
+    /// probability tables.
+    ///
+    /// # Example
+    /// ```
+    /// use pgm_rust::pgm::variable::DiscreteVariable;
+    ///
+    /// let rain = DiscreteVariable::new("rain", &["no", "yes"]);
+    /// rain.cardinality() == 2; // true
+    /// rain.state_index("yes") == Some(1); // true
+    /// ```
+    pub fn new(name: &str, states: &[&str]) -> DiscreteVariable {
+        match DiscreteVariable::try_new(name, states) {
+            Err(msg) => panic!("{msg}"),
+            Ok(v) => v,
+        }
+    }
+
+    /// Create a variable from its name and its states, see
+    /// [DiscreteVariable::new]
+    /// # Description
+    /// Returns the reason as an error instead of panicking when there is
+    /// no state or when a state is repeated.
+    ///
+    /// # Example
+    /// ```
+    /// use pgm_rust::pgm::variable::DiscreteVariable;
+    ///
+    /// DiscreteVariable::try_new("rain", &["no", "no"]).is_err(); // true
+    /// ```
+    pub fn try_new(name: &str, states: &[&str]) -> Result<DiscreteVariable, String> {
+        if states.is_empty() {
+            return Err(format!("variable {name} has no state"));
+        }
+        for (i, s) in states.iter().enumerate() {
+            if states[..i].contains(s) {
+                return Err(format!("state {s} of variable {name} is repeated"));
+            }
+        }
+        Ok(DiscreteVariable {
+            name: name.to_string(),
+            states: states.iter().map(|s| s.to_string()).collect(),
+        })
+    }
+
+    /// name of the variable
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// states of the variable in table order
+    pub fn states(&self) -> &[String] {
+        &self.states
+    }
+
+    /// number of states of the variable
+    pub fn cardinality(&self) -> usize {
+        self.states.len()
+    }
+
+    /// position of a state in table order
+    pub fn state_index(&self, state: &str) -> Option<usize> {
+        self.states.iter().position(|s| s == state)
+    }
+}
+
+impl fmt::Display for DiscreteVariable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{{{}}}", self.name, self.states.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let v = DiscreteVariable::new("X", &["a", "b", "c"]);
+        assert_eq!(v.name(), "X");
+        assert_eq!(v.cardinality(), 3);
+        assert_eq!(v.state_index("c"), Some(2));
+        assert_eq!(v.state_index("d"), None);
+        assert_eq!(v.to_string(), "X{a, b, c}");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_repeated_state() {
+        DiscreteVariable::new("X", &["a", "a"]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_no_state() {
+        DiscreteVariable::new("X", &[]);
+    }
+}
@@ -0,0 +1,116 @@
+//! seeded pseudo random number generation
+//!
+//! Randomized operations of the library take a seed and draw from
+//! [SplitMix64], so that their outputs are reproducible between runs and
+//! platforms.
+
+/// SplitMix64 pseudo random number generator.
+/// It is small and fast, and its output only depends on the seed. It is not
+/// suitable for cryptographic use.
+///
+/// # References
+/// Steele G, Lea D, Flood C. Fast splittable pseudorandom number generators.
+/// 2014.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    /// create a generator from a seed
+    pub fn new(seed: u64) -> SplitMix64 {
+        SplitMix64 { state: seed }
+    }
+
+    /// next 64 random bits
+    /// # Example
+    /// ```
+    /// use pgm_rust::rng::SplitMix64;
+    ///
+    /// let mut r1 = SplitMix64::new(42);
+    /// let mut r2 = SplitMix64::new(42);
+    /// r1.next_u64() == r2.next_u64(); // true
+    /// ```
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// uniform float in `[0, 1)`
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// uniform integer in `[0, n)`, panics if `n` is zero
+    pub fn gen_range(&mut self, n: usize) -> usize {
+        if n == 0 {
+            panic!("can not draw from an empty range");
+        }
+        let n = n as u64;
+        // reject the incomplete last block to avoid modulo bias
+        let zone = u64::MAX - (u64::MAX % n);
+        loop {
+            let x = self.next_u64();
+            if x < zone {
+                return (x % n) as usize;
+            }
+        }
+    }
+
+    /// shuffle a slice in place with the Fisher-Yates algorithm
+    pub fn shuffle<T>(&mut self, xs: &mut [T]) {
+        for i in (1..xs.len()).rev() {
+            let j = self.gen_range(i + 1);
+            xs.swap(i, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_u64_reference() {
+        // first outputs for seed 1234567 of the reference implementation
+        let mut r = SplitMix64::new(1234567);
+        assert_eq!(r.next_u64(), 6457827717110365317);
+        assert_eq!(r.next_u64(), 3203168211198807973);
+    }
+
+    #[test]
+    fn test_next_f64() {
+        let mut r = SplitMix64::new(7);
+        for _ in 0..1000 {
+            let x = r.next_f64();
+            assert!((0.0..1.0).contains(&x));
+        }
+    }
+
+    #[test]
+    fn test_gen_range() {
+        let mut r = SplitMix64::new(7);
+        let mut seen = [false; 5];
+        for _ in 0..1000 {
+            seen[r.gen_range(5)] = true;
+        }
+        assert!(seen.iter().all(|s| *s));
+    }
+
+    #[test]
+    fn test_shuffle() {
+        let mut r = SplitMix64::new(3);
+        let mut xs: Vec<usize> = (0..10).collect();
+        r.shuffle(&mut xs);
+        let mut sorted = xs.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..10).collect::<Vec<usize>>());
+        let mut r2 = SplitMix64::new(3);
+        let mut ys: Vec<usize> = (0..10).collect();
+        r2.shuffle(&mut ys);
+        assert_eq!(xs, ys);
+    }
+}